@@ -63,6 +63,39 @@
 #[cfg(feature = "di")]
 pub use allframe_macros::{domain, handler, repository, use_case};
 
+use std::marker::PhantomData;
+
+/// A type's architecture layer (1 = domain ... 4 = handler)
+///
+/// Implemented by structs and enums annotated with `#[domain]`,
+/// `#[repository]`, `#[use_case]` or `#[handler]`. The macros check field
+/// and argument types against it at compile time.
+pub trait Layered {
+    /// Layer number
+    const LAYER: u8;
+}
+
+/// Layer lookup used by the architecture macros' compile-time checks
+///
+/// `LayerProbe::<T>::LAYER` is `T`'s layer when `T: Layered`, and 0 (no
+/// layer) through [`Unlayered`] otherwise.
+#[doc(hidden)]
+pub struct LayerProbe<T: ?Sized>(PhantomData<T>);
+
+impl<T: ?Sized + Layered> LayerProbe<T> {
+    /// The layer of `T`
+    pub const LAYER: u8 = T::LAYER;
+}
+
+/// Fallback for [`LayerProbe`] on types without a layer
+#[doc(hidden)]
+pub trait Unlayered {
+    /// No layer
+    const LAYER: u8 = 0;
+}
+
+impl<T: ?Sized> Unlayered for LayerProbe<T> {}
+
 /// Layer metadata for runtime introspection
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct LayerMetadata {
@@ -100,6 +133,8 @@ impl LayerMetadata {
     }
 
     /// Check if this layer can depend on another layer
+    ///
+    /// Handlers may not depend on repositories directly.
     pub fn can_depend_on(&self, other_layer: &str) -> bool {
         let other_number = match other_layer {
             "domain" => 1,
@@ -109,6 +144,10 @@ impl LayerMetadata {
             _ => return false,
         };
 
+        if self.layer_number == 4 && other_number == 2 {
+            return false;
+        }
+
         // A layer can only depend on layers with lower numbers
         self.layer_number > other_number
     }
@@ -149,9 +188,9 @@ mod tests {
         assert!(use_case.can_depend_on("repository"));
         assert!(!use_case.can_depend_on("handler"));
 
-        // Handler can depend on all lower layers
+        // Handler can depend on use cases and domain, not repositories
         assert!(handler.can_depend_on("domain"));
-        assert!(handler.can_depend_on("repository"));
+        assert!(!handler.can_depend_on("repository"));
         assert!(handler.can_depend_on("use_case"));
     }
 }
@@ -55,12 +55,14 @@ use super::{extract_bearer_token, AuthContext, AuthError, Authenticator};
 ///
 /// # Extracting Optional Auth
 ///
-/// Wrap in `Option` to make auth optional:
+/// [`AuthLayer`] stores the user in request extensions. Unless the layer
+/// is [`required`](AuthLayer::required), wrap it in `Option` to branch on
+/// anonymous requests:
 ///
 /// ```rust,ignore
-/// async fn handler(auth: Option<AuthenticatedUser<MyClaims>>) -> String {
+/// async fn handler(auth: Option<Extension<AuthenticatedUser<MyClaims>>>) -> String {
 ///     match auth {
-///         Some(AuthenticatedUser(claims)) => format!("Hello, {}", claims.sub),
+///         Some(Extension(AuthenticatedUser(claims))) => format!("Hello, {}", claims.sub),
 ///         None => "Hello, anonymous!".to_string(),
 ///     }
 /// }
@@ -91,7 +93,12 @@ impl<C> std::ops::Deref for AuthenticatedUser<C> {
 /// Layer for adding authentication to a router.
 ///
 /// This layer validates the Authorization header on each request and
-/// stores the auth context in request extensions.
+/// stores the auth context ([`AuthContext`]) and the claims
+/// ([`AuthenticatedUser`]) in request extensions.
+///
+/// By default, requests without a valid token proceed as anonymous. With
+/// [`required`](Self::required), they are rejected with the status of the
+/// [`AuthError`] (usually `401 Unauthorized`).
 ///
 /// # Example
 ///
@@ -102,11 +109,12 @@ impl<C> std::ops::Deref for AuthenticatedUser<C> {
 ///
 /// let app = Router::new()
 ///     .route("/protected", get(handler))
-///     .layer(AuthLayer::new(validator));
+///     .layer(AuthLayer::new(validator).required());
 /// ```
 #[derive(Clone)]
 pub struct AuthLayer<A> {
     authenticator: Arc<A>,
+    required: bool,
 }
 
 impl<A> AuthLayer<A> {
@@ -114,8 +122,28 @@ impl<A> AuthLayer<A> {
     pub fn new(authenticator: A) -> Self {
         Self {
             authenticator: Arc::new(authenticator),
+            required: false,
         }
     }
+
+    /// Reject requests without a valid token
+    ///
+    /// The response carries the [`AuthError`]'s status and, for `401`, a
+    /// `WWW-Authenticate: Bearer` header.
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    /// Authenticate if a valid token is present, otherwise let the request
+    /// through as anonymous (the default).
+    ///
+    /// Handlers find [`AuthenticatedUser`] in the request extensions only
+    /// for authenticated requests.
+    pub fn optional(mut self) -> Self {
+        self.required = false;
+        self
+    }
 }
 
 impl<S, A> tower::Layer<S> for AuthLayer<A>
@@ -128,6 +156,7 @@ where
         AuthService {
             inner,
             authenticator: self.authenticator.clone(),
+            required: self.required,
         }
     }
 }
@@ -137,14 +166,19 @@ where
 pub struct AuthService<S, A> {
     inner: S,
     authenticator: Arc<A>,
+    required: bool,
 }
 
-impl<S, A, ReqBody> tower::Service<hyper::Request<ReqBody>> for AuthService<S, A>
+impl<S, A, ReqBody, ResBody> tower::Service<hyper::Request<ReqBody>> for AuthService<S, A>
 where
-    S: tower::Service<hyper::Request<ReqBody>> + Clone + Send + 'static,
+    S: tower::Service<hyper::Request<ReqBody>, Response = hyper::Response<ResBody>>
+        + Clone
+        + Send
+        + 'static,
     S::Future: Send,
     A: Authenticator + 'static,
     ReqBody: Send + 'static,
+    ResBody: Default + Send + 'static,
 {
     type Response = S::Response;
     type Error = S::Error;
@@ -156,20 +190,33 @@ where
 
     fn call(&mut self, mut req: hyper::Request<ReqBody>) -> Self::Future {
         let authenticator = self.authenticator.clone();
+        let required = self.required;
         let mut inner = self.inner.clone();
 
         Box::pin(async move {
             // Extract token from Authorization header
-            if let Some(auth_header) = req.headers().get(hyper::header::AUTHORIZATION) {
-                if let Ok(header_str) = auth_header.to_str() {
-                    if let Some(token) = extract_bearer_token(header_str) {
-                        // Validate token
-                        if let Ok(claims) = authenticator.authenticate(token).await {
-                            let ctx = AuthContext::new(claims, token);
-                            req.extensions_mut().insert(ctx);
-                        }
-                    }
+            let token = req
+                .headers()
+                .get(hyper::header::AUTHORIZATION)
+                .and_then(|header| header.to_str().ok())
+                .and_then(extract_bearer_token)
+                .map(str::to_string);
+
+            let result = match &token {
+                Some(token) => authenticator.authenticate(token).await,
+                None => Err(AuthError::MissingToken),
+            };
+
+            match result {
+                Ok(claims) => {
+                    req.extensions_mut()
+                        .insert(AuthenticatedUser(claims.clone()));
+                    req.extensions_mut()
+                        .insert(AuthContext::new(claims, token.unwrap_or_default()));
                 }
+                Err(error) if required => return Ok(reject(&error)),
+                // Optional auth: proceed as anonymous
+                Err(_) => {}
             }
 
             inner.call(req).await
@@ -177,10 +224,25 @@ where
     }
 }
 
+/// Response for a request that failed authentication.
+fn reject<B: Default>(error: &AuthError) -> hyper::Response<B> {
+    let mut response = hyper::Response::new(B::default());
+    *response.status_mut() =
+        hyper::StatusCode::from_u16(error.status_code()).unwrap_or(hyper::StatusCode::UNAUTHORIZED);
+    if response.status() == hyper::StatusCode::UNAUTHORIZED {
+        response.headers_mut().insert(
+            hyper::header::WWW_AUTHENTICATE,
+            hyper::header::HeaderValue::from_static("Bearer"),
+        );
+    }
+    response
+}
+
 /// Optional auth layer that doesn't reject unauthenticated requests.
 ///
 /// Use this when you want to allow both authenticated and unauthenticated
-/// access, but still make auth info available when present.
+/// access, but still make auth info available when present. Equivalent to
+/// [`AuthLayer::optional`].
 #[derive(Clone)]
 pub struct OptionalAuthLayer<A> {
     authenticator: Arc<A>,
@@ -205,6 +267,7 @@ where
         AuthService {
             inner,
             authenticator: self.authenticator.clone(),
+            required: false,
         }
     }
 }
@@ -328,6 +391,94 @@ mod tests {
         assert_eq!(claims.sub, "user123");
     }
 
+    #[derive(Clone, Debug, PartialEq)]
+    struct User {
+        sub: String,
+    }
+
+    /// Accepts `valid-<user>` tokens
+    #[derive(Clone)]
+    struct Prefix;
+
+    #[async_trait::async_trait]
+    impl Authenticator for Prefix {
+        type Claims = User;
+
+        async fn authenticate(&self, token: &str) -> Result<User, AuthError> {
+            token
+                .strip_prefix("valid-")
+                .map(|sub| User { sub: sub.into() })
+                .ok_or_else(|| AuthError::InvalidToken("unknown".into()))
+        }
+    }
+
+    /// Responds with the authenticated subject, or `anonymous`
+    #[derive(Clone)]
+    struct Whoami;
+
+    impl tower::Service<hyper::Request<()>> for Whoami {
+        type Response = hyper::Response<String>;
+        type Error = std::convert::Infallible;
+        type Future = std::future::Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: hyper::Request<()>) -> Self::Future {
+            let body = match req.extensions().get::<AuthenticatedUser<User>>() {
+                Some(user) => user.sub.clone(),
+                None => "anonymous".to_string(),
+            };
+            std::future::ready(Ok(hyper::Response::new(body)))
+        }
+    }
+
+    async fn send(layer: &AuthLayer<Prefix>, token: Option<&str>) -> hyper::Response<String> {
+        use tower::{Layer, Service};
+
+        let mut req = hyper::Request::builder();
+        if let Some(token) = token {
+            req = req.header("Authorization", format!("Bearer {}", token));
+        }
+        layer
+            .layer(Whoami)
+            .call(req.body(()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_auth_layer_required() {
+        let layer = AuthLayer::new(Prefix).required();
+
+        let res = send(&layer, Some("valid-alice")).await;
+        assert_eq!(res.body(), "alice");
+
+        let res = send(&layer, None).await;
+        assert_eq!(res.status(), 401);
+        assert_eq!(res.headers()["www-authenticate"], "Bearer");
+
+        let res = send(&layer, Some("forged")).await;
+        assert_eq!(res.status(), 401);
+    }
+
+    #[tokio::test]
+    async fn test_auth_layer_optional() {
+        // Anonymous requests are let through by default
+        let layer = AuthLayer::new(Prefix);
+
+        let res = send(&layer, Some("valid-alice")).await;
+        assert_eq!(res.body(), "alice");
+
+        // Missing and invalid tokens proceed as anonymous
+        let res = send(&layer, None).await;
+        assert_eq!(res.status(), 200);
+        assert_eq!(res.body(), "anonymous");
+        let res = send(&layer, Some("forged")).await;
+        assert_eq!(res.body(), "anonymous");
+    }
+
     #[test]
     fn test_auth_rejection() {
         let rejection = AuthRejection::new(AuthError::MissingToken);
@@ -0,0 +1,241 @@
+//! Trying several authenticators in turn.
+//!
+//! Services that accept more than one kind of credential (e.g. a JWT or an
+//! API key) combine their authenticators into a [`ChainedAuthenticator`].
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use allframe_core::auth::{Authenticator, ChainedAuthenticator, JwtConfig, JwtValidator};
+//!
+//! let auth = ChainedAuthenticator::new()
+//!     .with_authenticator(JwtValidator::<UserClaims>::new(JwtConfig::hs256("secret")))
+//!     .with_authenticator(ApiKeyAuthenticator::new(keys));
+//!
+//! let claims = auth.authenticate(token).await?;
+//! if let Some(user) = claims.downcast_ref::<UserClaims>() {
+//!     // Authenticated with a JWT
+//! } else if let Some(key) = claims.downcast_ref::<ApiKey>() {
+//!     // Authenticated with an API key
+//! }
+//! ```
+
+use std::{any::Any, fmt, sync::Arc};
+
+use super::{AuthError, Authenticator};
+
+/// Claims from whichever authenticator in a chain accepted the token.
+///
+/// Recover the concrete claims type with [`downcast_ref`](Self::downcast_ref).
+#[derive(Clone)]
+pub struct AnyClaims {
+    claims: Arc<dyn Any + Send + Sync>,
+    authenticator: usize,
+}
+
+impl AnyClaims {
+    /// The claims, if they are of type `C`.
+    pub fn downcast_ref<C: 'static>(&self) -> Option<&C> {
+        self.claims.downcast_ref()
+    }
+
+    /// Whether the claims are of type `C`.
+    pub fn is<C: 'static>(&self) -> bool {
+        self.claims.is::<C>()
+    }
+
+    /// Position in the chain of the authenticator that accepted the token.
+    pub fn authenticator_index(&self) -> usize {
+        self.authenticator
+    }
+}
+
+impl fmt::Debug for AnyClaims {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AnyClaims")
+            .field("authenticator", &self.authenticator)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Type-erased authenticator in a chain.
+#[async_trait::async_trait]
+trait ErasedAuthenticator: Send + Sync {
+    async fn authenticate_any(&self, token: &str) -> Result<Arc<dyn Any + Send + Sync>, AuthError>;
+}
+
+#[async_trait::async_trait]
+impl<A: Authenticator> ErasedAuthenticator for A {
+    async fn authenticate_any(&self, token: &str) -> Result<Arc<dyn Any + Send + Sync>, AuthError> {
+        let claims = self.authenticate(token).await?;
+        Ok(Arc::new(claims))
+    }
+}
+
+/// Authenticator that tries each configured authenticator in order.
+///
+/// Returns the claims of the first one to accept the token. If all reject
+/// it, returns the most specific error: a token that was recognized but
+/// rejected (e.g. [`AuthError::TokenExpired`]) wins over
+/// [`AuthError::InvalidToken`], which wins over [`AuthError::MissingToken`].
+/// Among equally specific errors, the first is returned.
+#[derive(Clone, Default)]
+pub struct ChainedAuthenticator {
+    authenticators: Vec<Arc<dyn ErasedAuthenticator>>,
+}
+
+impl ChainedAuthenticator {
+    /// Create an empty chain.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an authenticator to the end of the chain.
+    pub fn with_authenticator<A: Authenticator + 'static>(mut self, authenticator: A) -> Self {
+        self.authenticators.push(Arc::new(authenticator));
+        self
+    }
+
+    /// Number of authenticators in the chain.
+    pub fn len(&self) -> usize {
+        self.authenticators.len()
+    }
+
+    /// Check if the chain is empty.
+    pub fn is_empty(&self) -> bool {
+        self.authenticators.is_empty()
+    }
+}
+
+/// How specific an error is, for picking which one a chain reports.
+fn specificity(error: &AuthError) -> u8 {
+    match error {
+        AuthError::MissingToken => 0,
+        AuthError::InvalidToken(_) => 1,
+        AuthError::TokenExpired
+        | AuthError::InvalidSignature
+        | AuthError::InvalidIssuer
+        | AuthError::InvalidAudience
+        | AuthError::ValidationFailed(_) => 2,
+        AuthError::Internal(_) => 3,
+    }
+}
+
+#[async_trait::async_trait]
+impl Authenticator for ChainedAuthenticator {
+    type Claims = AnyClaims;
+
+    async fn authenticate(&self, token: &str) -> Result<Self::Claims, AuthError> {
+        let mut best: Option<AuthError> = None;
+        for (index, authenticator) in self.authenticators.iter().enumerate() {
+            match authenticator.authenticate_any(token).await {
+                Ok(claims) => {
+                    return Ok(AnyClaims {
+                        claims,
+                        authenticator: index,
+                    })
+                }
+                Err(error) => {
+                    if best
+                        .as_ref()
+                        .map_or(true, |best| specificity(&error) > specificity(best))
+                    {
+                        best = Some(error);
+                    }
+                }
+            }
+        }
+        Err(best.unwrap_or(AuthError::MissingToken))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct User(String);
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct ApiKey(String);
+
+    /// Accepts `jwt.<user>` tokens
+    struct Jwt;
+
+    #[async_trait::async_trait]
+    impl Authenticator for Jwt {
+        type Claims = User;
+
+        async fn authenticate(&self, token: &str) -> Result<User, AuthError> {
+            match token.strip_prefix("jwt.") {
+                Some("expired") => Err(AuthError::TokenExpired),
+                Some(user) => Ok(User(user.to_string())),
+                None => Err(AuthError::InvalidToken("not a JWT".into())),
+            }
+        }
+    }
+
+    /// Accepts `key-<id>` tokens
+    struct Keys;
+
+    #[async_trait::async_trait]
+    impl Authenticator for Keys {
+        type Claims = ApiKey;
+
+        async fn authenticate(&self, token: &str) -> Result<ApiKey, AuthError> {
+            if token.is_empty() {
+                return Err(AuthError::MissingToken);
+            }
+            token
+                .strip_prefix("key-")
+                .map(|id| ApiKey(id.to_string()))
+                .ok_or_else(|| AuthError::InvalidToken("unknown API key".into()))
+        }
+    }
+
+    fn chain() -> ChainedAuthenticator {
+        ChainedAuthenticator::new()
+            .with_authenticator(Jwt)
+            .with_authenticator(Keys)
+    }
+
+    #[tokio::test]
+    async fn test_chain_returns_first_success() {
+        let auth = chain();
+        assert_eq!(auth.len(), 2);
+
+        let claims = auth.authenticate("jwt.alice").await.unwrap();
+        assert_eq!(claims.downcast_ref::<User>(), Some(&User("alice".into())));
+        assert_eq!(claims.authenticator_index(), 0);
+
+        let claims = auth.authenticate("key-42").await.unwrap();
+        assert!(claims.is::<ApiKey>());
+        assert!(claims.downcast_ref::<User>().is_none());
+        assert_eq!(claims.authenticator_index(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_chain_returns_most_specific_error() {
+        let auth = chain();
+
+        // Rejected as expired by the first, unknown to the second
+        assert!(matches!(
+            auth.authenticate("jwt.expired").await,
+            Err(AuthError::TokenExpired)
+        ));
+        // Invalid for both: the first invalid error wins
+        assert!(matches!(
+            auth.authenticate("garbage").await,
+            Err(AuthError::InvalidToken(msg)) if msg == "not a JWT"
+        ));
+        // Missing for one, invalid for the other
+        assert!(matches!(
+            auth.authenticate("").await,
+            Err(AuthError::InvalidToken(_))
+        ));
+        assert!(matches!(
+            ChainedAuthenticator::new().authenticate("token").await,
+            Err(AuthError::MissingToken)
+        ));
+    }
+}
@@ -0,0 +1,161 @@
+//! Forwarding authentication to downstream services.
+//!
+//! A gateway or BFF calling other services on behalf of a user passes the
+//! user's token along, either verbatim or exchanged for a token meant for
+//! the downstream service (on-behalf-of flow). The correlation id of the
+//! current request is forwarded with it.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use allframe_core::auth::{AuthContext, TokenForwarder};
+//!
+//! async fn list_orders(ctx: &AuthContext<Claims>) -> Result<String, Box<dyn std::error::Error>> {
+//!     let forwarder = TokenForwarder::new();
+//!     let request = reqwest::Client::new().get("http://orders/api/orders");
+//!     let response = forwarder.apply(ctx, request).await?.send().await?;
+//!     Ok(response.text().await?)
+//! }
+//! ```
+
+use std::sync::Arc;
+
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
+
+use super::{AuthContext, AuthError};
+use crate::router::{request_id, REQUEST_ID_HEADER};
+
+/// Exchanges a user's token for one to call a downstream service with.
+///
+/// Implement this for an OAuth 2.0 token exchange (RFC 8693) or an
+/// on-behalf-of grant. Implementations should cache exchanged tokens until
+/// they expire.
+#[async_trait::async_trait]
+pub trait TokenExchanger: Send + Sync {
+    /// Exchange the incoming token for a downstream token.
+    async fn exchange(&self, token: &str) -> Result<String, AuthError>;
+}
+
+impl<C> AuthContext<C> {
+    /// Add the bearer token and the current request id to outgoing headers.
+    ///
+    /// The token is forwarded verbatim and marked sensitive, so it's
+    /// redacted from `Debug` output. The request id is taken from
+    /// [`request_id`] when called while handling a request.
+    pub fn inject_into_headers(&self, headers: &mut HeaderMap) -> Result<(), AuthError> {
+        inject(headers, &self.token)
+    }
+}
+
+fn inject(headers: &mut HeaderMap, token: &str) -> Result<(), AuthError> {
+    let mut value = HeaderValue::from_str(&format!("Bearer {}", token))
+        .map_err(|_| AuthError::InvalidToken("not a valid header value".into()))?;
+    value.set_sensitive(true);
+    headers.insert(AUTHORIZATION, value);
+
+    if let Some(id) = request_id().and_then(|id| HeaderValue::from_str(&id).ok()) {
+        headers.insert(REQUEST_ID_HEADER, id);
+    }
+    Ok(())
+}
+
+/// Attaches authentication to requests made to downstream services.
+///
+/// Forwards the token verbatim by default; use
+/// [`with_exchange`](Self::with_exchange) to send an exchanged token
+/// instead.
+#[derive(Clone, Default)]
+pub struct TokenForwarder {
+    exchanger: Option<Arc<dyn TokenExchanger>>,
+}
+
+impl TokenForwarder {
+    /// Create a forwarder that passes tokens on verbatim.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a forwarder that exchanges tokens before passing them on.
+    pub fn with_exchange(exchanger: impl TokenExchanger + 'static) -> Self {
+        Self {
+            exchanger: Some(Arc::new(exchanger)),
+        }
+    }
+
+    /// Headers to send downstream on behalf of the authenticated user.
+    pub async fn headers<C>(&self, ctx: &AuthContext<C>) -> Result<HeaderMap, AuthError> {
+        let mut headers = HeaderMap::new();
+        match &self.exchanger {
+            Some(exchanger) => inject(&mut headers, &exchanger.exchange(&ctx.token).await?)?,
+            None => ctx.inject_into_headers(&mut headers)?,
+        }
+        Ok(headers)
+    }
+
+    /// Add the downstream headers to a `reqwest` request.
+    pub async fn apply<C>(
+        &self,
+        ctx: &AuthContext<C>,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::RequestBuilder, AuthError> {
+        Ok(request.headers(self.headers(ctx).await?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::router::with_request_id;
+
+    struct Prefixer;
+
+    #[async_trait::async_trait]
+    impl TokenExchanger for Prefixer {
+        async fn exchange(&self, token: &str) -> Result<String, AuthError> {
+            if token.is_empty() {
+                return Err(AuthError::Internal("exchange rejected".into()));
+            }
+            Ok(format!("obo-{}", token))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_inject_into_headers() {
+        let ctx = AuthContext::new((), "abc123");
+        let mut headers = HeaderMap::new();
+
+        with_request_id("req-1".to_string(), async {
+            ctx.inject_into_headers(&mut headers).unwrap();
+        })
+        .await;
+
+        assert_eq!(headers[AUTHORIZATION], "Bearer abc123");
+        assert!(headers[AUTHORIZATION].is_sensitive());
+        assert_eq!(headers[REQUEST_ID_HEADER], "req-1");
+
+        // Outside a request there's no id to forward
+        let mut headers = HeaderMap::new();
+        ctx.inject_into_headers(&mut headers).unwrap();
+        assert!(!headers.contains_key(REQUEST_ID_HEADER));
+
+        let ctx = AuthContext::new((), "bad\ntoken");
+        assert!(ctx.inject_into_headers(&mut headers).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_forwarder_exchanges_token() {
+        let forwarder = TokenForwarder::with_exchange(Prefixer);
+        let request = reqwest::Client::new().get("http://orders.internal/api");
+
+        let request = forwarder
+            .apply(&AuthContext::new((), "abc123"), request)
+            .await
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(request.headers()[AUTHORIZATION], "Bearer obo-abc123");
+
+        let result = forwarder.headers(&AuthContext::new((), "")).await;
+        assert!(matches!(result, Err(AuthError::Internal(_))));
+    }
+}
@@ -35,6 +35,9 @@ use serde::de::DeserializeOwned;
 
 use super::{AuthError, Authenticator};
 
+/// Default clock skew tolerance for `exp` and `nbf`, in seconds.
+pub const DEFAULT_LEEWAY_SECONDS: u64 = 30;
+
 /// JWT algorithm to use for validation.
 #[derive(Debug, Clone)]
 pub enum JwtAlgorithm {
@@ -91,7 +94,8 @@ pub struct JwtConfig {
     pub issuer: Option<String>,
     /// Expected audience (aud claim).
     pub audience: Option<String>,
-    /// Leeway in seconds for expiration checks.
+    /// Clock skew tolerance in seconds for `exp` and `nbf` checks
+    /// (default: [`DEFAULT_LEEWAY_SECONDS`]).
     pub leeway_seconds: u64,
     /// Whether to validate expiration.
     pub validate_exp: bool,
@@ -114,7 +118,7 @@ impl JwtConfig {
             algorithm: JwtAlgorithm::HS256(secret.into()),
             issuer: None,
             audience: None,
-            leeway_seconds: 0,
+            leeway_seconds: DEFAULT_LEEWAY_SECONDS,
             validate_exp: true,
             validate_nbf: true,
         }
@@ -126,7 +130,7 @@ impl JwtConfig {
             algorithm: JwtAlgorithm::HS384(secret.into()),
             issuer: None,
             audience: None,
-            leeway_seconds: 0,
+            leeway_seconds: DEFAULT_LEEWAY_SECONDS,
             validate_exp: true,
             validate_nbf: true,
         }
@@ -138,7 +142,7 @@ impl JwtConfig {
             algorithm: JwtAlgorithm::HS512(secret.into()),
             issuer: None,
             audience: None,
-            leeway_seconds: 0,
+            leeway_seconds: DEFAULT_LEEWAY_SECONDS,
             validate_exp: true,
             validate_nbf: true,
         }
@@ -153,7 +157,7 @@ impl JwtConfig {
             algorithm: JwtAlgorithm::RS256(public_key_pem.into()),
             issuer: None,
             audience: None,
-            leeway_seconds: 0,
+            leeway_seconds: DEFAULT_LEEWAY_SECONDS,
             validate_exp: true,
             validate_nbf: true,
         }
@@ -165,7 +169,7 @@ impl JwtConfig {
             algorithm: JwtAlgorithm::RS384(public_key_pem.into()),
             issuer: None,
             audience: None,
-            leeway_seconds: 0,
+            leeway_seconds: DEFAULT_LEEWAY_SECONDS,
             validate_exp: true,
             validate_nbf: true,
         }
@@ -177,7 +181,7 @@ impl JwtConfig {
             algorithm: JwtAlgorithm::RS512(public_key_pem.into()),
             issuer: None,
             audience: None,
-            leeway_seconds: 0,
+            leeway_seconds: DEFAULT_LEEWAY_SECONDS,
             validate_exp: true,
             validate_nbf: true,
         }
@@ -189,7 +193,7 @@ impl JwtConfig {
             algorithm: JwtAlgorithm::EdDSA(public_key_pem.into()),
             issuer: None,
             audience: None,
-            leeway_seconds: 0,
+            leeway_seconds: DEFAULT_LEEWAY_SECONDS,
             validate_exp: true,
             validate_nbf: true,
         }
@@ -249,7 +253,12 @@ impl JwtConfig {
         self
     }
 
-    /// Set the leeway for expiration checks (in seconds).
+    /// Set the clock skew tolerance for `exp` and `nbf` checks (in
+    /// seconds).
+    ///
+    /// A token is accepted until `leeway` seconds after it expires and from
+    /// `leeway` seconds before it becomes valid, so services with slightly
+    /// skewed clocks agree. Use `0` for strict checks.
     pub fn with_leeway(mut self, seconds: u64) -> Self {
         self.leeway_seconds = seconds;
         self
@@ -354,6 +363,9 @@ impl<C: Clone + Send + Sync + DeserializeOwned + 'static> JwtValidator<C> {
                 use jsonwebtoken::errors::ErrorKind;
                 match e.kind() {
                     ErrorKind::ExpiredSignature => AuthError::TokenExpired,
+                    ErrorKind::ImmatureSignature => {
+                        AuthError::InvalidToken("token not yet valid".into())
+                    }
                     ErrorKind::InvalidSignature => AuthError::InvalidSignature,
                     ErrorKind::InvalidIssuer => AuthError::InvalidIssuer,
                     ErrorKind::InvalidAudience => AuthError::InvalidAudience,
@@ -512,6 +524,57 @@ mod tests {
         assert!(matches!(result, Err(AuthError::InvalidToken(_))));
     }
 
+    #[derive(serde::Serialize)]
+    struct TimedClaims {
+        sub: String,
+        exp: i64,
+        nbf: i64,
+    }
+
+    fn timed_token(exp_offset: i64, nbf_offset: i64) -> String {
+        let now = chrono::Utc::now().timestamp();
+        create_test_token(&TimedClaims {
+            sub: "user123".to_string(),
+            exp: now + exp_offset,
+            nbf: now + nbf_offset,
+        })
+    }
+
+    #[test]
+    fn test_jwt_validator_leeway() {
+        let validator = JwtValidator::<TestClaims>::new(JwtConfig::hs256(TEST_SECRET));
+        assert_eq!(validator.config().leeway_seconds, DEFAULT_LEEWAY_SECONDS);
+
+        // Expired, but within the leeway
+        assert!(validator.validate(&timed_token(-25, -60)).is_ok());
+        // Expired beyond the leeway
+        assert!(matches!(
+            validator.validate(&timed_token(-35, -60)),
+            Err(AuthError::TokenExpired)
+        ));
+
+        // Not yet valid, but within the leeway
+        assert!(validator.validate(&timed_token(3600, 25)).is_ok());
+        // Not yet valid beyond the leeway
+        assert!(matches!(
+            validator.validate(&timed_token(3600, 35)),
+            Err(AuthError::InvalidToken(_))
+        ));
+    }
+
+    #[test]
+    fn test_jwt_validator_strict() {
+        let validator =
+            JwtValidator::<TestClaims>::new(JwtConfig::hs256(TEST_SECRET).with_leeway(0));
+
+        assert!(matches!(
+            validator.validate(&timed_token(-5, -60)),
+            Err(AuthError::TokenExpired)
+        ));
+        assert!(validator.validate(&timed_token(3600, 5)).is_err());
+        assert!(validator.validate(&timed_token(3600, -5)).is_ok());
+    }
+
     #[tokio::test]
     async fn test_jwt_validator_authenticator_trait() {
         let claims = TestClaims {
@@ -6,6 +6,7 @@
 //! - **`auth-jwt`**: JWT validation using `jsonwebtoken`
 //! - **`auth-axum`**: Axum extractors and middleware
 //! - **`auth-tonic`**: gRPC interceptors
+//! - **`auth-forward`**: Forwarding tokens to downstream services
 //!
 //! # Core Concepts
 //!
@@ -14,6 +15,7 @@
 //! - [`Authenticator`]: Validates tokens and returns claims
 //! - [`Claims`]: Marker trait for claim types
 //! - [`AuthContext`]: Holds authenticated user information
+//! - [`ChainedAuthenticator`]: Tries several authenticators in turn
 //!
 //! # Example: Using Core Traits
 //!
@@ -52,9 +54,12 @@
 //! | `auth-jwt` | JWT validation with HS256/RS256 support |
 //! | `auth-axum` | Axum extractors and middleware |
 //! | `auth-tonic` | gRPC interceptors |
+//! | `auth-forward` | Token forwarding and exchange for downstream calls |
 
 use std::fmt;
 
+mod chain;
+
 #[cfg(feature = "auth-jwt")]
 pub mod jwt;
 
@@ -64,14 +69,20 @@ pub mod axum;
 #[cfg(feature = "auth-tonic")]
 pub mod tonic;
 
+#[cfg(feature = "auth-forward")]
+pub mod forward;
+
 // Re-exports
+pub use chain::{AnyClaims, ChainedAuthenticator};
 #[cfg(feature = "auth-jwt")]
 pub use jwt::{JwtAlgorithm, JwtConfig, JwtValidator};
 
 #[cfg(feature = "auth-axum")]
 pub use self::axum::{AuthLayer, AuthenticatedUser};
 #[cfg(feature = "auth-tonic")]
-pub use self::tonic::AuthInterceptor;
+pub use self::tonic::{AuthInterceptor, GrpcAuthLayer, MethodAuth};
+#[cfg(feature = "auth-forward")]
+pub use forward::{TokenExchanger, TokenForwarder};
 
 /// Error type for authentication failures.
 #[derive(Debug, Clone)]
@@ -189,6 +200,9 @@ pub struct AuthContext<C> {
     /// The validated claims.
     pub claims: C,
     /// The original token (for forwarding to downstream services).
+    ///
+    /// With the `auth-forward` feature, see `inject_into_headers` and
+    /// `TokenForwarder`.
     pub token: String,
 }
 
@@ -251,12 +265,19 @@ pub trait HasExpiration {
 
     /// Check if the claims have expired.
     fn is_expired(&self) -> bool {
+        self.is_expired_with_leeway(0)
+    }
+
+    /// Check if the claims expired more than `leeway_seconds` ago.
+    ///
+    /// The leeway tolerates clock skew between the issuer and this service.
+    fn is_expired_with_leeway(&self, leeway_seconds: u64) -> bool {
         if let Some(exp) = self.expiration() {
             let now = std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
                 .as_secs() as i64;
-            exp < now
+            exp.saturating_add(leeway_seconds as i64) < now
         } else {
             false
         }
@@ -355,4 +376,28 @@ mod tests {
         let no_exp = MockClaims { exp: None };
         assert!(!no_exp.is_expired());
     }
+
+    #[test]
+    fn test_has_expiration_leeway() {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+
+        let just_expired = MockClaims {
+            exp: Some(now - 10),
+        };
+        assert!(just_expired.is_expired());
+        assert!(!just_expired.is_expired_with_leeway(30));
+
+        let long_expired = MockClaims {
+            exp: Some(now - 60),
+        };
+        assert!(long_expired.is_expired_with_leeway(30));
+
+        let future = MockClaims {
+            exp: Some(i64::MAX),
+        };
+        assert!(!future.is_expired_with_leeway(30));
+    }
 }
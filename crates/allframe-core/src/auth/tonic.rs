@@ -19,10 +19,35 @@
 //! // Use with a service
 //! let service = MyServiceServer::with_interceptor(impl, interceptor);
 //! ```
+//!
+//! # Per-Method Requirements
+//!
+//! Health checks and other public methods can skip authentication. Method
+//! requirements need the request path, which tonic doesn't pass to
+//! interceptors, so apply the interceptor as a layer instead:
+//!
+//! ```rust,ignore
+//! let interceptor = AuthInterceptor::new(validator)
+//!     .with_public_method("grpc.health.v1.Health");
+//!
+//! Server::builder()
+//!     .layer(interceptor.into_layer())
+//!     .add_service(health_service)
+//!     .add_service(MyServiceServer::new(impl))
+//!     .serve(addr)
+//!     .await?;
+//! ```
 
-use std::{marker::PhantomData, sync::Arc};
+use std::{
+    collections::HashMap,
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
 
-use tonic::{metadata::MetadataMap, Request, Status};
+use tonic::{codegen::http, metadata::MetadataMap, Request, Status};
 
 use super::{extract_bearer_token, AuthContext, AuthError, Authenticator};
 
@@ -48,6 +73,8 @@ use super::{extract_bearer_token, AuthContext, AuthError, Authenticator};
 pub struct AuthInterceptor<A, C> {
     authenticator: Arc<A>,
     required: bool,
+    /// Requirements by method (`package.Service/Method`) or service
+    methods: Arc<HashMap<String, MethodAuth>>,
     _phantom: PhantomData<C>,
 }
 
@@ -56,11 +83,23 @@ impl<A, C> Clone for AuthInterceptor<A, C> {
         Self {
             authenticator: self.authenticator.clone(),
             required: self.required,
+            methods: self.methods.clone(),
             _phantom: PhantomData,
         }
     }
 }
 
+/// Authentication requirement for a gRPC method.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MethodAuth {
+    /// Reject requests without a valid token.
+    Required,
+    /// Authenticate if a valid token is present, otherwise proceed.
+    Optional,
+    /// Skip authentication entirely.
+    Public,
+}
+
 impl<A, C> AuthInterceptor<A, C>
 where
     A: Authenticator<Claims = C>,
@@ -71,6 +110,7 @@ where
         Self {
             authenticator: Arc::new(authenticator),
             required: true,
+            methods: Arc::new(HashMap::new()),
             _phantom: PhantomData,
         }
     }
@@ -87,41 +127,78 @@ where
         self
     }
 
+    /// Set the requirement for a method (`package.Service/Method`) or for
+    /// every method of a service (`package.Service`).
+    ///
+    /// Only applies when used as a layer (see [`into_layer`](Self::into_layer)).
+    pub fn with_method_auth(mut self, method: impl Into<String>, auth: MethodAuth) -> Self {
+        let method = method.into();
+        Arc::make_mut(&mut self.methods).insert(method.trim_start_matches('/').to_string(), auth);
+        self
+    }
+
+    /// Skip authentication for a method or service, e.g.
+    /// `grpc.health.v1.Health/Check`.
+    ///
+    /// Only applies when used as a layer (see [`into_layer`](Self::into_layer)).
+    pub fn with_public_method(self, method: impl Into<String>) -> Self {
+        self.with_method_auth(method, MethodAuth::Public)
+    }
+
+    /// Use as a tower layer, which applies per-method requirements.
+    pub fn into_layer(self) -> GrpcAuthLayer<A, C> {
+        GrpcAuthLayer { interceptor: self }
+    }
+
+    /// The requirement for a request path (`/package.Service/Method`).
+    pub fn method_auth(&self, path: &str) -> MethodAuth {
+        let method = path.trim_start_matches('/');
+        let service = method.split('/').next().unwrap_or(method);
+        self.methods
+            .get(method)
+            .or_else(|| self.methods.get(service))
+            .copied()
+            .unwrap_or(if self.required {
+                MethodAuth::Required
+            } else {
+                MethodAuth::Optional
+            })
+    }
+
     /// Extract and validate the token from request metadata.
     fn authenticate_request<T>(&self, request: &Request<T>) -> Result<Option<C>, Status> {
-        let metadata = request.metadata();
-
-        // Try to get the authorization header
-        let token = match extract_token_from_metadata(metadata) {
-            Some(token) => token,
-            None => {
-                if self.required {
-                    return Err(auth_error_to_status(AuthError::MissingToken));
-                }
-                return Ok(None);
-            }
+        let token = extract_token_from_metadata(request.metadata());
+        let auth = if self.required {
+            MethodAuth::Required
+        } else {
+            MethodAuth::Optional
         };
 
         // Validate the token synchronously
         // Note: We use block_in_place since tonic interceptors are sync
-        let authenticator = self.authenticator.clone();
-        let token_owned = token.to_string();
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(self.authenticate_token(token, auth))
+        })
+    }
 
-        // For sync validation (like JWT), we can use blocking
-        let result = tokio::task::block_in_place(|| {
-            tokio::runtime::Handle::current()
-                .block_on(async { authenticator.authenticate(&token_owned).await })
-        });
+    /// Validate a token according to the requirement.
+    async fn authenticate_token(
+        &self,
+        token: Option<&str>,
+        auth: MethodAuth,
+    ) -> Result<Option<C>, Status> {
+        let token = match (token, auth) {
+            (_, MethodAuth::Public) | (None, MethodAuth::Optional) => return Ok(None),
+            (None, MethodAuth::Required) => {
+                return Err(auth_error_to_status(AuthError::MissingToken))
+            }
+            (Some(token), _) => token,
+        };
 
-        match result {
+        match self.authenticator.authenticate(token).await {
             Ok(claims) => Ok(Some(claims)),
-            Err(e) => {
-                if self.required {
-                    Err(auth_error_to_status(e))
-                } else {
-                    Ok(None)
-                }
-            }
+            Err(e) if auth == MethodAuth::Required => Err(auth_error_to_status(e)),
+            Err(_) => Ok(None),
         }
     }
 }
@@ -151,6 +228,98 @@ where
     }
 }
 
+/// Tower layer applying an [`AuthInterceptor`] with per-method
+/// requirements.
+///
+/// Created by [`AuthInterceptor::into_layer`]. On success the auth context
+/// is added to the request extensions, where [`GrpcAuthExt`] finds it.
+pub struct GrpcAuthLayer<A, C> {
+    interceptor: AuthInterceptor<A, C>,
+}
+
+impl<A, C> Clone for GrpcAuthLayer<A, C> {
+    fn clone(&self) -> Self {
+        Self {
+            interceptor: self.interceptor.clone(),
+        }
+    }
+}
+
+impl<S, A, C> tower::Layer<S> for GrpcAuthLayer<A, C> {
+    type Service = GrpcAuthService<S, A, C>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        GrpcAuthService {
+            inner,
+            interceptor: self.interceptor.clone(),
+        }
+    }
+}
+
+/// Service that authenticates gRPC requests by method.
+pub struct GrpcAuthService<S, A, C> {
+    inner: S,
+    interceptor: AuthInterceptor<A, C>,
+}
+
+impl<S: Clone, A, C> Clone for GrpcAuthService<S, A, C> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            interceptor: self.interceptor.clone(),
+        }
+    }
+}
+
+impl<S, A, C, ReqBody, ResBody> tower::Service<http::Request<ReqBody>> for GrpcAuthService<S, A, C>
+where
+    S: tower::Service<http::Request<ReqBody>, Response = http::Response<ResBody>>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send,
+    A: Authenticator<Claims = C> + 'static,
+    C: Clone + Send + Sync + 'static,
+    ReqBody: Send + 'static,
+    ResBody: Default + Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: http::Request<ReqBody>) -> Self::Future {
+        let interceptor = self.interceptor.clone();
+        // Call the instance that was polled ready
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        Box::pin(async move {
+            let auth = interceptor.method_auth(req.uri().path());
+            let token = req
+                .headers()
+                .get("authorization")
+                .and_then(|v| v.to_str().ok())
+                .and_then(extract_bearer_token)
+                .map(str::to_string);
+
+            match interceptor.authenticate_token(token.as_deref(), auth).await {
+                Ok(Some(claims)) => {
+                    let ctx = AuthContext::new(claims, token.unwrap_or_default());
+                    req.extensions_mut().insert(ctx);
+                }
+                Ok(None) => {}
+                Err(status) => return Ok(status.into_http()),
+            }
+
+            inner.call(req).await
+        })
+    }
+}
+
 /// Extract bearer token from gRPC metadata.
 fn extract_token_from_metadata(metadata: &MetadataMap) -> Option<&str> {
     metadata
@@ -235,6 +404,105 @@ where
 mod tests {
     use super::*;
 
+    #[derive(Clone)]
+    struct Claims {
+        sub: String,
+    }
+
+    /// Accepts any token, using it as the subject
+    struct AnyToken;
+
+    #[async_trait::async_trait]
+    impl Authenticator for AnyToken {
+        type Claims = Claims;
+
+        async fn authenticate(&self, token: &str) -> Result<Claims, AuthError> {
+            Ok(Claims {
+                sub: token.to_string(),
+            })
+        }
+    }
+
+    /// Responds with the authenticated subject, or `anonymous`
+    #[derive(Clone)]
+    struct Echo;
+
+    impl tower::Service<http::Request<()>> for Echo {
+        type Response = http::Response<String>;
+        type Error = std::convert::Infallible;
+        type Future = std::future::Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, req: http::Request<()>) -> Self::Future {
+            let sub = req
+                .extensions()
+                .get::<AuthContext<Claims>>()
+                .map_or("anonymous".to_string(), |ctx| ctx.claims.sub.clone());
+            std::future::ready(Ok(http::Response::new(sub)))
+        }
+    }
+
+    async fn send(
+        service: &mut GrpcAuthService<Echo, AnyToken, Claims>,
+        path: &str,
+        token: Option<&str>,
+    ) -> http::Response<String> {
+        let mut req = http::Request::builder().uri(path);
+        if let Some(token) = token {
+            req = req.header("authorization", format!("Bearer {}", token));
+        }
+        tower::Service::call(service, req.body(()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_layer_per_method_requirements() {
+        use tower::Layer;
+
+        let layer = AuthInterceptor::new(AnyToken)
+            .with_public_method("grpc.health.v1.Health/Check")
+            .with_method_auth("shop.Catalog", MethodAuth::Optional)
+            .into_layer();
+        let mut service = layer.layer(Echo);
+
+        // Public method: no token needed
+        let res = send(&mut service, "/grpc.health.v1.Health/Check", None).await;
+        assert_eq!(res.body(), "anonymous");
+
+        // Protected method: rejected without a token, authenticated with one
+        let res = send(&mut service, "/shop.Orders/List", None).await;
+        assert_eq!(res.headers()["grpc-status"], "16");
+        assert!(res.body().is_empty());
+        let res = send(&mut service, "/shop.Orders/List", Some("alice")).await;
+        assert_eq!(res.body(), "alice");
+
+        // Service-wide optional requirement
+        let res = send(&mut service, "/shop.Catalog/Search", None).await;
+        assert_eq!(res.body(), "anonymous");
+        let res = send(&mut service, "/shop.Catalog/Search", Some("bob")).await;
+        assert_eq!(res.body(), "bob");
+    }
+
+    #[test]
+    fn test_method_auth_lookup() {
+        let interceptor = AuthInterceptor::new(AnyToken)
+            .optional()
+            .with_method_auth("/admin.Users/Delete", MethodAuth::Required);
+
+        assert_eq!(
+            interceptor.method_auth("/admin.Users/Delete"),
+            MethodAuth::Required
+        );
+        assert_eq!(
+            interceptor.method_auth("/admin.Users/List"),
+            MethodAuth::Optional
+        );
+    }
+
     #[test]
     fn test_extract_token_from_metadata() {
         let mut metadata = MetadataMap::new();
@@ -264,11 +532,6 @@ mod tests {
 
     #[test]
     fn test_grpc_auth_ext() {
-        #[derive(Clone)]
-        struct Claims {
-            sub: String,
-        }
-
         let mut request = Request::new(());
 
         // No auth initially
@@ -0,0 +1,132 @@
+//! Serialization formats for cached values
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{CacheError, CacheResult};
+
+/// Format cached values are serialized with
+///
+/// JSON is the default because entries stay readable with tools like
+/// `redis-cli`. The binary formats are smaller and faster to encode, which
+/// matters for large values and high-throughput Redis caches:
+/// - `Bincode` (requires `cache-bincode` feature) - most compact, but only
+///   for types with a fixed serde layout (no `#[serde(flatten)]`,
+///   `#[serde(untagged)]` or `serde_json::Value`)
+/// - `MessagePack` (requires `cache-msgpack` feature) - self-describing, so
+///   it handles any type JSON does
+///
+/// All instances sharing a cache must use the same codec.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Codec {
+    /// JSON via `serde_json`
+    #[default]
+    Json,
+    /// Bincode
+    #[cfg(feature = "cache-bincode")]
+    Bincode,
+    /// MessagePack via `rmp-serde`
+    #[cfg(feature = "cache-msgpack")]
+    MessagePack,
+}
+
+impl Codec {
+    /// Serialize a value
+    pub fn encode<T: Serialize + ?Sized>(&self, value: &T) -> CacheResult<Vec<u8>> {
+        match self {
+            Codec::Json => {
+                serde_json::to_vec(value).map_err(|e| CacheError::Serialization(e.to_string()))
+            }
+            #[cfg(feature = "cache-bincode")]
+            Codec::Bincode => {
+                bincode::serialize(value).map_err(|e| CacheError::Serialization(e.to_string()))
+            }
+            #[cfg(feature = "cache-msgpack")]
+            Codec::MessagePack => {
+                rmp_serde::to_vec_named(value).map_err(|e| CacheError::Serialization(e.to_string()))
+            }
+        }
+    }
+
+    /// Deserialize a value
+    pub fn decode<T: DeserializeOwned>(&self, data: &[u8]) -> CacheResult<T> {
+        match self {
+            Codec::Json => {
+                serde_json::from_slice(data).map_err(|e| CacheError::Deserialization(e.to_string()))
+            }
+            #[cfg(feature = "cache-bincode")]
+            Codec::Bincode => {
+                bincode::deserialize(data).map_err(|e| CacheError::Deserialization(e.to_string()))
+            }
+            #[cfg(feature = "cache-msgpack")]
+            Codec::MessagePack => {
+                rmp_serde::from_slice(data).map_err(|e| CacheError::Deserialization(e.to_string()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+    struct Quote {
+        symbol: String,
+        bid: f64,
+        ask: Option<f64>,
+        levels: Vec<(u32, f64)>,
+    }
+
+    fn codecs() -> Vec<Codec> {
+        vec![
+            Codec::Json,
+            #[cfg(feature = "cache-bincode")]
+            Codec::Bincode,
+            #[cfg(feature = "cache-msgpack")]
+            Codec::MessagePack,
+        ]
+    }
+
+    #[test]
+    fn test_codec_round_trip() {
+        let quote = Quote {
+            symbol: "BTC-USD".to_string(),
+            bid: 64_000.5,
+            ask: None,
+            levels: vec![(1, 0.5), (2, 1.25)],
+        };
+
+        for codec in codecs() {
+            let data = codec.encode(&quote).unwrap();
+            assert_eq!(codec.decode::<Quote>(&data).unwrap(), quote, "{:?}", codec);
+        }
+    }
+
+    #[test]
+    fn test_codec_decode_error() {
+        for codec in codecs() {
+            let result = codec.decode::<Quote>(&[0xff, 0x00]);
+            assert!(
+                matches!(result, Err(CacheError::Deserialization(_))),
+                "{:?}",
+                codec
+            );
+        }
+    }
+
+    #[cfg(feature = "cache-bincode")]
+    #[test]
+    fn test_bincode_is_smaller_than_json() {
+        let quotes: Vec<Quote> = (0..100)
+            .map(|i| Quote {
+                symbol: format!("SYM{}", i),
+                bid: i as f64 * 0.5,
+                ask: Some(i as f64),
+                levels: vec![],
+            })
+            .collect();
+        let json = Codec::Json.encode(&quotes).unwrap();
+        let bincode = Codec::Bincode.encode(&quotes).unwrap();
+        assert!(bincode.len() < json.len());
+    }
+}
@@ -1,7 +1,7 @@
 //! In-memory cache implementation
 
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     future::Future,
     pin::Pin,
     sync::RwLock,
@@ -10,21 +10,24 @@ use std::{
 
 use serde::{de::DeserializeOwned, Serialize};
 
-use super::{Cache, CacheConfig};
+use super::{Cache, CacheConfig, CacheLookup, Codec};
 
 /// An entry in the memory cache
 struct CacheEntry {
-    /// Serialized value
-    data: Vec<u8>,
+    /// Serialized value, `None` if the key is known to be absent
+    data: Option<Vec<u8>>,
     /// Expiration time (if any)
     expires_at: Option<Instant>,
+    /// Tags the entry is attached to
+    tags: Vec<String>,
 }
 
 impl CacheEntry {
-    fn new(data: Vec<u8>, ttl: Option<Duration>) -> Self {
+    fn new(data: Option<Vec<u8>>, ttl: Option<Duration>, tags: Vec<String>) -> Self {
         Self {
             data,
             expires_at: ttl.map(|d| Instant::now() + d),
+            tags,
         }
     }
 
@@ -33,6 +36,21 @@ impl CacheEntry {
     }
 }
 
+/// Keys of the entries attached to each tag
+type TagIndex = HashMap<String, HashSet<String>>;
+
+/// Remove `key` from the index entries of `tags`
+fn unlink(index: &mut TagIndex, key: &str, tags: &[String]) {
+    for tag in tags {
+        if let Some(keys) = index.get_mut(tag) {
+            keys.remove(key);
+            if keys.is_empty() {
+                index.remove(tag);
+            }
+        }
+    }
+}
+
 /// In-memory cache with TTL support
 ///
 /// This cache stores values in memory using a `HashMap` with optional
@@ -56,6 +74,8 @@ impl CacheEntry {
 /// ```
 pub struct MemoryCache {
     entries: RwLock<HashMap<String, CacheEntry>>,
+    /// Lock order: `entries`, then `tags`
+    tags: RwLock<TagIndex>,
     config: CacheConfig,
 }
 
@@ -69,6 +89,7 @@ impl MemoryCache {
     pub fn with_config(config: CacheConfig) -> Self {
         Self {
             entries: RwLock::new(HashMap::new()),
+            tags: RwLock::new(HashMap::new()),
             config,
         }
     }
@@ -84,7 +105,67 @@ impl MemoryCache {
     /// manually if needed.
     pub fn cleanup_expired(&self) {
         let mut entries = self.entries.write().unwrap();
-        entries.retain(|_, entry| !entry.is_expired());
+        let mut index = self.tags.write().unwrap();
+        Self::remove_expired(&mut entries, &mut index);
+    }
+
+    fn remove_expired(entries: &mut HashMap<String, CacheEntry>, index: &mut TagIndex) {
+        let expired: Vec<String> = entries
+            .iter()
+            .filter(|(_, entry)| entry.is_expired())
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in expired {
+            if let Some(entry) = entries.remove(&key) {
+                unlink(index, &key, &entry.tags);
+            }
+        }
+    }
+
+    fn lookup_key<T: DeserializeOwned>(&self, key: &str) -> CacheLookup<T> {
+        let entries = self.entries.read().unwrap();
+        match entries.get(key) {
+            Some(entry) if !entry.is_expired() => match &entry.data {
+                Some(data) => match self.config.codec.decode(data) {
+                    Ok(value) => CacheLookup::Hit(value),
+                    Err(_) => CacheLookup::Miss,
+                },
+                None => CacheLookup::Absent,
+            },
+            _ => CacheLookup::Miss,
+        }
+    }
+
+    /// Insert an entry, evicting one if the cache is full, and index its tags
+    fn insert(&self, key: String, entry: CacheEntry) {
+        let mut entries = self.entries.write().unwrap();
+        let mut index = self.tags.write().unwrap();
+
+        // Check max entries limit
+        if let Some(max) = self.config.max_entries {
+            if entries.len() >= max && !entries.contains_key(&key) {
+                // Remove expired entries first
+                Self::remove_expired(&mut entries, &mut index);
+
+                // If still at limit, remove oldest entry (simple LRU approximation)
+                if entries.len() >= max {
+                    if let Some(oldest_key) = entries.keys().next().cloned() {
+                        if let Some(oldest) = entries.remove(&oldest_key) {
+                            unlink(&mut index, &oldest_key, &oldest.tags);
+                        }
+                    }
+                }
+            }
+        }
+
+        for tag in &entry.tags {
+            index.entry(tag.clone()).or_default().insert(key.clone());
+        }
+        let tags = entry.tags.clone();
+        if let Some(old) = entries.insert(key.clone(), entry) {
+            let stale: Vec<String> = old.tags.into_iter().filter(|t| !tags.contains(t)).collect();
+            unlink(&mut index, &key, &stale);
+        }
     }
 
     /// Get the effective TTL for an entry
@@ -110,16 +191,24 @@ impl Cache for MemoryCache {
         key: &str,
     ) -> Pin<Box<dyn Future<Output = Option<T>> + Send + '_>> {
         let key = self.full_key(key);
-        Box::pin(async move {
-            let entries = self.entries.read().unwrap();
-            entries.get(&key).and_then(|entry| {
-                if entry.is_expired() {
-                    None
-                } else {
-                    serde_json::from_slice(&entry.data).ok()
-                }
-            })
-        })
+        Box::pin(async move { self.lookup_key(&key).into_option() })
+    }
+
+    fn lookup<T: DeserializeOwned + Send>(
+        &self,
+        key: &str,
+    ) -> Pin<Box<dyn Future<Output = CacheLookup<T>> + Send + '_>> {
+        let key = self.full_key(key);
+        Box::pin(async move { self.lookup_key(&key) })
+    }
+
+    fn cache_miss(
+        &self,
+        key: &str,
+        ttl: Duration,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        let key = self.full_key(key);
+        Box::pin(async move { self.insert(key, CacheEntry::new(None, Some(ttl), Vec::new())) })
     }
 
     fn set<T: Serialize + Send + Sync>(
@@ -132,30 +221,52 @@ impl Cache for MemoryCache {
         let ttl = self.effective_ttl(ttl);
 
         // Serialize outside the lock
-        let data = match serde_json::to_vec(value) {
+        let data = match self.config.codec.encode(value) {
             Ok(d) => d,
             Err(_) => return Box::pin(async {}),
         };
 
-        Box::pin(async move {
-            let mut entries = self.entries.write().unwrap();
+        Box::pin(async move { self.insert(key, CacheEntry::new(Some(data), ttl, Vec::new())) })
+    }
 
-            // Check max entries limit
-            if let Some(max) = self.config.max_entries {
-                if entries.len() >= max && !entries.contains_key(&key) {
-                    // Remove expired entries first
-                    entries.retain(|_, entry| !entry.is_expired());
+    fn set_tagged<T: Serialize + Send + Sync>(
+        &self,
+        key: &str,
+        value: &T,
+        ttl: Option<Duration>,
+        tags: &[&str],
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        let key = self.full_key(key);
+        let ttl = self.effective_ttl(ttl);
+        let tags = tags.iter().map(|tag| tag.to_string()).collect();
 
-                    // If still at limit, remove oldest entry (simple LRU approximation)
-                    if entries.len() >= max {
-                        if let Some(oldest_key) = entries.keys().next().cloned() {
-                            entries.remove(&oldest_key);
-                        }
+        let data = match self.config.codec.encode(value) {
+            Ok(d) => d,
+            Err(_) => return Box::pin(async {}),
+        };
+
+        Box::pin(async move { self.insert(key, CacheEntry::new(Some(data), ttl, tags)) })
+    }
+
+    fn invalidate_tag(&self, tag: &str) -> Pin<Box<dyn Future<Output = usize> + Send + '_>> {
+        let tag = tag.to_string();
+        Box::pin(async move {
+            let mut entries = self.entries.write().unwrap();
+            let mut index = self.tags.write().unwrap();
+            let Some(keys) = index.remove(&tag) else {
+                return 0;
+            };
+
+            let mut count = 0;
+            for key in keys {
+                if let Some(entry) = entries.remove(&key) {
+                    unlink(&mut index, &key, &entry.tags);
+                    if !entry.is_expired() {
+                        count += 1;
                     }
                 }
             }
-
-            entries.insert(key, CacheEntry::new(data, ttl));
+            count
         })
     }
 
@@ -163,7 +274,11 @@ impl Cache for MemoryCache {
         let key = self.full_key(key);
         Box::pin(async move {
             let mut entries = self.entries.write().unwrap();
-            entries.remove(&key).is_some()
+            let Some(entry) = entries.remove(&key) else {
+                return false;
+            };
+            unlink(&mut self.tags.write().unwrap(), &key, &entry.tags);
+            entry.data.is_some()
         })
     }
 
@@ -173,7 +288,7 @@ impl Cache for MemoryCache {
             let entries = self.entries.read().unwrap();
             entries
                 .get(&key)
-                .map(|entry| !entry.is_expired())
+                .map(|entry| !entry.is_expired() && entry.data.is_some())
                 .unwrap_or(false)
         })
     }
@@ -182,14 +297,18 @@ impl Cache for MemoryCache {
         Box::pin(async move {
             let mut entries = self.entries.write().unwrap();
             entries.clear();
+            self.tags.write().unwrap().clear();
         })
     }
 
     fn len(&self) -> Pin<Box<dyn Future<Output = Option<usize>> + Send + '_>> {
         Box::pin(async move {
             let entries = self.entries.read().unwrap();
-            // Count non-expired entries
-            let count = entries.values().filter(|e| !e.is_expired()).count();
+            // Count non-expired values
+            let count = entries
+                .values()
+                .filter(|e| !e.is_expired() && e.data.is_some())
+                .count();
             Some(count)
         })
     }
@@ -226,6 +345,12 @@ impl MemoryCacheBuilder {
         self
     }
 
+    /// Set the serialization format for values
+    pub fn codec(mut self, codec: Codec) -> Self {
+        self.config.codec = codec;
+        self
+    }
+
     /// Build the cache
     pub fn build(self) -> MemoryCache {
         MemoryCache::with_config(self.config)
@@ -388,4 +513,97 @@ mod tests {
         let value: Option<String> = cache.get("key").await;
         assert_eq!(value, None);
     }
+
+    #[tokio::test]
+    async fn test_memory_cache_invalidate_tag() {
+        let cache = MemoryCache::new();
+
+        cache
+            .set_tagged("ticker:BTC", &100, None, &["tickers", "crypto"])
+            .await;
+        cache
+            .set_tagged("ticker:ETH", &50, None, &["tickers", "crypto"])
+            .await;
+        cache
+            .set_tagged("ticker:AAPL", &200, None, &["tickers"])
+            .await;
+        cache.set("user:1", &"alice", None).await;
+
+        assert_eq!(cache.invalidate_tag("crypto").await, 2);
+        assert!(!cache.exists("ticker:BTC").await);
+        assert!(!cache.exists("ticker:ETH").await);
+        assert!(cache.exists("ticker:AAPL").await);
+        assert!(cache.exists("user:1").await);
+
+        // Invalidated entries are detached from their other tags
+        assert_eq!(cache.invalidate_tag("tickers").await, 1);
+        assert_eq!(cache.invalidate_tag("tickers").await, 0);
+        assert_eq!(cache.len().await, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_set_replaces_tags() {
+        let cache = MemoryCache::new();
+
+        cache.set_tagged("quote", &1, None, &["old"]).await;
+        cache.set_tagged("quote", &2, None, &["new"]).await;
+        assert_eq!(cache.invalidate_tag("old").await, 0);
+
+        cache.set("quote", &3, None).await;
+        assert_eq!(cache.invalidate_tag("new").await, 0);
+        assert_eq!(cache.get::<i32>("quote").await, Some(3));
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_codecs() {
+        #[derive(serde::Serialize, serde::Deserialize, PartialEq, Debug)]
+        struct Ticker {
+            symbol: String,
+            price: f64,
+        }
+
+        let codecs = [
+            Codec::Json,
+            #[cfg(feature = "cache-bincode")]
+            Codec::Bincode,
+            #[cfg(feature = "cache-msgpack")]
+            Codec::MessagePack,
+        ];
+        for codec in codecs {
+            let cache = MemoryCache::builder().codec(codec).build();
+            let ticker = Ticker {
+                symbol: "ETH".to_string(),
+                price: 3_100.25,
+            };
+
+            cache.set("ticker", &ticker, None).await;
+            assert_eq!(cache.get::<Ticker>("ticker").await, Some(ticker));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_memory_cache_negative_caching() {
+        let cache = MemoryCache::builder()
+            .default_ttl(Duration::from_secs(300))
+            .build();
+
+        cache
+            .cache_miss("asset:404", Duration::from_millis(5))
+            .await;
+        assert_eq!(cache.lookup::<i32>("asset:404").await, CacheLookup::Absent);
+        assert_eq!(cache.get::<i32>("asset:404").await, None);
+        assert!(!cache.exists("asset:404").await);
+        assert_eq!(cache.len().await, Some(0));
+
+        // The negative TTL doesn't use the default TTL
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(cache.lookup::<i32>("asset:404").await, CacheLookup::Miss);
+
+        // Setting the key clears the marker, and marking removes the value
+        cache.cache_miss("asset:1", Duration::from_secs(60)).await;
+        cache.set("asset:1", &1, None).await;
+        assert_eq!(cache.lookup::<i32>("asset:1").await, CacheLookup::Hit(1));
+        cache.cache_miss("asset:1", Duration::from_secs(60)).await;
+        assert_eq!(cache.lookup::<i32>("asset:1").await, CacheLookup::Absent);
+    }
 }
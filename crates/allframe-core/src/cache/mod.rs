@@ -1,7 +1,18 @@
 //! Cache abstraction for AllFrame applications
 //!
 //! This module provides a unified cache interface with support for multiple
-//! backends including in-memory caching and Redis.
+//! backends including in-memory caching and Redis (`cache-redis` feature).
+//!
+//! Entries can be tagged with [`Cache::set_tagged`] and removed as a group
+//! with [`Cache::invalidate_tag`]. [`Cache::cache_miss`] caches the absence
+//! of a key for a short TTL, which [`Cache::lookup`] reports as
+//! [`CacheLookup::Absent`].
+//!
+//! [`Cache::set_tagged`]: crate::cache::Cache::set_tagged
+//! [`Cache::invalidate_tag`]: crate::cache::Cache::invalidate_tag
+//! [`Cache::cache_miss`]: crate::cache::Cache::cache_miss
+//! [`Cache::lookup`]: crate::cache::Cache::lookup
+//! [`CacheLookup::Absent`]: crate::cache::CacheLookup::Absent
 //!
 //! # Example
 //!
@@ -22,9 +33,15 @@
 //! }
 //! ```
 
+mod codec;
 mod memory;
+#[cfg(feature = "cache-redis")]
+mod redis;
 mod traits;
 
+#[cfg(feature = "cache-redis")]
+pub use self::redis::RedisCache;
+pub use codec::Codec;
 pub use memory::MemoryCache;
 pub use traits::*;
 
@@ -69,6 +86,8 @@ pub struct CacheConfig {
     pub default_ttl: Option<std::time::Duration>,
     /// Maximum number of entries (for memory cache)
     pub max_entries: Option<usize>,
+    /// Serialization format for values (default: JSON)
+    pub codec: Codec,
 }
 
 impl CacheConfig {
@@ -95,6 +114,12 @@ impl CacheConfig {
         self
     }
 
+    /// Set the serialization format for values
+    pub fn codec(mut self, codec: Codec) -> Self {
+        self.codec = codec;
+        self
+    }
+
     /// Build a prefixed key
     pub fn build_key(&self, key: &str) -> String {
         match &self.prefix {
@@ -0,0 +1,428 @@
+//! Redis cache implementation
+
+use std::{future::Future, pin::Pin, time::Duration};
+
+use redis::{aio::ConnectionManager, AsyncCommands, Client};
+use serde::{de::DeserializeOwned, Serialize};
+
+use super::{Cache, CacheConfig, CacheError, CacheLookup, CacheResult};
+
+/// Redis-backed cache shared across instances
+///
+/// Values are serialized with the configured [`Codec`](super::Codec) (JSON
+/// by default) and stored under the configured key prefix. Operations
+/// that fail (e.g. because Redis is unreachable) behave like a cache miss.
+///
+/// Tags are stored as Redis sets of keys (`{prefix}:tag:{tag}`), and each
+/// tagged key keeps the set of its own tags (`{prefix}:tags:{key}`).
+/// Overwriting or deleting a key removes it from its tag sets, and
+/// invalidating a tag only deletes keys still tagged with it. Tag sets
+/// expire no earlier than their members. Keys marked absent with
+/// [`cache_miss`](Cache::cache_miss) are stored as `{prefix}:absent:{key}`.
+/// Scripts only touch keys passed to them, as Redis requires.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use allframe_core::cache::{Cache, CacheConfig, RedisCache};
+/// use std::time::Duration;
+///
+/// let cache = RedisCache::with_config(
+///     "redis://localhost:6379",
+///     CacheConfig::new().prefix("gateway"),
+/// )
+/// .await?;
+///
+/// cache.set_tagged("ticker:BTC", &price, None, &["tickers"]).await;
+/// cache.invalidate_tag("tickers").await;
+/// ```
+pub struct RedisCache {
+    conn: ConnectionManager,
+    config: CacheConfig,
+}
+
+impl RedisCache {
+    /// Connect to Redis with default configuration
+    pub async fn new(redis_url: &str) -> CacheResult<Self> {
+        Self::with_config(redis_url, CacheConfig::default()).await
+    }
+
+    /// Connect to Redis with custom configuration
+    pub async fn with_config(redis_url: &str, config: CacheConfig) -> CacheResult<Self> {
+        let client = Client::open(redis_url).map_err(|e| CacheError::Connection(e.to_string()))?;
+        let conn = ConnectionManager::new(client)
+            .await
+            .map_err(|e| CacheError::Connection(e.to_string()))?;
+        Ok(Self { conn, config })
+    }
+
+    /// Create from an existing Redis connection manager
+    pub fn from_connection(conn: ConnectionManager, config: CacheConfig) -> Self {
+        Self { conn, config }
+    }
+
+    /// Get the configuration
+    pub fn config(&self) -> &CacheConfig {
+        &self.config
+    }
+
+    fn full_key(&self, key: &str) -> String {
+        self.config.build_key(key)
+    }
+
+    fn tag_key(&self, tag: &str) -> String {
+        self.config.build_key(&format!("tag:{}", tag))
+    }
+
+    fn absent_key(&self, key: &str) -> String {
+        self.config.build_key(&format!("absent:{}", key))
+    }
+
+    /// Set of the tags attached to `key`
+    fn key_tags_key(&self, key: &str) -> String {
+        self.config.build_key(&format!("tags:{}", key))
+    }
+
+    /// Tags currently attached to `key`
+    async fn tags_of(&self, key: &str) -> Vec<String> {
+        let mut conn = self.conn.clone();
+        conn.smembers(self.key_tags_key(key))
+            .await
+            .unwrap_or_default()
+    }
+
+    async fn lookup_key<T: DeserializeOwned>(&self, key: &str) -> CacheLookup<T> {
+        let mut conn = self.conn.clone();
+        // The value and the absent marker
+        type Entry = (Option<Vec<u8>>, Option<Vec<u8>>);
+        let result: redis::RedisResult<Entry> = redis::cmd("MGET")
+            .arg(self.full_key(key))
+            .arg(self.absent_key(key))
+            .query_async(&mut conn)
+            .await;
+        match result {
+            Ok((Some(data), _)) => match self.config.codec.decode(&data) {
+                Ok(value) => CacheLookup::Hit(value),
+                Err(_) => CacheLookup::Miss,
+            },
+            Ok((None, Some(_))) => CacheLookup::Absent,
+            _ => CacheLookup::Miss,
+        }
+    }
+}
+
+impl Cache for RedisCache {
+    fn get<T: DeserializeOwned + Send>(
+        &self,
+        key: &str,
+    ) -> Pin<Box<dyn Future<Output = Option<T>> + Send + '_>> {
+        let key = key.to_string();
+        Box::pin(async move { self.lookup_key(&key).await.into_option() })
+    }
+
+    fn lookup<T: DeserializeOwned + Send>(
+        &self,
+        key: &str,
+    ) -> Pin<Box<dyn Future<Output = CacheLookup<T>> + Send + '_>> {
+        let key = key.to_string();
+        Box::pin(async move { self.lookup_key(&key).await })
+    }
+
+    fn cache_miss(
+        &self,
+        key: &str,
+        ttl: Duration,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        let mut pipe = redis::pipe();
+        pipe.atomic()
+            .del(&[self.full_key(key), self.key_tags_key(key)])
+            .ignore()
+            .cmd("SET")
+            .arg(self.absent_key(key))
+            .arg(1)
+            .arg("PX")
+            .arg(ttl.as_millis().max(1) as u64)
+            .ignore();
+
+        Box::pin(async move {
+            let mut conn = self.conn.clone();
+            let _: Result<(), _> = pipe.query_async(&mut conn).await;
+        })
+    }
+
+    fn set<T: Serialize + Send + Sync>(
+        &self,
+        key: &str,
+        value: &T,
+        ttl: Option<Duration>,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        self.set_tagged(key, value, ttl, &[])
+    }
+
+    fn set_tagged<T: Serialize + Send + Sync>(
+        &self,
+        key: &str,
+        value: &T,
+        ttl: Option<Duration>,
+        tags: &[&str],
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        let data = match self.config.codec.encode(value) {
+            Ok(d) => d,
+            Err(_) => return Box::pin(async {}),
+        };
+
+        let key = key.to_string();
+        let tags: Vec<String> = tags.iter().map(|tag| tag.to_string()).collect();
+        let ttl_ms = ttl
+            .or(self.config.default_ttl)
+            .map_or(0, |ttl| ttl.as_millis().max(1) as u64);
+
+        Box::pin(async move {
+            let mut conn = self.conn.clone();
+            let old_tags = self.tags_of(&key).await;
+
+            // Write the value, clear any absent marker, move the key from its
+            // old tag sets to the new ones and keep each tag set alive at
+            // least as long as the value, atomically
+            let script = redis::Script::new(
+                r#"
+                local ttl = tonumber(ARGV[3])
+                local old = tonumber(ARGV[4])
+                if ttl > 0 then
+                    redis.call('SET', KEYS[1], ARGV[2], 'PX', ttl)
+                else
+                    redis.call('SET', KEYS[1], ARGV[2])
+                end
+                redis.call('DEL', KEYS[2], KEYS[3])
+                for i = 4, 3 + old do
+                    redis.call('SREM', KEYS[i], ARGV[1])
+                end
+                for i = 4 + old, #KEYS do
+                    redis.call('SADD', KEYS[i], ARGV[1])
+                    redis.call('SADD', KEYS[3], ARGV[i - old + 1])
+                    if ttl == 0 then
+                        redis.call('PERSIST', KEYS[i])
+                    else
+                        local current = redis.call('PTTL', KEYS[i])
+                        if (current == -1 and redis.call('SCARD', KEYS[i]) == 1)
+                            or (current >= 0 and current < ttl) then
+                            redis.call('PEXPIRE', KEYS[i], ttl)
+                        end
+                    end
+                end
+                if ttl > 0 and #KEYS > 3 + old then
+                    redis.call('PEXPIRE', KEYS[3], ttl)
+                end
+                return 0
+                "#,
+            );
+            let mut invocation = script.key(self.full_key(&key));
+            invocation
+                .key(self.absent_key(&key))
+                .key(self.key_tags_key(&key));
+            for tag in &old_tags {
+                invocation.key(self.tag_key(tag));
+            }
+            for tag in &tags {
+                invocation.key(self.tag_key(tag));
+            }
+            invocation
+                .arg(&key)
+                .arg(data)
+                .arg(ttl_ms)
+                .arg(old_tags.len());
+            for tag in &tags {
+                invocation.arg(tag);
+            }
+            let _: Result<i64, _> = invocation.invoke_async(&mut conn).await;
+        })
+    }
+
+    fn invalidate_tag(&self, tag: &str) -> Pin<Box<dyn Future<Output = usize> + Send + '_>> {
+        let tag = tag.to_string();
+        Box::pin(async move {
+            let mut conn = self.conn.clone();
+            let tag_key = self.tag_key(&tag);
+            let members: Vec<String> = conn.smembers(&tag_key).await.unwrap_or_default();
+            if members.is_empty() {
+                return 0;
+            }
+
+            // Delete the members still tagged with `tag` and drop them from
+            // the tag set. Keys tagged since the tag set was read stay in it.
+            let script = redis::Script::new(
+                r#"
+                local deleted = 0
+                for i = 2, #KEYS, 2 do
+                    if redis.call('SISMEMBER', KEYS[i + 1], ARGV[1]) == 1 then
+                        deleted = deleted + redis.call('DEL', KEYS[i])
+                        redis.call('DEL', KEYS[i + 1])
+                    end
+                    redis.call('SREM', KEYS[1], ARGV[i / 2 + 1])
+                end
+                return deleted
+                "#,
+            );
+            let mut invocation = script.key(&tag_key);
+            invocation.arg(&tag);
+            for member in &members {
+                invocation
+                    .key(self.full_key(member))
+                    .key(self.key_tags_key(member))
+                    .arg(member);
+            }
+            invocation.invoke_async(&mut conn).await.unwrap_or(0)
+        })
+    }
+
+    fn delete(&self, key: &str) -> Pin<Box<dyn Future<Output = bool> + Send + '_>> {
+        let key = key.to_string();
+        Box::pin(async move {
+            let mut conn = self.conn.clone();
+            let mut pipe = redis::pipe();
+            pipe.atomic()
+                .del(self.full_key(&key))
+                .del(&[self.absent_key(&key), self.key_tags_key(&key)])
+                .ignore();
+            for tag in self.tags_of(&key).await {
+                pipe.srem(self.tag_key(&tag), &key).ignore();
+            }
+            let (deleted,): (usize,) = pipe.query_async(&mut conn).await.unwrap_or((0,));
+            deleted > 0
+        })
+    }
+
+    fn exists(&self, key: &str) -> Pin<Box<dyn Future<Output = bool> + Send + '_>> {
+        let key = self.full_key(key);
+        Box::pin(async move {
+            let mut conn = self.conn.clone();
+            conn.exists(&key).await.unwrap_or(false)
+        })
+    }
+
+    /// Delete every key under the configured prefix
+    ///
+    /// Without a prefix, this deletes every key in the Redis database.
+    fn clear(&self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        let pattern = self.full_key("*");
+        Box::pin(async move {
+            let mut conn = self.conn.clone();
+            let keys = {
+                let Ok(mut iter) = conn.scan_match::<_, String>(&pattern).await else {
+                    return;
+                };
+                let mut keys = Vec::new();
+                while let Some(key) = iter.next_item().await {
+                    keys.push(key);
+                }
+                keys
+            };
+            for chunk in keys.chunks(500) {
+                let _: Result<(), _> = conn.del(chunk).await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Integration tests require a running Redis instance
+    // Run with: cargo test --features cache-redis -- --ignored
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_redis_cache_invalidate_tag() {
+        let cache = RedisCache::with_config(
+            "redis://localhost:6379",
+            CacheConfig::new().prefix("allframe-test"),
+        )
+        .await
+        .expect("Failed to connect to Redis");
+        cache.clear().await;
+
+        cache
+            .set_tagged("ticker:BTC", &100, None, &["tickers", "crypto"])
+            .await;
+        cache
+            .set_tagged("ticker:ETH", &50, None, &["tickers", "crypto"])
+            .await;
+        cache
+            .set_tagged("ticker:AAPL", &200, None, &["tickers"])
+            .await;
+
+        assert_eq!(cache.invalidate_tag("crypto").await, 2);
+        assert!(!cache.exists("ticker:BTC").await);
+        assert_eq!(cache.get::<i32>("ticker:AAPL").await, Some(200));
+        assert_eq!(cache.invalidate_tag("tickers").await, 1);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_redis_cache_retagging_and_tag_expiry() {
+        let cache = RedisCache::with_config(
+            "redis://localhost:6379",
+            CacheConfig::new().prefix("allframe-test-retag"),
+        )
+        .await
+        .expect("Failed to connect to Redis");
+        cache.clear().await;
+        let mut conn = cache.conn.clone();
+
+        // Re-setting a key without tags takes it out of its tag sets
+        cache
+            .set_tagged("ticker:BTC", &100, None, &["tickers"])
+            .await;
+        cache.set("ticker:BTC", &101, None).await;
+        assert_eq!(cache.invalidate_tag("tickers").await, 0);
+        assert_eq!(cache.get::<i32>("ticker:BTC").await, Some(101));
+
+        // Deleting a key prunes its tag sets
+        cache.set_tagged("ticker:ETH", &50, None, &["crypto"]).await;
+        cache.delete("ticker:ETH").await;
+        let members: Vec<String> = conn.smembers(cache.tag_key("crypto")).await.unwrap();
+        assert!(members.is_empty());
+
+        // Tag sets live as long as their longest-lived member
+        let short = Duration::from_secs(10);
+        let long = Duration::from_secs(100);
+        cache
+            .set_tagged("ticker:SOL", &1, Some(long), &["alts"])
+            .await;
+        cache
+            .set_tagged("ticker:ADA", &2, Some(short), &["alts"])
+            .await;
+        let ttl: i64 = conn.pttl(cache.tag_key("alts")).await.unwrap();
+        assert!(ttl > short.as_millis() as i64);
+
+        cache.set_tagged("ticker:DOT", &3, None, &["alts"]).await;
+        let ttl: i64 = conn.pttl(cache.tag_key("alts")).await.unwrap();
+        assert_eq!(ttl, -1);
+        assert_eq!(cache.invalidate_tag("alts").await, 3);
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_redis_cache_negative_caching() {
+        let cache = RedisCache::with_config(
+            "redis://localhost:6379",
+            CacheConfig::new().prefix("allframe-test-negative"),
+        )
+        .await
+        .expect("Failed to connect to Redis");
+        cache.clear().await;
+
+        cache
+            .cache_miss("asset:404", Duration::from_millis(50))
+            .await;
+        assert_eq!(cache.lookup::<i32>("asset:404").await, CacheLookup::Absent);
+
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(cache.lookup::<i32>("asset:404").await, CacheLookup::Miss);
+
+        cache.cache_miss("asset:404", Duration::from_secs(60)).await;
+        cache.set("asset:404", &1, None).await;
+        assert_eq!(cache.lookup::<i32>("asset:404").await, CacheLookup::Hit(1));
+    }
+}
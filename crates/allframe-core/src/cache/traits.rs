@@ -28,6 +28,78 @@ pub trait Cache: Send + Sync {
         ttl: Option<Duration>,
     ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>>;
 
+    /// Set a value and attach it to one or more tags
+    ///
+    /// Tags group related entries (e.g. every ticker of a market) so they
+    /// can be removed together with [`invalidate_tag`](Self::invalidate_tag)
+    /// without tracking their keys.
+    ///
+    /// Default implementation calls `set` and ignores the tags. Backends
+    /// that support tagging override this and `invalidate_tag`.
+    fn set_tagged<T: Serialize + Send + Sync>(
+        &self,
+        key: &str,
+        value: &T,
+        ttl: Option<Duration>,
+        tags: &[&str],
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        let _ = tags;
+        self.set(key, value, ttl)
+    }
+
+    /// Delete every entry tagged with `tag`
+    ///
+    /// Returns the number of entries deleted. Default implementation deletes
+    /// nothing, as the default `set_tagged` doesn't record tags.
+    fn invalidate_tag(&self, tag: &str) -> Pin<Box<dyn Future<Output = usize> + Send + '_>> {
+        let _ = tag;
+        Box::pin(async { 0 })
+    }
+
+    /// Look up a value, distinguishing known-absent keys from misses
+    ///
+    /// Returns [`CacheLookup::Absent`] for keys marked with
+    /// [`cache_miss`](Self::cache_miss), so callers can skip the backend
+    /// lookup entirely.
+    ///
+    /// Default implementation maps `get` to `Hit` or `Miss`, for backends
+    /// without negative caching.
+    fn lookup<T: DeserializeOwned + Send>(
+        &self,
+        key: &str,
+    ) -> Pin<Box<dyn Future<Output = CacheLookup<T>> + Send + '_>> {
+        let key = key.to_string();
+        Box::pin(async move {
+            match self.get(&key).await {
+                Some(value) => CacheLookup::Hit(value),
+                None => CacheLookup::Miss,
+            }
+        })
+    }
+
+    /// Mark a key as known to be absent for `ttl`
+    ///
+    /// Negative caching: repeated lookups of a key that doesn't exist in the
+    /// backend are answered from the cache until `ttl` passes, protecting
+    /// the backend from floods of requests for non-existent keys. The TTL is
+    /// independent of the cache's default TTL, and is usually much shorter.
+    /// Any cached value for the key is removed; setting the key again clears
+    /// the marker.
+    ///
+    /// Default implementation only deletes the key, so `lookup` reports a
+    /// `Miss` rather than `Absent`.
+    fn cache_miss(
+        &self,
+        key: &str,
+        ttl: Duration,
+    ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+        let _ = ttl;
+        let key = key.to_string();
+        Box::pin(async move {
+            self.delete(&key).await;
+        })
+    }
+
     /// Delete a value from the cache
     ///
     /// Returns `true` if the key existed and was deleted.
@@ -101,6 +173,27 @@ pub trait Cache: Send + Sync {
     }
 }
 
+/// Result of [`Cache::lookup`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CacheLookup<T> {
+    /// The value was cached
+    Hit(T),
+    /// The key is known not to exist (see [`Cache::cache_miss`])
+    Absent,
+    /// Nothing is cached for the key
+    Miss,
+}
+
+impl<T> CacheLookup<T> {
+    /// The cached value, if any
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            CacheLookup::Hit(value) => Some(value),
+            CacheLookup::Absent | CacheLookup::Miss => None,
+        }
+    }
+}
+
 /// Extension trait for cache operations with typed keys
 pub trait CacheExt: Cache {
     /// Get a value using a typed cache key
@@ -177,4 +270,56 @@ mod tests {
         let err = CacheError::Connection("refused".into());
         assert!(err.to_string().contains("refused"));
     }
+
+    /// Cache implementing only the required methods
+    #[derive(Default)]
+    struct MinimalCache(std::sync::Mutex<std::collections::HashMap<String, Vec<u8>>>);
+
+    impl Cache for MinimalCache {
+        fn get<T: DeserializeOwned + Send>(
+            &self,
+            key: &str,
+        ) -> Pin<Box<dyn Future<Output = Option<T>> + Send + '_>> {
+            let value = self.0.lock().unwrap().get(key).cloned();
+            Box::pin(async move { value.and_then(|data| serde_json::from_slice(&data).ok()) })
+        }
+
+        fn set<T: Serialize + Send + Sync>(
+            &self,
+            key: &str,
+            value: &T,
+            _ttl: Option<Duration>,
+        ) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+            let data = serde_json::to_vec(value).unwrap();
+            self.0.lock().unwrap().insert(key.to_string(), data);
+            Box::pin(async {})
+        }
+
+        fn delete(&self, key: &str) -> Pin<Box<dyn Future<Output = bool> + Send + '_>> {
+            let deleted = self.0.lock().unwrap().remove(key).is_some();
+            Box::pin(async move { deleted })
+        }
+
+        fn exists(&self, key: &str) -> Pin<Box<dyn Future<Output = bool> + Send + '_>> {
+            let exists = self.0.lock().unwrap().contains_key(key);
+            Box::pin(async move { exists })
+        }
+
+        fn clear(&self) -> Pin<Box<dyn Future<Output = ()> + Send + '_>> {
+            self.0.lock().unwrap().clear();
+            Box::pin(async {})
+        }
+    }
+
+    #[tokio::test]
+    async fn test_default_lookup_and_cache_miss() {
+        let cache = MinimalCache::default();
+        cache.set("asset:1", &1, None).await;
+        assert_eq!(cache.lookup::<i32>("asset:1").await, CacheLookup::Hit(1));
+
+        // Without negative caching, a marked key is just a miss
+        cache.cache_miss("asset:1", Duration::from_secs(60)).await;
+        assert_eq!(cache.lookup::<i32>("asset:1").await, CacheLookup::Miss);
+        assert!(!cache.exists("asset:1").await);
+    }
 }
@@ -0,0 +1,575 @@
+//! Layered configuration loading
+//!
+//! [`ConfigLoader`] builds a service's configuration struct from up to three
+//! layers, each overriding the one before:
+//!
+//! 1. defaults, from a serializable value such as `T::default()`
+//! 2. config files, in the order they were added (TOML, JSON, or YAML with
+//!    the `config-yaml` feature)
+//! 3. environment variables with the loader's prefix
+//!
+//! An environment variable `<PREFIX>_<PATH>` sets the value at `PATH`,
+//! lowercased, with `__` separating nested keys: with prefix `APP`,
+//! `APP_SERVER__PORT=9090` sets `server.port`. Environment values are
+//! strings; numbers and booleans are parsed where the struct expects them,
+//! and values starting with `[` or `{` are read as JSON.
+//!
+//! The [`LoadedConfig`] remembers which layer each value came from, so a
+//! service can print where its settings came from when started with
+//! `--show-config`:
+//!
+//! ```rust,no_run
+//! use allframe_core::config::{show_config_requested, ConfigLoader};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, Default, Serialize, Deserialize)]
+//! struct ServerConfig {
+//!     host: String,
+//!     port: u16,
+//!     api_key: String,
+//! }
+//!
+//! let config = ConfigLoader::new()
+//!     .with_defaults(&ServerConfig::default())
+//!     .with_file("config.toml")
+//!     .with_env_prefix("APP")
+//!     .load::<ServerConfig>()
+//!     .expect("invalid configuration");
+//!
+//! if show_config_requested() {
+//!     // host = "0.0.0.0"  (file config.toml)
+//!     // port = "9090"  (env APP_PORT)
+//!     // api_key = "***"  (env APP_API_KEY)
+//!     print!("{}", config.report());
+//!     std::process::exit(0);
+//! }
+//! println!("listening on {}:{}", config.host, config.port);
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    fmt, io,
+    ops::Deref,
+    path::{Path, PathBuf},
+};
+
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+
+use crate::router::from_args;
+
+/// Command-line flag that asks a service to print its configuration
+pub const SHOW_CONFIG_FLAG: &str = "--show-config";
+
+/// Check if the process was started with `--show-config`
+pub fn show_config_requested() -> bool {
+    std::env::args().any(|arg| arg == SHOW_CONFIG_FLAG)
+}
+
+/// Errors from loading configuration
+#[derive(Debug)]
+pub enum ConfigError {
+    /// A config file couldn't be read
+    Read {
+        /// The file
+        path: PathBuf,
+        /// The underlying error
+        source: io::Error,
+    },
+    /// A config file isn't valid for its format
+    Parse {
+        /// The file
+        path: PathBuf,
+        /// What's wrong with it
+        message: String,
+    },
+    /// A config file's extension isn't a supported format
+    UnsupportedFormat(PathBuf),
+    /// The merged configuration doesn't fit the target type
+    Invalid(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Read { path, source } => {
+                write!(f, "Failed to read {}: {}", path.display(), source)
+            }
+            ConfigError::Parse { path, message } => {
+                write!(f, "Failed to parse {}: {}", path.display(), message)
+            }
+            ConfigError::UnsupportedFormat(path) => {
+                write!(f, "Unsupported config file format: {}", path.display())
+            }
+            ConfigError::Invalid(message) => write!(f, "Invalid configuration: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Read { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "di")]
+impl From<ConfigError> for crate::di::DependencyError {
+    fn from(error: ConfigError) -> Self {
+        crate::di::DependencyError::ConfigError(error.to_string())
+    }
+}
+
+/// Where a configuration value came from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// The loader's defaults
+    Default,
+    /// A config file
+    File(PathBuf),
+    /// An environment variable
+    Env(String),
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigSource::Default => write!(f, "default"),
+            ConfigSource::File(path) => write!(f, "file {}", path.display()),
+            ConfigSource::Env(var) => write!(f, "env {}", var),
+        }
+    }
+}
+
+/// Builder that merges defaults, files and environment variables
+#[derive(Debug, Clone, Default)]
+pub struct ConfigLoader {
+    defaults: Option<Value>,
+    files: Vec<(PathBuf, bool)>,
+    env_prefix: Option<String>,
+}
+
+impl ConfigLoader {
+    /// Create a loader with no layers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use a value's fields as defaults
+    ///
+    /// # Panics
+    ///
+    /// Panics if `defaults` doesn't serialize to JSON.
+    pub fn with_defaults<T: Serialize>(mut self, defaults: &T) -> Self {
+        self.defaults =
+            Some(serde_json::to_value(defaults).expect("config defaults serialize to JSON"));
+        self
+    }
+
+    /// Add a config file, skipped if it doesn't exist
+    ///
+    /// The format is chosen by extension: `.toml`, `.json`, or `.yaml` /
+    /// `.yml` with the `config-yaml` feature.
+    pub fn with_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.files.push((path.into(), false));
+        self
+    }
+
+    /// Add a config file that must exist
+    pub fn with_required_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.files.push((path.into(), true));
+        self
+    }
+
+    /// Read environment variables starting with `<prefix>_`
+    ///
+    /// Without a prefix, the environment isn't read.
+    pub fn with_env_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.env_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Merge the layers and deserialize the result
+    pub fn load<T: DeserializeOwned>(&self) -> Result<LoadedConfig<T>, ConfigError> {
+        self.load_with_env(std::env::vars())
+    }
+
+    fn load_with_env<T, I>(&self, env: I) -> Result<LoadedConfig<T>, ConfigError>
+    where
+        T: DeserializeOwned,
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut layers = Layers::default();
+        if let Some(defaults) = &self.defaults {
+            layers.apply(defaults.clone(), &ConfigSource::Default);
+        }
+
+        for (path, required) in &self.files {
+            if !required && !path.exists() {
+                continue;
+            }
+            let value = read_file(path)?;
+            layers.apply(value, &ConfigSource::File(path.clone()));
+        }
+
+        if let Some(prefix) = &self.env_prefix {
+            let prefix = format!("{}_", prefix);
+            let mut vars: Vec<(String, String)> = env
+                .into_iter()
+                .filter(|(name, _)| name.starts_with(&prefix) && name.len() > prefix.len())
+                .collect();
+            vars.sort();
+            for (name, raw) in vars {
+                let keys: Vec<String> = name[prefix.len()..]
+                    .split("__")
+                    .map(str::to_lowercase)
+                    .collect();
+                let mut value = env_value(raw);
+                for key in keys.iter().rev() {
+                    let mut object = Map::new();
+                    object.insert(key.clone(), value);
+                    value = Value::Object(object);
+                }
+                layers.apply(value, &ConfigSource::Env(name));
+            }
+        }
+
+        let value = from_args(&layers.merged.to_string())
+            .map_err(|e| ConfigError::Invalid(e.to_string()))?;
+        Ok(LoadedConfig {
+            value,
+            merged: layers.merged,
+            sources: layers.sources,
+        })
+    }
+}
+
+/// Merged configuration values with the source of each leaf
+struct Layers {
+    merged: Value,
+    sources: BTreeMap<String, ConfigSource>,
+}
+
+impl Default for Layers {
+    fn default() -> Self {
+        Self {
+            merged: Value::Object(Map::new()),
+            sources: BTreeMap::new(),
+        }
+    }
+}
+
+impl Layers {
+    fn apply(&mut self, overlay: Value, source: &ConfigSource) {
+        merge(&mut self.merged, overlay, "", source, &mut self.sources);
+    }
+}
+
+/// Deep-merge `overlay` into `base`, recording the source of replaced leaves
+fn merge(
+    base: &mut Value,
+    overlay: Value,
+    path: &str,
+    source: &ConfigSource,
+    sources: &mut BTreeMap<String, ConfigSource>,
+) {
+    match (base, overlay) {
+        (Value::Object(base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                let path = join(path, &key);
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value, &path, source, sources),
+                    None => {
+                        record(&value, &path, source, sources);
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => {
+            // Forget the sources of anything the new value replaces
+            let nested = format!("{}.", path);
+            sources.retain(|key, _| key != path && !key.starts_with(&nested));
+            record(&overlay, path, source, sources);
+            *base = overlay;
+        }
+    }
+}
+
+fn record(
+    value: &Value,
+    path: &str,
+    source: &ConfigSource,
+    sources: &mut BTreeMap<String, ConfigSource>,
+) {
+    match value {
+        Value::Object(fields) if !fields.is_empty() => {
+            for (key, value) in fields {
+                record(value, &join(path, key), source, sources);
+            }
+        }
+        _ => {
+            sources.insert(path.to_string(), source.clone());
+        }
+    }
+}
+
+fn join(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+fn env_value(raw: String) -> Value {
+    if raw.starts_with('[') || raw.starts_with('{') {
+        if let Ok(value) = serde_json::from_str(&raw) {
+            return value;
+        }
+    }
+    Value::String(raw)
+}
+
+fn read_file(path: &Path) -> Result<Value, ConfigError> {
+    let content = std::fs::read_to_string(path).map_err(|source| ConfigError::Read {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    let parse_error = |message: String| ConfigError::Parse {
+        path: path.to_path_buf(),
+        message,
+    };
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("toml") => toml::from_str(&content).map_err(|e| parse_error(e.to_string())),
+        Some("json") => serde_json::from_str(&content).map_err(|e| parse_error(e.to_string())),
+        #[cfg(feature = "config-yaml")]
+        Some("yaml" | "yml") => {
+            serde_yaml::from_str(&content).map_err(|e| parse_error(e.to_string()))
+        }
+        _ => Err(ConfigError::UnsupportedFormat(path.to_path_buf())),
+    }
+}
+
+/// Key fragments whose values [`LoadedConfig::report`] masks
+const SENSITIVE_KEYS: &[&str] = &["password", "secret", "token", "key", "credential"];
+
+/// Configuration loaded by a [`ConfigLoader`]
+///
+/// Dereferences to the configuration struct.
+#[derive(Debug, Clone)]
+pub struct LoadedConfig<T> {
+    value: T,
+    merged: Value,
+    sources: BTreeMap<String, ConfigSource>,
+}
+
+impl<T> LoadedConfig<T> {
+    /// Take the configuration struct
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    /// Where the value at a dotted path, such as `server.port`, came from
+    pub fn source(&self, path: &str) -> Option<&ConfigSource> {
+        self.sources.get(path)
+    }
+
+    /// Every configured value's dotted path and source, sorted by path
+    pub fn sources(&self) -> impl Iterator<Item = (&str, &ConfigSource)> {
+        self.sources
+            .iter()
+            .map(|(path, source)| (path.as_str(), source))
+    }
+
+    /// One line per value: `path = value  (source)`
+    ///
+    /// Values whose key looks sensitive (containing `password`, `secret`,
+    /// `token`, `key` or `credential`) are shown as `"***"`.
+    pub fn report(&self) -> String {
+        let mut report = String::new();
+        for (path, source) in &self.sources {
+            let leaf = path.rsplit('.').next().unwrap_or(path).to_lowercase();
+            let value = if SENSITIVE_KEYS.iter().any(|key| leaf.contains(key)) {
+                "\"***\"".to_string()
+            } else {
+                path.split('.')
+                    .try_fold(&self.merged, |value, key| value.get(key))
+                    .map_or_else(String::new, Value::to_string)
+            };
+            report.push_str(&format!("{} = {}  ({})\n", path, value, source));
+        }
+        report
+    }
+}
+
+impl<T> Deref for LoadedConfig<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct AppConfig {
+        name: String,
+        server: Server,
+        database: Database,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Server {
+        host: String,
+        port: u16,
+        tls: bool,
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Database {
+        url: String,
+        password: String,
+        replicas: Vec<String>,
+    }
+
+    fn defaults() -> AppConfig {
+        AppConfig {
+            name: "orders".to_string(),
+            server: Server {
+                host: "127.0.0.1".to_string(),
+                port: 8080,
+                tls: false,
+            },
+            database: Database {
+                url: "sqlite::memory:".to_string(),
+                password: String::new(),
+                replicas: Vec::new(),
+            },
+        }
+    }
+
+    fn env(vars: &[(&str, &str)]) -> Vec<(String, String)> {
+        vars.iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("allframe-config-{}-{}", std::process::id(), name));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_file(dir: &Path, name: &str, content: &str) -> PathBuf {
+        let path = dir.join(name);
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_precedence_and_sources() {
+        let dir = temp_dir("precedence");
+        let file = write_file(
+            &dir,
+            "config.toml",
+            "[server]\nhost = \"0.0.0.0\"\nport = 9000\n\n[database]\nurl = \"postgres://db/orders\"\n",
+        );
+
+        let config: LoadedConfig<AppConfig> = ConfigLoader::new()
+            .with_defaults(&defaults())
+            .with_file(&file)
+            .with_file(dir.join("missing.toml"))
+            .with_env_prefix("APP")
+            .load_with_env(env(&[
+                ("APP_SERVER__PORT", "9090"),
+                ("APP_SERVER__TLS", "true"),
+                ("APP_DATABASE__PASSWORD", "hunter2"),
+                ("APP_DATABASE__REPLICAS", r#"["r1","r2"]"#),
+                ("OTHER_SERVER__PORT", "1"),
+            ]))
+            .unwrap();
+
+        assert_eq!(config.name, "orders");
+        assert_eq!(config.server.host, "0.0.0.0");
+        assert_eq!(config.server.port, 9090);
+        assert!(config.server.tls);
+        assert_eq!(config.database.url, "postgres://db/orders");
+        assert_eq!(config.database.replicas, ["r1", "r2"]);
+
+        assert_eq!(config.source("name"), Some(&ConfigSource::Default));
+        assert_eq!(
+            config.source("server.host"),
+            Some(&ConfigSource::File(file.clone()))
+        );
+        assert_eq!(
+            config.source("server.port"),
+            Some(&ConfigSource::Env("APP_SERVER__PORT".to_string()))
+        );
+
+        let report = config.report();
+        assert!(report.contains("server.host = \"0.0.0.0\"  (file "));
+        assert!(report.contains("server.port = \"9090\"  (env APP_SERVER__PORT)"));
+        assert!(report.contains("database.password = \"***\"  (env APP_DATABASE__PASSWORD)"));
+        assert!(!report.contains("hunter2"));
+    }
+
+    #[test]
+    fn test_errors() {
+        let dir = temp_dir("errors");
+
+        let error = ConfigLoader::new()
+            .with_required_file(dir.join("missing.toml"))
+            .load::<AppConfig>()
+            .unwrap_err();
+        assert!(matches!(error, ConfigError::Read { .. }));
+
+        let file = write_file(&dir, "broken.toml", "[server\n");
+        let error = ConfigLoader::new()
+            .with_file(&file)
+            .load::<AppConfig>()
+            .unwrap_err();
+        assert!(matches!(error, ConfigError::Parse { .. }));
+
+        let file = write_file(&dir, "config.ini", "a=b");
+        let error = ConfigLoader::new()
+            .with_file(&file)
+            .load::<AppConfig>()
+            .unwrap_err();
+        assert!(matches!(error, ConfigError::UnsupportedFormat(_)));
+
+        let error = ConfigLoader::new()
+            .with_defaults(&defaults())
+            .with_env_prefix("APP")
+            .load_with_env::<AppConfig, _>(env(&[("APP_SERVER__PORT", "http")]))
+            .unwrap_err();
+        assert!(matches!(error, ConfigError::Invalid(_)));
+    }
+
+    #[test]
+    fn test_json_file_replaces_nested_values() {
+        let dir = temp_dir("json");
+        let file = write_file(&dir, "config.json", r#"{"database": {"replicas": ["a"]}}"#);
+
+        let config: LoadedConfig<AppConfig> = ConfigLoader::new()
+            .with_defaults(&defaults())
+            .with_file(&file)
+            .load_with_env(Vec::new())
+            .unwrap();
+        assert_eq!(config.database.replicas, ["a"]);
+        assert_eq!(config.source("database.url"), Some(&ConfigSource::Default));
+        assert_eq!(
+            config.source("database.replicas"),
+            Some(&ConfigSource::File(file))
+        );
+        assert_eq!(config.into_inner().server.port, 8080);
+    }
+}
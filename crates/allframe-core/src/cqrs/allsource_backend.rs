@@ -16,6 +16,11 @@ use super::Event;
 
 #[cfg(feature = "cqrs-allsource")]
 /// AllSource Core backend for production event sourcing
+///
+/// Storage errors (e.g. a persistence volume going away) fail the call. Wrap
+/// the backend in a `ResilientBackend` (requires
+/// the `resilience` feature) to retry with backoff, buffer appends while
+/// storage is unavailable, and report its state in health checks.
 #[derive(Clone)]
 pub struct AllSourceBackend<E: Event> {
     store: Arc<allsource_core::EventStore>,
@@ -4,25 +4,120 @@
 //! allowing AllFrame to support multiple storage implementations including
 //! in-memory (for testing/MVP) and AllSource Core (for production).
 
+use std::{collections::VecDeque, sync::Arc};
+
 use async_trait::async_trait;
 
-use super::Event;
+use super::{Event, EventEnvelope, EventMetadata};
 
 /// Backend trait for event storage implementations
+///
+/// # Backend parity
+///
+/// The in-memory and SQLite backends are interchangeable: for the same
+/// sequence of calls they return the same results, so code tested against
+/// [`InMemoryBackend`](super::InMemoryBackend) behaves the same on
+/// `SqliteEventStoreBackend`. Both guarantee that:
+///
+/// - an aggregate exists once it has an event; appending no events
+///   doesn't create it
+/// - [`get_events_after`](Self::get_events_after) and
+///   [`get_events_page`](Self::get_events_page) skip the first `version`
+///   events of the stream, and return nothing past its end
+/// - [`save_snapshot`](Self::save_snapshot) replaces the aggregate's
+///   previous snapshot, and [`get_latest_snapshot`](Self::get_latest_snapshot)
+///   returns it, or an error when there is none
+/// - [`stats`](Self::stats) counts events, aggregates and snapshots the same
+///   way, and `backend_specific` always has a `backend_type` entry
+///
+/// Error messages and other `backend_specific` entries differ, and the
+/// in-memory backend additionally drops an aggregate's snapshot when it
+/// evicts the aggregate.
 #[async_trait]
 pub trait EventStoreBackend<E: Event>: Send + Sync {
     /// Append events to an aggregate's event stream
     async fn append(&self, aggregate_id: &str, events: Vec<E>) -> Result<(), String>;
 
+    /// Append events to several aggregates' streams at once
+    ///
+    /// Backends should write the whole batch atomically. The default
+    /// implementation appends each aggregate in turn.
+    async fn append_batch(&self, batch: Vec<(String, Vec<E>)>) -> Result<(), String> {
+        for (aggregate_id, events) in batch {
+            self.append(&aggregate_id, events).await?;
+        }
+        Ok(())
+    }
+
+    /// Append events with metadata recorded alongside them
+    ///
+    /// The default implementation drops the metadata and calls
+    /// [`append`](Self::append); backends that store metadata override it
+    /// together with [`get_events_with_metadata`](Self::get_events_with_metadata).
+    async fn append_with_metadata(
+        &self,
+        aggregate_id: &str,
+        events: Vec<E>,
+        metadata: EventMetadata,
+    ) -> Result<(), String> {
+        let _ = metadata;
+        self.append(aggregate_id, events).await
+    }
+
     /// Get all events for a specific aggregate
     async fn get_events(&self, aggregate_id: &str) -> Result<Vec<E>, String>;
 
+    /// Get all events for an aggregate with their stored metadata
+    ///
+    /// Events appended without metadata have no causation or correlation
+    /// ID. Returns an error by default, for backends that don't store
+    /// metadata.
+    async fn get_events_with_metadata(
+        &self,
+        aggregate_id: &str,
+    ) -> Result<Vec<EventEnvelope<E>>, String> {
+        let _ = aggregate_id;
+        Err("Event metadata not supported by this backend".to_string())
+    }
+
     /// Get all events from all aggregates (for projection rebuild)
+    ///
+    /// Events are returned in global append order. Unless the backend has
+    /// evicted events, the event at index `i` has global sequence number
+    /// `i + 1`.
     async fn get_all_events(&self) -> Result<Vec<E>, String>;
 
+    /// Get all events with a global sequence number greater than `sequence`
+    ///
+    /// The default implementation skips the first `sequence` events of
+    /// [`get_all_events`](Self::get_all_events); override it when the
+    /// backend can seek directly.
+    async fn get_events_since(&self, sequence: u64) -> Result<Vec<E>, String> {
+        let events = self.get_all_events().await?;
+        Ok(events.into_iter().skip(sequence as usize).collect())
+    }
+
     /// Get events after a specific version (for snapshot optimization)
     async fn get_events_after(&self, aggregate_id: &str, version: u64) -> Result<Vec<E>, String>;
 
+    /// Get up to `limit` of an aggregate's events after version
+    /// `from_version`
+    ///
+    /// An aggregate's version is the number of events in its stream, so
+    /// `from_version` 0 starts at the first event. The default
+    /// implementation reads the rest of the stream with
+    /// [`get_events_after`](Self::get_events_after) and truncates it;
+    /// override it when the backend can read a page directly.
+    async fn get_events_page(
+        &self,
+        aggregate_id: &str,
+        from_version: u64,
+        limit: usize,
+    ) -> Result<EventPage<E>, String> {
+        let events = self.get_events_after(aggregate_id, from_version).await?;
+        Ok(EventPage::from_events(events, from_version, limit))
+    }
+
     /// Save a snapshot (optional, return Ok(()) if not supported)
     async fn save_snapshot(
         &self,
@@ -51,6 +146,96 @@ pub trait EventStoreBackend<E: Event>: Send + Sync {
     }
 }
 
+/// A page of an aggregate's events, from
+/// [`EventStoreBackend::get_events_page`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventPage<E> {
+    /// Events in stream order
+    pub events: Vec<E>,
+    /// The aggregate's version after the last event of the page; pass it as
+    /// `from_version` to read the next page
+    pub version: u64,
+    /// Whether the stream has events after this page
+    pub has_more: bool,
+}
+
+impl<E> EventPage<E> {
+    /// Build a page from the events after `from_version`, keeping the first
+    /// `limit`
+    pub fn from_events(mut events: Vec<E>, from_version: u64, limit: usize) -> Self {
+        let has_more = events.len() > limit;
+        events.truncate(limit);
+        Self {
+            version: from_version + events.len() as u64,
+            events,
+            has_more,
+        }
+    }
+}
+
+/// Reads an aggregate's events a page at a time
+///
+/// Created by [`EventStore::stream_events`](super::EventStore::stream_events)
+/// and `SqliteEventStoreBackend::stream_events`. Only the current page is
+/// held in memory, so long streams can be processed without loading them
+/// whole (with backends that override
+/// [`get_events_page`](EventStoreBackend::get_events_page)).
+pub struct EventStream<E, B> {
+    backend: Arc<B>,
+    aggregate_id: String,
+    page_size: usize,
+    version: u64,
+    page: VecDeque<E>,
+    has_more: bool,
+}
+
+impl<E: Event, B: EventStoreBackend<E>> EventStream<E, B> {
+    /// Stream `aggregate_id`'s events from the start, reading `page_size`
+    /// events per query (minimum 1)
+    pub fn new(backend: Arc<B>, aggregate_id: impl Into<String>, page_size: usize) -> Self {
+        Self {
+            backend,
+            aggregate_id: aggregate_id.into(),
+            page_size: page_size.max(1),
+            version: 0,
+            page: VecDeque::new(),
+            has_more: true,
+        }
+    }
+
+    /// Start after `version` instead of at the first event
+    pub fn from_version(mut self, version: u64) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// The next event, or `None` at the end of the stream
+    ///
+    /// After an error, the failed page is read again on the next call.
+    pub async fn next(&mut self) -> Option<Result<E, String>> {
+        if self.page.is_empty() && self.has_more {
+            match self
+                .backend
+                .get_events_page(&self.aggregate_id, self.version, self.page_size)
+                .await
+            {
+                Ok(page) => {
+                    self.version = page.version;
+                    self.has_more = page.has_more;
+                    self.page = page.events.into();
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        self.page.pop_front().map(Ok)
+    }
+
+    /// The aggregate's version after the events returned so far
+    pub fn version(&self) -> u64 {
+        self.version - self.page.len() as u64
+    }
+}
+
 /// Backend statistics
 #[derive(Debug, Clone, Default)]
 pub struct BackendStats {
@@ -10,11 +10,15 @@ use std::{
 };
 
 use async_trait::async_trait;
-use tokio::sync::RwLock;
+use tokio::sync::{OnceCell, RwLock};
 
-use super::Event;
+use super::{Event, EventStore, EventStoreBackend};
 
 /// Command trait marker
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a command",
+    note = "annotate the type with `#[command]`"
+)]
 pub trait Command: Send + Sync + 'static {}
 
 /// Validation error details
@@ -93,6 +97,12 @@ impl std::fmt::Display for CommandError {
 
 impl std::error::Error for CommandError {}
 
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        CommandError::BusinessLogic(message)
+    }
+}
+
 /// Command handler trait
 #[async_trait]
 pub trait CommandHandler<C: Command, E: Event>: Send + Sync {
@@ -127,10 +137,13 @@ impl<C: Command, E: Event, H: CommandHandler<C, E>> ErasedHandler<E> for Handler
 /// Type alias for handler storage
 type HandlerMap<E> = HashMap<TypeId, Arc<dyn ErasedHandler<E>>>;
 
+/// Events of each idempotent dispatch, set once its command succeeds
+type IdempotencyMap<E> = HashMap<String, Arc<OnceCell<Vec<E>>>>;
+
 /// Command Bus for dispatching commands to handlers
 pub struct CommandBus<E: Event> {
     handlers: Arc<RwLock<HandlerMap<E>>>,
-    idempotency_keys: Arc<RwLock<HashMap<String, Vec<E>>>>,
+    idempotency_keys: Arc<RwLock<IdempotencyMap<E>>>,
 }
 
 impl<E: Event> CommandBus<E> {
@@ -153,7 +166,7 @@ impl<E: Event> CommandBus<E> {
         handlers.insert(type_id, Arc::new(wrapper));
     }
 
-    /// Dispatch a command
+    /// Dispatch a command, returning the events its handler produced
     pub async fn dispatch<C: Command>(&self, command: C) -> CommandResult<E> {
         let type_id = TypeId::of::<C>();
         let handlers = self.handlers.read().await;
@@ -170,30 +183,84 @@ impl<E: Event> CommandBus<E> {
         }
     }
 
+    /// Dispatch a command and append its events to `aggregate_id`'s stream
+    ///
+    /// Returns the appended events. A failed append is reported as
+    /// [`CommandError::Internal`].
+    pub async fn dispatch_and_append<C: Command, B: EventStoreBackend<E>>(
+        &self,
+        command: C,
+        aggregate_id: &str,
+        store: &EventStore<E, B>,
+    ) -> CommandResult<E> {
+        let events = self.dispatch(command).await?;
+        store
+            .append(aggregate_id, events.clone())
+            .await
+            .map_err(CommandError::Internal)?;
+        Ok(events)
+    }
+
     /// Dispatch a command with idempotency key
+    ///
+    /// The first successful dispatch for a key stores its events, and later
+    /// dispatches with the same key return them without running the
+    /// handler. Concurrent dispatches with the same key wait for the first
+    /// one; if it fails, the next one runs the handler again.
     pub async fn dispatch_idempotent<C: Command>(
         &self,
         command: C,
         idempotency_key: String,
     ) -> CommandResult<E> {
-        // Check if already executed
-        {
-            let keys = self.idempotency_keys.read().await;
-            if let Some(events) = keys.get(&idempotency_key) {
-                return Ok(events.clone());
-            }
-        }
+        self.once(idempotency_key, self.dispatch(command)).await
+    }
 
-        // Execute command
-        let events = self.dispatch(command).await?;
+    /// [`dispatch_and_append`](Self::dispatch_and_append) with idempotency
+    /// key
+    ///
+    /// A repeated key returns the original events without appending them
+    /// again.
+    pub async fn dispatch_idempotent_and_append<C: Command, B: EventStoreBackend<E>>(
+        &self,
+        command: C,
+        idempotency_key: String,
+        aggregate_id: &str,
+        store: &EventStore<E, B>,
+    ) -> CommandResult<E> {
+        self.once(
+            idempotency_key,
+            self.dispatch_and_append(command, aggregate_id, store),
+        )
+        .await
+    }
 
-        // Store result
-        {
+    /// Run `dispatch` unless a dispatch with the same key already succeeded
+    async fn once(
+        &self,
+        idempotency_key: String,
+        dispatch: impl std::future::Future<Output = CommandResult<E>>,
+    ) -> CommandResult<E> {
+        let cell = {
             let mut keys = self.idempotency_keys.write().await;
-            keys.insert(idempotency_key, events.clone());
-        }
+            Arc::clone(keys.entry(idempotency_key).or_default())
+        };
+        cell.get_or_try_init(|| dispatch).await.cloned()
+    }
 
-        Ok(events)
+    /// Register every `#[command_handler]` function producing `E`
+    ///
+    /// Handlers are discovered from all linked crates. Returns the number of
+    /// handlers registered.
+    pub async fn auto_register(&self) -> usize {
+        let mut handlers = self.handlers.write().await;
+        let mut count = 0;
+        for registration in inventory::iter::<CommandHandlerRegistration> {
+            if let Ok(entry) = (registration.entry)().downcast::<RegisteredHandler<E>>() {
+                handlers.insert(entry.command, entry.handler);
+                count += 1;
+            }
+        }
+        count
     }
 
     /// Get number of registered handlers
@@ -217,12 +284,47 @@ impl<E: Event> Clone for CommandBus<E> {
     }
 }
 
+/// A handler collected by [`CommandBus::auto_register`]
+struct RegisteredHandler<E: Event> {
+    command: TypeId,
+    handler: Arc<dyn ErasedHandler<E>>,
+}
+
+/// Registration submitted by the `#[command_handler]` macro
+pub struct CommandHandlerRegistration {
+    /// Name of the handler function
+    pub name: &'static str,
+    entry: fn() -> Box<dyn Any + Send>,
+}
+
+impl CommandHandlerRegistration {
+    /// Create a registration from a function building the handler entry
+    pub const fn new(name: &'static str, entry: fn() -> Box<dyn Any + Send>) -> Self {
+        Self { name, entry }
+    }
+
+    /// Type-erase a handler for registration
+    pub fn entry<C: Command, E: Event, H: CommandHandler<C, E> + 'static>(
+        handler: H,
+    ) -> Box<dyn Any + Send> {
+        Box::new(RegisteredHandler::<E> {
+            command: TypeId::of::<C>(),
+            handler: Arc::new(HandlerWrapper {
+                handler: Arc::new(handler),
+                _phantom: std::marker::PhantomData,
+            }),
+        })
+    }
+}
+
+inventory::collect!(CommandHandlerRegistration);
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::cqrs::EventTypeName;
 
-    #[derive(Clone, serde::Serialize, serde::Deserialize)]
+    #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
     enum TestEvent {
         UserCreated { _id: String },
     }
@@ -323,4 +425,108 @@ mod tests {
         // Should be same events (idempotency)
         assert_eq!(result1.len(), result2.len());
     }
+
+    /// Handler that counts its calls and takes a while to run
+    struct SlowHandler {
+        calls: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl CommandHandler<CreateUserCommand, TestEvent> for SlowHandler {
+        async fn handle(&self, command: CreateUserCommand) -> CommandResult<TestEvent> {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            Ok(vec![TestEvent::UserCreated { _id: command.email }])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_idempotent_dispatch_runs_once() {
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let bus = CommandBus::new();
+        bus.register(SlowHandler {
+            calls: calls.clone(),
+        })
+        .await;
+
+        let first = bus.dispatch_idempotent(
+            CreateUserCommand {
+                email: "a@example.com".to_string(),
+            },
+            "key1".to_string(),
+        );
+        let second = bus.dispatch_idempotent(
+            CreateUserCommand {
+                email: "b@example.com".to_string(),
+            },
+            "key1".to_string(),
+        );
+        let (first, second) = tokio::join!(first, second);
+
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(first.unwrap(), second.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_failed_idempotent_dispatch_is_retried() {
+        let bus = CommandBus::new();
+        bus.register(CreateUserHandler).await;
+
+        let result = bus
+            .dispatch_idempotent(
+                CreateUserCommand {
+                    email: "".to_string(),
+                },
+                "key1".to_string(),
+            )
+            .await;
+        assert!(matches!(result, Err(CommandError::Validation(_))));
+
+        let events = bus
+            .dispatch_idempotent(
+                CreateUserCommand {
+                    email: "test@example.com".to_string(),
+                },
+                "key1".to_string(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(events.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_dispatch_and_append() {
+        let store = EventStore::<TestEvent, crate::cqrs::InMemoryBackend<TestEvent>>::new();
+        let bus = CommandBus::new();
+        bus.register(CreateUserHandler).await;
+
+        let events = bus
+            .dispatch_and_append(
+                CreateUserCommand {
+                    email: "test@example.com".to_string(),
+                },
+                "user-123",
+                &store,
+            )
+            .await
+            .unwrap();
+        assert_eq!(store.get_events("user-123").await.unwrap(), events);
+
+        // A repeated key returns the original events without appending them
+        for _ in 0..2 {
+            let events = bus
+                .dispatch_idempotent_and_append(
+                    CreateUserCommand {
+                        email: "test@example.com".to_string(),
+                    },
+                    "key1".to_string(),
+                    "user-456",
+                    &store,
+                )
+                .await
+                .unwrap();
+            assert_eq!(events.len(), 1);
+        }
+        assert_eq!(store.get_events("user-456").await.unwrap().len(), 1);
+    }
 }
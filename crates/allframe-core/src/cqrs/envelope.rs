@@ -0,0 +1,102 @@
+//! Event metadata for auditing and tracing
+//!
+//! Events can be appended with [`EventMetadata`] naming the command that
+//! caused them and the flow they belong to. Backends that support it store
+//! the metadata, an event ID and the append time alongside each event, and
+//! return them as [`EventEnvelope`]s from
+//! [`get_events_with_metadata`](super::EventStoreBackend::get_events_with_metadata).
+
+use std::{collections::HashMap, time::SystemTime};
+
+use serde::{Deserialize, Serialize};
+
+/// Metadata recorded with appended events
+///
+/// ```rust
+/// use allframe_core::cqrs::EventMetadata;
+///
+/// let metadata = EventMetadata::new()
+///     .with_causation_id("cmd-42")
+///     .with_correlation_id("checkout-7")
+///     .with("user_id", "alice");
+/// assert_eq!(metadata.metadata["user_id"], "alice");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EventMetadata {
+    /// ID of the command or event that caused these events
+    pub causation_id: Option<String>,
+    /// ID shared by every event of one flow, across aggregates and services
+    pub correlation_id: Option<String>,
+    /// Application-defined entries, such as the acting user
+    pub metadata: HashMap<String, String>,
+}
+
+impl EventMetadata {
+    /// Empty metadata
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the causation ID
+    pub fn with_causation_id(mut self, id: impl Into<String>) -> Self {
+        self.causation_id = Some(id.into());
+        self
+    }
+
+    /// Set the correlation ID
+    pub fn with_correlation_id(mut self, id: impl Into<String>) -> Self {
+        self.correlation_id = Some(id.into());
+        self
+    }
+
+    /// Add an application-defined entry
+    pub fn with(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+}
+
+/// A stored event with its metadata
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventEnvelope<E> {
+    /// The event
+    pub event: E,
+    /// Store-assigned event ID, unique within the store
+    pub event_id: String,
+    /// Aggregate the event belongs to
+    pub aggregate_id: String,
+    /// The aggregate's version after this event (the first event is 1)
+    pub version: u64,
+    /// When the event was appended
+    pub timestamp: SystemTime,
+    /// ID of the command or event that caused this event
+    pub causation_id: Option<String>,
+    /// ID shared by every event of one flow
+    pub correlation_id: Option<String>,
+    /// Application-defined entries
+    pub metadata: HashMap<String, String>,
+}
+
+impl<E> EventEnvelope<E> {
+    /// Wrap an event with the metadata it was appended with
+    pub fn new(
+        event: E,
+        event_id: impl Into<String>,
+        aggregate_id: impl Into<String>,
+        version: u64,
+        timestamp: SystemTime,
+        metadata: EventMetadata,
+    ) -> Self {
+        Self {
+            event,
+            event_id: event_id.into(),
+            aggregate_id: aggregate_id.into(),
+            version,
+            timestamp,
+            causation_id: metadata.causation_id,
+            correlation_id: metadata.correlation_id,
+            metadata: metadata.metadata,
+        }
+    }
+}
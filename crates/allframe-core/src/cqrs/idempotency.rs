@@ -0,0 +1,261 @@
+//! Idempotency registry for deduplicating message and command delivery
+//!
+//! A lightweight alternative to AllSource's `ExactlyOnceRegistry` that
+//! doesn't need the `cqrs-allsource` feature. Consumers call
+//! [`IdempotencyRegistry::seen`] with a message or command id before handling
+//! it, and skip it if it was already seen within the TTL.
+//!
+//! Backends:
+//! - [`InMemoryIdempotencyRegistry`] - per-process, lost on restart
+//! - `SqliteIdempotencyRegistry` - persistent (requires `cqrs-sqlite` feature)
+//!
+//! ```rust
+//! use std::time::Duration;
+//!
+//! use allframe_core::cqrs::{IdempotencyRegistry, InMemoryIdempotencyRegistry};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), String> {
+//! let registry = InMemoryIdempotencyRegistry::new(Duration::from_secs(3600));
+//!
+//! assert!(!registry.seen("order-42").await?); // first delivery: handle it
+//! assert!(registry.seen("order-42").await?); // redelivery: skip it
+//! # Ok(())
+//! # }
+//! ```
+
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+
+/// Registry of recently processed ids
+#[async_trait]
+pub trait IdempotencyRegistry: Send + Sync {
+    /// Record `id`, returning whether it was already seen within the TTL
+    ///
+    /// Checking and recording is atomic, so when the same id is delivered
+    /// concurrently exactly one caller gets `false`. Ids older than the TTL
+    /// count as unseen and are recorded again.
+    async fn seen(&self, id: &str) -> Result<bool, String>;
+
+    /// Remove expired ids, returning how many were removed
+    async fn evict_expired(&self) -> Result<usize, String>;
+}
+
+/// In-memory idempotency registry
+///
+/// Expired ids are evicted on [`evict_expired`](IdempotencyRegistry::evict_expired)
+/// and, to bound memory, periodically as new ids are recorded.
+pub struct InMemoryIdempotencyRegistry {
+    ttl: Duration,
+    entries: Mutex<Entries>,
+}
+
+struct Entries {
+    /// When each id was recorded
+    ids: HashMap<String, Instant>,
+    /// Entry count that triggers the next eviction sweep
+    sweep_at: usize,
+}
+
+/// Entry count below which the in-memory registry doesn't sweep
+const MIN_SWEEP_SIZE: usize = 1024;
+
+impl InMemoryIdempotencyRegistry {
+    /// Create a registry remembering ids for `ttl`
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            entries: Mutex::new(Entries {
+                ids: HashMap::new(),
+                sweep_at: MIN_SWEEP_SIZE,
+            }),
+        }
+    }
+
+    /// Number of ids currently remembered (including expired ones not yet
+    /// evicted)
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().ids.len()
+    }
+
+    /// Whether no ids are remembered
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn evict(&self, ids: &mut HashMap<String, Instant>, now: Instant) -> usize {
+        let before = ids.len();
+        ids.retain(|_, seen_at| now.duration_since(*seen_at) < self.ttl);
+        before - ids.len()
+    }
+}
+
+#[async_trait]
+impl IdempotencyRegistry for InMemoryIdempotencyRegistry {
+    async fn seen(&self, id: &str) -> Result<bool, String> {
+        let now = Instant::now();
+        let mut entries = self.entries.lock().unwrap();
+        let entries = &mut *entries;
+
+        if let Some(seen_at) = entries.ids.get_mut(id) {
+            if now.duration_since(*seen_at) < self.ttl {
+                return Ok(true);
+            }
+            *seen_at = now;
+            return Ok(false);
+        }
+
+        entries.ids.insert(id.to_string(), now);
+
+        // Sweep when the map has doubled since the last sweep
+        if entries.ids.len() >= entries.sweep_at {
+            self.evict(&mut entries.ids, now);
+            entries.sweep_at = (entries.ids.len() * 2).max(MIN_SWEEP_SIZE);
+        }
+        Ok(false)
+    }
+
+    async fn evict_expired(&self) -> Result<usize, String> {
+        let mut entries = self.entries.lock().unwrap();
+        Ok(self.evict(&mut entries.ids, Instant::now()))
+    }
+}
+
+#[cfg(feature = "cqrs-sqlite")]
+pub use sqlite::SqliteIdempotencyRegistry;
+
+#[cfg(feature = "cqrs-sqlite")]
+mod sqlite {
+    use std::{
+        sync::{Arc, Mutex},
+        time::{Duration, SystemTime, UNIX_EPOCH},
+    };
+
+    use async_trait::async_trait;
+    use rusqlite::Connection;
+
+    use super::IdempotencyRegistry;
+
+    /// SQLite-backed idempotency registry
+    ///
+    /// Remembers ids across restarts, so redeliveries after a crash are still
+    /// detected. All operations run via `spawn_blocking`.
+    #[derive(Clone)]
+    pub struct SqliteIdempotencyRegistry {
+        conn: Arc<Mutex<Connection>>,
+        ttl: Duration,
+    }
+
+    impl SqliteIdempotencyRegistry {
+        /// Open (or create) a registry at the given path, remembering ids for
+        /// `ttl`
+        pub async fn new(path: &str, ttl: Duration) -> Result<Self, String> {
+            let path = path.to_string();
+            let conn = tokio::task::spawn_blocking(move || {
+                let conn = Connection::open(&path).map_err(|e| format!("SQLite open: {}", e))?;
+                conn.execute_batch(
+                    "PRAGMA journal_mode=WAL;
+                    CREATE TABLE IF NOT EXISTS idempotency_keys (
+                        id TEXT PRIMARY KEY,
+                        seen_at INTEGER NOT NULL
+                    );
+                    CREATE INDEX IF NOT EXISTS idx_idempotency_seen_at
+                        ON idempotency_keys(seen_at);",
+                )
+                .map_err(|e| format!("Schema init: {}", e))?;
+                Ok::<_, String>(conn)
+            })
+            .await
+            .map_err(|e| format!("spawn_blocking: {}", e))??;
+
+            Ok(Self {
+                conn: Arc::new(Mutex::new(conn)),
+                ttl,
+            })
+        }
+
+        /// (now, cutoff) in unix milliseconds
+        fn window(&self) -> (i64, i64) {
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as i64;
+            (now, now - self.ttl.as_millis() as i64)
+        }
+    }
+
+    #[async_trait]
+    impl IdempotencyRegistry for SqliteIdempotencyRegistry {
+        async fn seen(&self, id: &str) -> Result<bool, String> {
+            let conn = Arc::clone(&self.conn);
+            let id = id.to_string();
+            let (now, cutoff) = self.window();
+            tokio::task::spawn_blocking(move || {
+                let conn = conn.lock().map_err(|e| format!("Lock: {}", e))?;
+                // Inserts new ids and refreshes expired ones; a live id is
+                // left untouched, so no row changes means it was seen
+                let changed = conn
+                    .execute(
+                        "INSERT INTO idempotency_keys (id, seen_at) VALUES (?1, ?2)
+                         ON CONFLICT(id) DO UPDATE SET seen_at = excluded.seen_at
+                         WHERE idempotency_keys.seen_at <= ?3",
+                        rusqlite::params![id, now, cutoff],
+                    )
+                    .map_err(|e| format!("Upsert: {}", e))?;
+                Ok(changed == 0)
+            })
+            .await
+            .map_err(|e| format!("spawn_blocking: {}", e))?
+        }
+
+        async fn evict_expired(&self) -> Result<usize, String> {
+            let conn = Arc::clone(&self.conn);
+            let (_, cutoff) = self.window();
+            tokio::task::spawn_blocking(move || {
+                let conn = conn.lock().map_err(|e| format!("Lock: {}", e))?;
+                conn.execute(
+                    "DELETE FROM idempotency_keys WHERE seen_at <= ?1",
+                    rusqlite::params![cutoff],
+                )
+                .map_err(|e| format!("Delete: {}", e))
+            })
+            .await
+            .map_err(|e| format!("spawn_blocking: {}", e))?
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_seen_deduplicates() {
+        let registry = InMemoryIdempotencyRegistry::new(Duration::from_secs(60));
+
+        assert!(!registry.seen("a").await.unwrap());
+        assert!(registry.seen("a").await.unwrap());
+        assert!(!registry.seen("b").await.unwrap());
+        assert_eq!(registry.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_expired_ids_are_unseen_and_evicted() {
+        let registry = InMemoryIdempotencyRegistry::new(Duration::from_millis(20));
+
+        assert!(!registry.seen("a").await.unwrap());
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert!(!registry.seen("a").await.unwrap());
+        assert!(registry.seen("a").await.unwrap());
+
+        assert!(!registry.seen("b").await.unwrap());
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        assert_eq!(registry.evict_expired().await.unwrap(), 2);
+        assert!(registry.is_empty());
+    }
+}
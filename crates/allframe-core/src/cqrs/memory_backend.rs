@@ -2,33 +2,276 @@
 //!
 //! This is the default backend for AllFrame CQRS, providing a simple
 //! HashMap-based storage suitable for testing, development, and MVPs.
+//!
+//! The backend is unbounded by default. Long-running processes can cap it
+//! with [`InMemoryBackend::with_max_events`] and
+//! [`InMemoryBackend::with_max_aggregates`], which evict the oldest
+//! aggregates, and flush evicted events elsewhere with
+//! [`InMemoryBackend::with_on_evict`].
+//!
+//! Within its limits it behaves like the SQLite backend, snapshots
+//! included (see the parity contract on
+//! [`EventStoreBackend`](super::EventStoreBackend)), so tests can run
+//! in-memory against code that uses SQLite in production.
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+    time::SystemTime,
+};
 
 use async_trait::async_trait;
 use tokio::sync::RwLock;
 
 use super::{
-    backend::{BackendStats, EventStoreBackend},
-    Event,
+    backend::{BackendStats, EventPage, EventStoreBackend},
+    Event, EventEnvelope, EventMetadata,
 };
 
 /// Type alias for snapshot storage (snapshot data + version)
 type SnapshotMap = HashMap<String, (Vec<u8>, u64)>;
 
+/// Callback receiving an evicted aggregate's id and events
+pub type EvictionHook<E> = Arc<dyn Fn(&str, Vec<E>) + Send + Sync>;
+
+/// A stored event with its global sequence number and metadata
+struct Entry<E> {
+    sequence: u64,
+    event: E,
+    timestamp: SystemTime,
+    metadata: Arc<EventMetadata>,
+}
+
+/// Events by aggregate, plus the global append order
+struct EventLog<E> {
+    /// Each aggregate's events, in order
+    streams: HashMap<String, Vec<Entry<E>>>,
+    /// (aggregate id, index in its stream) of every event, by sequence
+    order: BTreeMap<u64, (String, usize)>,
+    /// Aggregate ids by the sequence of their first event, oldest first
+    by_age: BTreeMap<u64, String>,
+    next_sequence: u64,
+    evicted_aggregates: u64,
+}
+
+impl<E: Clone> EventLog<E> {
+    fn new() -> Self {
+        Self {
+            streams: HashMap::new(),
+            order: BTreeMap::new(),
+            by_age: BTreeMap::new(),
+            next_sequence: 1,
+            evicted_aggregates: 0,
+        }
+    }
+
+    fn append(&mut self, aggregate_id: &str, events: Vec<E>, metadata: Arc<EventMetadata>) {
+        // An empty append doesn't create the aggregate
+        if events.is_empty() {
+            return;
+        }
+        let timestamp = SystemTime::now();
+        let stream = self.streams.entry(aggregate_id.to_string()).or_default();
+        for event in events {
+            let sequence = self.next_sequence;
+            self.next_sequence += 1;
+            if stream.is_empty() {
+                self.by_age.insert(sequence, aggregate_id.to_string());
+            }
+            self.order
+                .insert(sequence, (aggregate_id.to_string(), stream.len()));
+            stream.push(Entry {
+                sequence,
+                event,
+                timestamp,
+                metadata: Arc::clone(&metadata),
+            });
+        }
+    }
+
+    fn events(&self, aggregate_id: &str) -> Vec<E> {
+        self.streams
+            .get(aggregate_id)
+            .map(|stream| stream.iter().map(|entry| entry.event.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    fn envelopes(&self, aggregate_id: &str) -> Vec<EventEnvelope<E>> {
+        let Some(stream) = self.streams.get(aggregate_id) else {
+            return Vec::new();
+        };
+        stream
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| {
+                EventEnvelope::new(
+                    entry.event.clone(),
+                    entry.sequence.to_string(),
+                    aggregate_id,
+                    index as u64 + 1,
+                    entry.timestamp,
+                    (*entry.metadata).clone(),
+                )
+            })
+            .collect()
+    }
+
+    fn events_page(&self, aggregate_id: &str, from_version: u64, limit: usize) -> EventPage<E> {
+        let rest = self
+            .streams
+            .get(aggregate_id)
+            .and_then(|stream| stream.get(from_version as usize..))
+            .unwrap_or_default();
+        let page = &rest[..rest.len().min(limit)];
+        EventPage {
+            events: page.iter().map(|entry| entry.event.clone()).collect(),
+            version: from_version + page.len() as u64,
+            has_more: rest.len() > limit,
+        }
+    }
+
+    fn events_since(&self, sequence: u64) -> Vec<E> {
+        self.order
+            .range(sequence + 1..)
+            .map(|(_, (aggregate_id, index))| self.streams[aggregate_id][*index].event.clone())
+            .collect()
+    }
+
+    /// Evict the oldest aggregates until the log is within the limits
+    ///
+    /// Aggregates in `keep` (the ones just appended to) are never evicted,
+    /// so a single aggregate may exceed `max_events` on its own.
+    fn evict(
+        &mut self,
+        max_events: Option<usize>,
+        max_aggregates: Option<usize>,
+        keep: &[&str],
+    ) -> Vec<(String, Vec<E>)> {
+        let mut evicted = Vec::new();
+        while max_events.is_some_and(|max| self.order.len() > max)
+            || max_aggregates.is_some_and(|max| self.streams.len() > max)
+        {
+            let Some(aggregate_id) = self
+                .by_age
+                .values()
+                .find(|id| !keep.contains(&id.as_str()))
+                .cloned()
+            else {
+                break;
+            };
+            let stream = self.streams.remove(&aggregate_id).unwrap_or_default();
+            if let Some(first) = stream.first() {
+                self.by_age.remove(&first.sequence);
+            }
+            for entry in &stream {
+                self.order.remove(&entry.sequence);
+            }
+            self.evicted_aggregates += 1;
+            evicted.push((
+                aggregate_id,
+                stream.into_iter().map(|entry| entry.event).collect(),
+            ));
+        }
+        evicted
+    }
+}
+
 /// In-memory event store backend
+///
+/// Unbounded unless limits are set. When an append takes the backend over
+/// a limit, whole aggregates are evicted, oldest (by first event) first,
+/// along with their snapshots. The aggregates being appended to are never
+/// evicted. Global sequence numbers of the remaining events don't change,
+/// so [`get_events_since`](EventStoreBackend::get_events_since) keeps
+/// working across evictions.
+///
+/// ```rust
+/// use allframe_core::cqrs::InMemoryBackend;
+/// # use allframe_core::cqrs::{Event, EventTypeName};
+/// # #[derive(Clone, serde::Serialize, serde::Deserialize)]
+/// # struct OrderPlaced;
+/// # impl EventTypeName for OrderPlaced {}
+/// # impl Event for OrderPlaced {}
+///
+/// let backend = InMemoryBackend::<OrderPlaced>::new()
+///     .with_max_events(100_000)
+///     .with_max_aggregates(1_000)
+///     .with_on_evict(|aggregate_id, events| {
+///         println!("evicted {} ({} events)", aggregate_id, events.len());
+///     });
+/// ```
 #[derive(Clone)]
 pub struct InMemoryBackend<E: Event> {
-    events: Arc<RwLock<HashMap<String, Vec<E>>>>,
+    events: Arc<RwLock<EventLog<E>>>,
     snapshots: Arc<RwLock<SnapshotMap>>,
+    max_events: Option<usize>,
+    max_aggregates: Option<usize>,
+    on_evict: Option<EvictionHook<E>>,
 }
 
 impl<E: Event> InMemoryBackend<E> {
     /// Create a new in-memory backend
     pub fn new() -> Self {
         Self {
-            events: Arc::new(RwLock::new(HashMap::new())),
+            events: Arc::new(RwLock::new(EventLog::new())),
             snapshots: Arc::new(RwLock::new(HashMap::new())),
+            max_events: None,
+            max_aggregates: None,
+            on_evict: None,
+        }
+    }
+
+    /// Limit the total number of stored events
+    pub fn with_max_events(mut self, max: usize) -> Self {
+        self.max_events = Some(max);
+        self
+    }
+
+    /// Limit the number of stored aggregates
+    pub fn with_max_aggregates(mut self, max: usize) -> Self {
+        self.max_aggregates = Some(max);
+        self
+    }
+
+    /// Call `hook` with each evicted aggregate's id and events
+    ///
+    /// The hook runs after the append that caused the eviction, outside the
+    /// backend's locks, so it may read from the backend. To flush events to
+    /// a durable backend, send them to a task (e.g. over a channel) rather
+    /// than blocking in the hook.
+    pub fn with_on_evict<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&str, Vec<E>) + Send + Sync + 'static,
+    {
+        self.on_evict = Some(Arc::new(hook));
+        self
+    }
+
+    /// Append under one lock, then apply the limits
+    async fn append_all(&self, batch: Vec<(String, Vec<E>)>, metadata: EventMetadata) {
+        let metadata = Arc::new(metadata);
+        let mut store = self.events.write().await;
+        let keep: Vec<String> = batch.iter().map(|(id, _)| id.clone()).collect();
+        for (aggregate_id, events) in batch {
+            store.append(&aggregate_id, events, Arc::clone(&metadata));
+        }
+        let keep: Vec<&str> = keep.iter().map(String::as_str).collect();
+        let evicted = store.evict(self.max_events, self.max_aggregates, &keep);
+        drop(store);
+
+        if evicted.is_empty() {
+            return;
+        }
+        let mut snapshots = self.snapshots.write().await;
+        for (aggregate_id, _) in &evicted {
+            snapshots.remove(aggregate_id);
+        }
+        drop(snapshots);
+
+        if let Some(hook) = &self.on_evict {
+            for (aggregate_id, events) in evicted {
+                hook(&aggregate_id, events);
+            }
         }
     }
 }
@@ -42,31 +285,69 @@ impl<E: Event> Default for InMemoryBackend<E> {
 #[async_trait]
 impl<E: Event> EventStoreBackend<E> for InMemoryBackend<E> {
     async fn append(&self, aggregate_id: &str, events: Vec<E>) -> Result<(), String> {
-        let mut store = self.events.write().await;
-        let stream = store
-            .entry(aggregate_id.to_string())
-            .or_insert_with(Vec::new);
-        stream.extend(events);
+        self.append_all(
+            vec![(aggregate_id.to_string(), events)],
+            EventMetadata::default(),
+        )
+        .await;
+        Ok(())
+    }
+
+    async fn append_with_metadata(
+        &self,
+        aggregate_id: &str,
+        events: Vec<E>,
+        metadata: EventMetadata,
+    ) -> Result<(), String> {
+        self.append_all(vec![(aggregate_id.to_string(), events)], metadata)
+            .await;
+        Ok(())
+    }
+
+    async fn append_batch(&self, batch: Vec<(String, Vec<E>)>) -> Result<(), String> {
+        self.append_all(batch, EventMetadata::default()).await;
         Ok(())
     }
 
     async fn get_events(&self, aggregate_id: &str) -> Result<Vec<E>, String> {
-        let store = self.events.read().await;
-        Ok(store.get(aggregate_id).cloned().unwrap_or_default())
+        Ok(self.events.read().await.events(aggregate_id))
+    }
+
+    async fn get_events_with_metadata(
+        &self,
+        aggregate_id: &str,
+    ) -> Result<Vec<EventEnvelope<E>>, String> {
+        Ok(self.events.read().await.envelopes(aggregate_id))
     }
 
     async fn get_all_events(&self) -> Result<Vec<E>, String> {
-        let store = self.events.read().await;
-        let mut all_events = Vec::new();
-        for events in store.values() {
-            all_events.extend(events.clone());
-        }
-        Ok(all_events)
+        Ok(self.events.read().await.events_since(0))
+    }
+
+    async fn get_events_since(&self, sequence: u64) -> Result<Vec<E>, String> {
+        Ok(self.events.read().await.events_since(sequence))
     }
 
     async fn get_events_after(&self, aggregate_id: &str, version: u64) -> Result<Vec<E>, String> {
-        let events = self.get_events(aggregate_id).await?;
-        Ok(events.into_iter().skip(version as usize).collect())
+        Ok(self
+            .events
+            .read()
+            .await
+            .events_page(aggregate_id, version, usize::MAX)
+            .events)
+    }
+
+    async fn get_events_page(
+        &self,
+        aggregate_id: &str,
+        from_version: u64,
+        limit: usize,
+    ) -> Result<EventPage<E>, String> {
+        Ok(self
+            .events
+            .read()
+            .await
+            .events_page(aggregate_id, from_version, limit))
     }
 
     async fn save_snapshot(
@@ -92,12 +373,16 @@ impl<E: Event> EventStoreBackend<E> for InMemoryBackend<E> {
         let store = self.events.read().await;
         let snapshots = self.snapshots.read().await;
 
-        let total_events: u64 = store.values().map(|v| v.len() as u64).sum();
-        let total_aggregates = store.len() as u64;
+        let total_events = store.order.len() as u64;
+        let total_aggregates = store.streams.len() as u64;
         let total_snapshots = snapshots.len() as u64;
 
         let mut backend_specific = HashMap::new();
         backend_specific.insert("backend_type".to_string(), "in-memory".to_string());
+        backend_specific.insert(
+            "evicted_aggregates".to_string(),
+            store.evicted_aggregates.to_string(),
+        );
 
         BackendStats {
             total_events,
@@ -7,14 +7,21 @@
 pub mod allsource_backend;
 pub mod backend;
 pub mod command_bus;
+pub mod envelope;
 pub mod event_versioning;
+pub mod idempotency;
 pub mod query_bus;
 pub mod memory_backend;
 pub mod projection_registry;
+#[cfg(feature = "resilience")]
+pub mod resilient_backend;
 pub mod saga;
 pub mod saga_orchestrator;
+pub mod saga_store;
+pub mod snapshot_policy;
 pub mod sqlite_backend;
 pub mod sync;
+pub mod testing;
 
 /// Trait for resolving the event type name used in AllSource storage.
 ///
@@ -55,6 +62,25 @@ pub trait Projection: Send + Sync {
     fn apply(&mut self, event: &Self::Event);
 }
 
+/// Position in the global event log to start a subscription from
+///
+/// Events are numbered by a global sequence, starting at 1 for the first
+/// event appended to the store.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Start {
+    /// Replay every stored event
+    Beginning,
+    /// Replay stored events from this global sequence number (inclusive)
+    Sequence(u64),
+}
+
+/// Events read per query by [`EventStore::load_aggregate`]
+const AGGREGATE_PAGE_SIZE: usize = 1000;
+
+/// Snapshot policies by aggregate type
+type SnapshotConfigs<E, B> =
+    std::collections::HashMap<std::any::TypeId, snapshot_policy::SnapshotEntry<E, B>>;
+
 /// Event Store - append-only log of domain events
 ///
 /// The EventStore uses a pluggable backend architecture:
@@ -64,6 +90,11 @@ pub trait Projection: Send + Sync {
 pub struct EventStore<E: Event, B: EventStoreBackend<E> = InMemoryBackend<E>> {
     backend: std::sync::Arc<B>,
     subscribers: std::sync::Arc<tokio::sync::RwLock<Vec<tokio::sync::mpsc::Sender<E>>>>,
+    snapshot_configs: std::sync::Arc<SnapshotConfigs<E, B>>,
+    /// Type of each aggregate loaded with a snapshot policy, so appends to
+    /// it can consult that policy
+    snapshot_types:
+        std::sync::Arc<std::sync::RwLock<std::collections::HashMap<String, std::any::TypeId>>>,
     _phantom: std::marker::PhantomData<E>,
 }
 
@@ -86,27 +117,134 @@ impl<E: Event, B: EventStoreBackend<E>> EventStore<E, B> {
         Self {
             backend: std::sync::Arc::new(backend),
             subscribers: std::sync::Arc::new(tokio::sync::RwLock::new(Vec::new())),
+            snapshot_configs: std::sync::Arc::default(),
+            snapshot_types: std::sync::Arc::default(),
             _phantom: std::marker::PhantomData,
         }
     }
 
+    /// Snapshot aggregates of type `A` according to `policy`
+    ///
+    /// [`load_aggregate`](Self::load_aggregate) then starts from the latest
+    /// snapshot of an `A` and saves a new one when the policy says it is
+    /// due. Once an aggregate has been loaded as an `A`,
+    /// [`append`](Self::append) and [`append_batch`](Self::append_batch)
+    /// check the policy for it too. Snapshots are stored as JSON through
+    /// the backend's [`save_snapshot`](EventStoreBackend::save_snapshot);
+    /// backends without snapshot support keep replaying every event.
+    ///
+    /// Snapshots are keyed by aggregate id, like event streams, so ids must
+    /// be unique across aggregate types.
+    pub fn with_snapshot_policy<A>(mut self, policy: SnapshotPolicy) -> Self
+    where
+        A: Aggregate<Event = E> + serde::Serialize + serde::de::DeserializeOwned + 'static,
+    {
+        std::sync::Arc::make_mut(&mut self.snapshot_configs).insert(
+            std::any::TypeId::of::<A>(),
+            snapshot_policy::SnapshotEntry::new::<A>(policy),
+        );
+        self
+    }
+
     /// Get a reference to the backend
     pub fn backend(&self) -> &B {
         &self.backend
     }
 
     /// Append events to an aggregate's event stream
+    ///
+    /// Subscribers are notified after the backend write succeeds; a failed
+    /// write notifies no one.
     pub async fn append(&self, aggregate_id: &str, events: Vec<E>) -> Result<(), String> {
-        // Notify subscribers before appending
         let subscribers = self.subscribers.read().await;
-        for event in &events {
-            for subscriber in subscribers.iter() {
+        let notified = if subscribers.is_empty() {
+            Vec::new()
+        } else {
+            events.clone()
+        };
+
+        self.backend.append(aggregate_id, events).await?;
+
+        Self::notify(&subscribers, notified).await;
+        drop(subscribers);
+        self.snapshot_if_due(aggregate_id).await;
+        Ok(())
+    }
+
+    /// Append events with metadata, such as the causing command's ID
+    ///
+    /// Subscribers are notified as with [`append`](Self::append). Backends
+    /// that don't store metadata drop it.
+    pub async fn append_with_metadata(
+        &self,
+        aggregate_id: &str,
+        events: Vec<E>,
+        metadata: EventMetadata,
+    ) -> Result<(), String> {
+        let subscribers = self.subscribers.read().await;
+        let notified = if subscribers.is_empty() {
+            Vec::new()
+        } else {
+            events.clone()
+        };
+
+        self.backend
+            .append_with_metadata(aggregate_id, events, metadata)
+            .await?;
+
+        Self::notify(&subscribers, notified).await;
+        Ok(())
+    }
+
+    /// Append events to several aggregates in one backend write
+    ///
+    /// The batch is written atomically when the backend supports it (the
+    /// in-memory and SQLite backends do). Subscribers are notified once per
+    /// event, in batch order, after the write succeeds.
+    pub async fn append_batch(&self, batch: Vec<(String, Vec<E>)>) -> Result<(), String> {
+        let subscribers = self.subscribers.read().await;
+        let events: Vec<E> = if subscribers.is_empty() {
+            Vec::new()
+        } else {
+            batch
+                .iter()
+                .flat_map(|(_, events)| events.clone())
+                .collect()
+        };
+        let aggregate_ids: Vec<String> = batch.iter().map(|(id, _)| id.clone()).collect();
+
+        self.backend.append_batch(batch).await?;
+
+        Self::notify(&subscribers, events).await;
+        drop(subscribers);
+        for aggregate_id in &aggregate_ids {
+            self.snapshot_if_due(aggregate_id).await;
+        }
+        Ok(())
+    }
+
+    /// Save a snapshot of an aggregate if its type's policy says it is due
+    ///
+    /// Only aggregates already loaded with a policy are known to the store.
+    async fn snapshot_if_due(&self, aggregate_id: &str) {
+        let type_id = self
+            .snapshot_types
+            .read()
+            .unwrap()
+            .get(aggregate_id)
+            .copied();
+        if let Some(entry) = type_id.and_then(|type_id| self.snapshot_configs.get(&type_id)) {
+            (entry.after_append)(self, aggregate_id).await;
+        }
+    }
+
+    /// Send each event to every subscriber, in order
+    async fn notify(subscribers: &[tokio::sync::mpsc::Sender<E>], events: Vec<E>) {
+        for event in events {
+            for subscriber in subscribers {
                 let _ = subscriber.send(event.clone()).await;
             }
         }
-
-        // Delegate to backend
-        self.backend.append(aggregate_id, events).await
     }
 
     /// Get all events for an aggregate
@@ -114,6 +252,16 @@ impl<E: Event, B: EventStoreBackend<E>> EventStore<E, B> {
         self.backend.get_events(aggregate_id).await
     }
 
+    /// Get all events for an aggregate with their stored metadata
+    ///
+    /// Supported by the in-memory and SQLite backends.
+    pub async fn get_events_with_metadata(
+        &self,
+        aggregate_id: &str,
+    ) -> Result<Vec<EventEnvelope<E>>, String> {
+        self.backend.get_events_with_metadata(aggregate_id).await
+    }
+
     /// Get events after a specific version for an aggregate
     pub async fn get_events_after(
         &self,
@@ -123,6 +271,107 @@ impl<E: Event, B: EventStoreBackend<E>> EventStore<E, B> {
         self.backend.get_events_after(aggregate_id, version).await
     }
 
+    /// Get up to `limit` of an aggregate's events after `from_version`
+    ///
+    /// The page says whether more events follow; pass its
+    /// [`version`](EventPage::version) as `from_version` to read the next
+    /// one.
+    pub async fn get_events_page(
+        &self,
+        aggregate_id: &str,
+        from_version: u64,
+        limit: usize,
+    ) -> Result<EventPage<E>, String> {
+        self.backend
+            .get_events_page(aggregate_id, from_version, limit)
+            .await
+    }
+
+    /// Read an aggregate's events a page of `page_size` at a time
+    pub fn stream_events(&self, aggregate_id: &str, page_size: usize) -> EventStream<E, B> {
+        EventStream::new(self.backend.clone(), aggregate_id, page_size)
+    }
+
+    /// Rebuild an aggregate from its events, returning it with its version
+    ///
+    /// Events are read in pages, so only one page of the stream is held in
+    /// memory at a time. With a [`SnapshotPolicy`] registered for `A`, the
+    /// aggregate starts from its latest snapshot, and a new snapshot is
+    /// saved when the policy says it is due. Failing to save the snapshot
+    /// doesn't fail the load; the next load or append tries again.
+    pub async fn load_aggregate<A: Aggregate<Event = E> + 'static>(
+        &self,
+        aggregate_id: &str,
+    ) -> Result<(A, u64), String> {
+        let config = self
+            .snapshot_configs
+            .get(&std::any::TypeId::of::<A>())
+            .and_then(|entry| {
+                entry
+                    .config
+                    .downcast_ref::<snapshot_policy::SnapshotConfig<A>>()
+            })
+            .filter(|config| config.policy != SnapshotPolicy::Disabled);
+        let Some(config) = config else {
+            let (aggregate, version, _) = self.replay(aggregate_id, A::default(), 0).await?;
+            return Ok((aggregate, version));
+        };
+        if !self
+            .snapshot_types
+            .read()
+            .unwrap()
+            .contains_key(aggregate_id)
+        {
+            self.snapshot_types
+                .write()
+                .unwrap()
+                .insert(aggregate_id.to_string(), std::any::TypeId::of::<A>());
+        }
+
+        // A missing or unreadable snapshot means replaying every event
+        let snapshot = match self.backend.get_latest_snapshot(aggregate_id).await {
+            Ok((data, version)) => config
+                .decode(&data)
+                .map(|(aggregate, taken_at)| (aggregate, version, Some(taken_at))),
+            Err(_) => None,
+        };
+        let (aggregate, from_version, taken_at) =
+            snapshot.unwrap_or_else(|| (A::default(), 0, None));
+
+        let (aggregate, version, replayed) =
+            self.replay(aggregate_id, aggregate, from_version).await?;
+        if config.policy.is_due(replayed, taken_at) {
+            // The aggregate was rebuilt either way; a failed snapshot only
+            // means the next load replays more events
+            if let Ok(data) = config.encode(&aggregate) {
+                let _ = self
+                    .backend
+                    .save_snapshot(aggregate_id, data, version)
+                    .await;
+            }
+        }
+        Ok((aggregate, version))
+    }
+
+    /// Apply an aggregate's events after `from_version`, returning it with
+    /// its version and the number of events applied
+    async fn replay<A: Aggregate<Event = E>>(
+        &self,
+        aggregate_id: &str,
+        mut aggregate: A,
+        from_version: u64,
+    ) -> Result<(A, u64, u64), String> {
+        let mut events = self
+            .stream_events(aggregate_id, AGGREGATE_PAGE_SIZE)
+            .from_version(from_version);
+        let mut replayed = 0;
+        while let Some(event) = events.next().await {
+            aggregate.apply_event(&event?);
+            replayed += 1;
+        }
+        Ok((aggregate, events.version(), replayed))
+    }
+
     /// Get all events from all aggregates (for projection rebuild)
     pub async fn get_all_events(&self) -> Result<Vec<E>, String> {
         self.backend.get_all_events().await
@@ -133,6 +382,48 @@ impl<E: Event, B: EventStoreBackend<E>> EventStore<E, B> {
         let mut subscribers = self.subscribers.write().await;
         subscribers.push(tx);
     }
+
+    /// Subscribe to the event stream, first replaying stored events
+    ///
+    /// Stored events from `start` are sent to `tx`, followed by every event
+    /// appended afterwards, with no gaps or duplicates at the boundary.
+    /// Delivery runs on a background task and waits for the receiver: while
+    /// it replays, live events are buffered up to the capacity of `tx`, after
+    /// which appends wait, as with [`subscribe`](Self::subscribe). The
+    /// subscription ends when the receiver is dropped.
+    pub async fn subscribe_from(
+        &self,
+        tx: tokio::sync::mpsc::Sender<E>,
+        start: Start,
+    ) -> Result<(), String> {
+        let after = match start {
+            Start::Beginning => 0,
+            Start::Sequence(sequence) => sequence.saturating_sub(1),
+        };
+
+        // Appends hold the read lock, so while we hold the write lock the
+        // replayed history ends exactly where live delivery begins
+        let mut subscribers = self.subscribers.write().await;
+        let history = self.backend.get_events_since(after).await?;
+        let (live_tx, mut live_rx) = tokio::sync::mpsc::channel(tx.max_capacity());
+        subscribers.push(live_tx);
+        drop(subscribers);
+
+        tokio::spawn(async move {
+            for event in history {
+                if tx.send(event).await.is_err() {
+                    return;
+                }
+            }
+            while let Some(event) = live_rx.recv().await {
+                if tx.send(event).await.is_err() {
+                    return;
+                }
+            }
+        });
+
+        Ok(())
+    }
 }
 
 /// Snapshot for aggregate optimization
@@ -166,10 +457,14 @@ pub use allsource_core::PipelineManager;
 pub use allsource_core::SchemaRegistry;
 pub use backend::*;
 pub use command_bus::*;
+pub use envelope::*;
 pub use event_versioning::*;
+pub use idempotency::*;
 pub use query_bus::*;
 pub use memory_backend::*;
 pub use projection_registry::*;
+#[cfg(feature = "resilience")]
+pub use resilient_backend::*;
 pub use saga::{
     CompensationResult, MacroSagaOrchestrator, Saga, SagaContext, SagaError,
     SagaStep as MacroSagaStep, StepExecutionResult, StepOutput,
@@ -180,7 +475,9 @@ pub use saga::{CompensationStrategy, FileSnapshot, WriteFileStep};
 pub use saga::SqliteSavepoint;
 pub use saga_orchestrator::{
     SagaDefinition, SagaMetadata, SagaOrchestrator, SagaResult, SagaStatus,
-    SagaStep as OrchestratorSagaStep,
+    SagaStep as OrchestratorSagaStep, StepPolicy,
 };
+pub use saga_store::*;
+pub use snapshot_policy::SnapshotPolicy;
 pub use sqlite_backend::*;
 pub use sync::*;
@@ -5,12 +5,17 @@
 //! - Consistency tracking
 //! - Rebuild functionality
 //! - Multi-projection coordination
+//! - Typed read access for query handlers
 
-use std::{collections::HashMap, marker::PhantomData, sync::Arc};
+use std::{any::Any, collections::HashMap, marker::PhantomData, sync::Arc};
 
 use tokio::sync::{mpsc, RwLock};
 
 use super::{Event, EventStore, EventStoreBackend, Projection};
+use crate::shutdown::ShutdownToken;
+
+/// Events applied per write-lock acquisition during a rebuild
+const REBUILD_BATCH_SIZE: usize = 1000;
 
 /// Position tracker for projection consistency
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -58,6 +63,8 @@ trait ErasedProjection<E: Event>: Send + Sync {
     fn position(&self) -> ProjectionPosition;
     /// Mark as rebuilding
     fn set_rebuilding(&mut self, rebuilding: bool);
+    /// The wrapped projection, for downcasting
+    fn as_any(&self) -> &dyn Any;
 }
 
 /// Concrete wrapper for projections
@@ -66,7 +73,7 @@ struct ProjectionWrapper<P: Projection> {
     metadata: ProjectionMetadata,
 }
 
-impl<P: Projection> ErasedProjection<P::Event> for ProjectionWrapper<P> {
+impl<P: Projection + 'static> ErasedProjection<P::Event> for ProjectionWrapper<P> {
     fn apply_event(&mut self, event: &P::Event) {
         self.projection.apply(event);
         self.metadata
@@ -85,6 +92,10 @@ impl<P: Projection> ErasedProjection<P::Event> for ProjectionWrapper<P> {
     fn set_rebuilding(&mut self, rebuilding: bool) {
         self.metadata.rebuilding = rebuilding;
     }
+
+    fn as_any(&self) -> &dyn Any {
+        &self.projection
+    }
 }
 
 /// Type alias for projection storage
@@ -138,8 +149,110 @@ impl<E: Event, B: EventStoreBackend<E>> ProjectionRegistry<E, B> {
         None
     }
 
+    /// Run a read closure against a named projection
+    ///
+    /// The closure runs under the registry's read lock, so concurrent queries
+    /// don't wait for each other, and event application only waits for the
+    /// closure to return. Keep it short and copy out what you need.
+    ///
+    /// Returns an error if no projection is registered under `name`, or if it
+    /// isn't a `P`.
+    pub async fn query<P, Q>(&self, name: &str, f: impl FnOnce(&P) -> Q) -> Result<Q, String>
+    where
+        P: Projection<Event = E> + 'static,
+    {
+        let projections = self.projections.read().await;
+        let projection = projections
+            .get(name)
+            .ok_or_else(|| format!("Projection '{}' not found", name))?;
+        let projection = projection.as_any().downcast_ref::<P>().ok_or_else(|| {
+            format!(
+                "Projection '{}' is not a {}",
+                name,
+                std::any::type_name::<P>()
+            )
+        })?;
+        Ok(f(projection))
+    }
+
+    /// Read one item from a named projection
+    ///
+    /// ```rust,ignore
+    /// let user = registry
+    ///     .projection_one::<UserList, User>("users", |p| p.users.get(&id))
+    ///     .await?;
+    /// ```
+    pub async fn projection_one<P, T>(
+        &self,
+        name: &str,
+        f: impl FnOnce(&P) -> Option<&T>,
+    ) -> Result<Option<T>, String>
+    where
+        P: Projection<Event = E> + 'static,
+        T: Clone,
+    {
+        self.query(name, |projection: &P| f(projection).cloned())
+            .await
+    }
+
+    /// Read a list of items from a named projection
+    ///
+    /// ```rust,ignore
+    /// let users = registry
+    ///     .projection_all::<UserList, User>("users", |p| p.users.values().collect())
+    ///     .await?;
+    /// ```
+    pub async fn projection_all<P, T>(
+        &self,
+        name: &str,
+        f: impl FnOnce(&P) -> Vec<&T>,
+    ) -> Result<Vec<T>, String>
+    where
+        P: Projection<Event = E> + 'static,
+        T: Clone,
+    {
+        self.query(name, |projection: &P| {
+            f(projection).into_iter().cloned().collect()
+        })
+        .await
+    }
+
     /// Rebuild a specific projection from scratch
     pub async fn rebuild(&self, name: &str) -> Result<(), String> {
+        self.rebuild_batched(name, None, |_, _| {}).await
+    }
+
+    /// Rebuild a projection, reporting progress and stopping on shutdown
+    ///
+    /// Events are applied in batches; `progress(done, total)` is called
+    /// before the first batch and after each one, so a splash screen can
+    /// show how far the rebuild has got. Between batches the write lock is
+    /// released and `shutdown` is checked. If it has fired the rebuild stops
+    /// with an error, leaving the projection partially rebuilt until the
+    /// next rebuild.
+    ///
+    /// ```rust,ignore
+    /// registry
+    ///     .rebuild_with_progress("orders", &shutdown.token(), |done, total| {
+    ///         println!("Rebuilding orders: {done}/{total}");
+    ///     })
+    ///     .await?;
+    /// ```
+    pub async fn rebuild_with_progress(
+        &self,
+        name: &str,
+        shutdown: &ShutdownToken,
+        progress: impl FnMut(u64, u64),
+    ) -> Result<(), String> {
+        self.rebuild_batched(name, Some(shutdown), progress).await
+    }
+
+    async fn rebuild_batched(
+        &self,
+        name: &str,
+        shutdown: Option<&ShutdownToken>,
+        mut progress: impl FnMut(u64, u64),
+    ) -> Result<(), String> {
         // Mark projection as rebuilding
         {
             let mut projections = self.projections.write().await;
@@ -150,18 +263,44 @@ impl<E: Event, B: EventStoreBackend<E>> ProjectionRegistry<E, B> {
             }
         }
 
-        // Get all events from event store
-        let events = self.event_store.get_all_events().await?;
+        let result = self.apply_history(name, shutdown, &mut progress).await;
 
-        // Apply all events to the projection
-        {
-            let mut projections = self.projections.write().await;
-            if let Some(projection) = projections.get_mut(name) {
-                for event in events {
-                    projection.apply_event(&event);
+        if let Some(projection) = self.projections.write().await.get_mut(name) {
+            projection.set_rebuilding(false);
+        }
+        result
+    }
+
+    async fn apply_history(
+        &self,
+        name: &str,
+        shutdown: Option<&ShutdownToken>,
+        progress: &mut impl FnMut(u64, u64),
+    ) -> Result<(), String> {
+        let events = self.event_store.get_all_events().await?;
+        let total = events.len() as u64;
+        let mut done = 0;
+        progress(done, total);
+
+        for batch in events.chunks(REBUILD_BATCH_SIZE) {
+            if shutdown.is_some_and(ShutdownToken::is_shutdown) {
+                return Err(format!(
+                    "Rebuild of projection '{}' cancelled after {} of {} events",
+                    name, done, total
+                ));
+            }
+            {
+                let mut projections = self.projections.write().await;
+                let projection = projections
+                    .get_mut(name)
+                    .ok_or_else(|| format!("Projection '{}' not found", name))?;
+                for event in batch {
+                    projection.apply_event(event);
                 }
-                projection.set_rebuilding(false);
             }
+            done += batch.len() as u64;
+            progress(done, total);
+            tokio::task::yield_now().await;
         }
 
         Ok(())
@@ -266,7 +405,6 @@ mod tests {
             }
         }
 
-        #[allow(dead_code)]
         fn get(&self, id: &str) -> Option<i32> {
             self.data.get(id).copied()
         }
@@ -380,4 +518,144 @@ mod tests {
         assert_eq!(meta1.position.version, 1);
         assert_eq!(meta2.position.version, 1);
     }
+
+    #[tokio::test]
+    async fn test_rebuild_with_progress() {
+        let store = EventStore::<TestEvent, InMemoryBackend<TestEvent>>::new();
+        let events = (0..1500)
+            .map(|value| TestEvent::Updated {
+                id: "1".to_string(),
+                value,
+            })
+            .collect();
+        store.append("test", events).await.unwrap();
+
+        let registry = ProjectionRegistry::new(store);
+        registry.register("proj", TestProjection::new()).await;
+
+        let shutdown = crate::shutdown::GracefulShutdown::new();
+        let mut reports = Vec::new();
+        registry
+            .rebuild_with_progress("proj", &shutdown.token(), |done, total| {
+                reports.push((done, total))
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(reports, [(0, 1500), (1000, 1500), (1500, 1500)]);
+        let metadata = registry.get_metadata("proj").await.unwrap();
+        assert_eq!(metadata.position.version, 1500);
+    }
+
+    #[tokio::test]
+    async fn test_rebuild_with_progress_cancelled() {
+        let store = EventStore::<TestEvent, InMemoryBackend<TestEvent>>::new();
+        let events = (0..1500)
+            .map(|value| TestEvent::Updated {
+                id: "1".to_string(),
+                value,
+            })
+            .collect();
+        store.append("test", events).await.unwrap();
+
+        let registry = ProjectionRegistry::new(store);
+        registry.register("proj", TestProjection::new()).await;
+
+        // Shut down once the first batch is applied
+        let shutdown = crate::shutdown::GracefulShutdown::new();
+        let err = registry
+            .rebuild_with_progress("proj", &shutdown.token(), |done, _| {
+                if done > 0 {
+                    shutdown.shutdown();
+                }
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            err,
+            "Rebuild of projection 'proj' cancelled after 1000 of 1500 events"
+        );
+        let value = registry
+            .query("proj", |p: &TestProjection| p.get("1"))
+            .await
+            .unwrap();
+        assert_eq!(value, Some(999));
+
+        assert!(registry
+            .rebuild_with_progress("missing", &shutdown.token(), |_, _| {})
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_typed_query() {
+        let store = EventStore::<TestEvent, InMemoryBackend<TestEvent>>::new();
+        store
+            .append(
+                "test",
+                vec![
+                    TestEvent::Created {
+                        id: "1".to_string(),
+                        value: 10,
+                    },
+                    TestEvent::Created {
+                        id: "2".to_string(),
+                        value: 20,
+                    },
+                ],
+            )
+            .await
+            .unwrap();
+
+        let registry = ProjectionRegistry::new(store);
+        registry.register("values", TestProjection::new()).await;
+        registry.rebuild("values").await.unwrap();
+
+        let value = registry
+            .query("values", |p: &TestProjection| p.get("2"))
+            .await
+            .unwrap();
+        assert_eq!(value, Some(20));
+
+        let one = registry
+            .projection_one::<TestProjection, i32>("values", |p| p.data.get("1"))
+            .await
+            .unwrap();
+        assert_eq!(one, Some(10));
+
+        let mut all = registry
+            .projection_all::<TestProjection, i32>("values", |p| p.data.values().collect())
+            .await
+            .unwrap();
+        all.sort();
+        assert_eq!(all, vec![10, 20]);
+    }
+
+    #[tokio::test]
+    async fn test_query_errors() {
+        struct OtherProjection;
+
+        impl Projection for OtherProjection {
+            type Event = TestEvent;
+
+            fn apply(&mut self, _event: &Self::Event) {}
+        }
+
+        let store = EventStore::<TestEvent, InMemoryBackend<TestEvent>>::new();
+        let registry = ProjectionRegistry::new(store);
+        registry.register("values", TestProjection::new()).await;
+
+        let missing = registry
+            .query("missing", |_: &TestProjection| ())
+            .await
+            .unwrap_err();
+        assert!(missing.contains("not found"));
+
+        let wrong_type = registry
+            .query("values", |_: &OtherProjection| ())
+            .await
+            .unwrap_err();
+        assert!(wrong_type.contains("is not a"));
+    }
 }
@@ -2,17 +2,64 @@
 //!
 //! The QueryBus provides automatic query routing and error handling,
 //! mirroring the CommandBus pattern for the read side.
+//!
+//! Middleware added with [`QueryBus::layer`] wraps every dispatch, for
+//! caching, authorization or logging. [`QueryCache`] caches the results of
+//! one query type:
+//!
+//! ```rust
+//! use std::time::Duration;
+//!
+//! use allframe_core::cqrs::{Query, QueryBus, QueryCache, QueryHandler, QueryResult};
+//! use async_trait::async_trait;
+//!
+//! struct GetPrice {
+//!     sku: String,
+//! }
+//!
+//! impl Query for GetPrice {}
+//!
+//! struct PriceHandler;
+//!
+//! #[async_trait]
+//! impl QueryHandler<GetPrice, u64> for PriceHandler {
+//!     async fn handle(&self, query: GetPrice) -> QueryResult<u64> {
+//!         Ok(query.sku.len() as u64 * 100)
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() -> QueryResult<()> {
+//! let bus = QueryBus::new();
+//! bus.register(PriceHandler).await;
+//! bus.layer(QueryCache::<GetPrice, u64>::new(Duration::from_secs(60), |q| {
+//!     q.sku.clone()
+//! }))
+//! .await;
+//!
+//! let price: u64 = bus.dispatch(GetPrice { sku: "apple".into() }).await?;
+//! assert_eq!(price, 500);
+//! # Ok(())
+//! # }
+//! ```
 
 use std::{
     any::{Any, TypeId},
     collections::HashMap,
-    sync::Arc,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
 };
 
 use async_trait::async_trait;
 use tokio::sync::RwLock;
 
 /// Query trait marker
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` is not a query",
+    note = "annotate the type with `#[query]`"
+)]
 pub trait Query: Send + Sync + 'static {}
 
 /// Query execution result
@@ -23,6 +70,8 @@ pub type QueryResult<R> = Result<R, QueryError>;
 pub enum QueryError {
     /// Query handler not found
     NotFound(String),
+    /// Rejected by middleware, e.g. for lacking permission
+    Unauthorized(String),
     /// Internal error
     Internal(String),
 }
@@ -31,6 +80,7 @@ impl std::fmt::Display for QueryError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             QueryError::NotFound(msg) => write!(f, "Handler not found: {}", msg),
+            QueryError::Unauthorized(msg) => write!(f, "Unauthorized: {}", msg),
             QueryError::Internal(msg) => write!(f, "Internal error: {}", msg),
         }
     }
@@ -38,6 +88,12 @@ impl std::fmt::Display for QueryError {
 
 impl std::error::Error for QueryError {}
 
+impl From<String> for QueryError {
+    fn from(message: String) -> Self {
+        QueryError::Internal(message)
+    }
+}
+
 /// Query handler trait
 #[async_trait]
 pub trait QueryHandler<Q: Query, R: Send + Sync + 'static>: Send + Sync {
@@ -48,7 +104,7 @@ pub trait QueryHandler<Q: Query, R: Send + Sync + 'static>: Send + Sync {
 /// Type-erased query handler wrapper
 #[async_trait]
 trait ErasedQueryHandler: Send + Sync {
-    async fn handle_erased(&self, query: Box<dyn Any + Send>) -> Result<Box<dyn Any + Send>, QueryError>;
+    async fn handle_erased(&self, query: Box<dyn Any + Send>) -> ErasedQueryResult;
 }
 
 /// Wrapper to type-erase query handlers
@@ -61,7 +117,7 @@ struct QueryHandlerWrapper<Q: Query, R: Send + Sync + 'static, H: QueryHandler<Q
 impl<Q: Query, R: Send + Sync + 'static, H: QueryHandler<Q, R>> ErasedQueryHandler
     for QueryHandlerWrapper<Q, R, H>
 {
-    async fn handle_erased(&self, query: Box<dyn Any + Send>) -> Result<Box<dyn Any + Send>, QueryError> {
+    async fn handle_erased(&self, query: Box<dyn Any + Send>) -> ErasedQueryResult {
         match query.downcast::<Q>() {
             Ok(q) => {
                 let result = self.handler.handle(*q).await?;
@@ -77,9 +133,77 @@ impl<Q: Query, R: Send + Sync + 'static, H: QueryHandler<Q, R>> ErasedQueryHandl
 /// Type alias for handler storage
 type HandlerMap = HashMap<TypeId, Arc<dyn ErasedQueryHandler>>;
 
+/// Type-erased query result
+pub type ErasedQueryResult = Result<Box<dyn Any + Send>, QueryError>;
+
+/// Future returned by query middleware and the handler chain
+pub type QueryFuture<'a> = Pin<Box<dyn Future<Output = ErasedQueryResult> + Send + 'a>>;
+
+/// A query flowing through the middleware stack
+pub struct QueryRequest {
+    /// Type name of the query
+    pub query_type: &'static str,
+    query: Box<dyn Any + Send>,
+}
+
+impl QueryRequest {
+    fn new<Q: Query>(query: Q) -> Self {
+        Self {
+            query_type: std::any::type_name::<Q>(),
+            query: Box::new(query),
+        }
+    }
+
+    /// The query, if it is a `Q`
+    pub fn query<Q: Query>(&self) -> Option<&Q> {
+        self.query.downcast_ref()
+    }
+}
+
+/// Middleware wrapping query dispatch
+pub trait QueryMiddleware: Send + Sync {
+    /// Handle a query, usually by calling `next.run(request)`
+    ///
+    /// The result must be the handler's result type; return early (e.g.
+    /// with [`QueryError::Unauthorized`]) to skip the handler.
+    fn handle<'a>(&'a self, request: QueryRequest, next: QueryNext<'a>) -> QueryFuture<'a>;
+}
+
+impl<M: QueryMiddleware + ?Sized> QueryMiddleware for Arc<M> {
+    fn handle<'a>(&'a self, request: QueryRequest, next: QueryNext<'a>) -> QueryFuture<'a> {
+        (**self).handle(request, next)
+    }
+}
+
+/// Handler invoked after the last middleware layer
+type QueryEndpoint<'a> = dyn Fn(QueryRequest) -> QueryFuture<'a> + Send + Sync + 'a;
+
+/// The remaining middleware layers and the handler
+pub struct QueryNext<'a> {
+    layers: &'a [Arc<dyn QueryMiddleware>],
+    endpoint: &'a QueryEndpoint<'a>,
+}
+
+impl<'a> QueryNext<'a> {
+    /// Pass the query to the next layer (or the handler)
+    pub fn run(self, request: QueryRequest) -> QueryFuture<'a> {
+        match self.layers.split_first() {
+            Some((layer, rest)) => layer.handle(
+                request,
+                QueryNext {
+                    layers: rest,
+                    endpoint: self.endpoint,
+                },
+            ),
+            None => (self.endpoint)(request),
+        }
+    }
+}
+
 /// Query Bus for dispatching queries to handlers
 pub struct QueryBus {
     handlers: Arc<RwLock<HandlerMap>>,
+    layers: Arc<RwLock<Vec<Arc<dyn QueryMiddleware>>>>,
 }
 
 impl QueryBus {
@@ -87,6 +211,7 @@ impl QueryBus {
     pub fn new() -> Self {
         Self {
             handlers: Arc::new(RwLock::new(HashMap::new())),
+            layers: Arc::new(RwLock::new(Vec::new())),
         }
     }
 
@@ -104,27 +229,56 @@ impl QueryBus {
         handlers.insert(type_id, Arc::new(wrapper));
     }
 
+    /// Add a middleware layer
+    ///
+    /// Layers wrap every dispatch in the order they were added.
+    pub async fn layer<M: QueryMiddleware + 'static>(&self, middleware: M) {
+        self.layers.write().await.push(Arc::new(middleware));
+    }
+
     /// Dispatch a query
     pub async fn dispatch<Q: Query, R: Send + Sync + 'static>(&self, query: Q) -> QueryResult<R> {
-        let type_id = TypeId::of::<Q>();
-        let handlers = self.handlers.read().await;
-
-        match handlers.get(&type_id) {
-            Some(handler) => {
-                let boxed_query: Box<dyn Any + Send> = Box::new(query);
-                let result = handler.handle_erased(boxed_query).await?;
-                match result.downcast::<R>() {
-                    Ok(r) => Ok(*r),
-                    Err(_) => Err(QueryError::Internal(
-                        "Type mismatch in query result".to_string(),
-                    )),
-                }
+        let handler = self
+            .handlers
+            .read()
+            .await
+            .get(&TypeId::of::<Q>())
+            .cloned()
+            .ok_or_else(|| {
+                QueryError::NotFound(format!(
+                    "No handler registered for query type: {}",
+                    std::any::type_name::<Q>()
+                ))
+            })?;
+        let layers = self.layers.read().await.clone();
+
+        let endpoint = |request: QueryRequest| handler.handle_erased(request.query);
+        let next = QueryNext {
+            layers: &layers,
+            endpoint: &endpoint,
+        };
+        match next.run(QueryRequest::new(query)).await?.downcast::<R>() {
+            Ok(r) => Ok(*r),
+            Err(_) => Err(QueryError::Internal(
+                "Type mismatch in query result".to_string(),
+            )),
+        }
+    }
+
+    /// Register every `#[query_handler]` function
+    ///
+    /// Handlers are discovered from all linked crates. Returns the number of
+    /// handlers registered.
+    pub async fn auto_register(&self) -> usize {
+        let mut handlers = self.handlers.write().await;
+        let mut count = 0;
+        for registration in inventory::iter::<QueryHandlerRegistration> {
+            if let Ok(entry) = (registration.entry)().downcast::<RegisteredQueryHandler>() {
+                handlers.insert(entry.query, entry.handler);
+                count += 1;
             }
-            None => Err(QueryError::NotFound(format!(
-                "No handler registered for query type: {}",
-                std::any::type_name::<Q>()
-            ))),
         }
+        count
     }
 
     /// Get number of registered handlers
@@ -143,10 +297,114 @@ impl Clone for QueryBus {
     fn clone(&self) -> Self {
         Self {
             handlers: Arc::clone(&self.handlers),
+            layers: Arc::clone(&self.layers),
         }
     }
 }
 
+/// Cached results of one query type
+type CacheEntries<R> = HashMap<String, (Instant, R)>;
+
+/// Middleware caching the results of `Q` queries for a TTL
+///
+/// Results are keyed by the string `key` builds from the query, so queries
+/// with equal keys share a result. Other query types pass through. `R` must
+/// be the type `Q` is dispatched with; errors aren't cached.
+pub struct QueryCache<Q, R> {
+    ttl: Duration,
+    key: Box<dyn Fn(&Q) -> String + Send + Sync>,
+    entries: Mutex<CacheEntries<R>>,
+}
+
+impl<Q: Query, R: Clone + Send + 'static> QueryCache<Q, R> {
+    /// Cache results for `ttl`, keyed by `key`
+    pub fn new(ttl: Duration, key: impl Fn(&Q) -> String + Send + Sync + 'static) -> Self {
+        Self {
+            ttl,
+            key: Box::new(key),
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Drop the cached result for `query`, e.g. after a command changed it
+    pub fn invalidate(&self, query: &Q) {
+        self.entries.lock().unwrap().remove(&(self.key)(query));
+    }
+
+    /// Drop all cached results
+    pub fn clear(&self) {
+        self.entries.lock().unwrap().clear();
+    }
+
+    fn cached(&self, key: &str) -> Option<R> {
+        let mut entries = self.entries.lock().unwrap();
+        match entries.get(key) {
+            Some((stored_at, result)) if stored_at.elapsed() < self.ttl => Some(result.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+}
+
+impl<Q: Query, R: Clone + Send + 'static> QueryMiddleware for QueryCache<Q, R> {
+    fn handle<'a>(&'a self, request: QueryRequest, next: QueryNext<'a>) -> QueryFuture<'a> {
+        let Some(query) = request.query::<Q>() else {
+            return next.run(request);
+        };
+        let key = (self.key)(query);
+        if let Some(result) = self.cached(&key) {
+            return Box::pin(async move { Ok(Box::new(result) as Box<dyn Any + Send>) });
+        }
+
+        Box::pin(async move {
+            let result = next.run(request).await?;
+            if let Some(result) = result.downcast_ref::<R>() {
+                let mut entries = self.entries.lock().unwrap();
+                entries.insert(key, (Instant::now(), result.clone()));
+            }
+            Ok(result)
+        })
+    }
+}
+
+/// A handler collected by [`QueryBus::auto_register`]
+struct RegisteredQueryHandler {
+    query: TypeId,
+    handler: Arc<dyn ErasedQueryHandler>,
+}
+
+/// Registration submitted by the `#[query_handler]` macro
+pub struct QueryHandlerRegistration {
+    /// Name of the handler function
+    pub name: &'static str,
+    entry: fn() -> Box<dyn Any + Send>,
+}
+
+impl QueryHandlerRegistration {
+    /// Create a registration from a function building the handler entry
+    pub const fn new(name: &'static str, entry: fn() -> Box<dyn Any + Send>) -> Self {
+        Self { name, entry }
+    }
+
+    /// Type-erase a handler for registration
+    pub fn entry<Q: Query, R: Send + Sync + 'static, H: QueryHandler<Q, R> + 'static>(
+        handler: H,
+    ) -> Box<dyn Any + Send> {
+        Box::new(RegisteredQueryHandler {
+            query: TypeId::of::<Q>(),
+            handler: Arc::new(QueryHandlerWrapper {
+                handler: Arc::new(handler),
+                _phantom: std::marker::PhantomData,
+            }),
+        })
+    }
+}
+
+inventory::collect!(QueryHandlerRegistration);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -157,7 +415,7 @@ mod tests {
 
     impl Query for GetUserQuery {}
 
-    #[derive(Debug, PartialEq)]
+    #[derive(Debug, Clone, PartialEq)]
     struct UserResult {
         id: String,
         name: String,
@@ -204,4 +462,98 @@ mod tests {
 
         assert!(matches!(result, Err(QueryError::NotFound(_))));
     }
+
+    /// Handler counting its calls
+    struct CountingHandler {
+        calls: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl QueryHandler<GetUserQuery, UserResult> for CountingHandler {
+        async fn handle(&self, query: GetUserQuery) -> QueryResult<UserResult> {
+            let n = self.calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(UserResult {
+                id: query.id,
+                name: format!("User {}", n),
+            })
+        }
+    }
+
+    fn get_user(id: &str) -> GetUserQuery {
+        GetUserQuery { id: id.to_string() }
+    }
+
+    #[tokio::test]
+    async fn test_query_cache() {
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let bus = QueryBus::new();
+        bus.register(CountingHandler {
+            calls: calls.clone(),
+        })
+        .await;
+        let cache = Arc::new(QueryCache::<GetUserQuery, UserResult>::new(
+            Duration::from_secs(60),
+            |q| q.id.clone(),
+        ));
+        bus.layer(cache.clone()).await;
+
+        let first: UserResult = bus.dispatch(get_user("1")).await.unwrap();
+        let cached: UserResult = bus.dispatch(get_user("1")).await.unwrap();
+        assert_eq!(first, cached);
+        let other: UserResult = bus.dispatch(get_user("2")).await.unwrap();
+        assert_eq!(other.name, "User 1");
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        cache.invalidate(&get_user("1"));
+        let refreshed: UserResult = bus.dispatch(get_user("1")).await.unwrap();
+        assert_eq!(refreshed.name, "User 2");
+    }
+
+    #[tokio::test]
+    async fn test_query_cache_expires() {
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let bus = QueryBus::new();
+        bus.register(CountingHandler {
+            calls: calls.clone(),
+        })
+        .await;
+        bus.layer(QueryCache::<GetUserQuery, UserResult>::new(
+            Duration::from_millis(20),
+            |q| q.id.clone(),
+        ))
+        .await;
+
+        let _: UserResult = bus.dispatch(get_user("1")).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        let _: UserResult = bus.dispatch(get_user("1")).await.unwrap();
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+
+    /// Rejects queries for users other than "1"
+    struct OnlyUserOne;
+
+    impl QueryMiddleware for OnlyUserOne {
+        fn handle<'a>(&'a self, request: QueryRequest, next: QueryNext<'a>) -> QueryFuture<'a> {
+            match request.query::<GetUserQuery>() {
+                Some(query) if query.id != "1" => {
+                    let message = format!("user {}", query.id);
+                    Box::pin(async move { Err(QueryError::Unauthorized(message)) })
+                }
+                _ => next.run(request),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_middleware_rejects_query() {
+        let bus = QueryBus::new();
+        bus.register(GetUserHandler).await;
+        bus.layer(OnlyUserOne).await;
+
+        let user: UserResult = bus.dispatch(get_user("1")).await.unwrap();
+        assert_eq!(user.name, "Test User");
+
+        let result = bus.dispatch::<_, UserResult>(get_user("2")).await;
+        assert!(matches!(result, Err(QueryError::Unauthorized(_))));
+    }
 }
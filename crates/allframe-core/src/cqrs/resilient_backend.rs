@@ -0,0 +1,671 @@
+//! Retrying, buffering decorator for event store backends
+//!
+//! [`ResilientBackend`] wraps another backend (typically
+//! [`AllSourceBackend`](super::AllSourceBackend) with persistence) so that
+//! transient storage failures don't fail appends:
+//! - every operation is retried with exponential backoff via
+//!   [`RetryExecutor`]
+//! - appends that still fail are kept in a bounded in-memory write-ahead
+//!   buffer and replayed, in order, before the next write, on
+//!   [`flush`](EventStoreBackend::flush) and on health checks
+//! - reads include buffered events, so aggregates see their own writes
+//! - metadata passed to
+//!   [`append_with_metadata`](EventStoreBackend::append_with_metadata) is
+//!   buffered and replayed with its events
+//!
+//! With the `health` feature, the backend implements
+//! [`Dependency`](crate::health::Dependency), reporting buffered events as
+//! degraded and an unreachable backend as unhealthy. Every check probes
+//! the wrapped backend with a cheap read, so an outage shows up even when
+//! nothing is buffered.
+
+use std::{
+    collections::VecDeque,
+    sync::atomic::{AtomicBool, Ordering},
+    time::SystemTime,
+};
+
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+
+use super::{
+    backend::{BackendStats, EventStoreBackend},
+    Event, EventEnvelope, EventMetadata,
+};
+use crate::resilience::{RetryConfig, RetryExecutor};
+
+/// Default limit on buffered events
+const DEFAULT_MAX_BUFFERED_EVENTS: usize = 10_000;
+
+/// Aggregate read by health checks to probe the wrapped backend
+#[cfg(feature = "health")]
+const HEALTH_PROBE_AGGREGATE: &str = "__allframe_health_probe";
+
+/// Backend error as a `std::error::Error`, for [`RetryExecutor`]
+#[derive(Debug)]
+struct BackendError(String);
+
+impl std::fmt::Display for BackendError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for BackendError {}
+
+/// An append waiting to be written to the wrapped backend
+#[derive(Clone)]
+struct Pending<E> {
+    aggregate_id: String,
+    events: Vec<E>,
+    /// Set for appends made with `append_with_metadata`
+    metadata: Option<EventMetadata>,
+    /// When the append was buffered, reported as the events' timestamp
+    buffered_at: SystemTime,
+}
+
+impl<E> Pending<E> {
+    fn new(aggregate_id: String, events: Vec<E>, metadata: Option<EventMetadata>) -> Self {
+        Self {
+            aggregate_id,
+            events,
+            metadata,
+            buffered_at: SystemTime::now(),
+        }
+    }
+}
+
+/// Event store backend decorator adding retries and a write-ahead buffer
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use allframe_core::cqrs::{InMemoryBackend, ResilientBackend};
+/// use allframe_core::resilience::RetryConfig;
+/// # use allframe_core::cqrs::{Event, EventTypeName};
+/// # #[derive(Clone, serde::Serialize, serde::Deserialize)]
+/// # struct OrderPlaced;
+/// # impl EventTypeName for OrderPlaced {}
+/// # impl Event for OrderPlaced {}
+///
+/// let backend = ResilientBackend::new(InMemoryBackend::<OrderPlaced>::new())
+///     .with_retry(RetryConfig::new(5).with_initial_interval(Duration::from_millis(100)))
+///     .with_max_buffered_events(1_000);
+/// ```
+pub struct ResilientBackend<E: Event, B: EventStoreBackend<E>> {
+    inner: B,
+    retry: RetryExecutor,
+    /// Appends not yet written to `inner`, in order
+    buffer: Mutex<VecDeque<Pending<E>>>,
+    /// Serializes writes to `inner`, so buffered events stay ahead of new
+    /// ones without holding `buffer` across retries
+    writes: Mutex<()>,
+    max_buffered_events: usize,
+    connected: AtomicBool,
+    name: String,
+}
+
+impl<E: Event, B: EventStoreBackend<E>> ResilientBackend<E, B> {
+    /// Wrap a backend with the default retry configuration
+    pub fn new(inner: B) -> Self {
+        Self {
+            inner,
+            retry: RetryExecutor::new(RetryConfig::default()),
+            buffer: Mutex::new(VecDeque::new()),
+            writes: Mutex::new(()),
+            max_buffered_events: DEFAULT_MAX_BUFFERED_EVENTS,
+            connected: AtomicBool::new(true),
+            name: "event-store".to_string(),
+        }
+    }
+
+    /// Set the retry/backoff configuration
+    pub fn with_retry(mut self, config: RetryConfig) -> Self {
+        self.retry = RetryExecutor::new(config);
+        self
+    }
+
+    /// Set how many events may be buffered while the backend is unavailable
+    ///
+    /// Appends beyond this limit fail. Use `0` to disable buffering.
+    pub fn with_max_buffered_events(mut self, max: usize) -> Self {
+        self.max_buffered_events = max;
+        self
+    }
+
+    /// Set the name reported in health checks (default: `event-store`)
+    pub fn with_name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// The wrapped backend
+    pub fn inner(&self) -> &B {
+        &self.inner
+    }
+
+    /// Whether the last operation against the wrapped backend succeeded
+    pub fn is_connected(&self) -> bool {
+        self.connected.load(Ordering::Relaxed)
+    }
+
+    /// Number of events waiting to be written
+    pub async fn buffered_events(&self) -> usize {
+        count_events(&*self.buffer.lock().await)
+    }
+
+    /// Write buffered events to the wrapped backend
+    ///
+    /// Returns the number of events written. Stops at the first append that
+    /// still fails after retrying, keeping it and later appends buffered.
+    pub async fn replay_buffered(&self) -> Result<usize, String> {
+        let _writes = self.writes.lock().await;
+        self.replay().await
+    }
+
+    /// Replay the buffer; the caller holds `writes`
+    async fn replay(&self) -> Result<usize, String> {
+        // Work from a copy so reads aren't blocked while retries back off.
+        // Events leave the buffer only once written, so reads keep seeing
+        // them, and `writes` keeps anyone else from touching its front.
+        let pending: Vec<Pending<E>> = self.buffer.lock().await.iter().cloned().collect();
+        let mut written = 0;
+        for append in pending {
+            let count = append.events.len();
+            self.write(std::slice::from_ref(&append)).await?;
+            self.buffer.lock().await.pop_front();
+            written += count;
+        }
+        Ok(written)
+    }
+
+    /// Write appends to the wrapped backend with retries
+    async fn write(&self, appends: &[Pending<E>]) -> Result<(), String> {
+        match appends {
+            [Pending {
+                aggregate_id,
+                events,
+                metadata: Some(metadata),
+                ..
+            }] => {
+                self.call("append_with_metadata", || {
+                    self.inner
+                        .append_with_metadata(aggregate_id, events.clone(), metadata.clone())
+                })
+                .await
+            }
+            [Pending {
+                aggregate_id,
+                events,
+                metadata: None,
+                ..
+            }] => {
+                self.call("append", || self.inner.append(aggregate_id, events.clone()))
+                    .await
+            }
+            _ => {
+                let batch: Vec<(String, Vec<E>)> = appends
+                    .iter()
+                    .map(|append| (append.aggregate_id.clone(), append.events.clone()))
+                    .collect();
+                self.call("append_batch", || self.inner.append_batch(batch.clone()))
+                    .await
+            }
+        }
+    }
+
+    /// Run an operation against the wrapped backend with retries
+    async fn call<'a, T, F, Fut>(&'a self, operation: &str, mut f: F) -> Result<T, String>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T, String>> + 'a,
+    {
+        let result = self
+            .retry
+            .execute(operation, || {
+                let fut = f();
+                async move { fut.await.map_err(BackendError) }
+            })
+            .await;
+        self.connected.store(result.is_ok(), Ordering::Relaxed);
+        result.map_err(|e| e.to_string())
+    }
+
+    /// Append, buffering the events if the wrapped backend stays unavailable
+    async fn append_all(&self, batch: Vec<Pending<E>>) -> Result<(), String> {
+        let _writes = self.writes.lock().await;
+
+        // Keep ordering: nothing new is written while older events wait
+        let pending = !self.buffer.lock().await.is_empty();
+        let written = if !pending || self.replay().await.is_ok() {
+            self.write(&batch).await
+        } else {
+            Err("backend unavailable".to_string())
+        };
+
+        let Err(error) = written else {
+            return Ok(());
+        };
+        let mut buffer = self.buffer.lock().await;
+        let incoming: usize = batch.iter().map(|append| append.events.len()).sum();
+        if count_events(&*buffer) + incoming > self.max_buffered_events {
+            return Err(format!(
+                "{} (write-ahead buffer full: {} events)",
+                error,
+                count_events(&*buffer)
+            ));
+        }
+        buffer.extend(batch);
+        Ok(())
+    }
+
+    /// Buffered events, optionally only those of one aggregate
+    async fn buffered(&self, aggregate_id: Option<&str>) -> Vec<E> {
+        let buffer = self.buffer.lock().await;
+        buffer
+            .iter()
+            .filter(|append| aggregate_id.map_or(true, |id| append.aggregate_id == id))
+            .flat_map(|append| append.events.iter().cloned())
+            .collect()
+    }
+}
+
+fn count_events<E>(buffer: &VecDeque<Pending<E>>) -> usize {
+    buffer.iter().map(|append| append.events.len()).sum()
+}
+
+#[async_trait]
+impl<E: Event, B: EventStoreBackend<E>> EventStoreBackend<E> for ResilientBackend<E, B> {
+    async fn append(&self, aggregate_id: &str, events: Vec<E>) -> Result<(), String> {
+        self.append_all(vec![Pending::new(aggregate_id.to_string(), events, None)])
+            .await
+    }
+
+    async fn append_batch(&self, batch: Vec<(String, Vec<E>)>) -> Result<(), String> {
+        let batch = batch
+            .into_iter()
+            .map(|(aggregate_id, events)| Pending::new(aggregate_id, events, None))
+            .collect();
+        self.append_all(batch).await
+    }
+
+    async fn append_with_metadata(
+        &self,
+        aggregate_id: &str,
+        events: Vec<E>,
+        metadata: EventMetadata,
+    ) -> Result<(), String> {
+        self.append_all(vec![Pending::new(
+            aggregate_id.to_string(),
+            events,
+            Some(metadata),
+        )])
+        .await
+    }
+
+    /// Stored envelopes followed by buffered ones
+    ///
+    /// Buffered events have an empty `event_id` until they are written, and
+    /// the time they were buffered as their timestamp.
+    async fn get_events_with_metadata(
+        &self,
+        aggregate_id: &str,
+    ) -> Result<Vec<EventEnvelope<E>>, String> {
+        // Not retried: backends without metadata support report an error
+        let mut envelopes = self.inner.get_events_with_metadata(aggregate_id).await?;
+        let mut version = envelopes.last().map_or(0, |envelope| envelope.version);
+        let buffer = self.buffer.lock().await;
+        for append in buffer
+            .iter()
+            .filter(|append| append.aggregate_id == aggregate_id)
+        {
+            for event in &append.events {
+                version += 1;
+                envelopes.push(EventEnvelope::new(
+                    event.clone(),
+                    String::new(),
+                    aggregate_id,
+                    version,
+                    append.buffered_at,
+                    append.metadata.clone().unwrap_or_default(),
+                ));
+            }
+        }
+        Ok(envelopes)
+    }
+
+    async fn get_events(&self, aggregate_id: &str) -> Result<Vec<E>, String> {
+        let mut events = self
+            .call("get_events", || self.inner.get_events(aggregate_id))
+            .await?;
+        events.extend(self.buffered(Some(aggregate_id)).await);
+        Ok(events)
+    }
+
+    async fn get_all_events(&self) -> Result<Vec<E>, String> {
+        let mut events = self
+            .call("get_all_events", || self.inner.get_all_events())
+            .await?;
+        events.extend(self.buffered(None).await);
+        Ok(events)
+    }
+
+    async fn get_events_since(&self, sequence: u64) -> Result<Vec<E>, String> {
+        if self.buffer.lock().await.is_empty() {
+            return self
+                .call("get_events_since", || self.inner.get_events_since(sequence))
+                .await;
+        }
+        // Buffered events follow everything stored, so count from the start
+        let events = self.get_all_events().await?;
+        Ok(events.into_iter().skip(sequence as usize).collect())
+    }
+
+    async fn get_events_after(&self, aggregate_id: &str, version: u64) -> Result<Vec<E>, String> {
+        let events = self.get_events(aggregate_id).await?;
+        Ok(events.into_iter().skip(version as usize).collect())
+    }
+
+    async fn save_snapshot(
+        &self,
+        aggregate_id: &str,
+        snapshot_data: Vec<u8>,
+        version: u64,
+    ) -> Result<(), String> {
+        self.call("save_snapshot", || {
+            self.inner
+                .save_snapshot(aggregate_id, snapshot_data.clone(), version)
+        })
+        .await
+    }
+
+    async fn get_latest_snapshot(&self, aggregate_id: &str) -> Result<(Vec<u8>, u64), String> {
+        // Not retried: a missing snapshot is reported as an error too
+        self.inner.get_latest_snapshot(aggregate_id).await
+    }
+
+    async fn flush(&self) -> Result<(), String> {
+        self.replay_buffered().await?;
+        self.call("flush", || self.inner.flush()).await
+    }
+
+    async fn stats(&self) -> BackendStats {
+        let mut stats = self.inner.stats().await;
+        stats.backend_specific.insert(
+            "buffered_events".to_string(),
+            self.buffered_events().await.to_string(),
+        );
+        stats
+    }
+}
+
+#[cfg(feature = "health")]
+impl<E: Event, B: EventStoreBackend<E>> crate::health::Dependency for ResilientBackend<E, B> {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn check(
+        &self,
+    ) -> std::pin::Pin<
+        Box<dyn std::future::Future<Output = crate::health::DependencyStatus> + Send + '_>,
+    > {
+        use crate::health::DependencyStatus;
+
+        Box::pin(async move {
+            // A health check doubles as a reconnection attempt
+            if let Err(error) = self.replay_buffered().await {
+                return DependencyStatus::Degraded(format!(
+                    "{} events buffered, backend unavailable: {}",
+                    self.buffered_events().await,
+                    error
+                ));
+            }
+
+            // Probe once, without retries, even when nothing was buffered
+            let probe = self.inner.get_events_after(HEALTH_PROBE_AGGREGATE, 0).await;
+            self.connected.store(probe.is_ok(), Ordering::Relaxed);
+            match probe {
+                Ok(_) => DependencyStatus::Healthy,
+                Err(error) => {
+                    DependencyStatus::Unhealthy(format!("backend unavailable: {}", error))
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use super::*;
+    use crate::cqrs::{EventTypeName, InMemoryBackend};
+
+    #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Tick(u32);
+
+    impl EventTypeName for Tick {}
+    impl Event for Tick {}
+
+    /// In-memory backend that fails every operation while `down` is set
+    #[derive(Clone, Default)]
+    struct Flaky {
+        inner: InMemoryBackend<Tick>,
+        down: Arc<AtomicBool>,
+    }
+
+    impl Flaky {
+        fn check(&self) -> Result<(), String> {
+            if self.down.load(Ordering::SeqCst) {
+                Err("connection refused".to_string())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[async_trait]
+    impl EventStoreBackend<Tick> for Flaky {
+        async fn append(&self, aggregate_id: &str, events: Vec<Tick>) -> Result<(), String> {
+            self.check()?;
+            self.inner.append(aggregate_id, events).await
+        }
+
+        async fn append_with_metadata(
+            &self,
+            aggregate_id: &str,
+            events: Vec<Tick>,
+            metadata: EventMetadata,
+        ) -> Result<(), String> {
+            self.check()?;
+            self.inner
+                .append_with_metadata(aggregate_id, events, metadata)
+                .await
+        }
+
+        async fn get_events_with_metadata(
+            &self,
+            aggregate_id: &str,
+        ) -> Result<Vec<EventEnvelope<Tick>>, String> {
+            self.check()?;
+            self.inner.get_events_with_metadata(aggregate_id).await
+        }
+
+        async fn get_events(&self, aggregate_id: &str) -> Result<Vec<Tick>, String> {
+            self.check()?;
+            self.inner.get_events(aggregate_id).await
+        }
+
+        async fn get_all_events(&self) -> Result<Vec<Tick>, String> {
+            self.check()?;
+            self.inner.get_all_events().await
+        }
+
+        async fn get_events_after(
+            &self,
+            aggregate_id: &str,
+            version: u64,
+        ) -> Result<Vec<Tick>, String> {
+            self.check()?;
+            self.inner.get_events_after(aggregate_id, version).await
+        }
+    }
+
+    fn resilient(flaky: &Flaky) -> ResilientBackend<Tick, Flaky> {
+        ResilientBackend::new(flaky.clone())
+            .with_retry(RetryConfig::new(1).with_initial_interval(Duration::from_millis(1)))
+            .with_max_buffered_events(3)
+    }
+
+    #[tokio::test]
+    async fn test_buffers_appends_while_down_and_replays_in_order() {
+        let flaky = Flaky::default();
+        let backend = resilient(&flaky);
+
+        backend.append("a", vec![Tick(1)]).await.unwrap();
+
+        flaky.down.store(true, Ordering::SeqCst);
+        backend.append("a", vec![Tick(2)]).await.unwrap();
+        backend.append("b", vec![Tick(3)]).await.unwrap();
+        assert_eq!(backend.buffered_events().await, 2);
+        assert!(!backend.is_connected());
+
+        // Buffer is bounded
+        let error = backend
+            .append("a", vec![Tick(4), Tick(5)])
+            .await
+            .unwrap_err();
+        assert!(error.contains("buffer full"));
+
+        flaky.down.store(false, Ordering::SeqCst);
+        backend.append("a", vec![Tick(6)]).await.unwrap();
+        assert_eq!(backend.buffered_events().await, 0);
+        assert_eq!(
+            flaky.inner.get_all_events().await.unwrap(),
+            vec![Tick(1), Tick(2), Tick(3), Tick(6)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reads_include_buffered_events() {
+        let flaky = Flaky::default();
+        let backend = resilient(&flaky);
+        backend.append("a", vec![Tick(1)]).await.unwrap();
+
+        // Fail only the write: reads still reach the backend
+        flaky.down.store(true, Ordering::SeqCst);
+        backend.append("a", vec![Tick(2)]).await.unwrap();
+        flaky.down.store(false, Ordering::SeqCst);
+
+        let buffered = backend.buffer.lock().await.len();
+        assert_eq!(buffered, 1);
+        assert_eq!(
+            backend.inner().inner.get_events("a").await.unwrap(),
+            vec![Tick(1)]
+        );
+        assert_eq!(
+            backend.get_events("a").await.unwrap(),
+            vec![Tick(1), Tick(2)]
+        );
+        assert_eq!(backend.get_events_since(1).await.unwrap(), vec![Tick(2)]);
+    }
+
+    #[tokio::test]
+    async fn test_metadata_round_trips_through_buffer() {
+        let flaky = Flaky::default();
+        let backend = resilient(&flaky);
+        let metadata = |causation: &str| {
+            EventMetadata::new()
+                .with_causation_id(causation)
+                .with_correlation_id("checkout-7")
+        };
+
+        backend
+            .append_with_metadata("a", vec![Tick(1)], metadata("cmd-1"))
+            .await
+            .unwrap();
+
+        flaky.down.store(true, Ordering::SeqCst);
+        backend
+            .append_with_metadata("a", vec![Tick(2)], metadata("cmd-2"))
+            .await
+            .unwrap();
+        flaky.down.store(false, Ordering::SeqCst);
+
+        // Buffered events are read back with their metadata
+        let envelopes = backend.get_events_with_metadata("a").await.unwrap();
+        let causations: Vec<_> = envelopes
+            .iter()
+            .map(|envelope| (envelope.version, envelope.causation_id.as_deref()))
+            .collect();
+        assert_eq!(causations, vec![(1, Some("cmd-1")), (2, Some("cmd-2"))]);
+        assert!(envelopes[1].event_id.is_empty());
+
+        // ...and written with it on replay
+        assert_eq!(backend.replay_buffered().await.unwrap(), 1);
+        let stored = flaky.inner.get_events_with_metadata("a").await.unwrap();
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[1].causation_id.as_deref(), Some("cmd-2"));
+        assert_eq!(stored[1].correlation_id.as_deref(), Some("checkout-7"));
+        assert!(!stored[1].event_id.is_empty());
+    }
+
+    #[cfg(feature = "health")]
+    #[tokio::test]
+    async fn test_health_reports_buffer_and_reconnects() {
+        use crate::health::Dependency;
+
+        let flaky = Flaky::default();
+        let backend = resilient(&flaky);
+        assert!(backend.check().await.is_healthy());
+
+        flaky.down.store(true, Ordering::SeqCst);
+        backend.append("a", vec![Tick(1)]).await.unwrap();
+        assert!(backend.check().await.is_degraded());
+
+        flaky.down.store(false, Ordering::SeqCst);
+        assert!(backend.check().await.is_healthy());
+        assert_eq!(flaky.inner.get_all_events().await.unwrap(), vec![Tick(1)]);
+
+        // An outage shows up even with nothing buffered
+        flaky.down.store(true, Ordering::SeqCst);
+        assert!(backend.check().await.is_unhealthy());
+        assert!(!backend.is_connected());
+
+        flaky.down.store(false, Ordering::SeqCst);
+        assert!(backend.check().await.is_healthy());
+        assert!(backend.is_connected());
+    }
+
+    #[tokio::test]
+    async fn test_reads_not_blocked_while_replay_backs_off() {
+        let flaky = Flaky::default();
+        let backend = Arc::new(
+            ResilientBackend::new(flaky.clone())
+                .with_retry(RetryConfig::new(2).with_initial_interval(Duration::from_millis(200))),
+        );
+
+        flaky.down.store(true, Ordering::SeqCst);
+        backend.append("a", vec![Tick(1)]).await.unwrap();
+
+        let replay = {
+            let backend = Arc::clone(&backend);
+            tokio::spawn(async move { backend.replay_buffered().await })
+        };
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let events = tokio::time::timeout(Duration::from_millis(100), backend.buffered_events())
+            .await
+            .expect("buffer locked during replay backoff");
+        assert_eq!(events, 1);
+        assert!(replay.await.unwrap().is_err());
+        assert_eq!(backend.buffered_events().await, 1);
+    }
+}
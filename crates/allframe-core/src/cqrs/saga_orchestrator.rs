@@ -3,12 +3,19 @@
 //! This module provides automatic saga orchestration, eliminating boilerplate
 //! for multi-aggregate transactions with automatic compensation and retry
 //! logic.
+//!
+//! With a [`SagaStore`](super::SagaStore), the orchestrator records each
+//! saga's progress so that sagas interrupted by a restart can be picked up
+//! again with [`SagaOrchestrator::resume`].
 
 use std::{collections::HashMap, fmt, marker::PhantomData, sync::Arc, time::Duration};
 
-use tokio::{sync::RwLock, time::timeout};
+use tokio::{
+    sync::RwLock,
+    time::{timeout, timeout_at},
+};
 
-use super::Event;
+use super::{Event, SagaRecord, SagaStore};
 
 /// Result type for saga operations
 pub type SagaResult<T> = Result<T, SagaError>;
@@ -39,10 +46,21 @@ pub enum SagaError {
         /// Duration that was exceeded
         duration: Duration,
     },
+    /// The saga's overall deadline passed
+    DeadlineExceeded {
+        /// Index of the step that was cancelled
+        step_index: usize,
+        /// The saga's deadline
+        deadline: Duration,
+    },
     /// Invalid step index
     InvalidStep(usize),
     /// Saga already executing
     AlreadyExecuting,
+    /// A resumed saga had already finished with this status
+    AlreadyFinished(SagaStatus),
+    /// Saving or loading the saga's progress failed
+    Persistence(String),
 }
 
 impl fmt::Display for SagaError {
@@ -64,8 +82,22 @@ impl fmt::Display for SagaError {
             } => {
                 write!(f, "Step {} timed out after {:?}", step_index, duration)
             }
+            SagaError::DeadlineExceeded {
+                step_index,
+                deadline,
+            } => {
+                write!(
+                    f,
+                    "Saga deadline of {:?} exceeded at step {}",
+                    deadline, step_index
+                )
+            }
             SagaError::InvalidStep(index) => write!(f, "Invalid step index: {}", index),
             SagaError::AlreadyExecuting => write!(f, "Saga is already executing"),
+            SagaError::AlreadyFinished(status) => {
+                write!(f, "Saga already finished as {:?}", status)
+            }
+            SagaError::Persistence(error) => write!(f, "Saga persistence failed: {}", error),
         }
     }
 }
@@ -73,18 +105,22 @@ impl fmt::Display for SagaError {
 impl std::error::Error for SagaError {}
 
 /// Status of a saga execution
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum SagaStatus {
     /// Saga not yet started
     NotStarted,
     /// Saga is currently executing
     Executing,
+    /// A step failed and completed steps are being compensated
+    Compensating,
     /// Saga completed successfully
     Completed,
     /// Saga failed and compensation was successful
     Compensated,
     /// Saga failed and compensation also failed
     Failed,
+    /// Saga exceeded its deadline and completed steps were compensated
+    TimedOut,
 }
 
 /// Metadata about saga execution
@@ -120,18 +156,127 @@ pub trait SagaStep<E: Event>: Send + Sync {
     }
 }
 
+/// Retry and timeout policy for a saga step
+///
+/// The default policy runs the step once with its own
+/// [`timeout_duration`](SagaStep::timeout_duration). With retries, a step
+/// that fails or times out is retried with exponential backoff before the
+/// saga gives up and compensates. Errors rejected by
+/// [`with_retry_if`](Self::with_retry_if) fail the step immediately;
+/// timeouts are always retried.
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use allframe_core::cqrs::StepPolicy;
+///
+/// let policy = StepPolicy::new()
+///     .with_max_retries(3)
+///     .with_initial_interval(Duration::from_millis(200))
+///     .with_timeout(Duration::from_secs(5))
+///     .with_retry_if(|error| !error.starts_with("declined"));
+/// ```
+#[derive(Clone)]
+pub struct StepPolicy {
+    max_retries: u32,
+    initial_interval: Duration,
+    max_interval: Duration,
+    multiplier: f64,
+    timeout: Option<Duration>,
+    retryable: Arc<dyn Fn(&str) -> bool + Send + Sync>,
+}
+
+impl StepPolicy {
+    /// Create a policy that runs the step once
+    pub fn new() -> Self {
+        Self {
+            max_retries: 0,
+            initial_interval: Duration::from_millis(100),
+            max_interval: Duration::from_secs(10),
+            multiplier: 2.0,
+            timeout: None,
+            retryable: Arc::new(|_| true),
+        }
+    }
+
+    /// Set the number of retries after the first attempt
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Set the delay before the first retry (default: 100ms)
+    pub fn with_initial_interval(mut self, interval: Duration) -> Self {
+        self.initial_interval = interval;
+        self
+    }
+
+    /// Set the maximum delay between retries (default: 10s)
+    pub fn with_max_interval(mut self, interval: Duration) -> Self {
+        self.max_interval = interval;
+        self
+    }
+
+    /// Set the backoff multiplier (default: 2.0)
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier;
+        self
+    }
+
+    /// Set the timeout for each attempt, overriding the step's own
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Only retry errors for which `classifier` returns `true`
+    ///
+    /// Other errors are terminal and trigger compensation right away.
+    pub fn with_retry_if<F>(mut self, classifier: F) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        self.retryable = Arc::new(classifier);
+        self
+    }
+
+    fn interval(&self, retry: u32) -> Duration {
+        let interval = self.initial_interval.as_secs_f64() * self.multiplier.powi(retry as i32);
+        Duration::from_secs_f64(interval.min(self.max_interval.as_secs_f64()))
+    }
+}
+
+impl Default for StepPolicy {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Why a step failed after exhausting its policy
+enum StepFailure {
+    Error(String),
+    Timeout(Duration),
+    DeadlineExceeded,
+}
+
 /// Saga definition with ordered steps
 pub struct SagaDefinition<E: Event> {
     /// Unique saga ID
     id: String,
     /// Ordered list of steps
     steps: Vec<Box<dyn SagaStep<E>>>,
+    /// Retry and timeout policy of each step
+    policies: Vec<StepPolicy>,
     /// Metadata
     metadata: SagaMetadata,
     /// Compensation strategy (optional, for UC-036.7)
     compensation_strategy: Option<super::saga::CompensationStrategy>,
     /// Directory for snapshots (optional, for UC-036.7)
     snapshot_dir: Option<std::path::PathBuf>,
+    /// Overall time limit for executing all steps
+    deadline: Option<Duration>,
+    /// Application data persisted with the saga's progress
+    context: serde_json::Value,
 }
 
 impl<E: Event> SagaDefinition<E> {
@@ -148,14 +293,23 @@ impl<E: Event> SagaDefinition<E> {
             },
             id,
             steps: Vec::new(),
+            policies: Vec::new(),
             compensation_strategy: None,
             snapshot_dir: None,
+            deadline: None,
+            context: serde_json::Value::Null,
         }
     }
 
     /// Add a step to the saga
-    pub fn add_step<S: SagaStep<E> + 'static>(mut self, step: S) -> Self {
+    pub fn add_step<S: SagaStep<E> + 'static>(self, step: S) -> Self {
+        self.add_step_with(step, StepPolicy::default())
+    }
+
+    /// Add a step with a retry and timeout policy
+    pub fn add_step_with<S: SagaStep<E> + 'static>(mut self, step: S, policy: StepPolicy) -> Self {
         self.steps.push(Box::new(step));
+        self.policies.push(policy);
         self.metadata.total_steps = self.steps.len();
         self
     }
@@ -186,6 +340,61 @@ impl<E: Event> SagaDefinition<E> {
         self.snapshot_dir = Some(dir.to_path_buf());
         self
     }
+
+    /// Set an overall deadline for executing the saga's steps
+    ///
+    /// When the deadline passes, the in-flight step is cancelled (its future
+    /// is dropped), completed steps are compensated and the saga ends as
+    /// [`SagaStatus::TimedOut`]. Compensation itself isn't bounded by the
+    /// deadline.
+    pub fn with_deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Set application data to persist with the saga's progress
+    ///
+    /// The context is stored in each [`SagaRecord`], so after a restart the
+    /// application can rebuild the saga's steps from it and resume it.
+    pub fn with_context(mut self, context: serde_json::Value) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Get the application data persisted with the saga
+    pub fn context(&self) -> &serde_json::Value {
+        &self.context
+    }
+
+    fn record(&self, error: Option<&str>) -> SagaRecord {
+        SagaRecord {
+            id: self.id.clone(),
+            status: self.metadata.status.clone(),
+            steps_executed: self.metadata.steps_executed,
+            total_steps: self.metadata.total_steps,
+            context: self.context.clone(),
+            error: error.map(str::to_string),
+            updated_at: self.metadata.updated_at,
+        }
+    }
+}
+
+/// Where a run of a saga starts
+enum Start {
+    /// Execute the steps from this index on
+    Step(usize),
+    /// Compensate the executed steps after this failure
+    Compensation(SagaError),
+}
+
+impl SagaError {
+    /// The failure reason persisted in a [`SagaRecord`]
+    fn reason(&self) -> String {
+        match self {
+            SagaError::StepFailed { error, .. } => error.clone(),
+            other => other.to_string(),
+        }
+    }
 }
 
 /// Orchestrator for executing sagas
@@ -194,6 +403,8 @@ pub struct SagaOrchestrator<E: Event> {
     sagas: Arc<RwLock<HashMap<String, SagaMetadata>>>,
     /// Completed sagas history
     history: Arc<RwLock<Vec<SagaMetadata>>>,
+    /// Where saga progress is persisted, if anywhere
+    store: Option<Arc<dyn SagaStore>>,
     _phantom: PhantomData<E>,
 }
 
@@ -203,106 +414,239 @@ impl<E: Event> SagaOrchestrator<E> {
         Self {
             sagas: Arc::new(RwLock::new(HashMap::new())),
             history: Arc::new(RwLock::new(Vec::new())),
+            store: None,
             _phantom: PhantomData,
         }
     }
 
+    /// Persist saga progress to `store`
+    ///
+    /// Progress is saved when a saga starts, after each step, when
+    /// compensation starts and when the saga finishes. A failed save stops
+    /// the saga with [`SagaError::Persistence`] as if the process had
+    /// crashed: nothing is compensated, and [`resume`](Self::resume)
+    /// continues from the last saved progress. Steps of resumable sagas must
+    /// therefore tolerate being executed or compensated again.
+    pub fn with_store<S: SagaStore + 'static>(mut self, store: S) -> Self {
+        self.store = Some(Arc::new(store));
+        self
+    }
+
     /// Execute a saga with automatic compensation on failure
-    pub async fn execute(&self, mut saga: SagaDefinition<E>) -> SagaResult<Vec<E>> {
-        // Check if saga is already running
-        {
-            let sagas = self.sagas.read().await;
-            if sagas.contains_key(&saga.id) {
-                return Err(SagaError::AlreadyExecuting);
-            }
+    pub async fn execute(&self, saga: SagaDefinition<E>) -> SagaResult<Vec<E>> {
+        self.run(saga, Start::Step(0)).await
+    }
+
+    /// Continue a saga from its persisted progress
+    ///
+    /// `saga` must be rebuilt with the same ID and steps as the interrupted
+    /// one, typically from its [`SagaRecord::context`]. What happens depends
+    /// on the stored record:
+    /// - none: the saga is executed from the start
+    /// - [`SagaStatus::Executing`]: the remaining steps are executed, and
+    ///   only their events are returned
+    /// - [`SagaStatus::Compensating`]: compensation is finished and the
+    ///   original failure is returned as [`SagaError::StepFailed`]
+    /// - any other status: [`SagaError::AlreadyFinished`]
+    ///
+    /// Without a store this is the same as [`execute`](Self::execute).
+    pub async fn resume(&self, mut saga: SagaDefinition<E>) -> SagaResult<Vec<E>> {
+        let record = match &self.store {
+            Some(store) => store.load(&saga.id).await.map_err(SagaError::Persistence)?,
+            None => None,
+        };
+        let Some(record) = record else {
+            return self.run(saga, Start::Step(0)).await;
+        };
+        if !record.is_incomplete() {
+            return Err(SagaError::AlreadyFinished(record.status));
+        }
+        if record.steps_executed > saga.steps.len() {
+            return Err(SagaError::InvalidStep(record.steps_executed));
         }
 
-        // Mark as executing
-        saga.metadata.status = SagaStatus::Executing;
-        saga.metadata.updated_at = std::time::SystemTime::now();
+        if record.status != SagaStatus::Compensating {
+            return self.run(saga, Start::Step(record.steps_executed)).await;
+        }
+        saga.metadata.steps_executed = record.steps_executed;
+        let error = SagaError::StepFailed {
+            step_index: record.steps_executed,
+            step_name: saga
+                .steps
+                .get(record.steps_executed)
+                .map(|step| step.name().to_string())
+                .unwrap_or_default(),
+            error: record.error.unwrap_or_default(),
+        };
+        self.run(saga, Start::Compensation(error)).await
+    }
+
+    /// Records of persisted sagas that were executing or compensating
+    ///
+    /// Empty without a store.
+    pub async fn incomplete_sagas(&self) -> SagaResult<Vec<SagaRecord>> {
+        match &self.store {
+            Some(store) => store.incomplete().await.map_err(SagaError::Persistence),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn run(&self, mut saga: SagaDefinition<E>, start: Start) -> SagaResult<Vec<E>> {
+        // Mark as executing, unless it already is
         {
             let mut sagas = self.sagas.write().await;
+            if sagas.contains_key(&saga.id) {
+                return Err(SagaError::AlreadyExecuting);
+            }
+            saga.metadata.status = SagaStatus::Executing;
+            saga.metadata.updated_at = std::time::SystemTime::now();
             sagas.insert(saga.id.clone(), saga.metadata.clone());
         }
 
-        let mut all_events = Vec::new();
-        let mut executed_steps = 0;
+        let result = match start {
+            Start::Step(index) => self.run_steps(&mut saga, index).await,
+            Start::Compensation(error) => self
+                .compensate_saga(&mut saga, &error, SagaStatus::Compensated)
+                .await
+                .and(Err(error)),
+        };
+
+        // Remove from active sagas
+        self.sagas.write().await.remove(&saga.id);
+        result
+    }
 
-        // Execute each step
-        for (index, step) in saga.steps.iter().enumerate() {
-            // Execute step with timeout
-            let step_timeout = step.timeout_duration();
-            let result = timeout(step_timeout, step.execute()).await;
+    /// Execute the steps from `start` on, compensating on failure
+    async fn run_steps(&self, saga: &mut SagaDefinition<E>, start: usize) -> SagaResult<Vec<E>> {
+        saga.metadata.steps_executed = start;
+        self.save(saga, None).await?;
 
-            match result {
-                Ok(Ok(events)) => {
+        let mut all_events = Vec::new();
+        let mut failure = None;
+        let deadline = saga
+            .deadline
+            .map(|deadline| tokio::time::Instant::now() + deadline);
+
+        // Execute each remaining step
+        for (index, (step, policy)) in saga
+            .steps
+            .iter()
+            .zip(&saga.policies)
+            .enumerate()
+            .skip(start)
+        {
+            let attempt = Self::run_step(step.as_ref(), policy);
+            let result = match deadline {
+                // Don't start a step once the deadline has passed
+                Some(at) if at <= tokio::time::Instant::now() => Err(StepFailure::DeadlineExceeded),
+                Some(at) => timeout_at(at, attempt)
+                    .await
+                    .unwrap_or(Err(StepFailure::DeadlineExceeded)),
+                None => attempt.await,
+            };
+
+            failure = match result {
+                Ok(events) => {
                     // Step succeeded
                     all_events.extend(events);
-                    executed_steps += 1;
-                    saga.metadata.steps_executed = executed_steps;
+                    saga.metadata.steps_executed = index + 1;
                     saga.metadata.updated_at = std::time::SystemTime::now();
+                    self.save(saga, None).await?;
+                    continue;
                 }
-                Ok(Err(error)) => {
-                    // Step failed - compensate previous steps
-                    saga.metadata.status = SagaStatus::Failed;
-                    let compensation_result = self.compensate_steps(&saga.steps[0..index]).await;
-
-                    // Remove from active sagas
-                    {
-                        let mut sagas = self.sagas.write().await;
-                        sagas.remove(&saga.id);
-                    }
-
-                    // Add to history
-                    {
-                        let mut history = self.history.write().await;
-                        saga.metadata.status = if compensation_result.is_ok() {
-                            SagaStatus::Compensated
-                        } else {
-                            SagaStatus::Failed
-                        };
-                        history.push(saga.metadata.clone());
-                    }
-
-                    return Err(SagaError::StepFailed {
-                        step_index: index,
-                        step_name: step.name().to_string(),
-                        error,
-                    });
-                }
-                Err(_) => {
-                    // Timeout
-                    saga.metadata.status = SagaStatus::Failed;
-                    let _ = self.compensate_steps(&saga.steps[0..index]).await;
-
-                    {
-                        let mut sagas = self.sagas.write().await;
-                        sagas.remove(&saga.id);
-                    }
-
-                    return Err(SagaError::Timeout {
-                        step_index: index,
-                        duration: step_timeout,
-                    });
-                }
-            }
+                Err(StepFailure::Error(error)) => Some(SagaError::StepFailed {
+                    step_index: index,
+                    step_name: step.name().to_string(),
+                    error,
+                }),
+                Err(StepFailure::Timeout(step_timeout)) => Some(SagaError::Timeout {
+                    step_index: index,
+                    duration: step_timeout,
+                }),
+                // The in-flight step has been dropped
+                Err(StepFailure::DeadlineExceeded) => Some(SagaError::DeadlineExceeded {
+                    step_index: index,
+                    deadline: saga.deadline.unwrap_or_default(),
+                }),
+            };
+            break;
+        }
+
+        if let Some(error) = failure {
+            // Compensate the completed steps
+            let status = match error {
+                SagaError::DeadlineExceeded { .. } => SagaStatus::TimedOut,
+                _ => SagaStatus::Compensated,
+            };
+            self.compensate_saga(saga, &error, status).await?;
+            return Err(error);
         }
 
         // All steps completed successfully
         saga.metadata.status = SagaStatus::Completed;
+        self.finish(saga, None).await?;
+        Ok(all_events)
+    }
+
+    /// Compensate the executed steps after `error`, ending as `status` or,
+    /// if compensation fails, [`SagaStatus::Failed`]
+    async fn compensate_saga(
+        &self,
+        saga: &mut SagaDefinition<E>,
+        error: &SagaError,
+        status: SagaStatus,
+    ) -> SagaResult<()> {
+        let reason = error.reason();
+        saga.metadata.status = SagaStatus::Compensating;
         saga.metadata.updated_at = std::time::SystemTime::now();
+        self.save(saga, Some(&reason)).await?;
+
+        let executed = saga.metadata.steps_executed;
+        let compensation_result = self.compensate_steps(&saga.steps[0..executed]).await;
+        saga.metadata.status = if compensation_result.is_ok() {
+            status
+        } else {
+            SagaStatus::Failed
+        };
+        self.finish(saga, Some(&reason)).await
+    }
 
-        // Remove from active and add to history
-        {
-            let mut sagas = self.sagas.write().await;
-            sagas.remove(&saga.id);
-        }
-        {
-            let mut history = self.history.write().await;
-            history.push(saga.metadata);
+    /// Add a finished saga to the history and persist its outcome
+    async fn finish(&self, saga: &mut SagaDefinition<E>, error: Option<&str>) -> SagaResult<()> {
+        saga.metadata.updated_at = std::time::SystemTime::now();
+        self.history.write().await.push(saga.metadata.clone());
+        self.save(saga, error).await
+    }
+
+    async fn save(&self, saga: &SagaDefinition<E>, error: Option<&str>) -> SagaResult<()> {
+        match &self.store {
+            Some(store) => store
+                .save(&saga.record(error))
+                .await
+                .map_err(SagaError::Persistence),
+            None => Ok(()),
         }
+    }
 
-        Ok(all_events)
+    /// Execute a step, retrying failures and timeouts as the policy allows
+    async fn run_step(step: &dyn SagaStep<E>, policy: &StepPolicy) -> Result<Vec<E>, StepFailure> {
+        let step_timeout = policy.timeout.unwrap_or_else(|| step.timeout_duration());
+        let mut retry = 0;
+        loop {
+            let failure = match timeout(step_timeout, step.execute()).await {
+                Ok(Ok(events)) => return Ok(events),
+                Ok(Err(error)) if !(policy.retryable)(&error) => {
+                    return Err(StepFailure::Error(error))
+                }
+                Ok(Err(error)) => StepFailure::Error(error),
+                Err(_) => StepFailure::Timeout(step_timeout),
+            };
+            if retry >= policy.max_retries {
+                return Err(failure);
+            }
+            tokio::time::sleep(policy.interval(retry)).await;
+            retry += 1;
+        }
     }
 
     /// Compensate (rollback) executed steps in reverse order
@@ -354,6 +698,7 @@ impl<E: Event> Clone for SagaOrchestrator<E> {
         Self {
             sagas: Arc::clone(&self.sagas),
             history: Arc::clone(&self.history),
+            store: self.store.clone(),
             _phantom: PhantomData,
         }
     }
@@ -503,4 +848,382 @@ mod tests {
 
         assert_eq!(orchestrator.history_count().await, 2);
     }
+
+    /// Fails with `error` on the first `failures` attempts
+    struct FlakyStep {
+        failures: u32,
+        error: &'static str,
+        attempts: Arc<std::sync::atomic::AtomicU32>,
+    }
+
+    #[async_trait::async_trait]
+    impl SagaStep<TestEvent> for FlakyStep {
+        async fn execute(&self) -> Result<Vec<TestEvent>, String> {
+            let attempt = self
+                .attempts
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if attempt < self.failures {
+                return Err(self.error.to_string());
+            }
+            Ok(vec![TestEvent::Debited {
+                account: "A".to_string(),
+                amount: 1.0,
+            }])
+        }
+
+        async fn compensate(&self) -> Result<Vec<TestEvent>, String> {
+            Ok(vec![])
+        }
+
+        fn name(&self) -> &str {
+            "FlakyStep"
+        }
+    }
+
+    fn flaky(failures: u32, error: &'static str) -> (FlakyStep, Arc<std::sync::atomic::AtomicU32>) {
+        let attempts = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let step = FlakyStep {
+            failures,
+            error,
+            attempts: Arc::clone(&attempts),
+        };
+        (step, attempts)
+    }
+
+    fn fast_retries(max_retries: u32) -> StepPolicy {
+        StepPolicy::new()
+            .with_max_retries(max_retries)
+            .with_initial_interval(Duration::from_millis(1))
+    }
+
+    #[tokio::test]
+    async fn test_step_retries_transient_failures() {
+        let orchestrator = SagaOrchestrator::<TestEvent>::new();
+        let (step, attempts) = flaky(2, "connection reset");
+
+        let saga = SagaDefinition::new("retry-1").add_step_with(step, fast_retries(3));
+
+        assert_eq!(orchestrator.execute(saga).await.unwrap().len(), 1);
+        assert_eq!(attempts.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_step_fails_after_exhausting_retries() {
+        let orchestrator = SagaOrchestrator::<TestEvent>::new();
+        let (step, attempts) = flaky(5, "connection reset");
+
+        let saga = SagaDefinition::new("retry-2").add_step_with(step, fast_retries(2));
+
+        let error = orchestrator.execute(saga).await.unwrap_err();
+        assert!(matches!(error, SagaError::StepFailed { step_index: 0, .. }));
+        assert_eq!(attempts.load(std::sync::atomic::Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_terminal_errors_are_not_retried() {
+        let orchestrator = SagaOrchestrator::<TestEvent>::new();
+        let (step, attempts) = flaky(1, "declined: insufficient funds");
+
+        let policy = fast_retries(3).with_retry_if(|error| !error.starts_with("declined"));
+        let saga = SagaDefinition::new("retry-3")
+            .add_step(DebitStep {
+                account: "A".to_string(),
+                amount: 10.0,
+            })
+            .add_step_with(step, policy);
+
+        assert!(orchestrator.execute(saga).await.is_err());
+        assert_eq!(attempts.load(std::sync::atomic::Ordering::SeqCst), 1);
+        assert_eq!(
+            orchestrator.get_history().await[0].status,
+            SagaStatus::Compensated
+        );
+    }
+
+    #[tokio::test]
+    async fn test_step_policy_timeout_is_retried() {
+        struct SlowOnceStep {
+            attempts: std::sync::atomic::AtomicU32,
+        }
+
+        #[async_trait::async_trait]
+        impl SagaStep<TestEvent> for SlowOnceStep {
+            async fn execute(&self) -> Result<Vec<TestEvent>, String> {
+                if self
+                    .attempts
+                    .fetch_add(1, std::sync::atomic::Ordering::SeqCst)
+                    == 0
+                {
+                    tokio::time::sleep(Duration::from_secs(60)).await;
+                }
+                Ok(vec![])
+            }
+
+            async fn compensate(&self) -> Result<Vec<TestEvent>, String> {
+                Ok(vec![])
+            }
+
+            fn name(&self) -> &str {
+                "SlowOnceStep"
+            }
+        }
+
+        let orchestrator = SagaOrchestrator::<TestEvent>::new();
+        let saga = SagaDefinition::new("retry-4").add_step_with(
+            SlowOnceStep {
+                attempts: std::sync::atomic::AtomicU32::new(0),
+            },
+            fast_retries(1).with_timeout(Duration::from_millis(20)),
+        );
+
+        assert!(orchestrator.execute(saga).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_saga_deadline_cancels_step_and_compensates() {
+        struct HangingStep {
+            dropped: Arc<std::sync::atomic::AtomicBool>,
+        }
+
+        struct DropFlag(Arc<std::sync::atomic::AtomicBool>);
+
+        impl Drop for DropFlag {
+            fn drop(&mut self) {
+                self.0.store(true, std::sync::atomic::Ordering::SeqCst);
+            }
+        }
+
+        #[async_trait::async_trait]
+        impl SagaStep<TestEvent> for HangingStep {
+            async fn execute(&self) -> Result<Vec<TestEvent>, String> {
+                let _flag = DropFlag(Arc::clone(&self.dropped));
+                std::future::pending::<()>().await;
+                Ok(vec![])
+            }
+
+            async fn compensate(&self) -> Result<Vec<TestEvent>, String> {
+                Ok(vec![])
+            }
+
+            fn name(&self) -> &str {
+                "HangingStep"
+            }
+        }
+
+        let orchestrator = SagaOrchestrator::<TestEvent>::new();
+        let dropped = Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let saga = SagaDefinition::new("deadline-1")
+            .add_step(DebitStep {
+                account: "A".to_string(),
+                amount: 10.0,
+            })
+            .add_step(HangingStep {
+                dropped: Arc::clone(&dropped),
+            })
+            .with_deadline(Duration::from_millis(50));
+
+        let error = orchestrator.execute(saga).await.unwrap_err();
+
+        assert!(matches!(
+            error,
+            SagaError::DeadlineExceeded { step_index: 1, .. }
+        ));
+        assert!(dropped.load(std::sync::atomic::Ordering::SeqCst));
+        assert_eq!(orchestrator.running_count().await, 0);
+        let history = orchestrator.get_history().await;
+        assert_eq!(history[0].status, SagaStatus::TimedOut);
+        assert_eq!(history[0].steps_executed, 1);
+    }
+
+    #[tokio::test]
+    async fn test_saga_deadline_bounds_retries() {
+        let orchestrator = SagaOrchestrator::<TestEvent>::new();
+        let (step, attempts) = flaky(u32::MAX, "connection reset");
+
+        let policy = StepPolicy::new()
+            .with_max_retries(u32::MAX)
+            .with_initial_interval(Duration::from_millis(10))
+            .with_max_interval(Duration::from_millis(10));
+        let saga = SagaDefinition::new("deadline-2")
+            .add_step_with(step, policy)
+            .with_deadline(Duration::from_millis(100));
+
+        let error = orchestrator.execute(saga).await.unwrap_err();
+
+        assert!(matches!(error, SagaError::DeadlineExceeded { .. }));
+        assert!(attempts.load(std::sync::atomic::Ordering::SeqCst) > 1);
+    }
+
+    /// Counts executions and compensations, failing when `fail` is set
+    struct CountingStep {
+        name: &'static str,
+        fail: bool,
+        executed: Arc<std::sync::atomic::AtomicU32>,
+        compensated: Arc<std::sync::atomic::AtomicU32>,
+    }
+
+    #[async_trait::async_trait]
+    impl SagaStep<TestEvent> for CountingStep {
+        async fn execute(&self) -> Result<Vec<TestEvent>, String> {
+            self.executed
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            if self.fail {
+                return Err("out of stock".to_string());
+            }
+            Ok(vec![TestEvent::Debited {
+                account: self.name.to_string(),
+                amount: 1.0,
+            }])
+        }
+
+        async fn compensate(&self) -> Result<Vec<TestEvent>, String> {
+            self.compensated
+                .fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Ok(vec![])
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+    }
+
+    #[derive(Default)]
+    struct Counters {
+        executed: Arc<std::sync::atomic::AtomicU32>,
+        compensated: Arc<std::sync::atomic::AtomicU32>,
+    }
+
+    impl Counters {
+        fn step(&self, name: &'static str, fail: bool) -> CountingStep {
+            CountingStep {
+                name,
+                fail,
+                executed: Arc::clone(&self.executed),
+                compensated: Arc::clone(&self.compensated),
+            }
+        }
+
+        fn counts(&self) -> (u32, u32) {
+            (
+                self.executed.load(std::sync::atomic::Ordering::SeqCst),
+                self.compensated.load(std::sync::atomic::Ordering::SeqCst),
+            )
+        }
+    }
+
+    fn stored(id: &str, status: SagaStatus, steps_executed: usize) -> SagaRecord {
+        SagaRecord {
+            id: id.to_string(),
+            status,
+            steps_executed,
+            total_steps: 2,
+            context: serde_json::Value::Null,
+            error: Some("out of stock".to_string()),
+            updated_at: std::time::SystemTime::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_store_records_progress_and_failure() {
+        let store = crate::cqrs::InMemorySagaStore::new();
+        let orchestrator = SagaOrchestrator::<TestEvent>::new().with_store(store.clone());
+        let counters = Counters::default();
+
+        let saga = SagaDefinition::new("order-1")
+            .add_step(counters.step("reserve", false))
+            .add_step(counters.step("confirm", true))
+            .with_context(serde_json::json!({ "order_id": 1 }));
+        orchestrator.execute(saga).await.unwrap_err();
+
+        let record = store.load("order-1").await.unwrap().unwrap();
+        assert_eq!(record.status, SagaStatus::Compensated);
+        assert_eq!(record.steps_executed, 1);
+        assert_eq!(record.error.as_deref(), Some("out of stock"));
+        assert_eq!(record.context["order_id"], 1);
+        assert_eq!(counters.counts(), (2, 1));
+        assert!(orchestrator.incomplete_sagas().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_resume_continues_after_last_completed_step() {
+        let store = crate::cqrs::InMemorySagaStore::new();
+        store
+            .save(&stored("order-2", SagaStatus::Executing, 1))
+            .await
+            .unwrap();
+        let orchestrator = SagaOrchestrator::<TestEvent>::new().with_store(store.clone());
+        assert_eq!(orchestrator.incomplete_sagas().await.unwrap().len(), 1);
+
+        let counters = Counters::default();
+        let saga = SagaDefinition::new("order-2")
+            .add_step(counters.step("reserve", false))
+            .add_step(counters.step("confirm", false));
+        let events = orchestrator.resume(saga).await.unwrap();
+
+        // Only the confirm step ran
+        assert_eq!(events.len(), 1);
+        assert_eq!(counters.counts(), (1, 0));
+        let record = store.load("order-2").await.unwrap().unwrap();
+        assert_eq!(record.status, SagaStatus::Completed);
+        assert_eq!(record.steps_executed, 2);
+
+        // A finished saga isn't run again
+        let saga = SagaDefinition::new("order-2").add_step(counters.step("reserve", false));
+        assert!(matches!(
+            orchestrator.resume(saga).await,
+            Err(SagaError::AlreadyFinished(SagaStatus::Completed))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_resume_finishes_compensation() {
+        let store = crate::cqrs::InMemorySagaStore::new();
+        store
+            .save(&stored("order-3", SagaStatus::Compensating, 1))
+            .await
+            .unwrap();
+        let orchestrator = SagaOrchestrator::<TestEvent>::new().with_store(store.clone());
+
+        let counters = Counters::default();
+        let saga = SagaDefinition::new("order-3")
+            .add_step(counters.step("reserve", false))
+            .add_step(counters.step("confirm", true));
+        let error = orchestrator.resume(saga).await.unwrap_err();
+
+        assert_eq!(error.to_string(), "Step 1 (confirm) failed: out of stock");
+        assert_eq!(counters.counts(), (0, 1));
+        assert_eq!(
+            store.load("order-3").await.unwrap().unwrap().status,
+            SagaStatus::Compensated
+        );
+    }
+
+    #[tokio::test]
+    async fn test_failed_save_stops_saga_without_compensating() {
+        struct BrokenStore;
+
+        #[async_trait::async_trait]
+        impl SagaStore for BrokenStore {
+            async fn save(&self, _record: &SagaRecord) -> Result<(), String> {
+                Err("disk full".to_string())
+            }
+
+            async fn load(&self, _id: &str) -> Result<Option<SagaRecord>, String> {
+                Ok(None)
+            }
+
+            async fn incomplete(&self) -> Result<Vec<SagaRecord>, String> {
+                Ok(Vec::new())
+            }
+        }
+
+        let orchestrator = SagaOrchestrator::<TestEvent>::new().with_store(BrokenStore);
+        let counters = Counters::default();
+        let saga = SagaDefinition::new("order-4").add_step(counters.step("reserve", false));
+
+        let error = orchestrator.execute(saga).await.unwrap_err();
+        assert!(matches!(error, SagaError::Persistence(ref e) if e == "disk full"));
+        assert_eq!(counters.counts(), (0, 0));
+        assert_eq!(orchestrator.running_count().await, 0);
+    }
 }
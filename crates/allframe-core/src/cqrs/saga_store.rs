@@ -0,0 +1,295 @@
+//! Saga progress persistence for resuming after a restart
+//!
+//! A [`SagaOrchestrator`](super::SagaOrchestrator) with a store records each
+//! saga's progress as it runs. After a crash, the sagas that were still
+//! executing or compensating are listed with [`SagaStore::incomplete`],
+//! rebuilt by the application from their [`SagaRecord::context`] and passed
+//! to [`SagaOrchestrator::resume`](super::SagaOrchestrator::resume).
+//!
+//! Backends:
+//! - [`InMemorySagaStore`] - per-process, lost on restart
+//! - `SqliteSagaStore` - persistent (requires `cqrs-sqlite` feature)
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::SystemTime,
+};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use super::SagaStatus;
+
+/// Persisted progress of a saga
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SagaRecord {
+    /// Saga ID
+    pub id: String,
+    /// Status when the record was saved
+    pub status: SagaStatus,
+    /// Number of steps that completed (and, while compensating, still need
+    /// compensating)
+    pub steps_executed: usize,
+    /// Number of steps in the saga
+    pub total_steps: usize,
+    /// Application data needed to rebuild the saga's steps
+    pub context: serde_json::Value,
+    /// Why the saga is compensating or failed
+    pub error: Option<String>,
+    /// When the record was saved
+    pub updated_at: SystemTime,
+}
+
+impl SagaRecord {
+    /// Whether the saga was still executing or compensating
+    pub fn is_incomplete(&self) -> bool {
+        matches!(
+            self.status,
+            SagaStatus::NotStarted | SagaStatus::Executing | SagaStatus::Compensating
+        )
+    }
+}
+
+/// Storage for saga progress
+#[async_trait]
+pub trait SagaStore: Send + Sync {
+    /// Insert or replace the record with the same ID
+    async fn save(&self, record: &SagaRecord) -> Result<(), String>;
+
+    /// Load a saga's record
+    async fn load(&self, id: &str) -> Result<Option<SagaRecord>, String>;
+
+    /// Records of sagas that were executing or compensating, least recently
+    /// saved first
+    async fn incomplete(&self) -> Result<Vec<SagaRecord>, String>;
+}
+
+#[async_trait]
+impl<T: SagaStore + ?Sized> SagaStore for Arc<T> {
+    async fn save(&self, record: &SagaRecord) -> Result<(), String> {
+        (**self).save(record).await
+    }
+
+    async fn load(&self, id: &str) -> Result<Option<SagaRecord>, String> {
+        (**self).load(id).await
+    }
+
+    async fn incomplete(&self) -> Result<Vec<SagaRecord>, String> {
+        (**self).incomplete().await
+    }
+}
+
+/// In-memory saga store for tests and single-process use
+///
+/// Cloning shares the underlying records.
+#[derive(Clone, Default)]
+pub struct InMemorySagaStore {
+    records: Arc<Mutex<HashMap<String, SagaRecord>>>,
+}
+
+impl InMemorySagaStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl SagaStore for InMemorySagaStore {
+    async fn save(&self, record: &SagaRecord) -> Result<(), String> {
+        let mut records = self.records.lock().unwrap();
+        records.insert(record.id.clone(), record.clone());
+        Ok(())
+    }
+
+    async fn load(&self, id: &str) -> Result<Option<SagaRecord>, String> {
+        Ok(self.records.lock().unwrap().get(id).cloned())
+    }
+
+    async fn incomplete(&self) -> Result<Vec<SagaRecord>, String> {
+        let mut incomplete: Vec<SagaRecord> = self
+            .records
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|record| record.is_incomplete())
+            .cloned()
+            .collect();
+        incomplete.sort_by_key(|record| record.updated_at);
+        Ok(incomplete)
+    }
+}
+
+#[cfg(feature = "cqrs-sqlite")]
+pub use sqlite::SqliteSagaStore;
+
+#[cfg(feature = "cqrs-sqlite")]
+mod sqlite {
+    use std::sync::{Arc, Mutex};
+
+    use async_trait::async_trait;
+    use rusqlite::{Connection, OptionalExtension};
+
+    use super::{SagaRecord, SagaStore};
+
+    /// SQLite-backed saga store
+    ///
+    /// Records are stored as JSON, one row per saga. All operations run via
+    /// `spawn_blocking`.
+    #[derive(Clone)]
+    pub struct SqliteSagaStore {
+        conn: Arc<Mutex<Connection>>,
+    }
+
+    impl SqliteSagaStore {
+        /// Open (or create) a store at the given path
+        pub async fn new(path: &str) -> Result<Self, String> {
+            let path = path.to_string();
+            let conn = tokio::task::spawn_blocking(move || {
+                let conn = Connection::open(&path).map_err(|e| format!("SQLite open: {}", e))?;
+                conn.execute_batch(
+                    "PRAGMA journal_mode=WAL;
+                    CREATE TABLE IF NOT EXISTS sagas (
+                        id TEXT PRIMARY KEY,
+                        incomplete INTEGER NOT NULL,
+                        record TEXT NOT NULL,
+                        seq INTEGER NOT NULL
+                    );
+                    CREATE INDEX IF NOT EXISTS idx_sagas_incomplete
+                        ON sagas(incomplete, seq);",
+                )
+                .map_err(|e| format!("Schema init: {}", e))?;
+                Ok::<_, String>(conn)
+            })
+            .await
+            .map_err(|e| format!("spawn_blocking: {}", e))??;
+
+            Ok(Self {
+                conn: Arc::new(Mutex::new(conn)),
+            })
+        }
+    }
+
+    fn decode(json: String) -> Result<SagaRecord, String> {
+        serde_json::from_str(&json).map_err(|e| format!("Decode saga record: {}", e))
+    }
+
+    #[async_trait]
+    impl SagaStore for SqliteSagaStore {
+        async fn save(&self, record: &SagaRecord) -> Result<(), String> {
+            let conn = Arc::clone(&self.conn);
+            let id = record.id.clone();
+            let incomplete = record.is_incomplete();
+            let json =
+                serde_json::to_string(record).map_err(|e| format!("Encode saga record: {}", e))?;
+            tokio::task::spawn_blocking(move || {
+                let conn = conn.lock().map_err(|e| format!("Lock: {}", e))?;
+                // `seq` orders records by when they were last saved
+                conn.execute(
+                    "INSERT INTO sagas (id, incomplete, record, seq)
+                     VALUES (?1, ?2, ?3, (SELECT COALESCE(MAX(seq), 0) + 1 FROM sagas))
+                     ON CONFLICT(id) DO UPDATE SET
+                        incomplete = excluded.incomplete,
+                        record = excluded.record,
+                        seq = excluded.seq",
+                    rusqlite::params![id, incomplete, json],
+                )
+                .map_err(|e| format!("Upsert: {}", e))?;
+                Ok(())
+            })
+            .await
+            .map_err(|e| format!("spawn_blocking: {}", e))?
+        }
+
+        async fn load(&self, id: &str) -> Result<Option<SagaRecord>, String> {
+            let conn = Arc::clone(&self.conn);
+            let id = id.to_string();
+            tokio::task::spawn_blocking(move || {
+                let conn = conn.lock().map_err(|e| format!("Lock: {}", e))?;
+                conn.query_row(
+                    "SELECT record FROM sagas WHERE id = ?1",
+                    rusqlite::params![id],
+                    |row| row.get::<_, String>(0),
+                )
+                .optional()
+                .map_err(|e| format!("Query: {}", e))?
+                .map(decode)
+                .transpose()
+            })
+            .await
+            .map_err(|e| format!("spawn_blocking: {}", e))?
+        }
+
+        async fn incomplete(&self) -> Result<Vec<SagaRecord>, String> {
+            let conn = Arc::clone(&self.conn);
+            tokio::task::spawn_blocking(move || {
+                let conn = conn.lock().map_err(|e| format!("Lock: {}", e))?;
+                let mut stmt = conn
+                    .prepare("SELECT record FROM sagas WHERE incomplete = 1 ORDER BY seq")
+                    .map_err(|e| format!("Prepare: {}", e))?;
+                let rows = stmt
+                    .query_map([], |row| row.get::<_, String>(0))
+                    .map_err(|e| format!("Query: {}", e))?;
+                rows.map(|row| decode(row.map_err(|e| format!("Row: {}", e))?))
+                    .collect()
+            })
+            .await
+            .map_err(|e| format!("spawn_blocking: {}", e))?
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: &str, status: SagaStatus) -> SagaRecord {
+        SagaRecord {
+            id: id.to_string(),
+            status,
+            steps_executed: 1,
+            total_steps: 2,
+            context: serde_json::json!({ "order_id": id }),
+            error: None,
+            updated_at: SystemTime::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_store_lists_incomplete_sagas() {
+        let store = InMemorySagaStore::new();
+        store
+            .save(&record("a", SagaStatus::Executing))
+            .await
+            .unwrap();
+        store
+            .save(&record("b", SagaStatus::Completed))
+            .await
+            .unwrap();
+        store
+            .save(&record("c", SagaStatus::Compensating))
+            .await
+            .unwrap();
+
+        let ids: Vec<String> = store
+            .incomplete()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|r| r.id)
+            .collect();
+        assert_eq!(ids, ["a", "c"]);
+
+        store
+            .save(&record("a", SagaStatus::Completed))
+            .await
+            .unwrap();
+        assert_eq!(store.incomplete().await.unwrap().len(), 1);
+        assert_eq!(
+            store.load("a").await.unwrap().unwrap().status,
+            SagaStatus::Completed
+        );
+        assert!(store.load("missing").await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,178 @@
+//! Automatic aggregate snapshots
+//!
+//! A [`SnapshotPolicy`] registered for an aggregate type with
+//! [`EventStore::with_snapshot_policy`](super::EventStore::with_snapshot_policy)
+//! makes [`load_aggregate`](super::EventStore::load_aggregate) start from the
+//! aggregate's latest snapshot and save a new one once the policy's threshold
+//! is crossed, keeping load times bounded without manual bookkeeping.
+//! Appends to an aggregate the store has loaded check the policy as well.
+//! Aggregate types without a policy are always replayed from their first
+//! event.
+
+use std::{
+    any::Any,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+use super::{backend::EventStoreBackend, Aggregate, Event, EventStore};
+
+/// When [`load_aggregate`](super::EventStore::load_aggregate) saves a new
+/// snapshot of an aggregate
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SnapshotPolicy {
+    /// Never snapshot
+    #[default]
+    Disabled,
+    /// Snapshot once a load replays at least this many events (minimum 1)
+    EveryEvents(u64),
+    /// Snapshot when a load replays any events and the latest snapshot is at
+    /// least this old
+    Every(Duration),
+}
+
+impl SnapshotPolicy {
+    /// Whether a snapshot is due after replaying `events` events on top of
+    /// the snapshot taken at `taken_at` (`None` when there was none)
+    pub fn is_due(&self, events: u64, taken_at: Option<SystemTime>) -> bool {
+        match *self {
+            SnapshotPolicy::Disabled => false,
+            SnapshotPolicy::EveryEvents(n) => events >= n.max(1),
+            SnapshotPolicy::Every(interval) => {
+                events > 0
+                    && taken_at.map_or(true, |taken_at| {
+                        taken_at.elapsed().is_ok_and(|age| age >= interval)
+                    })
+            }
+        }
+    }
+}
+
+/// Snapshots an aggregate after an append, if its policy says it is due
+pub(crate) type AfterAppend<E, B> =
+    for<'a> fn(&'a EventStore<E, B>, &'a str) -> Pin<Box<dyn Future<Output = ()> + Send + 'a>>;
+
+/// A policy registered for an aggregate type
+pub(crate) struct SnapshotEntry<E: Event, B: EventStoreBackend<E>> {
+    /// The type's `SnapshotConfig<A>`
+    pub(crate) config: Arc<dyn Any + Send + Sync>,
+    pub(crate) after_append: AfterAppend<E, B>,
+}
+
+impl<E: Event, B: EventStoreBackend<E>> Clone for SnapshotEntry<E, B> {
+    fn clone(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            after_append: self.after_append,
+        }
+    }
+}
+
+impl<E: Event, B: EventStoreBackend<E>> SnapshotEntry<E, B> {
+    pub(crate) fn new<A>(policy: SnapshotPolicy) -> Self
+    where
+        A: Aggregate<Event = E> + Serialize + DeserializeOwned + 'static,
+    {
+        Self {
+            config: Arc::new(SnapshotConfig::<A>::new(policy)),
+            after_append: |store, aggregate_id| {
+                Box::pin(async move {
+                    // Loading saves the snapshot when it is due
+                    let _ = store.load_aggregate::<A>(aggregate_id).await;
+                })
+            },
+        }
+    }
+}
+
+/// Snapshot data written by [`load_aggregate`](super::EventStore::load_aggregate)
+#[derive(Serialize, Deserialize)]
+struct StoredSnapshot<A> {
+    /// When the snapshot was taken, in unix milliseconds
+    taken_at: u64,
+    aggregate: A,
+}
+
+/// A policy with the serialization of its aggregate type
+pub(crate) struct SnapshotConfig<A> {
+    pub(crate) policy: SnapshotPolicy,
+    encode: fn(&A) -> Result<Vec<u8>, String>,
+    decode: fn(&[u8]) -> Option<(A, SystemTime)>,
+}
+
+impl<A: Serialize + DeserializeOwned> SnapshotConfig<A> {
+    pub(crate) fn new(policy: SnapshotPolicy) -> Self {
+        Self {
+            policy,
+            encode: |aggregate| {
+                let taken_at = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_millis() as u64;
+                serde_json::to_vec(&StoredSnapshot {
+                    taken_at,
+                    aggregate,
+                })
+                .map_err(|e| format!("Encode snapshot: {}", e))
+            },
+            decode: |data| {
+                let snapshot: StoredSnapshot<A> = serde_json::from_slice(data).ok()?;
+                let taken_at = UNIX_EPOCH + Duration::from_millis(snapshot.taken_at);
+                Some((snapshot.aggregate, taken_at))
+            },
+        }
+    }
+}
+
+impl<A> SnapshotConfig<A> {
+    pub(crate) fn encode(&self, aggregate: &A) -> Result<Vec<u8>, String> {
+        (self.encode)(aggregate)
+    }
+
+    /// The aggregate and the time it was taken, or `None` if the data isn't
+    /// a snapshot of this aggregate type (e.g. saved by hand, or before the
+    /// type changed)
+    pub(crate) fn decode(&self, data: &[u8]) -> Option<(A, SystemTime)> {
+        (self.decode)(data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_events_policy() {
+        let policy = SnapshotPolicy::EveryEvents(100);
+        assert!(!policy.is_due(99, None));
+        assert!(policy.is_due(100, Some(SystemTime::now())));
+        assert!(!SnapshotPolicy::Disabled.is_due(1000, None));
+    }
+
+    #[test]
+    fn test_time_based_policy() {
+        let policy = SnapshotPolicy::Every(Duration::from_secs(60));
+        let old = SystemTime::now() - Duration::from_secs(120);
+
+        assert!(policy.is_due(1, None));
+        assert!(policy.is_due(1, Some(old)));
+        assert!(!policy.is_due(1, Some(SystemTime::now())));
+        // Nothing new to snapshot
+        assert!(!policy.is_due(0, Some(old)));
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let config = SnapshotConfig::<Vec<i32>>::new(SnapshotPolicy::EveryEvents(1));
+        let data = config.encode(&vec![1, 2, 3]).unwrap();
+
+        let (aggregate, taken_at) = config.decode(&data).unwrap();
+        assert_eq!(aggregate, [1, 2, 3]);
+        assert!(taken_at.elapsed().unwrap() < Duration::from_secs(60));
+        assert!(config.decode(b"not a snapshot").is_none());
+    }
+}
@@ -9,11 +9,15 @@ mod inner {
     use std::collections::HashMap;
     use std::marker::PhantomData;
     use std::sync::{Arc, Mutex};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
     use async_trait::async_trait;
     use rusqlite::Connection;
 
-    use super::super::{BackendStats, Event, EventStoreBackend};
+    use super::super::{
+        BackendStats, Event, EventEnvelope, EventMetadata, EventPage, EventStoreBackend,
+        EventStream,
+    };
 
     /// SQLite-backed event store backend.
     ///
@@ -29,7 +33,8 @@ mod inner {
         /// Create a new SQLite event store backend at the given path.
         ///
         /// Enables WAL journal mode and creates the `events` and `snapshots`
-        /// tables if they don't exist.
+        /// tables if they don't exist, adding the metadata columns to
+        /// `events` tables created by earlier versions.
         pub async fn new(path: &str) -> Result<Self, String> {
             let path = path.to_string();
             let conn = tokio::task::spawn_blocking(move || {
@@ -41,7 +46,9 @@ mod inner {
                         id INTEGER PRIMARY KEY AUTOINCREMENT,
                         aggregate_id TEXT NOT NULL,
                         event_data BLOB NOT NULL,
-                        created_at TEXT NOT NULL DEFAULT (datetime('now'))
+                        created_at TEXT NOT NULL DEFAULT (datetime('now')),
+                        recorded_at INTEGER,
+                        metadata TEXT
                     );
                     CREATE INDEX IF NOT EXISTS idx_events_aggregate ON events(aggregate_id);
                     CREATE TABLE IF NOT EXISTS snapshots (
@@ -51,6 +58,7 @@ mod inner {
                     );",
                 )
                 .map_err(|e| format!("Schema init: {}", e))?;
+                add_metadata_columns(&conn)?;
                 Ok::<_, String>(conn)
             })
             .await
@@ -61,13 +69,24 @@ mod inner {
                 _phantom: PhantomData,
             })
         }
-    }
 
-    #[async_trait]
-    impl<E: Event> EventStoreBackend<E> for SqliteEventStoreBackend<E> {
-        async fn append(&self, aggregate_id: &str, events: Vec<E>) -> Result<(), String> {
+        /// Insert a batch in one transaction, recording `metadata` with
+        /// every event
+        async fn write(
+            &self,
+            batch: Vec<(String, Vec<E>)>,
+            metadata: EventMetadata,
+        ) -> Result<(), String> {
             let conn = Arc::clone(&self.conn);
-            let agg_id = aggregate_id.to_string();
+            let metadata = if metadata == EventMetadata::default() {
+                None
+            } else {
+                Some(serde_json::to_string(&metadata).map_err(|e| format!("Serialize: {}", e))?)
+            };
+            let recorded_at = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as i64;
             tokio::task::spawn_blocking(move || {
                 let conn = conn.lock().map_err(|e| format!("Lock: {}", e))?;
                 let tx = conn
@@ -76,14 +95,16 @@ mod inner {
                 {
                     let mut stmt = tx
                         .prepare_cached(
-                            "INSERT INTO events (aggregate_id, event_data) VALUES (?1, ?2)",
+                            "INSERT INTO events (aggregate_id, event_data, recorded_at, metadata) VALUES (?1, ?2, ?3, ?4)",
                         )
                         .map_err(|e| format!("Prepare: {}", e))?;
-                    for event in &events {
-                        let data = serde_json::to_vec(event)
-                            .map_err(|e| format!("Serialize: {}", e))?;
-                        stmt.execute(rusqlite::params![agg_id, data])
-                            .map_err(|e| format!("Insert: {}", e))?;
+                    for (agg_id, events) in &batch {
+                        for event in events {
+                            let data = serde_json::to_vec(event)
+                                .map_err(|e| format!("Serialize: {}", e))?;
+                            stmt.execute(rusqlite::params![agg_id, data, recorded_at, metadata])
+                                .map_err(|e| format!("Insert: {}", e))?;
+                        }
                     }
                 }
                 tx.commit().map_err(|e| format!("Commit: {}", e))?;
@@ -93,6 +114,62 @@ mod inner {
             .map_err(|e| format!("spawn_blocking: {}", e))?
         }
 
+        /// Read an aggregate's events `page_size` at a time
+        ///
+        /// Each page is a separate query, so the stream is never loaded
+        /// whole.
+        pub fn stream_events(&self, aggregate_id: &str, page_size: usize) -> EventStream<E, Self> {
+            EventStream::new(Arc::new(self.clone()), aggregate_id, page_size)
+        }
+    }
+
+    /// Add the `recorded_at` and `metadata` columns to an `events` table
+    /// created before they existed
+    fn add_metadata_columns(conn: &Connection) -> Result<(), String> {
+        let mut stmt = conn
+            .prepare("PRAGMA table_info(events)")
+            .map_err(|e| format!("Schema check: {}", e))?;
+        let columns = stmt
+            .query_map([], |row| row.get::<_, String>(1))
+            .map_err(|e| format!("Schema check: {}", e))?
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("Schema check: {}", e))?;
+        for (column, definition) in [("recorded_at", "INTEGER"), ("metadata", "TEXT")] {
+            if !columns.iter().any(|c| c == column) {
+                conn.execute_batch(&format!(
+                    "ALTER TABLE events ADD COLUMN {} {};",
+                    column, definition
+                ))
+                .map_err(|e| format!("Schema migration: {}", e))?;
+            }
+        }
+        Ok(())
+    }
+
+    #[async_trait]
+    impl<E: Event> EventStoreBackend<E> for SqliteEventStoreBackend<E> {
+        async fn append(&self, aggregate_id: &str, events: Vec<E>) -> Result<(), String> {
+            self.write(
+                vec![(aggregate_id.to_string(), events)],
+                EventMetadata::default(),
+            )
+            .await
+        }
+
+        async fn append_with_metadata(
+            &self,
+            aggregate_id: &str,
+            events: Vec<E>,
+            metadata: EventMetadata,
+        ) -> Result<(), String> {
+            self.write(vec![(aggregate_id.to_string(), events)], metadata)
+                .await
+        }
+
+        async fn append_batch(&self, batch: Vec<(String, Vec<E>)>) -> Result<(), String> {
+            self.write(batch, EventMetadata::default()).await
+        }
+
         async fn get_events(&self, aggregate_id: &str) -> Result<Vec<E>, String> {
             let conn = Arc::clone(&self.conn);
             let agg_id = aggregate_id.to_string();
@@ -121,6 +198,58 @@ mod inner {
             .map_err(|e| format!("spawn_blocking: {}", e))?
         }
 
+        async fn get_events_with_metadata(
+            &self,
+            aggregate_id: &str,
+        ) -> Result<Vec<EventEnvelope<E>>, String> {
+            let conn = Arc::clone(&self.conn);
+            let agg_id = aggregate_id.to_string();
+            tokio::task::spawn_blocking(move || {
+                let conn = conn.lock().map_err(|e| format!("Lock: {}", e))?;
+                // Rows written before metadata was recorded fall back to
+                // `created_at`, which has second precision
+                let mut stmt = conn
+                    .prepare_cached(
+                        "SELECT id, event_data, COALESCE(recorded_at, CAST(strftime('%s', created_at) AS INTEGER) * 1000), metadata
+                         FROM events WHERE aggregate_id = ?1 ORDER BY id",
+                    )
+                    .map_err(|e| format!("Prepare: {}", e))?;
+                let rows = stmt
+                    .query_map(rusqlite::params![agg_id], |row| {
+                        Ok((
+                            row.get::<_, i64>(0)?,
+                            row.get::<_, Vec<u8>>(1)?,
+                            row.get::<_, i64>(2)?,
+                            row.get::<_, Option<String>>(3)?,
+                        ))
+                    })
+                    .map_err(|e| format!("Query: {}", e))?;
+                let mut envelopes = Vec::new();
+                for (index, row) in rows.enumerate() {
+                    let (id, data, recorded_at, metadata) =
+                        row.map_err(|e| format!("Row: {}", e))?;
+                    let event: E = serde_json::from_slice(&data)
+                        .map_err(|e| format!("Deserialize: {}", e))?;
+                    let metadata = match metadata {
+                        Some(json) => serde_json::from_str(&json)
+                            .map_err(|e| format!("Deserialize metadata: {}", e))?,
+                        None => EventMetadata::default(),
+                    };
+                    envelopes.push(EventEnvelope::new(
+                        event,
+                        id.to_string(),
+                        agg_id.as_str(),
+                        index as u64 + 1,
+                        UNIX_EPOCH + Duration::from_millis(recorded_at.max(0) as u64),
+                        metadata,
+                    ));
+                }
+                Ok(envelopes)
+            })
+            .await
+            .map_err(|e| format!("spawn_blocking: {}", e))?
+        }
+
         async fn get_all_events(&self) -> Result<Vec<E>, String> {
             let conn = Arc::clone(&self.conn);
             tokio::task::spawn_blocking(move || {
@@ -144,6 +273,31 @@ mod inner {
             .map_err(|e| format!("spawn_blocking: {}", e))?
         }
 
+        async fn get_events_since(&self, sequence: u64) -> Result<Vec<E>, String> {
+            let conn = Arc::clone(&self.conn);
+            tokio::task::spawn_blocking(move || {
+                let conn = conn.lock().map_err(|e| format!("Lock: {}", e))?;
+                let mut stmt = conn
+                    .prepare_cached("SELECT event_data FROM events ORDER BY id LIMIT -1 OFFSET ?1")
+                    .map_err(|e| format!("Prepare: {}", e))?;
+                let rows = stmt
+                    .query_map(rusqlite::params![sequence as i64], |row| {
+                        row.get::<_, Vec<u8>>(0)
+                    })
+                    .map_err(|e| format!("Query: {}", e))?;
+                let mut events = Vec::new();
+                for row in rows {
+                    let data = row.map_err(|e| format!("Row: {}", e))?;
+                    let event: E = serde_json::from_slice(&data)
+                        .map_err(|e| format!("Deserialize: {}", e))?;
+                    events.push(event);
+                }
+                Ok(events)
+            })
+            .await
+            .map_err(|e| format!("spawn_blocking: {}", e))?
+        }
+
         async fn get_events_after(
             &self,
             aggregate_id: &str,
@@ -177,6 +331,41 @@ mod inner {
             .map_err(|e| format!("spawn_blocking: {}", e))?
         }
 
+        async fn get_events_page(
+            &self,
+            aggregate_id: &str,
+            from_version: u64,
+            limit: usize,
+        ) -> Result<EventPage<E>, String> {
+            let conn = Arc::clone(&self.conn);
+            let agg_id = aggregate_id.to_string();
+            tokio::task::spawn_blocking(move || {
+                let conn = conn.lock().map_err(|e| format!("Lock: {}", e))?;
+                // One extra row tells whether more events follow
+                let mut stmt = conn
+                    .prepare_cached(
+                        "SELECT event_data FROM events WHERE aggregate_id = ?1 ORDER BY id LIMIT ?2 OFFSET ?3",
+                    )
+                    .map_err(|e| format!("Prepare: {}", e))?;
+                let rows = stmt
+                    .query_map(
+                        rusqlite::params![agg_id, limit as i64 + 1, from_version as i64],
+                        |row| row.get::<_, Vec<u8>>(0),
+                    )
+                    .map_err(|e| format!("Query: {}", e))?;
+                let mut events = Vec::new();
+                for row in rows {
+                    let data = row.map_err(|e| format!("Row: {}", e))?;
+                    let event: E = serde_json::from_slice(&data)
+                        .map_err(|e| format!("Deserialize: {}", e))?;
+                    events.push(event);
+                }
+                Ok(EventPage::from_events(events, from_version, limit))
+            })
+            .await
+            .map_err(|e| format!("spawn_blocking: {}", e))?
+        }
+
         async fn save_snapshot(
             &self,
             aggregate_id: &str,
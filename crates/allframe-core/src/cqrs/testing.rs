@@ -0,0 +1,165 @@
+//! Test helpers for aggregates, projections and command handlers
+//!
+//! [`replay`] applies events to a fresh aggregate, letting a test inspect the
+//! state after each one. [`AggregateTest`] runs given-when-then tests of
+//! aggregates that handle their own commands: given past events, when a
+//! command is handled, then expect these events (or an error).
+//!
+//! ```rust
+//! use allframe_core::cqrs::{
+//!     testing::AggregateTest, Aggregate, CommandError, CommandHandler, CommandResult, Event,
+//!     EventTypeName,
+//! };
+//! use allframe_core::async_trait::async_trait;
+//!
+//! #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+//! enum LightEvent {
+//!     SwitchedOn,
+//!     SwitchedOff,
+//! }
+//!
+//! impl EventTypeName for LightEvent {}
+//! impl Event for LightEvent {}
+//!
+//! #[derive(Default)]
+//! struct Light {
+//!     on: bool,
+//! }
+//!
+//! impl Aggregate for Light {
+//!     type Event = LightEvent;
+//!
+//!     fn apply_event(&mut self, event: &LightEvent) {
+//!         self.on = *event == LightEvent::SwitchedOn;
+//!     }
+//! }
+//!
+//! struct SwitchOn;
+//! impl allframe_core::cqrs::Command for SwitchOn {}
+//!
+//! #[async_trait]
+//! impl CommandHandler<SwitchOn, LightEvent> for Light {
+//!     async fn handle(&self, _: SwitchOn) -> CommandResult<LightEvent> {
+//!         if self.on {
+//!             return Err(CommandError::BusinessLogic("already on".into()));
+//!         }
+//!         Ok(vec![LightEvent::SwitchedOn])
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let light = AggregateTest::<Light>::given([LightEvent::SwitchedOff])
+//!     .when(SwitchOn)
+//!     .await
+//!     .then_expect([LightEvent::SwitchedOn]);
+//! assert!(light.on);
+//!
+//! AggregateTest::<Light>::given([LightEvent::SwitchedOn])
+//!     .when(SwitchOn)
+//!     .await
+//!     .then_expect_error();
+//! # }
+//! ```
+
+use std::fmt::Debug;
+
+use super::{Aggregate, Command, CommandError, CommandHandler, CommandResult, Projection};
+
+/// Apply `events` to a fresh aggregate, calling `inspect` after each one
+///
+/// Returns the final state.
+pub fn replay<A: Aggregate>(
+    events: impl IntoIterator<Item = A::Event>,
+    mut inspect: impl FnMut(&A),
+) -> A {
+    let mut aggregate = A::default();
+    for event in events {
+        aggregate.apply_event(&event);
+        inspect(&aggregate);
+    }
+    aggregate
+}
+
+/// Apply `events` to `projection`, calling `inspect` after each one
+///
+/// Returns the final state.
+pub fn replay_projection<P: Projection>(
+    mut projection: P,
+    events: impl IntoIterator<Item = P::Event>,
+    mut inspect: impl FnMut(&P),
+) -> P {
+    for event in events {
+        projection.apply(&event);
+        inspect(&projection);
+    }
+    projection
+}
+
+/// Given-when-then test of an aggregate handling commands
+///
+/// The aggregate handles a command through its own
+/// [`CommandHandler`] implementation, reading the state rebuilt from the
+/// given events.
+pub struct AggregateTest<A: Aggregate> {
+    aggregate: A,
+}
+
+impl<A: Aggregate> AggregateTest<A> {
+    /// Start from the state produced by `events`
+    pub fn given(events: impl IntoIterator<Item = A::Event>) -> Self {
+        Self {
+            aggregate: replay(events, |_| {}),
+        }
+    }
+
+    /// Handle `command` against the current state
+    pub async fn when<C: Command>(self, command: C) -> AggregateTestOutcome<A>
+    where
+        A: CommandHandler<C, A::Event>,
+    {
+        let result = self.aggregate.handle(command).await;
+        AggregateTestOutcome {
+            aggregate: self.aggregate,
+            result,
+        }
+    }
+}
+
+/// The result of [`AggregateTest::when`], to check with a `then_` method
+#[must_use = "check the outcome with a `then_` method"]
+pub struct AggregateTestOutcome<A: Aggregate> {
+    aggregate: A,
+    result: CommandResult<A::Event>,
+}
+
+impl<A: Aggregate> AggregateTestOutcome<A>
+where
+    A::Event: PartialEq + Debug,
+{
+    /// Assert that the command produced exactly `expected`
+    ///
+    /// Returns the aggregate with the events applied, for further
+    /// assertions on its state.
+    #[track_caller]
+    pub fn then_expect(mut self, expected: impl IntoIterator<Item = A::Event>) -> A {
+        let events = match self.result {
+            Ok(events) => events,
+            Err(e) => panic!("expected events, but the command failed: {}", e),
+        };
+        assert_eq!(events, expected.into_iter().collect::<Vec<_>>());
+        for event in &events {
+            self.aggregate.apply_event(event);
+        }
+        self.aggregate
+    }
+
+    /// Assert that the command failed, returning the error
+    #[track_caller]
+    pub fn then_expect_error(self) -> CommandError {
+        match self.result {
+            Ok(events) => panic!("expected an error, but the command produced {:?}", events),
+            Err(e) => e,
+        }
+    }
+}
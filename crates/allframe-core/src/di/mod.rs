@@ -111,7 +111,14 @@ pub trait Provider<T>: Send + Sync {
     async fn provide(&self) -> Result<T, DependencyError>;
 }
 
+/// Derive `FromEnv` by loading a struct with
+/// [`ConfigLoader`](crate::config::ConfigLoader)
+pub use allframe_macros::FromEnv;
+
 /// Trait for types that can be loaded from environment variables
+///
+/// Derive it to load the type from defaults, config files and the
+/// environment with [`ConfigLoader`](crate::config::ConfigLoader).
 pub trait FromEnv: Sized {
     /// Load configuration from environment variables
     fn from_env() -> Result<Self, DependencyError>;
@@ -17,6 +17,8 @@
 //!     .await?;
 //! ```
 
+#[cfg(unix)]
+use std::path::{Path, PathBuf};
 use std::{net::SocketAddr, time::Duration};
 
 use super::tls::TlsConfig;
@@ -54,6 +56,8 @@ impl std::error::Error for GrpcServerError {}
 /// including TLS, reflection, health checks, and graceful shutdown.
 pub struct GrpcServerBuilder {
     addr: SocketAddr,
+    #[cfg(unix)]
+    uds_path: Option<PathBuf>,
     tls_config: Option<TlsConfig>,
     reflection_descriptor: Option<&'static [u8]>,
     health_check: bool,
@@ -72,6 +76,8 @@ impl GrpcServerBuilder {
     pub fn new() -> Self {
         Self {
             addr: "[::1]:50051".parse().unwrap(),
+            #[cfg(unix)]
+            uds_path: None,
             tls_config: None,
             reflection_descriptor: None,
             health_check: false,
@@ -101,6 +107,17 @@ impl GrpcServerBuilder {
         self
     }
 
+    /// Serve on a Unix domain socket at `path` instead of a TCP address
+    ///
+    /// For sidecars and other co-located clients. Connections are plaintext;
+    /// TLS settings don't apply. A socket file left at `path` by a previous
+    /// run is replaced, and the file is removed when the server stops.
+    #[cfg(unix)]
+    pub fn uds(mut self, path: impl Into<PathBuf>) -> Self {
+        self.uds_path = Some(path.into());
+        self
+    }
+
     /// Enable TLS with the given configuration
     pub fn tls(mut self, config: TlsConfig) -> Self {
         self.tls_config = Some(config);
@@ -167,6 +184,12 @@ impl GrpcServerBuilder {
         self.addr
     }
 
+    /// Get the configured Unix domain socket path, if any
+    #[cfg(unix)]
+    pub fn get_uds_path(&self) -> Option<&Path> {
+        self.uds_path.as_deref()
+    }
+
     /// Check if TLS is configured
     pub fn has_tls(&self) -> bool {
         self.tls_config.is_some()
@@ -209,6 +232,11 @@ impl GrpcServerBuilder {
         self,
         router: tonic::transport::server::Router,
     ) -> Result<(), GrpcServerError> {
+        #[cfg(unix)]
+        if let Some(path) = self.uds_path.clone() {
+            return self.serve_router_uds(router, &path).await;
+        }
+
         // Start the server
         if let Some(shutdown) = self.shutdown {
             let mut token = shutdown.token();
@@ -228,6 +256,36 @@ impl GrpcServerBuilder {
         Ok(())
     }
 
+    #[cfg(all(unix, feature = "router-grpc"))]
+    async fn serve_router_uds(
+        self,
+        router: tonic::transport::server::Router,
+        path: &Path,
+    ) -> Result<(), GrpcServerError> {
+        use std::os::unix::fs::FileTypeExt;
+
+        let bind_error =
+            |e: std::io::Error| GrpcServerError::Bind(format!("{}: {}", path.display(), e));
+        // A socket left by a previous run would make the bind fail
+        if std::fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_socket()) {
+            std::fs::remove_file(path).map_err(bind_error)?;
+        }
+        let incoming = UnixIncoming(tokio::net::UnixListener::bind(path).map_err(bind_error)?);
+
+        let result = if let Some(shutdown) = self.shutdown {
+            let mut token = shutdown.token();
+            router
+                .serve_with_incoming_shutdown(incoming, async move {
+                    token.cancelled().await;
+                })
+                .await
+        } else {
+            router.serve_with_incoming(incoming).await
+        };
+        let _ = std::fs::remove_file(path);
+        result.map_err(|e| GrpcServerError::Server(e.to_string()))
+    }
+
     /// Build a tonic Server with reflection service
     ///
     /// Returns a tonic::transport::Server builder. Call add_service on it
@@ -298,6 +356,24 @@ impl GrpcServerBuilder {
     }
 }
 
+/// Connections accepted on a Unix domain socket
+#[cfg(all(unix, feature = "router-grpc"))]
+struct UnixIncoming(tokio::net::UnixListener);
+
+#[cfg(all(unix, feature = "router-grpc"))]
+impl futures_core::Stream for UnixIncoming {
+    type Item = std::io::Result<tokio::net::UnixStream>;
+
+    fn poll_next(
+        self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.0
+            .poll_accept(cx)
+            .map(|accepted| Some(accepted.map(|(stream, _)| stream)))
+    }
+}
+
 /// Helper type for accepting various address formats
 pub struct SocketAddrInput(SocketAddr);
 
@@ -385,6 +461,87 @@ mod tests {
         assert_eq!(builder.shutdown_timeout, Duration::from_secs(60));
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_builder_uds() {
+        let builder = GrpcServerBuilder::new();
+        assert!(builder.get_uds_path().is_none());
+
+        let builder = builder.uds("/tmp/allframe.sock");
+        assert_eq!(
+            builder.get_uds_path(),
+            Some(Path::new("/tmp/allframe.sock"))
+        );
+    }
+
+    /// Connects a tonic channel to a Unix domain socket
+    #[cfg(all(unix, feature = "router-grpc", feature = "health"))]
+    struct UdsConnector(PathBuf);
+
+    #[cfg(all(unix, feature = "router-grpc", feature = "health"))]
+    impl tonic::codegen::Service<tonic::codegen::http::Uri> for UdsConnector {
+        type Response = hyper_util::rt::TokioIo<tokio::net::UnixStream>;
+        type Error = std::io::Error;
+        type Future = tonic::codegen::BoxFuture<Self::Response, Self::Error>;
+
+        fn poll_ready(
+            &mut self,
+            _cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<Result<(), Self::Error>> {
+            std::task::Poll::Ready(Ok(()))
+        }
+
+        fn call(&mut self, _uri: tonic::codegen::http::Uri) -> Self::Future {
+            let path = self.0.clone();
+            Box::pin(async move {
+                let stream = tokio::net::UnixStream::connect(path).await?;
+                Ok(hyper_util::rt::TokioIo::new(stream))
+            })
+        }
+    }
+
+    #[cfg(all(unix, feature = "router-grpc", feature = "health"))]
+    #[tokio::test]
+    async fn test_serve_over_uds() {
+        use tonic_health::pb::{health_client::HealthClient, HealthCheckRequest};
+
+        let path = std::env::temp_dir().join(format!("allframe-grpc-{}.sock", std::process::id()));
+        // A stale socket from an earlier run is replaced
+        drop(std::os::unix::net::UnixListener::bind(&path).unwrap());
+
+        let builder = GrpcServerBuilder::new().uds(&path);
+        let (_reporter, health) = tonic_health::server::health_reporter();
+        let router = builder.server_builder().add_service(health);
+        let server = tokio::spawn(builder.serve_router(router));
+
+        // Retry until the server task has replaced the stale socket
+        let endpoint = tonic::transport::Endpoint::from_static("http://localhost");
+        let mut attempts = 0;
+        let channel = loop {
+            match endpoint
+                .connect_with_connector(UdsConnector(path.clone()))
+                .await
+            {
+                Ok(channel) => break channel,
+                Err(_) if attempts < 50 => {
+                    attempts += 1;
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+                Err(e) => panic!("failed to connect over UDS: {}", e),
+            }
+        };
+        let response = HealthClient::new(channel)
+            .check(HealthCheckRequest {
+                service: String::new(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(response.into_inner().status, 1); // SERVING
+
+        server.abort();
+        let _ = std::fs::remove_file(&path);
+    }
+
     #[test]
     fn test_grpc_server_error_display() {
         let err = GrpcServerError::Bind("address in use".to_string());
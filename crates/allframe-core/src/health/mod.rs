@@ -33,6 +33,7 @@ mod server;
 mod types;
 
 use std::{
+    collections::HashMap,
     future::Future,
     pin::Pin,
     sync::Arc,
@@ -72,6 +73,105 @@ pub trait Dependency: Send + Sync {
     fn timeout(&self) -> Duration {
         Duration::from_secs(5)
     }
+
+    /// The group of redundant dependencies this one belongs to
+    ///
+    /// Grouped dependencies are aggregated into a single status according to
+    /// the group's quorum (see [`HealthCheck::group_quorum`]).
+    fn group(&self) -> Option<&str> {
+        None
+    }
+}
+
+impl<T: Dependency + ?Sized> Dependency for Arc<T> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn check(&self) -> Pin<Box<dyn Future<Output = DependencyStatus> + Send + '_>> {
+        (**self).check()
+    }
+
+    fn is_critical(&self) -> bool {
+        (**self).is_critical()
+    }
+
+    fn timeout(&self) -> Duration {
+        (**self).timeout()
+    }
+
+    fn group(&self) -> Option<&str> {
+        (**self).group()
+    }
+}
+
+/// A dependency with overridden criticality, timeout or group
+///
+/// ```rust
+/// use allframe_core::health::{AlwaysHealthy, ConfiguredDependency, SimpleHealthCheck};
+///
+/// let checker = SimpleHealthCheck::new()
+///     .add_dependency(ConfiguredDependency::new(AlwaysHealthy::new("redis-1")).with_group("cache"))
+///     .add_dependency(ConfiguredDependency::new(AlwaysHealthy::new("redis-2")).with_group("cache"))
+///     .with_group_quorum("cache", 1);
+/// ```
+pub struct ConfiguredDependency<D> {
+    inner: D,
+    critical: Option<bool>,
+    timeout: Option<Duration>,
+    group: Option<String>,
+}
+
+impl<D: Dependency> ConfiguredDependency<D> {
+    /// Wrap a dependency, keeping its own settings
+    pub fn new(inner: D) -> Self {
+        Self {
+            inner,
+            critical: None,
+            timeout: None,
+            group: None,
+        }
+    }
+
+    /// Override whether the dependency is critical
+    pub fn with_critical(mut self, critical: bool) -> Self {
+        self.critical = Some(critical);
+        self
+    }
+
+    /// Override the check timeout
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Put the dependency in a group
+    pub fn with_group(mut self, group: impl Into<String>) -> Self {
+        self.group = Some(group.into());
+        self
+    }
+}
+
+impl<D: Dependency> Dependency for ConfiguredDependency<D> {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn check(&self) -> Pin<Box<dyn Future<Output = DependencyStatus> + Send + '_>> {
+        self.inner.check()
+    }
+
+    fn is_critical(&self) -> bool {
+        self.critical.unwrap_or_else(|| self.inner.is_critical())
+    }
+
+    fn timeout(&self) -> Duration {
+        self.timeout.unwrap_or_else(|| self.inner.timeout())
+    }
+
+    fn group(&self) -> Option<&str> {
+        self.group.as_deref().or_else(|| self.inner.group())
+    }
 }
 
 /// A collection of dependencies that can be health-checked together
@@ -79,20 +179,31 @@ pub trait HealthCheck: Send + Sync {
     /// Get all dependencies to check
     fn dependencies(&self) -> Vec<Arc<dyn Dependency>>;
 
+    /// Healthy members a dependency group needs to be healthy
+    ///
+    /// `None` (the default) requires every member of the group.
+    fn group_quorum(&self, _group: &str) -> Option<usize> {
+        None
+    }
+
     /// Check all dependencies and return a comprehensive health report
+    ///
+    /// Ungrouped dependencies count individually. Each group counts as one
+    /// unit whose status follows its quorum, and which is critical if any of
+    /// its members is. A critical unit that is unhealthy makes the report
+    /// unhealthy; any other unit that isn't healthy makes it degraded.
     fn check_all(&self) -> Pin<Box<dyn Future<Output = HealthReport> + Send + '_>> {
         let deps = self.dependencies();
         Box::pin(async move {
             let start = Instant::now();
             let mut reports = Vec::with_capacity(deps.len());
-            let mut has_critical_failure = false;
-            let mut has_degradation = false;
 
             for dep in deps {
                 let dep_start = Instant::now();
                 let timeout = dep.timeout();
                 let is_critical = dep.is_critical();
                 let name = dep.name().to_string();
+                let group = dep.group().map(str::to_string);
 
                 let status = match tokio::time::timeout(timeout, dep.check()).await {
                     Ok(status) => status,
@@ -102,37 +213,49 @@ pub trait HealthCheck: Send + Sync {
                     )),
                 };
 
-                let duration = dep_start.elapsed();
-
-                match &status {
-                    DependencyStatus::Unhealthy(_) if is_critical => {
-                        has_critical_failure = true;
-                    }
-                    DependencyStatus::Unhealthy(_) | DependencyStatus::Degraded(_) => {
-                        has_degradation = true;
-                    }
-                    _ => {}
-                }
-
                 reports.push(DependencyReport {
                     name,
                     status,
-                    duration,
+                    duration: dep_start.elapsed(),
                     critical: is_critical,
+                    group,
                 });
             }
 
-            let overall_status = if has_critical_failure {
-                OverallStatus::Unhealthy
-            } else if has_degradation {
-                OverallStatus::Degraded
-            } else {
-                OverallStatus::Healthy
-            };
+            let groups = group_reports(&reports, |group| self.group_quorum(group));
+
+            let units = reports
+                .iter()
+                .filter(|report| report.group.is_none())
+                .map(|report| {
+                    let status = match report.status {
+                        DependencyStatus::Healthy => OverallStatus::Healthy,
+                        DependencyStatus::Degraded(_) => OverallStatus::Degraded,
+                        DependencyStatus::Unhealthy(_) => OverallStatus::Unhealthy,
+                    };
+                    (status, report.critical)
+                })
+                .chain(groups.iter().map(|group| (group.status, group.critical)));
+
+            let mut overall_status = OverallStatus::Healthy;
+            for (status, critical) in units {
+                match status {
+                    OverallStatus::Unhealthy if critical => {
+                        overall_status = OverallStatus::Unhealthy;
+                    }
+                    OverallStatus::Unhealthy | OverallStatus::Degraded => {
+                        if overall_status == OverallStatus::Healthy {
+                            overall_status = OverallStatus::Degraded;
+                        }
+                    }
+                    OverallStatus::Healthy => {}
+                }
+            }
 
             HealthReport {
                 status: overall_status,
                 dependencies: reports,
+                groups,
                 total_duration: start.elapsed(),
                 timestamp: std::time::SystemTime::now(),
             }
@@ -140,9 +263,68 @@ pub trait HealthCheck: Send + Sync {
     }
 }
 
+/// Aggregate grouped dependency reports, in order of first appearance
+///
+/// A group is healthy when at least `quorum` members are healthy, degraded
+/// when that many are at least degraded, and unhealthy otherwise.
+fn group_reports(
+    reports: &[DependencyReport],
+    quorum: impl Fn(&str) -> Option<usize>,
+) -> Vec<GroupReport> {
+    let mut groups: Vec<GroupReport> = Vec::new();
+    let mut available = Vec::new();
+
+    for report in reports {
+        let Some(name) = &report.group else {
+            continue;
+        };
+        let index = match groups.iter().position(|g| &g.name == name) {
+            Some(index) => index,
+            None => {
+                groups.push(GroupReport {
+                    name: name.clone(),
+                    status: OverallStatus::Healthy,
+                    healthy: 0,
+                    total: 0,
+                    quorum: 0,
+                    critical: false,
+                });
+                available.push(0);
+                groups.len() - 1
+            }
+        };
+
+        let group = &mut groups[index];
+        group.total += 1;
+        group.critical |= report.critical;
+        if report.status.is_healthy() {
+            group.healthy += 1;
+        }
+        if !report.status.is_unhealthy() {
+            available[index] += 1;
+        }
+    }
+
+    for (group, available) in groups.iter_mut().zip(available) {
+        group.quorum = quorum(&group.name)
+            .unwrap_or(group.total)
+            .clamp(1, group.total);
+        group.status = if group.healthy >= group.quorum {
+            OverallStatus::Healthy
+        } else if available >= group.quorum {
+            OverallStatus::Degraded
+        } else {
+            OverallStatus::Unhealthy
+        };
+    }
+
+    groups
+}
+
 /// A simple health checker that holds a list of dependencies
 pub struct SimpleHealthCheck {
     dependencies: Vec<Arc<dyn Dependency>>,
+    quorums: HashMap<String, usize>,
 }
 
 impl SimpleHealthCheck {
@@ -150,6 +332,7 @@ impl SimpleHealthCheck {
     pub fn new() -> Self {
         Self {
             dependencies: Vec::new(),
+            quorums: HashMap::new(),
         }
     }
 
@@ -164,6 +347,12 @@ impl SimpleHealthCheck {
         self.dependencies.push(dep);
         self
     }
+
+    /// Require only `quorum` healthy members for a group to be healthy
+    pub fn with_group_quorum(mut self, group: impl Into<String>, quorum: usize) -> Self {
+        self.quorums.insert(group.into(), quorum);
+        self
+    }
 }
 
 impl Default for SimpleHealthCheck {
@@ -176,6 +365,10 @@ impl HealthCheck for SimpleHealthCheck {
     fn dependencies(&self) -> Vec<Arc<dyn Dependency>> {
         self.dependencies.clone()
     }
+
+    fn group_quorum(&self, group: &str) -> Option<usize> {
+        self.quorums.get(group).copied()
+    }
 }
 
 /// A dependency that always reports healthy (useful for testing)
@@ -288,4 +481,71 @@ mod tests {
         let dep = AlwaysHealthy::new("test");
         assert!(dep.is_critical());
     }
+
+    fn replica(name: &str, healthy: bool) -> ConfiguredDependency<Arc<dyn Dependency>> {
+        let dep: Arc<dyn Dependency> = if healthy {
+            Arc::new(AlwaysHealthy::new(name))
+        } else {
+            Arc::new(AlwaysUnhealthy::new(name, "down"))
+        };
+        ConfiguredDependency::new(dep).with_group("cache")
+    }
+
+    #[tokio::test]
+    async fn test_group_quorum_met() {
+        let checker = SimpleHealthCheck::new()
+            .add_dependency(replica("redis-1", false))
+            .add_dependency(replica("redis-2", true))
+            .add_dependency(replica("redis-3", false))
+            .with_group_quorum("cache", 1);
+
+        let report = checker.check_all().await;
+        assert_eq!(report.status, OverallStatus::Healthy);
+
+        let group = report.group("cache").unwrap();
+        assert_eq!(group.status, OverallStatus::Healthy);
+        assert_eq!((group.healthy, group.total, group.quorum), (1, 3, 1));
+        assert_eq!(report.failed_dependencies().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_group_quorum_missed() {
+        let checker = SimpleHealthCheck::new()
+            .add_dependency(replica("redis-1", false))
+            .add_dependency(replica("redis-2", true))
+            .add_dependency(AlwaysHealthy::new("db"))
+            .with_group_quorum("cache", 2);
+
+        let report = checker.check_all().await;
+        assert_eq!(
+            report.group("cache").unwrap().status,
+            OverallStatus::Unhealthy
+        );
+        assert_eq!(report.status, OverallStatus::Unhealthy);
+    }
+
+    #[tokio::test]
+    async fn test_group_defaults_to_all_members() {
+        let checker = SimpleHealthCheck::new()
+            .add_dependency(replica("redis-1", true).with_critical(false))
+            .add_dependency(replica("redis-2", false).with_critical(false));
+
+        let report = checker.check_all().await;
+        let group = report.group("cache").unwrap();
+        assert_eq!(group.quorum, 2);
+        assert_eq!(group.status, OverallStatus::Unhealthy);
+        assert!(!group.critical);
+        assert_eq!(report.status, OverallStatus::Degraded);
+    }
+
+    #[test]
+    fn test_configured_dependency_overrides() {
+        let dep = ConfiguredDependency::new(AlwaysHealthy::new("api"))
+            .with_critical(false)
+            .with_timeout(Duration::from_secs(1));
+
+        assert!(!dep.is_critical());
+        assert_eq!(dep.timeout(), Duration::from_secs(1));
+        assert_eq!(dep.group(), None);
+    }
 }
@@ -85,6 +85,26 @@ pub struct DependencyReport {
     pub duration: Duration,
     /// Whether this dependency is critical
     pub critical: bool,
+    /// Group this dependency belongs to, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+}
+
+/// Report for a group of redundant dependencies
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupReport {
+    /// Name of the group
+    pub name: String,
+    /// Group status, derived from its members and quorum
+    pub status: OverallStatus,
+    /// Number of healthy members
+    pub healthy: usize,
+    /// Total number of members
+    pub total: usize,
+    /// Healthy members required for the group to be healthy
+    pub quorum: usize,
+    /// Whether any member of the group is critical
+    pub critical: bool,
 }
 
 /// Complete health report for the application
@@ -94,6 +114,9 @@ pub struct HealthReport {
     pub status: OverallStatus,
     /// Individual dependency reports
     pub dependencies: Vec<DependencyReport>,
+    /// Reports for dependency groups
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<GroupReport>,
     /// Total time taken to check all dependencies
     #[serde(with = "duration_millis")]
     pub total_duration: Duration,
@@ -121,6 +144,11 @@ impl HealthReport {
             .collect()
     }
 
+    /// Get the report for a dependency group
+    pub fn group(&self, name: &str) -> Option<&GroupReport> {
+        self.groups.iter().find(|g| g.name == name)
+    }
+
     /// Get degraded dependencies
     pub fn degraded_dependencies(&self) -> Vec<&DependencyReport> {
         self.dependencies
@@ -237,7 +265,9 @@ mod tests {
                 status: DependencyStatus::Healthy,
                 duration: Duration::from_millis(10),
                 critical: true,
+                group: None,
             }],
+            groups: Vec::new(),
             total_duration: Duration::from_millis(15),
             timestamp: SystemTime::now(),
         };
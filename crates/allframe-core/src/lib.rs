@@ -95,12 +95,19 @@
 //! | `router` | Protocol-agnostic routing | ✅ |
 //! | `router-graphql` | GraphQL adapter with async-graphql | ❌ |
 //! | `router-grpc` | gRPC adapter with tonic | ❌ |
+//! | `router-multipart` | `multipart/form-data` uploads for REST | ❌ |
+//! | `router-compression` | gzip/brotli compression of REST responses | ❌ |
 //! | `di` | Compile-time dependency injection | ✅ |
 //! | `cqrs` | CQRS + Event Sourcing infrastructure | ✅ |
 //! | `otel` | OpenTelemetry tracing | ✅ |
 //! | `health` | Health check endpoints | ✅ |
 //! | `resilience` | Retry, Circuit Breaker, Rate Limiting | ❌ |
 //! | `security` | Safe logging, credential obfuscation | ❌ |
+//! | `messaging` | Broker-agnostic producer/consumer, DLQ | ❌ |
+//! | `messaging-kafka` | Kafka producer/consumer via rdkafka | ❌ |
+//! | `config-yaml` | YAML files for the config loader | ❌ |
+//! | `money` | Decimal parsing and formatting for monetary amounts | ❌ |
+//! | `websocket` | Pub/sub channels for WebSocket services | ❌ |
 //! | `cqrs-sqlite` | SQLite event store (WAL mode) | ❌ |
 //! | `offline` | Full offline bundle (cqrs + sqlite + di + security) | ❌ |
 //!
@@ -124,12 +131,19 @@
 //!
 //! - [`router`] - Protocol-agnostic request routing (REST, GraphQL, gRPC)
 //! - [`shutdown`] - Graceful shutdown utilities
+//! - [`config`] - Layered configuration (defaults, files, environment)
 //! - [`cache`] - Caching infrastructure
 //! - `cqrs` - CQRS + Event Sourcing (requires `cqrs` feature)
 //! - `resilience` - Retry, Circuit Breaker, Rate Limiting (requires
 //!   `resilience` feature)
 //! - `security` - Safe logging and credential obfuscation (requires `security`
 //!   feature)
+//! - `messaging` - Message producers/consumers with at-least-once delivery
+//!   (requires `messaging` feature)
+//! - `money` - Deterministic decimal handling for monetary amounts
+//!   (requires `money` feature)
+//! - `websocket` - Pub/sub channels for real-time services (requires
+//!   `websocket` feature)
 //! - `di` - Compile-time dependency injection (requires `di` feature)
 //! - `otel` - OpenTelemetry instrumentation (requires `otel` feature)
 //! - `health` - Health check infrastructure (requires `health` feature)
@@ -300,6 +314,22 @@ pub mod health;
 /// - `grpc_explorer_html` - gRPC Explorer
 pub mod router;
 
+/// Cursor-based pagination for list endpoints.
+///
+/// [`Page`](pagination::Page) and [`Cursor`](pagination::Cursor) give REST
+/// and GraphQL handlers the same paging semantics and OpenAPI schema, with
+/// offset and keyset strategies.
+#[cfg(feature = "router")]
+#[cfg_attr(docsrs, doc(cfg(feature = "router")))]
+pub mod pagination;
+
+/// Layered configuration loading.
+///
+/// [`ConfigLoader`](config::ConfigLoader) merges defaults, TOML/JSON/YAML
+/// files and prefixed environment variables into a serde struct, and reports
+/// where each value came from.
+pub mod config;
+
 /// Graceful shutdown utilities for production services.
 ///
 /// Handle SIGTERM/SIGINT signals and coordinate clean shutdown across tasks.
@@ -371,6 +401,45 @@ pub mod resilience;
 #[cfg_attr(docsrs, doc(cfg(feature = "security")))]
 pub mod security;
 
+/// Message broker abstraction with at-least-once delivery.
+///
+/// Broker-agnostic producer/consumer traits, dead letter routing, and a
+/// Kafka backend behind the `messaging-kafka` feature:
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use allframe_core::messaging::{AtLeastOnceConsumer, DeadLetterQueue, KafkaConfig, KafkaConsumer, KafkaProducer};
+///
+/// let config = KafkaConfig::new("localhost:9092", "orders-service");
+/// let consumer = AtLeastOnceConsumer::new(KafkaConsumer::new(&config)?)
+///     .with_dlq(DeadLetterQueue::new(KafkaProducer::new(&config)?))
+///     .max_attempts(3);
+///
+/// consumer.run(|message| async move { handle(message).await }, shutdown.token()).await?;
+/// ```
+#[cfg(feature = "messaging")]
+#[cfg_attr(docsrs, doc(cfg(feature = "messaging")))]
+pub mod messaging;
+
+/// Deterministic decimal handling for monetary amounts.
+///
+/// [`AmountFormat`](money::AmountFormat) parses amounts from strings with
+/// scale and sign validation, rounds computed values, and formats them to a
+/// fixed number of decimal places.
+#[cfg(feature = "money")]
+#[cfg_attr(docsrs, doc(cfg(feature = "money")))]
+pub mod money;
+
+/// Real-time building blocks for WebSocket services.
+///
+/// Pub/sub channels with bounded per-connection queues, and JSON-RPC style
+/// dispatch of incoming messages to router handlers, independent of the
+/// WebSocket library that owns the sockets.
+#[cfg(feature = "websocket")]
+#[cfg_attr(docsrs, doc(cfg(feature = "websocket")))]
+pub mod websocket;
+
 /// gRPC server infrastructure with TLS support.
 ///
 /// Production-ready gRPC server with health checks and reflection.
@@ -419,15 +488,24 @@ pub use allframe_macros::circuit_breaker;
 /// Re-export rate_limited attribute macro
 #[cfg(feature = "resilience")]
 pub use allframe_macros::rate_limited;
+/// Re-export resilient attribute macro
+#[cfg(feature = "resilience")]
+pub use allframe_macros::resilient;
 /// Re-export retry attribute macro
 #[cfg(feature = "resilience")]
 pub use allframe_macros::retry;
+/// Re-export timeout attribute macro
+#[cfg(feature = "resilience")]
+pub use allframe_macros::timeout;
 /// Re-export GrpcError derive macro for automatic tonic::Status conversion
 #[cfg(feature = "router-grpc")]
 pub use allframe_macros::GrpcError;
 /// Re-export HealthCheck derive macro for automatic health check implementation
 #[cfg(feature = "di")]
 pub use allframe_macros::HealthCheck;
+/// Re-export HttpError derive macro for REST status code mapping
+#[cfg(feature = "router")]
+pub use allframe_macros::HttpError;
 /// Re-export Obfuscate derive macro for safe logging
 #[cfg(feature = "security")]
 pub use allframe_macros::Obfuscate;
@@ -457,6 +535,10 @@ pub use governor;
 /// Re-export hyper for HTTP primitives
 #[cfg(feature = "health")]
 pub use hyper;
+/// Re-export inventory for handler auto-registration (used by CQRS macros)
+#[cfg(feature = "cqrs")]
+#[doc(hidden)]
+pub use inventory;
 /// Re-export moka for high-performance caching
 #[cfg(feature = "cache-memory")]
 pub use moka;
@@ -540,8 +622,8 @@ pub mod prelude {
         SimpleHealthCheck,
     };
     pub use crate::router::{
-        GraphQLAdapter, GrpcAdapter, GrpcRequest, GrpcStatus, Method, ProtocolAdapter, RestAdapter,
-        RestRequest, RestResponse, RouteMetadata, Router, State, ToJsonSchema,
+        GraphQLAdapter, GrpcAdapter, GrpcRequest, GrpcStatus, HttpError, Method, ProtocolAdapter,
+        RestAdapter, RestRequest, RestResponse, RouteMetadata, Router, State, ToJsonSchema,
     };
     #[cfg(feature = "router")]
     pub use crate::router::{GraphQLConfig, GrpcConfig, RestConfig, RouterConfig, ServerConfig};
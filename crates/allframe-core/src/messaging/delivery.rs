@@ -0,0 +1,287 @@
+//! At-least-once consumption with manual offset commit
+
+use std::{fmt::Display, future::Future, time::Duration};
+
+use super::{DeadLetterQueue, Message, MessageConsumer, MessagingError};
+use crate::shutdown::ShutdownToken;
+
+/// What happened to a message processed by [`AtLeastOnceConsumer`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeliveryOutcome {
+    /// The handler succeeded and the offset was committed
+    Handled,
+    /// The handler failed on every attempt; the message was sent to the
+    /// dead letter queue and the offset was committed
+    DeadLettered,
+}
+
+/// Drives a [`MessageConsumer`] with at-least-once delivery semantics.
+///
+/// A message's offset is committed only after the handler succeeds. Failed
+/// handlers are retried up to `max_attempts` times; if they still fail the
+/// message is routed to the dead letter queue (when configured) and then
+/// committed. Without a dead letter queue the offset is left uncommitted and
+/// a [`MessagingError::Handler`] is returned, so the broker will redeliver
+/// the message.
+pub struct AtLeastOnceConsumer<C: MessageConsumer> {
+    consumer: C,
+    dlq: Option<DeadLetterQueue>,
+    max_attempts: u32,
+    retry_backoff: Duration,
+    poll_timeout: Duration,
+}
+
+impl<C: MessageConsumer> AtLeastOnceConsumer<C> {
+    /// Wrap a consumer. Defaults: 3 attempts, 100ms backoff, 1s poll timeout.
+    pub fn new(consumer: C) -> Self {
+        Self {
+            consumer,
+            dlq: None,
+            max_attempts: 3,
+            retry_backoff: Duration::from_millis(100),
+            poll_timeout: Duration::from_secs(1),
+        }
+    }
+
+    /// Route messages that exhaust their attempts to `dlq`.
+    pub fn with_dlq(mut self, dlq: DeadLetterQueue) -> Self {
+        self.dlq = Some(dlq);
+        self
+    }
+
+    /// Set the number of handler attempts per message (minimum 1).
+    pub fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Set the delay between handler attempts.
+    pub fn retry_backoff(mut self, backoff: Duration) -> Self {
+        self.retry_backoff = backoff;
+        self
+    }
+
+    /// Set how long each poll waits for a message.
+    pub fn poll_timeout(mut self, timeout: Duration) -> Self {
+        self.poll_timeout = timeout;
+        self
+    }
+
+    /// Access the wrapped consumer.
+    pub fn consumer(&self) -> &C {
+        &self.consumer
+    }
+
+    /// Handle a single message, committing it according to the outcome.
+    pub async fn process<F, Fut, E>(
+        &self,
+        message: Message,
+        handler: F,
+    ) -> Result<DeliveryOutcome, MessagingError>
+    where
+        F: Fn(Message) -> Fut,
+        Fut: Future<Output = Result<(), E>>,
+        E: Display,
+    {
+        let mut attempt = 0;
+        let error = loop {
+            attempt += 1;
+            match handler(message.clone()).await {
+                Ok(()) => {
+                    self.consumer.commit(&message).await?;
+                    return Ok(DeliveryOutcome::Handled);
+                }
+                Err(e) if attempt >= self.max_attempts => break e.to_string(),
+                Err(_) => tokio::time::sleep(self.retry_backoff).await,
+            }
+        };
+
+        match &self.dlq {
+            Some(dlq) => {
+                dlq.send(&message, &error).await?;
+                self.consumer.commit(&message).await?;
+                Ok(DeliveryOutcome::DeadLettered)
+            }
+            None => Err(MessagingError::Handler(error)),
+        }
+    }
+
+    /// Poll for one message and process it.
+    ///
+    /// Returns `Ok(None)` if no message arrived within the poll timeout.
+    pub async fn poll_once<F, Fut, E>(
+        &self,
+        handler: F,
+    ) -> Result<Option<DeliveryOutcome>, MessagingError>
+    where
+        F: Fn(Message) -> Fut,
+        Fut: Future<Output = Result<(), E>>,
+        E: Display,
+    {
+        match self.consumer.poll(self.poll_timeout).await? {
+            Some(message) => self.process(message, handler).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Consume messages until `shutdown` fires.
+    ///
+    /// The message in flight when shutdown is requested is allowed to finish
+    /// (and be committed) before returning. Any error stops the loop.
+    pub async fn run<F, Fut, E>(
+        &self,
+        handler: F,
+        mut shutdown: ShutdownToken,
+    ) -> Result<(), MessagingError>
+    where
+        F: Fn(Message) -> Fut,
+        Fut: Future<Output = Result<(), E>>,
+        E: Display,
+    {
+        while !shutdown.is_shutdown() {
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                polled = self.consumer.poll(self.poll_timeout) => {
+                    if let Some(message) = polled? {
+                        self.process(message, &handler).await?;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    };
+
+    use super::*;
+    use crate::messaging::{InMemoryBroker, MessageProducer, OutgoingMessage};
+
+    async fn broker_with(payloads: &[&str]) -> InMemoryBroker {
+        let broker = InMemoryBroker::new();
+        for payload in payloads {
+            broker
+                .send("orders", OutgoingMessage::new(payload.as_bytes().to_vec()))
+                .await
+                .unwrap();
+        }
+        broker.subscribe(&["orders".to_string()]).await.unwrap();
+        broker
+    }
+
+    #[tokio::test]
+    async fn test_commits_after_successful_handling() {
+        let broker = broker_with(&["ok"]).await;
+        let consumer = AtLeastOnceConsumer::new(broker.clone()).poll_timeout(Duration::ZERO);
+
+        let outcome = consumer
+            .poll_once(|_| async { Ok::<_, String>(()) })
+            .await
+            .unwrap();
+
+        assert_eq!(outcome, Some(DeliveryOutcome::Handled));
+        assert_eq!(broker.committed_offset("orders").await, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_retries_before_succeeding() {
+        let broker = broker_with(&["flaky"]).await;
+        let consumer = AtLeastOnceConsumer::new(broker.clone())
+            .max_attempts(3)
+            .retry_backoff(Duration::ZERO)
+            .poll_timeout(Duration::ZERO);
+
+        let calls = Arc::new(AtomicU32::new(0));
+        let outcome = consumer
+            .poll_once(|_| {
+                let calls = calls.clone();
+                async move {
+                    if calls.fetch_add(1, Ordering::SeqCst) < 2 {
+                        Err("transient")
+                    } else {
+                        Ok(())
+                    }
+                }
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(outcome, Some(DeliveryOutcome::Handled));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_poison_message_is_dead_lettered_and_committed() {
+        let broker = broker_with(&["poison"]).await;
+        let consumer = AtLeastOnceConsumer::new(broker.clone())
+            .with_dlq(DeadLetterQueue::new(broker.clone()))
+            .max_attempts(2)
+            .retry_backoff(Duration::ZERO)
+            .poll_timeout(Duration::ZERO);
+
+        let outcome = consumer
+            .poll_once(|_| async { Err::<(), _>("cannot parse") })
+            .await
+            .unwrap();
+
+        assert_eq!(outcome, Some(DeliveryOutcome::DeadLettered));
+        assert_eq!(broker.committed_offset("orders").await, Some(1));
+        let dead = broker.messages("orders.dlq").await;
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].header("dlq.error"), Some("cannot parse"));
+    }
+
+    #[tokio::test]
+    async fn test_failure_without_dlq_leaves_offset_uncommitted() {
+        let broker = broker_with(&["poison"]).await;
+        let consumer = AtLeastOnceConsumer::new(broker.clone())
+            .max_attempts(1)
+            .poll_timeout(Duration::ZERO);
+
+        let result = consumer.poll_once(|_| async { Err::<(), _>("down") }).await;
+
+        assert_eq!(result, Err(MessagingError::Handler("down".to_string())));
+        assert_eq!(broker.committed_offset("orders").await, None);
+
+        broker.rewind().await;
+        let redelivered = broker.poll(Duration::ZERO).await.unwrap();
+        assert_eq!(redelivered.unwrap().offset, 0);
+    }
+
+    #[tokio::test]
+    async fn test_run_stops_on_shutdown() {
+        let broker = broker_with(&["a", "b"]).await;
+        let consumer =
+            AtLeastOnceConsumer::new(broker.clone()).poll_timeout(Duration::from_millis(10));
+        let shutdown = Arc::new(crate::shutdown::GracefulShutdown::new());
+        let token = shutdown.token();
+
+        let handled = Arc::new(AtomicU32::new(0));
+        let counter = handled.clone();
+        let trigger = shutdown.clone();
+        consumer
+            .run(
+                move |_| {
+                    let counter = counter.clone();
+                    let trigger = trigger.clone();
+                    async move {
+                        if counter.fetch_add(1, Ordering::SeqCst) == 1 {
+                            trigger.shutdown();
+                        }
+                        Ok::<_, String>(())
+                    }
+                },
+                token,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(handled.load(Ordering::SeqCst), 2);
+        assert_eq!(broker.committed_offset("orders").await, Some(2));
+    }
+}
@@ -0,0 +1,103 @@
+//! Dead letter queue routing for poison messages
+
+use std::sync::Arc;
+
+use super::{Message, MessageProducer, MessagingError, OutgoingMessage};
+
+/// Header recording the topic a dead-lettered message came from.
+pub const DLQ_ORIGINAL_TOPIC_HEADER: &str = "dlq.original_topic";
+/// Header recording the partition a dead-lettered message came from.
+pub const DLQ_ORIGINAL_PARTITION_HEADER: &str = "dlq.original_partition";
+/// Header recording the offset a dead-lettered message came from.
+pub const DLQ_ORIGINAL_OFFSET_HEADER: &str = "dlq.original_offset";
+/// Header recording why the message was dead-lettered.
+pub const DLQ_ERROR_HEADER: &str = "dlq.error";
+
+/// Routes messages that could not be handled to a dead letter topic.
+///
+/// The dead letter topic is the original topic plus a suffix (`.dlq` by
+/// default). The original payload, key and headers are preserved, and the
+/// source position and failure reason are added as `dlq.*` headers.
+#[derive(Clone)]
+pub struct DeadLetterQueue {
+    producer: Arc<dyn MessageProducer>,
+    suffix: String,
+}
+
+impl DeadLetterQueue {
+    /// Create a dead letter queue publishing through `producer`.
+    pub fn new<P: MessageProducer + 'static>(producer: P) -> Self {
+        Self::from_arc(Arc::new(producer))
+    }
+
+    /// Create a dead letter queue from a shared producer.
+    pub fn from_arc(producer: Arc<dyn MessageProducer>) -> Self {
+        Self {
+            producer,
+            suffix: ".dlq".to_string(),
+        }
+    }
+
+    /// Set the suffix appended to the original topic name.
+    pub fn with_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.suffix = suffix.into();
+        self
+    }
+
+    /// The dead letter topic for messages from `topic`.
+    pub fn topic_for(&self, topic: &str) -> String {
+        format!("{}{}", topic, self.suffix)
+    }
+
+    /// Publish `message` to its dead letter topic, recording `error`.
+    pub async fn send(&self, message: &Message, error: &str) -> Result<(), MessagingError> {
+        let outgoing = OutgoingMessage::from(message)
+            .header(DLQ_ORIGINAL_TOPIC_HEADER, message.topic.clone())
+            .header(DLQ_ORIGINAL_PARTITION_HEADER, message.partition.to_string())
+            .header(DLQ_ORIGINAL_OFFSET_HEADER, message.offset.to_string())
+            .header(DLQ_ERROR_HEADER, error);
+
+        self.producer
+            .send(&self.topic_for(&message.topic), outgoing)
+            .await
+    }
+}
+
+impl std::fmt::Debug for DeadLetterQueue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DeadLetterQueue")
+            .field("suffix", &self.suffix)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::messaging::InMemoryBroker;
+
+    #[tokio::test]
+    async fn test_dead_letter_preserves_message_and_records_origin() {
+        let broker = InMemoryBroker::new();
+        let dlq = DeadLetterQueue::new(broker.clone()).with_suffix("-dead");
+
+        let message = Message {
+            topic: "payments".to_string(),
+            partition: 0,
+            offset: 42,
+            key: Some("acct-1".to_string()),
+            payload: b"boom".to_vec(),
+            headers: vec![("event_id".to_string(), "evt-1".to_string())],
+        };
+        dlq.send(&message, "invalid amount").await.unwrap();
+
+        let dead = broker.messages("payments-dead").await;
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].payload, b"boom");
+        assert_eq!(dead[0].key.as_deref(), Some("acct-1"));
+        assert_eq!(dead[0].event_id(), Some("evt-1"));
+        assert_eq!(dead[0].header(DLQ_ORIGINAL_TOPIC_HEADER), Some("payments"));
+        assert_eq!(dead[0].header(DLQ_ORIGINAL_OFFSET_HEADER), Some("42"));
+        assert_eq!(dead[0].header(DLQ_ERROR_HEADER), Some("invalid amount"));
+    }
+}
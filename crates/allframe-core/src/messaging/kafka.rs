@@ -0,0 +1,219 @@
+//! Kafka-backed producer and consumer (rdkafka)
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use rdkafka::{
+    config::ClientConfig,
+    consumer::{CommitMode, Consumer, StreamConsumer},
+    message::{Header, Headers, OwnedHeaders},
+    producer::{FutureProducer, FutureRecord, Producer},
+    Message as _, Offset, TopicPartitionList,
+};
+
+use super::{Message, MessageConsumer, MessageProducer, MessagingError, OutgoingMessage};
+
+/// Kafka client configuration.
+#[derive(Debug, Clone)]
+pub struct KafkaConfig {
+    /// Comma-separated `host:port` bootstrap servers
+    pub brokers: String,
+    /// Consumer group ID (consumers only)
+    pub group_id: String,
+    /// How long `send` waits to enqueue when the local queue is full
+    pub send_timeout: Duration,
+    /// Additional librdkafka properties
+    pub properties: HashMap<String, String>,
+}
+
+impl KafkaConfig {
+    /// Create a configuration for the given brokers and consumer group.
+    pub fn new(brokers: impl Into<String>, group_id: impl Into<String>) -> Self {
+        Self {
+            brokers: brokers.into(),
+            group_id: group_id.into(),
+            send_timeout: Duration::from_secs(5),
+            properties: HashMap::new(),
+        }
+    }
+
+    /// Set the send enqueue timeout.
+    pub fn send_timeout(mut self, timeout: Duration) -> Self {
+        self.send_timeout = timeout;
+        self
+    }
+
+    /// Set an additional librdkafka property (e.g. `security.protocol`).
+    pub fn property(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.properties.insert(key.into(), value.into());
+        self
+    }
+
+    fn client_config(&self) -> ClientConfig {
+        let mut config = ClientConfig::new();
+        config.set("bootstrap.servers", &self.brokers);
+        for (key, value) in &self.properties {
+            config.set(key, value);
+        }
+        config
+    }
+}
+
+fn kafka_error(err: rdkafka::error::KafkaError) -> MessagingError {
+    MessagingError::Connection(err.to_string())
+}
+
+/// Kafka consumer with auto-commit disabled.
+///
+/// Offsets are stored only when [`commit`](MessageConsumer::commit) is
+/// called, giving at-least-once delivery when combined with
+/// [`AtLeastOnceConsumer`](super::AtLeastOnceConsumer). `commit` waits for
+/// the broker to acknowledge the offset, so a message whose commit
+/// succeeded is not redelivered after a restart.
+pub struct KafkaConsumer {
+    consumer: Arc<StreamConsumer>,
+}
+
+impl KafkaConsumer {
+    /// Create a consumer from the given configuration.
+    pub fn new(config: &KafkaConfig) -> Result<Self, MessagingError> {
+        let consumer = config
+            .client_config()
+            .set("group.id", &config.group_id)
+            .set("enable.auto.commit", "false")
+            .set("enable.auto.offset.store", "false")
+            .set("enable.partition.eof", "false")
+            .create()
+            .map_err(kafka_error)?;
+        Ok(Self {
+            consumer: Arc::new(consumer),
+        })
+    }
+
+    /// Access the underlying rdkafka consumer.
+    pub fn inner(&self) -> &StreamConsumer {
+        &self.consumer
+    }
+}
+
+#[async_trait]
+impl MessageConsumer for KafkaConsumer {
+    async fn subscribe(&self, topics: &[String]) -> Result<(), MessagingError> {
+        let topics: Vec<&str> = topics.iter().map(String::as_str).collect();
+        self.consumer.subscribe(&topics).map_err(kafka_error)
+    }
+
+    async fn poll(&self, timeout: Duration) -> Result<Option<Message>, MessagingError> {
+        let received = match tokio::time::timeout(timeout, self.consumer.recv()).await {
+            Ok(received) => received.map_err(|e| MessagingError::Receive(e.to_string()))?,
+            Err(_) => return Ok(None),
+        };
+
+        let headers = received
+            .headers()
+            .map(|headers| {
+                headers
+                    .iter()
+                    .map(|header| {
+                        let value = header
+                            .value
+                            .map(|v| String::from_utf8_lossy(v).into_owned())
+                            .unwrap_or_default();
+                        (header.key.to_string(), value)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Ok(Some(Message {
+            topic: received.topic().to_string(),
+            partition: received.partition(),
+            offset: received.offset(),
+            key: received
+                .key()
+                .map(|k| String::from_utf8_lossy(k).into_owned()),
+            payload: received.payload().map(<[u8]>::to_vec).unwrap_or_default(),
+            headers,
+        }))
+    }
+
+    async fn commit(&self, message: &Message) -> Result<(), MessagingError> {
+        let mut offsets = TopicPartitionList::new();
+        offsets
+            .add_partition_offset(
+                &message.topic,
+                message.partition,
+                Offset::Offset(message.offset + 1),
+            )
+            .map_err(|e| MessagingError::Commit(e.to_string()))?;
+        // A synchronous commit blocks until the broker answers
+        let consumer = self.consumer.clone();
+        tokio::task::spawn_blocking(move || consumer.commit(&offsets, CommitMode::Sync))
+            .await
+            .map_err(|e| MessagingError::Commit(e.to_string()))?
+            .map_err(|e| MessagingError::Commit(e.to_string()))
+    }
+}
+
+/// Kafka producer.
+pub struct KafkaProducer {
+    producer: FutureProducer,
+    send_timeout: Duration,
+}
+
+impl KafkaProducer {
+    /// Create a producer from the given configuration.
+    ///
+    /// Idempotent production is enabled so broker-side retries cannot
+    /// duplicate messages.
+    pub fn new(config: &KafkaConfig) -> Result<Self, MessagingError> {
+        let producer = config
+            .client_config()
+            .set("enable.idempotence", "true")
+            .create()
+            .map_err(kafka_error)?;
+        Ok(Self {
+            producer,
+            send_timeout: config.send_timeout,
+        })
+    }
+
+    /// Access the underlying rdkafka producer.
+    pub fn inner(&self) -> &FutureProducer {
+        &self.producer
+    }
+}
+
+#[async_trait]
+impl MessageProducer for KafkaProducer {
+    async fn send(&self, topic: &str, message: OutgoingMessage) -> Result<(), MessagingError> {
+        let headers = message
+            .headers
+            .iter()
+            .fold(OwnedHeaders::new(), |headers, (key, value)| {
+                headers.insert(Header {
+                    key,
+                    value: Some(value.as_str()),
+                })
+            });
+
+        let mut record = FutureRecord::to(topic)
+            .payload(&message.payload)
+            .headers(headers);
+        if let Some(key) = &message.key {
+            record = record.key(key);
+        }
+
+        self.producer
+            .send(record, self.send_timeout)
+            .await
+            .map(|_| ())
+            .map_err(|(e, _)| MessagingError::Send(e.to_string()))
+    }
+
+    async fn flush(&self, timeout: Duration) -> Result<(), MessagingError> {
+        self.producer
+            .flush(timeout)
+            .map_err(|e| MessagingError::Send(e.to_string()))
+    }
+}
@@ -0,0 +1,186 @@
+//! In-memory message broker for tests and single-process deployments
+
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use tokio::sync::{Mutex, Notify};
+
+use super::{Message, MessageConsumer, MessageProducer, MessagingError, OutgoingMessage};
+
+#[derive(Default)]
+struct BrokerState {
+    topics: HashMap<String, Vec<Message>>,
+    subscriptions: Vec<String>,
+    positions: HashMap<String, i64>,
+    committed: HashMap<String, i64>,
+}
+
+/// In-memory broker implementing both [`MessageProducer`] and
+/// [`MessageConsumer`].
+///
+/// Each topic is a single partition (`0`). Like Kafka, polling advances a
+/// read position while [`commit`](MessageConsumer::commit) records the
+/// committed offset separately; [`rewind`](InMemoryBroker::rewind) moves the
+/// read position back to the committed offset, simulating a consumer restart
+/// so uncommitted messages are redelivered.
+///
+/// Cloning shares the underlying state.
+#[derive(Clone, Default)]
+pub struct InMemoryBroker {
+    state: Arc<Mutex<BrokerState>>,
+    notify: Arc<Notify>,
+}
+
+impl InMemoryBroker {
+    /// Create an empty broker.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// All messages published to a topic, in offset order.
+    pub async fn messages(&self, topic: &str) -> Vec<Message> {
+        let state = self.state.lock().await;
+        state.topics.get(topic).cloned().unwrap_or_default()
+    }
+
+    /// The committed offset for a topic (the next offset to be consumed).
+    pub async fn committed_offset(&self, topic: &str) -> Option<i64> {
+        self.state.lock().await.committed.get(topic).copied()
+    }
+
+    /// Reset read positions to the committed offsets.
+    pub async fn rewind(&self) {
+        let mut state = self.state.lock().await;
+        let committed = state.committed.clone();
+        for (topic, position) in state.positions.iter_mut() {
+            *position = committed.get(topic).copied().unwrap_or(0);
+        }
+    }
+
+    async fn try_next(&self) -> Option<Message> {
+        let mut state = self.state.lock().await;
+        let BrokerState {
+            topics,
+            subscriptions,
+            positions,
+            ..
+        } = &mut *state;
+
+        for topic in subscriptions.iter() {
+            let position = positions.entry(topic.clone()).or_insert(0);
+            if let Some(message) = topics
+                .get(topic)
+                .and_then(|messages| messages.get(*position as usize))
+            {
+                *position += 1;
+                return Some(message.clone());
+            }
+        }
+        None
+    }
+}
+
+#[async_trait]
+impl MessageProducer for InMemoryBroker {
+    async fn send(&self, topic: &str, message: OutgoingMessage) -> Result<(), MessagingError> {
+        {
+            let mut state = self.state.lock().await;
+            let messages = state.topics.entry(topic.to_string()).or_default();
+            messages.push(Message {
+                topic: topic.to_string(),
+                partition: 0,
+                offset: messages.len() as i64,
+                key: message.key,
+                payload: message.payload,
+                headers: message.headers,
+            });
+        }
+        self.notify.notify_waiters();
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl MessageConsumer for InMemoryBroker {
+    async fn subscribe(&self, topics: &[String]) -> Result<(), MessagingError> {
+        let mut state = self.state.lock().await;
+        for topic in topics {
+            if !state.subscriptions.contains(topic) {
+                state.subscriptions.push(topic.clone());
+                let committed = state.committed.get(topic).copied().unwrap_or(0);
+                state.positions.insert(topic.clone(), committed);
+            }
+        }
+        Ok(())
+    }
+
+    async fn poll(&self, timeout: Duration) -> Result<Option<Message>, MessagingError> {
+        let notified = self.notify.notified();
+        if let Some(message) = self.try_next().await {
+            return Ok(Some(message));
+        }
+        let _ = tokio::time::timeout(timeout, notified).await;
+        Ok(self.try_next().await)
+    }
+
+    async fn commit(&self, message: &Message) -> Result<(), MessagingError> {
+        let mut state = self.state.lock().await;
+        let committed = state.committed.entry(message.topic.clone()).or_insert(0);
+        *committed = (*committed).max(message.offset + 1);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_publish_and_poll_in_order() {
+        let broker = InMemoryBroker::new();
+        broker.subscribe(&["t".to_string()]).await.unwrap();
+        broker.send("t", OutgoingMessage::new("a")).await.unwrap();
+        broker.send("t", OutgoingMessage::new("b")).await.unwrap();
+
+        let first = broker.poll(Duration::ZERO).await.unwrap().unwrap();
+        let second = broker.poll(Duration::ZERO).await.unwrap().unwrap();
+        assert_eq!((first.offset, first.payload), (0, b"a".to_vec()));
+        assert_eq!((second.offset, second.payload), (1, b"b".to_vec()));
+        assert!(broker.poll(Duration::ZERO).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_uncommitted_messages_are_redelivered_after_rewind() {
+        let broker = InMemoryBroker::new();
+        broker.subscribe(&["t".to_string()]).await.unwrap();
+        broker.send("t", OutgoingMessage::new("a")).await.unwrap();
+        broker.send("t", OutgoingMessage::new("b")).await.unwrap();
+
+        let first = broker.poll(Duration::ZERO).await.unwrap().unwrap();
+        broker.commit(&first).await.unwrap();
+        let _second = broker.poll(Duration::ZERO).await.unwrap().unwrap();
+
+        broker.rewind().await;
+        let redelivered = broker.poll(Duration::ZERO).await.unwrap().unwrap();
+        assert_eq!(redelivered.offset, 1);
+        assert_eq!(broker.committed_offset("t").await, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_poll_wakes_on_publish() {
+        let broker = InMemoryBroker::new();
+        broker.subscribe(&["t".to_string()]).await.unwrap();
+
+        let producer = broker.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            producer
+                .send("t", OutgoingMessage::new("late"))
+                .await
+                .unwrap();
+        });
+
+        let message = broker.poll(Duration::from_secs(5)).await.unwrap();
+        assert_eq!(message.unwrap().payload, b"late");
+    }
+}
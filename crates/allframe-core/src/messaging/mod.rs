@@ -0,0 +1,291 @@
+//! Message broker abstraction for event producers and consumers.
+//!
+//! This module provides broker-agnostic [`MessageProducer`] and
+//! [`MessageConsumer`] traits, a [`DeadLetterQueue`] for poison messages, and
+//! an [`AtLeastOnceConsumer`] that commits offsets only after a message has
+//! been handled successfully. On the producing side, an [`OutboxRelay`]
+//! publishes messages written to a transactional outbox.
+//!
+//! Backends:
+//! - [`InMemoryBroker`] - single-process broker for tests and offline use
+//! - `KafkaConsumer` / `KafkaProducer` - rdkafka-backed (requires
+//!   `messaging-kafka` feature)
+//!
+//! # Example
+//!
+//! ```rust
+//! use allframe_core::messaging::{
+//!     AtLeastOnceConsumer, DeadLetterQueue, InMemoryBroker, MessageConsumer, MessageProducer,
+//!     OutgoingMessage,
+//! };
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), allframe_core::messaging::MessagingError> {
+//! let broker = InMemoryBroker::new();
+//! broker.send("orders", OutgoingMessage::new(b"created".to_vec())).await?;
+//!
+//! broker.subscribe(&["orders".to_string()]).await?;
+//! let consumer = AtLeastOnceConsumer::new(broker.clone())
+//!     .with_dlq(DeadLetterQueue::new(broker.clone()))
+//!     .max_attempts(3);
+//!
+//! consumer
+//!     .poll_once(|message| async move {
+//!         assert_eq!(message.payload, b"created");
+//!         Ok::<_, String>(())
+//!     })
+//!     .await?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod delivery;
+mod dlq;
+#[cfg(feature = "messaging-kafka")]
+mod kafka;
+mod memory;
+mod outbox;
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Serialize};
+
+pub use delivery::{AtLeastOnceConsumer, DeliveryOutcome};
+pub use dlq::{
+    DeadLetterQueue, DLQ_ERROR_HEADER, DLQ_ORIGINAL_OFFSET_HEADER, DLQ_ORIGINAL_PARTITION_HEADER,
+    DLQ_ORIGINAL_TOPIC_HEADER,
+};
+#[cfg(feature = "messaging-kafka")]
+pub use kafka::{KafkaConfig, KafkaConsumer, KafkaProducer};
+pub use memory::InMemoryBroker;
+pub use outbox::{InMemoryOutbox, OutboxEntry, OutboxRelay, OutboxStore, RelayStats};
+
+/// Header carrying the unique event identifier.
+pub const EVENT_ID_HEADER: &str = "event_id";
+
+/// Header carrying the event type discriminator.
+pub const EVENT_TYPE_HEADER: &str = "event_type";
+
+/// A message received from a broker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Message {
+    /// Topic the message was read from
+    pub topic: String,
+    /// Partition the message was read from
+    pub partition: i32,
+    /// Offset of the message within its partition
+    pub offset: i64,
+    /// Optional message key
+    pub key: Option<String>,
+    /// Raw message payload
+    pub payload: Vec<u8>,
+    /// Message headers, in broker order
+    pub headers: Vec<(String, String)>,
+}
+
+impl Message {
+    /// Get the value of the first header with the given name.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(k, _)| k == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Get the event ID header, if present.
+    pub fn event_id(&self) -> Option<&str> {
+        self.header(EVENT_ID_HEADER)
+    }
+
+    /// Get the event type header, if present.
+    pub fn event_type(&self) -> Option<&str> {
+        self.header(EVENT_TYPE_HEADER)
+    }
+
+    /// A key that uniquely identifies this message.
+    ///
+    /// Uses the event ID header when present, otherwise falls back to
+    /// `topic:partition:offset`.
+    pub fn dedup_key(&self) -> String {
+        match self.event_id() {
+            Some(id) => id.to_string(),
+            None => format!("{}:{}:{}", self.topic, self.partition, self.offset),
+        }
+    }
+
+    /// Deserialize the payload as JSON.
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T, MessagingError> {
+        serde_json::from_slice(&self.payload)
+            .map_err(|e| MessagingError::Serialization(e.to_string()))
+    }
+}
+
+/// A message to be published to a broker.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OutgoingMessage {
+    /// Optional message key (used for partitioning)
+    pub key: Option<String>,
+    /// Raw message payload
+    pub payload: Vec<u8>,
+    /// Message headers
+    pub headers: Vec<(String, String)>,
+}
+
+impl OutgoingMessage {
+    /// Create a message with the given payload.
+    pub fn new(payload: impl Into<Vec<u8>>) -> Self {
+        Self {
+            key: None,
+            payload: payload.into(),
+            headers: Vec::new(),
+        }
+    }
+
+    /// Create a message whose payload is the JSON encoding of `value`.
+    pub fn json<T: Serialize>(value: &T) -> Result<Self, MessagingError> {
+        let payload =
+            serde_json::to_vec(value).map_err(|e| MessagingError::Serialization(e.to_string()))?;
+        Ok(Self::new(payload))
+    }
+
+    /// Set the message key.
+    pub fn key(mut self, key: impl Into<String>) -> Self {
+        self.key = Some(key.into());
+        self
+    }
+
+    /// Add a header.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+impl From<&Message> for OutgoingMessage {
+    fn from(message: &Message) -> Self {
+        Self {
+            key: message.key.clone(),
+            payload: message.payload.clone(),
+            headers: message.headers.clone(),
+        }
+    }
+}
+
+/// Errors that can occur during messaging operations
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MessagingError {
+    /// Could not connect to or configure the broker
+    Connection(String),
+    /// Publishing a message failed
+    Send(String),
+    /// Receiving a message failed
+    Receive(String),
+    /// Committing an offset failed
+    Commit(String),
+    /// Payload (de)serialization failed
+    Serialization(String),
+    /// The message handler failed and the message was not dead-lettered
+    Handler(String),
+}
+
+impl std::fmt::Display for MessagingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MessagingError::Connection(msg) => write!(f, "Connection error: {}", msg),
+            MessagingError::Send(msg) => write!(f, "Send error: {}", msg),
+            MessagingError::Receive(msg) => write!(f, "Receive error: {}", msg),
+            MessagingError::Commit(msg) => write!(f, "Commit error: {}", msg),
+            MessagingError::Serialization(msg) => write!(f, "Serialization error: {}", msg),
+            MessagingError::Handler(msg) => write!(f, "Handler error: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for MessagingError {}
+
+/// Publishes messages to a broker.
+#[async_trait]
+pub trait MessageProducer: Send + Sync {
+    /// Publish a message to a topic.
+    async fn send(&self, topic: &str, message: OutgoingMessage) -> Result<(), MessagingError>;
+
+    /// Wait for in-flight messages to be delivered.
+    async fn flush(&self, timeout: Duration) -> Result<(), MessagingError> {
+        let _ = timeout;
+        Ok(())
+    }
+}
+
+/// Receives messages from a broker with manual offset management.
+///
+/// Implementations must not auto-commit: an offset is only committed when
+/// [`commit`](MessageConsumer::commit) is called, so uncommitted messages are
+/// redelivered after a restart or rebalance.
+#[async_trait]
+pub trait MessageConsumer: Send + Sync {
+    /// Subscribe to the given topics.
+    async fn subscribe(&self, topics: &[String]) -> Result<(), MessagingError>;
+
+    /// Wait up to `timeout` for the next message.
+    async fn poll(&self, timeout: Duration) -> Result<Option<Message>, MessagingError>;
+
+    /// Commit the offset of a handled message.
+    async fn commit(&self, message: &Message) -> Result<(), MessagingError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message() -> Message {
+        Message {
+            topic: "orders".to_string(),
+            partition: 2,
+            offset: 7,
+            key: None,
+            payload: br#"{"id":1}"#.to_vec(),
+            headers: vec![(EVENT_TYPE_HEADER.to_string(), "OrderPlaced".to_string())],
+        }
+    }
+
+    #[test]
+    fn test_message_headers() {
+        let msg = message();
+        assert_eq!(msg.event_type(), Some("OrderPlaced"));
+        assert_eq!(msg.event_id(), None);
+    }
+
+    #[test]
+    fn test_dedup_key_falls_back_to_position() {
+        let mut msg = message();
+        assert_eq!(msg.dedup_key(), "orders:2:7");
+
+        msg.headers
+            .push((EVENT_ID_HEADER.to_string(), "evt-1".to_string()));
+        assert_eq!(msg.dedup_key(), "evt-1");
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        #[derive(Serialize, serde::Deserialize, PartialEq, Debug)]
+        struct Order {
+            id: u32,
+        }
+
+        let out = OutgoingMessage::json(&Order { id: 1 })
+            .unwrap()
+            .key("order-1")
+            .header(EVENT_TYPE_HEADER, "OrderPlaced");
+        assert_eq!(out.key.as_deref(), Some("order-1"));
+
+        let order: Order = message().json().unwrap();
+        assert_eq!(order, Order { id: 1 });
+    }
+
+    #[test]
+    fn test_messaging_error_display() {
+        let err = MessagingError::Commit("broker gone".into());
+        assert_eq!(err.to_string(), "Commit error: broker gone");
+    }
+}
@@ -0,0 +1,384 @@
+//! Transactional outbox relay
+//!
+//! A service that changes state and publishes an event about it can't make
+//! both atomic against a database and a broker. With the outbox pattern the
+//! event is written to an outbox table in the same transaction as the
+//! state change, and an [`OutboxRelay`] publishes unsent entries afterwards,
+//! giving at-least-once publishing.
+
+use std::{sync::Arc, time::Duration};
+
+use async_trait::async_trait;
+use tokio::sync::Mutex;
+
+use super::{MessageProducer, MessagingError, OutgoingMessage};
+use crate::shutdown::ShutdownToken;
+
+/// A message waiting in an outbox.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutboxEntry {
+    /// Store-assigned entry ID
+    pub id: String,
+    /// Topic to publish to
+    pub topic: String,
+    /// The message to publish
+    pub message: OutgoingMessage,
+    /// Failed publish attempts so far
+    pub attempts: u32,
+}
+
+/// Storage for outbox entries, as read by an [`OutboxRelay`].
+///
+/// Adding entries is store-specific, since it has to happen in the
+/// application's own transaction.
+#[async_trait]
+pub trait OutboxStore: Send + Sync {
+    /// Up to `limit` unsent entries with fewer than `max_attempts` failed
+    /// attempts, oldest first.
+    async fn pending(
+        &self,
+        limit: usize,
+        max_attempts: u32,
+    ) -> Result<Vec<OutboxEntry>, MessagingError>;
+
+    /// Mark an entry as published.
+    async fn mark_sent(&self, id: &str) -> Result<(), MessagingError>;
+
+    /// Record a failed publish attempt.
+    async fn mark_failed(&self, id: &str, error: &str) -> Result<(), MessagingError>;
+}
+
+#[async_trait]
+impl<T: OutboxStore + ?Sized> OutboxStore for Arc<T> {
+    async fn pending(
+        &self,
+        limit: usize,
+        max_attempts: u32,
+    ) -> Result<Vec<OutboxEntry>, MessagingError> {
+        (**self).pending(limit, max_attempts).await
+    }
+
+    async fn mark_sent(&self, id: &str) -> Result<(), MessagingError> {
+        (**self).mark_sent(id).await
+    }
+
+    async fn mark_failed(&self, id: &str, error: &str) -> Result<(), MessagingError> {
+        (**self).mark_failed(id, error).await
+    }
+}
+
+/// What one [`OutboxRelay::relay_once`] pass did.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RelayStats {
+    /// Entries published and marked sent
+    pub published: usize,
+    /// Entries whose publish failed (at most one, as a failure ends the pass)
+    pub failed: usize,
+}
+
+/// Publishes pending outbox entries to a broker.
+///
+/// Entries are published oldest first. A failed publish is recorded on the
+/// entry and ends the pass, so later entries aren't published ahead of it;
+/// entries that reach `max_attempts` failures are left in the store for
+/// inspection and no longer relayed.
+pub struct OutboxRelay {
+    store: Arc<dyn OutboxStore>,
+    producer: Arc<dyn MessageProducer>,
+    batch_size: usize,
+    poll_interval: Duration,
+    max_attempts: u32,
+}
+
+impl OutboxRelay {
+    /// Relay from `store` to `producer`. Defaults: batches of 100, 1s poll
+    /// interval, 5 attempts per entry.
+    pub fn new<S, P>(store: S, producer: P) -> Self
+    where
+        S: OutboxStore + 'static,
+        P: MessageProducer + 'static,
+    {
+        Self {
+            store: Arc::new(store),
+            producer: Arc::new(producer),
+            batch_size: 100,
+            poll_interval: Duration::from_secs(1),
+            max_attempts: 5,
+        }
+    }
+
+    /// Set how many entries each pass reads (minimum 1).
+    pub fn batch_size(mut self, size: usize) -> Self {
+        self.batch_size = size.max(1);
+        self
+    }
+
+    /// Set how long [`run`](Self::run) waits when the outbox is drained.
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Set the number of publish attempts per entry (minimum 1).
+    pub fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Publish one batch of pending entries.
+    ///
+    /// Only store errors are returned; publish failures are recorded on the
+    /// entry and counted in [`RelayStats::failed`].
+    pub async fn relay_once(&self) -> Result<RelayStats, MessagingError> {
+        let mut stats = RelayStats::default();
+        for entry in self
+            .store
+            .pending(self.batch_size, self.max_attempts)
+            .await?
+        {
+            match self.producer.send(&entry.topic, entry.message).await {
+                Ok(()) => {
+                    self.store.mark_sent(&entry.id).await?;
+                    stats.published += 1;
+                }
+                Err(e) => {
+                    self.store.mark_failed(&entry.id, &e.to_string()).await?;
+                    stats.failed += 1;
+                    break;
+                }
+            }
+        }
+        Ok(stats)
+    }
+
+    /// Relay entries until `shutdown` fires.
+    ///
+    /// Full batches are followed by another pass straight away; otherwise
+    /// the relay waits for the poll interval. A store error stops the loop.
+    pub async fn run(&self, mut shutdown: ShutdownToken) -> Result<(), MessagingError> {
+        while !shutdown.is_shutdown() {
+            let stats = self.relay_once().await?;
+            if stats.published >= self.batch_size {
+                continue;
+            }
+            tokio::select! {
+                _ = shutdown.cancelled() => break,
+                _ = tokio::time::sleep(self.poll_interval) => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct StoredEntry {
+    entry: OutboxEntry,
+    sent: bool,
+    last_error: Option<String>,
+}
+
+/// In-memory [`OutboxStore`] for tests and single-process use.
+///
+/// Cloning shares the underlying entries.
+#[derive(Clone, Default)]
+pub struct InMemoryOutbox {
+    entries: Arc<Mutex<Vec<StoredEntry>>>,
+}
+
+impl InMemoryOutbox {
+    /// Create an empty outbox.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a message to publish to `topic`, returning the entry ID.
+    pub async fn enqueue(&self, topic: impl Into<String>, message: OutgoingMessage) -> String {
+        let mut entries = self.entries.lock().await;
+        let id = (entries.len() + 1).to_string();
+        entries.push(StoredEntry {
+            entry: OutboxEntry {
+                id: id.clone(),
+                topic: topic.into(),
+                message,
+                attempts: 0,
+            },
+            sent: false,
+            last_error: None,
+        });
+        id
+    }
+
+    /// Number of entries not yet published.
+    pub async fn unsent_count(&self) -> usize {
+        self.entries.lock().await.iter().filter(|e| !e.sent).count()
+    }
+
+    /// The last publish error recorded for an entry.
+    pub async fn last_error(&self, id: &str) -> Option<String> {
+        let entries = self.entries.lock().await;
+        entries
+            .iter()
+            .find(|e| e.entry.id == id)
+            .and_then(|e| e.last_error.clone())
+    }
+}
+
+#[async_trait]
+impl OutboxStore for InMemoryOutbox {
+    async fn pending(
+        &self,
+        limit: usize,
+        max_attempts: u32,
+    ) -> Result<Vec<OutboxEntry>, MessagingError> {
+        let entries = self.entries.lock().await;
+        Ok(entries
+            .iter()
+            .filter(|e| !e.sent && e.entry.attempts < max_attempts)
+            .take(limit)
+            .map(|e| e.entry.clone())
+            .collect())
+    }
+
+    async fn mark_sent(&self, id: &str) -> Result<(), MessagingError> {
+        let mut entries = self.entries.lock().await;
+        if let Some(e) = entries.iter_mut().find(|e| e.entry.id == id) {
+            e.sent = true;
+        }
+        Ok(())
+    }
+
+    async fn mark_failed(&self, id: &str, error: &str) -> Result<(), MessagingError> {
+        let mut entries = self.entries.lock().await;
+        if let Some(e) = entries.iter_mut().find(|e| e.entry.id == id) {
+            e.entry.attempts += 1;
+            e.last_error = Some(error.to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+    use crate::messaging::InMemoryBroker;
+
+    /// Producer that fails its first `failures` sends
+    struct FlakyProducer {
+        broker: InMemoryBroker,
+        failures: AtomicU32,
+    }
+
+    #[async_trait]
+    impl MessageProducer for FlakyProducer {
+        async fn send(&self, topic: &str, message: OutgoingMessage) -> Result<(), MessagingError> {
+            if self
+                .failures
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok()
+            {
+                return Err(MessagingError::Send("broker unavailable".to_string()));
+            }
+            self.broker.send(topic, message).await
+        }
+    }
+
+    async fn outbox_with(payloads: &[&str]) -> InMemoryOutbox {
+        let outbox = InMemoryOutbox::new();
+        for payload in payloads {
+            outbox
+                .enqueue("orders", OutgoingMessage::new(payload.as_bytes().to_vec()))
+                .await;
+        }
+        outbox
+    }
+
+    #[tokio::test]
+    async fn test_relays_in_order_and_marks_sent() {
+        let outbox = outbox_with(&["a", "b", "c"]).await;
+        let broker = InMemoryBroker::new();
+        let relay = OutboxRelay::new(outbox.clone(), broker.clone()).batch_size(2);
+
+        let stats = relay.relay_once().await.unwrap();
+        assert_eq!((stats.published, stats.failed), (2, 0));
+        relay.relay_once().await.unwrap();
+
+        let payloads: Vec<Vec<u8>> = broker
+            .messages("orders")
+            .await
+            .into_iter()
+            .map(|m| m.payload)
+            .collect();
+        assert_eq!(payloads, [b"a".to_vec(), b"b".to_vec(), b"c".to_vec()]);
+        assert_eq!(outbox.unsent_count().await, 0);
+
+        // Nothing is published twice
+        assert_eq!(relay.relay_once().await.unwrap(), RelayStats::default());
+    }
+
+    #[tokio::test]
+    async fn test_failure_ends_pass_and_is_retried() {
+        let outbox = outbox_with(&["a", "b"]).await;
+        let broker = InMemoryBroker::new();
+        let producer = FlakyProducer {
+            broker: broker.clone(),
+            failures: AtomicU32::new(1),
+        };
+        let relay = OutboxRelay::new(outbox.clone(), producer);
+
+        let stats = relay.relay_once().await.unwrap();
+        assert_eq!((stats.published, stats.failed), (0, 1));
+        assert!(broker.messages("orders").await.is_empty());
+        assert_eq!(
+            outbox.last_error("1").await.as_deref(),
+            Some("Send error: broker unavailable")
+        );
+
+        let stats = relay.relay_once().await.unwrap();
+        assert_eq!(stats.published, 2);
+        assert_eq!(broker.messages("orders").await[0].payload, b"a");
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let outbox = outbox_with(&["poison"]).await;
+        let producer = FlakyProducer {
+            broker: InMemoryBroker::new(),
+            failures: AtomicU32::new(u32::MAX),
+        };
+        let relay = OutboxRelay::new(outbox.clone(), producer).max_attempts(2);
+
+        relay.relay_once().await.unwrap();
+        relay.relay_once().await.unwrap();
+        assert_eq!(relay.relay_once().await.unwrap(), RelayStats::default());
+        assert_eq!(outbox.unsent_count().await, 1);
+    }
+
+    #[tokio::test]
+    async fn test_run_stops_on_shutdown() {
+        let outbox = outbox_with(&["a"]).await;
+        let broker = InMemoryBroker::new();
+        let relay = Arc::new(
+            OutboxRelay::new(outbox.clone(), broker.clone())
+                .poll_interval(Duration::from_millis(5)),
+        );
+        let shutdown = crate::shutdown::GracefulShutdown::new();
+
+        let running = tokio::spawn({
+            let relay = relay.clone();
+            let token = shutdown.token();
+            async move { relay.run(token).await }
+        });
+        outbox
+            .enqueue("orders", OutgoingMessage::new(b"b".to_vec()))
+            .await;
+        while broker.messages("orders").await.len() < 2 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+
+        shutdown.shutdown();
+        running.await.unwrap().unwrap();
+        assert_eq!(outbox.unsent_count().await, 0);
+    }
+}
@@ -0,0 +1,265 @@
+//! Deterministic decimal handling for monetary values
+//!
+//! Monetary amounts cross service boundaries as strings (protobuf, JSON)
+//! and must round-trip exactly. A bare `.parse()` accepts inputs a
+//! financial service shouldn't, such as `1_000`, and silently keeps more
+//! decimal places than the currency or asset has. An [`AmountFormat`]
+//! describes what a valid amount looks like and parses, rounds and formats
+//! amounts consistently:
+//!
+//! - inputs are plain decimal numbers: an optional leading `-`, digits, and
+//!   an optional fractional part; no exponents, separators, `NaN` or
+//!   `inf`
+//! - more decimal places than the format's scale are rejected rather than
+//!   rounded away, unless they are trailing zeros
+//! - negative amounts are rejected unless allowed
+//! - formatting always writes exactly `scale` decimal places
+//!
+//! ```rust
+//! use allframe_core::money::{AmountFormat, Decimal, MoneyError};
+//!
+//! const USD: AmountFormat = AmountFormat::new(2);
+//!
+//! let price = USD.parse("19.9").unwrap();
+//! assert_eq!(USD.format(price), "19.90");
+//! assert_eq!(USD.format(USD.round(price / Decimal::from(3))), "6.63");
+//!
+//! assert!(matches!(USD.parse("19.999"), Err(MoneyError::TooManyDecimals { .. })));
+//! assert!(matches!(USD.parse("-5"), Err(MoneyError::Negative(_))));
+//! ```
+
+use std::{fmt, str::FromStr};
+
+pub use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::router::HttpError;
+
+/// Errors from parsing a monetary amount
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MoneyError {
+    /// The input is empty
+    Empty,
+    /// The input isn't a plain decimal number
+    Invalid(String),
+    /// The input has more decimal places than the format allows
+    TooManyDecimals {
+        /// The rejected input
+        value: String,
+        /// Decimal places the format allows
+        scale: u32,
+    },
+    /// The input is negative and the format doesn't allow it
+    Negative(String),
+    /// The input doesn't fit in a [`Decimal`]
+    OutOfRange(String),
+}
+
+impl fmt::Display for MoneyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoneyError::Empty => write!(f, "Amount is empty"),
+            MoneyError::Invalid(value) => write!(f, "Invalid amount: '{}'", value),
+            MoneyError::TooManyDecimals { value, scale } => write!(
+                f,
+                "Amount '{}' has more than {} decimal places",
+                value, scale
+            ),
+            MoneyError::Negative(value) => write!(f, "Amount '{}' must not be negative", value),
+            MoneyError::OutOfRange(value) => write!(f, "Amount '{}' is out of range", value),
+        }
+    }
+}
+
+impl std::error::Error for MoneyError {}
+
+impl HttpError for MoneyError {
+    fn status_code(&self) -> u16 {
+        400
+    }
+}
+
+/// Scale and sign rules for a kind of monetary amount
+///
+/// Use one format per currency or asset, e.g. 2 decimal places for USD or
+/// 8 for BTC. Formats are `const`-constructible, so they can be declared
+/// once as constants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AmountFormat {
+    scale: u32,
+    allow_negative: bool,
+    rounding: RoundingStrategy,
+}
+
+impl AmountFormat {
+    /// Format with `scale` decimal places, rejecting negative amounts
+    ///
+    /// Rounds half to even (banker's rounding). `scale` is capped at 28,
+    /// the most a [`Decimal`] can hold.
+    pub const fn new(scale: u32) -> Self {
+        Self {
+            scale: if scale > 28 { 28 } else { scale },
+            allow_negative: false,
+            rounding: RoundingStrategy::MidpointNearestEven,
+        }
+    }
+
+    /// Allow negative amounts, e.g. for balance adjustments
+    pub const fn with_negative(mut self, allow: bool) -> Self {
+        self.allow_negative = allow;
+        self
+    }
+
+    /// Set how [`round`](Self::round) and [`format`](Self::format) round
+    pub const fn with_rounding(mut self, rounding: RoundingStrategy) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Number of decimal places
+    pub const fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    /// Parse and validate an amount
+    ///
+    /// The result carries exactly `scale` decimal places, so equal amounts
+    /// parsed from `"1.5"` and `"1.50"` also format the same.
+    pub fn parse(&self, input: &str) -> Result<Decimal, MoneyError> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Err(MoneyError::Empty);
+        }
+        if !is_plain_decimal(input) {
+            return Err(MoneyError::Invalid(input.to_string()));
+        }
+
+        let mut value =
+            Decimal::from_str(input).map_err(|_| MoneyError::OutOfRange(input.to_string()))?;
+        if value.normalize().scale() > self.scale {
+            return Err(MoneyError::TooManyDecimals {
+                value: input.to_string(),
+                scale: self.scale,
+            });
+        }
+        if value.is_sign_negative() && !value.is_zero() && !self.allow_negative {
+            return Err(MoneyError::Negative(input.to_string()));
+        }
+        if value.is_zero() {
+            value.set_sign_positive(true);
+        }
+        value.rescale(self.scale);
+        Ok(value)
+    }
+
+    /// Round a computed value to the format's scale
+    pub fn round(&self, value: Decimal) -> Decimal {
+        let mut rounded = value.round_dp_with_strategy(self.scale, self.rounding);
+        rounded.rescale(self.scale);
+        rounded
+    }
+
+    /// Format a value with exactly `scale` decimal places
+    ///
+    /// Values with more decimal places are rounded first. Negative zero is
+    /// written without a sign.
+    pub fn format(&self, value: Decimal) -> String {
+        let mut rounded = self.round(value);
+        if rounded.is_zero() {
+            rounded.set_sign_positive(true);
+        }
+        rounded.to_string()
+    }
+}
+
+/// Check for `-?digits(.digits)?`
+fn is_plain_decimal(input: &str) -> bool {
+    let unsigned = input.strip_prefix('-').unwrap_or(input);
+    let (whole, fraction) = match unsigned.split_once('.') {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (unsigned, None),
+    };
+    let digits = |s: &str| !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit());
+    digits(whole) && fraction.map_or(true, digits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USD: AmountFormat = AmountFormat::new(2);
+    const BTC: AmountFormat = AmountFormat::new(8);
+
+    fn dec(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn test_parse_normalizes_scale() {
+        assert_eq!(USD.parse("12").unwrap().to_string(), "12.00");
+        assert_eq!(USD.parse(" 12.5 ").unwrap().to_string(), "12.50");
+        // Trailing zeros beyond the scale don't change the value
+        assert_eq!(USD.parse("12.500").unwrap().to_string(), "12.50");
+        assert_eq!(BTC.parse("0.00000001").unwrap(), dec("0.00000001"));
+        assert_eq!(USD.parse("-0").unwrap().to_string(), "0.00");
+    }
+
+    #[test]
+    fn test_rejects_invalid_inputs() {
+        for input in [
+            "abc", "NaN", "inf", "1e5", "1_000", "1,000.00", "+5", ".5", "5.", "--1", "0x10",
+        ] {
+            assert_eq!(
+                USD.parse(input),
+                Err(MoneyError::Invalid(input.to_string())),
+                "{input}"
+            );
+        }
+        assert_eq!(USD.parse("  "), Err(MoneyError::Empty));
+        assert_eq!(
+            USD.parse("1.005"),
+            Err(MoneyError::TooManyDecimals {
+                value: "1.005".to_string(),
+                scale: 2
+            })
+        );
+        assert_eq!(
+            USD.parse("-3.10"),
+            Err(MoneyError::Negative("-3.10".to_string()))
+        );
+        assert!(matches!(
+            USD.parse("123456789012345678901234567890"),
+            Err(MoneyError::OutOfRange(_))
+        ));
+        assert_eq!(USD.parse("abc").unwrap_err().status_code(), 400);
+
+        let signed = USD.with_negative(true);
+        assert_eq!(signed.parse("-3.10").unwrap().to_string(), "-3.10");
+    }
+
+    #[test]
+    fn test_rounding() {
+        // Banker's rounding by default: halves go to the even digit
+        assert_eq!(USD.format(dec("2.345")), "2.34");
+        assert_eq!(USD.format(dec("2.355")), "2.36");
+        assert_eq!(USD.format(dec("-2.345")), "-2.34");
+        assert_eq!(USD.format(dec("2.3451")), "2.35");
+
+        let half_up = USD.with_rounding(RoundingStrategy::MidpointAwayFromZero);
+        assert_eq!(half_up.format(dec("2.345")), "2.35");
+        assert_eq!(half_up.format(dec("-2.345")), "-2.35");
+
+        let truncate = USD.with_rounding(RoundingStrategy::ToZero);
+        assert_eq!(truncate.round(dec("2.349")), dec("2.34"));
+
+        // Negative zero formats without a sign
+        assert_eq!(USD.format(dec("-0.001")), "0.00");
+        assert_eq!(BTC.format(Decimal::ONE / Decimal::from(3)), "0.33333333");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for input in ["0.00", "1.10", "99999999.99", "0.01"] {
+            assert_eq!(USD.format(USD.parse(input).unwrap()), input);
+        }
+    }
+}
@@ -1,7 +1,7 @@
 //! Observability builder for easy setup of tracing and metrics
 //!
 //! This module provides a fluent builder API for configuring OpenTelemetry
-//! tracing with OTLP export, structured logging, and more.
+//! tracing and metrics with OTLP export, structured logging, and more.
 //!
 //! # Example
 //!
@@ -14,13 +14,17 @@
 //!     .otlp_endpoint_from_env()
 //!     .json_logging()
 //!     .log_level_from_env()
+//!     .with_metrics()
 //!     .build()?;
 //!
 //! // Guard keeps the subscriber active
-//! // When dropped, flushes pending spans
+//! // When dropped, flushes pending spans and metrics
 //! ```
 
-use std::env;
+use std::{env, time::Duration};
+
+/// Default interval between metric exports
+const DEFAULT_METRICS_INTERVAL: Duration = Duration::from_secs(60);
 
 /// Builder for configuring observability (tracing, metrics, logging)
 pub struct ObservabilityBuilder {
@@ -30,6 +34,8 @@ pub struct ObservabilityBuilder {
     otlp_endpoint: Option<String>,
     json_logging: bool,
     log_level: String,
+    metrics: bool,
+    metrics_interval: Duration,
 }
 
 impl ObservabilityBuilder {
@@ -42,6 +48,8 @@ impl ObservabilityBuilder {
             otlp_endpoint: None,
             json_logging: false,
             log_level: "info".to_string(),
+            metrics: false,
+            metrics_interval: DEFAULT_METRICS_INTERVAL,
         }
     }
 
@@ -76,6 +84,9 @@ impl ObservabilityBuilder {
     }
 
     /// Enable JSON-formatted log output (for production)
+    ///
+    /// Log lines recorded inside a span include its `trace_id` and `span_id`,
+    /// so they can be correlated with the exported trace.
     pub fn json_logging(mut self) -> Self {
         self.json_logging = true;
         self
@@ -95,10 +106,27 @@ impl ObservabilityBuilder {
         self
     }
 
+    /// Export metrics to the OTLP endpoint alongside traces
+    ///
+    /// Installs the global meter provider, so instruments created with
+    /// `opentelemetry::global::meter` are exported with the same resource
+    /// attributes (service name, version, environment) as spans. Has no
+    /// effect without an OTLP endpoint.
+    pub fn with_metrics(mut self) -> Self {
+        self.metrics = true;
+        self
+    }
+
+    /// Set how often metrics are exported (default: 60 seconds)
+    pub fn metrics_interval(mut self, interval: Duration) -> Self {
+        self.metrics_interval = interval;
+        self
+    }
+
     /// Build and initialize the observability stack
     ///
     /// Returns a guard that must be kept alive for the duration of the program.
-    /// When the guard is dropped, pending spans are flushed.
+    /// When the guard is dropped, pending spans and metrics are flushed.
     #[cfg(feature = "otel-otlp")]
     pub fn build(self) -> Result<ObservabilityGuard, ObservabilityError> {
         use opentelemetry::trace::TracerProvider as _;
@@ -128,6 +156,31 @@ impl ObservabilityBuilder {
 
         let resource = opentelemetry_sdk::Resource::new(resource_attrs);
 
+        // Build meter provider, sharing the resource with traces
+        let meter_provider = match &self.otlp_endpoint {
+            Some(endpoint) if self.metrics => {
+                let exporter = opentelemetry_otlp::MetricExporter::builder()
+                    .with_tonic()
+                    .with_endpoint(endpoint)
+                    .build()
+                    .map_err(|e| ObservabilityError::ExporterInit(e.to_string()))?;
+                let reader = opentelemetry_sdk::metrics::PeriodicReader::builder(
+                    exporter,
+                    opentelemetry_sdk::runtime::Tokio,
+                )
+                .with_interval(self.metrics_interval)
+                .build();
+
+                let provider = opentelemetry_sdk::metrics::SdkMeterProvider::builder()
+                    .with_reader(reader)
+                    .with_resource(resource.clone())
+                    .build();
+                opentelemetry::global::set_meter_provider(provider.clone());
+                Some(provider)
+            }
+            _ => None,
+        };
+
         // Build tracer provider
         let tracer_provider = if let Some(endpoint) = &self.otlp_endpoint {
             // OTLP exporter with batch processing
@@ -155,12 +208,16 @@ impl ObservabilityBuilder {
         // Build subscriber based on logging format
         // Note: telemetry layer must be added last so it sees all events
         if self.json_logging {
-            let fmt_layer = tracing_subscriber::fmt::layer()
+            // Log lines carry the trace and span ids for correlation
+            let format = tracing_subscriber::fmt::format()
                 .json()
                 .with_target(true)
                 .with_thread_ids(true)
                 .with_file(true)
                 .with_line_number(true);
+            let fmt_layer = tracing_subscriber::fmt::layer()
+                .json()
+                .event_format(super::TraceCorrelation::new(format));
 
             let telemetry_layer = tracing_opentelemetry::layer().with_tracer(tracer);
 
@@ -189,6 +246,7 @@ impl ObservabilityBuilder {
 
         Ok(ObservabilityGuard {
             _tracer_provider: Some(tracer_provider),
+            _meter_provider: meter_provider,
         })
     }
 
@@ -208,16 +266,20 @@ impl ObservabilityBuilder {
         Ok(ObservabilityGuard {
             #[cfg(feature = "otel-otlp")]
             _tracer_provider: None,
+            #[cfg(feature = "otel-otlp")]
+            _meter_provider: None,
         })
     }
 }
 
 /// Guard that keeps the observability stack active
 ///
-/// When dropped, flushes any pending spans to the exporter.
+/// When dropped, flushes any pending spans and metrics to the exporter.
 pub struct ObservabilityGuard {
     #[cfg(feature = "otel-otlp")]
     _tracer_provider: Option<opentelemetry_sdk::trace::TracerProvider>,
+    #[cfg(feature = "otel-otlp")]
+    _meter_provider: Option<opentelemetry_sdk::metrics::SdkMeterProvider>,
 }
 
 impl Drop for ObservabilityGuard {
@@ -229,6 +291,14 @@ impl Drop for ObservabilityGuard {
                 eprintln!("Error shutting down tracer provider: {:?}", e);
             }
         }
+
+        #[cfg(feature = "otel-otlp")]
+        if let Some(provider) = self._meter_provider.take() {
+            // Export the last metrics and shutdown the meter provider
+            if let Err(e) = provider.shutdown() {
+                eprintln!("Error shutting down meter provider: {:?}", e);
+            }
+        }
     }
 }
 
@@ -275,6 +345,8 @@ mod tests {
         assert!(builder.otlp_endpoint.is_none());
         assert!(!builder.json_logging);
         assert_eq!(builder.log_level, "info");
+        assert!(!builder.metrics);
+        assert_eq!(builder.metrics_interval, DEFAULT_METRICS_INTERVAL);
     }
 
     #[test]
@@ -284,7 +356,9 @@ mod tests {
             .environment("production")
             .otlp_endpoint("http://localhost:4317")
             .json_logging()
-            .log_level("debug");
+            .log_level("debug")
+            .with_metrics()
+            .metrics_interval(Duration::from_secs(10));
 
         assert_eq!(builder.service_version, Some("1.0.0".to_string()));
         assert_eq!(builder.environment, Some("production".to_string()));
@@ -294,6 +368,8 @@ mod tests {
         );
         assert!(builder.json_logging);
         assert_eq!(builder.log_level, "debug");
+        assert!(builder.metrics);
+        assert_eq!(builder.metrics_interval, Duration::from_secs(10));
     }
 
     #[test]
@@ -0,0 +1,241 @@
+//! Correlating logs with traces
+//!
+//! With OTLP export enabled, every span has an OpenTelemetry trace id and
+//! span id. [`current_trace_id`] and [`current_span_id`] return those of the
+//! active span, and [`TraceCorrelation`] adds them to JSON log lines so a log
+//! aggregator can link each line to its trace.
+//!
+//! [`ObservabilityBuilder`](super::ObservabilityBuilder) installs
+//! [`TraceCorrelation`] when JSON logging is enabled:
+//!
+//! ```json
+//! {"timestamp":"...","level":"INFO","fields":{"message":"order placed"},"target":"shop","trace_id":"4bf92f3577b34da6a3ce929d0e0e4736","span_id":"00f067aa0ba902b7"}
+//! ```
+
+/// Trace id reported outside an exported span (the invalid, all-zero id)
+const INVALID_TRACE_ID: &str = "00000000000000000000000000000000";
+
+/// Span id reported outside an exported span (the invalid, all-zero id)
+const INVALID_SPAN_ID: &str = "0000000000000000";
+
+/// Get the OpenTelemetry trace id of the current span, as 32 hex digits
+///
+/// Returns the all-zero invalid id outside a span, or when spans aren't
+/// tracked by OpenTelemetry (requires the `otel-otlp` feature and a
+/// subscriber set up by [`ObservabilityBuilder`](super::ObservabilityBuilder)).
+pub fn current_trace_id() -> String {
+    #[cfg(feature = "otel-otlp")]
+    if let Some((trace_id, _)) = otlp::current_ids() {
+        return trace_id.to_string();
+    }
+    INVALID_TRACE_ID.to_string()
+}
+
+/// Get the OpenTelemetry span id of the current span, as 16 hex digits
+///
+/// Returns the all-zero invalid id outside a span, or when spans aren't
+/// tracked by OpenTelemetry.
+pub fn current_span_id() -> String {
+    #[cfg(feature = "otel-otlp")]
+    if let Some((_, span_id)) = otlp::current_ids() {
+        return span_id.to_string();
+    }
+    INVALID_SPAN_ID.to_string()
+}
+
+#[cfg(feature = "otel-otlp")]
+pub use otlp::TraceCorrelation;
+
+#[cfg(feature = "otel-otlp")]
+pub(super) use otlp::with_otel_data;
+
+#[cfg(feature = "otel-otlp")]
+mod otlp {
+    use std::fmt;
+
+    use opentelemetry::trace::{SpanId, TraceContextExt, TraceId};
+    use tracing::{Event, Subscriber};
+    use tracing_opentelemetry::OtelData;
+    use tracing_subscriber::{
+        fmt::{format::Writer, FmtContext, FormatEvent, FormatFields},
+        registry::{LookupSpan, Registry},
+    };
+
+    /// Run `f` on the OpenTelemetry data of a span
+    ///
+    /// Returns `None` if the span isn't tracked by an OpenTelemetry layer.
+    pub(in crate::otel) fn with_otel_data<R>(
+        span: &tracing::Span,
+        f: impl FnOnce(&mut OtelData) -> R,
+    ) -> Option<R> {
+        span.with_subscriber(|(id, dispatch)| {
+            let registry = dispatch.downcast_ref::<Registry>()?;
+            let span = registry.span(id)?;
+            let mut extensions = span.extensions_mut();
+            extensions.get_mut::<OtelData>().map(f)
+        })
+        .flatten()
+    }
+
+    /// Trace id and span id of a span, if valid
+    fn ids(data: &OtelData) -> Option<(TraceId, SpanId)> {
+        // Only root spans get a trace id up front; others inherit it
+        let trace_id = data
+            .builder
+            .trace_id
+            .unwrap_or_else(|| data.parent_cx.span().span_context().trace_id());
+        let span_id = data.builder.span_id?;
+        (trace_id != TraceId::INVALID && span_id != SpanId::INVALID).then_some((trace_id, span_id))
+    }
+
+    pub(super) fn current_ids() -> Option<(TraceId, SpanId)> {
+        with_otel_data(&tracing::Span::current(), |data| ids(data)).flatten()
+    }
+
+    /// Event formatter that adds `trace_id` and `span_id` to JSON log lines
+    ///
+    /// Wraps a JSON formatter, such as `tracing_subscriber::fmt::format().json()`,
+    /// and adds the ids of the span the event was recorded in as top-level
+    /// fields. Events outside an exported span are written unchanged.
+    ///
+    /// ```rust,ignore
+    /// use allframe_core::otel::TraceCorrelation;
+    ///
+    /// let fmt_layer = tracing_subscriber::fmt::layer()
+    ///     .json()
+    ///     .event_format(TraceCorrelation::new(tracing_subscriber::fmt::format().json()));
+    /// ```
+    #[derive(Debug, Clone, Default)]
+    pub struct TraceCorrelation<F> {
+        inner: F,
+    }
+
+    impl<F> TraceCorrelation<F> {
+        /// Wrap a JSON event formatter
+        pub fn new(inner: F) -> Self {
+            Self { inner }
+        }
+    }
+
+    impl<S, N, F> FormatEvent<S, N> for TraceCorrelation<F>
+    where
+        S: Subscriber + for<'a> LookupSpan<'a>,
+        N: for<'a> FormatFields<'a> + 'static,
+        F: FormatEvent<S, N>,
+    {
+        fn format_event(
+            &self,
+            ctx: &FmtContext<'_, S, N>,
+            mut writer: Writer<'_>,
+            event: &Event<'_>,
+        ) -> fmt::Result {
+            let span_ids = ctx
+                .event_scope()
+                .and_then(|mut scope| scope.next())
+                .and_then(|span| span.extensions().get::<OtelData>().and_then(ids));
+            let Some((trace_id, span_id)) = span_ids else {
+                return self.inner.format_event(ctx, writer, event);
+            };
+
+            let mut line = String::new();
+            self.inner
+                .format_event(ctx, Writer::new(&mut line), event)?;
+
+            // Insert the ids before the closing brace of the JSON object
+            let end = line.trim_end().len();
+            if !line[..end].ends_with('}') {
+                return writer.write_str(&line);
+            }
+            let (object, rest) = line.split_at(end - 1);
+            write!(
+                writer,
+                "{},\"trace_id\":\"{}\",\"span_id\":\"{}\"{}",
+                object, trace_id, span_id, rest
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ids_outside_span() {
+        assert_eq!(current_trace_id(), INVALID_TRACE_ID);
+        assert_eq!(current_span_id(), INVALID_SPAN_ID);
+    }
+
+    #[cfg(feature = "otel-otlp")]
+    #[test]
+    fn test_json_logs_carry_trace_context() {
+        use std::sync::{Arc, Mutex};
+
+        use opentelemetry::trace::TracerProvider as _;
+        use tracing_subscriber::{fmt::MakeWriter, layer::SubscriberExt};
+
+        #[derive(Clone, Default)]
+        struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+        impl std::io::Write for Buffer {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().extend_from_slice(buf);
+                Ok(buf.len())
+            }
+
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        impl<'a> MakeWriter<'a> for Buffer {
+            type Writer = Buffer;
+
+            fn make_writer(&'a self) -> Self::Writer {
+                self.clone()
+            }
+        }
+
+        let provider = opentelemetry_sdk::trace::TracerProvider::builder().build();
+        let buffer = Buffer::default();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")))
+            .with(
+                tracing_subscriber::fmt::layer()
+                    .json()
+                    .event_format(TraceCorrelation::new(
+                        tracing_subscriber::fmt::format().json(),
+                    ))
+                    .with_writer(buffer.clone()),
+            );
+
+        let (trace_id, span_id, child_trace_id) =
+            tracing::subscriber::with_default(subscriber, || {
+                tracing::info!("outside");
+                let span = tracing::info_span!("request");
+                let _entered = span.enter();
+                tracing::info!("inside");
+                let ids = (current_trace_id(), current_span_id());
+
+                let child = tracing::info_span!("query");
+                let child_trace_id = child.in_scope(current_trace_id);
+                (ids.0, ids.1, child_trace_id)
+            });
+
+        assert_ne!(trace_id, INVALID_TRACE_ID);
+        assert_ne!(span_id, INVALID_SPAN_ID);
+        // Child spans belong to the same trace
+        assert_eq!(child_trace_id, trace_id);
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].get("trace_id").is_none());
+        assert_eq!(lines[1]["fields"]["message"], "inside");
+        assert_eq!(lines[1]["trace_id"], trace_id.as_str());
+        assert_eq!(lines[1]["span_id"], span_id.as_str());
+    }
+}
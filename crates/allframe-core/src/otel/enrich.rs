@@ -0,0 +1,272 @@
+//! Enriching the current span from application code
+//!
+//! [`add_attribute`] and [`record_event`] annotate whichever span is current,
+//! so they can be called mid-function without creating spans by hand.
+//!
+//! Inside a `#[traced]` function the current span is the one the macro
+//! created, so attributes and events land on it:
+//!
+//! ```rust,ignore
+//! use allframe_core::otel::{add_attribute, record_event, traced};
+//!
+//! #[traced]
+//! async fn place_order(order: Order) -> Result<OrderId, OrderError> {
+//!     add_attribute("order.items", order.items.len() as i64);
+//!     let id = reserve_stock(&order).await?;
+//!     record_event("stock.reserved", &[("order.id", id.to_string().into())]);
+//!     Ok(id)
+//! }
+//! ```
+//!
+//! Tracing spans have a fixed set of fields, declared when the span is
+//! created. An attribute whose key matches a declared field (e.g. one
+//! declared as `tracing::field::Empty`) is recorded through `tracing` and
+//! shows up in logs as well as in exported traces. Any other attribute is
+//! only exported to OpenTelemetry, and is dropped without the `otel-otlp`
+//! feature.
+//!
+//! Events are added to the exported span with their attributes intact.
+//! Without an OpenTelemetry layer, they are emitted as `INFO` tracing events
+//! instead, so they still reach the logs.
+
+use std::fmt;
+
+/// Value of a span attribute or event attribute
+#[derive(Debug, Clone, PartialEq)]
+pub enum AttributeValue {
+    /// String value
+    String(String),
+    /// Integer value
+    I64(i64),
+    /// Floating point value
+    F64(f64),
+    /// Boolean value
+    Bool(bool),
+}
+
+impl fmt::Display for AttributeValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AttributeValue::String(v) => write!(f, "{:?}", v),
+            AttributeValue::I64(v) => write!(f, "{}", v),
+            AttributeValue::F64(v) => write!(f, "{}", v),
+            AttributeValue::Bool(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+impl From<&str> for AttributeValue {
+    fn from(value: &str) -> Self {
+        AttributeValue::String(value.to_string())
+    }
+}
+
+impl From<String> for AttributeValue {
+    fn from(value: String) -> Self {
+        AttributeValue::String(value)
+    }
+}
+
+impl From<i64> for AttributeValue {
+    fn from(value: i64) -> Self {
+        AttributeValue::I64(value)
+    }
+}
+
+impl From<i32> for AttributeValue {
+    fn from(value: i32) -> Self {
+        AttributeValue::I64(value.into())
+    }
+}
+
+impl From<u32> for AttributeValue {
+    fn from(value: u32) -> Self {
+        AttributeValue::I64(value.into())
+    }
+}
+
+impl From<f64> for AttributeValue {
+    fn from(value: f64) -> Self {
+        AttributeValue::F64(value)
+    }
+}
+
+impl From<bool> for AttributeValue {
+    fn from(value: bool) -> Self {
+        AttributeValue::Bool(value)
+    }
+}
+
+#[cfg(feature = "otel-otlp")]
+impl From<AttributeValue> for opentelemetry::Value {
+    fn from(value: AttributeValue) -> Self {
+        match value {
+            AttributeValue::String(v) => v.into(),
+            AttributeValue::I64(v) => v.into(),
+            AttributeValue::F64(v) => v.into(),
+            AttributeValue::Bool(v) => v.into(),
+        }
+    }
+}
+
+/// Add an attribute to the current span
+///
+/// Does nothing outside a span.
+pub fn add_attribute(key: &str, value: impl Into<AttributeValue>) {
+    let span = tracing::Span::current();
+    let value = value.into();
+
+    let declared = span
+        .metadata()
+        .is_some_and(|metadata| metadata.fields().field(key).is_some());
+    if declared {
+        match &value {
+            AttributeValue::String(v) => span.record(key, v.as_str()),
+            AttributeValue::I64(v) => span.record(key, v),
+            AttributeValue::F64(v) => span.record(key, v),
+            AttributeValue::Bool(v) => span.record(key, v),
+        };
+    }
+
+    #[cfg(feature = "otel-otlp")]
+    if !declared {
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+        span.set_attribute(key.to_string(), value);
+    }
+}
+
+/// Record an event on the current span
+///
+/// Events mark something that happened at a point in time during the span,
+/// such as a retry or a cache miss.
+pub fn record_event(name: &str, attributes: &[(&str, AttributeValue)]) {
+    #[cfg(feature = "otel-otlp")]
+    if otlp::add_event(name, attributes) {
+        return;
+    }
+
+    tracing::info!(
+        target: "allframe::otel",
+        attributes = %Attributes(attributes),
+        "{}",
+        name
+    );
+}
+
+/// Formats event attributes as `key=value` pairs
+struct Attributes<'a>(&'a [(&'a str, AttributeValue)]);
+
+impl fmt::Display for Attributes<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, (key, value)) in self.0.iter().enumerate() {
+            if i > 0 {
+                f.write_str(" ")?;
+            }
+            write!(f, "{}={}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "otel-otlp")]
+mod otlp {
+    use std::time::SystemTime;
+
+    use opentelemetry::{trace::Event, KeyValue};
+
+    use super::AttributeValue;
+    use crate::otel::correlation::with_otel_data;
+
+    /// Add an event to the OpenTelemetry data of the current span
+    ///
+    /// Returns `false` if the current span isn't tracked by an
+    /// OpenTelemetry layer.
+    pub(super) fn add_event(name: &str, attributes: &[(&str, AttributeValue)]) -> bool {
+        with_otel_data(&tracing::Span::current(), |data| {
+            let attributes = attributes
+                .iter()
+                .map(|(key, value)| KeyValue::new(key.to_string(), value.clone()))
+                .collect();
+            data.builder
+                .events
+                .get_or_insert_with(Vec::new)
+                .push(Event::new(
+                    name.to_string(),
+                    SystemTime::now(),
+                    attributes,
+                    0,
+                ));
+        })
+        .is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outside_span_is_noop() {
+        add_attribute("user.id", "alice");
+        record_event("cache.miss", &[("key", "user:1".into())]);
+    }
+
+    #[test]
+    fn test_attributes_display() {
+        let attributes = [
+            ("key", AttributeValue::from("user:1")),
+            ("attempt", 2.into()),
+            ("hit", false.into()),
+        ];
+        assert_eq!(
+            Attributes(&attributes).to_string(),
+            r#"key="user:1" attempt=2 hit=false"#
+        );
+    }
+
+    #[cfg(feature = "otel-otlp")]
+    #[test]
+    fn test_enrich_otel_span() {
+        use opentelemetry::{Key, Value};
+        use tracing_opentelemetry::OtelData;
+        use tracing_subscriber::{layer::SubscriberExt, registry::LookupSpan, Registry};
+
+        let subscriber = Registry::default().with(tracing_opentelemetry::layer());
+        tracing::subscriber::with_default(subscriber, || {
+            let span = tracing::info_span!("checkout", order_id = tracing::field::Empty);
+            let _entered = span.enter();
+
+            add_attribute("order_id", "o-1");
+            add_attribute("items", 3);
+            record_event("stock.reserved", &[("warehouse", "eu-1".into())]);
+
+            let (attributes, events) = span
+                .with_subscriber(|(id, dispatch)| {
+                    let registry = dispatch.downcast_ref::<Registry>().unwrap();
+                    let span = registry.span(id).unwrap();
+                    let extensions = span.extensions();
+                    let builder = &extensions.get::<OtelData>().unwrap().builder;
+                    (
+                        builder.attributes.clone().unwrap_or_default(),
+                        builder.events.clone().unwrap_or_default(),
+                    )
+                })
+                .unwrap();
+
+            let attribute = |key: &str| {
+                attributes
+                    .iter()
+                    .filter(|kv| kv.key == Key::from(key.to_string()))
+                    .map(|kv| kv.value.clone())
+                    .collect::<Vec<_>>()
+            };
+            // Declared fields are recorded through tracing, exactly once
+            assert_eq!(attribute("order_id"), vec![Value::from("o-1")]);
+            assert_eq!(attribute("items"), vec![Value::I64(3)]);
+
+            assert_eq!(events.len(), 1);
+            assert_eq!(events[0].name, "stock.reserved");
+            assert_eq!(events[0].attributes[0].value, Value::from("eu-1"));
+        });
+    }
+}
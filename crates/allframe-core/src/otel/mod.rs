@@ -24,8 +24,13 @@
 //! // Guard keeps the subscriber active
 //! // When dropped, flushes pending spans
 //! ```
+//!
+//! Use [`add_attribute`] and [`record_event`] to annotate the current span,
+//! e.g. the one created by `#[traced]`.
 
 mod builder;
+mod correlation;
+mod enrich;
 mod testing;
 
 // Re-export the traced macro
@@ -37,21 +42,15 @@ use std::collections::HashMap;
 pub use allframe_macros::traced;
 // Re-export builder types
 pub use builder::{Observability, ObservabilityBuilder, ObservabilityError, ObservabilityGuard};
+// Re-export log/trace correlation
+#[cfg(feature = "otel-otlp")]
+pub use correlation::TraceCorrelation;
+pub use correlation::{current_span_id, current_trace_id};
+// Re-export span enrichment
+pub use enrich::{add_attribute, record_event, AttributeValue};
 // Re-export testing utilities
 pub use testing::{Histogram, MetricsRecorder, Span, SpanContext, SpanRecorder};
 
-/// Get the current span ID (placeholder - use tracing for real spans)
-#[deprecated(since = "0.2.0", note = "Use tracing::Span::current() instead")]
-pub fn current_span_id() -> String {
-    "span-placeholder".to_string()
-}
-
-/// Get the current trace ID (placeholder - use tracing for real traces)
-#[deprecated(since = "0.2.0", note = "Use tracing::Span::current() instead")]
-pub fn current_trace_id() -> String {
-    "trace-placeholder".to_string()
-}
-
 /// Start a new trace (placeholder)
 #[deprecated(since = "0.2.0", note = "Use tracing spans instead")]
 pub fn start_trace(_trace_id: &str) {
@@ -63,12 +63,48 @@ impl SpanRecorder {
     }
 }
 
+/// Name and sorted labels identifying a metric series
+type SeriesKey = (String, Vec<(String, String)>);
+
+fn series_key(name: &str, labels: &[(&str, &str)]) -> SeriesKey {
+    let mut labels: Vec<(String, String)> = labels
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    labels.sort();
+    (name.to_string(), labels)
+}
+
+/// Whether a series has the given name and all of the given labels
+fn matches(key: &SeriesKey, name: &str, labels: &[(&str, &str)]) -> bool {
+    key.0 == name
+        && labels
+            .iter()
+            .all(|(k, v)| key.1.iter().any(|(sk, sv)| sk == k && sv == v))
+}
+
 /// MetricsRecorder for testing - records all metrics
+///
+/// Counters and histograms are recorded per series, i.e. per combination of
+/// name and labels. Queries select every series with the given name that
+/// has all of the given labels, so `counter_value("requests_total", &[])`
+/// is the total across all labels.
+///
+/// ```rust
+/// use allframe_core::otel::MetricsRecorder;
+///
+/// let metrics = MetricsRecorder::new();
+/// metrics.increment_counter_with_labels("requests_total", &[("method", "GET")], 1);
+/// metrics.increment_counter_with_labels("requests_total", &[("method", "POST")], 1);
+///
+/// assert_eq!(metrics.counter_value("requests_total", &[("method", "GET")]), 1);
+/// assert_eq!(metrics.counter_value("requests_total", &[]), 2);
+/// ```
 #[derive(Clone, Default)]
 pub struct MetricsRecorder {
-    counters: Arc<RwLock<HashMap<String, u64>>>,
+    counters: Arc<RwLock<HashMap<SeriesKey, u64>>>,
     gauges: Arc<RwLock<HashMap<String, i64>>>,
-    histograms: Arc<RwLock<HashMap<String, Vec<f64>>>>,
+    histograms: Arc<RwLock<HashMap<SeriesKey, Vec<f64>>>>,
 }
 
 impl MetricsRecorder {
@@ -88,8 +124,13 @@ impl MetricsRecorder {
 
     /// Increment a counter
     pub fn increment_counter(&self, name: &str, value: u64) {
+        self.increment_counter_with_labels(name, &[], value);
+    }
+
+    /// Increment the counter series with the given labels
+    pub fn increment_counter_with_labels(&self, name: &str, labels: &[(&str, &str)], value: u64) {
         let mut counters = self.counters.write().unwrap();
-        *counters.entry(name.to_string()).or_insert(0) += value;
+        *counters.entry(series_key(name, labels)).or_insert(0) += value;
     }
 
     /// Set a gauge value
@@ -100,18 +141,21 @@ impl MetricsRecorder {
 
     /// Record a histogram value
     pub fn record_histogram(&self, name: &str, value: f64) {
+        self.record_histogram_with_labels(name, &[], value);
+    }
+
+    /// Record a value in the histogram series with the given labels
+    pub fn record_histogram_with_labels(&self, name: &str, labels: &[(&str, &str)], value: f64) {
         let mut histograms = self.histograms.write().unwrap();
-        histograms.entry(name.to_string()).or_default().push(value);
+        histograms
+            .entry(series_key(name, labels))
+            .or_default()
+            .push(value);
     }
 
-    /// Get counter value
+    /// Get counter value, summed across all labels
     pub fn get_counter(&self, name: &str) -> u64 {
-        self.counters
-            .read()
-            .unwrap()
-            .get(name)
-            .copied()
-            .unwrap_or(0)
+        self.counter_value(name, &[])
     }
 
     /// Get gauge value
@@ -119,22 +163,39 @@ impl MetricsRecorder {
         self.gauges.read().unwrap().get(name).copied().unwrap_or(0)
     }
 
-    /// Get histogram
+    /// Get histogram, merged across all labels
     pub fn get_histogram(&self, name: &str) -> Histogram {
+        self.histogram(name, &[])
+    }
+
+    /// Get counter with labels
+    pub fn get_counter_with_labels(&self, name: &str, labels: &[(&str, &str)]) -> u64 {
+        self.counter_value(name, labels)
+    }
+
+    /// Sum of the counter series that have all of the given labels
+    pub fn counter_value(&self, name: &str, labels: &[(&str, &str)]) -> u64 {
+        self.counters
+            .read()
+            .unwrap()
+            .iter()
+            .filter(|(key, _)| matches(key, name, labels))
+            .map(|(_, value)| value)
+            .sum()
+    }
+
+    /// Values of the histogram series that have all of the given labels
+    pub fn histogram(&self, name: &str, labels: &[(&str, &str)]) -> Histogram {
         let values = self
             .histograms
             .read()
             .unwrap()
-            .get(name)
-            .cloned()
-            .unwrap_or_default();
+            .iter()
+            .filter(|(key, _)| matches(key, name, labels))
+            .flat_map(|(_, values)| values.iter().copied())
+            .collect();
         Histogram::new(values)
     }
-
-    /// Get counter with labels (placeholder)
-    pub fn get_counter_with_labels(&self, name: &str, _labels: &[(&str, &str)]) -> u64 {
-        self.get_counter(name)
-    }
 }
 
 /// SpanContext for distributed tracing
@@ -258,4 +319,26 @@ mod tests {
         let hist = recorder.get_histogram("latency");
         assert_eq!(hist.count(), 2);
     }
+
+    #[test]
+    fn test_metrics_recorder_labels() {
+        let recorder = MetricsRecorder::new();
+
+        // Label order doesn't matter
+        recorder.increment_counter_with_labels("hits", &[("a", "1"), ("b", "2")], 1);
+        recorder.increment_counter_with_labels("hits", &[("b", "2"), ("a", "1")], 1);
+        recorder.increment_counter_with_labels("hits", &[("a", "1"), ("b", "3")], 5);
+        assert_eq!(recorder.counter_value("hits", &[("a", "1"), ("b", "2")]), 2);
+        assert_eq!(recorder.counter_value("hits", &[("a", "1")]), 7);
+        assert_eq!(recorder.counter_value("hits", &[("a", "2")]), 0);
+        assert_eq!(recorder.counter_value("misses", &[]), 0);
+
+        recorder.record_histogram_with_labels("latency", &[("route", "/a")], 10.0);
+        recorder.record_histogram_with_labels("latency", &[("route", "/b")], 30.0);
+        assert_eq!(
+            recorder.histogram("latency", &[("route", "/a")]).sum(),
+            10.0
+        );
+        assert_eq!(recorder.histogram("latency", &[]).count(), 2);
+    }
 }
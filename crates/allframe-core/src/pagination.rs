@@ -0,0 +1,288 @@
+//! Cursor-based pagination
+//!
+//! List endpoints return a [`Page`] of items with an opaque [`Cursor`] for
+//! the next page. Clients pass the cursor back unchanged to continue; it is
+//! the URL-safe base64 encoding of the position the next page starts
+//! after, so its contents are not part of the API.
+//!
+//! Two strategies are provided:
+//!
+//! - [`paginate`] (offset): the cursor holds the index of the next item.
+//!   Simple, but items inserted or removed before the cursor shift the
+//!   following pages.
+//! - [`paginate_by_key`] (keyset): the cursor holds the key of the last item
+//!   returned, and the next page starts after it. Pages stay consistent
+//!   while items are added or removed, provided keys are unique.
+//!
+//! ```rust
+//! use allframe_core::pagination::{paginate_by_key, Page};
+//!
+//! let ids = vec![3, 1, 2, 5, 4];
+//! let first: Page<i32> = paginate_by_key(ids.clone(), 2, None, |id| *id).unwrap();
+//! assert_eq!(first.items, vec![1, 2]);
+//!
+//! let second = paginate_by_key(ids, 2, first.next_cursor.as_ref(), |id| *id).unwrap();
+//! assert_eq!(second.items, vec![3, 4]);
+//! ```
+
+use std::{fmt, str::FromStr};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::router::{HttpError, ToJsonSchema};
+
+/// Errors from decoding a pagination cursor
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PaginationError {
+    /// The cursor wasn't issued by this endpoint or was tampered with
+    InvalidCursor(String),
+}
+
+impl fmt::Display for PaginationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PaginationError::InvalidCursor(cursor) => write!(f, "Invalid cursor: {}", cursor),
+        }
+    }
+}
+
+impl std::error::Error for PaginationError {}
+
+impl HttpError for PaginationError {
+    fn status_code(&self) -> u16 {
+        400
+    }
+}
+
+/// Opaque position in a paginated list
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Cursor(String);
+
+impl Cursor {
+    /// Create a cursor pointing after `key`
+    pub fn encode<K: Serialize>(key: &K) -> Self {
+        let json = serde_json::to_vec(key).expect("cursor key serializes to JSON");
+        Self(URL_SAFE_NO_PAD.encode(json))
+    }
+
+    /// Recover the key a cursor points after
+    pub fn decode<K: DeserializeOwned>(&self) -> Result<K, PaginationError> {
+        URL_SAFE_NO_PAD
+            .decode(&self.0)
+            .ok()
+            .and_then(|json| serde_json::from_slice(&json).ok())
+            .ok_or_else(|| PaginationError::InvalidCursor(self.0.clone()))
+    }
+
+    /// The encoded cursor, as sent to clients
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Cursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for Cursor {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.to_string()))
+    }
+}
+
+impl From<String> for Cursor {
+    fn from(s: String) -> Self {
+        Self(s)
+    }
+}
+
+/// One page of a list, with the cursor for the next page
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Page<T> {
+    /// Items on this page
+    pub items: Vec<T>,
+    /// Cursor for the next page, or `None` on the last page
+    pub next_cursor: Option<Cursor>,
+}
+
+impl<T> Page<T> {
+    /// Check if there are more pages after this one
+    pub fn has_more(&self) -> bool {
+        self.next_cursor.is_some()
+    }
+
+    /// Convert the items, keeping the cursor
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            next_cursor: self.next_cursor,
+        }
+    }
+}
+
+impl<T: ToJsonSchema> ToJsonSchema for Page<T> {
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "items": Vec::<T>::schema(),
+                "next_cursor": {
+                    "type": ["string", "null"],
+                    "description": "Opaque cursor for the next page; absent on the last page"
+                }
+            },
+            "required": ["items"]
+        })
+    }
+}
+
+/// Paginate ordered items by offset
+///
+/// The cursor encodes the index of the first item of the next page. A
+/// `limit` of 0 is treated as 1.
+pub fn paginate<T>(
+    items: impl IntoIterator<Item = T>,
+    limit: usize,
+    cursor: Option<&Cursor>,
+) -> Result<Page<T>, PaginationError> {
+    let limit = limit.max(1);
+    let offset: usize = cursor.map(Cursor::decode).transpose()?.unwrap_or(0);
+
+    let mut rest = items.into_iter().skip(offset);
+    let items: Vec<T> = rest.by_ref().take(limit).collect();
+    let next_cursor = rest.next().map(|_| Cursor::encode(&(offset + limit)));
+    Ok(Page { items, next_cursor })
+}
+
+/// Paginate items by a unique key
+///
+/// Items are sorted by `key`, and the page starts after the key encoded in
+/// the cursor. Keys must be unique, e.g. an id or a `(created_at, id)`
+/// tuple, for pages not to skip or repeat items. A `limit` of 0 is treated
+/// as 1.
+pub fn paginate_by_key<T, K, F>(
+    items: impl IntoIterator<Item = T>,
+    limit: usize,
+    cursor: Option<&Cursor>,
+    key: F,
+) -> Result<Page<T>, PaginationError>
+where
+    K: Ord + Serialize + DeserializeOwned,
+    F: Fn(&T) -> K,
+{
+    let limit = limit.max(1);
+    let after: Option<K> = cursor.map(Cursor::decode).transpose()?;
+
+    let mut items: Vec<T> = items
+        .into_iter()
+        .filter(|item| after.as_ref().map_or(true, |after| key(item) > *after))
+        .collect();
+    items.sort_by_key(|item| key(item));
+
+    let next_cursor = if items.len() > limit {
+        items.truncate(limit);
+        items.last().map(|last| Cursor::encode(&key(last)))
+    } else {
+        None
+    };
+    Ok(Page { items, next_cursor })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct Order {
+        id: u32,
+        placed_at: u64,
+    }
+
+    fn orders() -> Vec<Order> {
+        // Several orders share a timestamp, so the key needs the id too
+        [(4, 100), (1, 100), (6, 300), (3, 200), (2, 100), (5, 200)]
+            .into_iter()
+            .map(|(id, placed_at)| Order { id, placed_at })
+            .collect()
+    }
+
+    fn all_pages<F>(mut next: F) -> Vec<Vec<u32>>
+    where
+        F: FnMut(Option<&Cursor>) -> Page<Order>,
+    {
+        let mut pages = Vec::new();
+        let mut cursor = None;
+        loop {
+            let page = next(cursor.as_ref());
+            pages.push(page.items.iter().map(|o| o.id).collect());
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => return pages,
+            }
+        }
+    }
+
+    #[test]
+    fn test_offset_pagination() {
+        let pages = all_pages(|cursor| paginate(orders(), 4, cursor).unwrap());
+        assert_eq!(pages, vec![vec![4, 1, 6, 3], vec![2, 5]]);
+
+        // An exact multiple of the limit has no empty trailing page
+        let pages = all_pages(|cursor| paginate(orders(), 3, cursor).unwrap());
+        assert_eq!(pages, vec![vec![4, 1, 6], vec![3, 2, 5]]);
+    }
+
+    #[test]
+    fn test_keyset_pagination_is_stable() {
+        let key = |o: &Order| (o.placed_at, o.id);
+        let pages = all_pages(|cursor| paginate_by_key(orders(), 2, cursor, key).unwrap());
+        assert_eq!(pages, vec![vec![1, 2], vec![4, 3], vec![5, 6]]);
+
+        // Items inserted before the cursor don't shift later pages
+        let first = paginate_by_key(orders(), 2, None, key).unwrap();
+        let mut changed = orders();
+        changed.push(Order {
+            id: 0,
+            placed_at: 50,
+        });
+        changed.retain(|o| o.id != 1);
+        let second = paginate_by_key(changed, 2, first.next_cursor.as_ref(), key).unwrap();
+        assert_eq!(
+            second.items.iter().map(|o| o.id).collect::<Vec<_>>(),
+            [4, 3]
+        );
+    }
+
+    #[test]
+    fn test_invalid_cursor() {
+        let cursor: Cursor = "not a cursor!".parse().unwrap();
+        let error = paginate(orders(), 2, Some(&cursor)).unwrap_err();
+        assert_eq!(error.status_code(), 400);
+
+        // A keyset cursor doesn't decode as an offset
+        let cursor = Cursor::encode(&(100u64, 1u32));
+        assert!(paginate(orders(), 2, Some(&cursor)).is_err());
+    }
+
+    #[test]
+    fn test_page_serialization_and_schema() {
+        let page = paginate(vec!["a", "b", "c"], 2, None).unwrap();
+        let json = serde_json::to_value(&page).unwrap();
+        assert_eq!(json["items"], json!(["a", "b"]));
+        assert_eq!(
+            json["next_cursor"],
+            json!(page.next_cursor.unwrap().as_str())
+        );
+
+        let schema = Page::<String>::schema();
+        assert_eq!(schema["properties"]["items"]["items"]["type"], "string");
+        assert_eq!(schema["required"], json!(["items"]));
+    }
+}
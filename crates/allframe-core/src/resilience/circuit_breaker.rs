@@ -67,7 +67,10 @@ pub struct CircuitBreakerConfig {
     pub timeout: Duration,
     /// Window for counting failures (failures outside this window don't count).
     pub failure_window: Duration,
-    /// Maximum concurrent requests in half-open state.
+    /// Maximum concurrent trial requests in half-open state.
+    ///
+    /// Requests beyond this are rejected as if the circuit were open until
+    /// a trial resolves, so a recovering service isn't flooded.
     pub half_open_requests: u32,
 }
 
@@ -110,11 +113,19 @@ impl CircuitBreakerConfig {
         self
     }
 
-    /// Set the number of concurrent requests allowed in half-open state.
-    pub fn with_half_open_requests(mut self, requests: u32) -> Self {
-        self.half_open_requests = requests.max(1);
+    /// Set the number of concurrent trial requests allowed in half-open
+    /// state.
+    pub fn with_half_open_max_calls(mut self, max_calls: u32) -> Self {
+        self.half_open_requests = max_calls.max(1);
         self
     }
+
+    /// Set the number of concurrent requests allowed in half-open state.
+    ///
+    /// Same as [`with_half_open_max_calls`](Self::with_half_open_max_calls).
+    pub fn with_half_open_requests(self, requests: u32) -> Self {
+        self.with_half_open_max_calls(requests)
+    }
 }
 
 /// Statistics for a circuit breaker.
@@ -132,6 +143,8 @@ pub struct CircuitBreakerStats {
     pub failures_in_window: u32,
     /// Successes in half-open state.
     pub half_open_successes: u32,
+    /// Trial requests in flight in half-open state.
+    pub half_open_in_flight: u32,
     /// Time since last state change.
     pub time_in_state: Duration,
 }
@@ -182,12 +195,18 @@ impl CircuitBreaker {
     /// Returns `Ok(())` if the request can proceed, `Err(CircuitOpenError)` if
     /// the circuit is open.
     pub fn check(&self) -> Result<(), CircuitOpenError> {
+        self.acquire().map(|_| ())
+    }
+
+    /// Like [`check`](Self::check), returning whether the request took a
+    /// half-open trial slot.
+    fn acquire(&self) -> Result<bool, CircuitOpenError> {
         self.maybe_transition_to_half_open();
 
         let state = *self.state.read();
 
         match state {
-            CircuitState::Closed => Ok(()),
+            CircuitState::Closed => Ok(false),
             CircuitState::Open => {
                 self.rejected_count.fetch_add(1, Ordering::Relaxed);
                 let elapsed = self.state_changed_at.read().elapsed();
@@ -199,10 +218,15 @@ impl CircuitBreaker {
             }
             CircuitState::HalfOpen => {
                 // Allow limited requests in half-open state
-                let in_flight = self.half_open_in_flight.load(Ordering::Acquire);
-                if in_flight < self.config.half_open_requests {
-                    self.half_open_in_flight.fetch_add(1, Ordering::AcqRel);
-                    Ok(())
+                let acquired = self.half_open_in_flight.fetch_update(
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                    |in_flight| {
+                        (in_flight < self.config.half_open_requests).then_some(in_flight + 1)
+                    },
+                );
+                if acquired.is_ok() {
+                    Ok(true)
                 } else {
                     self.rejected_count.fetch_add(1, Ordering::Relaxed);
                     Err(CircuitOpenError {
@@ -221,7 +245,7 @@ impl CircuitBreaker {
         let state = *self.state.read();
 
         if state == CircuitState::HalfOpen {
-            self.half_open_in_flight.fetch_sub(1, Ordering::AcqRel);
+            self.release_trial();
             let successes = self.half_open_successes.fetch_add(1, Ordering::AcqRel) + 1;
 
             if successes >= self.config.success_threshold {
@@ -255,7 +279,7 @@ impl CircuitBreaker {
                 }
             }
             CircuitState::HalfOpen => {
-                self.half_open_in_flight.fetch_sub(1, Ordering::AcqRel);
+                self.release_trial();
                 // Single failure in half-open reopens the circuit
                 self.transition_to(CircuitState::Open);
             }
@@ -271,9 +295,17 @@ impl CircuitBreaker {
         F: FnOnce() -> Fut,
         Fut: Future<Output = Result<T, E>>,
     {
-        self.check().map_err(CircuitBreakerError::CircuitOpen)?;
+        let trial = self.acquire().map_err(CircuitBreakerError::CircuitOpen)?;
+
+        // Free the trial slot if the future is dropped before resolving
+        let mut guard = TrialGuard {
+            breaker: self,
+            armed: trial,
+        };
+        let result = f().await;
+        guard.armed = false;
 
-        match f().await {
+        match result {
             Ok(result) => {
                 self.record_success();
                 Ok(result)
@@ -285,6 +317,15 @@ impl CircuitBreaker {
         }
     }
 
+    /// Free a half-open trial slot.
+    fn release_trial(&self) {
+        let _ = self.half_open_in_flight.fetch_update(
+            Ordering::AcqRel,
+            Ordering::Acquire,
+            |in_flight| in_flight.checked_sub(1),
+        );
+    }
+
     /// Get the current state.
     pub fn get_state(&self) -> CircuitState {
         self.maybe_transition_to_half_open();
@@ -308,6 +349,7 @@ impl CircuitBreaker {
             rejected_count: self.rejected_count.load(Ordering::Relaxed),
             failures_in_window,
             half_open_successes: self.half_open_successes.load(Ordering::Relaxed),
+            half_open_in_flight: self.half_open_in_flight.load(Ordering::Relaxed),
             time_in_state: self.state_changed_at.read().elapsed(),
         }
     }
@@ -358,6 +400,20 @@ impl CircuitBreaker {
     }
 }
 
+/// Releases a half-open trial slot when a call is cancelled.
+struct TrialGuard<'a> {
+    breaker: &'a CircuitBreaker,
+    armed: bool,
+}
+
+impl Drop for TrialGuard<'_> {
+    fn drop(&mut self) {
+        if self.armed {
+            self.breaker.release_trial();
+        }
+    }
+}
+
 /// Error type for circuit breaker operations.
 #[derive(Debug)]
 pub enum CircuitBreakerError<E> {
@@ -632,7 +688,7 @@ mod tests {
         let config = CircuitBreakerConfig::new(10)
             .with_success_threshold(5)
             .with_timeout(Duration::from_secs(60))
-            .with_half_open_requests(3);
+            .with_half_open_max_calls(3);
 
         assert_eq!(config.failure_threshold, 10);
         assert_eq!(config.success_threshold, 5);
@@ -725,6 +781,46 @@ mod tests {
         assert_eq!(cb.get_state(), CircuitState::Open);
     }
 
+    #[test]
+    fn test_circuit_breaker_limits_half_open_trials() {
+        let config = CircuitBreakerConfig::new(1)
+            .with_timeout(Duration::from_millis(10))
+            .with_success_threshold(3)
+            .with_half_open_max_calls(2);
+        let cb = CircuitBreaker::new("test", config);
+
+        cb.record_failure();
+        std::thread::sleep(Duration::from_millis(20));
+
+        cb.check().unwrap();
+        cb.check().unwrap();
+        assert!(cb.check().is_err());
+        let stats = cb.get_stats();
+        assert_eq!(stats.half_open_in_flight, 2);
+        assert_eq!(stats.rejected_count, 1);
+
+        // A resolved trial frees its slot
+        cb.record_success();
+        assert_eq!(cb.get_stats().half_open_in_flight, 1);
+        cb.check().unwrap();
+        assert!(cb.check().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_circuit_breaker_cancelled_trial_frees_slot() {
+        let config = CircuitBreakerConfig::new(1).with_timeout(Duration::from_millis(10));
+        let cb = CircuitBreaker::new("test", config);
+
+        cb.record_failure();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let call = cb.call(std::future::pending::<Result<(), std::io::Error>>);
+        let _ = tokio::time::timeout(Duration::from_millis(10), call).await;
+
+        assert_eq!(cb.get_stats().half_open_in_flight, 0);
+        assert!(cb.check().is_ok());
+    }
+
     #[test]
     fn test_circuit_breaker_reset() {
         let config = CircuitBreakerConfig::new(1);
@@ -6,6 +6,7 @@
 //! - **Rate Limiting**: Token bucket rate limiting with adaptive and keyed
 //!   variants
 //! - **Circuit Breaker**: Fail-fast pattern with configurable thresholds
+//! - **Timeout**: Bound the duration of a single async call
 //!
 //! # Example
 //!
@@ -43,6 +44,7 @@ mod rate_limit;
 mod rate_limit_redis;
 pub mod offline;
 mod retry;
+mod timeout;
 
 pub use circuit_breaker::{
     CircuitBreaker, CircuitBreakerConfig, CircuitBreakerError, CircuitBreakerManager,
@@ -53,10 +55,12 @@ pub use rate_limit::{
 };
 #[cfg(feature = "resilience-redis")]
 pub use rate_limit_redis::{
-    KeyedRedisRateLimiter, RedisRateLimiter, RedisRateLimiterConfig, RedisRateLimiterError,
+    DistributedRateLimiter, KeyedRedisRateLimiter, RedisFailureMode, RedisRateLimiter,
+    RedisRateLimiterConfig, RedisRateLimiterError,
 };
 pub use offline::{
     AlwaysOnlineProbe, CallResult, ConnectivityProbe, ConnectivityStatus, InMemoryQueue,
     OfflineCircuitBreaker, PendingOperation, ReplayReport, StoreAndForward,
 };
 pub use retry::{AdaptiveRetry, RetryBudget, RetryConfig, RetryError, RetryExecutor, RetryPolicy};
+pub use timeout::{with_timeout, TimeoutError};
@@ -1,7 +1,9 @@
 //! Redis-backed rate limiting for distributed deployments.
 //!
 //! Provides sliding window rate limiting using Redis as a backend,
-//! allowing rate limits to be shared across multiple instances.
+//! allowing rate limits to be shared across multiple instances, and a
+//! token bucket limiter ([`DistributedRateLimiter`]) that falls back to
+//! local limiting while Redis is unavailable.
 //!
 //! # Example
 //!
@@ -17,11 +19,19 @@
 //! }
 //! ```
 
-use std::time::Duration;
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
 
-use redis::{aio::ConnectionManager, AsyncCommands, Client};
+use parking_lot::Mutex;
+use redis::{
+    aio::{ConnectionManager, ConnectionManagerConfig},
+    AsyncCommands, Client,
+};
+use tokio::sync::OnceCell;
 
-use super::RateLimitError;
+use super::{KeyedRateLimiter, RateLimitError};
 
 /// Configuration for Redis rate limiter.
 #[derive(Debug, Clone)]
@@ -323,6 +333,211 @@ impl KeyedRedisRateLimiter {
     }
 }
 
+/// What a [`DistributedRateLimiter`] does when Redis is unavailable.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RedisFailureMode {
+    /// Limit each instance locally with the same rate (default).
+    ///
+    /// Keeps serving traffic, at up to N times the limit across N
+    /// instances.
+    #[default]
+    FailOpen,
+    /// Reject every request.
+    FailClosed,
+}
+
+/// How long to wait for a Redis connection.
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// How long to wait before trying to connect again after a failure.
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Token bucket refill and take, atomically, using the Redis clock so
+/// instances with skewed clocks agree. Returns 0 if a token was taken,
+/// otherwise the milliseconds until one is available.
+const TOKEN_BUCKET_SCRIPT: &str = r#"
+local key = KEYS[1]
+local rate = tonumber(ARGV[1])
+local capacity = tonumber(ARGV[2])
+
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+
+local bucket = redis.call('HMGET', key, 'tokens', 'ts')
+local tokens = tonumber(bucket[1]) or capacity
+local ts = tonumber(bucket[2]) or now
+tokens = math.min(capacity, tokens + (now - ts) * rate / 1000)
+
+local retry_ms = 0
+if tokens >= 1 then
+    tokens = tokens - 1
+else
+    retry_ms = math.ceil((1 - tokens) * 1000 / rate)
+end
+
+redis.call('HSET', key, 'tokens', tostring(tokens), 'ts', now)
+redis.call('PEXPIRE', key, math.ceil(capacity * 1000 / rate) + 1000)
+return retry_ms
+"#;
+
+/// Redis-backed token bucket rate limiter, keyed by client.
+///
+/// The distributed counterpart of [`KeyedRateLimiter`]: every instance
+/// behind a load balancer draws from the same per-key bucket, refilled at
+/// `rps` tokens per second up to `burst_size`, so the limit holds across
+/// the whole deployment.
+///
+/// The limiter connects lazily and keeps working while Redis is down, as
+/// set by [`RedisFailureMode`]. Unlike [`RedisRateLimiter`], it can be
+/// created before Redis is reachable.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use allframe_core::resilience::{DistributedRateLimiter, RedisFailureMode};
+///
+/// let limiter = DistributedRateLimiter::new("redis://localhost:6379", 100, 20)?
+///     .with_failure_mode(RedisFailureMode::FailClosed);
+///
+/// if limiter.check("client:42").await.is_ok() {
+///     // Process request
+/// }
+/// ```
+pub struct DistributedRateLimiter {
+    client: Client,
+    conn: OnceCell<ConnectionManager>,
+    /// Earliest time to try connecting again after a failed attempt
+    retry_connect_at: Mutex<Option<Instant>>,
+    /// [`TOKEN_BUCKET_SCRIPT`], hashed once
+    script: redis::Script,
+    rps: u32,
+    burst_size: u32,
+    key_prefix: String,
+    failure_mode: RedisFailureMode,
+    local: KeyedRateLimiter<String>,
+    fallbacks: AtomicU64,
+}
+
+impl DistributedRateLimiter {
+    /// Create a distributed rate limiter.
+    ///
+    /// Only the URL is validated here; the connection is made on first use.
+    ///
+    /// # Arguments
+    /// * `redis_url` - Redis connection URL (e.g., "redis://localhost:6379")
+    /// * `rps` - Sustained requests per second per key
+    /// * `burst_size` - Bucket capacity per key
+    pub fn new(redis_url: &str, rps: u32, burst_size: u32) -> Result<Self, RedisRateLimiterError> {
+        let client = Client::open(redis_url)
+            .map_err(|e| RedisRateLimiterError::Connection(e.to_string()))?;
+
+        Ok(Self {
+            client,
+            conn: OnceCell::new(),
+            retry_connect_at: Mutex::new(None),
+            script: redis::Script::new(TOKEN_BUCKET_SCRIPT),
+            rps: rps.max(1),
+            burst_size: burst_size.max(1),
+            key_prefix: "ratelimit:bucket".to_string(),
+            failure_mode: RedisFailureMode::default(),
+            local: KeyedRateLimiter::new(rps, burst_size),
+            fallbacks: AtomicU64::new(0),
+        })
+    }
+
+    /// Set a custom key prefix.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.key_prefix = prefix.into();
+        self
+    }
+
+    /// Set what happens when Redis is unavailable.
+    pub fn with_failure_mode(mut self, mode: RedisFailureMode) -> Self {
+        self.failure_mode = mode;
+        self
+    }
+
+    /// Check if a request for the given key is allowed.
+    pub async fn check(&self, key: &str) -> Result<(), RateLimitError> {
+        match self.take_token(key).await {
+            Some(0) => Ok(()),
+            Some(retry_ms) => Err(RateLimitError {
+                retry_after: Duration::from_millis(retry_ms),
+            }),
+            None => {
+                self.fallbacks.fetch_add(1, Ordering::Relaxed);
+                match self.failure_mode {
+                    RedisFailureMode::FailOpen => self.local.check(&key.to_string()),
+                    RedisFailureMode::FailClosed => Err(RateLimitError {
+                        retry_after: Duration::from_secs(1),
+                    }),
+                }
+            }
+        }
+    }
+
+    /// Reset the bucket for a key.
+    pub async fn reset(&self, key: &str) -> Result<(), RedisRateLimiterError> {
+        let mut conn = self
+            .connection()
+            .await
+            .ok_or_else(|| RedisRateLimiterError::Connection("Redis unavailable".to_string()))?;
+        let _: () = conn.del(self.bucket_key(key)).await?;
+        self.local.remove_limit(&key.to_string());
+        Ok(())
+    }
+
+    /// Number of checks decided without Redis.
+    pub fn fallback_count(&self) -> u64 {
+        self.fallbacks.load(Ordering::Relaxed)
+    }
+
+    fn bucket_key(&self, key: &str) -> String {
+        format!("{}:{}", self.key_prefix, key)
+    }
+
+    /// Take a token from the key's bucket, or `None` if Redis failed
+    async fn take_token(&self, key: &str) -> Option<u64> {
+        let mut conn = self.connection().await?;
+        self.script
+            .key(self.bucket_key(key))
+            .arg(self.rps)
+            .arg(self.burst_size)
+            .invoke_async(&mut conn)
+            .await
+            .ok()
+    }
+
+    /// The Redis connection, connecting at most every [`RECONNECT_INTERVAL`]
+    async fn connection(&self) -> Option<ConnectionManager> {
+        if let Some(conn) = self.conn.get() {
+            return Some(conn.clone());
+        }
+        if self
+            .retry_connect_at
+            .lock()
+            .is_some_and(|at| Instant::now() < at)
+        {
+            return None;
+        }
+
+        let config = ConnectionManagerConfig::new()
+            .set_number_of_retries(0)
+            .set_connection_timeout(CONNECT_TIMEOUT);
+        let result = self
+            .conn
+            .get_or_try_init(|| ConnectionManager::new_with_config(self.client.clone(), config))
+            .await;
+        match result {
+            Ok(conn) => Some(conn.clone()),
+            Err(_) => {
+                *self.retry_connect_at.lock() = Some(Instant::now() + RECONNECT_INTERVAL);
+                None
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -421,4 +636,46 @@ mod tests {
         // Should work again
         assert!(limiter.check("test:reset").await.is_ok());
     }
+
+    #[tokio::test]
+    async fn test_distributed_limiter_falls_back_to_local_limiting() {
+        // Nothing listens on port 1, so every check falls back
+        let limiter = DistributedRateLimiter::new("redis://127.0.0.1:1", 1, 2).unwrap();
+
+        assert!(limiter.check("client:1").await.is_ok());
+        assert!(limiter.check("client:1").await.is_ok());
+        assert!(limiter.check("client:1").await.is_err());
+        assert!(limiter.check("client:2").await.is_ok());
+        assert_eq!(limiter.fallback_count(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_distributed_limiter_fail_closed() {
+        let limiter = DistributedRateLimiter::new("redis://127.0.0.1:1", 100, 100)
+            .unwrap()
+            .with_failure_mode(RedisFailureMode::FailClosed);
+
+        assert!(limiter.check("client:1").await.is_err());
+        assert!(limiter.reset("client:1").await.is_err());
+    }
+
+    #[tokio::test]
+    #[ignore = "requires Redis"]
+    async fn test_distributed_limiter_shares_bucket_across_instances() {
+        let a = DistributedRateLimiter::new("redis://localhost:6379", 1, 3)
+            .unwrap()
+            .with_failure_mode(RedisFailureMode::FailClosed);
+        let b = DistributedRateLimiter::new("redis://localhost:6379", 1, 3)
+            .unwrap()
+            .with_failure_mode(RedisFailureMode::FailClosed);
+        a.reset("test:bucket").await.unwrap();
+
+        assert!(a.check("test:bucket").await.is_ok());
+        assert!(b.check("test:bucket").await.is_ok());
+        assert!(a.check("test:bucket").await.is_ok());
+
+        let err = b.check("test:bucket").await.unwrap_err();
+        assert!(err.retry_after > Duration::ZERO);
+        assert_eq!(a.fallback_count() + b.fallback_count(), 0);
+    }
 }
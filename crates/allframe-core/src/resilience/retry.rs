@@ -5,7 +5,10 @@
 
 use std::{
     future::Future,
-    sync::atomic::{AtomicU32, Ordering},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
     time::{Duration, Instant},
 };
 
@@ -155,6 +158,7 @@ impl RetryPolicy for NeverRetry {
 pub struct RetryExecutor<P: RetryPolicy = AlwaysRetry> {
     config: RetryConfig,
     policy: P,
+    budget: Option<Arc<RetryBudget>>,
 }
 
 impl RetryExecutor<AlwaysRetry> {
@@ -163,6 +167,7 @@ impl RetryExecutor<AlwaysRetry> {
         Self {
             config,
             policy: AlwaysRetry,
+            budget: None,
         }
     }
 }
@@ -170,7 +175,21 @@ impl RetryExecutor<AlwaysRetry> {
 impl<P: RetryPolicy> RetryExecutor<P> {
     /// Create a retry executor with a custom policy.
     pub fn with_policy(config: RetryConfig, policy: P) -> Self {
-        Self { config, policy }
+        Self {
+            config,
+            policy,
+            budget: None,
+        }
+    }
+
+    /// Gate retries on a retry budget, usually shared by all executors.
+    ///
+    /// Every execution is recorded with the budget and every retry takes
+    /// a token from it. When the budget is exhausted, the error is returned
+    /// without retrying.
+    pub fn with_budget(mut self, budget: Arc<RetryBudget>) -> Self {
+        self.budget = Some(budget);
+        self
     }
 
     /// Execute an async operation with retries.
@@ -180,6 +199,7 @@ impl<P: RetryPolicy> RetryExecutor<P> {
     /// - Max retries is reached
     /// - Max elapsed time is reached
     /// - The retry policy says not to retry
+    /// - The retry budget, if any, is exhausted
     pub async fn execute<F, Fut, T, E>(&self, name: &str, mut f: F) -> Result<T, RetryError<E>>
     where
         F: FnMut() -> Fut,
@@ -189,6 +209,10 @@ impl<P: RetryPolicy> RetryExecutor<P> {
         let start = Instant::now();
         let mut attempts = 0u32;
 
+        if let Some(budget) = &self.budget {
+            budget.record_request();
+        }
+
         loop {
             attempts += 1;
 
@@ -224,6 +248,24 @@ impl<P: RetryPolicy> RetryExecutor<P> {
                         }
                     }
 
+                    // Check the system-wide retry budget last, so only actual
+                    // retries take tokens
+                    if let Some(budget) = &self.budget {
+                        if !budget.try_consume(1) {
+                            #[cfg(feature = "otel")]
+                            tracing::debug!(
+                                operation = name,
+                                attempt = attempts,
+                                "retry budget exhausted, not retrying"
+                            );
+                            return Err(RetryError {
+                                last_error: e,
+                                attempts,
+                                elapsed: start.elapsed(),
+                            });
+                        }
+                    }
+
                     // Calculate and wait for backoff interval
                     let interval = self.config.calculate_interval(attempts - 1);
 
@@ -249,17 +291,39 @@ impl<P: RetryPolicy> RetryExecutor<P> {
     }
 }
 
+/// Thousandths of a token, so fractional deposits add up.
+const MILLI: f64 = 1000.0;
+
 /// Prevents retry storms by limiting total retry capacity.
 ///
 /// A retry budget tracks available retry tokens and prevents excessive
-/// retries when the system is under stress.
+/// retries when the system is under stress. Tokens are recovered over time
+/// and, with [`with_retry_ratio`](Self::with_retry_ratio), earned by
+/// requests, so retries stay a bounded fraction of traffic.
+///
+/// Share one budget between [`RetryExecutor`]s with
+/// [`RetryExecutor::with_budget`]:
+///
+/// ```rust
+/// use std::sync::Arc;
+///
+/// use allframe_core::resilience::{RetryBudget, RetryConfig, RetryExecutor};
+///
+/// // Retries may not exceed 10% of requests, after a burst of 10
+/// let budget = Arc::new(RetryBudget::new(10, 0.0).with_retry_ratio(0.1));
+///
+/// let orders = RetryExecutor::new(RetryConfig::default()).with_budget(budget.clone());
+/// let payments = RetryExecutor::new(RetryConfig::default()).with_budget(budget);
+/// ```
 pub struct RetryBudget {
     /// Maximum tokens available.
     max_tokens: u32,
-    /// Current available tokens.
-    tokens: AtomicU32,
+    /// Current available tokens, in thousandths.
+    tokens: AtomicU64,
     /// Tokens recovered per second.
     recovery_rate: f64,
+    /// Tokens earned per recorded request.
+    retry_ratio: f64,
     /// Last recovery time.
     last_recovery: RwLock<Instant>,
 }
@@ -273,43 +337,53 @@ impl RetryBudget {
     pub fn new(max_tokens: u32, recovery_rate: f64) -> Self {
         Self {
             max_tokens,
-            tokens: AtomicU32::new(max_tokens),
+            tokens: AtomicU64::new(max_tokens as u64 * MILLI as u64),
             recovery_rate,
+            retry_ratio: 0.0,
             last_recovery: RwLock::new(Instant::now()),
         }
     }
 
+    /// Earn `ratio` tokens per recorded request (e.g. `0.1` allows retries
+    /// for 10% of requests).
+    pub fn with_retry_ratio(mut self, ratio: f64) -> Self {
+        self.retry_ratio = ratio.max(0.0);
+        self
+    }
+
+    /// Record a request, earning tokens at the retry ratio.
+    pub fn record_request(&self) {
+        self.deposit((self.retry_ratio * MILLI) as u64);
+    }
+
     /// Try to consume retry tokens.
     ///
     /// Returns true if tokens were available and consumed.
     pub fn try_consume(&self, amount: u32) -> bool {
         self.recover_tokens();
 
-        loop {
-            let current = self.tokens.load(Ordering::Acquire);
-            if current < amount {
-                return false;
-            }
-
-            if self
-                .tokens
-                .compare_exchange(
-                    current,
-                    current - amount,
-                    Ordering::AcqRel,
-                    Ordering::Relaxed,
-                )
-                .is_ok()
-            {
-                return true;
-            }
-        }
+        let amount = amount as u64 * MILLI as u64;
+        self.tokens
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |current| {
+                current.checked_sub(amount)
+            })
+            .is_ok()
     }
 
     /// Get remaining tokens.
     pub fn remaining(&self) -> u32 {
         self.recover_tokens();
-        self.tokens.load(Ordering::Acquire)
+        (self.tokens.load(Ordering::Acquire) / MILLI as u64) as u32
+    }
+
+    /// Add thousandths of a token, up to the maximum.
+    fn deposit(&self, amount: u64) {
+        let max = self.max_tokens as u64 * MILLI as u64;
+        let _ = self
+            .tokens
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |current| {
+                Some((current + amount).min(max))
+            });
     }
 
     /// Recover tokens based on elapsed time.
@@ -321,9 +395,7 @@ impl RetryBudget {
             // Only recover every 100ms
             let recovered = (elapsed.as_secs_f64() * self.recovery_rate) as u32;
             if recovered > 0 {
-                let current = self.tokens.load(Ordering::Acquire);
-                let new_value = (current + recovered).min(self.max_tokens);
-                self.tokens.store(new_value, Ordering::Release);
+                self.deposit(recovered as u64 * MILLI as u64);
                 *last = Instant::now();
             }
         }
@@ -331,7 +403,8 @@ impl RetryBudget {
 
     /// Reset the budget to full capacity.
     pub fn reset(&self) {
-        self.tokens.store(self.max_tokens, Ordering::Release);
+        self.tokens
+            .store(self.max_tokens as u64 * MILLI as u64, Ordering::Release);
         *self.last_recovery.write() = Instant::now();
     }
 }
@@ -428,7 +501,7 @@ impl AdaptiveRetry {
 
 #[cfg(test)]
 mod tests {
-    use std::sync::Arc;
+    use std::sync::atomic::AtomicU32;
 
     use super::*;
 
@@ -502,6 +575,60 @@ mod tests {
         assert_eq!(budget.remaining(), 10);
     }
 
+    #[test]
+    fn test_retry_budget_ratio() {
+        let budget = RetryBudget::new(2, 0.0).with_retry_ratio(0.1);
+        assert!(budget.try_consume(2));
+
+        for _ in 0..9 {
+            budget.record_request();
+        }
+        assert!(!budget.try_consume(1));
+        budget.record_request();
+        assert!(budget.try_consume(1));
+
+        // Deposits are capped at the maximum
+        for _ in 0..100 {
+            budget.record_request();
+        }
+        assert_eq!(budget.remaining(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_retry_executor_depleted_budget_suppresses_retries() {
+        let budget = Arc::new(RetryBudget::new(2, 0.0));
+        let config = RetryConfig::new(5)
+            .with_initial_interval(Duration::from_millis(1))
+            .with_max_elapsed_time(None);
+        let executor = RetryExecutor::new(config).with_budget(budget.clone());
+        let calls = Arc::new(AtomicU32::new(0));
+
+        let run = || {
+            let calls = calls.clone();
+            executor.execute("test", move || {
+                let calls = calls.clone();
+                async move {
+                    let call = calls.fetch_add(1, Ordering::SeqCst);
+                    Err::<(), _>(std::io::Error::new(
+                        std::io::ErrorKind::Other,
+                        format!("failure {}", call),
+                    ))
+                }
+            })
+        };
+
+        // The budget covers two retries, then runs out
+        let err = run().await.unwrap_err();
+        assert_eq!(err.attempts, 3);
+        assert_eq!(budget.remaining(), 0);
+
+        // Further failures aren't retried and return the original error
+        let err = run().await.unwrap_err();
+        assert_eq!(err.attempts, 1);
+        assert_eq!(err.last_error.to_string(), "failure 3");
+        assert_eq!(calls.load(Ordering::SeqCst), 4);
+    }
+
     #[test]
     fn test_adaptive_retry_success_rate() {
         let adaptive = AdaptiveRetry::new(RetryConfig::default());
@@ -0,0 +1,49 @@
+//! Timeouts for async operations.
+//!
+//! Bounds how long a single call may take, failing with [`TimeoutError`]
+//! instead of waiting indefinitely.
+
+use std::{future::Future, time::Duration};
+
+/// Error returned when an operation exceeds its timeout.
+#[derive(Debug, Clone)]
+pub struct TimeoutError {
+    /// The timeout that was exceeded.
+    pub duration: Duration,
+}
+
+impl std::fmt::Display for TimeoutError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "operation timed out after {:?}", self.duration)
+    }
+}
+
+impl std::error::Error for TimeoutError {}
+
+/// Run a future, cancelling it if it doesn't complete within `duration`.
+pub async fn with_timeout<F: Future>(
+    duration: Duration,
+    future: F,
+) -> Result<F::Output, TimeoutError> {
+    tokio::time::timeout(duration, future)
+        .await
+        .map_err(|_| TimeoutError { duration })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_with_timeout() {
+        let ok = with_timeout(Duration::from_secs(1), async { 42 }).await;
+        assert_eq!(ok.unwrap(), 42);
+
+        let slow = with_timeout(
+            Duration::from_millis(10),
+            tokio::time::sleep(Duration::from_secs(5)),
+        )
+        .await;
+        assert_eq!(slow.unwrap_err().duration, Duration::from_millis(10));
+    }
+}
@@ -1,6 +1,6 @@
 //! Protocol adapter trait for supporting multiple protocols
 
-use std::{future::Future, pin::Pin};
+use std::{fmt, future::Future, pin::Pin};
 
 /// Protocol adapter trait
 ///
@@ -21,8 +21,56 @@ pub trait ProtocolAdapter: Send + Sync {
         &self,
         request: &str,
     ) -> Pin<Box<dyn Future<Output = Result<String, String>> + Send + '_>>;
+
+    /// Handlers this adapter's routes call, as `(route, handler)` pairs
+    ///
+    /// Used by [`Router::validate`](super::Router::validate) to find routes
+    /// pointing to handlers that were never registered. Defaults to none.
+    fn handler_references(&self) -> Vec<(String, String)> {
+        Vec::new()
+    }
 }
 
+/// An adapter route that references an unregistered handler
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DanglingRoute {
+    /// Name of the adapter the route belongs to
+    pub adapter: String,
+    /// The route, e.g. `GET /users`
+    pub route: String,
+    /// Name of the missing handler
+    pub handler: String,
+}
+
+/// Error returned by [`Router::validate`](super::Router::validate)
+///
+/// Lists every dangling route, not just the first one found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RouterValidationError {
+    /// Routes referencing unregistered handlers
+    pub dangling: Vec<DanglingRoute>,
+}
+
+impl fmt::Display for RouterValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} route(s) reference unregistered handlers:",
+            self.dangling.len()
+        )?;
+        for route in &self.dangling {
+            write!(
+                f,
+                "\n  {} {} -> '{}'",
+                route.adapter, route.route, route.handler
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for RouterValidationError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
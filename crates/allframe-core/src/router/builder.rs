@@ -3,6 +3,8 @@
 //! This module provides a builder pattern for configuring routes with
 //! metadata, tags, descriptions, and other OpenAPI properties.
 
+use std::time::Duration;
+
 use serde_json::Value;
 
 use crate::router::{Method, RouteMetadata};
@@ -48,6 +50,27 @@ impl RouteBuilder {
         self
     }
 
+    /// Set a timeout for this route, overriding the router default
+    ///
+    /// Requests to this route still running after `timeout` fail with
+    /// `504 Gateway Timeout`, whether they call the handler directly or
+    /// come through the REST adapter.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.metadata = self.metadata.with_timeout(timeout);
+        self
+    }
+
+    /// Set a rate limit for this route, overriding the router default
+    ///
+    /// Requests to this route over the limit fail with `429 Too Many
+    /// Requests` and a `retry-after` header, whether they call the handler
+    /// directly or come through the REST adapter.
+    #[cfg(feature = "resilience")]
+    pub fn rate_limit(mut self, rps: u32, burst: u32) -> Self {
+        self.metadata = self.metadata.with_rate_limit(rps, burst);
+        self
+    }
+
     /// Build the route metadata
     pub fn build(self) -> RouteMetadata {
         self.metadata
@@ -136,6 +159,21 @@ mod tests {
         assert_eq!(metadata.response_schema, Some(response));
     }
 
+    #[cfg(feature = "resilience")]
+    #[test]
+    fn test_route_builder_with_limits() {
+        let metadata = RouteBuilder::new("/search", Method::GET)
+            .timeout(Duration::from_secs(2))
+            .rate_limit(5, 10)
+            .build();
+
+        assert_eq!(metadata.timeout, Some(Duration::from_secs(2)));
+        assert_eq!(
+            metadata.rate_limit,
+            Some(crate::router::RouteRateLimit { rps: 5, burst: 10 })
+        );
+    }
+
     #[test]
     fn test_route_builder_all_http_methods() {
         let methods = vec![
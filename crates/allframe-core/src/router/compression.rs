@@ -0,0 +1,135 @@
+//! Response compression for the REST adapter
+//!
+//! [`RestAdapter::with_compression`](super::RestAdapter::with_compression)
+//! enables gzip and brotli compression of response bodies, negotiated from
+//! the request's `Accept-Encoding` header. Bodies smaller than the
+//! configured minimum are sent as-is, since compressing them saves little
+//! and costs CPU.
+
+use std::io::Write;
+
+/// Default minimum body size to compress, in bytes
+pub const DEFAULT_COMPRESSION_MIN_BYTES: usize = 1024;
+
+/// A content coding the REST adapter can compress with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentEncoding {
+    /// `gzip`
+    Gzip,
+    /// `br` (brotli)
+    Brotli,
+}
+
+impl ContentEncoding {
+    /// The coding's name in `Content-Encoding`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContentEncoding::Gzip => "gzip",
+            ContentEncoding::Brotli => "br",
+        }
+    }
+
+    /// Pick the preferred coding from an `Accept-Encoding` header
+    ///
+    /// Honors `q` values and `*`. Prefers brotli when both are equally
+    /// acceptable. Returns `None` if the client accepts neither.
+    pub fn negotiate(accept_encoding: &str) -> Option<Self> {
+        let mut wildcard = None;
+        let mut gzip = None;
+        let mut brotli = None;
+        for entry in accept_encoding.split(',') {
+            let mut params = entry.split(';');
+            let coding = params.next().unwrap_or("").trim().to_ascii_lowercase();
+            let quality = params
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            match coding.as_str() {
+                "gzip" | "x-gzip" => gzip = Some(quality),
+                "br" => brotli = Some(quality),
+                "*" => wildcard = Some(quality),
+                _ => {}
+            }
+        }
+
+        let brotli = brotli.or(wildcard).unwrap_or(0.0);
+        let gzip = gzip.or(wildcard).unwrap_or(0.0);
+        if brotli <= 0.0 && gzip <= 0.0 {
+            None
+        } else if brotli >= gzip {
+            Some(ContentEncoding::Brotli)
+        } else {
+            Some(ContentEncoding::Gzip)
+        }
+    }
+
+    /// Compress `body` with this coding
+    pub fn compress(&self, body: &[u8]) -> Vec<u8> {
+        match self {
+            ContentEncoding::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder
+                    .write_all(body)
+                    .expect("writing to a Vec can't fail");
+                encoder.finish().expect("writing to a Vec can't fail")
+            }
+            ContentEncoding::Brotli => {
+                // Quality 5 compresses much faster than the maximum of 11,
+                // which suits responses compressed on every request
+                let mut encoder = brotli::CompressorWriter::new(Vec::new(), 4096, 5, 22);
+                encoder
+                    .write_all(body)
+                    .expect("writing to a Vec can't fail");
+                encoder.into_inner()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+
+    #[test]
+    fn test_negotiate() {
+        assert_eq!(
+            ContentEncoding::negotiate("gzip, deflate, br"),
+            Some(ContentEncoding::Brotli)
+        );
+        assert_eq!(
+            ContentEncoding::negotiate("gzip, br;q=0.5"),
+            Some(ContentEncoding::Gzip)
+        );
+        assert_eq!(
+            ContentEncoding::negotiate("*;q=0.1, br;q=0"),
+            Some(ContentEncoding::Gzip)
+        );
+        assert_eq!(ContentEncoding::negotiate("deflate, identity"), None);
+        assert_eq!(ContentEncoding::negotiate("gzip;q=0"), None);
+        assert_eq!(ContentEncoding::negotiate(""), None);
+    }
+
+    #[test]
+    fn test_compress_round_trip() {
+        let body = "{\"items\": [1, 2, 3]}".repeat(100);
+
+        let gzipped = ContentEncoding::Gzip.compress(body.as_bytes());
+        let mut decoded = String::new();
+        flate2::read::GzDecoder::new(gzipped.as_slice())
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, body);
+        assert!(gzipped.len() < body.len());
+
+        let brotli = ContentEncoding::Brotli.compress(body.as_bytes());
+        let mut decoded = String::new();
+        brotli::Decompressor::new(brotli.as_slice(), 4096)
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, body);
+        assert!(brotli.len() < body.len());
+    }
+}
@@ -3,8 +3,12 @@
 //! This module enables AllFrame's key differentiator: write handlers once,
 //! expose them via multiple protocols through configuration alone.
 
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
+use super::{graphql, rest};
+
 /// Router configuration with protocol selection
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RouterConfig {
@@ -41,6 +45,25 @@ pub struct RestConfig {
     /// Path prefix for all REST endpoints
     #[serde(default = "default_rest_prefix")]
     pub path_prefix: String,
+
+    /// Maximum request body size in bytes (larger bodies get HTTP 413)
+    #[serde(default = "default_max_body_bytes")]
+    pub max_body_bytes: usize,
+
+    /// Maximum JSON nesting depth of request bodies (deeper bodies get HTTP
+    /// 400)
+    #[serde(default = "default_max_json_depth")]
+    pub max_json_depth: usize,
+
+    /// Compress responses with gzip or brotli when the client accepts it
+    #[cfg(feature = "router-compression")]
+    #[serde(default)]
+    pub compression: bool,
+
+    /// Minimum response body size to compress, in bytes
+    #[cfg(feature = "router-compression")]
+    #[serde(default = "default_compression_min_bytes")]
+    pub compression_min_bytes: usize,
 }
 
 fn default_rest_port() -> u16 {
@@ -51,6 +74,19 @@ fn default_rest_prefix() -> String {
     "/api/v1".to_string()
 }
 
+fn default_max_body_bytes() -> usize {
+    rest::DEFAULT_MAX_BODY_BYTES
+}
+
+fn default_max_json_depth() -> usize {
+    rest::DEFAULT_MAX_JSON_DEPTH
+}
+
+#[cfg(feature = "router-compression")]
+fn default_compression_min_bytes() -> usize {
+    super::compression::DEFAULT_COMPRESSION_MIN_BYTES
+}
+
 /// GraphQL protocol configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GraphQLConfig {
@@ -65,6 +101,41 @@ pub struct GraphQLConfig {
     /// Enable GraphiQL playground
     #[serde(default)]
     pub playground: bool,
+
+    /// Maximum selection-set depth of a query
+    #[serde(default = "default_max_query_depth")]
+    pub max_query_depth: usize,
+
+    /// Maximum number of field selections in a query
+    #[serde(default = "default_max_query_nodes")]
+    pub max_query_nodes: usize,
+
+    /// Maximum field-weighted complexity of a query (unlimited if unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_complexity: Option<usize>,
+
+    /// Cost of individual fields by name (fields not listed cost 1)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub field_costs: HashMap<String, usize>,
+
+    /// Allow `__schema`/`__type` introspection queries
+    #[serde(default = "default_introspection")]
+    pub introspection: bool,
+}
+
+impl Default for GraphQLConfig {
+    fn default() -> Self {
+        Self {
+            port: default_graphql_port(),
+            path: default_graphql_path(),
+            playground: false,
+            max_query_depth: default_max_query_depth(),
+            max_query_nodes: default_max_query_nodes(),
+            max_complexity: None,
+            field_costs: HashMap::new(),
+            introspection: default_introspection(),
+        }
+    }
 }
 
 fn default_graphql_port() -> u16 {
@@ -75,6 +146,18 @@ fn default_graphql_path() -> String {
     "/graphql".to_string()
 }
 
+fn default_max_query_depth() -> usize {
+    graphql::DEFAULT_MAX_QUERY_DEPTH
+}
+
+fn default_max_query_nodes() -> usize {
+    graphql::DEFAULT_MAX_QUERY_NODES
+}
+
+fn default_introspection() -> bool {
+    true
+}
+
 /// gRPC protocol configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GrpcConfig {
@@ -128,6 +211,20 @@ impl RouterConfig {
     pub fn grpc(&self) -> Option<&GrpcConfig> {
         self.server.grpc.as_ref()
     }
+
+    /// Maximum REST request body size, falling back to the default
+    pub fn max_body_bytes(&self) -> usize {
+        self.rest()
+            .map(RestConfig::max_body_bytes)
+            .unwrap_or(rest::DEFAULT_MAX_BODY_BYTES)
+    }
+
+    /// Maximum REST JSON nesting depth, falling back to the default
+    pub fn max_json_depth(&self) -> usize {
+        self.rest()
+            .map(RestConfig::max_json_depth)
+            .unwrap_or(rest::DEFAULT_MAX_JSON_DEPTH)
+    }
 }
 
 impl RestConfig {
@@ -140,6 +237,23 @@ impl RestConfig {
     pub fn path_prefix(&self) -> &str {
         &self.path_prefix
     }
+
+    /// Get the maximum request body size in bytes
+    pub fn max_body_bytes(&self) -> usize {
+        self.max_body_bytes
+    }
+
+    /// Get the maximum JSON nesting depth
+    pub fn max_json_depth(&self) -> usize {
+        self.max_json_depth
+    }
+
+    /// Get the minimum body size to compress, or `None` if compression is
+    /// off
+    #[cfg(feature = "router-compression")]
+    pub fn compression_min_bytes(&self) -> Option<usize> {
+        self.compression.then_some(self.compression_min_bytes)
+    }
 }
 
 impl GraphQLConfig {
@@ -157,6 +271,49 @@ impl GraphQLConfig {
     pub fn playground(&self) -> bool {
         self.playground
     }
+
+    /// Get the maximum query depth
+    pub fn max_query_depth(&self) -> usize {
+        self.max_query_depth
+    }
+
+    /// Get the maximum number of field selections
+    pub fn max_query_nodes(&self) -> usize {
+        self.max_query_nodes
+    }
+
+    /// Set the maximum field-weighted query complexity
+    pub fn max_complexity(mut self, max_complexity: usize) -> Self {
+        self.max_complexity = Some(max_complexity);
+        self
+    }
+
+    /// Set the cost of a field, counted each time it is selected
+    pub fn field_cost(mut self, field: impl Into<String>, cost: usize) -> Self {
+        self.field_costs.insert(field.into(), cost);
+        self
+    }
+
+    /// Reject introspection queries, as recommended for production
+    pub fn disable_introspection(mut self) -> Self {
+        self.introspection = false;
+        self
+    }
+
+    /// Get the complexity budget, if any
+    pub fn complexity_limit(&self) -> Option<usize> {
+        self.max_complexity
+    }
+
+    /// Get the cost of a field (1 unless configured)
+    pub fn cost_of(&self, field: &str) -> usize {
+        self.field_costs.get(field).copied().unwrap_or(1)
+    }
+
+    /// Check if introspection is enabled
+    pub fn introspection_enabled(&self) -> bool {
+        self.introspection
+    }
 }
 
 impl GrpcConfig {
@@ -276,4 +433,95 @@ mod tests {
         assert_eq!(grpc.port(), 9090);
         assert!(!grpc.reflection()); // Default is false
     }
+
+    #[test]
+    fn test_request_limits() {
+        let toml = r#"
+            [server]
+            protocols = ["rest", "graphql"]
+
+            [server.rest]
+            max_body_bytes = 4096
+            max_json_depth = 8
+
+            [server.graphql]
+            max_query_depth = 5
+            max_query_nodes = 50
+        "#;
+
+        let config = RouterConfig::from_toml(toml).unwrap();
+        assert_eq!(config.max_body_bytes(), 4096);
+        assert_eq!(config.max_json_depth(), 8);
+
+        let graphql = config.graphql().unwrap();
+        assert_eq!(graphql.max_query_depth(), 5);
+        assert_eq!(graphql.max_query_nodes(), 50);
+    }
+
+    #[test]
+    fn test_graphql_complexity_config() {
+        let toml = r#"
+            [server]
+            protocols = ["graphql"]
+
+            [server.graphql]
+            max_complexity = 200
+            introspection = false
+
+            [server.graphql.field_costs]
+            users = 10
+        "#;
+
+        let config = RouterConfig::from_toml(toml).unwrap();
+        let graphql = config.graphql().unwrap();
+        assert_eq!(graphql.complexity_limit(), Some(200));
+        assert_eq!(graphql.cost_of("users"), 10);
+        assert_eq!(graphql.cost_of("name"), 1);
+        assert!(!graphql.introspection_enabled());
+
+        let built = GraphQLConfig::default()
+            .max_complexity(50)
+            .field_cost("search", 25)
+            .disable_introspection();
+        assert_eq!(built.complexity_limit(), Some(50));
+        assert_eq!(built.cost_of("search"), 25);
+        assert!(!built.introspection_enabled());
+        assert!(GraphQLConfig::default().introspection_enabled());
+    }
+
+    #[test]
+    fn test_request_limit_defaults() {
+        let toml = r#"
+            [server]
+            protocols = ["rest"]
+        "#;
+
+        let config = RouterConfig::from_toml(toml).unwrap();
+        assert_eq!(config.max_body_bytes(), 1024 * 1024);
+        assert_eq!(config.max_json_depth(), 64);
+    }
+
+    #[cfg(feature = "router-compression")]
+    #[test]
+    fn test_compression_config() {
+        let toml = r#"
+            [server]
+            protocols = ["rest"]
+
+            [server.rest]
+            compression = true
+        "#;
+        let config = RouterConfig::from_toml(toml).unwrap();
+        assert_eq!(config.rest().unwrap().compression_min_bytes(), Some(1024));
+
+        let toml = r#"
+            [server]
+            protocols = ["rest"]
+
+            [server.rest]
+            compression_min_bytes = 256
+        "#;
+        let config = RouterConfig::from_toml(toml).unwrap();
+        assert_eq!(config.rest().unwrap().compression_min_bytes(), None);
+    }
 }
@@ -10,6 +10,8 @@
 //! - **Breaking Change Detection**: Detect API contract violations
 //! - **Mock Server Generation**: Generate mock servers from specs
 //! - **Coverage Reporting**: Track contract test coverage
+//! - **Consumer-Driven Contracts**: Export Pact-style contract files and
+//!   verify a provider router against them
 //!
 //! # Example
 //!
@@ -27,8 +29,24 @@
 //!     Ok(())
 //! }
 //! ```
+//!
+//! # Consumer-Driven Contracts
+//!
+//! A consumer exports the routes it depends on, and the provider verifies
+//! its own router against that file in CI:
+//!
+//! ```rust,ignore
+//! // Consumer side
+//! let config = ContractTestConfig::new().participants("web-client", "gateway");
+//! ContractTester::with_config(&client_router, config).export("contracts/gateway.json")?;
+//!
+//! // Provider side
+//! let results = ContractTester::new(&gateway_router)
+//!     .verify_against("contracts/gateway.json")?;
+//! assert!(results.all_passed(), "{:?}", results.failed_tests());
+//! ```
 
-use std::collections::HashMap;
+use std::{collections::HashMap, io, path::Path};
 
 use serde::{Deserialize, Serialize};
 
@@ -156,6 +174,12 @@ pub struct ContractTestConfig {
 
     /// Fail on first error
     pub fail_fast: bool,
+
+    /// Consumer name written to exported contracts
+    pub consumer: String,
+
+    /// Provider name written to exported contracts
+    pub provider: String,
 }
 
 impl Default for ContractTestConfig {
@@ -166,6 +190,8 @@ impl Default for ContractTestConfig {
             detect_breaking_changes: true,
             generate_mocks: false,
             fail_fast: false,
+            consumer: "consumer".to_string(),
+            provider: "provider".to_string(),
         }
     }
 }
@@ -205,6 +231,142 @@ impl ContractTestConfig {
         self.fail_fast = enable;
         self
     }
+
+    /// Set the consumer and provider names used in exported contracts
+    pub fn participants(
+        mut self,
+        consumer: impl Into<String>,
+        provider: impl Into<String>,
+    ) -> Self {
+        self.consumer = consumer.into();
+        self.provider = provider.into();
+        self
+    }
+}
+
+/// A participant in a contract (consumer or provider)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContractParticipant {
+    /// Service name
+    pub name: String,
+}
+
+/// Request expectation of a contract interaction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContractRequest {
+    /// HTTP method (empty for non-REST protocols)
+    pub method: String,
+
+    /// Route path
+    pub path: String,
+
+    /// Protocol the route is exposed on
+    pub protocol: String,
+
+    /// JSON Schema of the request body the consumer sends
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<serde_json::Value>,
+}
+
+/// Response expectation of a contract interaction
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContractResponse {
+    /// JSON Schema of the response body the consumer relies on
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<serde_json::Value>,
+}
+
+/// A single request/response expectation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContractInteraction {
+    /// Human-readable description
+    pub description: String,
+
+    /// Expected request
+    pub request: ContractRequest,
+
+    /// Expected response
+    pub response: ContractResponse,
+}
+
+impl From<&RouteMetadata> for ContractInteraction {
+    fn from(route: &RouteMetadata) -> Self {
+        Self {
+            description: route.description.clone().unwrap_or_else(|| {
+                format!("{} {}", route.method, route.path)
+                    .trim()
+                    .to_string()
+            }),
+            request: ContractRequest {
+                method: route.method.clone(),
+                path: route.path.clone(),
+                protocol: route.protocol.clone(),
+                schema: route.request_schema.clone(),
+            },
+            response: ContractResponse {
+                schema: route.response_schema.clone(),
+            },
+        }
+    }
+}
+
+/// A consumer-driven contract file
+///
+/// Serialized in a Pact-style JSON layout: `consumer`, `provider`,
+/// `interactions` and `metadata`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Contract {
+    /// The consuming service
+    pub consumer: ContractParticipant,
+
+    /// The providing service
+    pub provider: ContractParticipant,
+
+    /// Expected interactions
+    pub interactions: Vec<ContractInteraction>,
+
+    /// Contract file metadata
+    #[serde(default)]
+    pub metadata: serde_json::Value,
+}
+
+impl Contract {
+    /// Build a contract from route metadata
+    pub fn from_routes(
+        consumer: impl Into<String>,
+        provider: impl Into<String>,
+        routes: &[RouteMetadata],
+    ) -> Self {
+        Self {
+            consumer: ContractParticipant {
+                name: consumer.into(),
+            },
+            provider: ContractParticipant {
+                name: provider.into(),
+            },
+            interactions: routes.iter().map(ContractInteraction::from).collect(),
+            metadata: serde_json::json!({
+                "allframe": { "version": env!("CARGO_PKG_VERSION") }
+            }),
+        }
+    }
+
+    /// Read a contract from a JSON file
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        serde_json::from_str(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Write the contract to a JSON file, creating parent directories
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        std::fs::write(path, json)
+    }
 }
 
 /// Contract tester for API routes
@@ -317,6 +479,102 @@ async fn test_{}_contract() {{
         )
     }
 
+    /// Build a contract describing the routes of this router
+    pub fn contract(&self) -> Contract {
+        Contract::from_routes(&self.config.consumer, &self.config.provider, &self.routes)
+    }
+
+    /// Export the request/response expectations of all routes to a JSON
+    /// contract file
+    pub fn export(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.contract().save(path)
+    }
+
+    /// Verify that this router satisfies a consumer's contract file
+    ///
+    /// Produces one result per interaction. An interaction fails when the
+    /// provider has no matching route, when the provider requires request
+    /// fields the consumer does not send, or when the provider's response
+    /// lacks fields the consumer requires. Returns an error only if the
+    /// contract file cannot be read or parsed.
+    pub fn verify_against(&self, path: impl AsRef<Path>) -> io::Result<ContractTestResults> {
+        Ok(self.verify_contract(&Contract::load(path)?))
+    }
+
+    /// Verify that this router satisfies an in-memory contract
+    pub fn verify_contract(&self, contract: &Contract) -> ContractTestResults {
+        let mut results = Vec::new();
+
+        for interaction in &contract.interactions {
+            let result = self.verify_interaction(interaction);
+            let passed = result.passed;
+            results.push(result);
+
+            if self.config.fail_fast && !passed {
+                break;
+            }
+        }
+
+        ContractTestResults::new(results)
+    }
+
+    fn verify_interaction(&self, interaction: &ContractInteraction) -> ContractTestResult {
+        let expected = &interaction.request;
+        let Some(route) = self.routes.iter().find(|r| {
+            r.path == expected.path
+                && r.method.eq_ignore_ascii_case(&expected.method)
+                && r.protocol == expected.protocol
+        }) else {
+            return ContractTestResult::failed(
+                &expected.path,
+                &expected.method,
+                format!(
+                    "Provider has no {} route {} {}",
+                    expected.protocol, expected.method, expected.path
+                ),
+            );
+        };
+
+        let mut result = ContractTestResult::passed(&expected.path, &expected.method);
+
+        if self.config.validate_requests {
+            if let Some(consumer) = &expected.schema {
+                match &route.request_schema {
+                    // The consumer's request must satisfy the provider's schema
+                    Some(provider) => {
+                        for error in schema_mismatches(provider, consumer, "request") {
+                            result = result.with_error(error);
+                        }
+                    }
+                    None => {
+                        result = result.with_error("request: provider route lacks request schema")
+                    }
+                }
+            }
+        }
+
+        if self.config.validate_responses {
+            if let Some(consumer) = &interaction.response.schema {
+                match &route.response_schema {
+                    // The provider's response must satisfy the consumer's schema
+                    Some(provider) => {
+                        for error in schema_mismatches(consumer, provider, "response") {
+                            result = result.with_error(error);
+                        }
+                    }
+                    None => {
+                        result = result.with_error("response: provider route lacks response schema")
+                    }
+                }
+            }
+        }
+
+        if !result.passed && result.failure_reason.is_none() {
+            result.failure_reason = Some("Contract not satisfied".to_string());
+        }
+        result
+    }
+
     /// Get coverage statistics
     pub fn coverage_stats(&self) -> HashMap<String, f64> {
         let mut stats = HashMap::new();
@@ -331,6 +589,62 @@ async fn test_{}_contract() {{
     }
 }
 
+/// Check that data described by `actual` satisfies the `expected` schema.
+///
+/// Every property `expected` requires must be present in `actual` and, where
+/// both declare a `type`, the types must agree. Nested objects and array items
+/// are checked recursively.
+fn schema_mismatches(
+    expected: &serde_json::Value,
+    actual: &serde_json::Value,
+    location: &str,
+) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    let expected_type = expected.get("type");
+    let actual_type = actual.get("type");
+    if let (Some(e), Some(a)) = (expected_type, actual_type) {
+        if e != a {
+            errors.push(format!("{}: expected type {}, found {}", location, e, a));
+            return errors;
+        }
+    }
+
+    let empty = serde_json::Map::new();
+    let expected_props = expected
+        .get("properties")
+        .and_then(|p| p.as_object())
+        .unwrap_or(&empty);
+    let actual_props = actual
+        .get("properties")
+        .and_then(|p| p.as_object())
+        .unwrap_or(&empty);
+
+    if let Some(required) = expected.get("required").and_then(|r| r.as_array()) {
+        for field in required.iter().filter_map(|f| f.as_str()) {
+            if !actual_props.contains_key(field) {
+                errors.push(format!("{}: missing required field '{}'", location, field));
+            }
+        }
+    }
+
+    for (name, expected_prop) in expected_props {
+        if let Some(actual_prop) = actual_props.get(name) {
+            errors.extend(schema_mismatches(
+                expected_prop,
+                actual_prop,
+                &format!("{}.{}", location, name),
+            ));
+        }
+    }
+
+    if let (Some(e), Some(a)) = (expected.get("items"), actual.get("items")) {
+        errors.extend(schema_mismatches(e, a, &format!("{}[]", location)));
+    }
+
+    errors
+}
+
 /// Helper trait for generating contract tests
 pub trait ContractTestable {
     /// Generate contract tests for all routes
@@ -457,6 +771,8 @@ mod tests {
             description: Some("Get users".to_string()),
             request_schema: None,
             response_schema: None,
+            timeout: None,
+            rate_limit: None,
         };
 
         let code = tester.generate_test_code(&route);
@@ -476,4 +792,109 @@ mod tests {
         assert_eq!(stats.get("total_routes"), Some(&0.0));
         assert_eq!(stats.get("tested_routes"), Some(&0.0));
     }
+
+    fn user_route() -> RouteMetadata {
+        RouteMetadata::new("/users", "POST", "rest")
+            .with_request_schema(serde_json::json!({
+                "type": "object",
+                "properties": {"name": {"type": "string"}},
+                "required": ["name"]
+            }))
+            .with_response_schema(serde_json::json!({
+                "type": "object",
+                "properties": {
+                    "id": {"type": "integer"},
+                    "name": {"type": "string"}
+                },
+                "required": ["id", "name"]
+            }))
+    }
+
+    #[test]
+    fn test_export_and_verify_round_trip() {
+        let mut router = Router::new();
+        router.add_route(user_route());
+
+        let path = std::env::temp_dir().join(format!(
+            "allframe-contract-{}/users.json",
+            std::process::id()
+        ));
+        let config = ContractTestConfig::new().participants("web", "users-api");
+        ContractTester::with_config(&router, config)
+            .export(&path)
+            .unwrap();
+
+        let contract = Contract::load(&path).unwrap();
+        assert_eq!(contract.consumer.name, "web");
+        assert_eq!(contract.provider.name, "users-api");
+        assert_eq!(contract.interactions.len(), 1);
+
+        let results = ContractTester::new(&router).verify_against(&path).unwrap();
+        std::fs::remove_dir_all(path.parent().unwrap()).ok();
+
+        assert_eq!(results.total, 1);
+        assert!(results.all_passed(), "{:?}", results.failed_tests());
+    }
+
+    #[test]
+    fn test_verify_detects_missing_route() {
+        let contract = Contract::from_routes("web", "api", &[user_route()]);
+        let router = Router::new();
+
+        let results = ContractTester::new(&router).verify_contract(&contract);
+        assert_eq!(results.failed, 1);
+        assert!(results.results[0]
+            .failure_reason
+            .as_deref()
+            .unwrap()
+            .contains("no rest route POST /users"));
+    }
+
+    #[test]
+    fn test_verify_detects_breaking_changes() {
+        let contract = Contract::from_routes("web", "api", &[user_route()]);
+
+        // Provider now requires an extra request field and dropped `name`
+        // from its response.
+        let mut router = Router::new();
+        router.add_route(
+            RouteMetadata::new("/users", "POST", "rest")
+                .with_request_schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {
+                        "name": {"type": "string"},
+                        "email": {"type": "string"}
+                    },
+                    "required": ["name", "email"]
+                }))
+                .with_response_schema(serde_json::json!({
+                    "type": "object",
+                    "properties": {"id": {"type": "string"}},
+                    "required": ["id"]
+                })),
+        );
+
+        let results = ContractTester::new(&router).verify_contract(&contract);
+        let errors = &results.results[0].errors;
+        assert!(!results.all_passed());
+        assert!(errors.contains(&"request: missing required field 'email'".to_string()));
+        assert!(errors.contains(&"response: missing required field 'name'".to_string()));
+        assert!(errors
+            .iter()
+            .any(|e| e.starts_with("response.id: expected type")));
+    }
+
+    #[test]
+    fn test_verify_allows_additive_changes() {
+        let contract = Contract::from_routes("web", "api", &[user_route()]);
+
+        let mut router = Router::new();
+        let mut route = user_route();
+        route.response_schema.as_mut().unwrap()["properties"]["email"] =
+            serde_json::json!({"type": "string"});
+        router.add_route(route);
+
+        let results = ContractTester::new(&router).verify_contract(&contract);
+        assert!(results.all_passed(), "{:?}", results.failed_tests());
+    }
 }
@@ -0,0 +1,371 @@
+//! Content-type aware request extraction
+//!
+//! Builds a handler's args from the parts of an HTTP request, so handlers
+//! don't care which part a field came from:
+//!
+//! - the body, decoded as JSON (`application/json`) or as a form
+//!   (`application/x-www-form-urlencoded`) according to its `Content-Type`
+//! - the query string
+//! - path parameters (`/users/:id`)
+//!
+//! The parts are merged into one JSON object. Path parameters take
+//! precedence over body fields, which take precedence over query
+//! parameters. Form, query and path values are strings; typed handlers
+//! deserialize them with [`from_args`], which parses numbers and booleans
+//! from strings where the handler's input type expects them.
+//!
+//! ```rust
+//! use allframe_core::router::RestAdapter;
+//!
+//! let mut rest = RestAdapter::new();
+//! rest.route("POST", "/users/:id/notes", "add_note");
+//!
+//! let (handler, args) = rest
+//!     .extract_args(
+//!         "POST",
+//!         "/users/42/notes?draft=true",
+//!         Some("application/x-www-form-urlencoded"),
+//!         "text=hello+world",
+//!     )
+//!     .unwrap();
+//! assert_eq!(handler, "add_note");
+//! assert_eq!(
+//!     serde_json::from_str::<serde_json::Value>(&args).unwrap(),
+//!     serde_json::json!({ "id": "42", "text": "hello world", "draft": "true" })
+//! );
+//! ```
+
+use serde::{
+    de::{
+        value::{MapDeserializer, SeqDeserializer},
+        DeserializeOwned, IntoDeserializer, Visitor,
+    },
+    forward_to_deserialize_any, Deserializer,
+};
+#[cfg(feature = "router")]
+use serde_json::Map;
+use serde_json::Value;
+
+#[cfg(feature = "router")]
+use super::RestResponse;
+
+/// How a request body is encoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BodyFormat {
+    /// `application/json` and `application/*+json`
+    Json,
+    /// `application/x-www-form-urlencoded`
+    Form,
+}
+
+impl BodyFormat {
+    /// Body format for a `Content-Type` header value
+    ///
+    /// Parameters such as `charset` are ignored. Returns `None` for media
+    /// types that can't be decoded into handler args.
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        let media_type = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        match media_type.as_str() {
+            "application/json" => Some(BodyFormat::Json),
+            "application/x-www-form-urlencoded" => Some(BodyFormat::Form),
+            other if other.starts_with("application/") && other.ends_with("+json") => {
+                Some(BodyFormat::Json)
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Build handler args from a request's body, query string and path params
+///
+/// Bodies without a `Content-Type` are decoded as JSON. Fails with
+/// `415 Unsupported Media Type` for other content types and
+/// `400 Bad Request` for malformed bodies. A JSON body that isn't an object
+/// is passed through as-is, unless there are query or path parameters to
+/// merge with it.
+#[cfg(feature = "router")]
+pub fn request_args(
+    content_type: Option<&str>,
+    body: &str,
+    query: &str,
+    path_params: &[(String, String)],
+) -> Result<Value, RestResponse> {
+    let mut args = parse_form(query);
+
+    if !body.trim().is_empty() {
+        let format = match content_type {
+            Some(content_type) => BodyFormat::from_content_type(content_type).ok_or_else(|| {
+                error_response(415, format!("Unsupported content type: {}", content_type))
+            })?,
+            None => BodyFormat::Json,
+        };
+        match format {
+            BodyFormat::Form => args.extend(parse_form(body)),
+            BodyFormat::Json => {
+                let value: Value = serde_json::from_str(body)
+                    .map_err(|e| error_response(400, format!("Invalid JSON body: {}", e)))?;
+                match value {
+                    Value::Object(fields) => args.extend(fields),
+                    value if args.is_empty() && path_params.is_empty() => return Ok(value),
+                    _ => {
+                        return Err(error_response(
+                            400,
+                            "JSON body must be an object to merge with query and path parameters"
+                                .to_string(),
+                        ))
+                    }
+                }
+            }
+        }
+    }
+
+    for (name, value) in path_params {
+        args.insert(name.clone(), Value::String(value.clone()));
+    }
+    Ok(Value::Object(args))
+}
+
+/// Deserialize handler args, accepting numbers and booleans as strings
+///
+/// Behaves like `serde_json::from_str`, except that a string such as
+/// `"42"` or `"true"` deserializes into a field expecting a number or a
+/// boolean. This lets form, query and path values, which are always
+/// strings, fill typed handler inputs.
+pub fn from_args<T: DeserializeOwned>(args: &str) -> Result<T, serde_json::Error> {
+    let value: Value = serde_json::from_str(args)?;
+    T::deserialize(Lenient(value))
+}
+
+/// Parse `application/x-www-form-urlencoded` pairs into a JSON object
+///
+/// A key that appears more than once becomes an array of its values.
+#[cfg(feature = "router")]
+fn parse_form(input: &str) -> Map<String, Value> {
+    let mut fields = Map::new();
+    for (key, value) in form_urlencoded::parse(input.as_bytes()) {
+        let value = Value::String(value.into_owned());
+        match fields.get_mut(key.as_ref()) {
+            Some(Value::Array(values)) => values.push(value),
+            Some(existing) => {
+                let first = existing.take();
+                *existing = Value::Array(vec![first, value]);
+            }
+            None => {
+                fields.insert(key.into_owned(), value);
+            }
+        }
+    }
+    fields
+}
+
+#[cfg(feature = "router")]
+fn error_response(status: u16, error: String) -> RestResponse {
+    RestResponse::json(&serde_json::json!({ "error": error })).with_status(status)
+}
+
+/// Deserializer over a JSON value that parses scalars out of strings on
+/// demand
+struct Lenient(Value);
+
+impl<'de> IntoDeserializer<'de, serde_json::Error> for Lenient {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+macro_rules! deserialize_from_str {
+    ($($method:ident => $ty:ty, $visit:ident;)*) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                if let Value::String(s) = &self.0 {
+                    if let Ok(parsed) = s.parse::<$ty>() {
+                        return visitor.$visit(parsed);
+                    }
+                }
+                self.deserialize_any(visitor)
+            }
+        )*
+    };
+}
+
+impl<'de> Deserializer<'de> for Lenient {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::Array(items) => {
+                let mut seq = SeqDeserializer::new(items.into_iter().map(Lenient));
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+            Value::Object(fields) => {
+                let mut map = MapDeserializer::new(
+                    fields.into_iter().map(|(key, value)| (key, Lenient(value))),
+                );
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    deserialize_from_str! {
+        deserialize_bool => bool, visit_bool;
+        deserialize_i8 => i8, visit_i8;
+        deserialize_i16 => i16, visit_i16;
+        deserialize_i32 => i32, visit_i32;
+        deserialize_i64 => i64, visit_i64;
+        deserialize_u8 => u8, visit_u8;
+        deserialize_u16 => u16, visit_u16;
+        deserialize_u32 => u32, visit_u32;
+        deserialize_u64 => u64, visit_u64;
+        deserialize_f32 => f32, visit_f32;
+        deserialize_f64 => f64, visit_f64;
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_enum(name, variants, visitor)
+    }
+
+    forward_to_deserialize_any! {
+        i128 u128 char str string bytes byte_buf unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::Deserialize;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Search {
+        id: u64,
+        q: String,
+        page: Option<u32>,
+        exact: bool,
+        #[serde(default)]
+        tags: Vec<String>,
+    }
+
+    #[cfg(feature = "router")]
+    fn params(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[cfg(feature = "router")]
+    #[test]
+    fn test_merges_form_query_and_path() {
+        let args = request_args(
+            Some("application/x-www-form-urlencoded; charset=utf-8"),
+            "q=rust+lang&exact=true&id=1",
+            "page=2&tags=a&tags=b",
+            &params(&[("id", "7")]),
+        )
+        .unwrap();
+
+        let search: Search = from_args(&args.to_string()).unwrap();
+        assert_eq!(
+            search,
+            Search {
+                // Path params win over the body
+                id: 7,
+                q: "rust lang".to_string(),
+                page: Some(2),
+                exact: true,
+                tags: vec!["a".to_string(), "b".to_string()],
+            }
+        );
+    }
+
+    #[cfg(feature = "router")]
+    #[test]
+    fn test_json_body_keeps_its_types() {
+        let args = request_args(
+            Some("application/json"),
+            r#"{"q":"007","exact":false}"#,
+            "id=3",
+            &[],
+        )
+        .unwrap();
+
+        let search: Search = from_args(&args.to_string()).unwrap();
+        assert_eq!(search.id, 3);
+        // Numeric-looking strings stay strings where a string is expected
+        assert_eq!(search.q, "007");
+        assert_eq!(search.page, None);
+        assert!(!search.exact);
+
+        // Without other params, any JSON body passes through
+        let args = request_args(None, "[1,2]", "", &[]).unwrap();
+        assert_eq!(args, serde_json::json!([1, 2]));
+    }
+
+    #[cfg(feature = "router")]
+    #[test]
+    fn test_rejects_unsupported_and_malformed_bodies() {
+        let error = request_args(Some("text/plain"), "hello", "", &[]).unwrap_err();
+        assert_eq!(error.status_code(), 415);
+
+        let error = request_args(Some("application/json"), "{oops", "", &[]).unwrap_err();
+        assert_eq!(error.status_code(), 400);
+
+        let error = request_args(None, "[1]", "page=1", &[]).unwrap_err();
+        assert_eq!(error.status_code(), 400);
+
+        // No body, no content type needed
+        assert!(request_args(Some("text/plain"), "", "q=x", &[]).is_ok());
+    }
+
+    #[test]
+    fn test_from_args_reports_type_errors() {
+        let error = from_args::<Search>(r#"{"id":"abc","q":"x","exact":true}"#).unwrap_err();
+        assert!(error.to_string().contains("invalid type"));
+    }
+
+    #[test]
+    fn test_body_format_from_content_type() {
+        assert_eq!(
+            BodyFormat::from_content_type("Application/JSON; charset=utf-8"),
+            Some(BodyFormat::Json)
+        );
+        assert_eq!(
+            BodyFormat::from_content_type("application/problem+json"),
+            Some(BodyFormat::Json)
+        );
+        assert_eq!(BodyFormat::from_content_type("multipart/form-data"), None);
+    }
+}
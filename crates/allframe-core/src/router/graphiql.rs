@@ -10,6 +10,8 @@
 //! - **Query History**: Persistent query history
 //! - **Variables Editor**: JSON variable editing with validation
 //! - **Headers Configuration**: Custom HTTP headers
+//! - **Default Headers**: Editable headers (e.g., `Authorization`) prefilled in
+//!   the headers editor
 //! - **Subscription Support**: WebSocket subscriptions
 //! - **Schema Explorer**: Interactive schema documentation
 //!
@@ -68,6 +70,9 @@ pub struct GraphiQLConfig {
     /// Custom HTTP headers
     pub headers: HashMap<String, String>,
 
+    /// Headers prefilled in the editable headers editor
+    pub default_headers: HashMap<String, String>,
+
     /// CDN URL for GraphiQL (for version pinning)
     pub cdn_url: String,
 
@@ -84,6 +89,7 @@ impl Default for GraphiQLConfig {
             enable_explorer: true,
             enable_history: true,
             headers: HashMap::new(),
+            default_headers: HashMap::new(),
             cdn_url: "https://unpkg.com/graphiql@3.0.0/graphiql.min.css".to_string(),
             custom_css: None,
         }
@@ -132,6 +138,30 @@ impl GraphiQLConfig {
         self
     }
 
+    /// Prefill the headers editor with default headers
+    ///
+    /// Unlike [`add_header`](Self::add_header), these headers are visible and
+    /// editable in the UI, so users can paste a token before running queries
+    /// against protected endpoints. Nothing leaves the browser except the
+    /// requests the user makes.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    ///
+    /// use allframe_core::router::GraphiQLConfig;
+    ///
+    /// let config = GraphiQLConfig::new().with_default_headers(HashMap::from([(
+    ///     "Authorization".to_string(),
+    ///     "Bearer ".to_string(),
+    /// )]));
+    /// ```
+    pub fn with_default_headers(mut self, headers: HashMap<String, String>) -> Self {
+        self.default_headers.extend(headers);
+        self
+    }
+
     /// Set the CDN URL for version pinning
     pub fn cdn_url(mut self, url: impl Into<String>) -> Self {
         self.cdn_url = url.into();
@@ -161,6 +191,11 @@ impl GraphiQLConfig {
             config["headers"] = serde_json::to_value(&self.headers).unwrap();
         }
 
+        if !self.default_headers.is_empty() {
+            let editor = serde_json::to_string_pretty(&self.default_headers).unwrap();
+            config["defaultHeaders"] = serde_json::Value::String(editor);
+        }
+
         config
     }
 }
@@ -246,6 +281,7 @@ pub fn graphiql_html(config: &GraphiQLConfig, title: &str) -> String {
             React.createElement(GraphiQL, {{
                 fetcher: fetcher,
                 defaultEditorToolsVisibility: config.explorer,
+                defaultHeaders: config.defaultHeaders,
                 storage: config.history ? window.localStorage : null
             }})
         );
@@ -358,4 +394,29 @@ mod tests {
 
         assert!(html.contains("body { background: #1a1a1a; }"));
     }
+
+    #[test]
+    fn test_graphiql_default_headers() {
+        let config = GraphiQLConfig::new().with_default_headers(HashMap::from([(
+            "Authorization".to_string(),
+            "Bearer ".to_string(),
+        )]));
+
+        let json = config.to_json();
+        let editor: HashMap<String, String> =
+            serde_json::from_str(json["defaultHeaders"].as_str().unwrap()).unwrap();
+        assert_eq!(editor.get("Authorization"), Some(&"Bearer ".to_string()));
+        assert!(json.get("headers").is_none());
+
+        let html = graphiql_html(&config, "Test API");
+        assert!(html.contains("defaultHeaders: config.defaultHeaders"));
+        assert!(html.contains("Authorization"));
+    }
+
+    #[test]
+    fn test_graphiql_without_default_headers() {
+        let json = GraphiQLConfig::new().to_json();
+
+        assert!(json.get("defaultHeaders").is_none());
+    }
 }
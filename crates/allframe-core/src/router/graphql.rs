@@ -4,8 +4,89 @@
 
 use std::{future::Future, pin::Pin};
 
+#[cfg(feature = "router")]
+use super::GraphQLConfig;
 use super::ProtocolAdapter;
 
+/// Default maximum selection-set depth of a GraphQL query
+pub const DEFAULT_MAX_QUERY_DEPTH: usize = 15;
+
+/// Default maximum number of field selections in a GraphQL query
+pub const DEFAULT_MAX_QUERY_NODES: usize = 1000;
+
+/// Selection-set depth and number of field selections in a GraphQL query
+///
+/// A lightweight scan that doesn't build an AST: arguments, strings,
+/// comments, aliases, directives and fragment type conditions are skipped.
+pub fn query_depth_and_nodes(query: &str) -> (usize, usize) {
+    let chars: Vec<char> = query.chars().collect();
+    let mut depth = 0usize;
+    let mut max_depth = 0usize;
+    let mut nodes = 0usize;
+    let mut parens = 0usize;
+    let mut after_directive = false;
+    let mut after_spread = false;
+    let mut after_on = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '#' => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '"' => {
+                i += 1;
+                while i < chars.len() && chars[i] != '"' {
+                    if chars[i] == '\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            '{' if parens == 0 => {
+                depth += 1;
+                max_depth = max_depth.max(depth);
+            }
+            '}' if parens == 0 => depth = depth.saturating_sub(1),
+            '(' => parens += 1,
+            ')' => parens = parens.saturating_sub(1),
+            '@' => after_directive = true,
+            '.' => after_spread = true,
+            c if c.is_alphabetic() || c == '_' => {
+                let start = i;
+                while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let ident: String = chars[start..i].iter().collect();
+
+                let mut next = i;
+                while next < chars.len() && chars[next].is_whitespace() {
+                    next += 1;
+                }
+                let is_alias = chars.get(next) == Some(&':');
+                let is_on = after_spread && ident == "on";
+
+                // `... on Type` and `@directive` names aren't field selections
+                if depth > 0 && parens == 0 && !is_alias && !after_directive && !after_on && !is_on
+                {
+                    nodes += 1;
+                }
+                after_directive = false;
+                after_spread = false;
+                after_on = is_on;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+
+    (max_depth, nodes)
+}
+
 /// GraphQL operation type
 #[derive(Debug, Clone, PartialEq)]
 pub enum OperationType {
@@ -46,6 +127,8 @@ impl GraphQLOperation {
 /// Handles GraphQL protocol-specific request/response transformation.
 pub struct GraphQLAdapter {
     operations: Vec<GraphQLOperation>,
+    max_depth: usize,
+    max_nodes: usize,
 }
 
 impl GraphQLAdapter {
@@ -53,7 +136,40 @@ impl GraphQLAdapter {
     pub fn new() -> Self {
         Self {
             operations: Vec::new(),
+            max_depth: DEFAULT_MAX_QUERY_DEPTH,
+            max_nodes: DEFAULT_MAX_QUERY_NODES,
+        }
+    }
+
+    /// Create a GraphQL adapter using the limits from a [`GraphQLConfig`]
+    #[cfg(feature = "router")]
+    pub fn from_config(config: &GraphQLConfig) -> Self {
+        Self::new().with_limits(config.max_query_depth(), config.max_query_nodes())
+    }
+
+    /// Set the maximum query depth and number of field selections
+    pub fn with_limits(mut self, max_depth: usize, max_nodes: usize) -> Self {
+        self.max_depth = max_depth;
+        self.max_nodes = max_nodes;
+        self
+    }
+
+    /// Check a query against the configured depth and node limits
+    pub fn check_limits(&self, query: &str) -> Result<(), String> {
+        let (depth, nodes) = query_depth_and_nodes(query);
+        if depth > self.max_depth {
+            return Err(format!(
+                "Query depth {} exceeds maximum of {}",
+                depth, self.max_depth
+            ));
+        }
+        if nodes > self.max_nodes {
+            return Err(format!(
+                "Query selects {} fields, exceeding maximum of {}",
+                nodes, self.max_nodes
+            ));
         }
+        Ok(())
     }
 
     /// Register a GraphQL query
@@ -208,12 +324,27 @@ impl ProtocolAdapter for GraphQLAdapter {
         "graphql"
     }
 
+    fn handler_references(&self) -> Vec<(String, String)> {
+        self.operations
+            .iter()
+            .map(|op| {
+                let kind = match op.operation_type {
+                    OperationType::Query => "query",
+                    OperationType::Mutation => "mutation",
+                };
+                (format!("{} {}", kind, op.name), op.handler.clone())
+            })
+            .collect()
+    }
+
     fn handle(
         &self,
         request: &str,
     ) -> Pin<Box<dyn Future<Output = Result<String, String>> + Send + '_>> {
-        // Parse query before async block
-        let parse_result = self.parse_query(request);
+        // Reject abusive queries before parsing them
+        let parse_result = self
+            .check_limits(request)
+            .and_then(|_| self.parse_query(request));
         let operations = self.operations.clone();
 
         Box::pin(async move {
@@ -457,6 +588,54 @@ mod tests {
         assert!(schema.contains("mutation: Mutation"));
     }
 
+    #[test]
+    fn test_query_depth_and_nodes() {
+        assert_eq!(query_depth_and_nodes("{ user }"), (1, 1));
+        assert_eq!(
+            query_depth_and_nodes(
+                "query GetUser($id: ID!) { user(id: $id) { name posts { title } } }"
+            ),
+            (3, 4)
+        );
+        // Aliases, directives, fragment type conditions, strings and comments
+        assert_eq!(
+            query_depth_and_nodes(
+                r#"{
+                    me: user(name: "{ not } a { field }") @include(if: true) {
+                        # comment { ignored }
+                        ... on Admin { level }
+                    }
+                }"#
+            ),
+            (3, 2)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handle_rejects_deep_query() {
+        let mut adapter = GraphQLAdapter::new().with_limits(2, 100);
+        adapter.query("user", "get_user");
+
+        let response = adapter
+            .handle("{ user { friends { name } } }")
+            .await
+            .unwrap();
+        assert!(response.contains(r#""errors""#));
+        assert!(response.contains("Query depth 3 exceeds maximum of 2"));
+
+        let response = adapter.handle("{ user { name } }").await.unwrap();
+        assert!(response.contains(r#""data""#));
+    }
+
+    #[tokio::test]
+    async fn test_handle_rejects_too_many_nodes() {
+        let mut adapter = GraphQLAdapter::new().with_limits(10, 3);
+        adapter.query("user", "get_user");
+
+        let response = adapter.handle("{ user { id name email } }").await.unwrap();
+        assert!(response.contains("Query selects 4 fields, exceeding maximum of 3"));
+    }
+
     #[tokio::test]
     async fn test_handle_query_success() {
         let mut adapter = GraphQLAdapter::new();
@@ -3,14 +3,23 @@
 //! This module provides full GraphQL AST parsing, schema introspection,
 //! and resolver system using the async-graphql library.
 
-use std::{future::Future, pin::Pin};
+use std::{collections::HashMap, future::Future, pin::Pin};
 
 #[cfg(feature = "router-graphql")]
 use async_graphql::{
-    http::GraphiQLSource, parser::parse_query, Error as GraphQLError, Request as GraphQLRequest,
+    http::GraphiQLSource,
+    parser::{
+        parse_query,
+        types::{ExecutableDocument, FragmentDefinition, Selection, SelectionSet},
+        Positioned,
+    },
+    Error as GraphQLError, Name, Request as GraphQLRequest, Value,
 };
 
-use super::ProtocolAdapter;
+use super::{GraphQLConfig, ProtocolAdapter};
+
+/// Field arguments whose value multiplies the complexity of the selection
+const LIST_SIZE_ARGUMENTS: &[&str] = &["first", "last", "limit"];
 
 /// Production GraphQL adapter with full AST parsing
 ///
@@ -20,9 +29,28 @@ use super::ProtocolAdapter;
 /// - AST validation and optimization
 /// - GraphiQL playground support
 /// - Schema introspection
+/// - Depth and field-weighted complexity limits, configured through
+///   [`GraphQLConfig`]
+///
+/// Each selected field costs 1 unless overridden with
+/// [`GraphQLConfig::field_cost`]. The cost of a field's sub-selection is
+/// multiplied by its `first`, `last` or `limit` argument, so paginated lists
+/// are charged for every item they can return.
+///
+/// ```rust,ignore
+/// let config = GraphQLConfig::default()
+///     .max_complexity(500)
+///     .field_cost("search", 20)
+///     .disable_introspection();
+/// let adapter = GraphQLProductionAdapter::new("/graphql").with_config(&config);
+/// ```
 #[cfg(feature = "router-graphql")]
 pub struct GraphQLProductionAdapter {
     playground_endpoint: String,
+    max_depth: Option<usize>,
+    max_complexity: Option<usize>,
+    field_costs: HashMap<String, usize>,
+    introspection: bool,
 }
 
 #[cfg(feature = "router-graphql")]
@@ -31,14 +59,132 @@ impl GraphQLProductionAdapter {
     pub fn new(playground_endpoint: impl Into<String>) -> Self {
         Self {
             playground_endpoint: playground_endpoint.into(),
+            max_depth: None,
+            max_complexity: None,
+            field_costs: HashMap::new(),
+            introspection: true,
         }
     }
 
+    /// Apply the depth, complexity and introspection settings of a
+    /// [`GraphQLConfig`]
+    pub fn with_config(mut self, config: &GraphQLConfig) -> Self {
+        self.max_depth = Some(config.max_query_depth());
+        self.max_complexity = config.complexity_limit();
+        self.field_costs = config.field_costs.clone();
+        self.introspection = config.introspection_enabled();
+        self
+    }
+
     /// Parse and validate a GraphQL query
     pub fn parse_query(query: &str) -> Result<(), GraphQLError> {
         parse_query(query).map(|_| ()).map_err(|e| e.into())
     }
 
+    /// Compute the field-weighted complexity of a query
+    ///
+    /// For documents with several operations, the most expensive one counts.
+    pub fn query_complexity(&self, query: &str) -> Result<usize, GraphQLError> {
+        let document = parse_query(query)?;
+        Ok(document
+            .operations
+            .iter()
+            .map(|(_, op)| self.selection_complexity(&op.node.selection_set.node, &document, 0))
+            .max()
+            .unwrap_or(0))
+    }
+
+    /// Check a query against the configured limits
+    ///
+    /// Returns the GraphQL error message for queries that are invalid,
+    /// too deep, too complex or use disabled introspection.
+    pub fn check_query(&self, query: &str) -> Result<(), String> {
+        let document = parse_query(query).map_err(|e| format!("Invalid GraphQL query: {}", e))?;
+
+        for (_, op) in document.operations.iter() {
+            let selection_set = &op.node.selection_set.node;
+
+            if !self.introspection && uses_introspection(selection_set, &document, 0) {
+                return Err("Introspection is disabled".to_string());
+            }
+
+            if let Some(max_depth) = self.max_depth {
+                let depth = selection_depth(selection_set, &document, 0);
+                if depth > max_depth {
+                    return Err(format!(
+                        "Query depth {} exceeds maximum of {}",
+                        depth, max_depth
+                    ));
+                }
+            }
+
+            if let Some(max_complexity) = self.max_complexity {
+                let complexity = self.selection_complexity(selection_set, &document, 0);
+                if complexity > max_complexity {
+                    return Err(format!(
+                        "Query complexity {} exceeds maximum of {}",
+                        complexity, max_complexity
+                    ));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn selection_complexity(
+        &self,
+        selection_set: &SelectionSet,
+        document: &ExecutableDocument,
+        fragment_depth: usize,
+    ) -> usize {
+        selection_set
+            .items
+            .iter()
+            .map(|item| match &item.node {
+                Selection::Field(field) => {
+                    let field = &field.node;
+                    let name = field.name.node.as_str();
+                    if name.starts_with("__") && name != "__schema" && name != "__type" {
+                        return 0;
+                    }
+
+                    let cost = self.field_costs.get(name).copied().unwrap_or(1);
+                    let multiplier = field
+                        .arguments
+                        .iter()
+                        .filter(|(arg, _)| LIST_SIZE_ARGUMENTS.contains(&arg.node.as_str()))
+                        .find_map(|(_, value)| match value.node.clone().into_const() {
+                            Some(Value::Number(n)) => n.as_u64().map(|n| n as usize),
+                            _ => None,
+                        })
+                        .unwrap_or(1);
+                    let children = self.selection_complexity(
+                        &field.selection_set.node,
+                        document,
+                        fragment_depth,
+                    );
+                    cost.saturating_add(children.saturating_mul(multiplier))
+                }
+                Selection::InlineFragment(fragment) => self.selection_complexity(
+                    &fragment.node.selection_set.node,
+                    document,
+                    fragment_depth,
+                ),
+                Selection::FragmentSpread(spread) => {
+                    match fragment(document, &spread.node.fragment_name.node, fragment_depth) {
+                        Some(def) => self.selection_complexity(
+                            &def.selection_set.node,
+                            document,
+                            fragment_depth + 1,
+                        ),
+                        None => 0,
+                    }
+                }
+            })
+            .fold(0, usize::saturating_add)
+    }
+
     /// Get GraphiQL playground HTML
     pub fn graphiql_source(&self) -> String {
         GraphiQLSource::build()
@@ -54,6 +200,86 @@ impl GraphQLProductionAdapter {
     }
 }
 
+/// Maximum fragment nesting followed while walking a query, guarding
+/// against cyclic fragment spreads
+#[cfg(feature = "router-graphql")]
+const MAX_FRAGMENT_DEPTH: usize = 32;
+
+/// Look up a fragment definition, giving up on deeply nested (or cyclic)
+/// spreads
+#[cfg(feature = "router-graphql")]
+fn fragment<'a>(
+    document: &'a ExecutableDocument,
+    name: &Name,
+    fragment_depth: usize,
+) -> Option<&'a FragmentDefinition> {
+    if fragment_depth >= MAX_FRAGMENT_DEPTH {
+        return None;
+    }
+    document.fragments.get(name).map(|def| &def.node)
+}
+
+/// Selection-set depth, counting fields only (fragments are flattened)
+#[cfg(feature = "router-graphql")]
+fn selection_depth(
+    selection_set: &SelectionSet,
+    document: &ExecutableDocument,
+    fragment_depth: usize,
+) -> usize {
+    selection_set
+        .items
+        .iter()
+        .map(|item| match &item.node {
+            Selection::Field(field) => {
+                1 + selection_depth(&field.node.selection_set.node, document, fragment_depth)
+            }
+            Selection::InlineFragment(fragment) => {
+                selection_depth(&fragment.node.selection_set.node, document, fragment_depth)
+            }
+            Selection::FragmentSpread(spread) => {
+                match fragment(document, &spread.node.fragment_name.node, fragment_depth) {
+                    Some(def) => {
+                        selection_depth(&def.selection_set.node, document, fragment_depth + 1)
+                    }
+                    None => 0,
+                }
+            }
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// Whether a selection set queries `__schema` or `__type`
+#[cfg(feature = "router-graphql")]
+fn uses_introspection(
+    selection_set: &SelectionSet,
+    document: &ExecutableDocument,
+    fragment_depth: usize,
+) -> bool {
+    selection_set
+        .items
+        .iter()
+        .any(|item: &Positioned<Selection>| match &item.node {
+            Selection::Field(field) => {
+                let name = field.node.name.node.as_str();
+                name == "__schema"
+                    || name == "__type"
+                    || uses_introspection(&field.node.selection_set.node, document, fragment_depth)
+            }
+            Selection::InlineFragment(fragment) => {
+                uses_introspection(&fragment.node.selection_set.node, document, fragment_depth)
+            }
+            Selection::FragmentSpread(spread) => {
+                match fragment(document, &spread.node.fragment_name.node, fragment_depth) {
+                    Some(def) => {
+                        uses_introspection(&def.selection_set.node, document, fragment_depth + 1)
+                    }
+                    None => false,
+                }
+            }
+        })
+}
+
 #[cfg(feature = "router-graphql")]
 impl ProtocolAdapter for GraphQLProductionAdapter {
     fn name(&self) -> &str {
@@ -72,6 +298,12 @@ impl ProtocolAdapter for GraphQLProductionAdapter {
             // Validate the query syntax
             match Self::parse_query(&request) {
                 Ok(_) => {
+                    // Enforce depth, complexity and introspection limits
+                    if let Err(message) = self.check_query(&request) {
+                        let error = serde_json::json!({ "errors": [{ "message": message }] });
+                        return Ok(error.to_string());
+                    }
+
                     // Query is valid - in production this would execute against a schema
                     Ok(
                         r#"{"data":{"message":"Query parsed and validated successfully"}}"#
@@ -139,6 +371,84 @@ mod tests {
         assert!(adapter.validate_request(&invalid_request).is_err());
     }
 
+    #[test]
+    fn test_query_complexity_with_field_costs() {
+        let config = GraphQLConfig::default().field_cost("posts", 5);
+        let adapter = GraphQLProductionAdapter::new("/graphql").with_config(&config);
+
+        // user(1) + name(1) + posts(5) + title(1)
+        let query = "{ user { name posts { title } } }";
+        assert_eq!(adapter.query_complexity(query).unwrap(), 8);
+
+        // __typename is free
+        let query = "{ user { __typename name } }";
+        assert_eq!(adapter.query_complexity(query).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_query_complexity_list_multiplier_and_fragments() {
+        let adapter = GraphQLProductionAdapter::new("/graphql");
+
+        // users(1) + 10 * (id + name)
+        let query = "{ users(first: 10) { id name } }";
+        assert_eq!(adapter.query_complexity(query).unwrap(), 21);
+
+        let query = r#"
+            query { user { ...UserFields ... on Admin { level } } }
+            fragment UserFields on User { id name }
+        "#;
+        assert_eq!(adapter.query_complexity(query).unwrap(), 4);
+
+        // Cyclic fragments don't recurse forever
+        let query = r#"
+            { user { ...A } }
+            fragment A on User { id ...A }
+        "#;
+        assert!(adapter.query_complexity(query).unwrap() > 1);
+    }
+
+    #[test]
+    fn test_check_query_limits() {
+        let config = GraphQLConfig::default()
+            .max_complexity(10)
+            .disable_introspection();
+        let adapter = GraphQLProductionAdapter::new("/graphql").with_config(&config);
+
+        assert!(adapter.check_query("{ user { name } }").is_ok());
+        assert!(adapter.check_query("{ user { __typename } }").is_ok());
+
+        let err = adapter
+            .check_query("{ users(limit: 100) { id } }")
+            .unwrap_err();
+        assert_eq!(err, "Query complexity 101 exceeds maximum of 10");
+
+        let err = adapter
+            .check_query("{ __schema { types { name } } }")
+            .unwrap_err();
+        assert_eq!(err, "Introspection is disabled");
+
+        let deep = GraphQLConfig {
+            max_query_depth: 2,
+            ..GraphQLConfig::default()
+        };
+        let adapter = GraphQLProductionAdapter::new("/graphql").with_config(&deep);
+        let err = adapter.check_query("{ a { b { c } } }").unwrap_err();
+        assert_eq!(err, "Query depth 3 exceeds maximum of 2");
+    }
+
+    #[tokio::test]
+    async fn test_handle_rejects_expensive_query() {
+        let config = GraphQLConfig::default().max_complexity(5);
+        let adapter = GraphQLProductionAdapter::new("/graphql").with_config(&config);
+
+        let response = adapter.handle("{ users(first: 50) { id } }").await.unwrap();
+        assert!(response.contains(r#""errors""#));
+        assert!(response.contains("Query complexity 51 exceeds maximum of 5"));
+
+        let response = adapter.handle("{ hello }").await.unwrap();
+        assert!(response.contains(r#""data""#));
+    }
+
     #[test]
     fn test_graphiql_source() {
         let adapter = GraphQLProductionAdapter::new("/graphql");
@@ -19,6 +19,29 @@ pub enum GrpcMethodType {
     BidirectionalStreaming,
 }
 
+impl GrpcMethodType {
+    /// Derive the method type from the `client_streaming` / `server_streaming`
+    /// flags of a proto method definition
+    pub fn from_streaming(client_streaming: bool, server_streaming: bool) -> Self {
+        match (client_streaming, server_streaming) {
+            (false, false) => GrpcMethodType::Unary,
+            (true, false) => GrpcMethodType::ClientStreaming,
+            (false, true) => GrpcMethodType::ServerStreaming,
+            (true, true) => GrpcMethodType::BidirectionalStreaming,
+        }
+    }
+
+    /// Get the snake_case name of the method type (e.g., "server_streaming")
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GrpcMethodType::Unary => "unary",
+            GrpcMethodType::ClientStreaming => "client_streaming",
+            GrpcMethodType::ServerStreaming => "server_streaming",
+            GrpcMethodType::BidirectionalStreaming => "bidirectional_streaming",
+        }
+    }
+}
+
 /// gRPC service method definition
 #[derive(Debug, Clone)]
 pub struct GrpcMethod {
@@ -227,6 +250,13 @@ impl ProtocolAdapter for GrpcAdapter {
         "grpc"
     }
 
+    fn handler_references(&self) -> Vec<(String, String)> {
+        self.methods
+            .iter()
+            .map(|method| (method.full_name(), method.handler.clone()))
+            .collect()
+    }
+
     fn handle(
         &self,
         request: &str,
@@ -258,12 +288,7 @@ impl ProtocolAdapter for GrpcAdapter {
                         GrpcStatus::Ok as u32,
                         method.handler,
                         method.full_name(),
-                        match method.method_type {
-                            GrpcMethodType::Unary => "unary",
-                            GrpcMethodType::ClientStreaming => "client_streaming",
-                            GrpcMethodType::ServerStreaming => "server_streaming",
-                            GrpcMethodType::BidirectionalStreaming => "bidirectional_streaming",
-                        }
+                        method.method_type.as_str()
                     );
                     Ok(response)
                 }
@@ -323,6 +348,27 @@ impl GrpcStatus {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_method_type_from_streaming_flags() {
+        assert_eq!(
+            GrpcMethodType::from_streaming(false, false),
+            GrpcMethodType::Unary
+        );
+        assert_eq!(
+            GrpcMethodType::from_streaming(true, false),
+            GrpcMethodType::ClientStreaming
+        );
+        assert_eq!(
+            GrpcMethodType::from_streaming(false, true),
+            GrpcMethodType::ServerStreaming
+        );
+        assert_eq!(
+            GrpcMethodType::from_streaming(true, true),
+            GrpcMethodType::BidirectionalStreaming
+        );
+        assert_eq!(GrpcMethodType::ServerStreaming.as_str(), "server_streaming");
+    }
+
     #[test]
     fn test_grpc_adapter_creation() {
         let adapter = GrpcAdapter::new();
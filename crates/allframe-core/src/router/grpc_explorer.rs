@@ -11,6 +11,8 @@
 //! - **Stream Testing**: Test server/client/bidirectional streams
 //! - **Request Builder**: Build and send gRPC requests with JSON
 //! - **Proto Viewer**: View service definitions
+//! - **Invocation**: Call unary methods through a server-side JSON bridge
+//!   (see `GrpcJsonBridge`, `router-grpc-json` feature)
 //!
 //! # Example
 //!
@@ -67,6 +69,12 @@ pub struct GrpcExplorerConfig {
 
     /// Request timeout in seconds
     pub timeout_seconds: u32,
+
+    /// URL of a JSON invoke endpoint (e.g., served by `GrpcJsonBridge`)
+    ///
+    /// When set, the explorer lists services from `GET {invoke_url}` and
+    /// calls unary methods with `POST {invoke_url}`, showing responses inline.
+    pub invoke_url: Option<String>,
 }
 
 impl Default for GrpcExplorerConfig {
@@ -79,6 +87,7 @@ impl Default for GrpcExplorerConfig {
             headers: HashMap::new(),
             custom_css: None,
             timeout_seconds: 30,
+            invoke_url: None,
         }
     }
 }
@@ -131,6 +140,12 @@ impl GrpcExplorerConfig {
         self
     }
 
+    /// Set the JSON invoke endpoint, making methods callable from the UI
+    pub fn invoke_url(mut self, url: impl Into<String>) -> Self {
+        self.invoke_url = Some(url.into());
+        self
+    }
+
     /// Convert configuration to JSON for embedding in HTML
     pub fn to_json(&self) -> serde_json::Value {
         let mut config = serde_json::json!({
@@ -145,6 +160,10 @@ impl GrpcExplorerConfig {
             config["headers"] = serde_json::to_value(&self.headers).unwrap();
         }
 
+        if let Some(url) = &self.invoke_url {
+            config["invokeUrl"] = serde_json::Value::String(url.clone());
+        }
+
         config
     }
 }
@@ -326,6 +345,54 @@ pub fn grpc_explorer_html(config: &GrpcExplorerConfig, title: &str) -> String {
             align-items: center;
         }}
 
+        .method-item {{
+            padding: 0.4rem 0.75rem;
+            margin: 0.25rem 0 0.25rem 0.5rem;
+            border-radius: 0.375rem;
+            cursor: pointer;
+            font-size: 0.875rem;
+        }}
+
+        .method-item:hover {{
+            background: {item_hover_bg};
+        }}
+
+        .method-type {{
+            font-size: 0.75rem;
+            opacity: 0.6;
+            margin-left: 0.5rem;
+        }}
+
+        .invoke-panel textarea,
+        .invoke-panel pre {{
+            width: 100%;
+            min-height: 12rem;
+            margin-top: 0.5rem;
+            padding: 0.75rem;
+            font-family: ui-monospace, SFMono-Regular, Menlo, monospace;
+            font-size: 0.875rem;
+            background: {item_bg};
+            color: {text_color};
+            border: 1px solid {border_color};
+            border-radius: 0.5rem;
+            white-space: pre-wrap;
+        }}
+
+        .invoke-panel button {{
+            margin-top: 0.75rem;
+            padding: 0.5rem 1.25rem;
+            background: {accent_color};
+            color: white;
+            border: none;
+            border-radius: 0.375rem;
+            cursor: pointer;
+        }}
+
+        .invoke-panel button:disabled {{
+            opacity: 0.5;
+            cursor: not-allowed;
+        }}
+
         .features-list li:before {{
             content: "✓";
             color: {accent_color};
@@ -386,13 +453,98 @@ pub fn grpc_explorer_html(config: &GrpcExplorerConfig, title: &str) -> String {
     <script>
         const config = {config_json};
 
-        // Placeholder for future gRPC Web client integration
-        // This will be enhanced with actual gRPC-Web support in future iterations
-
         console.log('gRPC Explorer Config:', config);
 
-        // Simulate service loading
-        setTimeout(() => {{
+        function escapeHtml(text) {{
+            const div = document.createElement('div');
+            div.textContent = text;
+            return div.innerHTML;
+        }}
+
+        // Render the invoke panel for a single method
+        function showMethod(method) {{
+            const detail = document.getElementById('service-detail');
+            const unary = method.type === 'unary';
+            detail.innerHTML = `
+                <div class="invoke-panel">
+                    <h2>${{escapeHtml(method.fullName)}}<span class="method-type">${{method.type}}</span></h2>
+                    <p style="margin-top: 0.5rem; opacity: 0.8;">${{escapeHtml(method.input)}} &rarr; ${{escapeHtml(method.output)}}</p>
+                    <textarea id="invoke-request" spellcheck="false"></textarea>
+                    <button id="invoke-button" ${{unary ? '' : 'disabled'}}>Invoke</button>
+                    ${{unary ? '' : '<p style="margin-top: 0.5rem;">Streaming methods cannot be invoked from the browser.</p>'}}
+                    <pre id="invoke-response"></pre>
+                </div>
+            `;
+            document.getElementById('invoke-request').value = JSON.stringify(method.example || {{}}, null, 2);
+            document.getElementById('invoke-button').onclick = () => invoke(method);
+        }}
+
+        // Call a unary method through the server-side JSON bridge
+        async function invoke(method) {{
+            const output = document.getElementById('invoke-response');
+            let request;
+            try {{
+                request = JSON.parse(document.getElementById('invoke-request').value || '{{}}');
+            }} catch (e) {{
+                output.textContent = 'Invalid JSON: ' + e.message;
+                return;
+            }}
+            output.textContent = 'Calling...';
+            const controller = new AbortController();
+            const timer = setTimeout(() => controller.abort(), config.timeout * 1000);
+            try {{
+                const res = await fetch(config.invokeUrl, {{
+                    method: 'POST',
+                    headers: {{ 'Content-Type': 'application/json' }},
+                    body: JSON.stringify({{ method: method.fullName, request, metadata: config.headers || {{}} }}),
+                    signal: controller.signal,
+                }});
+                const body = await res.json();
+                output.textContent = body.error
+                    ? `${{body.error.status}} (${{body.error.code}}): ${{body.error.message}}`
+                    : JSON.stringify(body.response, null, 2);
+            }} catch (e) {{
+                output.textContent = 'Request failed: ' + e.message;
+            }} finally {{
+                clearTimeout(timer);
+            }}
+        }}
+
+        // Load services from the invoke endpoint
+        async function loadServices() {{
+            const serviceList = document.getElementById('service-list');
+            const serverInfo = document.getElementById('server-info');
+            try {{
+                const res = await fetch(config.invokeUrl);
+                const listing = await res.json();
+                serviceList.innerHTML = '';
+                listing.services.forEach((service) => {{
+                    const item = document.createElement('div');
+                    item.className = 'service-item';
+                    item.innerHTML = `<div class="service-name">${{escapeHtml(service.name)}}</div>
+                        <div class="method-count">${{service.methods.length}} methods</div>`;
+                    service.methods.forEach((method) => {{
+                        const entry = document.createElement('div');
+                        entry.className = 'method-item';
+                        entry.innerHTML = `${{escapeHtml(method.name)}}<span class="method-type">${{method.type}}</span>`;
+                        entry.onclick = (event) => {{
+                            event.stopPropagation();
+                            showMethod(method);
+                        }};
+                        item.appendChild(entry);
+                    }});
+                    serviceList.appendChild(item);
+                }});
+                serverInfo.textContent = `Connected to ${{config.serverUrl}} via ${{config.invokeUrl}}`;
+            }} catch (e) {{
+                serviceList.innerHTML = `<div class="error"><strong>Failed to load services</strong><p style="margin-top: 0.5rem;">${{escapeHtml(e.message)}}</p></div>`;
+                serverInfo.textContent = `Server: ${{config.serverUrl}} (invoke endpoint unavailable)`;
+            }}
+        }}
+
+        if (config.invokeUrl) {{
+            loadServices();
+        }} else setTimeout(() => {{
             const serviceList = document.getElementById('service-list');
             const serverInfo = document.getElementById('server-info');
 
@@ -577,6 +729,20 @@ mod tests {
         assert_eq!(json["headers"]["X-API-Key"], "secret");
     }
 
+    #[test]
+    fn test_grpc_explorer_invoke_url() {
+        let config = GrpcExplorerConfig::new();
+        assert!(config.to_json().get("invokeUrl").is_none());
+        assert!(!grpc_explorer_html(&config, "API").contains("\"invokeUrl\""));
+
+        let config = config.invoke_url("/grpc/invoke");
+        assert_eq!(config.to_json()["invokeUrl"], "/grpc/invoke");
+
+        let html = grpc_explorer_html(&config, "API");
+        assert!(html.contains(r#""invokeUrl":"/grpc/invoke""#));
+        assert!(html.contains("async function invoke(method)"));
+    }
+
     #[test]
     fn test_grpc_explorer_custom_css() {
         let config = GrpcExplorerConfig::new().custom_css("body { background: #000; }");
@@ -0,0 +1,676 @@
+//! JSON to protobuf bridge for gRPC
+//!
+//! Lets browser tools call gRPC methods with plain JSON. The bridge reads the
+//! same encoded `FileDescriptorSet` used for gRPC reflection, converts the JSON
+//! request into a protobuf message, performs the unary call over a tonic
+//! [`Channel`], and converts the response back to JSON (proto3 JSON mapping).
+//!
+//! Mount [`GrpcJsonBridge::handle_get`] and [`GrpcJsonBridge::handle_post`] on
+//! an HTTP route and point [`GrpcExplorerConfig::invoke_url`] at it to make
+//! the gRPC explorer invokable.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use allframe_core::router::{grpc_explorer_html, GrpcExplorerConfig, GrpcJsonBridge};
+//!
+//! const DESCRIPTORS: &[u8] = tonic::include_file_descriptor_set!("users_descriptor");
+//!
+//! let bridge = GrpcJsonBridge::connect_lazy(DESCRIPTORS, "http://localhost:50051")?;
+//!
+//! // GET  /grpc/invoke -> bridge.handle_get()
+//! // POST /grpc/invoke -> bridge.handle_post(&body).await
+//! let html = grpc_explorer_html(
+//!     &GrpcExplorerConfig::new().invoke_url("/grpc/invoke"),
+//!     "Users API",
+//! );
+//! ```
+//!
+//! [`GrpcExplorerConfig::invoke_url`]: super::GrpcExplorerConfig::invoke_url
+
+use std::{collections::HashMap, pin::Pin};
+
+use futures_core::Stream;
+use prost::Message;
+use prost_reflect::{
+    DescriptorPool, DynamicMessage, MessageDescriptor, MethodDescriptor, SerializeOptions,
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio_stream::StreamExt;
+use tonic::{
+    codec::{Codec, DecodeBuf, Decoder, EncodeBuf, Encoder},
+    codegen::http::uri::PathAndQuery,
+    metadata::{MetadataKey, MetadataValue},
+    transport::Channel,
+    Code, Status,
+};
+
+use super::{GrpcMethodType, RequestId};
+
+/// Bridge that invokes unary gRPC methods with JSON payloads
+///
+/// Cloning is cheap; clones share the descriptor pool and channel.
+#[derive(Debug, Clone)]
+pub struct GrpcJsonBridge {
+    pool: DescriptorPool,
+    channel: Channel,
+}
+
+/// Body of an invoke request sent to [`GrpcJsonBridge::handle_post`]
+#[derive(Debug, Deserialize)]
+struct InvokeRequest {
+    method: String,
+    #[serde(default)]
+    request: Value,
+    #[serde(default)]
+    metadata: HashMap<String, String>,
+}
+
+impl GrpcJsonBridge {
+    /// Create a bridge from an encoded `FileDescriptorSet` and a channel
+    pub fn new(descriptor_set: &[u8], channel: Channel) -> Result<Self, String> {
+        let pool = DescriptorPool::decode(descriptor_set)
+            .map_err(|e| format!("Failed to decode file descriptor set: {}", e))?;
+        Ok(Self { pool, channel })
+    }
+
+    /// Create a bridge that connects to `url` on first use
+    pub fn connect_lazy(descriptor_set: &[u8], url: impl Into<String>) -> Result<Self, String> {
+        let channel = Channel::from_shared(url.into())
+            .map_err(|e| format!("Invalid gRPC server URL: {}", e))?
+            .connect_lazy();
+        Self::new(descriptor_set, channel)
+    }
+
+    /// The descriptors the bridge was created with
+    pub(crate) fn pool(&self) -> &DescriptorPool {
+        &self.pool
+    }
+
+    /// Look up a method by `package.Service/Method` or `package.Service.Method`
+    pub fn method(&self, name: &str) -> Option<MethodDescriptor> {
+        let name = name.trim_start_matches('/');
+        let (service, method) = name.rsplit_once('/').or_else(|| name.rsplit_once('.'))?;
+        self.pool
+            .get_service_by_name(service)?
+            .methods()
+            .find(|m| m.name() == method)
+    }
+
+    /// Describe all services and methods as JSON for the explorer UI
+    ///
+    /// Each method includes its streaming type and an example request with
+    /// every field set to its default value.
+    pub fn services(&self) -> Value {
+        let services: Vec<Value> = self
+            .pool
+            .services()
+            .map(|service| {
+                let methods: Vec<Value> = service
+                    .methods()
+                    .map(|method| {
+                        let method_type = GrpcMethodType::from_streaming(
+                            method.is_client_streaming(),
+                            method.is_server_streaming(),
+                        );
+                        json!({
+                            "name": method.name(),
+                            "fullName": format!("{}/{}", service.full_name(), method.name()),
+                            "type": method_type.as_str(),
+                            "input": method.input().full_name(),
+                            "output": method.output().full_name(),
+                            "example": to_json(&DynamicMessage::new(method.input())),
+                        })
+                    })
+                    .collect();
+                json!({ "name": service.full_name(), "methods": methods })
+            })
+            .collect();
+        json!({ "services": services })
+    }
+
+    /// Invoke a unary method with a JSON request, returning the JSON response
+    ///
+    /// `metadata` entries are sent as ASCII request metadata (headers).
+    pub async fn invoke_unary(
+        &self,
+        method: &str,
+        request: Value,
+        metadata: &HashMap<String, String>,
+    ) -> Result<Value, Status> {
+        let descriptor = self
+            .method(method)
+            .ok_or_else(|| Status::unimplemented(format!("Unknown method: {}", method)))?;
+        if descriptor.is_client_streaming() || descriptor.is_server_streaming() {
+            return Err(Status::unimplemented(format!(
+                "{} is a streaming method; only unary methods can be invoked",
+                descriptor.full_name()
+            )));
+        }
+
+        let (request, path) = grpc_request(&descriptor, request, metadata)?;
+        let response = self
+            .client()
+            .await?
+            .unary(request, path, DynamicCodec(descriptor.output()))
+            .await?;
+
+        Ok(to_json(response.get_ref()))
+    }
+
+    /// Invoke a server-streaming method with a JSON request, returning the
+    /// JSON responses as they arrive
+    ///
+    /// Fails before returning the stream if the call can't be started; an
+    /// error partway through ends the stream with that error.
+    pub async fn invoke_server_streaming(
+        &self,
+        method: &str,
+        request: Value,
+        metadata: &HashMap<String, String>,
+    ) -> Result<JsonStream, Status> {
+        let descriptor = self
+            .method(method)
+            .ok_or_else(|| Status::unimplemented(format!("Unknown method: {}", method)))?;
+        if descriptor.is_client_streaming() || !descriptor.is_server_streaming() {
+            return Err(Status::unimplemented(format!(
+                "{} is not a server-streaming method",
+                descriptor.full_name()
+            )));
+        }
+
+        let (request, path) = grpc_request(&descriptor, request, metadata)?;
+        let responses = self
+            .client()
+            .await?
+            .server_streaming(request, path, DynamicCodec(descriptor.output()))
+            .await?
+            .into_inner();
+
+        Ok(Box::pin(
+            responses.map(|message| message.map(|m| to_json(&m))),
+        ))
+    }
+
+    async fn client(&self) -> Result<tonic::client::Grpc<Channel>, Status> {
+        let mut client = tonic::client::Grpc::new(self.channel.clone());
+        client
+            .ready()
+            .await
+            .map_err(|e| Status::unavailable(format!("Service unavailable: {}", e)))?;
+        Ok(client)
+    }
+
+    /// Handle `GET` on the invoke endpoint: the JSON service listing
+    pub fn handle_get(&self) -> String {
+        self.services().to_string()
+    }
+
+    /// Handle `POST` on the invoke endpoint
+    ///
+    /// Expects `{"method": "pkg.Service/Method", "request": {...},
+    /// "metadata": {...}}` and returns `{"response": {...}}` on success or
+    /// `{"error": {"code": 5, "status": "NotFound", "message": "..."}}`.
+    pub async fn handle_post(&self, body: &str) -> String {
+        let result = match serde_json::from_str::<InvokeRequest>(body) {
+            Ok(invoke) => {
+                self.invoke_unary(&invoke.method, invoke.request, &invoke.metadata)
+                    .await
+            }
+            Err(e) => Err(Status::invalid_argument(format!(
+                "Invalid invoke request: {}",
+                e
+            ))),
+        };
+
+        match result {
+            Ok(response) => json!({ "response": response }).to_string(),
+            Err(status) => status_json(&status).to_string(),
+        }
+    }
+}
+
+/// JSON messages streamed back by [`GrpcJsonBridge::invoke_server_streaming`]
+pub type JsonStream = Pin<Box<dyn Stream<Item = Result<Value, Status>> + Send>>;
+
+/// `{"error": {"code": 5, "status": "NotFound", "message": "..."}}`
+pub(crate) fn status_json(status: &Status) -> Value {
+    json!({
+        "error": {
+            "code": status.code() as i32,
+            "status": format!("{:?}", status.code()),
+            "message": status.message(),
+        }
+    })
+}
+
+/// Build the request and path for calling `descriptor` with a JSON request
+fn grpc_request(
+    descriptor: &MethodDescriptor,
+    request: Value,
+    metadata: &HashMap<String, String>,
+) -> Result<(tonic::Request<DynamicMessage>, PathAndQuery), Status> {
+    let message = from_json(descriptor.input(), request)?;
+
+    let mut request = tonic::Request::new(message);
+    for (key, value) in metadata {
+        let key = MetadataKey::from_bytes(key.to_ascii_lowercase().as_bytes())
+            .map_err(|_| Status::invalid_argument(format!("Invalid metadata key: {}", key)))?;
+        let value = MetadataValue::try_from(value.as_str())
+            .map_err(|_| Status::invalid_argument(format!("Invalid metadata value for {}", key)))?;
+        request.metadata_mut().insert(key, value);
+    }
+    // Forward the caller's correlation id unless the metadata sets one
+    if let Some(id) = RequestId::current() {
+        if RequestId::from_metadata(request.metadata()).is_none() {
+            id.inject_metadata(request.metadata_mut());
+        }
+    }
+
+    let path = PathAndQuery::try_from(format!(
+        "/{}/{}",
+        descriptor.parent_service().full_name(),
+        descriptor.name()
+    ))
+    .map_err(|e| Status::internal(format!("Invalid method path: {}", e)))?;
+
+    Ok((request, path))
+}
+
+fn from_json(descriptor: MessageDescriptor, value: Value) -> Result<DynamicMessage, Status> {
+    let value = if value.is_null() { json!({}) } else { value };
+    DynamicMessage::deserialize(descriptor, value)
+        .map_err(|e| Status::invalid_argument(format!("Invalid request message: {}", e)))
+}
+
+fn to_json(message: &DynamicMessage) -> Value {
+    let options = SerializeOptions::new().skip_default_fields(false);
+    message
+        .serialize_with_options(serde_json::value::Serializer, &options)
+        .unwrap_or(Value::Null)
+}
+
+/// Codec encoding and decoding [`DynamicMessage`]s of a known output type
+struct DynamicCodec(MessageDescriptor);
+
+struct DynamicEncoder;
+
+struct DynamicDecoder(MessageDescriptor);
+
+impl Codec for DynamicCodec {
+    type Encode = DynamicMessage;
+    type Decode = DynamicMessage;
+    type Encoder = DynamicEncoder;
+    type Decoder = DynamicDecoder;
+
+    fn encoder(&mut self) -> Self::Encoder {
+        DynamicEncoder
+    }
+
+    fn decoder(&mut self) -> Self::Decoder {
+        DynamicDecoder(self.0.clone())
+    }
+}
+
+impl Encoder for DynamicEncoder {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn encode(&mut self, item: Self::Item, dst: &mut EncodeBuf<'_>) -> Result<(), Self::Error> {
+        item.encode(dst)
+            .map_err(|e| Status::internal(format!("Failed to encode message: {}", e)))
+    }
+}
+
+impl Decoder for DynamicDecoder {
+    type Item = DynamicMessage;
+    type Error = Status;
+
+    fn decode(&mut self, src: &mut DecodeBuf<'_>) -> Result<Option<Self::Item>, Self::Error> {
+        DynamicMessage::decode(self.0.clone(), src)
+            .map(Some)
+            .map_err(|e| Status::new(Code::Internal, format!("Failed to decode message: {}", e)))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use prost_types::{
+        field_descriptor_proto::{Label, Type},
+        DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+        MethodDescriptorProto, ServiceDescriptorProto,
+    };
+
+    use super::*;
+
+    pub(crate) fn descriptor_set() -> Vec<u8> {
+        let field = |name: &str, json_name: &str, number: i32, ty: Type| FieldDescriptorProto {
+            name: Some(name.to_string()),
+            json_name: Some(json_name.to_string()),
+            number: Some(number),
+            label: Some(Label::Optional as i32),
+            r#type: Some(ty as i32),
+            ..Default::default()
+        };
+        let method = |name: &str, server_streaming: bool| MethodDescriptorProto {
+            name: Some(name.to_string()),
+            input_type: Some(".greet.v1.HelloRequest".to_string()),
+            output_type: Some(".greet.v1.HelloReply".to_string()),
+            server_streaming: Some(server_streaming),
+            ..Default::default()
+        };
+
+        let set = FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                name: Some("greet.proto".to_string()),
+                package: Some("greet.v1".to_string()),
+                message_type: vec![
+                    DescriptorProto {
+                        name: Some("HelloRequest".to_string()),
+                        field: vec![
+                            field("name", "name", 1, Type::String),
+                            field("times", "times", 2, Type::Int32),
+                        ],
+                        ..Default::default()
+                    },
+                    DescriptorProto {
+                        name: Some("HelloReply".to_string()),
+                        field: vec![field("message", "message", 1, Type::String)],
+                        ..Default::default()
+                    },
+                ],
+                service: vec![ServiceDescriptorProto {
+                    name: Some("Greeter".to_string()),
+                    method: vec![method("SayHello", false), method("StreamHellos", true)],
+                    ..Default::default()
+                }],
+                syntax: Some("proto3".to_string()),
+                ..Default::default()
+            }],
+        };
+        set.encode_to_vec()
+    }
+
+    fn bridge() -> GrpcJsonBridge {
+        // Port 9 (discard) is never listening, so calls fail fast if attempted
+        GrpcJsonBridge::connect_lazy(&descriptor_set(), "http://127.0.0.1:9").unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_method_lookup() {
+        let bridge = bridge();
+        assert!(bridge.method("greet.v1.Greeter/SayHello").is_some());
+        assert!(bridge.method("/greet.v1.Greeter/SayHello").is_some());
+        assert!(bridge.method("greet.v1.Greeter.SayHello").is_some());
+        assert!(bridge.method("greet.v1.Greeter/Missing").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_services_listing() {
+        let listing: Value = serde_json::from_str(&bridge().handle_get()).unwrap();
+        let service = &listing["services"][0];
+
+        assert_eq!(service["name"], "greet.v1.Greeter");
+        assert_eq!(
+            service["methods"][0]["fullName"],
+            "greet.v1.Greeter/SayHello"
+        );
+        assert_eq!(service["methods"][0]["type"], "unary");
+        assert_eq!(
+            service["methods"][0]["example"],
+            json!({"name": "", "times": 0})
+        );
+        assert_eq!(service["methods"][1]["type"], "server_streaming");
+    }
+
+    #[tokio::test]
+    async fn test_json_round_trip() {
+        let bridge = bridge();
+        let input = bridge.method("greet.v1.Greeter/SayHello").unwrap().input();
+
+        let message = from_json(input.clone(), json!({"name": "Ada", "times": 2})).unwrap();
+        let decoded =
+            DynamicMessage::decode(input.clone(), message.encode_to_vec().as_slice()).unwrap();
+        assert_eq!(to_json(&decoded), json!({"name": "Ada", "times": 2}));
+
+        let err = from_json(input, json!({"times": "many"})).unwrap_err();
+        assert_eq!(err.code(), Code::InvalidArgument);
+    }
+
+    #[tokio::test]
+    async fn test_grpc_request_forwards_request_id() {
+        let bridge = bridge();
+        let method = bridge.method("greet.v1.Greeter/SayHello").unwrap();
+
+        let (request, _) = RequestId::parse("req-7")
+            .unwrap()
+            .scope(async { grpc_request(&method, json!({}), &HashMap::new()) })
+            .await
+            .unwrap();
+        assert_eq!(
+            RequestId::from_metadata(request.metadata())
+                .unwrap()
+                .as_str(),
+            "req-7"
+        );
+
+        let explicit = HashMap::from([("X-Request-Id".to_string(), "mine".to_string())]);
+        let (request, _) = RequestId::parse("req-7")
+            .unwrap()
+            .scope(async { grpc_request(&method, json!({}), &explicit) })
+            .await
+            .unwrap();
+        assert_eq!(
+            RequestId::from_metadata(request.metadata())
+                .unwrap()
+                .as_str(),
+            "mine"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handle_post_errors() {
+        let bridge = bridge();
+
+        let response: Value = serde_json::from_str(&bridge.handle_post("not json").await).unwrap();
+        assert_eq!(response["error"]["status"], "InvalidArgument");
+
+        let response: Value = serde_json::from_str(
+            &bridge
+                .handle_post(r#"{"method": "greet.v1.Greeter/Missing"}"#)
+                .await,
+        )
+        .unwrap();
+        assert_eq!(response["error"]["code"], Code::Unimplemented as i32);
+
+        let response: Value = serde_json::from_str(
+            &bridge
+                .handle_post(r#"{"method": "greet.v1.Greeter/StreamHellos", "request": {}}"#)
+                .await,
+        )
+        .unwrap();
+        assert!(response["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("streaming method"));
+    }
+
+    /// Greeter server implemented with the dynamic codec
+    #[derive(Clone)]
+    struct Greeter(DescriptorPool);
+
+    impl tonic::server::NamedService for Greeter {
+        const NAME: &'static str = "greet.v1.Greeter";
+    }
+
+    struct SayHello(MessageDescriptor);
+
+    impl tonic::server::UnaryService<DynamicMessage> for SayHello {
+        type Response = DynamicMessage;
+        type Future = tonic::codegen::BoxFuture<tonic::Response<DynamicMessage>, Status>;
+
+        fn call(&mut self, request: tonic::Request<DynamicMessage>) -> Self::Future {
+            let reply = self.0.clone();
+            Box::pin(async move {
+                let user = request
+                    .metadata()
+                    .get("x-user")
+                    .and_then(|v| v.to_str().ok())
+                    .unwrap_or("anonymous")
+                    .to_string();
+                let input = to_json(request.get_ref());
+                let message = format!(
+                    "Hello {} x{} (from {})",
+                    input["name"].as_str().unwrap(),
+                    input["times"],
+                    user
+                );
+                Ok(tonic::Response::new(
+                    from_json(reply, json!({ "message": message })).unwrap(),
+                ))
+            })
+        }
+    }
+
+    impl tonic::codegen::Service<tonic::codegen::http::Request<tonic::body::Body>> for Greeter {
+        type Response = tonic::codegen::http::Response<tonic::body::Body>;
+        type Error = std::convert::Infallible;
+        type Future = tonic::codegen::BoxFuture<Self::Response, Self::Error>;
+
+        fn poll_ready(
+            &mut self,
+            _cx: &mut std::task::Context<'_>,
+        ) -> std::task::Poll<Result<(), Self::Error>> {
+            std::task::Poll::Ready(Ok(()))
+        }
+
+        fn call(
+            &mut self,
+            request: tonic::codegen::http::Request<tonic::body::Body>,
+        ) -> Self::Future {
+            let input = self.0.get_message_by_name("greet.v1.HelloRequest").unwrap();
+            let output = self.0.get_message_by_name("greet.v1.HelloReply").unwrap();
+            Box::pin(async move {
+                let mut grpc = tonic::server::Grpc::new(DynamicCodec(input));
+                if request.uri().path().ends_with("/StreamHellos") {
+                    Ok(grpc.server_streaming(StreamHellos(output), request).await)
+                } else {
+                    Ok(grpc.unary(SayHello(output), request).await)
+                }
+            })
+        }
+    }
+
+    /// Replies with `times` numbered hellos, failing on a name of "error"
+    struct StreamHellos(MessageDescriptor);
+
+    impl tonic::server::ServerStreamingService<DynamicMessage> for StreamHellos {
+        type Response = DynamicMessage;
+        type ResponseStream = ReplyStream;
+        type Future = tonic::codegen::BoxFuture<tonic::Response<Self::ResponseStream>, Status>;
+
+        fn call(&mut self, request: tonic::Request<DynamicMessage>) -> Self::Future {
+            let reply = self.0.clone();
+            Box::pin(async move {
+                let input = to_json(request.get_ref());
+                let name = input["name"].as_str().unwrap().to_string();
+                let times = input["times"].as_i64().unwrap();
+                let mut replies: Vec<Result<DynamicMessage, Status>> = (1..=times)
+                    .map(|i| {
+                        let message = format!("Hello {} #{}", name, i);
+                        Ok(from_json(reply.clone(), json!({ "message": message })).unwrap())
+                    })
+                    .collect();
+                if name == "error" {
+                    replies.push(Err(Status::data_loss("stream broke")));
+                }
+                let stream: Self::ResponseStream = Box::pin(tokio_stream::iter(replies));
+                Ok(tonic::Response::new(stream))
+            })
+        }
+    }
+
+    type ReplyStream = Pin<Box<dyn Stream<Item = Result<DynamicMessage, Status>> + Send>>;
+
+    /// Start a Greeter server, returning its address
+    pub(crate) async fn serve_greeter() -> std::net::SocketAddr {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let pool = DescriptorPool::decode(descriptor_set().as_slice()).unwrap();
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(Greeter(pool))
+                .serve_with_incoming(tonic::transport::server::TcpIncoming::from(listener)),
+        );
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_invoke_unary_end_to_end() {
+        let addr = serve_greeter().await;
+        let bridge =
+            GrpcJsonBridge::connect_lazy(&descriptor_set(), format!("http://{}", addr)).unwrap();
+        let response: Value = serde_json::from_str(
+            &bridge
+                .handle_post(
+                    r#"{"method": "greet.v1.Greeter/SayHello",
+                        "request": {"name": "Ada", "times": 2},
+                        "metadata": {"X-User": "grace"}}"#,
+                )
+                .await,
+        )
+        .unwrap();
+
+        assert_eq!(
+            response,
+            json!({"response": {"message": "Hello Ada x2 (from grace)"}})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invoke_unreachable_server() {
+        let response: Value = serde_json::from_str(
+            &bridge()
+                .handle_post(
+                    r#"{"method": "greet.v1.Greeter/SayHello", "request": {"name": "Ada"}}"#,
+                )
+                .await,
+        )
+        .unwrap();
+        assert_eq!(response["error"]["status"], "Unavailable");
+    }
+
+    #[tokio::test]
+    async fn test_invoke_server_streaming() {
+        let addr = serve_greeter().await;
+        let bridge =
+            GrpcJsonBridge::connect_lazy(&descriptor_set(), format!("http://{}", addr)).unwrap();
+
+        let replies: Vec<Value> = bridge
+            .invoke_server_streaming(
+                "greet.v1.Greeter/StreamHellos",
+                json!({"name": "Ada", "times": 2}),
+                &HashMap::new(),
+            )
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(
+            replies,
+            [
+                json!({"message": "Hello Ada #1"}),
+                json!({"message": "Hello Ada #2"})
+            ]
+        );
+
+        let err = bridge
+            .invoke_server_streaming("greet.v1.Greeter/SayHello", json!({}), &HashMap::new())
+            .await
+            .err()
+            .unwrap();
+        assert_eq!(err.code(), Code::Unimplemented);
+    }
+}
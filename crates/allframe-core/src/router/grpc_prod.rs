@@ -210,12 +210,392 @@ pub mod status {
     pub fn internal(message: impl Into<String>) -> Status {
         Status::new(Code::Internal, message)
     }
+
+    /// Map an arbitrary error to a status with sensible defaults
+    ///
+    /// Walks the error and its `source()` chain:
+    /// - a wrapped [`Status`] is returned as-is
+    /// - `std::io::Error` kinds map to their gRPC counterparts (e.g.
+    ///   `TimedOut` → `DEADLINE_EXCEEDED`, `NotFound` → `NOT_FOUND`,
+    ///   `ConnectionRefused` → `UNAVAILABLE`)
+    /// - `tokio::time::error::Elapsed` → `DEADLINE_EXCEEDED`
+    ///
+    /// Otherwise the message decides: "timed out"/"timeout"/"deadline" →
+    /// `DEADLINE_EXCEEDED`, "not found"/"no such" → `NOT_FOUND`, and
+    /// everything else is `INTERNAL`. The status message is the error's
+    /// `Display` output.
+    ///
+    /// Works with `anyhow::Error` via `err.as_ref()`:
+    ///
+    /// ```rust,ignore
+    /// async fn get_user(&self, req: Request<GetUserRequest>) -> Result<Response<User>, Status> {
+    ///     let user = self.repo.find(&req.get_ref().id).await.map_err(|e| status::from_error(e.as_ref()))?;
+    ///     Ok(Response::new(user))
+    /// }
+    /// ```
+    pub fn from_error(err: &(dyn std::error::Error + 'static)) -> Status {
+        let message = err.to_string();
+
+        let mut current = Some(err);
+        while let Some(e) = current {
+            if let Some(status) = e.downcast_ref::<Status>() {
+                return status.clone();
+            }
+            if let Some(io) = e.downcast_ref::<std::io::Error>() {
+                if let Some(code) = io_code(io.kind()) {
+                    return Status::new(code, message);
+                }
+            }
+            if e.is::<tokio::time::error::Elapsed>() {
+                return Status::new(Code::DeadlineExceeded, message);
+            }
+            current = e.source();
+        }
+
+        let lower = message.to_lowercase();
+        let code = if ["timed out", "timeout", "deadline"]
+            .iter()
+            .any(|needle| lower.contains(needle))
+        {
+            Code::DeadlineExceeded
+        } else if ["not found", "no such"]
+            .iter()
+            .any(|needle| lower.contains(needle))
+        {
+            Code::NotFound
+        } else {
+            Code::Internal
+        };
+        Status::new(code, message)
+    }
+
+    fn io_code(kind: std::io::ErrorKind) -> Option<Code> {
+        use std::io::ErrorKind;
+
+        match kind {
+            ErrorKind::TimedOut => Some(Code::DeadlineExceeded),
+            ErrorKind::NotFound => Some(Code::NotFound),
+            ErrorKind::PermissionDenied => Some(Code::PermissionDenied),
+            ErrorKind::AlreadyExists => Some(Code::AlreadyExists),
+            ErrorKind::InvalidInput | ErrorKind::InvalidData => Some(Code::InvalidArgument),
+            ErrorKind::ConnectionRefused
+            | ErrorKind::ConnectionReset
+            | ErrorKind::ConnectionAborted
+            | ErrorKind::NotConnected
+            | ErrorKind::BrokenPipe => Some(Code::Unavailable),
+            ErrorKind::Unsupported => Some(Code::Unimplemented),
+            ErrorKind::Interrupted => Some(Code::Cancelled),
+            _ => None,
+        }
+    }
+}
+
+/// Route metadata reflected from compiled proto descriptors
+///
+/// Reads a `FileDescriptorSet` (as produced by `tonic-build` /
+/// `protoc --descriptor_set_out`) and derives one [`RouteMetadata`] per RPC,
+/// so documentation reflects the `.proto` surface instead of hand-registered
+/// methods.
+///
+/// Each route uses the fully qualified method name as its path
+/// (`package.Service.Method`), the [`GrpcMethodType`] name as its method
+/// (e.g. `"server_streaming"`) and `"grpc"` as its protocol. Request and
+/// response schemas follow the proto3 JSON mapping, and leading comments on
+/// RPCs become route descriptions.
+///
+/// ```rust,ignore
+/// const DESCRIPTORS: &[u8] = tonic::include_file_descriptor_set!("users_descriptor");
+///
+/// let mut router = Router::new();
+/// router.add_grpc_descriptor_set(DESCRIPTORS)?;
+/// ```
+#[cfg(feature = "router-grpc")]
+pub mod descriptor {
+    use std::collections::{HashMap, HashSet};
+
+    use prost_types::{
+        field_descriptor_proto::{Label, Type},
+        DescriptorProto, EnumDescriptorProto, FieldDescriptorProto, FileDescriptorSet,
+    };
+    use serde_json::{json, Map, Value};
+
+    use super::*;
+    use crate::router::{GrpcMethodType, RouteMetadata};
+
+    /// `FileDescriptorProto` field number of `service`
+    const FILE_SERVICE_FIELD: i32 = 6;
+    /// `ServiceDescriptorProto` field number of `method`
+    const SERVICE_METHOD_FIELD: i32 = 2;
+
+    /// An RPC method described by a proto descriptor
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct GrpcMethodDescriptor {
+        /// Fully qualified service name (e.g., "users.v1.UserService")
+        pub service: String,
+        /// Method name (e.g., "GetUser")
+        pub method: String,
+        /// Streaming mode
+        pub method_type: GrpcMethodType,
+        /// Fully qualified input message type (e.g., "users.v1.GetUserRequest")
+        pub input_type: String,
+        /// Fully qualified output message type
+        pub output_type: String,
+        /// Leading comment on the RPC, if source info was retained
+        pub description: Option<String>,
+    }
+
+    impl GrpcMethodDescriptor {
+        /// Get the fully qualified method name ("package.Service.Method")
+        pub fn full_name(&self) -> String {
+            format!("{}.{}", self.service, self.method)
+        }
+    }
+
+    /// Decode an encoded `FileDescriptorSet`
+    pub fn decode(bytes: &[u8]) -> Result<FileDescriptorSet, String> {
+        FileDescriptorSet::decode(bytes)
+            .map_err(|e| format!("Failed to decode file descriptor set: {}", e))
+    }
+
+    /// List every RPC method declared in a descriptor set
+    pub fn methods(set: &FileDescriptorSet) -> Vec<GrpcMethodDescriptor> {
+        let mut methods = Vec::new();
+
+        for file in &set.file {
+            let comments: HashMap<&[i32], &str> = file
+                .source_code_info
+                .iter()
+                .flat_map(|info| &info.location)
+                .filter_map(|loc| {
+                    loc.leading_comments
+                        .as_deref()
+                        .map(|c| (loc.path.as_slice(), c))
+                })
+                .collect();
+
+            for (service_index, service) in file.service.iter().enumerate() {
+                let service_name = qualify(file.package(), service.name());
+
+                for (method_index, method) in service.method.iter().enumerate() {
+                    let path = [
+                        FILE_SERVICE_FIELD,
+                        service_index as i32,
+                        SERVICE_METHOD_FIELD,
+                        method_index as i32,
+                    ];
+                    let description = comments
+                        .get(path.as_slice())
+                        .map(|c| c.trim().to_string())
+                        .filter(|c| !c.is_empty());
+
+                    methods.push(GrpcMethodDescriptor {
+                        service: service_name.clone(),
+                        method: method.name().to_string(),
+                        method_type: GrpcMethodType::from_streaming(
+                            method.client_streaming(),
+                            method.server_streaming(),
+                        ),
+                        input_type: method.input_type().trim_start_matches('.').to_string(),
+                        output_type: method.output_type().trim_start_matches('.').to_string(),
+                        description,
+                    });
+                }
+            }
+        }
+
+        methods
+    }
+
+    /// Derive route metadata for every RPC method in a descriptor set
+    pub fn routes(set: &FileDescriptorSet) -> Vec<RouteMetadata> {
+        let types = TypeIndex::new(set);
+
+        methods(set)
+            .into_iter()
+            .map(|method| {
+                let mut route =
+                    RouteMetadata::new(method.full_name(), method.method_type.as_str(), "grpc")
+                        .with_request_schema(types.schema(&method.input_type))
+                        .with_response_schema(types.schema(&method.output_type));
+                if let Some(description) = method.description {
+                    route = route.with_description(description);
+                }
+                route
+            })
+            .collect()
+    }
+
+    fn qualify(package: &str, name: &str) -> String {
+        if package.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", package, name)
+        }
+    }
+
+    /// Message and enum definitions keyed by fully qualified name
+    struct TypeIndex<'a> {
+        messages: HashMap<String, &'a DescriptorProto>,
+        enums: HashMap<String, &'a EnumDescriptorProto>,
+    }
+
+    impl<'a> TypeIndex<'a> {
+        fn new(set: &'a FileDescriptorSet) -> Self {
+            let mut index = Self {
+                messages: HashMap::new(),
+                enums: HashMap::new(),
+            };
+            for file in &set.file {
+                for message in &file.message_type {
+                    index.add_message(file.package(), message);
+                }
+                for enum_type in &file.enum_type {
+                    index
+                        .enums
+                        .insert(qualify(file.package(), enum_type.name()), enum_type);
+                }
+            }
+            index
+        }
+
+        fn add_message(&mut self, scope: &str, message: &'a DescriptorProto) {
+            let name = qualify(scope, message.name());
+            for nested in &message.nested_type {
+                self.add_message(&name, nested);
+            }
+            for enum_type in &message.enum_type {
+                self.enums
+                    .insert(qualify(&name, enum_type.name()), enum_type);
+            }
+            self.messages.insert(name, message);
+        }
+
+        /// JSON schema for a message type (proto3 JSON mapping)
+        fn schema(&self, type_name: &str) -> Value {
+            self.message_schema(type_name, &mut HashSet::new())
+        }
+
+        fn message_schema(&self, type_name: &str, visiting: &mut HashSet<String>) -> Value {
+            let type_name = type_name.trim_start_matches('.');
+            if let Some(schema) = well_known_schema(type_name) {
+                return schema;
+            }
+            let Some(message) = self.messages.get(type_name) else {
+                return json!({ "type": "object", "title": type_name });
+            };
+            // Recursive messages are described by name only below the first level
+            if !visiting.insert(type_name.to_string()) {
+                return json!({ "type": "object", "title": type_name });
+            }
+
+            let mut properties = Map::new();
+            for field in &message.field {
+                let name = field
+                    .json_name
+                    .clone()
+                    .unwrap_or_else(|| field.name().to_string());
+                properties.insert(name, self.field_schema(field, visiting));
+            }
+
+            visiting.remove(type_name);
+            json!({
+                "type": "object",
+                "title": type_name,
+                "properties": properties,
+            })
+        }
+
+        fn field_schema(
+            &self,
+            field: &FieldDescriptorProto,
+            visiting: &mut HashSet<String>,
+        ) -> Value {
+            let type_name = field.type_name().trim_start_matches('.');
+
+            if field.label() == Label::Repeated {
+                // Map fields are repeated synthetic `*Entry` messages
+                if let Some(entry) = self.messages.get(type_name).filter(|m| {
+                    m.options
+                        .as_ref()
+                        .is_some_and(|o| o.map_entry.unwrap_or(false))
+                }) {
+                    let value = entry
+                        .field
+                        .iter()
+                        .find(|f| f.name() == "value")
+                        .map(|f| self.scalar_schema(f, visiting))
+                        .unwrap_or_else(|| json!({}));
+                    return json!({ "type": "object", "additionalProperties": value });
+                }
+                return json!({ "type": "array", "items": self.scalar_schema(field, visiting) });
+            }
+
+            self.scalar_schema(field, visiting)
+        }
+
+        fn scalar_schema(
+            &self,
+            field: &FieldDescriptorProto,
+            visiting: &mut HashSet<String>,
+        ) -> Value {
+            match field.r#type() {
+                Type::Double | Type::Float => json!({ "type": "number" }),
+                Type::Int32 | Type::Uint32 | Type::Sint32 | Type::Fixed32 | Type::Sfixed32 => {
+                    json!({ "type": "integer" })
+                }
+                // 64-bit integers are encoded as strings in proto3 JSON
+                Type::Int64 | Type::Uint64 | Type::Sint64 | Type::Fixed64 | Type::Sfixed64 => {
+                    json!({ "type": "string", "format": "int64" })
+                }
+                Type::Bool => json!({ "type": "boolean" }),
+                Type::String => json!({ "type": "string" }),
+                Type::Bytes => json!({ "type": "string", "format": "byte" }),
+                Type::Enum => {
+                    let type_name = field.type_name().trim_start_matches('.');
+                    match self.enums.get(type_name) {
+                        Some(e) => json!({
+                            "type": "string",
+                            "enum": e.value.iter().map(|v| v.name()).collect::<Vec<_>>(),
+                        }),
+                        None => json!({ "type": "string" }),
+                    }
+                }
+                Type::Message | Type::Group => self.message_schema(field.type_name(), visiting),
+            }
+        }
+    }
+
+    /// Schemas for well-known types with a special JSON representation
+    fn well_known_schema(type_name: &str) -> Option<Value> {
+        let schema = match type_name {
+            "google.protobuf.Timestamp" => json!({ "type": "string", "format": "date-time" }),
+            "google.protobuf.Duration" => json!({ "type": "string", "format": "duration" }),
+            "google.protobuf.Empty" => json!({ "type": "object", "properties": {} }),
+            "google.protobuf.Struct" => json!({ "type": "object" }),
+            "google.protobuf.Value" | "google.protobuf.Any" => json!({}),
+            "google.protobuf.StringValue" => json!({ "type": "string" }),
+            "google.protobuf.BoolValue" => json!({ "type": "boolean" }),
+            "google.protobuf.Int32Value" | "google.protobuf.UInt32Value" => {
+                json!({ "type": "integer" })
+            }
+            "google.protobuf.Int64Value" | "google.protobuf.UInt64Value" => {
+                json!({ "type": "string", "format": "int64" })
+            }
+            "google.protobuf.FloatValue" | "google.protobuf.DoubleValue" => {
+                json!({ "type": "number" })
+            }
+            "google.protobuf.BytesValue" => json!({ "type": "string", "format": "byte" }),
+            _ => return None,
+        };
+        Some(schema)
+    }
 }
 
 #[cfg(test)]
 #[cfg(feature = "router-grpc")]
 mod tests {
     use super::*;
+    use crate::router::GrpcMethodType;
 
     #[test]
     fn test_grpc_adapter_creation() {
@@ -224,6 +604,75 @@ mod tests {
         assert_eq!(adapter.name(), "grpc-production");
     }
 
+    #[test]
+    fn test_status_from_io_error() {
+        let timeout = std::io::Error::new(std::io::ErrorKind::TimedOut, "upstream slow");
+        let status = status::from_error(&timeout);
+        assert_eq!(status.code(), Code::DeadlineExceeded);
+        assert_eq!(status.message(), "upstream slow");
+
+        let refused = std::io::Error::from(std::io::ErrorKind::ConnectionRefused);
+        assert_eq!(status::from_error(&refused).code(), Code::Unavailable);
+
+        let missing = std::io::Error::from(std::io::ErrorKind::NotFound);
+        assert_eq!(status::from_error(&missing).code(), Code::NotFound);
+    }
+
+    #[test]
+    fn test_status_from_error_message_heuristics() {
+        let err: Box<dyn std::error::Error> = "user 42 not found".into();
+        assert_eq!(status::from_error(err.as_ref()).code(), Code::NotFound);
+
+        let err: Box<dyn std::error::Error> = "request timed out after 5s".into();
+        assert_eq!(
+            status::from_error(err.as_ref()).code(),
+            Code::DeadlineExceeded
+        );
+
+        let err: Box<dyn std::error::Error> = "database exploded".into();
+        let status = status::from_error(err.as_ref());
+        assert_eq!(status.code(), Code::Internal);
+        assert_eq!(status.message(), "database exploded");
+    }
+
+    #[test]
+    fn test_status_from_error_walks_source_chain() {
+        #[derive(Debug)]
+        struct Wrapper(std::io::Error);
+
+        impl std::fmt::Display for Wrapper {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(f, "loading config failed")
+            }
+        }
+
+        impl std::error::Error for Wrapper {
+            fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+                Some(&self.0)
+            }
+        }
+
+        let err = Wrapper(std::io::Error::from(std::io::ErrorKind::PermissionDenied));
+        let status = status::from_error(&err);
+        assert_eq!(status.code(), Code::PermissionDenied);
+        assert_eq!(status.message(), "loading config failed");
+
+        let status_err = Status::already_exists("duplicate");
+        assert_eq!(status::from_error(&status_err).code(), Code::AlreadyExists);
+    }
+
+    #[tokio::test]
+    async fn test_status_from_elapsed() {
+        let elapsed = tokio::time::timeout(
+            std::time::Duration::from_millis(1),
+            std::future::pending::<()>(),
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(status::from_error(&elapsed).code(), Code::DeadlineExceeded);
+    }
+
     #[test]
     fn test_status_codes() {
         let ok_status = status::ok();
@@ -301,6 +750,138 @@ mod tests {
         assert_eq!(adapter.service_name(), "UserService");
     }
 
+    fn user_service_descriptors() -> prost_types::FileDescriptorSet {
+        use prost_types::{
+            field_descriptor_proto::{Label, Type},
+            source_code_info::Location,
+            DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+            MethodDescriptorProto, ServiceDescriptorProto, SourceCodeInfo,
+        };
+
+        let field = |name: &str, json_name: &str, number: i32, ty: Type| FieldDescriptorProto {
+            name: Some(name.to_string()),
+            json_name: Some(json_name.to_string()),
+            number: Some(number),
+            label: Some(Label::Optional as i32),
+            r#type: Some(ty as i32),
+            ..Default::default()
+        };
+        let method = |name: &str, input: &str, output: &str, client: bool, server: bool| {
+            MethodDescriptorProto {
+                name: Some(name.to_string()),
+                input_type: Some(format!(".users.v1.{}", input)),
+                output_type: Some(format!(".users.v1.{}", output)),
+                client_streaming: Some(client),
+                server_streaming: Some(server),
+                ..Default::default()
+            }
+        };
+
+        let mut tags = field("tags", "tags", 3, Type::String);
+        tags.label = Some(Label::Repeated as i32);
+
+        FileDescriptorSet {
+            file: vec![FileDescriptorProto {
+                name: Some("users.proto".to_string()),
+                package: Some("users.v1".to_string()),
+                message_type: vec![
+                    DescriptorProto {
+                        name: Some("GetUserRequest".to_string()),
+                        field: vec![field("user_id", "userId", 1, Type::Int64)],
+                        ..Default::default()
+                    },
+                    DescriptorProto {
+                        name: Some("User".to_string()),
+                        field: vec![
+                            field("user_id", "userId", 1, Type::Int64),
+                            field("display_name", "displayName", 2, Type::String),
+                            tags,
+                        ],
+                        ..Default::default()
+                    },
+                ],
+                service: vec![ServiceDescriptorProto {
+                    name: Some("UserService".to_string()),
+                    method: vec![
+                        method("GetUser", "GetUserRequest", "User", false, false),
+                        method("WatchUsers", "GetUserRequest", "User", false, true),
+                        method("ImportUsers", "User", "GetUserRequest", true, false),
+                        method("SyncUsers", "User", "User", true, true),
+                    ],
+                    ..Default::default()
+                }],
+                source_code_info: Some(SourceCodeInfo {
+                    location: vec![Location {
+                        path: vec![6, 0, 2, 0],
+                        leading_comments: Some(" Fetch a single user.\n".to_string()),
+                        ..Default::default()
+                    }],
+                }),
+                syntax: Some("proto3".to_string()),
+                ..Default::default()
+            }],
+        }
+    }
+
+    #[test]
+    fn test_descriptor_methods_parse_streaming_flags() {
+        let methods = descriptor::methods(&user_service_descriptors());
+
+        let types: Vec<_> = methods
+            .iter()
+            .map(|m| (m.method.as_str(), m.method_type.clone()))
+            .collect();
+        assert_eq!(
+            types,
+            vec![
+                ("GetUser", GrpcMethodType::Unary),
+                ("WatchUsers", GrpcMethodType::ServerStreaming),
+                ("ImportUsers", GrpcMethodType::ClientStreaming),
+                ("SyncUsers", GrpcMethodType::BidirectionalStreaming),
+            ]
+        );
+        assert_eq!(methods[0].service, "users.v1.UserService");
+        assert_eq!(methods[0].input_type, "users.v1.GetUserRequest");
+        assert_eq!(
+            methods[0].description.as_deref(),
+            Some("Fetch a single user.")
+        );
+        assert_eq!(methods[1].description, None);
+    }
+
+    #[test]
+    fn test_descriptor_routes_include_schemas() {
+        let routes = descriptor::routes(&user_service_descriptors());
+
+        assert_eq!(routes.len(), 4);
+        assert_eq!(routes[0].path, "users.v1.UserService.GetUser");
+        assert_eq!(routes[0].method, "unary");
+        assert_eq!(routes[0].protocol, "grpc");
+        assert_eq!(routes[1].method, "server_streaming");
+
+        let response = routes[0].response_schema.as_ref().unwrap();
+        assert_eq!(response["title"], "users.v1.User");
+        assert_eq!(response["properties"]["userId"]["type"], "string");
+        assert_eq!(response["properties"]["displayName"]["type"], "string");
+        assert_eq!(response["properties"]["tags"]["type"], "array");
+    }
+
+    #[test]
+    fn test_router_add_grpc_descriptor_set() {
+        let bytes = protobuf::encode(&user_service_descriptors()).unwrap();
+
+        let mut router = crate::router::Router::new();
+        let added = router.add_grpc_descriptor_set(&bytes).unwrap();
+
+        assert_eq!(added, 4);
+        assert!(router
+            .routes()
+            .iter()
+            .any(|r| r.path == "users.v1.UserService.SyncUsers"
+                && r.method == "bidirectional_streaming"));
+        assert!(router.add_grpc_descriptor_set(b"\xff").is_err());
+    }
+
     #[test]
     fn test_all_status_codes() {
         // Test all standard gRPC status codes
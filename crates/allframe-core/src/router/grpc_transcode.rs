@@ -0,0 +1,446 @@
+//! gRPC-JSON transcoding for REST clients
+//!
+//! [`GrpcTranscoder`] makes gRPC methods reachable as REST endpoints with
+//! JSON bodies, using the message schemas from the service's
+//! `FileDescriptorSet`. Routes use `google.api.http`-style path templates,
+//! where `{field}` segments fill fields of the request message:
+//!
+//! - the request message is built from the JSON body (`POST`, `PUT`,
+//!   `PATCH`), the query string and the path fields, with path fields taking
+//!   precedence, as for REST handlers (see [`extract`](super::extract))
+//! - unary methods answer with the JSON response, or an error mapped to the
+//!   HTTP status matching its gRPC code
+//! - server-streaming methods answer with Server-Sent Events, one `data:`
+//!   event per message
+//!
+//! [`default_routes`](GrpcTranscoder::default_routes) adds a
+//! `POST /package.Service/Method` route for every method that can be
+//! transcoded, so a service is reachable without declaring routes.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use allframe_core::router::{GrpcJsonBridge, GrpcTranscoder, Transcoded};
+//!
+//! const DESCRIPTORS: &[u8] = tonic::include_file_descriptor_set!("users_descriptor");
+//!
+//! let bridge = GrpcJsonBridge::connect_lazy(DESCRIPTORS, "http://localhost:50051")?;
+//! let mut transcoder = GrpcTranscoder::new(bridge);
+//! transcoder
+//!     .route("GET", "/v1/users/{id}", "users.v1.Users/GetUser")?
+//!     .route("GET", "/v1/users/{id}/events", "users.v1.Users/WatchUser")?;
+//!
+//! match transcoder.handle(method, target, content_type, &body, &forwarded).await {
+//!     Some(Transcoded::Response(response)) => { /* send the REST response */ }
+//!     Some(Transcoded::Events(events)) => { /* stream as text/event-stream */ }
+//!     None => { /* not a transcoded route */ }
+//! }
+//! ```
+
+use std::collections::HashMap;
+
+use prost_reflect::{FieldDescriptor, Kind, MessageDescriptor, MethodDescriptor};
+use serde_json::Value;
+use tokio_stream::StreamExt;
+use tonic::{Code, Status};
+
+use super::{
+    grpc_json::{status_json, JsonStream},
+    request_args, sse_event, GrpcJsonBridge, RestResponse, RestRoute, SseConfig, SseStream,
+};
+
+/// Response to a transcoded request
+pub enum Transcoded {
+    /// A unary method's response, or an error for any method
+    Response(RestResponse),
+    /// A server-streaming method's messages, to send with
+    /// `content-type: text/event-stream`
+    ///
+    /// An error partway through the stream is sent as a final `error` event
+    /// with the same JSON body as error responses. Heartbeats are sent as
+    /// set with [`GrpcTranscoder::sse_config`].
+    Events(SseStream),
+}
+
+impl std::fmt::Debug for Transcoded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Transcoded::Response(response) => f.debug_tuple("Response").field(response).finish(),
+            Transcoded::Events(_) => f.write_str("Events(..)"),
+        }
+    }
+}
+
+struct TranscodeRoute {
+    route: RestRoute,
+    method: MethodDescriptor,
+}
+
+/// Serves gRPC methods as REST endpoints with JSON bodies
+///
+/// Unary and server-streaming methods can be transcoded; client and
+/// bidirectional streaming methods can't.
+pub struct GrpcTranscoder {
+    bridge: GrpcJsonBridge,
+    routes: Vec<TranscodeRoute>,
+    sse: SseConfig,
+}
+
+impl GrpcTranscoder {
+    /// Create a transcoder calling methods through `bridge`
+    pub fn new(bridge: GrpcJsonBridge) -> Self {
+        Self {
+            bridge,
+            routes: Vec::new(),
+            sse: SseConfig::default(),
+        }
+    }
+
+    /// Set the SSE settings for server-streaming methods, such as the
+    /// heartbeat interval
+    pub fn sse_config(&mut self, config: SseConfig) -> &mut Self {
+        self.sse = config;
+        self
+    }
+
+    /// Route `http_method` requests matching `path` to `grpc_method`
+    ///
+    /// `path` is a template such as `/v1/users/{id}`, where each `{field}`
+    /// names a top-level field of the request message. Fails if the method
+    /// doesn't exist or can't be transcoded, or a path field isn't a field
+    /// of its request message.
+    pub fn route(
+        &mut self,
+        http_method: &str,
+        path: &str,
+        grpc_method: &str,
+    ) -> Result<&mut Self, String> {
+        let method = self
+            .bridge
+            .method(grpc_method)
+            .ok_or_else(|| format!("Unknown gRPC method: {}", grpc_method))?;
+        if method.is_client_streaming() {
+            return Err(format!(
+                "{} is client-streaming and can't be transcoded",
+                method.full_name()
+            ));
+        }
+
+        let mut segments = Vec::new();
+        for segment in path.split('/').filter(|s| !s.is_empty()) {
+            match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+                Some(field) => {
+                    if find_field(&method.input(), field).is_none() {
+                        return Err(format!(
+                            "{} has no field '{}' for path {}",
+                            method.input().full_name(),
+                            field,
+                            path
+                        ));
+                    }
+                    segments.push(format!(":{}", field));
+                }
+                None => segments.push(segment.to_string()),
+            }
+        }
+
+        let route = RestRoute::new(
+            http_method.to_ascii_uppercase(),
+            format!("/{}", segments.join("/")),
+            method.full_name(),
+        );
+        self.routes.push(TranscodeRoute { route, method });
+        Ok(self)
+    }
+
+    /// Add a `POST /package.Service/Method` route for every unary and
+    /// server-streaming method
+    pub fn default_routes(&mut self) -> &mut Self {
+        let methods: Vec<MethodDescriptor> = self
+            .bridge
+            .pool()
+            .services()
+            .flat_map(|service| service.methods().collect::<Vec<_>>())
+            .filter(|method| !method.is_client_streaming())
+            .collect();
+        for method in methods {
+            let path = format!("/{}/{}", method.parent_service().full_name(), method.name());
+            self.routes.push(TranscodeRoute {
+                route: RestRoute::new("POST", path, method.full_name()),
+                method,
+            });
+        }
+        self
+    }
+
+    /// The routes as `(http method, path, gRPC method)`, in match order
+    pub fn routes(&self) -> Vec<(String, String, String)> {
+        self.routes
+            .iter()
+            .map(|r| {
+                (
+                    r.route.method.clone(),
+                    r.route.path.clone(),
+                    r.route.handler.clone(),
+                )
+            })
+            .collect()
+    }
+
+    /// Handle a REST request, or return `None` if no route matches
+    ///
+    /// `target` is the request path with an optional query string.
+    /// `metadata` is sent as gRPC request metadata; pass only the headers
+    /// that should reach the service, such as `authorization`.
+    pub async fn handle(
+        &self,
+        http_method: &str,
+        target: &str,
+        content_type: Option<&str>,
+        body: &str,
+        metadata: &HashMap<String, String>,
+    ) -> Option<Transcoded> {
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let (route, path_params) = self.routes.iter().find_map(|r| {
+            if !r.route.method.eq_ignore_ascii_case(http_method) {
+                return None;
+            }
+            r.route.path_params(path).map(|params| (r, params))
+        })?;
+
+        let mut request = match request_args(content_type, body, query, &path_params) {
+            Ok(request) => request,
+            Err(response) => return Some(Transcoded::Response(response)),
+        };
+        coerce_scalars(&route.method.input(), &mut request);
+
+        let name = route.method.full_name();
+        let result = if route.method.is_server_streaming() {
+            self.bridge
+                .invoke_server_streaming(name, request, metadata)
+                .await
+                .map(|messages| Transcoded::Events(sse_events(messages, &self.sse)))
+        } else {
+            self.bridge
+                .invoke_unary(name, request, metadata)
+                .await
+                .map(|response| Transcoded::Response(RestResponse::json(&response)))
+        };
+        Some(result.unwrap_or_else(|status| Transcoded::Response(error_response(&status))))
+    }
+}
+
+/// Frame streamed messages as SSE `data:` events, ending with an `error`
+/// event if the stream fails
+fn sse_events(mut messages: JsonStream, config: &SseConfig) -> SseStream {
+    SseStream::spawn(config, |tx| async move {
+        while let Some(message) = messages.next().await {
+            let (event, failed) = match message {
+                Ok(message) => (sse_event(None, &message.to_string()), false),
+                Err(status) => {
+                    let error = status_json(&status).to_string();
+                    (sse_event(Some("error"), &error), true)
+                }
+            };
+            if tx.send(event).await.is_err() || failed {
+                return;
+            }
+        }
+    })
+}
+
+fn error_response(status: &Status) -> RestResponse {
+    RestResponse::json(&status_json(status)).with_status(http_status(status.code()))
+}
+
+/// HTTP status for a gRPC status code, as mapped by `google.api.http`
+/// transcoding
+pub fn http_status(code: Code) -> u16 {
+    match code {
+        Code::Ok => 200,
+        Code::Cancelled => 499,
+        Code::InvalidArgument | Code::FailedPrecondition | Code::OutOfRange => 400,
+        Code::DeadlineExceeded => 504,
+        Code::NotFound => 404,
+        Code::AlreadyExists | Code::Aborted => 409,
+        Code::PermissionDenied => 403,
+        Code::ResourceExhausted => 429,
+        Code::Unimplemented => 501,
+        Code::Unavailable => 503,
+        Code::Unauthenticated => 401,
+        Code::Unknown | Code::Internal | Code::DataLoss => 500,
+    }
+}
+
+fn find_field(message: &MessageDescriptor, name: &str) -> Option<FieldDescriptor> {
+    message
+        .get_field_by_name(name)
+        .or_else(|| message.get_field_by_json_name(name))
+}
+
+/// Turn string values from the path, query or a form into the booleans and
+/// numbers their fields expect
+///
+/// 64-bit integers stay strings, which the proto3 JSON mapping accepts.
+fn coerce_scalars(message: &MessageDescriptor, request: &mut Value) {
+    let Value::Object(fields) = request else {
+        return;
+    };
+    for (name, value) in fields.iter_mut() {
+        let (Some(field), Value::String(text)) = (find_field(message, name), &*value) else {
+            continue;
+        };
+        if field.is_list() || field.is_map() {
+            continue;
+        }
+        let coerced = match field.kind() {
+            Kind::Bool => text.parse::<bool>().ok().map(Value::from),
+            Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 => {
+                text.parse::<i32>().ok().map(Value::from)
+            }
+            Kind::Uint32 | Kind::Fixed32 => text.parse::<u32>().ok().map(Value::from),
+            Kind::Float | Kind::Double => text.parse::<f64>().ok().map(Value::from),
+            _ => None,
+        };
+        if let Some(coerced) = coerced {
+            *value = coerced;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::router::grpc_json::tests::{descriptor_set, serve_greeter};
+
+    async fn transcoder() -> GrpcTranscoder {
+        let addr = serve_greeter().await;
+        let bridge =
+            GrpcJsonBridge::connect_lazy(&descriptor_set(), format!("http://{}", addr)).unwrap();
+        GrpcTranscoder::new(bridge)
+    }
+
+    fn response(transcoded: Option<Transcoded>) -> (u16, Value) {
+        match transcoded {
+            Some(Transcoded::Response(response)) => (
+                response.status_code(),
+                serde_json::from_str(response.body()).unwrap(),
+            ),
+            other => panic!("expected a response, got {:?}", other),
+        }
+    }
+
+    async fn events(transcoded: Option<Transcoded>) -> Vec<String> {
+        match transcoded {
+            Some(Transcoded::Events(events)) => events.collect().await,
+            other => panic!("expected events, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unary_route_with_path_and_query_fields() {
+        let mut transcoder = transcoder().await;
+        transcoder
+            .route("GET", "/v1/hello/{name}", "greet.v1.Greeter/SayHello")
+            .unwrap();
+        let metadata = HashMap::from([("x-user".to_string(), "grace".to_string())]);
+
+        let transcoded = transcoder
+            .handle("GET", "/v1/hello/Ada?times=3", None, "", &metadata)
+            .await;
+        assert_eq!(
+            response(transcoded),
+            (200, json!({"message": "Hello Ada x3 (from grace)"}))
+        );
+
+        let missing = transcoder
+            .handle("POST", "/v1/hello/Ada", None, "", &metadata)
+            .await;
+        assert!(missing.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_default_routes_take_json_bodies() {
+        let mut transcoder = transcoder().await;
+        transcoder.default_routes();
+        assert_eq!(transcoder.routes().len(), 2);
+
+        let transcoded = transcoder
+            .handle(
+                "POST",
+                "/greet.v1.Greeter/SayHello",
+                Some("application/json"),
+                r#"{"name": "Ada", "times": 2}"#,
+                &HashMap::new(),
+            )
+            .await;
+        assert_eq!(
+            response(transcoded),
+            (200, json!({"message": "Hello Ada x2 (from anonymous)"}))
+        );
+
+        let (status, body) = response(
+            transcoder
+                .handle(
+                    "POST",
+                    "/greet.v1.Greeter/SayHello",
+                    None,
+                    r#"{"times": "many"}"#,
+                    &HashMap::new(),
+                )
+                .await,
+        );
+        assert_eq!(status, 400);
+        assert_eq!(body["error"]["status"], "InvalidArgument");
+    }
+
+    #[tokio::test]
+    async fn test_server_streaming_as_sse() {
+        let mut transcoder = transcoder().await;
+        transcoder
+            .route("GET", "/v1/hellos/{name}", "greet.v1.Greeter/StreamHellos")
+            .unwrap();
+
+        let transcoded = transcoder
+            .handle("GET", "/v1/hellos/Ada?times=2", None, "", &HashMap::new())
+            .await;
+        assert_eq!(
+            events(transcoded).await,
+            [
+                "data: {\"message\":\"Hello Ada #1\"}\n\n",
+                "data: {\"message\":\"Hello Ada #2\"}\n\n",
+            ]
+        );
+
+        let transcoded = transcoder
+            .handle("GET", "/v1/hellos/error?times=1", None, "", &HashMap::new())
+            .await;
+        let events = events(transcoded).await;
+        assert_eq!(events.len(), 2);
+        assert!(events[1].starts_with("event: error\ndata: {"));
+        assert!(events[1].contains("stream broke"));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_routes() {
+        let mut transcoder = transcoder().await;
+        assert!(transcoder
+            .route("GET", "/v1/x", "greet.v1.Greeter/Missing")
+            .is_err());
+        let err = transcoder
+            .route("GET", "/v1/hello/{id}", "greet.v1.Greeter/SayHello")
+            .err()
+            .unwrap();
+        assert!(err.contains("no field 'id'"));
+    }
+
+    #[test]
+    fn test_http_status_mapping() {
+        assert_eq!(http_status(Code::NotFound), 404);
+        assert_eq!(http_status(Code::Unauthenticated), 401);
+        assert_eq!(http_status(Code::Unavailable), 503);
+        assert_eq!(http_status(Code::DataLoss), 500);
+    }
+}
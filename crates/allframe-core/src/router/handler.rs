@@ -8,12 +8,20 @@ use std::{
     fmt,
     future::Future,
     ops::Deref,
+    panic::{self, AssertUnwindSafe},
     pin::Pin,
     sync::{Arc, RwLock},
+    task::Poll,
+    time::Duration,
 };
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 
+use super::{
+    extract::from_args,
+    rest::{HttpError, RestResponse},
+};
+
 /// Shared, type-keyed state map used by stateful handlers.
 ///
 /// Wrapped in `Arc<RwLock<…>>` so that handlers registered before a state
@@ -142,6 +150,116 @@ impl fmt::Display for StreamError {
 
 impl std::error::Error for StreamError {}
 
+// ─── Execute error types ────────────────────────────────────────────────────
+
+/// Error returned by a handler.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HandlerError(pub String);
+
+impl fmt::Display for HandlerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for HandlerError {}
+
+/// Errors from executing a handler by name.
+///
+/// Transports map `NotFound` to 404 / `UNIMPLEMENTED` and `Handler` to
+/// 500 / `INTERNAL`. The [`HttpError`] impl gives the REST status of each
+/// variant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExecuteError {
+    /// No handler is registered under this name.
+    NotFound(String),
+    /// The handler (or middleware in front of it) returned an error.
+    Handler(HandlerError),
+    /// The handler didn't complete within the route's timeout.
+    TimedOut(Duration),
+    /// The route's rate limit was exceeded.
+    RateLimited {
+        /// How long until a request would be allowed.
+        retry_after: Duration,
+    },
+}
+
+impl fmt::Display for ExecuteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExecuteError::NotFound(name) => write!(f, "Handler '{name}' not found"),
+            ExecuteError::Handler(e) => write!(f, "{e}"),
+            ExecuteError::TimedOut(timeout) => write!(f, "Handler timed out after {timeout:?}"),
+            ExecuteError::RateLimited { retry_after } => {
+                write!(f, "Rate limit exceeded, retry after {retry_after:?}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExecuteError {}
+
+impl From<ExecuteError> for String {
+    fn from(error: ExecuteError) -> Self {
+        error.to_string()
+    }
+}
+
+impl HttpError for ExecuteError {
+    fn status_code(&self) -> u16 {
+        match self {
+            ExecuteError::NotFound(_) => 404,
+            ExecuteError::Handler(_) => 500,
+            ExecuteError::TimedOut(_) => 504,
+            ExecuteError::RateLimited { .. } => 429,
+        }
+    }
+
+    fn into_response(self) -> RestResponse {
+        let response = RestResponse::json(&serde_json::json!({ "error": self.to_string() }))
+            .with_status(self.status_code());
+        match self {
+            // Whole seconds, rounded up so clients don't retry too early
+            ExecuteError::RateLimited { retry_after } => {
+                let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+                response.header("retry-after", seconds.max(1).to_string())
+            }
+            _ => response,
+        }
+    }
+}
+
+/// Run a handler future, turning a panic into an error.
+///
+/// A panicking handler fails its own request with a `"... panicked: ..."`
+/// error instead of unwinding into the task serving the connection.
+/// `name` identifies the handler in the error and the log.
+pub(crate) async fn catch_panic<F>(name: &str, future: F) -> Result<String, String>
+where
+    F: Future<Output = Result<String, String>>,
+{
+    let mut future = std::pin::pin!(future);
+    let result = std::future::poll_fn(|cx| {
+        match panic::catch_unwind(AssertUnwindSafe(|| future.as_mut().poll(cx))) {
+            Ok(Poll::Ready(result)) => Poll::Ready(Ok(result)),
+            Ok(Poll::Pending) => Poll::Pending,
+            Err(payload) => Poll::Ready(Err(payload)),
+        }
+    })
+    .await;
+
+    result.unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        #[cfg(feature = "otel")]
+        tracing::error!(handler = name, panic = %message, "Handler panicked");
+        Err(format!("Handler '{name}' panicked: {message}"))
+    })
+}
+
 // ─── StreamSender ───────────────────────────────────────────────────────────
 
 /// Default bounded channel capacity for streaming handlers.
@@ -398,7 +516,7 @@ where
     R: IntoHandlerResult + 'static,
 {
     fn call(&self, args: &str) -> Pin<Box<dyn Future<Output = Result<String, String>> + Send + '_>> {
-        let parsed: Result<T, _> = serde_json::from_str(args);
+        let parsed: Result<T, _> = from_args(args);
         match parsed {
             Ok(value) => {
                 let fut = (self.func)(value);
@@ -458,7 +576,7 @@ where
             Err(msg) => return Box::pin(async move { Err(msg) }),
         };
 
-        let parsed: Result<T, _> = serde_json::from_str(args);
+        let parsed: Result<T, _> = from_args(args);
         match parsed {
             Ok(value) => {
                 let fut = (self.func)(State(state_arc), value);
@@ -623,7 +741,7 @@ where
         args: &str,
         tx: StreamSender,
     ) -> Pin<Box<dyn Future<Output = Result<String, String>> + Send + '_>> {
-        let parsed: Result<T, _> = serde_json::from_str(args);
+        let parsed: Result<T, _> = from_args(args);
         match parsed {
             Ok(value) => {
                 let fut = (self.func)(value, tx);
@@ -687,7 +805,7 @@ where
             Err(msg) => return Box::pin(async move { Err(msg) }),
         };
 
-        let parsed: Result<T, _> = serde_json::from_str(args);
+        let parsed: Result<T, _> = from_args(args);
         match parsed {
             Ok(value) => {
                 let fut = (self.func)(State(state_arc), value, tx);
@@ -0,0 +1,244 @@
+//! Per-route timeouts and rate limits
+//!
+//! Each REST and gRPC route can carry a timeout and a rate limit in its
+//! [`RouteMetadata`], overriding the router-wide defaults. Limits are keyed
+//! by handler name, using the `METHOD:/path` names that
+//! [`Router::get`](super::Router::get) and friends register handlers under,
+//! and by `grpc:Service.Method` for gRPC routes. Requests dispatched through
+//! an adapter are resolved to the same keys, so a route is limited however
+//! it is called.
+//!
+//! GraphQL routes don't get their own limits, since one query can select
+//! several of them; GraphQL requests share the router-wide defaults.
+//!
+//! Rate limits use the token bucket limiters from the `resilience` module
+//! and are only enforced with the `resilience` feature; timeouts are always
+//! enforced.
+
+use std::{cmp::Reverse, collections::HashMap, future::Future, time::Duration};
+
+#[cfg(feature = "resilience")]
+use super::RouteRateLimit;
+use super::{ExecuteError, HandlerError, MiddlewareRequest, RouteMetadata};
+#[cfg(feature = "resilience")]
+use crate::resilience::{KeyedRateLimiter, RateLimiter};
+
+/// Timeouts and rate limits for a router's routes
+#[derive(Default)]
+pub(crate) struct RouteLimits {
+    default_timeout: Option<Duration>,
+    timeouts: HashMap<String, Duration>,
+    /// REST route paths by method, most specific first
+    rest_routes: HashMap<String, Vec<String>>,
+    /// One bucket per handler, for handlers without their own limit
+    #[cfg(feature = "resilience")]
+    default_rate_limit: Option<KeyedRateLimiter<String>>,
+    #[cfg(feature = "resilience")]
+    rate_limiters: HashMap<String, RateLimiter>,
+}
+
+impl RouteLimits {
+    /// Set the timeout for handlers without their own
+    pub(crate) fn set_default_timeout(&mut self, timeout: Duration) {
+        self.default_timeout = Some(timeout);
+    }
+
+    /// Set the rate limit for handlers without their own
+    ///
+    /// Each handler gets a separate bucket with this limit.
+    #[cfg(feature = "resilience")]
+    pub(crate) fn set_default_rate_limit(&mut self, limit: RouteRateLimit) {
+        self.default_rate_limit = Some(KeyedRateLimiter::new(limit.rps, limit.burst));
+    }
+
+    /// Apply the limits from a route's metadata
+    ///
+    /// Limits on routes of protocols other than REST and gRPC are ignored.
+    pub(crate) fn set_route(&mut self, metadata: &RouteMetadata) {
+        let key = match metadata.protocol.as_str() {
+            "rest" => format!("{}:{}", metadata.method, metadata.path),
+            "grpc" => format!("grpc:{}", metadata.path),
+            _ => return,
+        };
+        if metadata.protocol == "rest" {
+            let paths = self.rest_routes.entry(metadata.method.clone()).or_default();
+            if !paths.contains(&metadata.path) {
+                paths.push(metadata.path.clone());
+                paths.sort_by_cached_key(|path| specificity(path));
+            }
+        }
+        if let Some(timeout) = metadata.timeout {
+            self.timeouts.insert(key.clone(), timeout);
+        }
+        #[cfg(feature = "resilience")]
+        if let Some(limit) = metadata.rate_limit {
+            self.rate_limiters
+                .insert(key, RateLimiter::new(limit.rps, limit.burst));
+        }
+    }
+
+    /// Key of the limits that apply to a dispatched request
+    ///
+    /// Direct handler calls use the handler name. REST requests use the
+    /// `METHOD:/path` key of the route they match, so a limit on
+    /// `/users/{id}` applies to `GET /users/42`. When several routes match,
+    /// the one with the most literal segments wins (`/users/me` over
+    /// `/users/{id}`), then the one whose first parameter comes later, then
+    /// the lowest path in string order. gRPC requests use
+    /// `grpc:Service.Method`. Requests for any other adapter share one key
+    /// per protocol.
+    pub(crate) fn key(&self, request: &MiddlewareRequest) -> String {
+        let operation = request.operation.as_str();
+        match request.protocol.as_str() {
+            "handler" => operation.to_string(),
+            "rest" => {
+                let (method, target) = operation.split_once(' ').unwrap_or((operation, ""));
+                let path = target.split('?').next().unwrap_or_default();
+                let key = format!("{method}:{path}");
+                if self.has_route(&key) {
+                    return key;
+                }
+                self.rest_routes
+                    .get(method)
+                    .and_then(|routes| routes.iter().find(|route| path_matches(route, path)))
+                    .map_or(key, |route| format!("{method}:{route}"))
+            }
+            "grpc" => {
+                let method = operation.split(':').next().unwrap_or_default();
+                format!("grpc:{method}")
+            }
+            protocol => protocol.to_string(),
+        }
+    }
+
+    /// Whether a key has its own timeout or rate limit
+    fn has_route(&self, key: &str) -> bool {
+        #[cfg(feature = "resilience")]
+        if self.rate_limiters.contains_key(key) {
+            return true;
+        }
+        self.timeouts.contains_key(key)
+    }
+
+    /// Timeout for a handler, if any
+    pub(crate) fn timeout(&self, handler: &str) -> Option<Duration> {
+        self.timeouts.get(handler).copied().or(self.default_timeout)
+    }
+
+    /// Take a token from the handler's rate limit, if it has one
+    #[cfg(feature = "resilience")]
+    fn check_rate_limit(&self, handler: &str) -> Result<(), ExecuteError> {
+        let result = match (self.rate_limiters.get(handler), &self.default_rate_limit) {
+            (Some(limiter), _) => limiter.check(),
+            (None, Some(limiters)) => limiters.check(&handler.to_string()),
+            (None, None) => Ok(()),
+        };
+        result.map_err(|e| ExecuteError::RateLimited {
+            retry_after: e.retry_after,
+        })
+    }
+
+    /// Run a handler call within its rate limit and timeout
+    ///
+    /// A rate-limited call is rejected without being polled.
+    pub(crate) async fn run<F>(&self, handler: &str, call: F) -> Result<String, ExecuteError>
+    where
+        F: Future<Output = Result<String, String>>,
+    {
+        #[cfg(feature = "resilience")]
+        self.check_rate_limit(handler)?;
+
+        let result = match self.timeout(handler) {
+            Some(timeout) => tokio::time::timeout(timeout, call)
+                .await
+                .map_err(|_| ExecuteError::TimedOut(timeout))?,
+            None => call.await,
+        };
+        result.map_err(|e| ExecuteError::Handler(HandlerError(e)))
+    }
+}
+
+/// Non-empty segments of a path
+fn segments(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|s| !s.is_empty())
+}
+
+/// Whether a route path segment is a `{param}` or `:param`
+fn is_param(segment: &str) -> bool {
+    segment.starts_with(':') || (segment.starts_with('{') && segment.ends_with('}'))
+}
+
+/// Sort key putting more specific route paths first
+///
+/// More literal segments first, then literal segments before parameters
+/// position by position, then the path itself so the order is total.
+fn specificity(pattern: &str) -> (Reverse<usize>, Vec<bool>, String) {
+    let params: Vec<bool> = segments(pattern).map(is_param).collect();
+    let literals = params.iter().filter(|param| !**param).count();
+    (Reverse(literals), params, pattern.to_string())
+}
+
+/// Whether a request path matches a route path with `{param}` or `:param`
+/// segments
+fn path_matches(pattern: &str, path: &str) -> bool {
+    let mut pattern = segments(pattern);
+    let mut path = segments(path);
+    loop {
+        match (pattern.next(), path.next()) {
+            (None, None) => return true,
+            (Some(expected), Some(actual)) => {
+                if !is_param(expected) && expected != actual {
+                    return false;
+                }
+            }
+            _ => return false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rest_key(limits: &RouteLimits, operation: &str) -> String {
+        limits.key(&MiddlewareRequest::new("rest", operation, ""))
+    }
+
+    #[test]
+    fn test_most_specific_route_wins() {
+        let mut limits = RouteLimits::default();
+        let second = Duration::from_secs(1);
+        // Registration order doesn't matter
+        for path in [
+            "/users/{id}/{tab}",
+            "/users/{id}",
+            "/users/:id/posts",
+            "/users/me/{tab}",
+            "/users/me",
+        ] {
+            limits.set_route(&RouteMetadata::new(path, "GET", "rest").with_timeout(second));
+        }
+        // Routes without limits still claim their requests
+        limits.set_route(&RouteMetadata::new("/users/admin", "GET", "rest"));
+
+        assert_eq!(rest_key(&limits, "GET /users/42"), "GET:/users/{id}");
+        assert_eq!(rest_key(&limits, "GET /users/me?x=1"), "GET:/users/me");
+        assert_eq!(rest_key(&limits, "GET /users/admin"), "GET:/users/admin");
+        assert_eq!(limits.timeout("GET:/users/admin"), None);
+        assert_eq!(
+            rest_key(&limits, "GET /users/42/posts"),
+            "GET:/users/:id/posts"
+        );
+        // Both have one literal segment besides `users`; the literal comes first
+        assert_eq!(
+            rest_key(&limits, "GET /users/me/posts"),
+            "GET:/users/me/{tab}"
+        );
+        assert_eq!(
+            rest_key(&limits, "GET /users/42/likes"),
+            "GET:/users/{id}/{tab}"
+        );
+        // Unmatched requests keep their own key
+        assert_eq!(rest_key(&limits, "POST /users/42"), "POST:/users/42");
+    }
+}
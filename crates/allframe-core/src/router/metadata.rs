@@ -4,8 +4,23 @@
 //! metadata about registered routes, which can then be used to generate
 //! OpenAPI specifications, GraphQL schemas, and gRPC reflection data.
 
+use std::time::Duration;
+
 use serde::{Deserialize, Serialize};
 
+/// Rate limit for a single route
+///
+/// A token bucket allowing `rps` requests per second on average, with
+/// bursts of up to `burst` requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouteRateLimit {
+    /// Sustained requests per second
+    pub rps: u32,
+
+    /// Burst capacity
+    pub burst: u32,
+}
+
 /// Metadata about a registered route
 ///
 /// Contains all information needed to generate documentation
@@ -30,6 +45,14 @@ pub struct RouteMetadata {
 
     /// Response schema as JSON Schema (if available)
     pub response_schema: Option<serde_json::Value>,
+
+    /// Timeout overriding the router default (if any)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<Duration>,
+
+    /// Rate limit overriding the router default (if any)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<RouteRateLimit>,
 }
 
 impl RouteMetadata {
@@ -46,6 +69,8 @@ impl RouteMetadata {
             description: None,
             request_schema: None,
             response_schema: None,
+            timeout: None,
+            rate_limit: None,
         }
     }
 
@@ -66,6 +91,25 @@ impl RouteMetadata {
         self.response_schema = Some(schema);
         self
     }
+
+    /// Set the timeout
+    ///
+    /// Enforced for REST and gRPC routes; see
+    /// [`Router::add_route`](super::Router::add_route).
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set the rate limit
+    ///
+    /// Enforced for REST and gRPC routes; see
+    /// [`Router::add_route`](super::Router::add_route).
+    #[cfg(feature = "resilience")]
+    pub fn with_rate_limit(mut self, rps: u32, burst: u32) -> Self {
+        self.rate_limit = Some(RouteRateLimit { rps, burst });
+        self
+    }
 }
 
 #[cfg(test)]
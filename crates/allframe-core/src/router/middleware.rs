@@ -0,0 +1,302 @@
+//! Router middleware
+//!
+//! Middleware wraps every request dispatched through the
+//! [`Router`](super::Router), whether it calls a handler directly or goes
+//! through a protocol adapter. Layers run in the order they were added via
+//! [`Router::layer`](super::Router::layer), each deciding whether and how to
+//! call the next one.
+//!
+//! ```rust
+//! use allframe_core::router::{Middleware, MiddlewareFuture, MiddlewareRequest, Next, Router};
+//!
+//! struct Deny;
+//!
+//! impl Middleware for Deny {
+//!     fn handle<'a>(&'a self, request: MiddlewareRequest, next: Next<'a>) -> MiddlewareFuture<'a> {
+//!         if request.operation == "admin" {
+//!             return Box::pin(async { Err("Forbidden".to_string()) });
+//!         }
+//!         next.run(request)
+//!     }
+//! }
+//!
+//! let mut router = Router::new();
+//! router.layer(Deny);
+//! ```
+
+use std::{collections::HashMap, future::Future, pin::Pin, sync::Arc};
+
+/// Future returned by middleware and the handler chain
+pub type MiddlewareFuture<'a> = Pin<Box<dyn Future<Output = Result<String, String>> + Send + 'a>>;
+
+/// Header carrying the correlation id of a request
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+tokio::task_local! {
+    static REQUEST_ID: String;
+}
+
+/// Correlation id of the request currently being handled
+///
+/// Set by [`LoggingMiddleware`] (or [`with_request_id`]) for the duration of
+/// the request, so handlers and nested router calls can read it.
+pub fn request_id() -> Option<String> {
+    REQUEST_ID.try_with(|id| id.clone()).ok()
+}
+
+/// Run a future with the given correlation id in scope
+pub async fn with_request_id<F>(request_id: String, future: F) -> F::Output
+where
+    F: Future,
+{
+    REQUEST_ID.scope(request_id, future).await
+}
+
+/// A request flowing through the middleware stack
+#[derive(Debug, Clone)]
+pub struct MiddlewareRequest {
+    /// Protocol the request arrived on (`handler`, `rest`, `graphql`, ...)
+    pub protocol: String,
+    /// Handler name or protocol operation (e.g. `GET /users`)
+    pub operation: String,
+    /// Raw request payload (handler args or adapter request)
+    pub payload: String,
+    /// Request headers / metadata, keyed by lowercase name
+    pub headers: HashMap<String, String>,
+}
+
+impl MiddlewareRequest {
+    /// Create a new request without headers
+    pub fn new(
+        protocol: impl Into<String>,
+        operation: impl Into<String>,
+        payload: impl Into<String>,
+    ) -> Self {
+        Self {
+            protocol: protocol.into(),
+            operation: operation.into(),
+            payload: payload.into(),
+            headers: HashMap::new(),
+        }
+    }
+
+    /// Add a header (the name is lowercased)
+    pub fn with_header(mut self, name: &str, value: impl Into<String>) -> Self {
+        self.headers.insert(name.to_ascii_lowercase(), value.into());
+        self
+    }
+
+    /// Get a header value by name (case-insensitive)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .get(&name.to_ascii_lowercase())
+            .map(String::as_str)
+    }
+}
+
+/// Middleware wrapping request dispatch
+pub trait Middleware: Send + Sync {
+    /// Handle a request, usually by calling `next.run(request)`
+    fn handle<'a>(&'a self, request: MiddlewareRequest, next: Next<'a>) -> MiddlewareFuture<'a>;
+}
+
+/// Endpoint invoked after the last middleware layer
+pub(crate) type Endpoint<'a> = dyn Fn(MiddlewareRequest) -> MiddlewareFuture<'a> + Send + Sync + 'a;
+
+/// The remaining middleware layers and the final endpoint
+pub struct Next<'a> {
+    layers: &'a [Arc<dyn Middleware>],
+    endpoint: &'a Endpoint<'a>,
+}
+
+impl<'a> Next<'a> {
+    pub(crate) fn new(layers: &'a [Arc<dyn Middleware>], endpoint: &'a Endpoint<'a>) -> Self {
+        Self { layers, endpoint }
+    }
+
+    /// Pass the request to the next layer (or the endpoint)
+    pub fn run(self, request: MiddlewareRequest) -> MiddlewareFuture<'a> {
+        match self.layers.split_first() {
+            Some((layer, rest)) => layer.handle(request, Next::new(rest, self.endpoint)),
+            None => (self.endpoint)(request),
+        }
+    }
+}
+
+/// Structured request logging with correlation ids
+///
+/// For every request, reuses a valid incoming `x-request-id` header (or the
+/// id of an enclosing request), otherwise generates a [`RequestId`]. The id is recorded as the
+/// `request_id` field of a `request` tracing span, so it is exported with OTel
+/// spans, and exposed to handlers through [`request_id`]. On completion it
+/// emits an event with protocol, operation, status and latency.
+///
+/// ```rust
+/// use allframe_core::router::{LoggingMiddleware, Router};
+///
+/// let mut router = Router::new();
+/// router.layer(LoggingMiddleware::new());
+/// ```
+#[cfg(feature = "otel")]
+#[derive(Debug, Clone, Default)]
+pub struct LoggingMiddleware {
+    _private: (),
+}
+
+#[cfg(feature = "otel")]
+impl LoggingMiddleware {
+    /// Create a new logging middleware
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(feature = "otel")]
+use super::RequestId;
+
+#[cfg(feature = "otel")]
+impl Middleware for LoggingMiddleware {
+    fn handle<'a>(&'a self, request: MiddlewareRequest, next: Next<'a>) -> MiddlewareFuture<'a> {
+        use tracing::Instrument;
+
+        let request_id = RequestId::from_request(&request)
+            .or_else(RequestId::current)
+            .unwrap_or_default();
+
+        let span = tracing::info_span!(
+            "request",
+            request_id = %request_id,
+            protocol = %request.protocol,
+            operation = %request.operation,
+        );
+
+        Box::pin(
+            async move {
+                let protocol = request.protocol.clone();
+                let operation = request.operation.clone();
+                let started = std::time::Instant::now();
+
+                let result = request_id.scope(next.run(request)).await;
+
+                let latency_ms = started.elapsed().as_secs_f64() * 1000.0;
+                let status = response_status(&result);
+                match &result {
+                    Ok(_) => tracing::info!(
+                        protocol = %protocol,
+                        operation = %operation,
+                        status = %status,
+                        latency_ms,
+                        "request completed"
+                    ),
+                    Err(error) => tracing::warn!(
+                        protocol = %protocol,
+                        operation = %operation,
+                        status = %status,
+                        latency_ms,
+                        error = %error,
+                        "request failed"
+                    ),
+                }
+                result
+            }
+            .instrument(span),
+        )
+    }
+}
+
+/// Status reported for a completed request
+///
+/// REST adapter responses carry their HTTP status (`HTTP 404 ...`); other
+/// results are reported as `ok` or `error`.
+#[cfg(feature = "otel")]
+fn response_status(result: &Result<String, String>) -> String {
+    match result {
+        Ok(response) => response
+            .strip_prefix("HTTP ")
+            .and_then(|rest| rest.split_whitespace().next())
+            .filter(|code| code.parse::<u16>().is_ok())
+            .unwrap_or("ok")
+            .to_string(),
+        Err(_) => "error".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Tag(&'static str);
+
+    impl Middleware for Tag {
+        fn handle<'a>(
+            &'a self,
+            request: MiddlewareRequest,
+            next: Next<'a>,
+        ) -> MiddlewareFuture<'a> {
+            Box::pin(async move {
+                let response = next.run(request).await?;
+                Ok(format!("{}({})", self.0, response))
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_layers_run_in_order() {
+        let layers: Vec<Arc<dyn Middleware>> = vec![Arc::new(Tag("outer")), Arc::new(Tag("inner"))];
+        let endpoint = |request: MiddlewareRequest| -> MiddlewareFuture<'_> {
+            Box::pin(async move { Ok(request.operation) })
+        };
+
+        let response = Next::new(&layers, &endpoint)
+            .run(MiddlewareRequest::new("handler", "get_user", "{}"))
+            .await
+            .unwrap();
+        assert_eq!(response, "outer(inner(get_user))");
+    }
+
+    #[test]
+    fn test_request_headers_are_case_insensitive() {
+        let request =
+            MiddlewareRequest::new("rest", "GET /users", "").with_header("X-Request-Id", "abc");
+        assert_eq!(request.header("x-request-id"), Some("abc"));
+        assert_eq!(request.header(REQUEST_ID_HEADER), Some("abc"));
+    }
+
+    #[tokio::test]
+    async fn test_request_id_scope() {
+        assert_eq!(request_id(), None);
+        let id = with_request_id("req-1".to_string(), async { request_id() }).await;
+        assert_eq!(id.as_deref(), Some("req-1"));
+    }
+
+    #[cfg(feature = "otel")]
+    #[test]
+    fn test_response_status() {
+        assert_eq!(response_status(&Ok("HTTP 404 {}".to_string())), "404");
+        assert_eq!(response_status(&Ok("{\"id\":1}".to_string())), "ok");
+        assert_eq!(response_status(&Err("boom".to_string())), "error");
+    }
+
+    #[cfg(feature = "otel")]
+    #[tokio::test]
+    async fn test_logging_middleware_propagates_request_id() {
+        let layers: Vec<Arc<dyn Middleware>> = vec![Arc::new(LoggingMiddleware::new())];
+        let endpoint = |_request: MiddlewareRequest| -> MiddlewareFuture<'_> {
+            Box::pin(async move { Ok(request_id().unwrap_or_default()) })
+        };
+
+        let request = MiddlewareRequest::new("handler", "whoami", "{}")
+            .with_header(REQUEST_ID_HEADER, "incoming-id");
+        let response = Next::new(&layers, &endpoint).run(request).await.unwrap();
+        assert_eq!(response, "incoming-id");
+
+        let request = MiddlewareRequest::new("handler", "whoami", "{}")
+            .with_header(REQUEST_ID_HEADER, "not a valid id");
+        let replaced = Next::new(&layers, &endpoint).run(request).await.unwrap();
+        assert_eq!(replaced.len(), 36);
+
+        let request = MiddlewareRequest::new("handler", "whoami", "{}");
+        let generated = Next::new(&layers, &endpoint).run(request).await.unwrap();
+        assert_eq!(generated.len(), 36);
+    }
+}
@@ -65,64 +65,109 @@ use serde::de::DeserializeOwned;
 use serde::Serialize;
 use serde_json::{Map, Value};
 use futures_core::Stream;
-use std::{collections::HashMap, future::Future, pin::Pin, sync::Arc};
+use std::{collections::HashMap, future::Future, pin::Pin, sync::Arc, time::Duration};
 
 pub mod adapter;
 pub mod builder;
+#[cfg(feature = "router-compression")]
+pub mod compression;
 #[cfg(feature = "router")]
 pub mod config;
 pub mod contract;
 pub mod docs;
+pub mod extract;
 pub mod graphiql;
 pub mod graphql;
 pub mod grpc;
 pub mod grpc_explorer;
 pub mod handler;
+mod limits;
 pub mod metadata;
 pub mod method;
+pub mod middleware;
+#[cfg(feature = "router-multipart")]
+pub mod multipart;
 pub mod openapi;
+pub mod request_id;
 pub mod rest;
 pub mod scalar;
 pub mod schema;
+pub mod sse;
 pub mod ts_codegen;
 
 // Production adapters (optional features)
 #[cfg(feature = "router-graphql")]
 pub mod graphql_prod;
+#[cfg(feature = "router-grpc-json")]
+pub mod grpc_json;
 #[cfg(feature = "router-grpc")]
 pub mod grpc_prod;
+#[cfg(feature = "router-grpc-json")]
+pub mod grpc_transcode;
 
-pub use adapter::ProtocolAdapter;
+pub use adapter::{DanglingRoute, ProtocolAdapter, RouterValidationError};
+/// Derive macro generating an `HttpError` implementation
+#[cfg(feature = "router")]
+pub use allframe_macros::HttpError;
 pub use builder::RouteBuilder;
+#[cfg(feature = "router-compression")]
+pub use compression::ContentEncoding;
 #[cfg(feature = "router")]
 pub use config::{GraphQLConfig, GrpcConfig, RestConfig, RouterConfig, ServerConfig};
 pub use contract::{
+    Contract, ContractInteraction, ContractParticipant, ContractRequest, ContractResponse,
     ContractTestConfig, ContractTestResult, ContractTestResults, ContractTestable, ContractTester,
 };
 pub use docs::DocsConfig;
+#[cfg(feature = "router")]
+pub use extract::request_args;
+pub use extract::{from_args, BodyFormat};
 pub use graphiql::{graphiql_html, GraphiQLConfig, GraphiQLTheme};
-pub use graphql::{GraphQLAdapter, GraphQLOperation, OperationType};
+pub use graphql::{query_depth_and_nodes, GraphQLAdapter, GraphQLOperation, OperationType};
 // Re-export production adapters when features are enabled
 #[cfg(feature = "router-graphql")]
 pub use graphql_prod::GraphQLProductionAdapter;
 pub use grpc::{GrpcAdapter, GrpcMethod, GrpcMethodType, GrpcRequest, GrpcStatus};
 pub use grpc_explorer::{grpc_explorer_html, GrpcExplorerConfig, GrpcExplorerTheme};
+#[cfg(feature = "router-grpc-json")]
+pub use grpc_json::GrpcJsonBridge;
 #[cfg(feature = "router-grpc")]
-pub use grpc_prod::{protobuf, status, streaming, GrpcProductionAdapter, GrpcService};
+pub use grpc_prod::{descriptor, protobuf, status, streaming, GrpcProductionAdapter, GrpcService};
+#[cfg(feature = "router-grpc-json")]
+pub use grpc_transcode::{GrpcTranscoder, Transcoded};
 pub use handler::{
-    Handler, HandlerFn, HandlerWithArgs, HandlerWithState, HandlerWithStateOnly,
-    IntoHandlerResult, IntoStreamItem, Json, SharedStateMap, State, StreamError, StreamHandler,
-    StreamReceiver, StreamSender, StreamingHandlerFn, StreamingHandlerWithArgs,
-    StreamingHandlerWithState, StreamingHandlerWithStateOnly, DEFAULT_STREAM_CAPACITY,
+    ExecuteError, Handler, HandlerError, HandlerFn, HandlerWithArgs, HandlerWithState,
+    HandlerWithStateOnly, IntoHandlerResult, IntoStreamItem, Json, SharedStateMap, State,
+    StreamError, StreamHandler, StreamReceiver, StreamSender, StreamingHandlerFn,
+    StreamingHandlerWithArgs, StreamingHandlerWithState, StreamingHandlerWithStateOnly,
+    DEFAULT_STREAM_CAPACITY,
 };
-pub use metadata::RouteMetadata;
+pub use metadata::{RouteMetadata, RouteRateLimit};
 pub use method::Method;
+#[cfg(feature = "otel")]
+pub use middleware::LoggingMiddleware;
+pub use middleware::{
+    request_id, with_request_id, Middleware, MiddlewareFuture, MiddlewareRequest, Next,
+    REQUEST_ID_HEADER,
+};
+#[cfg(feature = "router-multipart")]
+pub use multipart::{Multipart, MultipartConfig, MultipartError, Part};
 pub use openapi::{OpenApiGenerator, OpenApiServer};
-pub use rest::{RestAdapter, RestRequest, RestResponse, RestRoute};
-pub use scalar::{scalar_html, ScalarConfig, ScalarLayout, ScalarTheme};
+pub use request_id::RequestId;
+#[cfg(feature = "router-grpc")]
+pub use request_id::{RequestIdInterceptor, RequestIdService};
+pub use rest::{json_depth, HttpError, RestAdapter, RestRequest, RestResponse, RestRoute};
+pub use scalar::{scalar_html, ScalarAuthScheme, ScalarConfig, ScalarLayout, ScalarTheme};
 pub use schema::ToJsonSchema;
+/// Derive macro generating a `ToJsonSchema` implementation
+#[cfg(feature = "openapi")]
+pub use allframe_macros::ToJsonSchema;
+pub use sse::{sse_event, SseConfig, SseStream, SSE_CONTENT_TYPE};
 pub use ts_codegen::{generate_ts_client, HandlerMeta, TsField, TsType};
 
+use handler::catch_panic;
+use limits::RouteLimits;
+
 /// Drive a `Stream` to completion, forwarding items through a `StreamSender`.
 ///
 /// Returns a JSON result: `"null"` on success, or a JSON error string if a
@@ -231,6 +276,8 @@ pub struct Router {
     states: SharedStateMap,
     handler_metas: HashMap<String, HandlerMeta>,
     key_transform: Option<KeyTransform>,
+    middleware: Vec<Arc<dyn Middleware>>,
+    limits: RouteLimits,
     #[cfg(feature = "router")]
     #[allow(dead_code)]
     config: Option<RouterConfig>,
@@ -247,6 +294,8 @@ impl Router {
             states: Arc::new(std::sync::RwLock::new(HashMap::new())),
             handler_metas: HashMap::new(),
             key_transform: None,
+            middleware: Vec::new(),
+            limits: RouteLimits::default(),
             #[cfg(feature = "router")]
             config: None,
         }
@@ -263,15 +312,25 @@ impl Router {
             states: Arc::new(std::sync::RwLock::new(HashMap::new())),
             handler_metas: HashMap::new(),
             key_transform: None,
+            middleware: Vec::new(),
+            limits: RouteLimits::default(),
             config: Some(config.clone()),
         };
 
         // Auto-register adapters based on config
         if config.has_protocol("rest") {
-            router.add_adapter(Box::new(RestAdapter::new()));
+            let rest = match config.rest() {
+                Some(rest) => RestAdapter::from_config(rest),
+                None => RestAdapter::new(),
+            };
+            router.add_adapter(Box::new(rest));
         }
         if config.has_protocol("graphql") {
-            router.add_adapter(Box::new(GraphQLAdapter::new()));
+            let graphql = match config.graphql() {
+                Some(graphql) => GraphQLAdapter::from_config(graphql),
+                None => GraphQLAdapter::new(),
+            };
+            router.add_adapter(Box::new(graphql));
         }
         if config.has_protocol("grpc") {
             router.add_adapter(Box::new(GrpcAdapter::new()));
@@ -821,17 +880,142 @@ impl Router {
         self.adapters.get(name).map(|b| &**b)
     }
 
+    /// Set the timeout for handlers whose route doesn't set one
+    ///
+    /// Calls still running after `timeout` fail with
+    /// [`ExecuteError::TimedOut`] (`504 Gateway Timeout`).
+    pub fn with_default_timeout(mut self, timeout: Duration) -> Self {
+        self.limits.set_default_timeout(timeout);
+        self
+    }
+
+    /// Set the rate limit for handlers whose route doesn't set one
+    ///
+    /// Each handler gets its own token bucket. Calls over the limit fail
+    /// with [`ExecuteError::RateLimited`] (`429 Too Many Requests`).
+    #[cfg(feature = "resilience")]
+    pub fn with_default_rate_limit(mut self, rps: u32, burst: u32) -> Self {
+        self.limits
+            .set_default_rate_limit(RouteRateLimit { rps, burst });
+        self
+    }
+
+    /// Add a middleware layer
+    ///
+    /// Layers wrap every request dispatched through the router, both direct
+    /// handler calls and protocol adapter requests, in the order they were
+    /// added.
+    pub fn layer<M: Middleware + 'static>(&mut self, middleware: M) -> &mut Self {
+        self.middleware.push(Arc::new(middleware));
+        self
+    }
+
+    /// Check that every adapter route points to a registered handler
+    ///
+    /// Call this once all handlers and adapters are registered, before
+    /// serving, so a typo in a handler name fails at startup rather than as
+    /// an error on the first request. Reports every dangling route at once.
+    pub fn validate(&self) -> Result<(), RouterValidationError> {
+        let mut adapters: Vec<_> = self.adapters.values().collect();
+        adapters.sort_by(|a, b| a.name().cmp(b.name()));
+
+        let dangling: Vec<DanglingRoute> = adapters
+            .into_iter()
+            .flat_map(|adapter| {
+                adapter
+                    .handler_references()
+                    .into_iter()
+                    .map(move |(route, handler)| DanglingRoute {
+                        adapter: adapter.name().to_string(),
+                        route,
+                        handler,
+                    })
+            })
+            .filter(|route| {
+                !self.handlers.contains_key(&route.handler)
+                    && !self.streaming_handlers.contains_key(&route.handler)
+            })
+            .collect();
+
+        if dangling.is_empty() {
+            Ok(())
+        } else {
+            Err(RouterValidationError { dangling })
+        }
+    }
+
+    /// Dispatch a request through the middleware stack
+    ///
+    /// Requests with protocol `handler` call the named handler with the
+    /// payload as args; any other protocol is routed through its adapter.
+    /// The route's timeout and rate limit, if any, apply to the handler or
+    /// adapter call at the end of the stack. A panic in the handler or
+    /// adapter fails the request with an error instead of unwinding into
+    /// the caller.
+    pub async fn dispatch(&self, request: MiddlewareRequest) -> Result<String, String> {
+        self.dispatch_limited(request).await.map_err(String::from)
+    }
+
+    /// Dispatch a request, keeping timeouts and rate limits as typed errors
+    async fn dispatch_limited(&self, request: MiddlewareRequest) -> Result<String, ExecuteError> {
+        // Middleware only sees error strings; remember the last limit hit so
+        // callers still get a 504 or 429 for it
+        let limited = std::sync::Mutex::new(None);
+        let endpoint = |request: MiddlewareRequest| -> MiddlewareFuture<'_> {
+            let limited = &limited;
+            Box::pin(async move {
+                let key = self.limits.key(&request);
+                let call = async {
+                    if request.protocol == "handler" {
+                        self.call_handler_direct(&request.operation, &request.payload)
+                            .await
+                    } else {
+                        let adapter = self
+                            .get_adapter(&request.protocol)
+                            .ok_or_else(|| format!("Adapter not found: {}", request.protocol))?;
+                        catch_panic(&request.operation, adapter.handle(&request.payload)).await
+                    }
+                };
+                match self.limits.run(&key, call).await {
+                    Ok(response) => {
+                        *limited.lock().unwrap() = None;
+                        Ok(response)
+                    }
+                    Err(ExecuteError::Handler(HandlerError(error))) => {
+                        *limited.lock().unwrap() = None;
+                        Err(error)
+                    }
+                    Err(error) => {
+                        let message = error.to_string();
+                        *limited.lock().unwrap() = Some(error);
+                        Err(message)
+                    }
+                }
+            })
+        };
+        let result = Next::new(&self.middleware, &endpoint).run(request).await;
+        result.map_err(|error| {
+            limited
+                .into_inner()
+                .unwrap()
+                .unwrap_or(ExecuteError::Handler(HandlerError(error)))
+        })
+    }
+
     /// Route a request through the appropriate protocol adapter
     pub async fn route_request(&self, protocol: &str, request: &str) -> Result<String, String> {
-        let adapter = self
-            .get_adapter(protocol)
-            .ok_or_else(|| format!("Adapter not found: {}", protocol))?;
-
-        adapter.handle(request).await
+        // Operation is the leading "METHOD /path" or "query Name" part
+        let operation = request
+            .split_whitespace()
+            .take(2)
+            .collect::<Vec<_>>()
+            .join(" ");
+        self.dispatch(MiddlewareRequest::new(protocol, operation, request))
+            .await
     }
 
     /// Execute a handler by name (zero-arg shorthand)
-    pub async fn execute(&self, name: &str) -> Result<String, String> {
+    pub async fn execute(&self, name: &str) -> Result<String, ExecuteError> {
         self.execute_with_args(name, "{}").await
     }
 
@@ -841,7 +1025,28 @@ impl Router {
     }
 
     /// Execute a handler by name with JSON args
-    pub async fn execute_with_args(&self, name: &str, args: &str) -> Result<String, String> {
+    ///
+    /// Returns [`ExecuteError::NotFound`] without running the middleware
+    /// stack if no handler is registered under `name`. The handler's rate
+    /// limit and timeout, if any, apply as in [`Router::dispatch`].
+    pub async fn execute_with_args(&self, name: &str, args: &str) -> Result<String, ExecuteError> {
+        if !self.handlers.contains_key(name) {
+            return Err(ExecuteError::NotFound(name.to_string()));
+        }
+        if self.middleware.is_empty() {
+            self.limits
+                .run(name, self.call_handler_direct(name, args))
+                .await
+        } else {
+            self.dispatch_limited(MiddlewareRequest::new("handler", name, args))
+                .await
+        }
+    }
+
+    /// Call a handler without running the middleware stack
+    ///
+    /// A panicking handler returns an error, like a failing one.
+    async fn call_handler_direct(&self, name: &str, args: &str) -> Result<String, String> {
         let transformed;
         let args = match self.maybe_transform_args(args) {
             Some(t) => {
@@ -851,7 +1056,7 @@ impl Router {
             None => args,
         };
         match self.handlers.get(name) {
-            Some(handler) => handler.call(args).await,
+            Some(handler) => catch_panic(name, handler.call(args)).await,
             None => Err(format!("Handler '{}' not found", name)),
         }
     }
@@ -870,7 +1075,7 @@ impl Router {
     ///
     /// Forwards the request string as args to the handler.
     /// Used by MCP server and Tauri plugin.
-    pub async fn call_handler(&self, name: &str, request: &str) -> Result<String, String> {
+    pub async fn call_handler(&self, name: &str, request: &str) -> Result<String, ExecuteError> {
         self.execute_with_args(name, request).await
     }
 
@@ -898,10 +1103,59 @@ impl Router {
     ///
     /// This stores route metadata that can be used to generate
     /// documentation (OpenAPI, GraphQL schemas, gRPC reflection).
+    ///
+    /// The timeout and rate limit of a REST route apply to the handler
+    /// registered as `METHOD:/path`, the name [`Router::get`] and friends
+    /// use, and to REST requests matching the route. Those of a gRPC route
+    /// apply to requests for its `Service.Method`. GraphQL routes can't
+    /// have their own limits, since one query can select several fields;
+    /// theirs are ignored in favor of the router-wide defaults.
     pub fn add_route(&mut self, metadata: RouteMetadata) {
+        self.limits.set_route(&metadata);
         self.routes.push(metadata);
     }
 
+    /// Register a route configured with a [`RouteBuilder`]
+    ///
+    /// Like [`Router::get`] and friends, but carries the builder's metadata,
+    /// including any per-route timeout and, with the `resilience` feature,
+    /// rate limit:
+    ///
+    /// ```rust
+    /// use std::time::Duration;
+    /// use allframe_core::router::{Method, RouteBuilder, Router};
+    ///
+    /// let mut router = Router::new();
+    /// router.route(
+    ///     RouteBuilder::new("/search", Method::GET)
+    ///         .timeout(Duration::from_secs(5)),
+    ///     || async { "[]".to_string() },
+    /// );
+    /// ```
+    pub fn route<F, Fut>(&mut self, route: RouteBuilder, handler: F)
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = String> + Send + 'static,
+    {
+        let handler_name = format!("{}:{}", route.method(), route.path());
+        self.register(&handler_name, handler);
+        self.add_route(route.build());
+    }
+
+    /// Add gRPC routes reflected from an encoded `FileDescriptorSet`
+    ///
+    /// Registers one route per RPC declared in the descriptors (see
+    /// [`descriptor::routes`]), keeping docs in sync with the `.proto`
+    /// files. Returns the number of routes added.
+    #[cfg(feature = "router-grpc")]
+    pub fn add_grpc_descriptor_set(&mut self, bytes: &[u8]) -> Result<usize, String> {
+        let set = descriptor::decode(bytes)?;
+        let routes = descriptor::routes(&set);
+        let count = routes.len();
+        self.routes.extend(routes);
+        Ok(count)
+    }
+
     /// Get all registered routes
     ///
     /// Returns an immutable reference to all route metadata.
@@ -1017,34 +1271,32 @@ impl Router {
 
     /// Call handler via REST
     pub async fn call_rest(&self, method: &str, path: &str) -> Result<String, String> {
-        let adapter = self
-            .adapters
-            .get("rest")
-            .ok_or_else(|| "REST adapter not enabled".to_string())?;
+        if !self.has_adapter("rest") {
+            return Err("REST adapter not enabled".to_string());
+        }
 
         let request = format!("{} {}", method, path);
-        adapter.handle(&request).await
+        self.route_request("rest", &request).await
     }
 
     /// Call handler via GraphQL
     pub async fn call_graphql(&self, query: &str) -> Result<String, String> {
-        let adapter = self
-            .adapters
-            .get("graphql")
-            .ok_or_else(|| "GraphQL adapter not enabled".to_string())?;
+        if !self.has_adapter("graphql") {
+            return Err("GraphQL adapter not enabled".to_string());
+        }
 
-        adapter.handle(query).await
+        self.route_request("graphql", query).await
     }
 
     /// Call handler via gRPC
     pub async fn call_grpc(&self, method: &str, request: &str) -> Result<String, String> {
-        let adapter = self
-            .adapters
-            .get("grpc")
-            .ok_or_else(|| "gRPC adapter not enabled".to_string())?;
+        if !self.has_adapter("grpc") {
+            return Err("gRPC adapter not enabled".to_string());
+        }
 
         let grpc_request = format!("{}:{}", method, request);
-        adapter.handle(&grpc_request).await
+        self.dispatch(MiddlewareRequest::new("grpc", method, grpc_request))
+            .await
     }
 
     /// Generate Scalar documentation HTML with default configuration
@@ -1962,6 +2214,262 @@ mod tests {
         assert_eq!(rx.recv().await, Some("2".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_execute_distinguishes_not_found_from_handler_error() {
+        struct Passthrough;
+
+        impl Middleware for Passthrough {
+            fn handle<'a>(
+                &'a self,
+                request: MiddlewareRequest,
+                next: Next<'a>,
+            ) -> MiddlewareFuture<'a> {
+                next.run(request)
+            }
+        }
+
+        let mut router = Router::new();
+        router.register_result("fail", || async { Err::<String, _>("boom") });
+
+        assert_eq!(
+            router.execute("missing").await,
+            Err(ExecuteError::NotFound("missing".to_string()))
+        );
+        assert_eq!(
+            router.execute("fail").await,
+            Err(ExecuteError::Handler(HandlerError("boom".to_string())))
+        );
+
+        // Same through the middleware stack
+        router.layer(Passthrough);
+        assert!(matches!(
+            router.execute("missing").await,
+            Err(ExecuteError::NotFound(_))
+        ));
+        assert!(matches!(
+            router.execute("fail").await,
+            Err(ExecuteError::Handler(_))
+        ));
+
+        // Callers migrating from `Result<String, String>`
+        assert_eq!(
+            router.execute("missing").await.map_err(String::from),
+            Err("Handler 'missing' not found".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_route_timeout_overrides_default() {
+        let mut router = Router::new().with_default_timeout(Duration::from_millis(20));
+        router.get("/slow", || async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            "done".to_string()
+        });
+        router.route(
+            RouteBuilder::new("/report", Method::GET).timeout(Duration::from_millis(200)),
+            || async {
+                tokio::time::sleep(Duration::from_millis(50)).await;
+                "report".to_string()
+            },
+        );
+
+        let error = router.execute("GET:/slow").await.unwrap_err();
+        assert_eq!(error, ExecuteError::TimedOut(Duration::from_millis(20)));
+        assert_eq!(error.into_response().status_code(), 504);
+
+        assert_eq!(router.execute("GET:/report").await.unwrap(), "report");
+    }
+
+    #[cfg(feature = "resilience")]
+    #[tokio::test]
+    async fn test_route_rate_limit() {
+        let mut router = Router::new().with_default_rate_limit(1000, 1000);
+        router.route(
+            RouteBuilder::new("/search", Method::GET).rate_limit(1, 1),
+            || async { "[]".to_string() },
+        );
+        router.get("/health", || async { "ok".to_string() });
+
+        assert!(router.execute("GET:/search").await.is_ok());
+        let error = router.execute("GET:/search").await.unwrap_err();
+        assert!(matches!(error, ExecuteError::RateLimited { .. }));
+
+        let response = error.into_response();
+        assert_eq!(response.status_code(), 429);
+        assert_eq!(response.header_value("retry-after"), Some("1"));
+
+        // Other routes fall back to the default limit
+        for _ in 0..10 {
+            assert!(router.execute("GET:/health").await.is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_route_timeout_applies_to_dispatch() {
+        struct Passthrough;
+
+        impl Middleware for Passthrough {
+            fn handle<'a>(
+                &'a self,
+                request: MiddlewareRequest,
+                next: Next<'a>,
+            ) -> MiddlewareFuture<'a> {
+                next.run(request)
+            }
+        }
+
+        let mut router = Router::new();
+        router.route(
+            RouteBuilder::new("/slow", Method::GET).timeout(Duration::from_millis(20)),
+            || async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                "done".to_string()
+            },
+        );
+
+        let error = router
+            .dispatch(MiddlewareRequest::new("handler", "GET:/slow", "{}"))
+            .await
+            .unwrap_err();
+        assert_eq!(error, "Handler timed out after 20ms");
+
+        // Middleware sees the error, callers still get a 504
+        router.layer(Passthrough);
+        let error = router.execute("GET:/slow").await.unwrap_err();
+        assert_eq!(error, ExecuteError::TimedOut(Duration::from_millis(20)));
+    }
+
+    #[cfg(feature = "resilience")]
+    #[tokio::test]
+    async fn test_route_rate_limit_applies_to_adapter_requests() {
+        let mut router = Router::new();
+        router.route(
+            RouteBuilder::new("/users/{id}", Method::GET).rate_limit(1, 1),
+            || async { "User data".to_string() },
+        );
+        router.add_route(
+            RouteMetadata::new("UserService.GetUser", "unary", "grpc").with_rate_limit(1, 1),
+        );
+
+        let mut rest = RestAdapter::new();
+        rest.route("GET", "/users/:id", "GET:/users/{id}");
+        router.add_adapter(Box::new(rest));
+        let mut grpc = GrpcAdapter::new();
+        grpc.unary("UserService", "GetUser", "GET:/users/{id}");
+        router.add_adapter(Box::new(grpc));
+
+        // REST requests share the bucket of the route they match
+        assert!(router.route_request("rest", "GET /users/42").await.is_ok());
+        let error = router
+            .route_request("rest", "GET /users/43?fields=name")
+            .await
+            .unwrap_err();
+        assert!(error.starts_with("Rate limit exceeded"), "{error}");
+
+        assert!(router.call_grpc("UserService.GetUser", "{}").await.is_ok());
+        let error = router
+            .route_request("grpc", "UserService.GetUser:{\"id\":42}")
+            .await
+            .unwrap_err();
+        assert!(error.starts_with("Rate limit exceeded"), "{error}");
+    }
+
+    #[tokio::test]
+    async fn test_panicking_handler_returns_error() {
+        let mut router = Router::new();
+        router.register("explode", || async {
+            if true {
+                panic!("kaboom");
+            }
+            String::new()
+        });
+        router.register("ok", || async { "fine".to_string() });
+
+        let error = router.execute("explode").await.unwrap_err();
+        assert_eq!(
+            error,
+            ExecuteError::Handler(HandlerError(
+                "Handler 'explode' panicked: kaboom".to_string()
+            ))
+        );
+        assert_eq!(error.into_response().status_code(), 500);
+
+        // The router keeps serving
+        assert_eq!(router.execute("ok").await.unwrap(), "fine");
+    }
+
+    #[cfg(feature = "router")]
+    #[tokio::test]
+    async fn test_typed_handler_takes_form_and_path_params() {
+        #[derive(serde::Deserialize)]
+        struct Rename {
+            id: u32,
+            name: String,
+            notify: bool,
+        }
+
+        let mut router = Router::new();
+        router.register_with_args("rename_user", |args: Rename| async move {
+            format!("{} -> {} ({})", args.id, args.name, args.notify)
+        });
+        let mut rest = RestAdapter::new();
+        rest.route("PUT", "/users/:id", "rename_user");
+
+        let (handler, args) = rest
+            .extract_args(
+                "PUT",
+                "/users/42?notify=true",
+                Some("application/x-www-form-urlencoded"),
+                "name=Ada+Lovelace",
+            )
+            .unwrap();
+        assert_eq!(
+            router.execute_with_args(&handler, &args).await.unwrap(),
+            "42 -> Ada Lovelace (true)"
+        );
+
+        let missing = rest.extract_args("PUT", "/teams/1", None, "").unwrap_err();
+        assert_eq!(missing.status_code(), 404);
+    }
+
+    #[test]
+    fn test_validate_reports_dangling_routes() {
+        let mut router = Router::new();
+        router.register("list_users", || async { "[]".to_string() });
+
+        let mut rest = RestAdapter::new();
+        rest.route("GET", "/users", "list_users")
+            .route("GET", "/x", "missing_handler");
+        router.add_adapter(Box::new(rest));
+        let mut graphql = GraphQLAdapter::new();
+        graphql.query("user", "get_user");
+        router.add_adapter(Box::new(graphql));
+
+        let error = router.validate().unwrap_err();
+        assert_eq!(
+            error.dangling,
+            vec![
+                DanglingRoute {
+                    adapter: "graphql".to_string(),
+                    route: "query user".to_string(),
+                    handler: "get_user".to_string(),
+                },
+                DanglingRoute {
+                    adapter: "rest".to_string(),
+                    route: "GET /x".to_string(),
+                    handler: "missing_handler".to_string(),
+                },
+            ]
+        );
+        assert!(error
+            .to_string()
+            .contains("rest GET /x -> 'missing_handler'"));
+
+        router.register("get_user", || async { "{}".to_string() });
+        router.register("missing_handler", || async { "{}".to_string() });
+        assert!(router.validate().is_ok());
+    }
+
     #[tokio::test]
     async fn test_call_streaming_handler_not_found() {
         let router = Router::new();
@@ -2617,8 +3125,10 @@ mod tests {
         let result = router
             .call_handler("test", r#"{"workflowName":"deploy"}"#)
             .await;
-        assert!(result.is_err());
-        assert!(result.unwrap_err().contains("Failed to deserialize"));
+        assert!(matches!(
+            result,
+            Err(ExecuteError::Handler(HandlerError(msg))) if msg.contains("Failed to deserialize")
+        ));
     }
 
     #[tokio::test]
@@ -0,0 +1,476 @@
+//! Multipart form uploads
+//!
+//! Parses `multipart/form-data` request bodies into [`Part`]s, one at a
+//! time as the body streams in. Each part is limited in size, as is the
+//! body as a whole. Parts larger than the in-memory threshold are spooled
+//! to a temporary file as they arrive rather than buffered, so large
+//! uploads don't have to fit in memory.
+//!
+//! ```rust,ignore
+//! use allframe_core::router::{MultipartConfig, RestAdapter};
+//!
+//! let rest = RestAdapter::new().with_multipart(
+//!     MultipartConfig::default().with_max_part_bytes(20 * 1024 * 1024),
+//! );
+//!
+//! let mut multipart = rest.multipart(content_type, body_stream)?;
+//! while let Some(part) = multipart.next_part().await? {
+//!     if part.filename.is_some() {
+//!         part.persist(format!("uploads/{}", uuid)).await?;
+//!     }
+//! }
+//! ```
+
+use std::{
+    fmt, io,
+    path::{Path, PathBuf},
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use futures_core::Stream;
+use multer::{Constraints, SizeLimit};
+use tokio::io::AsyncWriteExt;
+
+use super::rest::HttpError;
+
+/// Default maximum size of a single part (10 MiB)
+pub const DEFAULT_MAX_PART_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Default maximum size of a whole multipart body (50 MiB)
+pub const DEFAULT_MAX_TOTAL_BYTES: u64 = 50 * 1024 * 1024;
+
+/// Default size above which a part is spooled to disk (256 KiB)
+pub const DEFAULT_MEMORY_THRESHOLD: usize = 256 * 1024;
+
+/// Limits and spooling settings for multipart bodies
+#[derive(Debug, Clone)]
+pub struct MultipartConfig {
+    max_part_bytes: u64,
+    max_total_bytes: u64,
+    memory_threshold: usize,
+    temp_dir: PathBuf,
+}
+
+impl Default for MultipartConfig {
+    fn default() -> Self {
+        Self {
+            max_part_bytes: DEFAULT_MAX_PART_BYTES,
+            max_total_bytes: DEFAULT_MAX_TOTAL_BYTES,
+            memory_threshold: DEFAULT_MEMORY_THRESHOLD,
+            temp_dir: std::env::temp_dir(),
+        }
+    }
+}
+
+impl MultipartConfig {
+    /// Set the maximum size of a single part
+    pub fn with_max_part_bytes(mut self, bytes: u64) -> Self {
+        self.max_part_bytes = bytes;
+        self
+    }
+
+    /// Set the maximum size of the whole body
+    pub fn with_max_total_bytes(mut self, bytes: u64) -> Self {
+        self.max_total_bytes = bytes;
+        self
+    }
+
+    /// Set the size above which a part is written to a temporary file
+    pub fn with_memory_threshold(mut self, bytes: usize) -> Self {
+        self.memory_threshold = bytes;
+        self
+    }
+
+    /// Set the directory spooled parts are written to
+    ///
+    /// Defaults to the system temporary directory.
+    pub fn with_temp_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.temp_dir = dir.into();
+        self
+    }
+}
+
+/// Errors from reading a multipart body
+#[derive(Debug)]
+pub enum MultipartError {
+    /// The `Content-Type` isn't `multipart/form-data` with a boundary
+    InvalidContentType(String),
+    /// A part exceeded the per-part size limit
+    PartTooLarge {
+        /// Name of the form field
+        name: Option<String>,
+        /// The limit, in bytes
+        limit: u64,
+    },
+    /// The body exceeded the total size limit
+    BodyTooLarge {
+        /// The limit, in bytes
+        limit: u64,
+    },
+    /// The body isn't valid multipart data
+    Malformed(String),
+    /// Spooling a part to disk failed
+    Io(io::Error),
+}
+
+impl fmt::Display for MultipartError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MultipartError::InvalidContentType(e) => write!(f, "Invalid content type: {}", e),
+            MultipartError::PartTooLarge {
+                name: Some(name),
+                limit,
+            } => write!(f, "Part '{}' exceeds limit of {} bytes", name, limit),
+            MultipartError::PartTooLarge { name: None, limit } => {
+                write!(f, "Part exceeds limit of {} bytes", limit)
+            }
+            MultipartError::BodyTooLarge { limit } => {
+                write!(f, "Multipart body exceeds limit of {} bytes", limit)
+            }
+            MultipartError::Malformed(e) => write!(f, "Malformed multipart body: {}", e),
+            MultipartError::Io(e) => write!(f, "Failed to store upload: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for MultipartError {}
+
+impl From<io::Error> for MultipartError {
+    fn from(error: io::Error) -> Self {
+        MultipartError::Io(error)
+    }
+}
+
+impl From<multer::Error> for MultipartError {
+    fn from(error: multer::Error) -> Self {
+        match error {
+            multer::Error::FieldSizeExceeded { limit, field_name } => {
+                MultipartError::PartTooLarge {
+                    name: field_name,
+                    limit,
+                }
+            }
+            multer::Error::StreamSizeExceeded { limit } => MultipartError::BodyTooLarge { limit },
+            multer::Error::NoMultipart
+            | multer::Error::NoBoundary
+            | multer::Error::DecodeContentType(_) => {
+                MultipartError::InvalidContentType(error.to_string())
+            }
+            other => MultipartError::Malformed(other.to_string()),
+        }
+    }
+}
+
+impl HttpError for MultipartError {
+    fn status_code(&self) -> u16 {
+        match self {
+            MultipartError::InvalidContentType(_) => 415,
+            MultipartError::PartTooLarge { .. } | MultipartError::BodyTooLarge { .. } => 413,
+            MultipartError::Malformed(_) => 400,
+            MultipartError::Io(_) => 500,
+        }
+    }
+}
+
+/// Temporary file removed when dropped
+#[derive(Debug)]
+struct TempFile {
+    path: PathBuf,
+}
+
+impl TempFile {
+    /// Create a file with a random name in `dir`, readable only by its
+    /// owner
+    async fn create(dir: &Path) -> io::Result<(Self, tokio::fs::File)> {
+        let dir = dir.to_path_buf();
+        let (file, path) = tokio::task::spawn_blocking(move || {
+            tempfile::Builder::new()
+                .prefix("allframe-upload-")
+                .tempfile_in(dir)?
+                .keep()
+                .map_err(|e| e.error)
+        })
+        .await
+        .map_err(|e| io::Error::new(io::ErrorKind::Other, e))??;
+        Ok((Self { path }, tokio::fs::File::from_std(file)))
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+#[derive(Debug)]
+enum PartData {
+    Memory(Bytes),
+    File(TempFile),
+}
+
+/// A part of a multipart body
+///
+/// Small parts are held in memory; larger ones live in a temporary file
+/// that is deleted when the part is dropped, unless it is
+/// [persisted](Self::persist).
+#[derive(Debug)]
+pub struct Part {
+    /// Form field name
+    pub name: Option<String>,
+    /// File name, for file uploads
+    pub filename: Option<String>,
+    /// Content type of the part, if given
+    pub content_type: Option<String>,
+    len: u64,
+    data: PartData,
+}
+
+impl Part {
+    /// Size of the part's content in bytes
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Check if the part is empty
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Path of the temporary file holding the content, if spooled to disk
+    pub fn path(&self) -> Option<&Path> {
+        match &self.data {
+            PartData::Memory(_) => None,
+            PartData::File(file) => Some(&file.path),
+        }
+    }
+
+    /// Read the content into memory
+    pub async fn bytes(&self) -> io::Result<Bytes> {
+        match &self.data {
+            PartData::Memory(bytes) => Ok(bytes.clone()),
+            PartData::File(file) => tokio::fs::read(&file.path).await.map(Bytes::from),
+        }
+    }
+
+    /// Read the content as UTF-8 text
+    pub async fn text(&self) -> io::Result<String> {
+        let bytes = self.bytes().await?;
+        String::from_utf8(bytes.to_vec()).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Save the content to `path`
+    ///
+    /// A spooled part is moved into place when `path` is on the same
+    /// filesystem as the temporary file, and copied otherwise.
+    pub async fn persist(self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        match &self.data {
+            PartData::Memory(bytes) => tokio::fs::write(path, bytes).await,
+            PartData::File(file) => {
+                if tokio::fs::rename(&file.path, path).await.is_err() {
+                    tokio::fs::copy(&file.path, path).await?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// A `multipart/form-data` body being read
+///
+/// Created with [`Multipart::new`] from a stream of body chunks, or with
+/// [`RestAdapter::multipart`](super::RestAdapter::multipart) using the
+/// adapter's limits.
+pub struct Multipart {
+    inner: multer::Multipart<'static>,
+    config: MultipartConfig,
+}
+
+impl Multipart {
+    /// Start reading a body with the given `Content-Type`
+    ///
+    /// Fails if the content type isn't `multipart/form-data` with a
+    /// boundary.
+    pub fn new<S, E>(
+        content_type: &str,
+        body: S,
+        config: MultipartConfig,
+    ) -> Result<Self, MultipartError>
+    where
+        S: Stream<Item = Result<Bytes, E>> + Send + 'static,
+        E: Into<Box<dyn std::error::Error + Send + Sync>> + 'static,
+    {
+        let boundary = multer::parse_boundary(content_type)?;
+        let constraints = Constraints::new().size_limit(
+            SizeLimit::new()
+                .per_field(config.max_part_bytes)
+                .whole_stream(config.max_total_bytes),
+        );
+        Ok(Self {
+            inner: multer::Multipart::with_constraints(body, boundary, constraints),
+            config,
+        })
+    }
+
+    /// Start reading a body that is already in memory
+    pub fn from_bytes(
+        content_type: &str,
+        body: impl Into<Bytes>,
+        config: MultipartConfig,
+    ) -> Result<Self, MultipartError> {
+        Self::new(content_type, Once(Some(body.into())), config)
+    }
+
+    /// Read the next part, or `None` at the end of the body
+    pub async fn next_part(&mut self) -> Result<Option<Part>, MultipartError> {
+        let Some(mut field) = self.inner.next_field().await? else {
+            return Ok(None);
+        };
+        let name = field.name().map(str::to_string);
+        let filename = field.file_name().map(str::to_string);
+        let content_type = field.content_type().map(|mime| mime.to_string());
+
+        let mut buffer = Vec::new();
+        let mut spooled: Option<(TempFile, tokio::fs::File)> = None;
+        let mut len = 0;
+        while let Some(chunk) = field.chunk().await? {
+            len += chunk.len() as u64;
+            if spooled.is_none() && buffer.len() + chunk.len() > self.config.memory_threshold {
+                let (temp, mut file) = TempFile::create(&self.config.temp_dir).await?;
+                file.write_all(&buffer).await?;
+                buffer = Vec::new();
+                spooled = Some((temp, file));
+            }
+            match &mut spooled {
+                Some((_, file)) => file.write_all(&chunk).await?,
+                None => buffer.extend_from_slice(&chunk),
+            }
+        }
+
+        let data = match spooled {
+            Some((temp, mut file)) => {
+                file.flush().await?;
+                PartData::File(temp)
+            }
+            None => PartData::Memory(Bytes::from(buffer)),
+        };
+        Ok(Some(Part {
+            name,
+            filename,
+            content_type,
+            len,
+            data,
+        }))
+    }
+}
+
+impl fmt::Debug for Multipart {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Multipart")
+            .field("config", &self.config)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Stream yielding a single chunk
+struct Once(Option<Bytes>);
+
+impl Stream for Once {
+    type Item = Result<Bytes, std::convert::Infallible>;
+
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(self.0.take().map(Ok))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTENT_TYPE: &str = "multipart/form-data; boundary=X-BOUNDARY";
+
+    fn body(parts: &[(&str, Option<&str>, &str)]) -> String {
+        let mut body = String::new();
+        for (name, filename, content) in parts {
+            body.push_str("--X-BOUNDARY\r\n");
+            match filename {
+                Some(filename) => body.push_str(&format!(
+                    "Content-Disposition: form-data; name=\"{}\"; filename=\"{}\"\r\nContent-Type: text/plain\r\n",
+                    name, filename
+                )),
+                None => body.push_str(&format!(
+                    "Content-Disposition: form-data; name=\"{}\"\r\n",
+                    name
+                )),
+            }
+            body.push_str(&format!("\r\n{}\r\n", content));
+        }
+        body.push_str("--X-BOUNDARY--\r\n");
+        body
+    }
+
+    #[tokio::test]
+    async fn test_reads_fields_and_files() {
+        let body = body(&[
+            ("title", None, "Quarterly report"),
+            ("document", Some("report.txt"), "line one\nline two"),
+        ]);
+        let mut multipart =
+            Multipart::from_bytes(CONTENT_TYPE, body, MultipartConfig::default()).unwrap();
+
+        let title = multipart.next_part().await.unwrap().unwrap();
+        assert_eq!(title.name.as_deref(), Some("title"));
+        assert_eq!(title.filename, None);
+        assert_eq!(title.text().await.unwrap(), "Quarterly report");
+
+        let document = multipart.next_part().await.unwrap().unwrap();
+        assert_eq!(document.filename.as_deref(), Some("report.txt"));
+        assert_eq!(document.content_type.as_deref(), Some("text/plain"));
+        assert_eq!(document.len(), 17);
+        assert!(document.path().is_none());
+
+        assert!(multipart.next_part().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_large_parts_spool_to_disk() {
+        let content = "x".repeat(4096);
+        let body = body(&[("upload", Some("big.bin"), &content)]);
+        let config = MultipartConfig::default().with_memory_threshold(1024);
+        let mut multipart = Multipart::from_bytes(CONTENT_TYPE, body, config).unwrap();
+
+        let part = multipart.next_part().await.unwrap().unwrap();
+        let path = part.path().unwrap().to_path_buf();
+        assert!(path.exists());
+        assert_eq!(part.len(), 4096);
+        assert_eq!(part.bytes().await.unwrap(), content.as_bytes());
+
+        // The temporary file goes away with the part
+        drop(part);
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_enforces_limits() {
+        let body = body(&[("upload", Some("big.bin"), &"x".repeat(100))]);
+        let config = MultipartConfig::default().with_max_part_bytes(10);
+        let mut multipart = Multipart::from_bytes(CONTENT_TYPE, body.clone(), config).unwrap();
+        let error = multipart.next_part().await.unwrap_err();
+        assert!(matches!(
+            error,
+            MultipartError::PartTooLarge { limit: 10, .. }
+        ));
+        assert_eq!(error.status_code(), 413);
+
+        let config = MultipartConfig::default().with_max_total_bytes(50);
+        let mut multipart = Multipart::from_bytes(CONTENT_TYPE, body, config).unwrap();
+        let error = multipart.next_part().await.unwrap_err();
+        assert!(matches!(error, MultipartError::BodyTooLarge { limit: 50 }));
+
+        let error = Multipart::from_bytes("application/json", "{}", MultipartConfig::default())
+            .unwrap_err();
+        assert_eq!(error.status_code(), 415);
+    }
+}
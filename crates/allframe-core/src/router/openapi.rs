@@ -88,6 +88,29 @@ impl OpenApiGenerator {
         self
     }
 
+    /// Add a server URL with a description
+    ///
+    /// Call once per environment; servers are emitted in the spec's
+    /// `servers` array in the order they were added, and the docs UI offers
+    /// them as "Try It" targets.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use allframe_core::router::{openapi::OpenApiGenerator, Router};
+    ///
+    /// let spec = OpenApiGenerator::new("API", "1.0.0")
+    ///     .add_server("http://localhost:3000", "Local")
+    ///     .add_server("https://staging.example.com", "Staging")
+    ///     .add_server("https://api.example.com", "Production")
+    ///     .generate(&Router::new());
+    ///
+    /// assert_eq!(spec["servers"].as_array().unwrap().len(), 3);
+    /// ```
+    pub fn add_server(self, url: impl Into<String>, description: impl Into<String>) -> Self {
+        self.with_server(url, Some(description))
+    }
+
     /// Add multiple servers
     pub fn with_servers(mut self, servers: Vec<OpenApiServer>) -> Self {
         self.servers = servers;
@@ -367,4 +390,31 @@ mod tests {
 
         assert_eq!(spec["info"]["description"], "A great API");
     }
+
+    #[tokio::test]
+    async fn test_openapi_multiple_servers() {
+        let router = Router::new();
+
+        let spec = OpenApiGenerator::new("Test API", "1.0.0")
+            .add_server("http://localhost:3000", "Local")
+            .add_server("https://staging.example.com", "Staging")
+            .add_server("https://api.example.com", "Production")
+            .generate(&router);
+
+        let servers = spec["servers"].as_array().unwrap();
+        assert_eq!(servers.len(), 3);
+        assert_eq!(servers[0]["url"], "http://localhost:3000");
+        assert_eq!(servers[0]["description"], "Local");
+        assert_eq!(servers[1]["url"], "https://staging.example.com");
+        assert_eq!(servers[1]["description"], "Staging");
+        assert_eq!(servers[2]["url"], "https://api.example.com");
+        assert_eq!(servers[2]["description"], "Production");
+    }
+
+    #[tokio::test]
+    async fn test_openapi_without_servers() {
+        let spec = OpenApiGenerator::new("Test API", "1.0.0").generate(&Router::new());
+
+        assert!(spec.get("servers").is_none());
+    }
 }
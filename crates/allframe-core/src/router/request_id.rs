@@ -0,0 +1,440 @@
+//! Request correlation ids across transports
+//!
+//! A [`RequestId`] is taken from the incoming request, or generated at the
+//! edge, and carried unchanged through every hop:
+//!
+//! - HTTP: the `x-request-id` header ([`REQUEST_ID_HEADER`])
+//! - gRPC: the `x-request-id` metadata key, read and echoed by
+//!   [`RequestIdService`] and sent by [`RequestIdInterceptor`]
+//! - tracing: the `request_id` field of the `request` span opened by
+//!   [`LoggingMiddleware`](super::LoggingMiddleware), exported with OTel spans
+//! - handlers: [`RequestId::current`], a task-local set for the request
+//!
+//! ```rust
+//! use allframe_core::router::RequestId;
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let id = RequestId::parse("req-42").unwrap();
+//! let seen = id.scope(async { RequestId::current() }).await;
+//! assert_eq!(seen.unwrap().as_str(), "req-42");
+//!
+//! let mut headers = Vec::new();
+//! RequestId::new().inject_headers(&mut headers);
+//! assert_eq!(headers[0].0, "x-request-id");
+//! # }
+//! ```
+
+use std::{
+    fmt,
+    future::Future,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use super::middleware::{request_id, with_request_id, MiddlewareRequest, REQUEST_ID_HEADER};
+
+/// A validated request correlation id
+///
+/// Incoming ids are accepted if they are 1 to [`RequestId::MAX_LEN`]
+/// visible ASCII characters, so they are safe to echo in headers and log
+/// fields. Generated ids are UUID-formatted.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct RequestId(String);
+
+impl RequestId {
+    /// Longest id accepted from a request
+    pub const MAX_LEN: usize = 128;
+
+    /// Generate a new random id
+    pub fn new() -> Self {
+        Self(generate())
+    }
+
+    /// Validate an incoming id
+    pub fn parse(value: &str) -> Result<Self, String> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Err("Request id is empty".to_string());
+        }
+        if value.len() > Self::MAX_LEN {
+            return Err(format!(
+                "Request id is longer than {} characters",
+                Self::MAX_LEN
+            ));
+        }
+        if !value.bytes().all(|b| b.is_ascii_graphic()) {
+            return Err("Request id must be visible ASCII".to_string());
+        }
+        Ok(Self(value.to_string()))
+    }
+
+    /// The id as a string
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The id of the request currently being handled, if any
+    pub fn current() -> Option<Self> {
+        request_id().map(Self)
+    }
+
+    /// The current request's id, or a new one outside a request
+    pub fn current_or_new() -> Self {
+        Self::current().unwrap_or_default()
+    }
+
+    /// Run `future` with this id as the current request id
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        with_request_id(self.0, future).await
+    }
+
+    /// Read the id from a middleware request's `x-request-id` header
+    ///
+    /// Returns `None` if the header is missing or invalid.
+    pub fn from_request(request: &MiddlewareRequest) -> Option<Self> {
+        request
+            .header(REQUEST_ID_HEADER)
+            .and_then(|value| Self::parse(value).ok())
+    }
+
+    /// Read the id from `(name, value)` headers (names compared
+    /// case-insensitively)
+    pub fn from_headers<'a, I>(headers: I) -> Option<Self>
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        headers
+            .into_iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(REQUEST_ID_HEADER))
+            .and_then(|(_, value)| Self::parse(value).ok())
+    }
+
+    /// Set the `x-request-id` header, replacing any existing one
+    pub fn inject_headers(&self, headers: &mut Vec<(String, String)>) {
+        headers.retain(|(name, _)| !name.eq_ignore_ascii_case(REQUEST_ID_HEADER));
+        headers.push((REQUEST_ID_HEADER.to_string(), self.0.clone()));
+    }
+
+    /// Read the id from HTTP headers
+    #[cfg(feature = "router-grpc")]
+    pub fn from_http_headers(headers: &tonic::codegen::http::HeaderMap) -> Option<Self> {
+        headers
+            .get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| Self::parse(value).ok())
+    }
+
+    /// Set the `x-request-id` HTTP header
+    #[cfg(feature = "router-grpc")]
+    pub fn inject_http_headers(&self, headers: &mut tonic::codegen::http::HeaderMap) {
+        if let Ok(value) = tonic::codegen::http::HeaderValue::from_str(&self.0) {
+            headers.insert(REQUEST_ID_HEADER, value);
+        }
+    }
+
+    /// Read the id from gRPC request metadata
+    #[cfg(feature = "router-grpc")]
+    pub fn from_metadata(metadata: &tonic::metadata::MetadataMap) -> Option<Self> {
+        metadata
+            .get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| Self::parse(value).ok())
+    }
+
+    /// Set the `x-request-id` gRPC metadata entry
+    #[cfg(feature = "router-grpc")]
+    pub fn inject_metadata(&self, metadata: &mut tonic::metadata::MetadataMap) {
+        if let Ok(value) = self.0.parse() {
+            metadata.insert(REQUEST_ID_HEADER, value);
+        }
+    }
+}
+
+impl Default for RequestId {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for RequestId {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<RequestId> for String {
+    fn from(id: RequestId) -> Self {
+        id.0
+    }
+}
+
+/// Random 128 bits in UUID v4 layout
+///
+/// Uses std's randomly keyed hasher rather than a crate, so ids are
+/// available without the `otel` feature. Ids only need to be unique, not
+/// unpredictable.
+fn generate() -> String {
+    use std::{
+        collections::hash_map::RandomState,
+        hash::{BuildHasher, Hasher},
+        time::{SystemTime, UNIX_EPOCH},
+    };
+
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or_default();
+
+    let mut halves = [0u64; 2];
+    for (i, half) in halves.iter_mut().enumerate() {
+        let mut hasher = RandomState::new().build_hasher();
+        hasher.write_u64(count);
+        hasher.write_u64(nanos);
+        hasher.write_usize(i);
+        *half = hasher.finish();
+    }
+    let hi = (halves[0] & !0xf000) | 0x4000;
+    let lo = (halves[1] & !(0b11 << 62)) | (0b10 << 62);
+    format!(
+        "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+        hi >> 32,
+        (hi >> 16) & 0xffff,
+        hi & 0xffff,
+        lo >> 48,
+        lo & 0xffff_ffff_ffff
+    )
+}
+
+/// gRPC client interceptor sending the current request id
+///
+/// Sets `x-request-id` metadata on outgoing calls to the id of the request
+/// being handled, so downstream services log the same id. Calls made
+/// outside a request get a new id; an id already set on the call is kept.
+///
+/// ```rust,ignore
+/// let client = MyServiceClient::with_interceptor(channel, RequestIdInterceptor);
+/// ```
+#[cfg(feature = "router-grpc")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RequestIdInterceptor;
+
+#[cfg(feature = "router-grpc")]
+impl tonic::service::Interceptor for RequestIdInterceptor {
+    fn call(
+        &mut self,
+        mut request: tonic::Request<()>,
+    ) -> Result<tonic::Request<()>, tonic::Status> {
+        if RequestId::from_metadata(request.metadata()).is_none() {
+            RequestId::current_or_new().inject_metadata(request.metadata_mut());
+        }
+        Ok(request)
+    }
+}
+
+/// gRPC server wrapper scoping each call to its request id
+///
+/// Reads `x-request-id` from the call's metadata (or generates an id), makes
+/// it the current [`RequestId`] while the service handles the call, opens a
+/// `request` tracing span with the id when `otel` is enabled, and echoes it
+/// in the response headers.
+///
+/// ```rust,ignore
+/// tonic::transport::Server::builder()
+///     .add_service(RequestIdService::new(MyServiceServer::new(my_service)))
+/// ```
+#[cfg(feature = "router-grpc")]
+#[derive(Debug, Clone)]
+pub struct RequestIdService<S> {
+    inner: S,
+}
+
+#[cfg(feature = "router-grpc")]
+impl<S> RequestIdService<S> {
+    /// Wrap a gRPC service
+    pub fn new(inner: S) -> Self {
+        Self { inner }
+    }
+}
+
+#[cfg(feature = "router-grpc")]
+impl<S: tonic::server::NamedService> tonic::server::NamedService for RequestIdService<S> {
+    const NAME: &'static str = S::NAME;
+}
+
+#[cfg(feature = "router-grpc")]
+impl<S, ReqBody, ResBody> tonic::codegen::Service<tonic::codegen::http::Request<ReqBody>>
+    for RequestIdService<S>
+where
+    S: tonic::codegen::Service<
+            tonic::codegen::http::Request<ReqBody>,
+            Response = tonic::codegen::http::Response<ResBody>,
+        > + Clone
+        + Send
+        + 'static,
+    S::Future: Send,
+    ReqBody: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = std::pin::Pin<
+        Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send + 'static>,
+    >;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: tonic::codegen::http::Request<ReqBody>) -> Self::Future {
+        // Call the instance that was polled ready
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        let id = RequestId::from_http_headers(request.headers()).unwrap_or_default();
+        #[cfg(feature = "otel")]
+        let span = tracing::info_span!("request", request_id = %id, protocol = "grpc");
+
+        let call = async move {
+            let scoped = id.clone().scope(async move { inner.call(request).await });
+            let mut response = scoped.await?;
+            id.inject_http_headers(response.headers_mut());
+            Ok(response)
+        };
+        #[cfg(feature = "otel")]
+        let call = tracing::Instrument::instrument(call, span);
+        Box::pin(call)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_validates() {
+        assert_eq!(RequestId::parse(" abc-123 ").unwrap().as_str(), "abc-123");
+        assert!(RequestId::parse("").is_err());
+        assert!(RequestId::parse("has space").is_err());
+        assert!(RequestId::parse("line\nbreak").is_err());
+        assert!(RequestId::parse(&"x".repeat(RequestId::MAX_LEN + 1)).is_err());
+    }
+
+    #[test]
+    fn test_generated_ids_are_unique_uuids() {
+        let a = RequestId::new();
+        let b = RequestId::new();
+        assert_ne!(a, b);
+        assert_eq!(a.as_str().len(), 36);
+        assert_eq!(a.as_str().as_bytes()[14], b'4');
+        assert!(matches!(
+            a.as_str().as_bytes()[19],
+            b'8' | b'9' | b'a' | b'b'
+        ));
+    }
+
+    #[test]
+    fn test_headers_round_trip() {
+        let mut headers = vec![("X-Request-Id".to_string(), "old".to_string())];
+        let id = RequestId::parse("req-1").unwrap();
+        id.inject_headers(&mut headers);
+        assert_eq!(headers, [("x-request-id".to_string(), "req-1".to_string())]);
+
+        let found = RequestId::from_headers(headers.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+        assert_eq!(found, Some(id));
+        assert_eq!(RequestId::from_headers([("x-request-id", "bad id")]), None);
+    }
+
+    #[tokio::test]
+    async fn test_current_in_scope() {
+        assert_eq!(RequestId::current(), None);
+        let id = RequestId::parse("req-2").unwrap();
+        let (current, or_new) = id
+            .clone()
+            .scope(async { (RequestId::current(), RequestId::current_or_new()) })
+            .await;
+        assert_eq!(current, Some(id.clone()));
+        assert_eq!(or_new, id);
+    }
+
+    #[cfg(feature = "router-grpc")]
+    #[tokio::test]
+    async fn test_grpc_metadata_and_interceptor() {
+        use tonic::service::Interceptor;
+
+        let mut metadata = tonic::metadata::MetadataMap::new();
+        RequestId::parse("req-3")
+            .unwrap()
+            .inject_metadata(&mut metadata);
+        assert_eq!(
+            RequestId::from_metadata(&metadata).unwrap().as_str(),
+            "req-3"
+        );
+
+        let request = RequestId::parse("req-4")
+            .unwrap()
+            .scope(async { RequestIdInterceptor.call(tonic::Request::new(())) })
+            .await
+            .unwrap();
+        assert_eq!(
+            RequestId::from_metadata(request.metadata())
+                .unwrap()
+                .as_str(),
+            "req-4"
+        );
+    }
+
+    #[cfg(feature = "router-grpc")]
+    #[tokio::test]
+    async fn test_grpc_service_scopes_and_echoes_id() {
+        use std::{
+            convert::Infallible,
+            task::{Context, Poll},
+        };
+
+        use tonic::codegen::{http, Service};
+
+        #[derive(Clone)]
+        struct Echo;
+
+        impl Service<http::Request<()>> for Echo {
+            type Response = http::Response<String>;
+            type Error = Infallible;
+            type Future = std::future::Ready<Result<Self::Response, Self::Error>>;
+
+            fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Infallible>> {
+                Poll::Ready(Ok(()))
+            }
+
+            fn call(&mut self, _: http::Request<()>) -> Self::Future {
+                let id = RequestId::current().map(String::from).unwrap_or_default();
+                std::future::ready(Ok(http::Response::new(id)))
+            }
+        }
+
+        let mut service = RequestIdService::new(Echo);
+        let request = http::Request::builder()
+            .header(REQUEST_ID_HEADER, "req-5")
+            .body(())
+            .unwrap();
+        let response = service.call(request).await.unwrap();
+        assert_eq!(response.body(), "req-5");
+        assert_eq!(response.headers()[REQUEST_ID_HEADER], "req-5");
+
+        let response = service.call(http::Request::new(())).await.unwrap();
+        assert_eq!(response.body().len(), 36);
+        assert_eq!(
+            response.headers()[REQUEST_ID_HEADER],
+            response.body().as_str()
+        );
+    }
+}
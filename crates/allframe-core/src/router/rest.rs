@@ -2,9 +2,93 @@
 //!
 //! Provides REST/HTTP support for the protocol-agnostic router.
 
-use std::{future::Future, pin::Pin};
+#[cfg(feature = "router")]
+use std::time::SystemTime;
+use std::{fmt, future::Future, pin::Pin};
 
+use serde::Serialize;
+
+#[cfg(feature = "router-compression")]
+use super::compression::ContentEncoding;
+#[cfg(feature = "router-multipart")]
+use super::multipart::{Multipart, MultipartConfig, MultipartError};
 use super::ProtocolAdapter;
+#[cfg(feature = "router")]
+use super::{extract::request_args, RestConfig};
+
+/// Default maximum request body size (1 MiB)
+pub const DEFAULT_MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Default maximum nesting depth of JSON request bodies
+pub const DEFAULT_MAX_JSON_DEPTH: usize = 64;
+
+/// Maximum nesting depth of arrays and objects in a JSON document
+///
+/// Scans the raw text without deserializing it, so it is safe to run on
+/// untrusted input before handing it to `serde_json`.
+pub fn json_depth(input: &str) -> usize {
+    let mut depth = 0usize;
+    let mut max_depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for byte in input.bytes() {
+        if in_string {
+            match byte {
+                _ if escaped => escaped = false,
+                b'\\' => escaped = true,
+                b'"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match byte {
+            b'"' => in_string = true,
+            b'{' | b'[' => {
+                depth += 1;
+                max_depth = max_depth.max(depth);
+            }
+            b'}' | b']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    max_depth
+}
+
+/// Errors that map onto an HTTP status code
+///
+/// Usually implemented with `#[derive(HttpError)]`, the REST counterpart to
+/// `#[derive(GrpcError)]`:
+///
+/// ```rust,ignore
+/// use allframe::HttpError;
+///
+/// #[derive(Debug, thiserror::Error, HttpError)]
+/// pub enum UserError {
+///     #[error("User not found: {0}")]
+///     #[http(404)]
+///     NotFound(String),
+///
+///     #[error("Invalid email")]
+///     #[http(BAD_REQUEST)]
+///     InvalidEmail,
+/// }
+/// ```
+pub trait HttpError: fmt::Display {
+    /// HTTP status code for this error
+    fn status_code(&self) -> u16;
+
+    /// Convert the error into a JSON `{"error": "..."}` response
+    fn into_response(self) -> RestResponse
+    where
+        Self: Sized,
+    {
+        RestResponse::json(&serde_json::json!({ "error": self.to_string() }))
+            .with_status(self.status_code())
+    }
+}
 
 /// REST route definition
 #[derive(Debug, Clone)]
@@ -66,6 +150,27 @@ impl RestRoute {
 
         true
     }
+
+    /// Values of the path parameters in a matching path
+    ///
+    /// Returns `None` if the path doesn't match this route.
+    pub fn path_params(&self, path: &str) -> Option<Vec<(String, String)>> {
+        if !self.matches_path(path) {
+            return None;
+        }
+        let route_segments = self.path.split('/').filter(|s| !s.is_empty());
+        let path_segments = path.split('/').filter(|s| !s.is_empty());
+        Some(
+            route_segments
+                .zip(path_segments)
+                .filter_map(|(route_seg, path_seg)| {
+                    route_seg
+                        .strip_prefix(':')
+                        .map(|name| (name.to_string(), path_seg.to_string()))
+                })
+                .collect(),
+        )
+    }
 }
 
 /// REST adapter for HTTP requests
@@ -73,12 +178,104 @@ impl RestRoute {
 /// Handles REST/HTTP protocol-specific request/response transformation.
 pub struct RestAdapter {
     routes: Vec<RestRoute>,
+    max_body_bytes: usize,
+    max_json_depth: usize,
+    #[cfg(feature = "router-multipart")]
+    multipart: MultipartConfig,
+    #[cfg(feature = "router-compression")]
+    compression_min_bytes: Option<usize>,
 }
 
 impl RestAdapter {
     /// Create a new REST adapter
     pub fn new() -> Self {
-        Self { routes: Vec::new() }
+        Self {
+            routes: Vec::new(),
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            max_json_depth: DEFAULT_MAX_JSON_DEPTH,
+            #[cfg(feature = "router-multipart")]
+            multipart: MultipartConfig::default(),
+            #[cfg(feature = "router-compression")]
+            compression_min_bytes: None,
+        }
+    }
+
+    /// Create a REST adapter using the limits from a [`RestConfig`]
+    #[cfg(feature = "router")]
+    pub fn from_config(config: &RestConfig) -> Self {
+        let adapter = Self::new().with_limits(config.max_body_bytes(), config.max_json_depth());
+        #[cfg(feature = "router-compression")]
+        let adapter = match config.compression_min_bytes() {
+            Some(min_bytes) => adapter.with_compression(min_bytes),
+            None => adapter,
+        };
+        adapter
+    }
+
+    /// Set the maximum body size in bytes and JSON nesting depth
+    pub fn with_limits(mut self, max_body_bytes: usize, max_json_depth: usize) -> Self {
+        self.max_body_bytes = max_body_bytes;
+        self.max_json_depth = max_json_depth;
+        self
+    }
+
+    /// Set the limits for `multipart/form-data` uploads
+    #[cfg(feature = "router-multipart")]
+    pub fn with_multipart(mut self, config: MultipartConfig) -> Self {
+        self.multipart = config;
+        self
+    }
+
+    /// Compress response bodies of at least `min_bytes` bytes
+    ///
+    /// Applied by [`compress`](Self::compress) and
+    /// [`render_compressed`](Self::render_compressed), with gzip or brotli
+    /// as negotiated from the request's `Accept-Encoding`.
+    #[cfg(feature = "router-compression")]
+    pub fn with_compression(mut self, min_bytes: usize) -> Self {
+        self.compression_min_bytes = Some(min_bytes);
+        self
+    }
+
+    /// Start reading a `multipart/form-data` body with this adapter's limits
+    ///
+    /// Multipart bodies are binary and can be large, so they are read from
+    /// a stream of chunks instead of going through
+    /// [`check_body`](Self::check_body). Parts are returned one at a time
+    /// by [`Multipart::next_part`].
+    #[cfg(feature = "router-multipart")]
+    pub fn multipart<S, E>(&self, content_type: &str, body: S) -> Result<Multipart, MultipartError>
+    where
+        S: futures_core::Stream<Item = Result<bytes::Bytes, E>> + Send + 'static,
+        E: Into<Box<dyn std::error::Error + Send + Sync>> + 'static,
+    {
+        Multipart::new(content_type, body, self.multipart.clone())
+    }
+
+    /// Check a request body against the configured limits
+    ///
+    /// Runs before deserialization. Oversized bodies are rejected with
+    /// `413 Payload Too Large`, overly nested JSON with `400 Bad Request`.
+    pub fn check_body(&self, body: &str) -> Result<(), RestResponse> {
+        if body.len() > self.max_body_bytes {
+            let error = format!(
+                "Request body of {} bytes exceeds limit of {} bytes",
+                body.len(),
+                self.max_body_bytes
+            );
+            return Err(RestResponse::json(&serde_json::json!({ "error": error })).with_status(413));
+        }
+
+        let depth = json_depth(body);
+        if depth > self.max_json_depth {
+            let error = format!(
+                "JSON nesting depth {} exceeds limit of {}",
+                depth, self.max_json_depth
+            );
+            return Err(RestResponse::json(&serde_json::json!({ "error": error })).with_status(400));
+        }
+
+        Ok(())
     }
 
     /// Register a REST route
@@ -94,6 +291,33 @@ impl RestAdapter {
             .find(|r| r.method == method && r.matches_path(path))
     }
 
+    /// Resolve a request to its handler and the handler's args
+    ///
+    /// `target` is the request path with an optional query string. The args
+    /// merge the body, query string and path parameters as described in the
+    /// [`extract`](super::extract) module. Fails with the response to send:
+    /// `404` if no route matches, or the status from the body limits or
+    /// [`request_args`].
+    #[cfg(feature = "router")]
+    pub fn extract_args(
+        &self,
+        method: &str,
+        target: &str,
+        content_type: Option<&str>,
+        body: &str,
+    ) -> Result<(String, String), RestResponse> {
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let route = self.match_route(method, path).ok_or_else(|| {
+            RestResponse::json(&serde_json::json!({ "error": "Not Found", "path": path }))
+                .with_status(404)
+        })?;
+        self.check_body(body)?;
+
+        let path_params = route.path_params(path).unwrap_or_default();
+        let args = request_args(content_type, body, query, &path_params)?;
+        Ok((route.handler.clone(), args.to_string()))
+    }
+
     /// Parse an HTTP request string
     ///
     /// Format: `METHOD /path [body]`
@@ -117,6 +341,177 @@ impl RestAdapter {
         format!("HTTP {} {}", status, body)
     }
 
+    /// Build the response for a fallible handler's result
+    ///
+    /// `Ok` values are serialized to a `200` JSON body, errors are rendered
+    /// with their [`HttpError`] status code.
+    pub fn respond<T, E>(&self, result: Result<T, E>) -> RestResponse
+    where
+        T: Serialize,
+        E: HttpError,
+    {
+        match result {
+            Ok(value) => RestResponse::json(&value),
+            Err(err) => err.into_response(),
+        }
+    }
+
+    /// Render a [`RestResponse`] as an HTTP/1.1 response message
+    ///
+    /// Headers are written in the order they were set, followed by
+    /// `content-length` unless the status forbids a body.
+    pub fn render_response(&self, response: &RestResponse) -> String {
+        let mut out = render_head(response, response.body().len());
+        if !is_bodiless(response.status_code()) {
+            out.push_str(response.body());
+        }
+        out
+    }
+
+    /// Compress a response's body if compression is enabled and the client
+    /// accepts it
+    ///
+    /// Returns the response with `content-encoding` and `vary` set, along
+    /// with the compressed body to send in place of
+    /// [`RestResponse::body`], or the response unchanged (apart from
+    /// `vary`) and `None` if the body is sent as-is. Responses that already
+    /// have a `content-encoding`, bodiless statuses and bodies below the
+    /// minimum size aren't compressed.
+    ///
+    /// Run [`conditional`](Self::conditional) first, so the ETag is hashed
+    /// from the uncompressed body. A strong ETag is made weak on compressed
+    /// responses, since the bytes sent differ from the ones hashed;
+    /// `If-None-Match` uses weak comparison, so revalidation still matches.
+    #[cfg(feature = "router-compression")]
+    pub fn compress(
+        &self,
+        request_headers: &[(String, String)],
+        response: RestResponse,
+    ) -> (RestResponse, Option<Vec<u8>>) {
+        let Some(min_bytes) = self.compression_min_bytes else {
+            return (response, None);
+        };
+        if is_bodiless(response.status_code())
+            || response.body().len() < min_bytes
+            || response.header_value("content-encoding").is_some()
+        {
+            return (response, None);
+        }
+
+        let vary = match response.header_value("vary") {
+            Some(vary)
+                if vary
+                    .split(',')
+                    .any(|v| v.trim().eq_ignore_ascii_case("accept-encoding")) =>
+            {
+                vary.to_string()
+            }
+            Some(vary) => format!("{}, accept-encoding", vary),
+            None => "accept-encoding".to_string(),
+        };
+        let response = response.header("vary", vary);
+
+        let encoding = request_headers
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case("accept-encoding"))
+            .and_then(|(_, value)| ContentEncoding::negotiate(value));
+        let Some(encoding) = encoding else {
+            return (response, None);
+        };
+
+        let body = encoding.compress(response.body().as_bytes());
+        let mut response = response.header("content-encoding", encoding.as_str());
+        if let Some(etag) = response.header_value("etag") {
+            if !etag.starts_with("W/") {
+                let weak = format!("W/{}", etag);
+                response = response.header("etag", weak);
+            }
+        }
+        (response, Some(body))
+    }
+
+    /// Render a response as an HTTP/1.1 response message, compressing the
+    /// body as described in [`compress`](Self::compress)
+    #[cfg(feature = "router-compression")]
+    pub fn render_compressed(
+        &self,
+        request_headers: &[(String, String)],
+        response: RestResponse,
+    ) -> Vec<u8> {
+        let (response, compressed) = self.compress(request_headers, response);
+        let body = match &compressed {
+            Some(body) => body.as_slice(),
+            None => response.body().as_bytes(),
+        };
+        let mut out = render_head(&response, body.len()).into_bytes();
+        if !is_bodiless(response.status_code()) {
+            out.extend_from_slice(body);
+        }
+        out
+    }
+
+    /// Answer a conditional request with `304 Not Modified` if possible
+    ///
+    /// For `GET` and `HEAD` requests answered with `200`, adds an `etag`
+    /// hashed from the body unless the handler set one with
+    /// [`RestResponse::etag`]. Then, if the request's `If-None-Match`
+    /// matches the ETag, or, without `If-None-Match`, its
+    /// `If-Modified-Since` is no earlier than the response's
+    /// `last-modified`, returns a bodiless `304` carrying the caching
+    /// headers. Other responses are returned unchanged. `If-Modified-Since`
+    /// is only honored with the `router` feature.
+    pub fn conditional(
+        &self,
+        method: &str,
+        request_headers: &[(String, String)],
+        response: RestResponse,
+    ) -> RestResponse {
+        if !matches!(method, "GET" | "HEAD") || response.status_code() != 200 {
+            return response;
+        }
+        let response = match response.header_value("etag") {
+            Some(_) => response,
+            None => {
+                let tag = format!("{:016x}", fnv1a(response.body().as_bytes()));
+                response.etag(tag)
+            }
+        };
+
+        let request_header = |name: &str| {
+            request_headers
+                .iter()
+                .find(|(existing, _)| existing.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.as_str())
+        };
+        let not_modified = match request_header("if-none-match") {
+            Some(if_none_match) => response
+                .header_value("etag")
+                .is_some_and(|etag| etag_matches(if_none_match, etag)),
+            #[cfg(feature = "router")]
+            None => {
+                let since = request_header("if-modified-since")
+                    .and_then(|date| httpdate::parse_http_date(date).ok());
+                let modified = response
+                    .header_value("last-modified")
+                    .and_then(|date| httpdate::parse_http_date(date).ok());
+                matches!((since, modified), (Some(since), Some(modified)) if modified <= since)
+            }
+            #[cfg(not(feature = "router"))]
+            None => false,
+        };
+        if !not_modified {
+            return response;
+        }
+
+        let mut not_modified = RestResponse::new(304, String::new());
+        for (name, value) in response.headers() {
+            if CACHE_HEADERS.iter().any(|h| h.eq_ignore_ascii_case(name)) {
+                not_modified = not_modified.header(name.clone(), value.clone());
+            }
+        }
+        not_modified
+    }
+
     /// Build a simulated HTTP request for testing
     ///
     /// In a real implementation, this would parse actual HTTP requests.
@@ -146,6 +541,18 @@ impl ProtocolAdapter for RestAdapter {
         "rest"
     }
 
+    fn handler_references(&self) -> Vec<(String, String)> {
+        self.routes
+            .iter()
+            .map(|route| {
+                (
+                    format!("{} {}", route.method, route.path),
+                    route.handler.clone(),
+                )
+            })
+            .collect()
+    }
+
     fn handle(
         &self,
         request: &str,
@@ -153,6 +560,12 @@ impl ProtocolAdapter for RestAdapter {
         // Parse the HTTP request before async block
         let parse_result = self.parse_request(request);
 
+        // Enforce body limits before anything deserializes the body
+        let limit_result = match &parse_result {
+            Ok((_, _, Some(body))) => self.check_body(body),
+            _ => Ok(()),
+        };
+
         // Clone routes for async block
         let routes = self.routes.clone();
 
@@ -166,6 +579,11 @@ impl ProtocolAdapter for RestAdapter {
                 }
             };
 
+            if let Err(response) = limit_result {
+                let response = format!("HTTP {} {}", response.status_code(), response.body());
+                return Ok(response);
+            }
+
             // Find matching route
             let matched_route = routes
                 .iter()
@@ -204,32 +622,219 @@ pub struct RestRequest {
     pub path: String,
 }
 
-/// Simplified HTTP response representation
+/// HTTP response with status, headers and body
 ///
-/// For MVP testing purposes. Full implementation will use proper HTTP types.
+/// Built with [`RestResponse::ok`], [`RestResponse::json`] or
+/// [`RestResponse::no_content`] and refined with the `with_status`/`header`
+/// builders:
+///
+/// ```rust
+/// use allframe_core::router::RestResponse;
+///
+/// let response = RestResponse::json(&serde_json::json!({ "id": 42 }))
+///     .with_status(201)
+///     .header("location", "/users/42");
+///
+/// assert_eq!(response.status_code(), 201);
+/// assert_eq!(response.header_value("content-type"), Some("application/json"));
+/// ```
 #[derive(Debug, Clone)]
 pub struct RestResponse {
     status: u16,
+    headers: Vec<(String, String)>,
     body: String,
 }
 
 impl RestResponse {
     /// Create a new response
     pub fn new(status: u16, body: String) -> Self {
-        Self { status, body }
+        Self {
+            status,
+            headers: Vec::new(),
+            body,
+        }
+    }
+
+    /// Create a `200 OK` response with the given body
+    pub fn ok(body: impl Into<String>) -> Self {
+        Self::new(200, body.into())
+    }
+
+    /// Create a `200 OK` response with a JSON-serialized body
+    ///
+    /// Sets `content-type: application/json`. If serialization fails the
+    /// response is a `500` with a JSON error body instead.
+    pub fn json<T: Serialize + ?Sized>(value: &T) -> Self {
+        let response = match serde_json::to_string(value) {
+            Ok(body) => Self::new(200, body),
+            Err(e) => Self::new(
+                500,
+                serde_json::json!({ "error": e.to_string() }).to_string(),
+            ),
+        };
+        response.header("content-type", "application/json")
+    }
+
+    /// Create a `204 No Content` response with an empty body
+    pub fn no_content() -> Self {
+        Self::new(204, String::new())
+    }
+
+    /// Set the HTTP status code
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Set a response header, replacing any existing value
+    ///
+    /// Header names are compared case-insensitively.
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        let name = name.into();
+        let value = value.into();
+        match self
+            .headers
+            .iter_mut()
+            .find(|(existing, _)| existing.eq_ignore_ascii_case(&name))
+        {
+            Some(entry) => entry.1 = value,
+            None => self.headers.push((name, value)),
+        }
+        self
+    }
+
+    /// Set the `etag` header
+    ///
+    /// Use a value that changes whenever the content does, such as a
+    /// version number or a content hash. Unquoted tags are quoted; weak tags
+    /// (`W/"..."`) are kept as-is.
+    pub fn etag(self, tag: impl Into<String>) -> Self {
+        let tag = tag.into();
+        let tag = if tag.starts_with('"') || tag.starts_with("W/") {
+            tag
+        } else {
+            format!("\"{}\"", tag)
+        };
+        self.header("etag", tag)
+    }
+
+    /// Set the `last-modified` header
+    #[cfg(feature = "router")]
+    pub fn last_modified(self, time: SystemTime) -> Self {
+        self.header("last-modified", httpdate::fmt_http_date(time))
     }
 
     /// Get the HTTP status code
-    pub fn status(&self) -> u16 {
+    pub fn status_code(&self) -> u16 {
         self.status
     }
 
+    /// Get the HTTP status code (same as [`status_code`](Self::status_code))
+    pub fn status(&self) -> u16 {
+        self.status_code()
+    }
+
+    /// Get the response headers in the order they were set
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// Get a header value by name (case-insensitive)
+    pub fn header_value(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(existing, _)| existing.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
     /// Get the response body
     pub fn body(&self) -> &str {
         &self.body
     }
 }
 
+/// Whether a status forbids a response body
+fn is_bodiless(status: u16) -> bool {
+    matches!(status, 100..=199 | 204 | 304)
+}
+
+/// Status line and headers of a response whose body is `body_len` bytes
+fn render_head(response: &RestResponse, body_len: usize) -> String {
+    let mut out = format!(
+        "HTTP/1.1 {} {}\r\n",
+        response.status_code(),
+        reason_phrase(response.status_code())
+    );
+    for (name, value) in response.headers() {
+        out.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    if !is_bodiless(response.status_code()) && response.header_value("content-length").is_none() {
+        out.push_str(&format!("content-length: {}\r\n", body_len));
+    }
+    out.push_str("\r\n");
+    out
+}
+
+/// Headers a `304 Not Modified` response repeats from the full response
+const CACHE_HEADERS: &[&str] = &[
+    "cache-control",
+    "content-location",
+    "date",
+    "etag",
+    "expires",
+    "last-modified",
+    "vary",
+];
+
+/// Whether an `If-None-Match` header matches an ETag
+///
+/// Uses weak comparison, as required for `If-None-Match`.
+fn etag_matches(if_none_match: &str, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let etag = opaque(etag);
+    if_none_match
+        .split(',')
+        .any(|candidate| candidate.trim() == "*" || opaque(candidate) == etag)
+}
+
+/// 64-bit FNV-1a hash, stable across processes and builds
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Standard reason phrase for an HTTP status code
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        202 => "Accepted",
+        204 => "No Content",
+        301 => "Moved Permanently",
+        302 => "Found",
+        304 => "Not Modified",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        409 => "Conflict",
+        410 => "Gone",
+        412 => "Precondition Failed",
+        413 => "Payload Too Large",
+        415 => "Unsupported Media Type",
+        422 => "Unprocessable Entity",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        501 => "Not Implemented",
+        502 => "Bad Gateway",
+        503 => "Service Unavailable",
+        504 => "Gateway Timeout",
+        _ => "",
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -414,6 +1019,168 @@ mod tests {
         assert!(response.contains("/users/42"));
     }
 
+    #[derive(Debug)]
+    enum TestError {
+        Missing,
+        Broken,
+    }
+
+    impl fmt::Display for TestError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                TestError::Missing => write!(f, "missing"),
+                TestError::Broken => write!(f, "broken"),
+            }
+        }
+    }
+
+    impl HttpError for TestError {
+        fn status_code(&self) -> u16 {
+            match self {
+                TestError::Missing => 404,
+                TestError::Broken => 500,
+            }
+        }
+    }
+
+    #[test]
+    fn test_http_error_into_response() {
+        let response = TestError::Missing.into_response();
+        assert_eq!(response.status_code(), 404);
+        assert_eq!(response.body(), "{\"error\":\"missing\"}");
+        assert_eq!(
+            response.header_value("content-type"),
+            Some("application/json")
+        );
+    }
+
+    #[test]
+    fn test_respond_uses_error_status() {
+        let adapter = RestAdapter::new();
+
+        let ok = adapter.respond::<_, TestError>(Ok(vec![1, 2, 3]));
+        assert_eq!(ok.status_code(), 200);
+        assert_eq!(ok.body(), "[1,2,3]");
+
+        let err = adapter.respond::<(), _>(Err(TestError::Broken));
+        assert_eq!(err.status_code(), 500);
+        assert!(err.body().contains("broken"));
+    }
+
+    #[test]
+    fn test_rest_response_builder() {
+        let response = RestResponse::ok("created")
+            .header("x-foo", "bar")
+            .header("Cache-Control", "no-store")
+            .with_status(201);
+
+        assert_eq!(response.status_code(), 201);
+        assert_eq!(response.body(), "created");
+        assert_eq!(response.header_value("X-Foo"), Some("bar"));
+        assert_eq!(
+            response.headers(),
+            &[
+                ("x-foo".to_string(), "bar".to_string()),
+                ("Cache-Control".to_string(), "no-store".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rest_response_header_replaces_existing() {
+        let response = RestResponse::json(&42).header("Content-Type", "application/problem+json");
+
+        assert_eq!(response.headers().len(), 1);
+        assert_eq!(
+            response.header_value("content-type"),
+            Some("application/problem+json")
+        );
+    }
+
+    #[test]
+    fn test_rest_response_json() {
+        let response = RestResponse::json(&serde_json::json!({ "id": 1 }));
+
+        assert_eq!(response.status_code(), 200);
+        assert_eq!(response.body(), "{\"id\":1}");
+        assert_eq!(
+            response.header_value("content-type"),
+            Some("application/json")
+        );
+    }
+
+    #[test]
+    fn test_render_response() {
+        let adapter = RestAdapter::new();
+        let response = RestResponse::json(&serde_json::json!({ "id": 1 }))
+            .with_status(201)
+            .header("location", "/users/1");
+
+        assert_eq!(
+            adapter.render_response(&response),
+            "HTTP/1.1 201 Created\r\n\
+             content-type: application/json\r\n\
+             location: /users/1\r\n\
+             content-length: 8\r\n\
+             \r\n\
+             {\"id\":1}"
+        );
+    }
+
+    #[test]
+    fn test_render_no_content() {
+        let adapter = RestAdapter::new();
+        let response = RestResponse::no_content().header("x-request-id", "abc");
+
+        assert_eq!(
+            adapter.render_response(&response),
+            "HTTP/1.1 204 No Content\r\nx-request-id: abc\r\n\r\n"
+        );
+    }
+
+    #[test]
+    fn test_json_depth() {
+        assert_eq!(json_depth("42"), 0);
+        assert_eq!(json_depth(r#"{"a":1}"#), 1);
+        assert_eq!(json_depth(r#"{"a":[{"b":[1,2]}]}"#), 4);
+        // Brackets inside strings don't count
+        assert_eq!(json_depth(r#"{"a":"[[[{{\"}}"}"#), 1);
+    }
+
+    #[test]
+    fn test_check_body_limits() {
+        let adapter = RestAdapter::new().with_limits(16, 2);
+
+        assert!(adapter.check_body(r#"{"a":[1]}"#).is_ok());
+
+        let too_large = adapter.check_body(&"x".repeat(17)).unwrap_err();
+        assert_eq!(too_large.status_code(), 413);
+        assert!(too_large.body().contains("exceeds limit of 16 bytes"));
+
+        let too_deep = adapter.check_body("[[[1]]]").unwrap_err();
+        assert_eq!(too_deep.status_code(), 400);
+        assert!(too_deep
+            .body()
+            .contains("JSON nesting depth 3 exceeds limit of 2"));
+    }
+
+    #[tokio::test]
+    async fn test_handle_rejects_oversized_and_deep_bodies() {
+        let mut adapter = RestAdapter::new().with_limits(64, 3);
+        adapter.route("POST", "/users", "create_user");
+
+        let large = format!("POST /users \"{}\"", "x".repeat(100));
+        let response = adapter.handle(&large).await.unwrap();
+        assert!(response.starts_with("HTTP 413 "));
+
+        let response = adapter.handle("POST /users [[[[1]]]]").await.unwrap();
+        assert!(response.starts_with("HTTP 400 "));
+        assert!(response.contains("JSON nesting depth 4"));
+
+        let response = adapter.handle("POST /users [[[1]]]").await.unwrap();
+        assert!(response.starts_with("HTTP 200 "));
+    }
+
     #[test]
     fn test_rest_route_new() {
         let route = RestRoute::new("GET", "/users", "list_users");
@@ -440,4 +1207,160 @@ mod tests {
         assert!(!route.matches_path("/users/42/posts"));
         assert!(!route.matches_path("/users/42/posts/100/extra"));
     }
+
+    fn request_headers(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_matching_if_none_match_returns_304() {
+        let adapter = RestAdapter::new();
+        let response = || RestResponse::ok("[1,2,3]").header("cache-control", "max-age=60");
+
+        // The first response carries a hashed ETag
+        let first = adapter.conditional("GET", &[], response());
+        assert_eq!(first.status_code(), 200);
+        let etag = first.header_value("etag").unwrap().to_string();
+        assert!(etag.starts_with('"') && etag.ends_with('"'));
+
+        let headers = request_headers(&[("If-None-Match", &format!("\"other\", W/{}", etag))]);
+        let cached = adapter.conditional("GET", &headers, response());
+        assert_eq!(cached.status_code(), 304);
+        assert_eq!(cached.body(), "");
+        assert_eq!(cached.header_value("etag"), Some(etag.as_str()));
+        assert_eq!(cached.header_value("cache-control"), Some("max-age=60"));
+        assert_eq!(cached.header_value("content-type"), None);
+        assert!(!adapter.render_response(&cached).contains("[1,2,3]"));
+
+        // A changed body gets a different ETag
+        let changed = adapter.conditional("GET", &headers, RestResponse::ok("[1,2,3,4]"));
+        assert_eq!(changed.status_code(), 200);
+        assert_ne!(changed.header_value("etag"), Some(etag.as_str()));
+
+        // Only safe methods are answered from cache
+        let posted = adapter.conditional("POST", &headers, response());
+        assert_eq!(posted.status_code(), 200);
+        assert_eq!(posted.header_value("etag"), None);
+    }
+
+    #[cfg(feature = "router")]
+    #[test]
+    fn test_handler_etag_and_last_modified() {
+        let adapter = RestAdapter::new();
+        let modified = SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_700_000_000);
+        let response = || RestResponse::ok("v7").etag("v7").last_modified(modified);
+
+        let headers = request_headers(&[("if-none-match", "\"v7\"")]);
+        assert_eq!(
+            adapter
+                .conditional("HEAD", &headers, response())
+                .status_code(),
+            304
+        );
+        let headers = request_headers(&[("if-none-match", "*")]);
+        assert_eq!(
+            adapter
+                .conditional("GET", &headers, response())
+                .status_code(),
+            304
+        );
+
+        let since = httpdate::fmt_http_date(modified);
+        let headers = request_headers(&[("If-Modified-Since", &since)]);
+        let cached = adapter.conditional("GET", &headers, response());
+        assert_eq!(cached.status_code(), 304);
+        assert_eq!(cached.header_value("last-modified"), Some(since.as_str()));
+
+        let earlier = httpdate::fmt_http_date(modified - std::time::Duration::from_secs(1));
+        let headers = request_headers(&[("If-Modified-Since", &earlier)]);
+        assert_eq!(
+            adapter
+                .conditional("GET", &headers, response())
+                .status_code(),
+            200
+        );
+
+        // If-None-Match takes precedence over If-Modified-Since
+        let headers =
+            request_headers(&[("If-None-Match", "\"v6\""), ("If-Modified-Since", &since)]);
+        assert_eq!(
+            adapter
+                .conditional("GET", &headers, response())
+                .status_code(),
+            200
+        );
+    }
+
+    #[cfg(feature = "router-compression")]
+    #[test]
+    fn test_compress_negotiates_encoding() {
+        use std::io::Read;
+
+        let adapter = RestAdapter::new().with_compression(64);
+        let body = serde_json::json!({ "items": vec!["item"; 50] }).to_string();
+        let headers = request_headers(&[("Accept-Encoding", "gzip")]);
+
+        let (response, compressed) = adapter.compress(&headers, RestResponse::ok(body.clone()));
+        assert_eq!(response.header_value("content-encoding"), Some("gzip"));
+        assert_eq!(response.header_value("vary"), Some("accept-encoding"));
+        let mut decoded = String::new();
+        flate2::read::GzDecoder::new(compressed.unwrap().as_slice())
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(decoded, body);
+
+        // Small bodies and clients without Accept-Encoding get the body as-is
+        let (small, compressed) = adapter.compress(&headers, RestResponse::ok("[1]"));
+        assert!(compressed.is_none());
+        assert_eq!(small.header_value("content-encoding"), None);
+        let (plain, compressed) = adapter.compress(&[], RestResponse::ok(body.clone()));
+        assert!(compressed.is_none());
+        assert_eq!(plain.header_value("vary"), Some("accept-encoding"));
+
+        // Compression is off by default
+        let (_, compressed) = RestAdapter::new().compress(&headers, RestResponse::ok(body));
+        assert!(compressed.is_none());
+    }
+
+    #[cfg(feature = "router-compression")]
+    #[test]
+    fn test_compressed_etag_revalidates() {
+        let adapter = RestAdapter::new().with_compression(16);
+        let response = || RestResponse::ok("[1,2,3,4,5,6,7,8,9,10]");
+        let accept = ("Accept-Encoding", "br");
+
+        let first = adapter.conditional("GET", &[], response());
+        let strong = first.header_value("etag").unwrap().to_string();
+        let (first, _) = adapter.compress(&request_headers(&[accept]), first);
+        let etag = first.header_value("etag").unwrap().to_string();
+        assert_eq!(etag, format!("W/{}", strong));
+        assert_eq!(first.header_value("content-encoding"), Some("br"));
+
+        // The weak ETag the client cached still matches the uncompressed hash
+        let headers = request_headers(&[accept, ("If-None-Match", &etag)]);
+        let cached = adapter.conditional("GET", &headers, response());
+        assert_eq!(cached.status_code(), 304);
+        let rendered = adapter.render_compressed(&headers, cached);
+        assert!(rendered.ends_with(b"\r\n\r\n"));
+        assert!(!String::from_utf8_lossy(&rendered).contains("content-encoding"));
+    }
+
+    #[cfg(feature = "router-compression")]
+    #[test]
+    fn test_render_compressed() {
+        let adapter = RestAdapter::new().with_compression(16);
+        let body = "x".repeat(100);
+        let headers = request_headers(&[("accept-encoding", "gzip")]);
+
+        let rendered = adapter.render_compressed(&headers, RestResponse::ok(body));
+        let split = rendered.windows(4).position(|w| w == b"\r\n\r\n").unwrap();
+        let head = String::from_utf8(rendered[..split].to_vec()).unwrap();
+        let compressed = &rendered[split + 4..];
+        assert!(head.contains("content-encoding: gzip"));
+        assert!(head.contains(&format!("content-length: {}", compressed.len())));
+        assert!(compressed.len() < 100);
+    }
 }
@@ -25,6 +25,18 @@
 //!     .custom_css("body { font-family: 'Inter'; }");
 //! let html = router.scalar_docs(config, "My API", "1.0.0");
 //! ```
+//!
+//! # Authentication
+//!
+//! For APIs behind auth, [`ScalarConfig::with_auth`] adds a token field to
+//! the "Try It" panel so users can paste credentials and call protected
+//! routes. Everything stays client-side; no token is embedded in the page.
+//!
+//! ```rust
+//! use allframe_core::router::{ScalarAuthScheme, ScalarConfig};
+//!
+//! let config = ScalarConfig::new().with_auth(ScalarAuthScheme::Bearer);
+//! ```
 
 use serde::{Deserialize, Serialize};
 
@@ -52,6 +64,51 @@ pub enum ScalarLayout {
     Modern,
 }
 
+/// Authentication scheme pre-configured in the Scalar "Try It" panel
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ScalarAuthScheme {
+    /// `Authorization: Bearer <token>`
+    Bearer,
+    /// `Authorization: Basic <credentials>`
+    Basic,
+    /// API key sent in the named header
+    ApiKey {
+        /// Header name (e.g., "X-API-Key")
+        header: String,
+    },
+}
+
+impl ScalarAuthScheme {
+    /// Name of the security scheme in the OpenAPI spec
+    pub fn scheme_name(&self) -> &'static str {
+        match self {
+            ScalarAuthScheme::Bearer => "bearerAuth",
+            ScalarAuthScheme::Basic => "basicAuth",
+            ScalarAuthScheme::ApiKey { .. } => "apiKeyAuth",
+        }
+    }
+
+    /// OpenAPI security scheme object for this scheme
+    pub fn security_scheme(&self) -> serde_json::Value {
+        match self {
+            ScalarAuthScheme::Bearer => serde_json::json!({
+                "type": "http",
+                "scheme": "bearer",
+            }),
+            ScalarAuthScheme::Basic => serde_json::json!({
+                "type": "http",
+                "scheme": "basic",
+            }),
+            ScalarAuthScheme::ApiKey { header } => serde_json::json!({
+                "type": "apiKey",
+                "in": "header",
+                "name": header,
+            }),
+        }
+    }
+}
+
 /// Configuration for Scalar UI
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ScalarConfig {
@@ -77,6 +134,8 @@ pub struct ScalarConfig {
     pub fallback_cdn_url: Option<String>,
     /// Proxy URL for "Try It" requests to avoid CORS issues (optional)
     pub proxy_url: Option<String>,
+    /// Authentication scheme for "Try It" requests (optional)
+    pub auth: Option<ScalarAuthScheme>,
 }
 
 impl Default for ScalarConfig {
@@ -93,6 +152,7 @@ impl Default for ScalarConfig {
             sri_hash: None,
             fallback_cdn_url: None,
             proxy_url: None,
+            auth: None,
         }
     }
 }
@@ -208,6 +268,26 @@ impl ScalarConfig {
         self
     }
 
+    /// Pre-configure an authentication scheme for "Try It" requests
+    ///
+    /// The UI shows an empty credential field (e.g., a bearer token input)
+    /// that users fill in the browser. If the spec does not already define
+    /// the scheme, it is added to `components.securitySchemes`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use allframe_core::router::{ScalarAuthScheme, ScalarConfig};
+    ///
+    /// let config = ScalarConfig::new().with_auth(ScalarAuthScheme::ApiKey {
+    ///     header: "X-API-Key".to_string(),
+    /// });
+    /// ```
+    pub fn with_auth(mut self, scheme: ScalarAuthScheme) -> Self {
+        self.auth = Some(scheme);
+        self
+    }
+
     /// Generate the configuration JSON for Scalar
     pub fn to_json(&self) -> serde_json::Value {
         let mut config = serde_json::json!({
@@ -223,6 +303,26 @@ impl ScalarConfig {
             config["proxy"] = serde_json::Value::String(proxy.clone());
         }
 
+        // Pre-select the auth scheme with empty credentials for the user to fill
+        if let Some(ref auth) = self.auth {
+            let mut authentication = serde_json::json!({
+                "preferredSecurityScheme": auth.scheme_name(),
+            });
+            match auth {
+                ScalarAuthScheme::Bearer => {
+                    authentication["http"] = serde_json::json!({ "bearer": { "token": "" } });
+                }
+                ScalarAuthScheme::Basic => {
+                    authentication["http"] =
+                        serde_json::json!({ "basic": { "username": "", "password": "" } });
+                }
+                ScalarAuthScheme::ApiKey { .. } => {
+                    authentication["apiKey"] = serde_json::json!({ "token": "" });
+                }
+            }
+            config["authentication"] = authentication;
+        }
+
         config
     }
 }
@@ -241,6 +341,11 @@ impl ScalarConfig {
 pub fn scalar_html(config: &ScalarConfig, title: &str, openapi_spec_json: &str) -> String {
     let configuration = config.to_json();
 
+    let openapi_spec = match &config.auth {
+        Some(auth) => with_security_scheme(openapi_spec_json, auth),
+        None => openapi_spec_json.to_string(),
+    };
+
     let custom_style = if let Some(css) = &config.custom_css {
         format!("<style>{}</style>", css)
     } else {
@@ -302,11 +407,41 @@ pub fn scalar_html(config: &ScalarConfig, title: &str, openapi_spec_json: &str)
         custom_style = custom_style,
         fallback_script = fallback_script,
         configuration = configuration,
-        openapi_spec = openapi_spec_json,
+        openapi_spec = openapi_spec,
         script_attrs = script_attrs,
     )
 }
 
+/// Add the auth scheme to the spec's security schemes unless already defined
+fn with_security_scheme(openapi_spec_json: &str, auth: &ScalarAuthScheme) -> String {
+    let Ok(mut spec) = serde_json::from_str::<serde_json::Value>(openapi_spec_json) else {
+        return openapi_spec_json.to_string();
+    };
+    let Some(root) = spec.as_object_mut() else {
+        return openapi_spec_json.to_string();
+    };
+
+    let schemes = root
+        .entry("components")
+        .or_insert_with(|| serde_json::json!({}))
+        .as_object_mut()
+        .map(|components| {
+            components
+                .entry("securitySchemes")
+                .or_insert_with(|| serde_json::json!({}))
+        });
+
+    match schemes.and_then(|s| s.as_object_mut()) {
+        Some(schemes) => {
+            schemes
+                .entry(auth.scheme_name())
+                .or_insert_with(|| auth.security_scheme());
+            spec.to_string()
+        }
+        None => openapi_spec_json.to_string(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -338,6 +473,7 @@ mod tests {
         assert_eq!(config.sri_hash, None);
         assert_eq!(config.fallback_cdn_url, None);
         assert_eq!(config.proxy_url, None);
+        assert_eq!(config.auth, None);
     }
 
     #[test]
@@ -524,4 +660,59 @@ mod tests {
 
         assert!(json.get("proxy").is_none());
     }
+
+    #[test]
+    fn test_scalar_config_to_json_without_auth() {
+        let json = ScalarConfig::default().to_json();
+
+        assert!(json.get("authentication").is_none());
+    }
+
+    #[test]
+    fn test_scalar_config_to_json_with_bearer_auth() {
+        let config = ScalarConfig::new().with_auth(ScalarAuthScheme::Bearer);
+        let json = config.to_json();
+
+        assert_eq!(
+            json["authentication"]["preferredSecurityScheme"],
+            "bearerAuth"
+        );
+        assert_eq!(json["authentication"]["http"]["bearer"]["token"], "");
+    }
+
+    #[test]
+    fn test_scalar_config_to_json_with_api_key_auth() {
+        let config = ScalarConfig::new().with_auth(ScalarAuthScheme::ApiKey {
+            header: "X-API-Key".to_string(),
+        });
+        let json = config.to_json();
+
+        assert_eq!(
+            json["authentication"]["preferredSecurityScheme"],
+            "apiKeyAuth"
+        );
+        assert_eq!(json["authentication"]["apiKey"]["token"], "");
+    }
+
+    #[test]
+    fn test_scalar_html_with_auth_adds_security_scheme() {
+        let config = ScalarConfig::new().with_auth(ScalarAuthScheme::ApiKey {
+            header: "X-API-Key".to_string(),
+        });
+        let spec = r#"{"openapi":"3.1.0","paths":{}}"#;
+        let html = scalar_html(&config, "Test API", spec);
+
+        assert!(html.contains(r#""preferredSecurityScheme":"apiKeyAuth""#));
+        assert!(html.contains(r#""securitySchemes":{"apiKeyAuth":{"#));
+        assert!(html.contains(r#""name":"X-API-Key""#));
+    }
+
+    #[test]
+    fn test_scalar_html_with_auth_keeps_existing_scheme() {
+        let config = ScalarConfig::new().with_auth(ScalarAuthScheme::Bearer);
+        let spec = r#"{"components":{"securitySchemes":{"bearerAuth":{"type":"http","scheme":"bearer","bearerFormat":"JWT"}}},"openapi":"3.1.0"}"#;
+        let html = scalar_html(&config, "Test API", spec);
+
+        assert!(html.contains(r#""bearerFormat":"JWT""#));
+    }
 }
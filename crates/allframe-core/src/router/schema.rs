@@ -3,6 +3,15 @@
 //! This module provides types and traits for generating JSON Schema
 //! from Rust types. This is used to automatically document request
 //! and response types in OpenAPI specifications.
+//!
+//! With the `openapi` feature, `#[derive(ToJsonSchema)]` generates the
+//! implementation for structs and enums, including `#[schema(description =
+//! "...", example = ...)]` field attributes.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    marker::PhantomData,
+};
 
 use serde_json::{json, Value};
 
@@ -57,6 +66,44 @@ impl ToJsonSchema for i64 {
     }
 }
 
+impl ToJsonSchema for i8 {
+    fn schema() -> Value {
+        json!({
+            "type": "integer",
+            "format": "int8"
+        })
+    }
+}
+
+impl ToJsonSchema for i16 {
+    fn schema() -> Value {
+        json!({
+            "type": "integer",
+            "format": "int16"
+        })
+    }
+}
+
+impl ToJsonSchema for u8 {
+    fn schema() -> Value {
+        json!({
+            "type": "integer",
+            "format": "uint8",
+            "minimum": 0
+        })
+    }
+}
+
+impl ToJsonSchema for u16 {
+    fn schema() -> Value {
+        json!({
+            "type": "integer",
+            "format": "uint16",
+            "minimum": 0
+        })
+    }
+}
+
 impl ToJsonSchema for u32 {
     fn schema() -> Value {
         json!({
@@ -77,6 +124,16 @@ impl ToJsonSchema for u64 {
     }
 }
 
+impl ToJsonSchema for usize {
+    fn schema() -> Value {
+        json!({
+            "type": "integer",
+            "format": "uint64",
+            "minimum": 0
+        })
+    }
+}
+
 impl ToJsonSchema for f32 {
     fn schema() -> Value {
         json!({
@@ -124,6 +181,73 @@ impl<T: ToJsonSchema> ToJsonSchema for Vec<T> {
     }
 }
 
+// Implement for string-keyed maps
+impl<T: ToJsonSchema> ToJsonSchema for HashMap<String, T> {
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "additionalProperties": T::schema()
+        })
+    }
+}
+
+impl<T: ToJsonSchema> ToJsonSchema for BTreeMap<String, T> {
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "additionalProperties": T::schema()
+        })
+    }
+}
+
+// Arbitrary JSON accepts any value
+impl ToJsonSchema for Value {
+    fn schema() -> Value {
+        json!({})
+    }
+}
+
+/// Looks up the schema of a type if it implements [`ToJsonSchema`]
+///
+/// Used by `#[api_handler]`, which can't tell from the syntax whether a
+/// request or response type has a schema. With [`ProbeSchema`] and
+/// [`ProbeNoSchema`] in scope, `(&SchemaProbe::<T>::new()).probe()` resolves
+/// to the schema when `T: ToJsonSchema` and to `None` otherwise.
+#[doc(hidden)]
+pub struct SchemaProbe<T>(PhantomData<T>);
+
+impl<T> SchemaProbe<T> {
+    /// Create a probe for `T`
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// Schema lookup for types implementing [`ToJsonSchema`]
+#[doc(hidden)]
+pub trait ProbeSchema {
+    /// The type's schema
+    fn probe(&self) -> Option<Value>;
+}
+
+impl<T: ToJsonSchema> ProbeSchema for SchemaProbe<T> {
+    fn probe(&self) -> Option<Value> {
+        Some(T::schema())
+    }
+}
+
+/// Fallback schema lookup for types without a schema
+#[doc(hidden)]
+pub trait ProbeNoSchema {
+    /// Always `None`
+    fn probe(&self) -> Option<Value> {
+        None
+    }
+}
+
+impl<T> ProbeNoSchema for &SchemaProbe<T> {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -242,4 +366,29 @@ mod tests {
         assert_eq!(schema["items"]["type"], "string");
         assert_eq!(schema["items"]["nullable"], true);
     }
+
+    #[test]
+    fn test_map_schema() {
+        let schema = HashMap::<String, i64>::schema();
+        assert_eq!(schema["type"], "object");
+        assert_eq!(schema["additionalProperties"]["type"], "integer");
+
+        let schema = BTreeMap::<String, bool>::schema();
+        assert_eq!(schema["additionalProperties"]["type"], "boolean");
+    }
+
+    #[test]
+    fn test_value_schema_accepts_anything() {
+        assert_eq!(Value::schema(), json!({}));
+    }
+
+    #[test]
+    // The borrow drives autoref specialization, as in generated code
+    #[allow(clippy::needless_borrow)]
+    fn test_schema_probe() {
+        struct NoSchema;
+
+        assert_eq!((&SchemaProbe::<u32>::new()).probe(), Some(u32::schema()));
+        assert_eq!((&SchemaProbe::<NoSchema>::new()).probe(), None);
+    }
 }
@@ -0,0 +1,308 @@
+//! Server-Sent Events for streaming handlers
+//!
+//! [`Router::sse`](super::Router::sse) runs a registered streaming handler
+//! and returns its messages as an [`SseStream`] of framed events, so
+//! browsers can consume it with `EventSource` without a gRPC-web proxy:
+//!
+//! - each streamed item is sent as a `data:` event
+//! - the handler's final result is sent as a `done` event, or an `error`
+//!   event with `{"error": "..."}` if it fails
+//! - a `: heartbeat` comment is sent periodically (every 15 seconds by
+//!   default) so proxies don't close idle connections
+//!
+//! Dropping the stream, e.g. when the client disconnects, cancels the
+//! handler's [`StreamSender`](super::StreamSender).
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::sync::Arc;
+//!
+//! use allframe_core::router::{Router, SseConfig, StreamSender};
+//! use futures_core::Stream;
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let mut router = Router::new();
+//! router.register_streaming("ticks", |tx: StreamSender| async move {
+//!     for i in 0..3 {
+//!         tx.send(format!("{{\"tick\":{}}}", i)).await.ok();
+//!     }
+//!     r#"{"ticks":3}"#.to_string()
+//! });
+//! let router = Arc::new(router);
+//!
+//! let mut events = router.sse("ticks", "{}", &SseConfig::default()).unwrap();
+//! let first = std::future::poll_fn(|cx| std::pin::Pin::new(&mut events).poll_next(cx)).await;
+//! assert_eq!(first.as_deref(), Some("data: {\"tick\":0}\n\n"));
+//! # }
+//! ```
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures_core::Stream;
+use tokio::{sync::mpsc, task::JoinHandle};
+
+use super::{Router, DEFAULT_STREAM_CAPACITY};
+
+/// `Content-Type` of an SSE response
+pub const SSE_CONTENT_TYPE: &str = "text/event-stream";
+
+/// Comment sent to keep idle connections open
+pub const SSE_HEARTBEAT: &str = ": heartbeat\n\n";
+
+/// Default interval between heartbeats
+pub const DEFAULT_SSE_HEARTBEAT: Duration = Duration::from_secs(15);
+
+/// Frame `data` as an SSE event, optionally with an event name
+///
+/// Each line of `data` becomes its own `data:` field, as the SSE format
+/// requires.
+pub fn sse_event(event: Option<&str>, data: &str) -> String {
+    let mut frame = String::with_capacity(data.len() + 16);
+    if let Some(event) = event {
+        frame.push_str("event: ");
+        frame.push_str(event);
+        frame.push('\n');
+    }
+    for line in data.split('\n') {
+        frame.push_str("data: ");
+        frame.push_str(line.strip_suffix('\r').unwrap_or(line));
+        frame.push('\n');
+    }
+    frame.push('\n');
+    frame
+}
+
+/// SSE stream settings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SseConfig {
+    heartbeat: Option<Duration>,
+}
+
+impl Default for SseConfig {
+    fn default() -> Self {
+        Self {
+            heartbeat: Some(DEFAULT_SSE_HEARTBEAT),
+        }
+    }
+}
+
+impl SseConfig {
+    /// Settings with the default heartbeat interval
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send a heartbeat comment every `interval`
+    pub fn heartbeat(mut self, interval: Duration) -> Self {
+        self.heartbeat = Some(interval);
+        self
+    }
+
+    /// Don't send heartbeats
+    pub fn without_heartbeat(mut self) -> Self {
+        self.heartbeat = None;
+        self
+    }
+
+    /// The heartbeat interval, if heartbeats are enabled
+    pub fn heartbeat_interval(&self) -> Option<Duration> {
+        self.heartbeat
+    }
+}
+
+/// A stream of framed SSE events, ready to write to the response body
+///
+/// Dropping the stream stops the task producing its events.
+pub struct SseStream {
+    rx: mpsc::Receiver<String>,
+    task: JoinHandle<()>,
+}
+
+impl SseStream {
+    /// Run `produce` in a task, streaming the events it sends, with
+    /// heartbeats as configured
+    pub(crate) fn spawn<F, Fut>(config: &SseConfig, produce: F) -> Self
+    where
+        F: FnOnce(mpsc::Sender<String>) -> Fut,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let (tx, rx) = mpsc::channel(DEFAULT_STREAM_CAPACITY);
+        let heartbeat_tx = tx.clone();
+        let heartbeat = config.heartbeat;
+        let events = produce(tx);
+
+        let task = tokio::spawn(async move {
+            let heartbeats = async move {
+                let Some(interval) = heartbeat else {
+                    return std::future::pending().await;
+                };
+                let mut ticks =
+                    tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+                loop {
+                    ticks.tick().await;
+                    if heartbeat_tx.send(SSE_HEARTBEAT.to_string()).await.is_err() {
+                        return;
+                    }
+                }
+            };
+            // Ends when the events are done or the client is gone
+            tokio::select! {
+                _ = events => {}
+                _ = heartbeats => {}
+            }
+        });
+        Self { rx, task }
+    }
+}
+
+impl Stream for SseStream {
+    type Item = String;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<String>> {
+        self.rx.poll_recv(cx)
+    }
+}
+
+impl Drop for SseStream {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl std::fmt::Debug for SseStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SseStream")
+            .field("finished", &self.task.is_finished())
+            .finish()
+    }
+}
+
+impl Router {
+    /// Run a streaming handler, returning its messages as SSE events
+    ///
+    /// Items are sent as `data:` events, followed by a `done` event with
+    /// the handler's result or an `error` event if it fails. Fails if no
+    /// streaming handler is registered under `name`.
+    pub fn sse(
+        self: &std::sync::Arc<Self>,
+        name: &str,
+        args: &str,
+        config: &SseConfig,
+    ) -> Result<SseStream, String> {
+        let (mut items, handle) = self.spawn_streaming_handler(name, args)?;
+        Ok(SseStream::spawn(config, |tx| async move {
+            while let Some(item) = items.recv().await {
+                if tx.send(sse_event(None, &item)).await.is_err() {
+                    return;
+                }
+            }
+            let last = match handle.await {
+                Ok(Ok(result)) => sse_event(Some("done"), &result),
+                Ok(Err(e)) => error_event(&e),
+                Err(_) => error_event("Streaming handler panicked"),
+            };
+            let _ = tx.send(last).await;
+        }))
+    }
+}
+
+fn error_event(error: &str) -> String {
+    sse_event(
+        Some("error"),
+        &serde_json::json!({ "error": error }).to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::router::StreamSender;
+
+    async fn collect(mut events: SseStream) -> Vec<String> {
+        let mut collected = Vec::new();
+        while let Some(event) = std::future::poll_fn(|cx| Pin::new(&mut events).poll_next(cx)).await
+        {
+            collected.push(event);
+        }
+        collected
+    }
+
+    #[test]
+    fn test_sse_event_framing() {
+        assert_eq!(sse_event(None, "{\"a\":1}"), "data: {\"a\":1}\n\n");
+        assert_eq!(
+            sse_event(Some("done"), "line one\r\nline two"),
+            "event: done\ndata: line one\ndata: line two\n\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_streaming_handler_as_sse() {
+        let mut router = Router::new();
+        router.register_streaming("count", |tx: StreamSender| async move {
+            tx.send("1".to_string()).await.ok();
+            tx.send("2".to_string()).await.ok();
+            r#"{"total":2}"#.to_string()
+        });
+        let router = Arc::new(router);
+
+        let events = router.sse("count", "{}", &SseConfig::default()).unwrap();
+        assert_eq!(
+            collect(events).await,
+            [
+                "data: 1\n\n",
+                "data: 2\n\n",
+                "event: done\ndata: {\"total\":2}\n\n"
+            ]
+        );
+        assert!(router.sse("missing", "{}", &SseConfig::default()).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sse_error_event() {
+        let mut router = Router::new();
+        router.register_streaming_with_args::<u32, _, _, _>(
+            "fail",
+            |_: u32, _: StreamSender| async { "unused".to_string() },
+        );
+        let router = Arc::new(router);
+
+        let events = router
+            .sse("fail", "\"nope\"", &SseConfig::default())
+            .unwrap();
+        let events = collect(events).await;
+        assert_eq!(events.len(), 1);
+        assert!(events[0].starts_with("event: error\ndata: {\"error\":"));
+    }
+
+    #[tokio::test]
+    async fn test_sse_heartbeats() {
+        let mut router = Router::new();
+        router.register_streaming("slow", |tx: StreamSender| async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            tx.send("late".to_string()).await.ok();
+            "null".to_string()
+        });
+        let router = Arc::new(router);
+
+        let config = SseConfig::new().heartbeat(Duration::from_millis(20));
+        let events = collect(router.sse("slow", "{}", &config).unwrap()).await;
+        let (heartbeats, rest) = events.split_at(events.len() - 2);
+        assert!(!heartbeats.is_empty());
+        assert!(heartbeats.iter().all(|event| event == SSE_HEARTBEAT));
+        assert_eq!(rest, ["data: late\n\n", "event: done\ndata: null\n\n"]);
+
+        let quiet = SseConfig::new().without_heartbeat();
+        let events = collect(router.sse("slow", "{}", &quiet).unwrap()).await;
+        assert_eq!(events.len(), 2);
+    }
+}
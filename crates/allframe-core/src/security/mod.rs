@@ -20,5 +20,6 @@
 mod obfuscation;
 
 pub use obfuscation::{
-    obfuscate_api_key, obfuscate_header, obfuscate_redis_url, obfuscate_url, Obfuscate, Sensitive,
+    obfuscate_api_key, obfuscate_hash, obfuscate_header, obfuscate_redis_url, obfuscate_reveal,
+    obfuscate_url, Obfuscate, Sensitive,
 };
@@ -78,19 +78,62 @@ pub fn obfuscate_redis_url(url: &str) -> String {
 /// assert_eq!(obfuscate_api_key(short), "***");
 /// ```
 pub fn obfuscate_api_key(key: &str) -> String {
-    let len = key.len();
+    // Show first 4 and last 4 characters
+    obfuscate_reveal(key, 4)
+}
+
+/// Obfuscate a value, revealing only its first and last `n` characters.
+///
+/// Values of `2 * n` characters or fewer are fully obfuscated.
+///
+/// # Example
+///
+/// ```
+/// use allframe_core::security::obfuscate_reveal;
+///
+/// assert_eq!(obfuscate_reveal("4111111111111111", 4), "4111***1111");
+/// assert_eq!(obfuscate_reveal("secret", 4), "***");
+/// ```
+pub fn obfuscate_reveal(value: &str, n: usize) -> String {
+    let len = value.chars().count();
 
-    if len <= 8 {
+    if len <= n * 2 {
         // Too short to safely reveal any part
         "***".to_string()
     } else {
-        // Show first 4 and last 4 characters
-        let prefix = &key[..4];
-        let suffix = &key[len - 4..];
+        let prefix: String = value.chars().take(n).collect();
+        let suffix: String = value.chars().skip(len - n).collect();
         format!("{}***{}", prefix, suffix)
     }
 }
 
+/// Replace a value with a short, stable hash.
+///
+/// The same input always produces the same hash (across processes and
+/// releases), so log lines can be correlated without revealing the value.
+/// The hash is not cryptographic; don't use it for low-entropy secrets that
+/// could be brute-forced.
+///
+/// # Example
+///
+/// ```
+/// use allframe_core::security::obfuscate_hash;
+///
+/// let hash = obfuscate_hash("alice@example.com");
+/// assert_eq!(hash, obfuscate_hash("alice@example.com"));
+/// assert_ne!(hash, obfuscate_hash("bob@example.com"));
+/// assert!(hash.starts_with('#'));
+/// ```
+pub fn obfuscate_hash(value: &str) -> String {
+    // 64-bit FNV-1a, folded to 32 bits
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in value.as_bytes() {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    format!("#{:08x}", (hash ^ (hash >> 32)) as u32)
+}
+
 /// Obfuscate a header value based on the header name.
 ///
 /// Sensitive headers (Authorization, Cookie, etc.) are fully obfuscated.
@@ -420,4 +463,19 @@ mod tests {
         let s = Sensitive::new("secret");
         assert_eq!(s.as_inner(), &"secret");
     }
+
+    #[test]
+    fn test_obfuscate_reveal() {
+        assert_eq!(obfuscate_reveal("4111111111111111", 4), "4111***1111");
+        assert_eq!(obfuscate_reveal("abcdef", 2), "ab***ef");
+        assert_eq!(obfuscate_reveal("abcd", 2), "***");
+        assert_eq!(obfuscate_reveal("pässwörter", 2), "pä***er");
+    }
+
+    #[test]
+    fn test_obfuscate_hash_is_stable() {
+        assert_eq!(obfuscate_hash("secret"), obfuscate_hash("secret"));
+        assert_eq!(obfuscate_hash("secret").len(), 9);
+        assert_ne!(obfuscate_hash("secret"), obfuscate_hash("secreu"));
+    }
 }
@@ -34,9 +34,18 @@
 //! }
 //! ```
 
-use std::{future::Future, pin::Pin, sync::Arc, time::Duration};
-
-use tokio::sync::{broadcast, watch};
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+use tokio::sync::{broadcast, watch, Notify};
 
 /// Shutdown signal types
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -78,9 +87,13 @@ impl ShutdownToken {
     }
 }
 
+/// Cleanup registered with [`GracefulShutdown::on_shutdown`]
+type ShutdownHook = Box<dyn FnOnce() -> Pin<Box<dyn Future<Output = ()> + Send>> + Send>;
+
 /// Builder for configuring graceful shutdown
 pub struct GracefulShutdownBuilder {
     timeout: Duration,
+    hook_timeout: Duration,
     on_signal: Option<Box<dyn Fn(ShutdownSignal) + Send + Sync>>,
 }
 
@@ -88,6 +101,7 @@ impl Default for GracefulShutdownBuilder {
     fn default() -> Self {
         Self {
             timeout: Duration::from_secs(30),
+            hook_timeout: Duration::from_secs(10),
             on_signal: None,
         }
     }
@@ -105,6 +119,12 @@ impl GracefulShutdownBuilder {
         self
     }
 
+    /// Set how long each shutdown hook may run (default: 10 seconds)
+    pub fn hook_timeout(mut self, timeout: Duration) -> Self {
+        self.hook_timeout = timeout;
+        self
+    }
+
     /// Set a callback to be called when a shutdown signal is received
     pub fn on_signal<F>(mut self, callback: F) -> Self
     where
@@ -121,6 +141,8 @@ impl GracefulShutdownBuilder {
             .map(|f| Arc::from(f) as Arc<dyn Fn(ShutdownSignal) + Send + Sync>);
         GracefulShutdown {
             timeout: self.timeout,
+            hook_timeout: self.hook_timeout,
+            hooks: Mutex::new(Vec::new()),
             on_signal,
             shutdown_tx: watch::channel(false).0,
             signal_tx: broadcast::channel(1).0,
@@ -133,6 +155,8 @@ impl GracefulShutdownBuilder {
 /// Provides utilities for handling graceful shutdown of services.
 pub struct GracefulShutdown {
     timeout: Duration,
+    hook_timeout: Duration,
+    hooks: Mutex<Vec<(i32, ShutdownHook)>>,
     on_signal: Option<Arc<dyn Fn(ShutdownSignal) + Send + Sync>>,
     shutdown_tx: watch::Sender<bool>,
     signal_tx: broadcast::Sender<ShutdownSignal>,
@@ -166,6 +190,67 @@ impl GracefulShutdown {
         self.signal_tx.subscribe()
     }
 
+    /// Register a cleanup hook to run on shutdown
+    ///
+    /// Hooks run in ascending `priority` order, and in registration order
+    /// among equal priorities, e.g. commit consumer offsets at priority 0,
+    /// flush metrics at 10 and close the database pool at 20. Each hook may
+    /// run for the hook timeout; a hook that exceeds it is abandoned and the
+    /// next one runs.
+    ///
+    /// Hooks run after a signal is received in [`wait`](Self::wait). After a
+    /// manual [`shutdown`](Self::shutdown), run them with
+    /// [`run_shutdown_hooks`](Self::run_shutdown_hooks).
+    ///
+    /// ```rust,no_run
+    /// use allframe_core::shutdown::GracefulShutdown;
+    ///
+    /// # async fn example() {
+    /// let shutdown = GracefulShutdown::new();
+    /// shutdown.on_shutdown(0, || async {
+    ///     // Commit consumer offsets
+    /// });
+    /// shutdown.on_shutdown(20, || async {
+    ///     // Close the database pool
+    /// });
+    ///
+    /// shutdown.wait().await;
+    /// # }
+    /// ```
+    pub fn on_shutdown<F, Fut>(&self, priority: i32, hook: F)
+    where
+        F: FnOnce() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let hook: ShutdownHook = Box::new(move || Box::pin(hook()));
+        self.hooks.lock().unwrap().push((priority, hook));
+    }
+
+    /// Run the registered shutdown hooks
+    ///
+    /// Each hook runs at most once: hooks that have already run are removed.
+    pub async fn run_shutdown_hooks(&self) {
+        let mut hooks = std::mem::take(&mut *self.hooks.lock().unwrap());
+        // Stable, so equal priorities keep their registration order
+        hooks.sort_by_key(|(priority, _)| *priority);
+
+        for (priority, hook) in hooks {
+            if tokio::time::timeout(self.hook_timeout, hook())
+                .await
+                .is_err()
+            {
+                #[cfg(feature = "otel")]
+                tracing::warn!(
+                    priority,
+                    timeout = ?self.hook_timeout,
+                    "Shutdown hook timed out"
+                );
+                #[cfg(not(feature = "otel"))]
+                let _ = priority;
+            }
+        }
+    }
+
     /// Trigger a manual shutdown
     pub fn shutdown(&self) {
         let _ = self.shutdown_tx.send(true);
@@ -177,7 +262,8 @@ impl GracefulShutdown {
 
     /// Wait for a shutdown signal (SIGINT or SIGTERM)
     ///
-    /// Returns the signal that triggered the shutdown.
+    /// Once the signal is received, runs the shutdown hooks, then returns the
+    /// signal that triggered the shutdown.
     pub async fn wait(&self) -> ShutdownSignal {
         let signal = wait_for_signal().await;
 
@@ -190,6 +276,8 @@ impl GracefulShutdown {
             callback(signal);
         }
 
+        self.run_shutdown_hooks().await;
+
         signal
     }
 
@@ -344,16 +432,57 @@ impl<F: Future> ShutdownExt for F {}
 ///
 ///     // Shutdown will cancel the spawned task
 ///     shutdown.shutdown();
+///
+///     // Wait for spawned tasks to wind down
+///     let unfinished = spawner.join_all(std::time::Duration::from_secs(10)).await;
+///     assert!(unfinished.is_empty());
 /// }
 /// ```
 pub struct ShutdownAwareTaskSpawner {
     shutdown: Arc<GracefulShutdown>,
+    tasks: Arc<TaskTracker>,
+}
+
+/// Names of the tasks a spawner has running
+#[derive(Default)]
+struct TaskTracker {
+    next_id: AtomicU64,
+    running: Mutex<BTreeMap<u64, String>>,
+    finished: Notify,
+}
+
+impl TaskTracker {
+    fn track(self: &Arc<Self>, name: &str) -> TaskGuard {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        self.running.lock().unwrap().insert(id, name.to_string());
+        TaskGuard {
+            tracker: self.clone(),
+            id,
+        }
+    }
+}
+
+/// Marks a task finished when dropped, including when it panics or is
+/// aborted
+struct TaskGuard {
+    tracker: Arc<TaskTracker>,
+    id: u64,
+}
+
+impl Drop for TaskGuard {
+    fn drop(&mut self) {
+        self.tracker.running.lock().unwrap().remove(&self.id);
+        self.tracker.finished.notify_waiters();
+    }
 }
 
 impl ShutdownAwareTaskSpawner {
     /// Create a new shutdown-aware task spawner
     pub fn new(shutdown: Arc<GracefulShutdown>) -> Self {
-        Self { shutdown }
+        Self {
+            shutdown,
+            tasks: Arc::new(TaskTracker::default()),
+        }
     }
 
     /// Get a reference to the underlying shutdown handler
@@ -370,9 +499,12 @@ impl ShutdownAwareTaskSpawner {
         Fut: Future<Output = ()> + Send,
     {
         let mut token = self.shutdown.token();
+        let guard = self.tasks.track(task_name);
         let task_name = task_name.to_string();
 
         tokio::spawn(async move {
+            let _guard = guard;
+
             #[cfg(feature = "otel")]
             tracing::info!(task = %task_name, "Starting task");
 
@@ -427,9 +559,12 @@ impl ShutdownAwareTaskSpawner {
         T: Send + 'static,
     {
         let mut token = self.shutdown.token();
+        let guard = self.tasks.track(task_name);
         let task_name = task_name.to_string();
 
         tokio::spawn(async move {
+            let _guard = guard;
+
             #[cfg(feature = "otel")]
             tracing::info!(task = %task_name, "Starting task");
 
@@ -458,12 +593,48 @@ impl ShutdownAwareTaskSpawner {
             result
         })
     }
+
+    /// Number of spawned tasks that haven't finished yet
+    pub fn running_tasks(&self) -> usize {
+        self.tasks.running.lock().unwrap().len()
+    }
+
+    /// Wait for every task spawned by this spawner (or its clones) to finish
+    ///
+    /// Call this after triggering shutdown so in-flight work can wind down
+    /// before the process exits. Returns the names of the tasks still
+    /// running when `timeout` expires, in the order they were spawned; an
+    /// empty list means everything finished.
+    pub async fn join_all(&self, timeout: Duration) -> Vec<String> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            // Register for wakeups before checking, so no finish is missed
+            let finished = self.tasks.finished.notified();
+            tokio::pin!(finished);
+            finished.as_mut().enable();
+
+            if self.tasks.running.lock().unwrap().is_empty() {
+                return Vec::new();
+            }
+            if tokio::time::timeout_at(deadline, finished).await.is_err() {
+                return self
+                    .tasks
+                    .running
+                    .lock()
+                    .unwrap()
+                    .values()
+                    .cloned()
+                    .collect();
+            }
+        }
+    }
 }
 
 impl Clone for ShutdownAwareTaskSpawner {
     fn clone(&self) -> Self {
         Self {
             shutdown: self.shutdown.clone(),
+            tasks: self.tasks.clone(),
         }
     }
 }
@@ -633,6 +804,38 @@ mod tests {
         assert_eq!(shutdown.timeout(), Duration::from_secs(60));
     }
 
+    #[tokio::test]
+    async fn test_shutdown_hooks_run_in_priority_order() {
+        let shutdown = GracefulShutdown::builder()
+            .hook_timeout(Duration::from_millis(50))
+            .build();
+        let order = Arc::new(Mutex::new(Vec::new()));
+
+        for (priority, name) in [(20, "close pool"), (0, "commit offsets"), (10, "flush")] {
+            let order = order.clone();
+            shutdown.on_shutdown(priority, move || async move {
+                order.lock().unwrap().push(name);
+            });
+        }
+        let slow = order.clone();
+        shutdown.on_shutdown(5, move || async move {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            slow.lock().unwrap().push("never");
+        });
+
+        let started = std::time::Instant::now();
+        shutdown.run_shutdown_hooks().await;
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(
+            *order.lock().unwrap(),
+            ["commit offsets", "flush", "close pool"]
+        );
+
+        // Hooks only run once
+        shutdown.run_shutdown_hooks().await;
+        assert_eq!(order.lock().unwrap().len(), 3);
+    }
+
     #[tokio::test]
     async fn test_spawn_task() {
         let shutdown = GracefulShutdown::new();
@@ -727,6 +930,30 @@ mod tests {
         assert!(Arc::ptr_eq(spawner.shutdown(), spawner2.shutdown()));
     }
 
+    #[tokio::test]
+    async fn test_shutdown_aware_spawner_join_all() {
+        let shutdown = Arc::new(GracefulShutdown::new());
+        let spawner = ShutdownAwareTaskSpawner::new(shutdown.clone());
+
+        spawner.spawn("consumer", || async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        });
+        // Tasks spawned through clones are tracked too
+        spawner
+            .clone()
+            .spawn_with_result("migration", || async { 42 });
+
+        // Before shutdown, only the short task finishes in time
+        let unfinished = spawner.join_all(Duration::from_millis(50)).await;
+        assert_eq!(unfinished, ["consumer"]);
+        assert_eq!(spawner.running_tasks(), 1);
+
+        shutdown.shutdown();
+        let unfinished = spawner.join_all(Duration::from_secs(1)).await;
+        assert!(unfinished.is_empty());
+        assert_eq!(spawner.running_tasks(), 0);
+    }
+
     #[tokio::test]
     async fn test_graceful_shutdown_ext_success() {
         let shutdown = GracefulShutdown::new();
@@ -0,0 +1,902 @@
+//! Pub/sub channels for WebSocket connections
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    fmt,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex, Weak,
+    },
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::sync::Notify;
+
+/// Default number of undelivered messages queued per connection
+pub const DEFAULT_QUEUE_CAPACITY: usize = 1024;
+
+/// Identifier of a connection registered with a [`ChannelManager`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ConnectionId(u64);
+
+impl fmt::Display for ConnectionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "conn-{}", self.0)
+    }
+}
+
+/// A message published to a channel
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ChannelMessage {
+    /// Channel the message was published to
+    pub channel: String,
+    /// Message payload
+    pub payload: Value,
+    /// Position of the message in the connection's stream, starting at 1
+    ///
+    /// Clients acknowledge messages by sequence number and present the
+    /// last one they processed when resuming a session.
+    pub seq: u64,
+}
+
+/// What to do when a connection's queue is full
+///
+/// A queue fills up when a client reads more slowly than messages are
+/// published to its channels.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SlowConsumerPolicy {
+    /// Discard the oldest queued message to make room for the new one
+    #[default]
+    DropOldest,
+    /// Disconnect the connection; its queued messages can still be read
+    Disconnect,
+}
+
+/// Default number of unacknowledged messages kept per session
+pub const DEFAULT_RESUME_MAX_MESSAGES: usize = 1000;
+
+/// Default time unacknowledged messages and detached sessions are kept
+pub const DEFAULT_RESUME_MAX_AGE: Duration = Duration::from_secs(120);
+
+/// How much a resumable session buffers for replay
+///
+/// Messages are kept until acknowledged, up to `max_messages` per session
+/// and for at most `max_age`. A session whose connection went away can be
+/// resumed within `max_age`; after that it is discarded.
+#[derive(Debug, Clone, Copy)]
+pub struct ResumeConfig {
+    max_messages: usize,
+    max_age: Duration,
+}
+
+impl Default for ResumeConfig {
+    fn default() -> Self {
+        Self {
+            max_messages: DEFAULT_RESUME_MAX_MESSAGES,
+            max_age: DEFAULT_RESUME_MAX_AGE,
+        }
+    }
+}
+
+impl ResumeConfig {
+    /// Set how many unacknowledged messages a session keeps
+    pub fn with_max_messages(mut self, max_messages: usize) -> Self {
+        self.max_messages = max_messages;
+        self
+    }
+
+    /// Set how long unacknowledged messages and detached sessions are kept
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+}
+
+/// Opaque token a client presents to resume its session after reconnecting
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ResumeToken(String);
+
+impl ResumeToken {
+    fn generate() -> Self {
+        Self(format!("{:032x}", rand::random::<u128>()))
+    }
+
+    /// The token, as sent to clients
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for ResumeToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl FromStr for ResumeToken {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.to_string()))
+    }
+}
+
+impl From<String> for ResumeToken {
+    fn from(s: String) -> Self {
+        Self(s)
+    }
+}
+
+/// Errors from resuming a session
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResumeError {
+    /// No session has this token; it expired, was closed, or never existed
+    UnknownToken,
+    /// Messages after the client's last acknowledged one are no longer
+    /// buffered; the session is closed and the client has to start over
+    MessagesLost {
+        /// Sequence number the client last acknowledged
+        last_acked: u64,
+        /// Oldest sequence number still buffered
+        oldest: u64,
+    },
+}
+
+impl fmt::Display for ResumeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ResumeError::UnknownToken => write!(f, "Unknown or expired resume token"),
+            ResumeError::MessagesLost { last_acked, oldest } => write!(
+                f,
+                "Messages after {} are no longer buffered (oldest is {})",
+                last_acked, oldest
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ResumeError {}
+
+/// Outgoing queue of one connection
+struct Queue {
+    messages: Mutex<VecDeque<ChannelMessage>>,
+    closed: AtomicBool,
+    dropped: AtomicU64,
+    notify: Notify,
+}
+
+impl Queue {
+    fn new(messages: VecDeque<ChannelMessage>) -> Arc<Self> {
+        Arc::new(Self {
+            messages: Mutex::new(messages),
+            closed: AtomicBool::new(false),
+            dropped: AtomicU64::new(0),
+            notify: Notify::new(),
+        })
+    }
+
+    fn close(&self) {
+        self.closed.store(true, Ordering::SeqCst);
+        self.notify.notify_one();
+    }
+}
+
+/// Replay buffer of a resumable connection
+struct Session {
+    token: ResumeToken,
+    unacked: VecDeque<(Instant, ChannelMessage)>,
+    detached_at: Option<Instant>,
+}
+
+impl Session {
+    fn trim(&mut self, config: &ResumeConfig, now: Instant) {
+        while self.unacked.len() > config.max_messages
+            || self
+                .unacked
+                .front()
+                .is_some_and(|(sent, _)| now.duration_since(*sent) > config.max_age)
+        {
+            self.unacked.pop_front();
+        }
+    }
+
+    fn expired(&self, config: &ResumeConfig, now: Instant) -> bool {
+        self.detached_at
+            .is_some_and(|detached| now.duration_since(detached) > config.max_age)
+    }
+}
+
+struct ConnectionEntry {
+    queue: Arc<Queue>,
+    channels: HashSet<String>,
+    next_seq: u64,
+    session: Option<Session>,
+}
+
+#[derive(Default)]
+struct State {
+    channels: HashMap<String, HashSet<ConnectionId>>,
+    connections: HashMap<ConnectionId, ConnectionEntry>,
+    tokens: HashMap<ResumeToken, ConnectionId>,
+}
+
+impl State {
+    fn remove_connection(&mut self, id: ConnectionId) {
+        let Some(entry) = self.connections.remove(&id) else {
+            return;
+        };
+        for channel in &entry.channels {
+            if let Some(subscribers) = self.channels.get_mut(channel) {
+                subscribers.remove(&id);
+                if subscribers.is_empty() {
+                    self.channels.remove(channel);
+                }
+            }
+        }
+        if let Some(session) = entry.session {
+            self.tokens.remove(&session.token);
+        }
+        entry.queue.close();
+    }
+
+    /// Close a connection's queue, keeping a resumable session for later
+    fn detach(&mut self, id: ConnectionId) {
+        match self.connections.get_mut(&id) {
+            Some(ConnectionEntry {
+                queue,
+                session: Some(session),
+                ..
+            }) => {
+                session.detached_at.get_or_insert_with(Instant::now);
+                queue.close();
+            }
+            _ => self.remove_connection(id),
+        }
+    }
+
+    fn remove_expired(&mut self, config: &ResumeConfig) {
+        let now = Instant::now();
+        let expired: Vec<ConnectionId> = self
+            .connections
+            .iter()
+            .filter(|(_, entry)| {
+                entry
+                    .session
+                    .as_ref()
+                    .is_some_and(|session| session.expired(config, now))
+            })
+            .map(|(id, _)| *id)
+            .collect();
+        for id in expired {
+            self.remove_connection(id);
+        }
+    }
+}
+
+struct Inner {
+    capacity: usize,
+    policy: SlowConsumerPolicy,
+    resume: Option<ResumeConfig>,
+    next_id: AtomicU64,
+    state: Mutex<State>,
+}
+
+impl Inner {
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Pub/sub channels over a set of connections
+///
+/// Each connection registered with [`connect`](Self::connect) gets a bounded
+/// queue of messages from the channels it subscribes to. The WebSocket
+/// server reads from the [`Connection`] and writes the messages to the
+/// socket. When a queue is full, the [`SlowConsumerPolicy`] decides whether
+/// the oldest message is dropped or the connection is disconnected, so one
+/// slow client never holds up publishers or other subscribers.
+///
+/// Connections are removed from all their channels when the [`Connection`]
+/// is dropped or [`disconnect`](Self::disconnect) is called. With
+/// [`with_resume`](Self::with_resume), a dropped connection instead leaves
+/// a session behind that keeps its subscriptions and buffers messages until
+/// the client [resumes](Self::resume) it or it expires.
+///
+/// Cloning shares the underlying state.
+#[derive(Clone)]
+pub struct ChannelManager {
+    inner: Arc<Inner>,
+}
+
+impl Default for ChannelManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChannelManager {
+    /// Create a manager with the default queue capacity and policy
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                capacity: DEFAULT_QUEUE_CAPACITY,
+                policy: SlowConsumerPolicy::default(),
+                resume: None,
+                next_id: AtomicU64::new(1),
+                state: Mutex::new(State::default()),
+            }),
+        }
+    }
+
+    /// Set how many undelivered messages each connection can queue
+    ///
+    /// Must be called before the manager is cloned or connections are
+    /// registered. A capacity of 0 is treated as 1.
+    pub fn with_queue_capacity(mut self, capacity: usize) -> Self {
+        self.inner_mut().capacity = capacity.max(1);
+        self
+    }
+
+    /// Set what happens when a connection's queue is full
+    ///
+    /// Must be called before the manager is cloned or connections are
+    /// registered.
+    pub fn with_slow_consumer_policy(mut self, policy: SlowConsumerPolicy) -> Self {
+        self.inner_mut().policy = policy;
+        self
+    }
+
+    /// Make connections resumable after a disconnect
+    ///
+    /// Each connection gets a [`ResumeToken`] and keeps its unacknowledged
+    /// messages, within the limits of `config`, so a client that reconnects
+    /// can pick up where it left off with [`resume`](Self::resume).
+    ///
+    /// Must be called before the manager is cloned or connections are
+    /// registered.
+    pub fn with_resume(mut self, config: ResumeConfig) -> Self {
+        self.inner_mut().resume = Some(config);
+        self
+    }
+
+    fn inner_mut(&mut self) -> &mut Inner {
+        Arc::get_mut(&mut self.inner).expect("ChannelManager configured after being shared")
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, State> {
+        self.inner.state()
+    }
+
+    /// Register a new connection
+    pub fn connect(&self) -> Connection {
+        let id = ConnectionId(self.inner.next_id.fetch_add(1, Ordering::Relaxed));
+        let queue = Queue::new(VecDeque::new());
+        let mut state = self.state();
+
+        let session = self.inner.resume.as_ref().map(|config| {
+            state.remove_expired(config);
+            Session {
+                token: ResumeToken::generate(),
+                unacked: VecDeque::new(),
+                detached_at: None,
+            }
+        });
+        let token = session.as_ref().map(|session| session.token.clone());
+        if let Some(token) = &token {
+            state.tokens.insert(token.clone(), id);
+        }
+        state.connections.insert(
+            id,
+            ConnectionEntry {
+                queue: queue.clone(),
+                channels: HashSet::new(),
+                next_seq: 1,
+                session,
+            },
+        );
+        Connection {
+            id,
+            token,
+            queue,
+            manager: Arc::downgrade(&self.inner),
+        }
+    }
+
+    /// Resume a session on a new connection
+    ///
+    /// `last_acked` is the sequence number of the last message the client
+    /// processed, or 0 if none. The returned connection keeps the session's
+    /// id, token and subscriptions, and first yields the buffered messages
+    /// after `last_acked`. If the session is still attached to a connection,
+    /// that connection is closed.
+    pub fn resume(&self, token: &ResumeToken, last_acked: u64) -> Result<Connection, ResumeError> {
+        let config = self.inner.resume.ok_or(ResumeError::UnknownToken)?;
+        let mut state = self.state();
+        state.remove_expired(&config);
+        let id = *state.tokens.get(token).ok_or(ResumeError::UnknownToken)?;
+
+        let entry = state
+            .connections
+            .get_mut(&id)
+            .expect("resume token of a registered connection");
+        let session = entry
+            .session
+            .as_mut()
+            .expect("resume token of a resumable connection");
+        session.trim(&config, Instant::now());
+
+        let oldest = session
+            .unacked
+            .front()
+            .map_or(entry.next_seq, |(_, message)| message.seq);
+        if last_acked.saturating_add(1) < oldest {
+            state.remove_connection(id);
+            return Err(ResumeError::MessagesLost { last_acked, oldest });
+        }
+
+        let replay = session
+            .unacked
+            .iter()
+            .filter(|(_, message)| message.seq > last_acked)
+            .map(|(_, message)| message.clone())
+            .collect();
+        session.detached_at = None;
+        entry.queue.close();
+        entry.queue = Queue::new(replay);
+        Ok(Connection {
+            id,
+            token: Some(token.clone()),
+            queue: entry.queue.clone(),
+            manager: Arc::downgrade(&self.inner),
+        })
+    }
+
+    /// Acknowledge a connection's messages up to and including `seq`
+    ///
+    /// Acknowledged messages are no longer kept for replay. Does nothing
+    /// unless the manager is [resumable](Self::with_resume).
+    pub fn ack(&self, conn: ConnectionId, seq: u64) {
+        let mut state = self.state();
+        if let Some(session) = state
+            .connections
+            .get_mut(&conn)
+            .and_then(|entry| entry.session.as_mut())
+        {
+            while session
+                .unacked
+                .front()
+                .is_some_and(|(_, message)| message.seq <= seq)
+            {
+                session.unacked.pop_front();
+            }
+        }
+    }
+
+    /// Subscribe a connection to a channel
+    ///
+    /// Returns `false` if the connection isn't registered.
+    pub fn subscribe(&self, channel: &str, conn: ConnectionId) -> bool {
+        let mut state = self.state();
+        let Some(entry) = state.connections.get_mut(&conn) else {
+            return false;
+        };
+        entry.channels.insert(channel.to_string());
+        state
+            .channels
+            .entry(channel.to_string())
+            .or_default()
+            .insert(conn);
+        true
+    }
+
+    /// Unsubscribe a connection from a channel
+    ///
+    /// Returns `false` if the connection wasn't subscribed.
+    pub fn unsubscribe(&self, channel: &str, conn: ConnectionId) -> bool {
+        let mut state = self.state();
+        if let Some(entry) = state.connections.get_mut(&conn) {
+            entry.channels.remove(channel);
+        }
+        let Some(subscribers) = state.channels.get_mut(channel) else {
+            return false;
+        };
+        let removed = subscribers.remove(&conn);
+        if subscribers.is_empty() {
+            state.channels.remove(channel);
+        }
+        removed
+    }
+
+    /// Publish a message to every subscriber of a channel
+    ///
+    /// Returns the number of connections the message was queued for,
+    /// including detached sessions that buffer it for replay.
+    pub fn publish(&self, channel: &str, payload: Value) -> usize {
+        let mut state = self.state();
+        let Some(subscribers) = state.channels.get(channel) else {
+            return 0;
+        };
+        let subscribers: Vec<ConnectionId> = subscribers.iter().copied().collect();
+        let now = Instant::now();
+
+        let mut delivered = 0;
+        let mut slow = Vec::new();
+        let mut expired = Vec::new();
+        for id in subscribers {
+            let entry = state
+                .connections
+                .get_mut(&id)
+                .expect("subscriber is a registered connection");
+            let message = ChannelMessage {
+                channel: channel.to_string(),
+                payload: payload.clone(),
+                seq: entry.next_seq,
+            };
+            entry.next_seq += 1;
+
+            if let (Some(session), Some(config)) = (&mut entry.session, &self.inner.resume) {
+                if session.expired(config, now) {
+                    expired.push(id);
+                    continue;
+                }
+                session.unacked.push_back((now, message.clone()));
+                session.trim(config, now);
+                if session.detached_at.is_some() {
+                    delivered += 1;
+                    continue;
+                }
+            }
+
+            let queue = &entry.queue;
+            let mut messages = queue.messages.lock().unwrap_or_else(|e| e.into_inner());
+            if messages.len() >= self.inner.capacity {
+                queue.dropped.fetch_add(1, Ordering::Relaxed);
+                match self.inner.policy {
+                    SlowConsumerPolicy::DropOldest => {
+                        messages.pop_front();
+                    }
+                    SlowConsumerPolicy::Disconnect => {
+                        slow.push(id);
+                        continue;
+                    }
+                }
+            }
+            messages.push_back(message);
+            drop(messages);
+            queue.notify.notify_one();
+            delivered += 1;
+        }
+
+        for id in expired {
+            state.remove_connection(id);
+        }
+        for id in slow {
+            #[cfg(feature = "otel")]
+            tracing::warn!(connection = %id, channel, "Disconnecting slow consumer");
+            state.detach(id);
+        }
+        delivered
+    }
+
+    /// Remove a connection from all its channels
+    ///
+    /// Its [`Connection`] yields any queued messages and then `None`. A
+    /// resumable session is closed for good.
+    pub fn disconnect(&self, conn: ConnectionId) {
+        self.state().remove_connection(conn);
+    }
+
+    /// Number of connections subscribed to a channel
+    pub fn subscriber_count(&self, channel: &str) -> usize {
+        self.state().channels.get(channel).map_or(0, HashSet::len)
+    }
+
+    /// Number of registered connections, including detached sessions
+    pub fn connection_count(&self) -> usize {
+        self.state().connections.len()
+    }
+
+    /// Channels a connection is subscribed to, in no particular order
+    pub fn channels_of(&self, conn: ConnectionId) -> Vec<String> {
+        self.state()
+            .connections
+            .get(&conn)
+            .map(|entry| entry.channels.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Debug for ChannelManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChannelManager")
+            .field("capacity", &self.inner.capacity)
+            .field("policy", &self.inner.policy)
+            .field("resume", &self.inner.resume)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A connection's end of a [`ChannelManager`]
+///
+/// Dropping it disconnects the connection, removing it from all channels,
+/// or detaches its session if the manager is resumable.
+pub struct Connection {
+    id: ConnectionId,
+    token: Option<ResumeToken>,
+    queue: Arc<Queue>,
+    manager: Weak<Inner>,
+}
+
+impl Connection {
+    /// The connection's identifier, for subscribing it to channels
+    pub fn id(&self) -> ConnectionId {
+        self.id
+    }
+
+    /// Token for resuming the session, if the manager is resumable
+    pub fn resume_token(&self) -> Option<&ResumeToken> {
+        self.token.as_ref()
+    }
+
+    /// Wait for the next message
+    ///
+    /// Returns `None` once the connection has been disconnected and its
+    /// queue is empty.
+    pub async fn recv(&mut self) -> Option<ChannelMessage> {
+        loop {
+            if let Some(message) = self.try_recv() {
+                return Some(message);
+            }
+            if self.is_closed() {
+                // A message may have been queued just before closing
+                return self.try_recv();
+            }
+            self.queue.notify.notified().await;
+        }
+    }
+
+    /// Take the next message if one is queued
+    pub fn try_recv(&mut self) -> Option<ChannelMessage> {
+        self.queue
+            .messages
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .pop_front()
+    }
+
+    /// Check if the connection has been disconnected
+    pub fn is_closed(&self) -> bool {
+        self.queue.closed.load(Ordering::SeqCst)
+    }
+
+    /// Number of messages not delivered because the queue was full
+    pub fn dropped(&self) -> u64 {
+        self.queue.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        let Some(inner) = self.manager.upgrade() else {
+            return;
+        };
+        let mut state = inner.state();
+        // A resumed session has moved on to a new connection
+        let current = state
+            .connections
+            .get(&self.id)
+            .is_some_and(|entry| Arc::ptr_eq(&entry.queue, &self.queue));
+        if current {
+            state.detach(self.id);
+        }
+    }
+}
+
+impl fmt::Debug for Connection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Connection")
+            .field("id", &self.id)
+            .field("closed", &self.is_closed())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_two_subscribers_receive_published_message() {
+        let channels = ChannelManager::new();
+        let mut alice = channels.connect();
+        let mut bob = channels.connect();
+        let mut carol = channels.connect();
+        assert!(channels.subscribe("orders", alice.id()));
+        assert!(channels.subscribe("orders", bob.id()));
+        assert!(channels.subscribe("invoices", carol.id()));
+
+        let delivered = channels.publish("orders", json!({ "id": 7 }));
+        assert_eq!(delivered, 2);
+
+        for conn in [&mut alice, &mut bob] {
+            let message = conn.recv().await.unwrap();
+            assert_eq!(message.channel, "orders");
+            assert_eq!(message.payload, json!({ "id": 7 }));
+            assert_eq!(message.seq, 1);
+        }
+        assert!(carol.try_recv().is_none());
+        assert!(alice.resume_token().is_none());
+        assert_eq!(channels.publish("nobody", json!(null)), 0);
+    }
+
+    #[tokio::test]
+    async fn test_cleanup_on_disconnect() {
+        let channels = ChannelManager::new();
+        let alice = channels.connect();
+        let mut bob = channels.connect();
+        channels.subscribe("chat", alice.id());
+        channels.subscribe("chat", bob.id());
+        channels.subscribe("typing", alice.id());
+        assert_eq!(channels.subscriber_count("chat"), 2);
+
+        drop(alice);
+        assert_eq!(channels.connection_count(), 1);
+        assert_eq!(channels.subscriber_count("chat"), 1);
+        assert_eq!(channels.subscriber_count("typing"), 0);
+
+        channels.publish("chat", json!("bye"));
+        channels.disconnect(bob.id());
+        assert!(!channels.subscribe("chat", bob.id()));
+        // Queued messages are still delivered before the end of the stream
+        assert_eq!(bob.recv().await.unwrap().payload, json!("bye"));
+        assert!(bob.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_slow_consumer_policies() {
+        let channels = ChannelManager::new().with_queue_capacity(2);
+        let mut slow = channels.connect();
+        channels.subscribe("ticks", slow.id());
+        for tick in 0..5 {
+            channels.publish("ticks", json!(tick));
+        }
+        assert_eq!(slow.dropped(), 3);
+        assert_eq!(slow.recv().await.unwrap().payload, json!(3));
+        assert_eq!(slow.recv().await.unwrap().payload, json!(4));
+        assert!(!slow.is_closed());
+
+        let channels = ChannelManager::new()
+            .with_queue_capacity(2)
+            .with_slow_consumer_policy(SlowConsumerPolicy::Disconnect);
+        let mut slow = channels.connect();
+        let mut fast = channels.connect();
+        channels.subscribe("ticks", slow.id());
+        channels.subscribe("ticks", fast.id());
+        for tick in 0..3 {
+            channels.publish("ticks", json!(tick));
+            fast.recv().await.unwrap();
+        }
+        assert!(slow.is_closed());
+        assert_eq!(channels.subscriber_count("ticks"), 1);
+        assert_eq!(slow.recv().await.unwrap().payload, json!(0));
+        assert_eq!(slow.recv().await.unwrap().payload, json!(1));
+        assert!(slow.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_recv_waits_for_publish() {
+        let channels = ChannelManager::new();
+        let mut conn = channels.connect();
+        channels.subscribe("news", conn.id());
+
+        let publisher = channels.clone();
+        let handle = tokio::spawn(async move {
+            tokio::task::yield_now().await;
+            publisher.publish("news", json!("extra"));
+        });
+        assert_eq!(conn.recv().await.unwrap().payload, json!("extra"));
+        handle.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_resume_replays_buffered_messages() {
+        let channels = ChannelManager::new().with_resume(ResumeConfig::default());
+        let mut conn = channels.connect();
+        let token = conn.resume_token().unwrap().clone();
+        channels.subscribe("orders", conn.id());
+
+        for id in 1..=3 {
+            channels.publish("orders", json!(id));
+        }
+        let first = conn.recv().await.unwrap();
+        channels.ack(conn.id(), first.seq);
+        // The second message reaches the client but isn't acknowledged
+        conn.recv().await.unwrap();
+
+        // The connection drops; the session keeps buffering
+        drop(conn);
+        assert_eq!(channels.subscriber_count("orders"), 1);
+        assert_eq!(channels.publish("orders", json!(4)), 1);
+
+        let mut conn = channels.resume(&token, first.seq).unwrap();
+        assert_eq!(conn.resume_token(), Some(&token));
+        let mut replayed = Vec::new();
+        while let Some(message) = conn.try_recv() {
+            replayed.push((message.seq, message.payload));
+        }
+        assert_eq!(replayed, vec![(2, json!(2)), (3, json!(3)), (4, json!(4))]);
+
+        // Live delivery continues on the resumed connection
+        channels.publish("orders", json!(5));
+        assert_eq!(conn.recv().await.unwrap().seq, 5);
+    }
+
+    #[tokio::test]
+    async fn test_resume_takes_over_live_connection() {
+        let channels = ChannelManager::new().with_resume(ResumeConfig::default());
+        let mut old = channels.connect();
+        channels.subscribe("chat", old.id());
+        let token = old.resume_token().unwrap().clone();
+
+        let mut new = channels.resume(&token, 0).unwrap();
+        assert!(old.is_closed());
+        assert!(old.recv().await.is_none());
+
+        // Dropping the replaced connection leaves the new one alone
+        drop(old);
+        channels.publish("chat", json!("hi"));
+        assert_eq!(new.recv().await.unwrap().payload, json!("hi"));
+    }
+
+    #[tokio::test]
+    async fn test_resume_limits() {
+        let unknown: ResumeToken = "nope".parse().unwrap();
+        assert_eq!(
+            ChannelManager::new().resume(&unknown, 0).unwrap_err(),
+            ResumeError::UnknownToken
+        );
+
+        // Messages beyond the buffer are lost, and so is the session
+        let config = ResumeConfig::default().with_max_messages(2);
+        let channels = ChannelManager::new().with_resume(config);
+        let conn = channels.connect();
+        let token = conn.resume_token().unwrap().clone();
+        channels.subscribe("ticks", conn.id());
+        drop(conn);
+        for tick in 1..=4 {
+            channels.publish("ticks", json!(tick));
+        }
+        assert_eq!(
+            channels.resume(&token, 1).unwrap_err(),
+            ResumeError::MessagesLost {
+                last_acked: 1,
+                oldest: 3
+            }
+        );
+        assert_eq!(channels.connection_count(), 0);
+
+        // Detached sessions expire
+        let config = ResumeConfig::default().with_max_age(Duration::from_millis(10));
+        let channels = ChannelManager::new().with_resume(config);
+        let token = channels.connect().resume_token().unwrap().clone();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(
+            channels.resume(&token, 0).unwrap_err(),
+            ResumeError::UnknownToken
+        );
+        assert_eq!(channels.connection_count(), 0);
+
+        // Closing a session for good forgets the token
+        let channels = ChannelManager::new().with_resume(ResumeConfig::default());
+        let conn = channels.connect();
+        let token = conn.resume_token().unwrap().clone();
+        channels.disconnect(conn.id());
+        assert!(channels.resume(&token, 0).is_err());
+    }
+}
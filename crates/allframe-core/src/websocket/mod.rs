@@ -0,0 +1,41 @@
+//! Real-time building blocks for WebSocket services
+//!
+//! The types here don't depend on a particular WebSocket library: the server
+//! owns the sockets and moves messages between them and these primitives.
+//!
+//! - [`ChannelManager`] - pub/sub channels with per-connection queues,
+//!   slow-consumer handling, and resumable sessions for clients that
+//!   reconnect
+//! - [`WsRpcDispatcher`] - JSON-RPC style dispatch of incoming messages to
+//!   [`Router`](crate::router::Router) handlers
+//!
+//! # Example
+//!
+//! ```rust
+//! use allframe_core::websocket::ChannelManager;
+//! use serde_json::json;
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let channels = ChannelManager::new();
+//! let mut alice = channels.connect();
+//! channels.subscribe("prices", alice.id());
+//!
+//! channels.publish("prices", json!({ "BTC": 64000 }));
+//! let message = alice.recv().await.unwrap();
+//! assert_eq!(message.channel, "prices");
+//! # }
+//! ```
+
+mod channels;
+mod rpc;
+
+pub use channels::{
+    ChannelManager, ChannelMessage, Connection, ConnectionId, ResumeConfig, ResumeError,
+    ResumeToken, SlowConsumerPolicy, DEFAULT_QUEUE_CAPACITY, DEFAULT_RESUME_MAX_AGE,
+    DEFAULT_RESUME_MAX_MESSAGES,
+};
+pub use rpc::{
+    RpcError, RpcRequest, RpcResponse, WsRpcDispatcher, HANDLER_ERROR, INVALID_REQUEST,
+    METHOD_NOT_FOUND, PARSE_ERROR, RATE_LIMITED, TIMED_OUT,
+};
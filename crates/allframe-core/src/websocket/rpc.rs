@@ -0,0 +1,358 @@
+//! JSON-RPC style dispatch of WebSocket messages to router handlers
+
+use std::{fmt, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::router::{ExecuteError, Router};
+
+/// The frame isn't valid JSON
+pub const PARSE_ERROR: i64 = -32700;
+/// The frame is JSON but not a request object
+pub const INVALID_REQUEST: i64 = -32600;
+/// No handler is registered under the method name
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// The handler returned an error
+pub const HANDLER_ERROR: i64 = -32000;
+/// The handler didn't complete within its route's timeout
+pub const TIMED_OUT: i64 = -32001;
+/// The route's rate limit was exceeded
+pub const RATE_LIMITED: i64 = -32002;
+
+/// A request frame: `{"id": .., "method": "..", "params": ..}`
+///
+/// Without an `id` the request is a notification, which gets no response.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RpcRequest {
+    /// Id the client correlates the response with
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<Value>,
+    /// Name of the router handler to call
+    pub method: String,
+    /// Handler args; a missing `params` calls the handler with `{}`
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub params: Value,
+}
+
+impl RpcRequest {
+    /// Check if the request is a notification, which gets no response
+    pub fn is_notification(&self) -> bool {
+        self.id.is_none()
+    }
+}
+
+/// Error member of a response frame
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RpcError {
+    /// Error code; see the constants in this module
+    pub code: i64,
+    /// Human-readable description
+    pub message: String,
+    /// Additional details, such as `retry_after_ms` for rate limiting
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+
+impl RpcError {
+    /// Create an error without details
+    pub fn new(code: i64, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+}
+
+impl fmt::Display for RpcError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.message, self.code)
+    }
+}
+
+impl std::error::Error for RpcError {}
+
+impl From<ExecuteError> for RpcError {
+    fn from(error: ExecuteError) -> Self {
+        let code = match &error {
+            ExecuteError::NotFound(_) => METHOD_NOT_FOUND,
+            ExecuteError::Handler(_) => HANDLER_ERROR,
+            ExecuteError::TimedOut(_) => TIMED_OUT,
+            ExecuteError::RateLimited { .. } => RATE_LIMITED,
+        };
+        let data = match &error {
+            ExecuteError::RateLimited { retry_after } => Some(serde_json::json!({
+                "retry_after_ms": retry_after.as_millis() as u64
+            })),
+            _ => None,
+        };
+        Self {
+            code,
+            message: error.to_string(),
+            data,
+        }
+    }
+}
+
+/// A response frame: `{"id": .., "result": ..}` or `{"id": .., "error": ..}`
+///
+/// The `id` is the request's, or `null` when the request couldn't be
+/// parsed far enough to find it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RpcResponse {
+    /// Id of the request this answers
+    pub id: Value,
+    /// Handler output, on success
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    /// What went wrong, on failure
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<RpcError>,
+}
+
+impl RpcResponse {
+    /// Successful response to the request with `id`
+    pub fn success(id: Value, result: Value) -> Self {
+        Self {
+            id,
+            result: Some(result),
+            error: None,
+        }
+    }
+
+    /// Error response to the request with `id`
+    pub fn failure(id: Value, error: RpcError) -> Self {
+        Self {
+            id,
+            result: None,
+            error: Some(error),
+        }
+    }
+
+    /// Serialize as a text frame
+    pub fn to_frame(&self) -> String {
+        serde_json::to_string(self).expect("RPC responses serialize to JSON")
+    }
+}
+
+/// Routes WebSocket messages to [`Router`] handlers by method name
+///
+/// Each text frame is a [`RpcRequest`]; its `method` names a handler, which
+/// is called with `params` as its args through
+/// [`Router::execute_with_args`], so middleware, timeouts and rate limits
+/// apply as for any other transport. Handler output that is valid JSON is
+/// returned as-is in `result`, anything else as a JSON string.
+///
+/// Frames are independent: the server can handle them concurrently and
+/// send each response as soon as it's ready. Clients match responses to
+/// requests by `id`, not by order.
+///
+/// ```rust
+/// use std::sync::Arc;
+///
+/// use allframe_core::{router::Router, websocket::WsRpcDispatcher};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let mut router = Router::new();
+/// router.register("ping", || async { "\"pong\"".to_string() });
+/// let rpc = WsRpcDispatcher::new(Arc::new(router));
+///
+/// let response = rpc.handle_frame(r#"{"id":1,"method":"ping"}"#).await;
+/// assert_eq!(response.as_deref(), Some(r#"{"id":1,"result":"pong"}"#));
+///
+/// // Notifications get no response
+/// assert!(rpc.handle_frame(r#"{"method":"ping"}"#).await.is_none());
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct WsRpcDispatcher {
+    router: Arc<Router>,
+}
+
+impl WsRpcDispatcher {
+    /// Dispatch to the handlers of `router`
+    pub fn new(router: Arc<Router>) -> Self {
+        Self { router }
+    }
+
+    /// Handle one text frame, returning the response frame to send
+    ///
+    /// Returns `None` for notifications. Frames that aren't valid requests
+    /// get an error response.
+    pub async fn handle_frame(&self, frame: &str) -> Option<String> {
+        let value: Value = match serde_json::from_str(frame) {
+            Ok(value) => value,
+            Err(e) => {
+                let error = RpcError::new(PARSE_ERROR, format!("Invalid JSON: {}", e));
+                return Some(RpcResponse::failure(Value::Null, error).to_frame());
+            }
+        };
+        let id = value.get("id").cloned().unwrap_or(Value::Null);
+        let request: RpcRequest = match serde_json::from_value(value) {
+            Ok(request) => request,
+            Err(e) => {
+                let error = RpcError::new(INVALID_REQUEST, format!("Invalid request: {}", e));
+                return Some(RpcResponse::failure(id, error).to_frame());
+            }
+        };
+        self.handle(request)
+            .await
+            .map(|response| response.to_frame())
+    }
+
+    /// Handle a parsed request
+    ///
+    /// Returns `None` for notifications, whose outcome is discarded.
+    pub async fn handle(&self, request: RpcRequest) -> Option<RpcResponse> {
+        let args = match request.params {
+            Value::Null => "{}".to_string(),
+            params => params.to_string(),
+        };
+        let outcome = self
+            .router
+            .execute_with_args(&request.method, &args)
+            .await
+            .map(|output| serde_json::from_str(&output).unwrap_or(Value::String(output)))
+            .map_err(RpcError::from);
+
+        let Some(id) = request.id else {
+            #[cfg(feature = "otel")]
+            if let Err(e) = &outcome {
+                tracing::warn!(method = %request.method, error = %e, "Notification failed");
+            }
+            return None;
+        };
+        Some(match outcome {
+            Ok(result) => RpcResponse::success(id, result),
+            Err(error) => RpcResponse::failure(id, error),
+        })
+    }
+}
+
+impl fmt::Debug for WsRpcDispatcher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WsRpcDispatcher").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+
+    use serde::Deserialize;
+    use serde_json::json;
+
+    use super::*;
+
+    #[derive(Deserialize)]
+    struct Add {
+        a: i64,
+        b: i64,
+    }
+
+    #[derive(Deserialize)]
+    struct Sleep {
+        ms: u64,
+    }
+
+    fn dispatcher(calls: Arc<AtomicUsize>) -> WsRpcDispatcher {
+        let mut router = Router::new();
+        router.register_with_args::<Add, _, _>("add", |args| async move {
+            (args.a + args.b).to_string()
+        });
+        router.register_with_args::<Sleep, _, _>("sleep", |args| async move {
+            tokio::time::sleep(Duration::from_millis(args.ms)).await;
+            format!("\"slept {}\"", args.ms)
+        });
+        router.register("record", move || {
+            let calls = calls.clone();
+            async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                "recorded".to_string()
+            }
+        });
+        router.register_result::<String, String, _, _>("fail", || async {
+            Err("out of stock".to_string())
+        });
+        WsRpcDispatcher::new(Arc::new(router))
+    }
+
+    async fn call(rpc: &WsRpcDispatcher, frame: Value) -> Value {
+        let response = rpc.handle_frame(&frame.to_string()).await.unwrap();
+        serde_json::from_str(&response).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_dispatches_to_handlers() {
+        let rpc = dispatcher(Arc::default());
+
+        let response = call(
+            &rpc,
+            json!({"id": 1, "method": "add", "params": {"a": 2, "b": 3}}),
+        )
+        .await;
+        assert_eq!(response, json!({"id": 1, "result": 5}));
+
+        // Non-JSON output comes back as a string
+        let response = call(&rpc, json!({"id": "r-2", "method": "record"})).await;
+        assert_eq!(response, json!({"id": "r-2", "result": "recorded"}));
+
+        let response = call(&rpc, json!({"id": 3, "method": "fail"})).await;
+        assert_eq!(response["id"], 3);
+        assert_eq!(response["error"]["code"], HANDLER_ERROR);
+        assert!(response["error"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("out of stock"));
+
+        let response = call(&rpc, json!({"id": 4, "method": "missing"})).await;
+        assert_eq!(response["error"]["code"], METHOD_NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_notifications_get_no_response() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let rpc = dispatcher(calls.clone());
+
+        assert!(rpc.handle_frame(r#"{"method":"record"}"#).await.is_none());
+        assert!(rpc.handle_frame(r#"{"method":"missing"}"#).await.is_none());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_malformed_frames() {
+        let rpc = dispatcher(Arc::default());
+
+        let response: Value =
+            serde_json::from_str(&rpc.handle_frame("{not json").await.unwrap()).unwrap();
+        assert_eq!(response["id"], Value::Null);
+        assert_eq!(response["error"]["code"], PARSE_ERROR);
+
+        // The id is echoed when it can be found
+        let response = call(&rpc, json!({"id": 9, "params": {}})).await;
+        assert_eq!(response["id"], 9);
+        assert_eq!(response["error"]["code"], INVALID_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_responses_correlate_by_id() {
+        let rpc = dispatcher(Arc::default());
+        let (slow, fast) = tokio::join!(
+            call(
+                &rpc,
+                json!({"id": "slow", "method": "sleep", "params": {"ms": 30}})
+            ),
+            call(
+                &rpc,
+                json!({"id": "fast", "method": "sleep", "params": {"ms": 1}})
+            ),
+        );
+        assert_eq!(slow, json!({"id": "slow", "result": "slept 30"}));
+        assert_eq!(fast, json!({"id": "fast", "result": "slept 1"}));
+    }
+}
@@ -0,0 +1,133 @@
+//! Archetype listing for `allframe archetypes`
+//!
+//! Descriptions and flag help come from the CLI definitions and option
+//! defaults from the config types, so the listing can't drift from what
+//! `ignite` actually does.
+
+use anyhow::Result;
+use clap::{CommandFactory, ValueEnum};
+
+use crate::{Archetype, Cli, CliArchetype, ProjectConfig};
+
+/// `ignite` flags that apply to an archetype, by argument ID
+fn ignite_flags(archetype: CliArchetype) -> &'static [&'static str] {
+    match archetype {
+        CliArchetype::Basic => &["with"],
+        CliArchetype::Gateway | CliArchetype::Bff | CliArchetype::LegacyAdapter => {
+            &["service_name", "api_base_url"]
+        }
+        CliArchetype::Consumer => &["service_name", "group_id"],
+        CliArchetype::Producer
+        | CliArchetype::Scheduled
+        | CliArchetype::WebsocketGateway
+        | CliArchetype::SagaOrchestrator => &["service_name"],
+    }
+}
+
+/// `(flag, help)` for each `ignite` flag of an archetype
+fn flag_help(archetype: CliArchetype) -> Vec<(String, String)> {
+    let cli = Cli::command();
+    let Some(ignite) = cli.find_subcommand("ignite") else {
+        return Vec::new();
+    };
+    ignite_flags(archetype)
+        .iter()
+        .filter_map(|id| ignite.get_arguments().find(|arg| arg.get_id() == *id))
+        .map(|arg| {
+            let flag = format!("--{}", arg.get_long().unwrap_or_default());
+            let help = arg
+                .get_help()
+                .map(|help| help.to_string())
+                .unwrap_or_default();
+            (flag, help.split_whitespace().collect::<Vec<_>>().join(" "))
+        })
+        .collect()
+}
+
+fn name_and_description(archetype: CliArchetype) -> (String, String) {
+    archetype
+        .to_possible_value()
+        .map(|value| {
+            let help = value.get_help().map(|h| h.to_string()).unwrap_or_default();
+            (value.get_name().to_string(), help)
+        })
+        .unwrap_or_default()
+}
+
+/// List every archetype with its description and `ignite` flags
+pub(crate) fn list() -> String {
+    let mut out = String::from("Available archetypes:\n\n");
+    for archetype in CliArchetype::value_variants() {
+        let (name, description) = name_and_description(*archetype);
+        out.push_str(&format!("  {:<20}{}\n", name, description));
+
+        let flags: Vec<String> = flag_help(*archetype)
+            .into_iter()
+            .map(|(flag, _)| flag)
+            .collect();
+        if !flags.is_empty() {
+            out.push_str(&format!("  {:<20}flags: {}\n", "", flags.join(", ")));
+        }
+    }
+    out.push_str(
+        "\nRun `allframe archetypes describe <archetype>` for all of an archetype's options.\n",
+    );
+    out
+}
+
+/// Describe an archetype's flags and its full configuration with defaults
+pub(crate) fn describe(archetype: CliArchetype) -> Result<String> {
+    let (name, description) = name_and_description(archetype);
+    let mut out = format!("{} - {}\n", name, description);
+
+    let flags = flag_help(archetype);
+    if !flags.is_empty() {
+        out.push_str("\nFlags:\n");
+        let width = flags.iter().map(|(flag, _)| flag.len()).max().unwrap_or(0);
+        for (flag, help) in flags {
+            out.push_str(&format!("  {:<width$}  {}\n", flag, help, width = width));
+        }
+    }
+
+    // Only the archetype's own section is set; drop the others
+    let config = ProjectConfig::new("my-service").with_archetype(Archetype::from(archetype));
+    let mut value = serde_json::to_value(&config)?;
+    if let Some(fields) = value.as_object_mut() {
+        fields.retain(|_, field| !field.is_null());
+    }
+    out.push_str("\nConfiguration (defaults for a project named `my-service`):\n\n");
+    for line in serde_yaml::to_string(&value)?.lines() {
+        out.push_str(&format!("  {}\n", line));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_covers_every_archetype() {
+        let listing = list();
+        for archetype in CliArchetype::value_variants() {
+            let (name, description) = name_and_description(*archetype);
+            assert!(listing.contains(&name), "{name}");
+            assert!(!description.is_empty(), "{name}");
+        }
+        assert!(listing.contains("flags: --service-name, --group-id"));
+    }
+
+    #[test]
+    fn test_describe_gateway() {
+        let description = describe(CliArchetype::Gateway).unwrap();
+        assert!(description.starts_with("gateway - API Gateway service"));
+        assert!(description.contains("--api-base-url  Base URL for gateway's external API"));
+        assert!(description.contains("auth_method: none"));
+        assert!(description.contains("rate_limit:"));
+        assert!(!description.contains("consumer:"));
+
+        let description = describe(CliArchetype::Basic).unwrap();
+        assert!(description.contains("--with  Optional allframe-core modules"));
+        assert!(description.contains("archetype: basic"));
+    }
+}
@@ -144,6 +144,57 @@ impl std::fmt::Display for MessageBroker {
     }
 }
 
+/// Optional `allframe-core` module layered onto the basic archetype
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OptionalModule {
+    /// Authentication, with an API key authenticator stub
+    Auth,
+    /// In-memory caching
+    Cache,
+    /// OpenTelemetry tracing, with an observability guard in main
+    Otel,
+}
+
+impl OptionalModule {
+    /// Every module, in the order `--with` lists them
+    pub const ALL: &'static [OptionalModule] = &[Self::Auth, Self::Cache, Self::Otel];
+
+    /// The `allframe-core` feature the module enables
+    pub fn feature(&self) -> &'static str {
+        match self {
+            Self::Auth => "auth",
+            Self::Cache => "cache-memory",
+            Self::Otel => "otel",
+        }
+    }
+}
+
+impl std::fmt::Display for OptionalModule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Auth => write!(f, "auth"),
+            Self::Cache => write!(f, "cache"),
+            Self::Otel => write!(f, "otel"),
+        }
+    }
+}
+
+impl std::str::FromStr for OptionalModule {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|module| module.to_string() == s.trim().to_lowercase())
+            .ok_or_else(|| {
+                let known: Vec<String> = Self::ALL.iter().map(ToString::to_string).collect();
+                format!("Unknown module: {} (known: {})", s, known.join(", "))
+            })
+    }
+}
+
 /// Main project configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectConfig {
@@ -185,6 +236,9 @@ pub struct ProjectConfig {
     /// Anti-corruption layer configuration
     #[serde(default)]
     pub acl: Option<AntiCorruptionLayerConfig>,
+    /// Optional modules layered onto the basic archetype
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modules: Vec<OptionalModule>,
 }
 
 fn default_true() -> bool {
@@ -207,6 +261,7 @@ impl Default for ProjectConfig {
             websocket_gateway: None,
             saga_orchestrator: None,
             acl: None,
+            modules: Vec::new(),
         }
     }
 }
@@ -253,6 +308,14 @@ impl ProjectConfig {
         self
     }
 
+    /// Set the optional modules, sorted and without duplicates
+    pub fn with_modules(mut self, modules: impl IntoIterator<Item = OptionalModule>) -> Self {
+        self.modules = modules.into_iter().collect();
+        self.modules.sort();
+        self.modules.dedup();
+        self
+    }
+
     /// Set the protocols
     pub fn with_protocols(mut self, protocols: Vec<Protocol>) -> Self {
         self.protocols = protocols;
@@ -335,6 +398,9 @@ pub struct GatewayConfig {
     /// API endpoints to wrap
     #[serde(default)]
     pub endpoints: Vec<EndpointConfig>,
+    /// Server URLs advertised in the OpenAPI spec (local, staging, prod...)
+    #[serde(default)]
+    pub docs_servers: Vec<DocsServerConfig>,
 }
 
 impl Default for GatewayConfig {
@@ -349,10 +415,20 @@ impl Default for GatewayConfig {
             server: ServerConfig::default(),
             entities: vec![],
             endpoints: vec![],
+            docs_servers: vec![],
         }
     }
 }
 
+/// Server entry for the OpenAPI `servers` array
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocsServerConfig {
+    /// Server URL (e.g., "https://api.example.com")
+    pub url: String,
+    /// Environment description (e.g., "Production")
+    pub description: String,
+}
+
 /// Rate limiting configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RateLimitConfig {
@@ -979,13 +1055,8 @@ impl Default for SagaOrchestratorConfig {
             display_name: "Saga Orchestrator".to_string(),
             sagas: vec![SagaDefinitionConfig {
                 name: "order_saga".to_string(),
-                description: "Order processing saga".to_string(),
-                steps: vec![
-                    "validate_order".to_string(),
-                    "reserve_inventory".to_string(),
-                    "process_payment".to_string(),
-                    "send_notification".to_string(),
-                ],
+                description: "Reserve stock for an order, then confirm it".to_string(),
+                steps: vec!["reserve_stock".to_string(), "confirm_order".to_string()],
             }],
             retry: RetryConfig::default(),
             server: ServerConfig::default(),
@@ -1095,6 +1166,23 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_optional_module_from_str() {
+        assert_eq!("auth".parse::<OptionalModule>(), Ok(OptionalModule::Auth));
+        assert_eq!(" OTEL".parse::<OptionalModule>(), Ok(OptionalModule::Otel));
+        assert_eq!(OptionalModule::Cache.feature(), "cache-memory");
+
+        let error = "metrics".parse::<OptionalModule>().unwrap_err();
+        assert_eq!(error, "Unknown module: metrics (known: auth, cache, otel)");
+
+        let config = ProjectConfig::new("svc").with_modules([
+            OptionalModule::Otel,
+            OptionalModule::Auth,
+            OptionalModule::Otel,
+        ]);
+        assert_eq!(config.modules, [OptionalModule::Auth, OptionalModule::Otel]);
+    }
+
     #[test]
     fn test_project_config_default() {
         let config = ProjectConfig::new("test-project");
@@ -25,6 +25,12 @@
 //! # Create a basic project
 //! allframe ignite my-service
 //!
+//! # Choose the archetype and its options interactively
+//! allframe ignite my-service --interactive
+//!
+//! # Create a basic project with authentication, caching and tracing wired in
+//! allframe ignite my-svc --with auth,cache,otel
+//!
 //! # Create a gateway project
 //! allframe ignite my-gateway --archetype gateway
 //!
@@ -48,20 +54,38 @@
 //!
 //! # Create a legacy adapter project
 //! allframe ignite my-adapter --archetype legacy-adapter
+//!
+//! # Add a shared errors crate with gRPC and HTTP status mappings
+//! allframe ignite my-gateway --archetype gateway --shared-errors
+//!
+//! # List archetypes, or show every option of one
+//! allframe archetypes
+//! allframe archetypes describe gateway
+//!
+//! # Generate a typed client from a third-party OpenAPI spec
+//! allframe forge openapi petstore.yaml
 //! ```
 
 #![deny(missing_docs)]
 
+mod archetypes;
 pub mod config;
+pub mod openapi;
 pub mod scaffolding;
 pub mod templates;
 pub mod validation;
+mod wizard;
 
-use std::path::{Path, PathBuf};
+use std::{
+    io::IsTerminal,
+    path::{Path, PathBuf},
+};
 
 use clap::{Parser, Subcommand, ValueEnum};
 pub use config::{Archetype, ProjectConfig};
 
+use crate::config::{AuthMethod, MessageBroker, OptionalModule};
+
 /// CLI archetype selection (maps to config::Archetype)
 #[derive(Debug, Clone, Copy, Default, ValueEnum)]
 pub enum CliArchetype {
@@ -153,6 +177,34 @@ enum SagaCommands {
     },
 }
 
+#[derive(Subcommand)]
+enum ArchetypeCommands {
+    /// Show an archetype's flags and every configuration option with its
+    /// default
+    Describe {
+        /// Archetype to describe
+        #[arg(value_enum)]
+        archetype: CliArchetype,
+    },
+}
+
+#[derive(Subcommand)]
+enum ForgeCommands {
+    /// Generate Rust types and a reqwest client from an OpenAPI 3.x spec
+    Openapi {
+        /// Path to the OpenAPI document (JSON or YAML)
+        spec: PathBuf,
+
+        /// Client name (default: derived from the spec's info.title)
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// Output file (default: src/infrastructure/<name>_client.rs)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
 #[derive(Parser)]
 #[command(name = "allframe")]
 #[command(about = "AllFrame CLI - The composable Rust API framework", long_about = None)]
@@ -169,9 +221,9 @@ enum Commands {
         /// Name of the project to create
         name: PathBuf,
 
-        /// Project archetype (basic, gateway, consumer)
-        #[arg(short, long, value_enum, default_value_t = CliArchetype::Basic)]
-        archetype: CliArchetype,
+        /// Project archetype (default: basic)
+        #[arg(short, long, value_enum)]
+        archetype: Option<CliArchetype>,
 
         /// Service name (e.g., "kraken" for gateway, "order-processor" for
         /// consumer)
@@ -186,12 +238,51 @@ enum Commands {
         #[arg(long)]
         group_id: Option<String>,
 
+        /// Also generate a shared `errors` crate mapping errors to gRPC and
+        /// HTTP status codes, making the project a workspace
+        #[arg(long)]
+        shared_errors: bool,
+
+        /// Optional allframe-core modules to wire into a basic project,
+        /// comma-separated: auth, cache, otel
+        #[arg(long = "with", value_delimiter = ',')]
+        with: Vec<OptionalModule>,
+
+        /// Prompt for the archetype and any options not given as flags
+        /// (skipped when stdin isn't a terminal)
+        #[arg(short, long)]
+        interactive: bool,
+    },
+    /// List project archetypes and their options
+    Archetypes {
+        #[command(subcommand)]
+        command: Option<ArchetypeCommands>,
     },
     /// Saga generation and management commands
     Saga {
         #[command(subcommand)]
         command: SagaCommands,
     },
+    /// Code generation from external specifications
+    Forge {
+        #[command(subcommand)]
+        command: ForgeCommands,
+    },
+}
+
+/// Options for `ignite`, from flags or the interactive wizard
+///
+/// Options left unset fall back to the archetype's config defaults.
+#[derive(Debug, Default)]
+struct IgniteOptions {
+    archetype: Option<CliArchetype>,
+    service_name: Option<String>,
+    api_base_url: Option<String>,
+    group_id: Option<String>,
+    auth_method: Option<AuthMethod>,
+    broker: Option<MessageBroker>,
+    shared_errors: bool,
+    modules: Vec<OptionalModule>,
 }
 
 /// Run the AllFrame CLI with command-line arguments.
@@ -211,28 +302,78 @@ pub fn run() -> anyhow::Result<()> {
             service_name,
             api_base_url,
             group_id,
+            shared_errors,
+            with,
+            interactive,
         } => {
-            ignite_project(&name, archetype, service_name, api_base_url, group_id)?;
+            let mut options = IgniteOptions {
+                archetype,
+                service_name,
+                api_base_url,
+                group_id,
+                shared_errors,
+                modules: with,
+                ..IgniteOptions::default()
+            };
+            if interactive {
+                prompt_for_options(&name, &mut options)?;
+            }
+            ignite_project(&name, options)?;
         }
+        Commands::Archetypes { command } => match command {
+            None => print!("{}", archetypes::list()),
+            Some(ArchetypeCommands::Describe { archetype }) => {
+                print!("{}", archetypes::describe(archetype)?);
+            }
+        },
         Commands::Saga { command } => {
             handle_saga_command(command)?;
         }
+        Commands::Forge { command } => {
+            handle_forge_command(command)?;
+        }
     }
 
     Ok(())
 }
 
+/// Run the ignite wizard for the options not given as flags
+///
+/// Without a terminal to prompt on, the wizard is skipped and the flags and
+/// defaults are used as they are, so scripts can pass `--interactive`
+/// safely.
+fn prompt_for_options(project_path: &Path, options: &mut IgniteOptions) -> anyhow::Result<()> {
+    let stdin = std::io::stdin();
+    if !stdin.is_terminal() {
+        eprintln!("stdin is not a terminal, skipping interactive prompts");
+        return Ok(());
+    }
+
+    let project_name = project_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    wizard::Wizard::new(stdin.lock(), std::io::stdout()).complete(project_name, options)
+}
+
 /// Create a new AllFrame project
 ///
 /// This function orchestrates the creation of a new AllFrame project with
 /// Clean Architecture structure.
-fn ignite_project(
-    project_path: &Path,
-    archetype: CliArchetype,
-    service_name: Option<String>,
-    api_base_url: Option<String>,
-    group_id: Option<String>,
-) -> anyhow::Result<()> {
+fn ignite_project(project_path: &Path, options: IgniteOptions) -> anyhow::Result<()> {
+    let IgniteOptions {
+        archetype,
+        service_name,
+        api_base_url,
+        group_id,
+        auth_method,
+        broker,
+        shared_errors,
+        modules,
+    } = options;
+
+    validation::validate_project_path(project_path)?;
+
     let project_name = project_path
         .file_name()
         .and_then(|n| n.to_str())
@@ -240,6 +381,10 @@ fn ignite_project(
 
     validation::validate_project_name(project_name)?;
 
+    if !modules.is_empty() && !matches!(archetype.unwrap_or_default(), CliArchetype::Basic) {
+        anyhow::bail!("--with only applies to the basic archetype");
+    }
+
     if project_path.exists() {
         anyhow::bail!("Directory already exists: {}", project_path.display());
     }
@@ -247,8 +392,8 @@ fn ignite_project(
     std::fs::create_dir_all(project_path)?;
 
     // Build project configuration based on archetype
-    let config = match archetype {
-        CliArchetype::Basic => ProjectConfig::new(project_name),
+    let config = match archetype.unwrap_or_default() {
+        CliArchetype::Basic => ProjectConfig::new(project_name).with_modules(modules),
         CliArchetype::Gateway => {
             let mut config = ProjectConfig::new(project_name).with_archetype(Archetype::Gateway);
 
@@ -266,6 +411,9 @@ fn ignite_project(
                 if let Some(url) = api_base_url {
                     gateway.api_base_url = url;
                 }
+                if let Some(auth_method) = auth_method {
+                    gateway.auth_method = auth_method;
+                }
             }
 
             config
@@ -289,6 +437,9 @@ fn ignite_project(
                 } else {
                     consumer.group_id = format!("{}-group", consumer.service_name);
                 }
+                if let Some(broker) = broker {
+                    consumer.broker = broker;
+                }
             }
 
             config
@@ -306,6 +457,9 @@ fn ignite_project(
                     producer.service_name = project_name.replace('-', "_");
                     producer.display_name = to_title_case(project_name);
                 }
+                if let Some(broker) = broker {
+                    producer.broker = broker;
+                }
             }
 
             config
@@ -414,7 +568,7 @@ fn ignite_project(
     match config.archetype {
         Archetype::Basic => {
             scaffolding::create_directory_structure(project_path)?;
-            scaffolding::generate_files(project_path, project_name)?;
+            scaffolding::generate_files_with_modules(project_path, project_name, &config.modules)?;
         }
         Archetype::Gateway => {
             scaffolding::create_gateway_structure(project_path)?;
@@ -453,12 +607,19 @@ fn ignite_project(
         }
     }
 
+    if shared_errors {
+        scaffolding::generate_shared_errors(project_path)?;
+    }
+
     println!(
         "AllFrame {} project created successfully: {}",
         config.archetype, project_name
     );
     println!("\nNext steps:");
     println!("  cd {}", project_name);
+    if shared_errors {
+        println!("  # Shared errors live in errors/, tested by `cargo test --workspace`");
+    }
 
     match config.archetype {
         Archetype::Gateway => {
@@ -531,6 +692,77 @@ fn to_title_case(s: &str) -> String {
         .join(" ")
 }
 
+/// Handle code generation commands
+fn handle_forge_command(command: ForgeCommands) -> anyhow::Result<()> {
+    match command {
+        ForgeCommands::Openapi { spec, name, output } => {
+            forge_openapi(&spec, name, output)?;
+        }
+    }
+    Ok(())
+}
+
+/// Generate an API client from an OpenAPI spec into the infrastructure layer
+fn forge_openapi(
+    spec_path: &Path,
+    name: Option<String>,
+    output: Option<PathBuf>,
+) -> anyhow::Result<()> {
+    let contents = std::fs::read_to_string(spec_path)
+        .map_err(|e| anyhow::anyhow!("Failed to read spec {}: {}", spec_path.display(), e))?;
+    let spec = openapi::parse_spec(&contents)?;
+
+    let name = name.unwrap_or_else(|| openapi::default_client_name(&spec));
+    let module = format!("{}_client", name.replace('-', "_").to_lowercase());
+    let output = output
+        .unwrap_or_else(|| PathBuf::from("src/infrastructure").join(format!("{}.rs", module)));
+
+    let code = openapi::generate_client(&spec, &name)?;
+
+    if let Some(parent) = output.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&output, code)?;
+    println!(
+        "Generated {} from {}",
+        output.display(),
+        spec_path.display()
+    );
+
+    // Register the module next to the generated file when a mod.rs exists
+    let module = output
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(&module)
+        .to_string();
+    let mod_file = output.with_file_name("mod.rs");
+    if mod_file.exists() {
+        let mod_content = std::fs::read_to_string(&mod_file)?;
+        let declared = mod_content.lines().any(|line| {
+            let line = line.trim();
+            line == format!("mod {};", module) || line == format!("pub mod {};", module)
+        });
+        if !declared {
+            let separator = if mod_content.ends_with('\n') || mod_content.is_empty() {
+                ""
+            } else {
+                "\n"
+            };
+            std::fs::write(
+                &mod_file,
+                format!("{}{}pub mod {};\n", mod_content, separator, module),
+            )?;
+            println!("Added `pub mod {};` to {}", module, mod_file.display());
+        }
+    }
+
+    println!("\nThe generated client requires these dependencies:");
+    println!("  reqwest = {{ version = \"0.12\", features = [\"json\"] }}");
+    println!("  serde = {{ version = \"1.0\", features = [\"derive\"] }}");
+    println!("  serde_json = \"1.0\"");
+    Ok(())
+}
+
 /// Handle saga-related commands
 fn handle_saga_command(command: SagaCommands) -> anyhow::Result<()> {
     match command {
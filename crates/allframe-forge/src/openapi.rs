@@ -0,0 +1,1196 @@
+//! OpenAPI client generation
+//!
+//! Generates typed request/response structs and a `reqwest`-based client from
+//! an OpenAPI 3.x specification (JSON or YAML). This is the consumption side
+//! of AllFrame's OpenAPI support: use it to talk to third-party APIs from a
+//! gateway or BFF.
+//!
+//! # Supported subset
+//!
+//! - Local `$ref`s (`#/components/...`), including references to request
+//!   bodies, responses and parameters
+//! - `components.schemas` objects, string enums, `allOf` composition and
+//!   aliases
+//! - Path, query and header parameters
+//! - JSON request and response bodies (`application/json` and `*+json`)
+//!
+//! External references and non-JSON request bodies are not supported; such
+//! operations are still generated, without a body argument.
+//!
+//! # Example
+//!
+//! ```
+//! use allframe_forge::openapi::{generate_client, parse_spec};
+//!
+//! let spec = parse_spec(
+//!     r##"
+//! openapi: 3.0.3
+//! info: { title: Petstore, version: "1.0" }
+//! paths:
+//!   /pets/{petId}:
+//!     get:
+//!       operationId: getPet
+//!       parameters:
+//!         - { name: petId, in: path, required: true, schema: { type: integer } }
+//!       responses:
+//!         "200":
+//!           description: A pet
+//!           content:
+//!             application/json:
+//!               schema: { $ref: "#/components/schemas/Pet" }
+//! components:
+//!   schemas:
+//!     Pet:
+//!       type: object
+//!       required: [id]
+//!       properties:
+//!         id: { type: integer, format: int64 }
+//!         name: { type: string }
+//! "##,
+//! )
+//! .unwrap();
+//!
+//! let code = generate_client(&spec, "petstore").unwrap();
+//! assert!(code.contains("pub struct Pet {"));
+//! assert!(code.contains("pub async fn get_pet(&self, pet_id: i64)"));
+//! ```
+
+use std::collections::BTreeSet;
+
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+
+/// HTTP methods that may appear in a path item, in generation order
+const METHODS: &[&str] = &[
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Rust keywords that must be written as raw identifiers
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "dyn", "else", "enum", "extern", "false",
+    "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub", "ref",
+    "return", "static", "struct", "trait", "true", "type", "unsafe", "use", "where", "while",
+    "abstract", "become", "box", "do", "final", "gen", "macro", "override", "priv", "try",
+    "typeof", "unsized", "virtual", "yield",
+];
+
+/// Maximum number of `$ref` hops followed before assuming a cycle
+const MAX_REF_DEPTH: usize = 32;
+
+/// Parse an OpenAPI document from JSON or YAML.
+///
+/// Only OpenAPI 3.x documents are accepted.
+pub fn parse_spec(contents: &str) -> Result<Value> {
+    let spec: Value = if contents.trim_start().starts_with('{') {
+        serde_json::from_str(contents).context("Invalid JSON OpenAPI document")?
+    } else {
+        serde_yaml::from_str(contents).context("Invalid YAML OpenAPI document")?
+    };
+
+    match spec.get("openapi").and_then(Value::as_str) {
+        Some(version) if version.starts_with("3.") => Ok(spec),
+        Some(version) => bail!("Unsupported OpenAPI version {} (expected 3.x)", version),
+        None => bail!("Not an OpenAPI 3.x document: missing `openapi` field"),
+    }
+}
+
+/// Generate a Rust module containing the types and client for a spec.
+///
+/// `client_name` is converted to PascalCase and used for the client
+/// (`<Name>Client`) and error (`<Name>ClientError`) types.
+pub fn generate_client(spec: &Value, client_name: &str) -> Result<String> {
+    Generator::new(spec, client_name).generate()
+}
+
+/// Derive a client name from the spec's `info.title`.
+pub fn default_client_name(spec: &Value) -> String {
+    let title = spec
+        .pointer("/info/title")
+        .and_then(Value::as_str)
+        .unwrap_or("api");
+    let name = to_snake_case(title);
+    if name.is_empty() {
+        "api".to_string()
+    } else {
+        name
+    }
+}
+
+/// A function argument generated from a parameter or request body
+struct Arg {
+    ident: String,
+    ty: String,
+    location: ArgLocation,
+    wire_name: String,
+    required: bool,
+    is_vec: bool,
+}
+
+#[derive(PartialEq)]
+enum ArgLocation {
+    Path,
+    Query,
+    Header,
+    Body,
+}
+
+struct Generator<'a> {
+    spec: &'a Value,
+    client: String,
+    types: Vec<String>,
+    type_names: BTreeSet<String>,
+}
+
+impl<'a> Generator<'a> {
+    fn new(spec: &'a Value, client_name: &str) -> Self {
+        Self {
+            spec,
+            client: format!("{}Client", to_pascal_case(client_name)),
+            types: Vec::new(),
+            type_names: BTreeSet::new(),
+        }
+    }
+
+    fn generate(mut self) -> Result<String> {
+        let spec = self.spec;
+
+        if let Some(schemas) = spec
+            .pointer("/components/schemas")
+            .and_then(Value::as_object)
+        {
+            // Reserve component names first so inline types never shadow them
+            for name in schemas.keys() {
+                self.type_names.insert(to_pascal_case(name));
+            }
+            for (name, schema) in schemas {
+                let type_name = to_pascal_case(name);
+                self.define_named(&type_name, schema)
+                    .with_context(|| format!("Invalid schema `{}`", name))?;
+            }
+        }
+
+        let mut methods = Vec::new();
+        if let Some(paths) = spec.get("paths").and_then(Value::as_object) {
+            for (path, item) in paths {
+                let item = self.deref(item)?;
+                for method in METHODS {
+                    if let Some(operation) = item.get(*method) {
+                        methods.push(self.operation(path, method, item, operation).with_context(
+                            || format!("Invalid operation {} {}", method.to_uppercase(), path),
+                        )?);
+                    }
+                }
+            }
+        }
+
+        Ok(self.render(&methods))
+    }
+
+    /// Follow `$ref`s until a concrete object is reached.
+    fn deref(&self, value: &'a Value) -> Result<&'a Value> {
+        let mut current = value;
+        for _ in 0..MAX_REF_DEPTH {
+            match current.get("$ref").and_then(Value::as_str) {
+                Some(reference) => current = self.resolve_ref(reference)?,
+                None => return Ok(current),
+            }
+        }
+        bail!("Reference cycle detected")
+    }
+
+    fn resolve_ref(&self, reference: &str) -> Result<&'a Value> {
+        let Some(pointer) = reference.strip_prefix('#') else {
+            bail!("External reference `{}` is not supported", reference);
+        };
+        self.spec
+            .pointer(pointer)
+            .with_context(|| format!("Unresolved reference `{}`", reference))
+    }
+
+    /// Emit a top-level type with the given name.
+    fn define_named(&mut self, name: &str, schema: &'a Value) -> Result<()> {
+        let description = schema.get("description").and_then(Value::as_str);
+
+        if let Some(variants) = string_enum(schema) {
+            self.types.push(render_enum(name, description, &variants));
+            return Ok(());
+        }
+
+        if let Some(properties) = self.object_properties(schema)? {
+            let code = self.render_struct(name, description, &properties)?;
+            self.types.push(code);
+            return Ok(());
+        }
+
+        let target = self.rust_type(schema, &format!("{}Value", name))?;
+        self.types.push(format!(
+            "{}pub type {} = {};\n",
+            doc_comment("", description),
+            name,
+            target
+        ));
+        Ok(())
+    }
+
+    /// Collect the properties and required set of an object schema, merging
+    /// `allOf` parts. Returns `None` for non-object schemas.
+    #[allow(clippy::type_complexity)]
+    fn object_properties(
+        &self,
+        schema: &'a Value,
+    ) -> Result<Option<(Vec<(&'a String, &'a Value)>, BTreeSet<&'a str>)>> {
+        let mut properties = Vec::new();
+        let mut required = BTreeSet::new();
+
+        if let Some(parts) = schema.get("allOf").and_then(Value::as_array) {
+            for part in parts {
+                let part = self.deref(part)?;
+                match self.object_properties(part)? {
+                    Some((props, req)) => {
+                        properties.extend(props);
+                        required.extend(req);
+                    }
+                    None => return Ok(None),
+                }
+            }
+        }
+
+        let Some(props) = schema.get("properties").and_then(Value::as_object) else {
+            return Ok((!properties.is_empty() || schema.get("allOf").is_some())
+                .then_some((properties, required)));
+        };
+
+        properties.retain(|(name, _)| !props.contains_key(name.as_str()));
+        properties.extend(props.iter());
+        if let Some(req) = schema.get("required").and_then(Value::as_array) {
+            required.extend(req.iter().filter_map(Value::as_str));
+        }
+        Ok(Some((properties, required)))
+    }
+
+    /// Map a schema to a Rust type, emitting inline types as needed.
+    fn rust_type(&mut self, schema: &'a Value, context: &str) -> Result<String> {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            if let Some(name) = reference.strip_prefix("#/components/schemas/") {
+                return Ok(to_pascal_case(name));
+            }
+            let target = self.resolve_ref(reference)?;
+            return self.rust_type(target, context);
+        }
+
+        let ty = self.rust_type_non_null(schema, context)?;
+        if is_nullable(schema) {
+            Ok(format!("Option<{}>", ty))
+        } else {
+            Ok(ty)
+        }
+    }
+
+    fn rust_type_non_null(&mut self, schema: &'a Value, context: &str) -> Result<String> {
+        if let Some(parts) = schema
+            .get("allOf")
+            .or_else(|| schema.get("oneOf"))
+            .or_else(|| schema.get("anyOf"))
+            .and_then(Value::as_array)
+        {
+            let non_null: Vec<_> = parts
+                .iter()
+                .filter(|p| p.get("type").and_then(Value::as_str) != Some("null"))
+                .collect();
+            if non_null.len() == 1 {
+                return self.rust_type(non_null[0], context);
+            }
+        }
+
+        if let Some(variants) = string_enum(schema) {
+            let name = self.unique_name(context);
+            let description = schema.get("description").and_then(Value::as_str);
+            self.types.push(render_enum(&name, description, &variants));
+            return Ok(name);
+        }
+
+        let ty = match schema_type(schema) {
+            Some("string") => match schema.get("format").and_then(Value::as_str) {
+                Some("binary") => "Vec<u8>".to_string(),
+                _ => "String".to_string(),
+            },
+            Some("integer") => match schema.get("format").and_then(Value::as_str) {
+                Some("int32") => "i32".to_string(),
+                _ => "i64".to_string(),
+            },
+            Some("number") => match schema.get("format").and_then(Value::as_str) {
+                Some("float") => "f32".to_string(),
+                _ => "f64".to_string(),
+            },
+            Some("boolean") => "bool".to_string(),
+            Some("array") => match schema.get("items") {
+                Some(items) => {
+                    let item = self.rust_type(items, &format!("{}Item", context))?;
+                    format!("Vec<{}>", item)
+                }
+                None => "Vec<serde_json::Value>".to_string(),
+            },
+            _ => {
+                if let Some(properties) = self.object_properties(schema)? {
+                    if !properties.0.is_empty() {
+                        let name = self.unique_name(context);
+                        let description = schema.get("description").and_then(Value::as_str);
+                        let code = self.render_struct(&name, description, &properties)?;
+                        self.types.push(code);
+                        return Ok(name);
+                    }
+                }
+                match schema.get("additionalProperties") {
+                    Some(additional) if additional.is_object() => {
+                        let value = self.rust_type(additional, &format!("{}Value", context))?;
+                        format!("std::collections::HashMap<String, {}>", value)
+                    }
+                    _ => "serde_json::Value".to_string(),
+                }
+            }
+        };
+        Ok(ty)
+    }
+
+    fn unique_name(&mut self, base: &str) -> String {
+        let base = to_pascal_case(base);
+        let mut name = base.clone();
+        let mut n = 2;
+        while !self.type_names.insert(name.clone()) {
+            name = format!("{}{}", base, n);
+            n += 1;
+        }
+        name
+    }
+
+    fn render_struct(
+        &mut self,
+        name: &str,
+        description: Option<&str>,
+        (properties, required): &(Vec<(&'a String, &'a Value)>, BTreeSet<&'a str>),
+    ) -> Result<String> {
+        let mut fields = String::new();
+        for (prop, schema) in properties {
+            let context = format!("{}{}", name, to_pascal_case(prop));
+            let mut ty = self.rust_type(schema, &context)?;
+            if ty == name {
+                ty = format!("Box<{}>", ty);
+            }
+            let ident = to_field_ident(prop);
+            let is_required = required.contains(prop.as_str());
+
+            let resolved = self.deref(schema)?;
+            let doc = resolved.get("description").and_then(Value::as_str);
+            fields.push_str(&doc_comment("    ", doc));
+
+            let mut attrs = Vec::new();
+            if unraw(&ident) != prop.as_str() {
+                attrs.push(format!("rename = \"{}\"", escape(prop)));
+            }
+            if !is_required && !ty.starts_with("Option<") {
+                ty = format!("Option<{}>", ty);
+            }
+            if ty.starts_with("Option<") {
+                attrs.push("default".to_string());
+                if !is_required {
+                    attrs.push("skip_serializing_if = \"Option::is_none\"".to_string());
+                }
+            }
+            if !attrs.is_empty() {
+                fields.push_str(&format!("    #[serde({})]\n", attrs.join(", ")));
+            }
+            fields.push_str(&format!("    pub {}: {},\n", ident, ty));
+        }
+
+        Ok(format!(
+            "{}#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\npub struct {} {{\n{}}}\n",
+            doc_comment("", description),
+            name,
+            fields
+        ))
+    }
+
+    /// Generate a client method for one operation.
+    fn operation(
+        &mut self,
+        path: &str,
+        method: &str,
+        item: &'a Value,
+        operation: &'a Value,
+    ) -> Result<String> {
+        let fn_name = match operation.get("operationId").and_then(Value::as_str) {
+            Some(id) => to_snake_case(id),
+            None => fallback_operation_name(method, path),
+        };
+        let fn_ident = to_field_ident(&fn_name);
+        let type_prefix = to_pascal_case(&fn_name);
+
+        // Path-level parameters apply unless overridden by the operation
+        let mut parameters: Vec<&Value> = Vec::new();
+        for source in [item.get("parameters"), operation.get("parameters")] {
+            for param in source.and_then(Value::as_array).into_iter().flatten() {
+                let param = self.deref(param)?;
+                let key = (param.get("name"), param.get("in"));
+                parameters.retain(|p| (p.get("name"), p.get("in")) != key);
+                parameters.push(param);
+            }
+        }
+
+        let mut args = Vec::new();
+        for param in parameters {
+            let wire_name = param
+                .get("name")
+                .and_then(Value::as_str)
+                .context("Parameter without a name")?;
+            let location = match param.get("in").and_then(Value::as_str) {
+                Some("path") => ArgLocation::Path,
+                Some("query") => ArgLocation::Query,
+                Some("header") => ArgLocation::Header,
+                _ => continue,
+            };
+            let required = location == ArgLocation::Path
+                || param.get("required").and_then(Value::as_bool) == Some(true);
+            let context = format!("{}{}", type_prefix, to_pascal_case(wire_name));
+            let ty = match param.get("schema") {
+                Some(schema) => self.rust_type(schema, &context)?,
+                None => "String".to_string(),
+            };
+            args.push(Arg {
+                ident: to_field_ident(wire_name),
+                is_vec: ty.starts_with("Vec<"),
+                ty,
+                location,
+                wire_name: wire_name.to_string(),
+                required,
+            });
+        }
+
+        let mut body_note = None;
+        if let Some(body) = operation.get("requestBody") {
+            let body = self.deref(body)?;
+            let required = body.get("required").and_then(Value::as_bool) == Some(true);
+            match json_schema(body) {
+                Some(schema) => {
+                    let ty = self.rust_type(schema, &format!("{}Request", type_prefix))?;
+                    args.push(Arg {
+                        ident: "body".to_string(),
+                        ty,
+                        location: ArgLocation::Body,
+                        wire_name: String::new(),
+                        required,
+                        is_vec: false,
+                    });
+                }
+                None => {
+                    body_note = Some(
+                        "    ///\n    /// The request body of this operation is not JSON and is \
+                         not sent; use\n    /// [`Self::http`] to build the request \
+                         manually.\n",
+                    );
+                }
+            }
+        }
+
+        let (response_ty, response_kind) = self.response_type(operation, &type_prefix)?;
+
+        // Signature
+        let mut signature = vec!["&self".to_string()];
+        for arg in &args {
+            signature.push(format!("{}: {}", arg.ident, arg_type(arg)));
+        }
+
+        // Doc comment
+        let mut doc = String::new();
+        let summary = operation
+            .get("summary")
+            .or_else(|| operation.get("description"))
+            .and_then(Value::as_str);
+        doc.push_str(&doc_comment("    ", summary));
+        if summary.is_some() {
+            doc.push_str("    ///\n");
+        }
+        doc.push_str(&format!("    /// `{} {}`\n", method.to_uppercase(), path));
+        if let Some(note) = body_note {
+            doc.push_str(note);
+        }
+        if operation.get("deprecated").and_then(Value::as_bool) == Some(true) {
+            doc.push_str("    #[deprecated]\n");
+        }
+
+        // Body
+        let mut body = String::new();
+        body.push_str(&format!(
+            "        let url = format!(\"{{}}{}\", self.base_url{});\n",
+            path_template(path, &args),
+            args.iter()
+                .filter(|a| a.location == ArgLocation::Path)
+                .map(|a| if a.ty == "String" {
+                    format!(", encode_path({})", a.ident)
+                } else {
+                    format!(", encode_path(&{}.to_string())", a.ident)
+                })
+                .collect::<String>()
+        ));
+        let binding = if args.iter().any(|a| a.location != ArgLocation::Path) {
+            "let mut request"
+        } else {
+            "let request"
+        };
+        body.push_str(&format!(
+            "        {} = self.http.request(reqwest::Method::{}, url);\n",
+            binding,
+            method.to_uppercase()
+        ));
+        for arg in &args {
+            let call = match arg.location {
+                ArgLocation::Query if arg.is_vec => {
+                    format!(
+                        "for value in {} {{\n{{indent}}    request = request.query(&[(\"{}\", value.to_string())]);\n{{indent}}}}\n",
+                        arg.ident,
+                        escape(&arg.wire_name)
+                    )
+                }
+                ArgLocation::Query => format!(
+                    "request = request.query(&[(\"{}\", {}.to_string())]);\n",
+                    escape(&arg.wire_name),
+                    arg.ident
+                ),
+                ArgLocation::Header => format!(
+                    "request = request.header(\"{}\", {}.to_string());\n",
+                    escape(&arg.wire_name),
+                    arg.ident
+                ),
+                ArgLocation::Body => format!("request = request.json({});\n", arg.ident),
+                ArgLocation::Path => continue,
+            };
+            if arg.required {
+                body.push_str(&format!("        {}", call.replace("{indent}", "        ")));
+            } else {
+                body.push_str(&format!(
+                    "        if let Some({ident}) = {ident} {{\n            {call}        }}\n",
+                    ident = arg.ident,
+                    call = call.replace("{indent}", "            ")
+                ));
+            }
+        }
+        body.push_str(match response_kind {
+            ResponseKind::Json => {
+                "        let response = self.send(request).await?;\n        Ok(response.json().await?)\n"
+            }
+            ResponseKind::Text => {
+                "        let response = self.send(request).await?;\n        Ok(response.text().await?)\n"
+            }
+            ResponseKind::Empty => "        self.send(request).await?;\n        Ok(())\n",
+        });
+
+        Ok(format!(
+            "{doc}    pub async fn {fn_ident}({signature}) -> Result<{response_ty}, {client}Error> {{\n{body}    }}\n",
+            signature = signature.join(", "),
+            client = self.client,
+        ))
+    }
+
+    /// Pick the success response type of an operation.
+    fn response_type(
+        &mut self,
+        operation: &'a Value,
+        type_prefix: &str,
+    ) -> Result<(String, ResponseKind)> {
+        let Some(responses) = operation.get("responses").and_then(Value::as_object) else {
+            return Ok(("()".to_string(), ResponseKind::Empty));
+        };
+
+        let success = responses
+            .iter()
+            .filter(|(code, _)| code.starts_with('2'))
+            .min_by_key(|(code, _)| code.as_str())
+            .or_else(|| responses.iter().find(|(code, _)| *code == "default"));
+
+        let Some((_, response)) = success else {
+            return Ok(("()".to_string(), ResponseKind::Empty));
+        };
+        let response = self.deref(response)?;
+
+        if let Some(schema) = json_schema(response) {
+            let ty = self.rust_type(schema, &format!("{}Response", type_prefix))?;
+            return Ok((ty, ResponseKind::Json));
+        }
+        match response.get("content").and_then(Value::as_object) {
+            Some(content) if !content.is_empty() => Ok(("String".to_string(), ResponseKind::Text)),
+            _ => Ok(("()".to_string(), ResponseKind::Empty)),
+        }
+    }
+
+    fn render(&self, methods: &[String]) -> String {
+        let title = self
+            .spec
+            .pointer("/info/title")
+            .and_then(Value::as_str)
+            .unwrap_or("API");
+        let version = self
+            .spec
+            .pointer("/info/version")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown");
+        let openapi = self
+            .spec
+            .get("openapi")
+            .and_then(Value::as_str)
+            .unwrap_or("3");
+        let base_url = self.spec.pointer("/servers/0/url").and_then(Value::as_str);
+        let client = &self.client;
+
+        let mut out = format!(
+            r#"//! {title} client (API version {version})
+//!
+//! Generated by `allframe forge openapi` from an OpenAPI {openapi} document.
+//! Requires `reqwest` (with the `json` feature), `serde` and `serde_json`.
+//! Regenerate instead of editing by hand.
+
+#![allow(dead_code, clippy::too_many_arguments, clippy::large_enum_variant)]
+
+use serde::{{Deserialize, Serialize}};
+
+"#,
+            title = single_line(title),
+            version = single_line(version),
+            openapi = openapi,
+        );
+
+        for ty in &self.types {
+            out.push_str(ty);
+            out.push('\n');
+        }
+
+        let default_base = base_url
+            .map(|url| {
+                format!(
+                    "\n    /// Base URL declared by the spec\n    pub const DEFAULT_BASE_URL: &'static str = \"{}\";\n",
+                    escape(url)
+                )
+            })
+            .unwrap_or_default();
+
+        out.push_str(&format!(
+            r#"/// Errors returned by [`{client}`]
+#[derive(Debug)]
+pub enum {client}Error {{
+    /// The request could not be sent or the response could not be decoded
+    Http(reqwest::Error),
+    /// The server responded with a non-success status
+    Status {{
+        /// HTTP status code
+        status: u16,
+        /// Response body
+        body: String,
+    }},
+}}
+
+impl std::fmt::Display for {client}Error {{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {{
+        match self {{
+            {client}Error::Http(e) => write!(f, "HTTP error: {{}}", e),
+            {client}Error::Status {{ status, body }} => {{
+                write!(f, "Unexpected status {{}}: {{}}", status, body)
+            }}
+        }}
+    }}
+}}
+
+impl std::error::Error for {client}Error {{
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {{
+        match self {{
+            {client}Error::Http(e) => Some(e),
+            {client}Error::Status {{ .. }} => None,
+        }}
+    }}
+}}
+
+impl From<reqwest::Error> for {client}Error {{
+    fn from(e: reqwest::Error) -> Self {{
+        {client}Error::Http(e)
+    }}
+}}
+
+/// HTTP client for the {title_line} API
+#[derive(Debug, Clone)]
+pub struct {client} {{
+    http: reqwest::Client,
+    base_url: String,
+}}
+
+impl {client} {{{default_base}
+    /// Create a client for the given base URL
+    pub fn new(base_url: impl Into<String>) -> Self {{
+        Self::with_client(reqwest::Client::new(), base_url)
+    }}
+
+    /// Create a client that reuses an existing `reqwest::Client`
+    pub fn with_client(http: reqwest::Client, base_url: impl Into<String>) -> Self {{
+        Self {{
+            http,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+        }}
+    }}
+
+    /// The underlying HTTP client
+    pub fn http(&self) -> &reqwest::Client {{
+        &self.http
+    }}
+
+    /// The base URL requests are sent to
+    pub fn base_url(&self) -> &str {{
+        &self.base_url
+    }}
+
+    async fn send(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<reqwest::Response, {client}Error> {{
+        let response = request.send().await?;
+        let status = response.status();
+        if status.is_success() {{
+            Ok(response)
+        }} else {{
+            let body = response.text().await.unwrap_or_default();
+            Err({client}Error::Status {{
+                status: status.as_u16(),
+                body,
+            }})
+        }}
+    }}
+"#,
+            client = client,
+            title_line = single_line(title),
+            default_base = default_base,
+        ));
+
+        for method in methods {
+            out.push('\n');
+            out.push_str(method);
+        }
+        out.push_str("}\n");
+
+        out.push_str(
+            r#"
+/// Percent-encode a path segment
+fn encode_path(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+"#,
+        );
+        out
+    }
+}
+
+enum ResponseKind {
+    Json,
+    Text,
+    Empty,
+}
+
+/// The argument type as it appears in a method signature
+fn arg_type(arg: &Arg) -> String {
+    let base = if arg.location == ArgLocation::Body {
+        format!("&{}", arg.ty)
+    } else if arg.ty == "String" {
+        "&str".to_string()
+    } else if let Some(inner) = arg
+        .ty
+        .strip_prefix("Vec<")
+        .and_then(|t| t.strip_suffix('>'))
+    {
+        format!("&[{}]", inner)
+    } else {
+        arg.ty.clone()
+    };
+    if arg.required {
+        base
+    } else {
+        format!("Option<{}>", base)
+    }
+}
+
+/// Convert `/pets/{petId}` into a format string with `{}` placeholders.
+fn path_template(path: &str, args: &[Arg]) -> String {
+    let mut template = String::new();
+    let mut rest = path;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 1..start + end];
+        template.push_str(&escape_format(&rest[..start]));
+        if args
+            .iter()
+            .any(|a| a.location == ArgLocation::Path && a.wire_name == name)
+        {
+            template.push_str("{}");
+        } else {
+            template.push_str(&escape_format(&rest[start..start + end + 1]));
+        }
+        rest = &rest[start + end + 1..];
+    }
+    template.push_str(&escape_format(rest));
+    template
+}
+
+fn escape_format(s: &str) -> String {
+    escape(s).replace('{', "{{").replace('}', "}}")
+}
+
+/// Find the JSON schema of a request body or response.
+fn json_schema(body: &Value) -> Option<&Value> {
+    let content = body.get("content")?.as_object()?;
+    content
+        .iter()
+        .find(|(media, _)| {
+            let media = media.split(';').next().unwrap_or_default().trim();
+            media == "application/json" || media.ends_with("+json")
+        })
+        .and_then(|(_, media)| media.get("schema"))
+}
+
+/// The schema's primary `type`, ignoring `null` in 3.1 type arrays.
+fn schema_type(schema: &Value) -> Option<&str> {
+    match schema.get("type")? {
+        Value::String(ty) => Some(ty),
+        Value::Array(types) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .find(|ty| *ty != "null"),
+        _ => None,
+    }
+}
+
+fn is_nullable(schema: &Value) -> bool {
+    schema.get("nullable").and_then(Value::as_bool) == Some(true)
+        || schema
+            .get("type")
+            .and_then(Value::as_array)
+            .is_some_and(|types| types.iter().any(|t| t == "null"))
+}
+
+/// The variants of a string enum schema, if it is one.
+fn string_enum(schema: &Value) -> Option<Vec<&str>> {
+    if schema_type(schema).is_some_and(|ty| ty != "string") {
+        return None;
+    }
+    let values = schema.get("enum")?.as_array()?;
+    let variants: Vec<&str> = values.iter().filter_map(Value::as_str).collect();
+    (!variants.is_empty() && variants.len() == values.len()).then_some(variants)
+}
+
+fn render_enum(name: &str, description: Option<&str>, variants: &[&str]) -> String {
+    let mut seen = BTreeSet::new();
+    let mut body = String::new();
+    for value in variants {
+        let mut variant = to_pascal_case(value);
+        if variant.is_empty() || variant.starts_with(|c: char| c.is_ascii_digit()) {
+            variant = format!("V{}", variant);
+        }
+        while !seen.insert(variant.clone()) {
+            variant.push('_');
+        }
+        body.push_str(&format!(
+            "    #[serde(rename = \"{}\")]\n    {},\n",
+            escape(value),
+            variant
+        ));
+    }
+    format!(
+        "{}#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]\npub enum {} {{\n{}}}\n",
+        doc_comment("", description),
+        name,
+        body
+    )
+}
+
+fn doc_comment(indent: &str, text: Option<&str>) -> String {
+    let Some(text) = text.map(str::trim).filter(|t| !t.is_empty()) else {
+        return String::new();
+    };
+    text.lines()
+        .map(|line| {
+            let line = line.trim_end();
+            if line.is_empty() {
+                format!("{}///\n", indent)
+            } else {
+                format!("{}/// {}\n", indent, line)
+            }
+        })
+        .collect()
+}
+
+fn fallback_operation_name(method: &str, path: &str) -> String {
+    let mut parts = vec![method.to_string()];
+    for segment in path.split('/').filter(|s| !s.is_empty()) {
+        match segment.strip_prefix('{').and_then(|s| s.strip_suffix('}')) {
+            Some(param) => parts.push(format!("by_{}", to_snake_case(param))),
+            None => parts.push(to_snake_case(segment)),
+        }
+    }
+    parts.join("_")
+}
+
+/// Split an identifier into lowercase words on case changes and
+/// non-alphanumeric characters.
+fn words(s: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let chars: Vec<char> = s.chars().collect();
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_ascii_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            continue;
+        }
+        let boundary = c.is_ascii_uppercase()
+            && !current.is_empty()
+            && (chars[i - 1].is_ascii_lowercase()
+                || chars[i - 1].is_ascii_digit()
+                || chars.get(i + 1).is_some_and(|n| n.is_ascii_lowercase()));
+        if boundary {
+            words.push(std::mem::take(&mut current));
+        }
+        current.push(c.to_ascii_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+fn to_snake_case(s: &str) -> String {
+    words(s).join("_")
+}
+
+fn to_pascal_case(s: &str) -> String {
+    words(s)
+        .iter()
+        .map(|word| {
+            let mut chars = word.chars();
+            match chars.next() {
+                Some(first) => first.to_ascii_uppercase().to_string() + chars.as_str(),
+                None => String::new(),
+            }
+        })
+        .collect()
+}
+
+/// Convert a property or parameter name into a valid field identifier.
+fn to_field_ident(name: &str) -> String {
+    let mut ident = to_snake_case(name);
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident = format!("_{}", ident);
+    }
+    match ident.as_str() {
+        "self" | "super" | "crate" | "Self" => format!("{}_", ident),
+        _ if KEYWORDS.contains(&ident.as_str()) => format!("r#{}", ident),
+        _ => ident,
+    }
+}
+
+fn unraw(ident: &str) -> &str {
+    ident.strip_prefix("r#").unwrap_or(ident)
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn single_line(s: &str) -> String {
+    s.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PETSTORE: &str = r##"
+openapi: 3.0.3
+info:
+  title: Swagger Petstore
+  version: 1.0.0
+servers:
+  - url: https://petstore.example.com/v1
+paths:
+  /pets:
+    get:
+      summary: List all pets
+      operationId: listPets
+      parameters:
+        - $ref: "#/components/parameters/Limit"
+        - name: tags
+          in: query
+          schema:
+            type: array
+            items: { type: string }
+      responses:
+        "200":
+          $ref: "#/components/responses/PetList"
+    post:
+      operationId: createPet
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema:
+              $ref: "#/components/schemas/NewPet"
+      responses:
+        "201":
+          description: Created
+          content:
+            application/json:
+              schema:
+                $ref: "#/components/schemas/Pet"
+  /pets/{petId}:
+    parameters:
+      - name: petId
+        in: path
+        required: true
+        schema: { type: string }
+    delete:
+      responses:
+        "204":
+          description: Deleted
+components:
+  parameters:
+    Limit:
+      name: limit
+      in: query
+      schema: { type: integer, format: int32 }
+  responses:
+    PetList:
+      description: A list of pets
+      content:
+        application/json:
+          schema:
+            type: array
+            items:
+              $ref: "#/components/schemas/Pet"
+  schemas:
+    NewPet:
+      type: object
+      required: [name]
+      properties:
+        name: { type: string }
+        tag: { type: string, nullable: true }
+    Pet:
+      description: A pet in the store
+      allOf:
+        - $ref: "#/components/schemas/NewPet"
+        - type: object
+          required: [id, status]
+          properties:
+            id: { type: integer, format: int64 }
+            status:
+              type: string
+              enum: [available, pending, sold]
+            owner:
+              type: object
+              properties:
+                displayName: { type: string }
+"##;
+
+    fn petstore() -> String {
+        let spec = parse_spec(PETSTORE).unwrap();
+        generate_client(&spec, "petstore").unwrap()
+    }
+
+    #[test]
+    fn test_parse_spec_rejects_swagger_2() {
+        let err = parse_spec(r#"{"swagger": "2.0"}"#).unwrap_err();
+        assert!(err.to_string().contains("missing `openapi`"));
+
+        let err = parse_spec("openapi: 2.0.0").unwrap_err();
+        assert!(err.to_string().contains("Unsupported OpenAPI version"));
+    }
+
+    #[test]
+    fn test_generates_component_types() {
+        let code = petstore();
+
+        assert!(code.contains("pub struct NewPet {\n    pub name: String,\n"));
+        assert!(code.contains("    pub tag: Option<String>,"));
+        // allOf parts are merged into a single struct
+        assert!(code.contains("/// A pet in the store\n#[derive"));
+        assert!(code.contains("pub struct Pet {"));
+        assert!(code.contains("    pub id: i64,"));
+        assert!(code.contains("    pub status: PetStatus,"));
+        assert!(code.contains(
+            "pub enum PetStatus {\n    #[serde(rename = \"available\")]\n    Available,"
+        ));
+        // Inline objects become named structs
+        assert!(code.contains("    pub owner: Option<PetOwner>,"));
+        assert!(code.contains(
+            "    #[serde(rename = \"displayName\", default, skip_serializing_if = \"Option::is_none\")]\n    pub display_name: Option<String>,"
+        ));
+    }
+
+    #[test]
+    fn test_generates_client_methods() {
+        let code = petstore();
+
+        assert!(code.contains("pub struct PetstoreClient {"));
+        assert!(code.contains("pub enum PetstoreClientError {"));
+        assert!(code.contains(
+            "pub const DEFAULT_BASE_URL: &'static str = \"https://petstore.example.com/v1\";"
+        ));
+
+        // $ref'd parameter and response, optional array query parameter
+        assert!(code.contains(
+            "pub async fn list_pets(&self, limit: Option<i32>, tags: Option<&[String]>) -> Result<Vec<Pet>, PetstoreClientError>"
+        ));
+        assert!(code.contains("request = request.query(&[(\"tags\", value.to_string())]);"));
+
+        // JSON request body
+        assert!(code.contains(
+            "pub async fn create_pet(&self, body: &NewPet) -> Result<Pet, PetstoreClientError>"
+        ));
+        assert!(code.contains("request = request.json(body);"));
+
+        // Path-level parameter, derived name, empty response
+        assert!(code.contains(
+            "pub async fn delete_pets_by_pet_id(&self, pet_id: &str) -> Result<(), PetstoreClientError>"
+        ));
+        assert!(
+            code.contains("let url = format!(\"{}/pets/{}\", self.base_url, encode_path(pet_id));")
+        );
+    }
+
+    #[test]
+    fn test_rejects_external_refs() {
+        let spec = parse_spec(
+            r#"{"openapi": "3.1.0", "info": {"title": "x", "version": "1"},
+                "components": {"schemas": {"A": {"$ref": "other.yaml#/A"}}}}"#,
+        )
+        .unwrap();
+        let err = generate_client(&spec, "x").unwrap_err();
+        assert!(format!("{:#}", err).contains("External reference"));
+    }
+
+    #[test]
+    fn test_identifiers() {
+        assert_eq!(to_snake_case("getPetByID"), "get_pet_by_id");
+        assert_eq!(to_snake_case("HTTPResponse"), "http_response");
+        assert_eq!(to_pascal_case("pet-store api"), "PetStoreApi");
+        assert_eq!(to_field_ident("type"), "r#type");
+        assert_eq!(to_field_ident("2fa"), "_2fa");
+        assert_eq!(to_field_ident("self"), "self_");
+        assert_eq!(
+            default_client_name(&parse_spec(PETSTORE).unwrap()),
+            "swagger_petstore"
+        );
+    }
+}
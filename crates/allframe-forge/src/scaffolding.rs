@@ -28,10 +28,27 @@ use std::{fs, path::Path};
 use anyhow::Result;
 
 use crate::{
-    config::ProjectConfig,
-    templates::{self, acl, bff, consumer, gateway, producer, saga, scheduled, websocket},
+    config::{OptionalModule, ProjectConfig},
+    templates::{self, acl, bff, consumer, errors, gateway, producer, saga, scheduled, websocket},
+    validation::confined_path,
 };
 
+/// Write a generated file, refusing paths that escape the project root
+fn write_file(
+    project_path: &Path,
+    relative: impl AsRef<Path>,
+    contents: impl AsRef<[u8]>,
+) -> Result<()> {
+    fs::write(confined_path(project_path, relative.as_ref())?, contents)?;
+    Ok(())
+}
+
+/// Create a generated directory, refusing paths that escape the project root
+fn create_dir(project_path: &Path, relative: &str) -> Result<()> {
+    fs::create_dir_all(confined_path(project_path, Path::new(relative))?)?;
+    Ok(())
+}
+
 /// Create the Clean Architecture directory structure
 ///
 /// Creates all necessary directories for a Clean Architecture project:
@@ -58,7 +75,7 @@ pub fn create_directory_structure(project_path: &Path) -> Result<()> {
     ];
 
     for dir in dirs {
-        fs::create_dir_all(project_path.join(dir))?;
+        create_dir(project_path, dir)?;
     }
 
     Ok(())
@@ -89,6 +106,9 @@ pub fn create_directory_structure(project_path: &Path) -> Result<()> {
 /// ## Presentation Layer
 /// - `src/presentation/mod.rs` - Presentation module (placeholder)
 ///
+/// ## Tests
+/// - `tests/integration.rs` - Greeting use case run end-to-end
+///
 /// # Arguments
 /// * `project_path` - Root path where files will be created
 /// * `project_name` - Name of the project (used in Cargo.toml and README)
@@ -96,54 +116,91 @@ pub fn create_directory_structure(project_path: &Path) -> Result<()> {
 /// # Errors
 /// Returns an error if any file write operation fails
 pub fn generate_files(project_path: &Path, project_name: &str) -> Result<()> {
+    generate_files_with_modules(project_path, project_name, &[])
+}
+
+/// Generate the basic project's files with optional modules wired in
+///
+/// Like [`generate_files`], and for each module enables its
+/// `allframe-core` feature in `Cargo.toml` and adds minimal wiring to
+/// `src/main.rs`: an observability guard for `otel`, a `MemoryCache` for
+/// `cache`, and for `auth` an API key authenticator in
+/// `src/infrastructure/authenticator.rs`.
+///
+/// # Errors
+/// Returns an error if any file write operation fails
+pub fn generate_files_with_modules(
+    project_path: &Path,
+    project_name: &str,
+    modules: &[OptionalModule],
+) -> Result<()> {
     // Root files
-    fs::write(
-        project_path.join("Cargo.toml"),
-        templates::cargo_toml(project_name),
+    write_file(
+        project_path,
+        "Cargo.toml",
+        templates::cargo_toml_with_modules(project_name, modules),
     )?;
-    fs::write(project_path.join("src/main.rs"), templates::main_rs())?;
-    fs::write(project_path.join(".gitignore"), templates::gitignore())?;
-    fs::write(
-        project_path.join("README.md"),
-        templates::readme(project_name),
+    write_file(
+        project_path,
+        "src/main.rs",
+        templates::main_rs_with_modules(modules),
     )?;
+    write_file(project_path, ".gitignore", templates::gitignore())?;
+    write_file(project_path, "README.md", templates::readme(project_name))?;
 
     // Domain layer
-    fs::write(
-        project_path.join("src/domain/mod.rs"),
-        templates::domain_mod(),
-    )?;
-    fs::write(
-        project_path.join("src/domain/greeter.rs"),
+    write_file(project_path, "src/domain/mod.rs", templates::domain_mod())?;
+    write_file(
+        project_path,
+        "src/domain/greeter.rs",
         templates::domain_greeter(),
     )?;
 
     // Application layer
-    fs::write(
-        project_path.join("src/application/mod.rs"),
+    write_file(
+        project_path,
+        "src/application/mod.rs",
         templates::application_mod(),
     )?;
-    fs::write(
-        project_path.join("src/application/greeting_service.rs"),
+    write_file(
+        project_path,
+        "src/application/greeting_service.rs",
         templates::application_greeting_service(),
     )?;
 
     // Infrastructure layer
-    fs::write(
-        project_path.join("src/infrastructure/mod.rs"),
-        templates::infrastructure_mod(),
-    )?;
-    fs::write(
-        project_path.join("src/infrastructure/console_greeter.rs"),
+    write_file(
+        project_path,
+        "src/infrastructure/mod.rs",
+        templates::infrastructure_mod_with_modules(modules),
+    )?;
+    write_file(
+        project_path,
+        "src/infrastructure/console_greeter.rs",
         templates::infrastructure_console_greeter(),
     )?;
+    if modules.contains(&OptionalModule::Auth) {
+        write_file(
+            project_path,
+            "src/infrastructure/authenticator.rs",
+            templates::infrastructure_authenticator(),
+        )?;
+    }
 
     // Presentation layer
-    fs::write(
-        project_path.join("src/presentation/mod.rs"),
+    write_file(
+        project_path,
+        "src/presentation/mod.rs",
         templates::presentation_mod(),
     )?;
 
+    // Integration tests
+    write_file(
+        project_path,
+        "tests/integration.rs",
+        templates::integration_tests_with_modules(modules),
+    )?;
+
     Ok(())
 }
 
@@ -175,7 +232,7 @@ pub fn create_gateway_structure(project_path: &Path) -> Result<()> {
     ];
 
     for dir in dirs {
-        fs::create_dir_all(project_path.join(dir))?;
+        create_dir(project_path, dir)?;
     }
 
     Ok(())
@@ -228,85 +285,102 @@ pub fn create_gateway_structure(project_path: &Path) -> Result<()> {
 /// Returns an error if any file write operation fails
 pub fn generate_gateway_files(project_path: &Path, config: &ProjectConfig) -> Result<()> {
     // Root files
-    fs::write(project_path.join("Cargo.toml"), gateway::cargo_toml(config))?;
-    fs::write(project_path.join("build.rs"), gateway::build_rs(config))?;
-    fs::write(project_path.join("src/main.rs"), gateway::main_rs(config))?;
-    fs::write(project_path.join(".gitignore"), templates::gitignore())?;
-    fs::write(project_path.join("README.md"), gateway::readme(config))?;
-    fs::write(project_path.join("Dockerfile"), gateway::dockerfile(config))?;
+    write_file(project_path, "Cargo.toml", gateway::cargo_toml(config))?;
+    write_file(project_path, "build.rs", gateway::build_rs(config))?;
+    write_file(project_path, "src/main.rs", gateway::main_rs(config))?;
+    write_file(project_path, ".gitignore", templates::gitignore())?;
+    write_file(project_path, "README.md", gateway::readme(config))?;
+    write_file(project_path, "Dockerfile", gateway::dockerfile(config))?;
 
     // Protocol buffers
     let gateway_config = config.gateway.as_ref().ok_or_else(|| {
         anyhow::anyhow!("Gateway config required for gateway archetype")
     })?;
-    fs::write(
-        project_path.join(format!("proto/{}.proto", gateway_config.service_name)),
+    write_file(
+        project_path,
+        format!("proto/{}.proto", gateway_config.service_name),
         gateway::proto_file(config),
     )?;
 
     // Configuration files
-    fs::write(
-        project_path.join("src/config.rs"),
-        gateway::config_rs(config),
-    )?;
-    fs::write(project_path.join("src/error.rs"), gateway::error_rs(config))?;
+    write_file(project_path, "src/config.rs", gateway::config_rs(config))?;
+    write_file(project_path, "src/error.rs", gateway::error_rs(config))?;
 
     // Domain layer
-    fs::write(
-        project_path.join("src/domain/mod.rs"),
+    write_file(
+        project_path,
+        "src/domain/mod.rs",
         gateway::domain_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/domain/entities.rs"),
+    write_file(
+        project_path,
+        "src/domain/entities.rs",
         gateway::domain_entities(config),
     )?;
-    fs::write(
-        project_path.join("src/domain/repository.rs"),
+    write_file(
+        project_path,
+        "src/domain/repository.rs",
         gateway::domain_repository(config),
     )?;
 
     // Application layer
-    fs::write(
-        project_path.join("src/application/mod.rs"),
+    write_file(
+        project_path,
+        "src/application/mod.rs",
         gateway::application_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/application/service.rs"),
+    write_file(
+        project_path,
+        "src/application/service.rs",
         gateway::application_service(config),
     )?;
 
     // Infrastructure layer
-    fs::write(
-        project_path.join("src/infrastructure/mod.rs"),
+    write_file(
+        project_path,
+        "src/infrastructure/mod.rs",
         gateway::infrastructure_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/infrastructure/http_client.rs"),
+    write_file(
+        project_path,
+        "src/infrastructure/http_client.rs",
         gateway::infrastructure_http_client(config),
     )?;
-    fs::write(
-        project_path.join("src/infrastructure/auth.rs"),
+    write_file(
+        project_path,
+        "src/infrastructure/auth.rs",
         gateway::infrastructure_auth(config),
     )?;
-    fs::write(
-        project_path.join("src/infrastructure/cache.rs"),
+    write_file(
+        project_path,
+        "src/infrastructure/cache.rs",
         gateway::infrastructure_cache(config),
     )?;
-    fs::write(
-        project_path.join("src/infrastructure/rate_limiter.rs"),
+    write_file(
+        project_path,
+        "src/infrastructure/rate_limiter.rs",
         gateway::infrastructure_rate_limiter(config),
     )?;
 
     // Presentation layer
-    fs::write(
-        project_path.join("src/presentation/mod.rs"),
+    write_file(
+        project_path,
+        "src/presentation/mod.rs",
         gateway::presentation_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/presentation/grpc.rs"),
+    write_file(
+        project_path,
+        "src/presentation/grpc.rs",
         gateway::presentation_grpc(config),
     )?;
 
+    // Integration tests
+    write_file(
+        project_path,
+        "tests/integration.rs",
+        gateway::integration_tests(config),
+    )?;
+
     Ok(())
 }
 
@@ -316,7 +390,7 @@ pub fn generate_gateway_files(project_path: &Path, config: &ProjectConfig) -> Re
 /// - `src/` - Source code root
 /// - `src/domain/` - Domain layer (events, handlers)
 /// - `src/application/` - Application layer (consumer orchestration)
-/// - `src/infrastructure/` - Infrastructure layer (broker, idempotency, health)
+/// - `src/infrastructure/` - Infrastructure layer (broker settings, health)
 /// - `tests/` - Integration tests
 ///
 /// # Arguments
@@ -334,7 +408,7 @@ pub fn create_consumer_structure(project_path: &Path) -> Result<()> {
     ];
 
     for dir in dirs {
-        fs::create_dir_all(project_path.join(dir))?;
+        create_dir(project_path, dir)?;
     }
 
     Ok(())
@@ -358,13 +432,12 @@ pub fn create_consumer_structure(project_path: &Path) -> Result<()> {
 ///
 /// ## Application Layer
 /// - `src/application/mod.rs` - Application module exports
-/// - `src/application/consumer.rs` - Consumer orchestration with
-///   retry/idempotency
+/// - `src/application/consumer.rs` - Consume loop with retry, idempotency
+///   and DLQ
 ///
 /// ## Infrastructure Layer
 /// - `src/infrastructure/mod.rs` - Infrastructure module exports
-/// - `src/infrastructure/broker.rs` - Message broker implementation (Kafka)
-/// - `src/infrastructure/idempotency.rs` - Idempotency store
+/// - `src/infrastructure/broker.rs` - Kafka connection settings
 /// - `src/infrastructure/health.rs` - Health check server
 ///
 /// ## Configuration
@@ -379,70 +452,69 @@ pub fn create_consumer_structure(project_path: &Path) -> Result<()> {
 /// Returns an error if any file write operation fails
 pub fn generate_consumer_files(project_path: &Path, config: &ProjectConfig) -> Result<()> {
     // Root files
-    fs::write(
-        project_path.join("Cargo.toml"),
-        consumer::cargo_toml(config),
-    )?;
-    fs::write(project_path.join("src/main.rs"), consumer::main_rs(config))?;
-    fs::write(project_path.join(".gitignore"), templates::gitignore())?;
-    fs::write(project_path.join("README.md"), consumer::readme(config))?;
-    fs::write(
-        project_path.join("Dockerfile"),
-        consumer::dockerfile(config),
-    )?;
+    write_file(project_path, "Cargo.toml", consumer::cargo_toml(config))?;
+    write_file(project_path, "src/main.rs", consumer::main_rs(config))?;
+    write_file(project_path, ".gitignore", templates::gitignore())?;
+    write_file(project_path, "README.md", consumer::readme(config))?;
+    write_file(project_path, "Dockerfile", consumer::dockerfile(config))?;
 
     // Configuration files
-    fs::write(
-        project_path.join("src/config.rs"),
-        consumer::config_rs(config),
-    )?;
-    fs::write(
-        project_path.join("src/error.rs"),
-        consumer::error_rs(config),
-    )?;
+    write_file(project_path, "src/config.rs", consumer::config_rs(config))?;
+    write_file(project_path, "src/error.rs", consumer::error_rs(config))?;
 
     // Domain layer
-    fs::write(
-        project_path.join("src/domain/mod.rs"),
+    write_file(
+        project_path,
+        "src/domain/mod.rs",
         consumer::domain_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/domain/events.rs"),
+    write_file(
+        project_path,
+        "src/domain/events.rs",
         consumer::domain_events(config),
     )?;
-    fs::write(
-        project_path.join("src/domain/handlers.rs"),
+    write_file(
+        project_path,
+        "src/domain/handlers.rs",
         consumer::domain_handlers(config),
     )?;
 
     // Application layer
-    fs::write(
-        project_path.join("src/application/mod.rs"),
+    write_file(
+        project_path,
+        "src/application/mod.rs",
         consumer::application_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/application/consumer.rs"),
+    write_file(
+        project_path,
+        "src/application/consumer.rs",
         consumer::application_consumer(config),
     )?;
 
     // Infrastructure layer
-    fs::write(
-        project_path.join("src/infrastructure/mod.rs"),
+    write_file(
+        project_path,
+        "src/infrastructure/mod.rs",
         consumer::infrastructure_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/infrastructure/broker.rs"),
+    write_file(
+        project_path,
+        "src/infrastructure/broker.rs",
         consumer::infrastructure_broker(config),
     )?;
-    fs::write(
-        project_path.join("src/infrastructure/idempotency.rs"),
-        consumer::infrastructure_idempotency(config),
-    )?;
-    fs::write(
-        project_path.join("src/infrastructure/health.rs"),
+    write_file(
+        project_path,
+        "src/infrastructure/health.rs",
         consumer::infrastructure_health(config),
     )?;
 
+    // Integration tests
+    write_file(
+        project_path,
+        "tests/integration.rs",
+        consumer::integration_tests(config),
+    )?;
+
     Ok(())
 }
 
@@ -452,9 +524,9 @@ pub fn generate_consumer_files(project_path: &Path, config: &ProjectConfig) -> R
 /// - `src/` - Source code root
 /// - `src/domain/` - Domain layer (entities, events, repository)
 /// - `src/application/` - Application layer (service, outbox)
-/// - `src/infrastructure/` - Infrastructure layer (repository, outbox,
-///   publisher)
+/// - `src/infrastructure/` - Infrastructure layer (repository, outbox)
 /// - `src/presentation/` - Presentation layer (HTTP handlers)
+/// - `migrations/` - SQL migrations
 /// - `tests/` - Integration tests
 ///
 /// # Arguments
@@ -469,11 +541,12 @@ pub fn create_producer_structure(project_path: &Path) -> Result<()> {
         "src/application",
         "src/infrastructure",
         "src/presentation",
+        "migrations",
         "tests",
     ];
 
     for dir in dirs {
-        fs::create_dir_all(project_path.join(dir))?;
+        create_dir(project_path, dir)?;
     }
 
     Ok(())
@@ -486,7 +559,7 @@ pub fn create_producer_structure(project_path: &Path) -> Result<()> {
 /// ## Root Files
 /// - `Cargo.toml` - Project manifest with producer dependencies
 /// - `src/main.rs` - Application entry point with API server and outbox
-///   processor
+///   relay
 /// - `README.md` - Project documentation
 /// - `Dockerfile` - Container build file
 /// - `.gitignore` - Git ignore rules
@@ -505,9 +578,8 @@ pub fn create_producer_structure(project_path: &Path) -> Result<()> {
 /// ## Infrastructure Layer
 /// - `src/infrastructure/mod.rs` - Infrastructure module exports
 /// - `src/infrastructure/repository.rs` - PostgreSQL repository
-/// - `src/infrastructure/outbox.rs` - PostgreSQL outbox
-/// - `src/infrastructure/publisher.rs` - Kafka event publisher
-/// - `src/infrastructure/outbox_processor.rs` - Outbox processor
+/// - `src/infrastructure/outbox.rs` - PostgreSQL outbox, read by the outbox
+///   relay
 /// - `src/infrastructure/health.rs` - Health check server
 ///
 /// ## Presentation Layer
@@ -518,6 +590,9 @@ pub fn create_producer_structure(project_path: &Path) -> Result<()> {
 /// - `src/config.rs` - Service configuration
 /// - `src/error.rs` - Error types
 ///
+/// ## Migrations
+/// - `migrations/0001_create_tables.sql` - Entity and outbox tables
+///
 /// # Arguments
 /// * `project_path` - Root path where files will be created
 /// * `config` - Project configuration
@@ -526,96 +601,103 @@ pub fn create_producer_structure(project_path: &Path) -> Result<()> {
 /// Returns an error if any file write operation fails
 pub fn generate_producer_files(project_path: &Path, config: &ProjectConfig) -> Result<()> {
     // Root files
-    fs::write(
-        project_path.join("Cargo.toml"),
-        producer::cargo_toml(config),
-    )?;
-    fs::write(project_path.join("src/main.rs"), producer::main_rs(config))?;
-    fs::write(project_path.join(".gitignore"), templates::gitignore())?;
-    fs::write(project_path.join("README.md"), producer::readme(config))?;
-    fs::write(
-        project_path.join("Dockerfile"),
-        producer::dockerfile(config),
-    )?;
+    write_file(project_path, "Cargo.toml", producer::cargo_toml(config))?;
+    write_file(project_path, "src/main.rs", producer::main_rs(config))?;
+    write_file(project_path, ".gitignore", templates::gitignore())?;
+    write_file(project_path, "README.md", producer::readme(config))?;
+    write_file(project_path, "Dockerfile", producer::dockerfile(config))?;
 
     // Configuration files
-    fs::write(
-        project_path.join("src/config.rs"),
-        producer::config_rs(config),
-    )?;
-    fs::write(
-        project_path.join("src/error.rs"),
-        producer::error_rs(config),
+    write_file(project_path, "src/config.rs", producer::config_rs(config))?;
+    write_file(project_path, "src/error.rs", producer::error_rs(config))?;
+
+    // Migrations
+    write_file(
+        project_path,
+        "migrations/0001_create_tables.sql",
+        producer::migration(config),
     )?;
 
     // Domain layer
-    fs::write(
-        project_path.join("src/domain/mod.rs"),
+    write_file(
+        project_path,
+        "src/domain/mod.rs",
         producer::domain_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/domain/entities.rs"),
+    write_file(
+        project_path,
+        "src/domain/entities.rs",
         producer::domain_entities(config),
     )?;
-    fs::write(
-        project_path.join("src/domain/events.rs"),
+    write_file(
+        project_path,
+        "src/domain/events.rs",
         producer::domain_events(config),
     )?;
-    fs::write(
-        project_path.join("src/domain/repository.rs"),
+    write_file(
+        project_path,
+        "src/domain/repository.rs",
         producer::domain_repository(config),
     )?;
 
     // Application layer
-    fs::write(
-        project_path.join("src/application/mod.rs"),
+    write_file(
+        project_path,
+        "src/application/mod.rs",
         producer::application_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/application/service.rs"),
+    write_file(
+        project_path,
+        "src/application/service.rs",
         producer::application_service(config),
     )?;
-    fs::write(
-        project_path.join("src/application/outbox.rs"),
+    write_file(
+        project_path,
+        "src/application/outbox.rs",
         producer::application_outbox(config),
     )?;
 
     // Infrastructure layer
-    fs::write(
-        project_path.join("src/infrastructure/mod.rs"),
+    write_file(
+        project_path,
+        "src/infrastructure/mod.rs",
         producer::infrastructure_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/infrastructure/repository.rs"),
+    write_file(
+        project_path,
+        "src/infrastructure/repository.rs",
         producer::infrastructure_repository(config),
     )?;
-    fs::write(
-        project_path.join("src/infrastructure/outbox.rs"),
+    write_file(
+        project_path,
+        "src/infrastructure/outbox.rs",
         producer::infrastructure_outbox(config),
     )?;
-    fs::write(
-        project_path.join("src/infrastructure/publisher.rs"),
-        producer::infrastructure_publisher(config),
-    )?;
-    fs::write(
-        project_path.join("src/infrastructure/outbox_processor.rs"),
-        producer::infrastructure_outbox_processor(config),
-    )?;
-    fs::write(
-        project_path.join("src/infrastructure/health.rs"),
+    write_file(
+        project_path,
+        "src/infrastructure/health.rs",
         producer::infrastructure_health(config),
     )?;
 
     // Presentation layer
-    fs::write(
-        project_path.join("src/presentation/mod.rs"),
+    write_file(
+        project_path,
+        "src/presentation/mod.rs",
         producer::presentation_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/presentation/handlers.rs"),
+    write_file(
+        project_path,
+        "src/presentation/handlers.rs",
         producer::presentation_handlers(config),
     )?;
 
+    // Integration tests
+    write_file(
+        project_path,
+        "tests/integration.rs",
+        producer::integration_tests(config),
+    )?;
+
     Ok(())
 }
 
@@ -645,7 +727,7 @@ pub fn create_bff_structure(project_path: &Path) -> Result<()> {
     ];
 
     for dir in dirs {
-        fs::create_dir_all(project_path.join(dir))?;
+        create_dir(project_path, dir)?;
     }
 
     Ok(())
@@ -698,76 +780,91 @@ pub fn generate_bff_files(project_path: &Path, config: &ProjectConfig) -> Result
     })?;
 
     // Root files
-    fs::write(project_path.join("Cargo.toml"), bff::cargo_toml(config))?;
-    fs::write(project_path.join("src/main.rs"), bff::main_rs(config))?;
-    fs::write(project_path.join(".gitignore"), templates::gitignore())?;
-    fs::write(project_path.join("README.md"), bff::readme(config))?;
-    fs::write(project_path.join("Dockerfile"), bff::dockerfile(config))?;
+    write_file(project_path, "Cargo.toml", bff::cargo_toml(config))?;
+    write_file(project_path, "src/main.rs", bff::main_rs(config))?;
+    write_file(project_path, ".gitignore", templates::gitignore())?;
+    write_file(project_path, "README.md", bff::readme(config))?;
+    write_file(project_path, "Dockerfile", bff::dockerfile(config))?;
 
     // Configuration files
-    fs::write(project_path.join("src/config.rs"), bff::config_rs(config))?;
-    fs::write(project_path.join("src/error.rs"), bff::error_rs(config))?;
+    write_file(project_path, "src/config.rs", bff::config_rs(config))?;
+    write_file(project_path, "src/error.rs", bff::error_rs(config))?;
 
     // Domain layer
-    fs::write(
-        project_path.join("src/domain/mod.rs"),
-        bff::domain_mod(config),
-    )?;
-    fs::write(
-        project_path.join("src/domain/models.rs"),
+    write_file(project_path, "src/domain/mod.rs", bff::domain_mod(config))?;
+    write_file(
+        project_path,
+        "src/domain/models.rs",
         bff::domain_models(config),
     )?;
-    fs::write(
-        project_path.join("src/domain/aggregates.rs"),
+    write_file(
+        project_path,
+        "src/domain/aggregates.rs",
         bff::domain_aggregates(config),
     )?;
 
     // Application layer
-    fs::write(
-        project_path.join("src/application/mod.rs"),
+    write_file(
+        project_path,
+        "src/application/mod.rs",
         bff::application_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/application/aggregator.rs"),
+    write_file(
+        project_path,
+        "src/application/aggregator.rs",
         bff::application_aggregator(config),
     )?;
 
     // Infrastructure layer
-    fs::write(
-        project_path.join("src/infrastructure/mod.rs"),
+    write_file(
+        project_path,
+        "src/infrastructure/mod.rs",
         bff::infrastructure_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/infrastructure/clients.rs"),
+    write_file(
+        project_path,
+        "src/infrastructure/clients.rs",
         bff::infrastructure_clients(config),
     )?;
-    fs::write(
-        project_path.join("src/infrastructure/cache.rs"),
+    write_file(
+        project_path,
+        "src/infrastructure/cache.rs",
         bff::infrastructure_cache(config),
     )?;
-    fs::write(
-        project_path.join("src/infrastructure/health.rs"),
+    write_file(
+        project_path,
+        "src/infrastructure/health.rs",
         bff::infrastructure_health(config),
     )?;
 
     // Presentation layer
-    fs::write(
-        project_path.join("src/presentation/mod.rs"),
+    write_file(
+        project_path,
+        "src/presentation/mod.rs",
         bff::presentation_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/presentation/handlers.rs"),
+    write_file(
+        project_path,
+        "src/presentation/handlers.rs",
         bff::presentation_handlers(config),
     )?;
 
     // GraphQL (if enabled)
     if bff_config.graphql_enabled {
-        fs::write(
-            project_path.join("src/presentation/graphql.rs"),
+        write_file(
+            project_path,
+            "src/presentation/graphql.rs",
             bff::presentation_graphql(config),
         )?;
     }
 
+    // Integration tests
+    write_file(
+        project_path,
+        "tests/integration.rs",
+        bff::integration_tests(config),
+    )?;
+
     Ok(())
 }
 
@@ -795,7 +892,7 @@ pub fn create_scheduled_structure(project_path: &Path) -> Result<()> {
     ];
 
     for dir in dirs {
-        fs::create_dir_all(project_path.join(dir))?;
+        create_dir(project_path, dir)?;
     }
 
     Ok(())
@@ -836,58 +933,59 @@ pub fn create_scheduled_structure(project_path: &Path) -> Result<()> {
 /// Returns an error if any file write operation fails
 pub fn generate_scheduled_files(project_path: &Path, config: &ProjectConfig) -> Result<()> {
     // Root files
-    fs::write(
-        project_path.join("Cargo.toml"),
-        scheduled::cargo_toml(config),
-    )?;
-    fs::write(project_path.join("src/main.rs"), scheduled::main_rs(config))?;
-    fs::write(project_path.join(".gitignore"), templates::gitignore())?;
-    fs::write(project_path.join("README.md"), scheduled::readme(config))?;
-    fs::write(
-        project_path.join("Dockerfile"),
-        scheduled::dockerfile(config),
-    )?;
+    write_file(project_path, "Cargo.toml", scheduled::cargo_toml(config))?;
+    write_file(project_path, "src/main.rs", scheduled::main_rs(config))?;
+    write_file(project_path, ".gitignore", templates::gitignore())?;
+    write_file(project_path, "README.md", scheduled::readme(config))?;
+    write_file(project_path, "Dockerfile", scheduled::dockerfile(config))?;
 
     // Configuration files
-    fs::write(
-        project_path.join("src/config.rs"),
-        scheduled::config_rs(config),
-    )?;
-    fs::write(
-        project_path.join("src/error.rs"),
-        scheduled::error_rs(config),
-    )?;
+    write_file(project_path, "src/config.rs", scheduled::config_rs(config))?;
+    write_file(project_path, "src/error.rs", scheduled::error_rs(config))?;
 
     // Domain layer
-    fs::write(
-        project_path.join("src/domain/mod.rs"),
+    write_file(
+        project_path,
+        "src/domain/mod.rs",
         scheduled::domain_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/domain/jobs.rs"),
+    write_file(
+        project_path,
+        "src/domain/jobs.rs",
         scheduled::domain_jobs(config),
     )?;
 
     // Application layer
-    fs::write(
-        project_path.join("src/application/mod.rs"),
+    write_file(
+        project_path,
+        "src/application/mod.rs",
         scheduled::application_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/application/scheduler.rs"),
+    write_file(
+        project_path,
+        "src/application/scheduler.rs",
         scheduled::application_scheduler(config),
     )?;
 
     // Infrastructure layer
-    fs::write(
-        project_path.join("src/infrastructure/mod.rs"),
+    write_file(
+        project_path,
+        "src/infrastructure/mod.rs",
         scheduled::infrastructure_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/infrastructure/health.rs"),
+    write_file(
+        project_path,
+        "src/infrastructure/health.rs",
         scheduled::infrastructure_health(config),
     )?;
 
+    // Integration tests
+    write_file(
+        project_path,
+        "tests/integration.rs",
+        scheduled::integration_tests(config),
+    )?;
+
     Ok(())
 }
 
@@ -917,7 +1015,7 @@ pub fn create_websocket_structure(project_path: &Path) -> Result<()> {
     ];
 
     for dir in dirs {
-        fs::create_dir_all(project_path.join(dir))?;
+        create_dir(project_path, dir)?;
     }
 
     Ok(())
@@ -963,72 +1061,76 @@ pub fn create_websocket_structure(project_path: &Path) -> Result<()> {
 /// Returns an error if any file write operation fails
 pub fn generate_websocket_files(project_path: &Path, config: &ProjectConfig) -> Result<()> {
     // Root files
-    fs::write(
-        project_path.join("Cargo.toml"),
-        websocket::cargo_toml(config),
-    )?;
-    fs::write(project_path.join("src/main.rs"), websocket::main_rs(config))?;
-    fs::write(project_path.join(".gitignore"), templates::gitignore())?;
-    fs::write(project_path.join("README.md"), websocket::readme(config))?;
-    fs::write(
-        project_path.join("Dockerfile"),
-        websocket::dockerfile(config),
-    )?;
+    write_file(project_path, "Cargo.toml", websocket::cargo_toml(config))?;
+    write_file(project_path, "src/main.rs", websocket::main_rs(config))?;
+    write_file(project_path, ".gitignore", templates::gitignore())?;
+    write_file(project_path, "README.md", websocket::readme(config))?;
+    write_file(project_path, "Dockerfile", websocket::dockerfile(config))?;
 
     // Configuration files
-    fs::write(
-        project_path.join("src/config.rs"),
-        websocket::config_rs(config),
-    )?;
-    fs::write(
-        project_path.join("src/error.rs"),
-        websocket::error_rs(config),
-    )?;
+    write_file(project_path, "src/config.rs", websocket::config_rs(config))?;
+    write_file(project_path, "src/error.rs", websocket::error_rs(config))?;
 
     // Domain layer
-    fs::write(
-        project_path.join("src/domain/mod.rs"),
+    write_file(
+        project_path,
+        "src/domain/mod.rs",
         websocket::domain_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/domain/messages.rs"),
+    write_file(
+        project_path,
+        "src/domain/messages.rs",
         websocket::domain_messages(config),
     )?;
-    fs::write(
-        project_path.join("src/domain/connection.rs"),
+    write_file(
+        project_path,
+        "src/domain/connection.rs",
         websocket::domain_connection(config),
     )?;
 
     // Application layer
-    fs::write(
-        project_path.join("src/application/mod.rs"),
+    write_file(
+        project_path,
+        "src/application/mod.rs",
         websocket::application_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/application/hub.rs"),
+    write_file(
+        project_path,
+        "src/application/hub.rs",
         websocket::application_hub(config),
     )?;
 
     // Infrastructure layer
-    fs::write(
-        project_path.join("src/infrastructure/mod.rs"),
+    write_file(
+        project_path,
+        "src/infrastructure/mod.rs",
         websocket::infrastructure_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/infrastructure/health.rs"),
+    write_file(
+        project_path,
+        "src/infrastructure/health.rs",
         websocket::infrastructure_health(config),
     )?;
 
     // Presentation layer
-    fs::write(
-        project_path.join("src/presentation/mod.rs"),
+    write_file(
+        project_path,
+        "src/presentation/mod.rs",
         websocket::presentation_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/presentation/handlers.rs"),
+    write_file(
+        project_path,
+        "src/presentation/handlers.rs",
         websocket::presentation_handlers(config),
     )?;
 
+    // Integration tests
+    write_file(
+        project_path,
+        "tests/integration.rs",
+        websocket::integration_tests(config),
+    )?;
+
     Ok(())
 }
 
@@ -1044,7 +1146,7 @@ pub fn create_saga_structure(project_path: &Path) -> Result<()> {
     ];
 
     for dir in dirs {
-        fs::create_dir_all(project_path.join(dir))?;
+        create_dir(project_path, dir)?;
     }
 
     Ok(())
@@ -1053,64 +1155,77 @@ pub fn create_saga_structure(project_path: &Path) -> Result<()> {
 /// Generate all Saga Orchestrator project files
 pub fn generate_saga_files(project_path: &Path, config: &ProjectConfig) -> Result<()> {
     // Root files
-    fs::write(project_path.join("Cargo.toml"), saga::cargo_toml(config))?;
-    fs::write(project_path.join("src/main.rs"), saga::main_rs(config))?;
-    fs::write(project_path.join(".gitignore"), templates::gitignore())?;
-    fs::write(project_path.join("README.md"), saga::readme(config))?;
-    fs::write(project_path.join("Dockerfile"), saga::dockerfile(config))?;
+    write_file(project_path, "Cargo.toml", saga::cargo_toml(config))?;
+    write_file(project_path, "src/main.rs", saga::main_rs(config))?;
+    write_file(project_path, ".gitignore", templates::gitignore())?;
+    write_file(project_path, "README.md", saga::readme(config))?;
+    write_file(project_path, "Dockerfile", saga::dockerfile(config))?;
 
     // Configuration files
-    fs::write(project_path.join("src/config.rs"), saga::config_rs(config))?;
-    fs::write(project_path.join("src/error.rs"), saga::error_rs(config))?;
+    write_file(project_path, "src/config.rs", saga::config_rs(config))?;
+    write_file(project_path, "src/error.rs", saga::error_rs(config))?;
 
     // Domain layer
-    fs::write(
-        project_path.join("src/domain/mod.rs"),
-        saga::domain_mod(config),
-    )?;
-    fs::write(
-        project_path.join("src/domain/saga.rs"),
-        saga::domain_saga(config),
-    )?;
-    fs::write(
-        project_path.join("src/domain/step.rs"),
-        saga::domain_step(config),
+    write_file(project_path, "src/domain/mod.rs", saga::domain_mod(config))?;
+    write_file(
+        project_path,
+        "src/domain/order.rs",
+        saga::domain_order(config),
     )?;
 
     // Application layer
-    fs::write(
-        project_path.join("src/application/mod.rs"),
+    write_file(
+        project_path,
+        "src/application/mod.rs",
         saga::application_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/application/orchestrator.rs"),
+    write_file(
+        project_path,
+        "src/application/orchestrator.rs",
         saga::application_orchestrator(config),
     )?;
-    fs::write(
-        project_path.join("src/application/steps.rs"),
+    write_file(
+        project_path,
+        "src/application/steps.rs",
         saga::application_steps(config),
     )?;
 
     // Infrastructure layer
-    fs::write(
-        project_path.join("src/infrastructure/mod.rs"),
+    write_file(
+        project_path,
+        "src/infrastructure/mod.rs",
         saga::infrastructure_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/infrastructure/health.rs"),
+    write_file(
+        project_path,
+        "src/infrastructure/health.rs",
         saga::infrastructure_health(config),
     )?;
+    write_file(
+        project_path,
+        "src/infrastructure/order_services.rs",
+        saga::infrastructure_order_services(config),
+    )?;
 
     // Presentation layer
-    fs::write(
-        project_path.join("src/presentation/mod.rs"),
+    write_file(
+        project_path,
+        "src/presentation/mod.rs",
         saga::presentation_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/presentation/handlers.rs"),
+    write_file(
+        project_path,
+        "src/presentation/handlers.rs",
         saga::presentation_handlers(config),
     )?;
 
+    // Integration tests
+    write_file(
+        project_path,
+        "tests/integration.rs",
+        saga::integration_tests(config),
+    )?;
+
     Ok(())
 }
 
@@ -1142,7 +1257,7 @@ pub fn create_acl_structure(project_path: &Path) -> Result<()> {
     ];
 
     for dir in dirs {
-        fs::create_dir_all(project_path.join(dir))?;
+        create_dir(project_path, dir)?;
     }
 
     Ok(())
@@ -1191,67 +1306,113 @@ pub fn create_acl_structure(project_path: &Path) -> Result<()> {
 /// Returns an error if any file write operation fails
 pub fn generate_acl_files(project_path: &Path, config: &ProjectConfig) -> Result<()> {
     // Root files
-    fs::write(project_path.join("Cargo.toml"), acl::cargo_toml(config))?;
-    fs::write(project_path.join("src/main.rs"), acl::main_rs(config))?;
-    fs::write(project_path.join(".gitignore"), templates::gitignore())?;
-    fs::write(project_path.join("README.md"), acl::readme(config))?;
-    fs::write(project_path.join("Dockerfile"), acl::dockerfile(config))?;
+    write_file(project_path, "Cargo.toml", acl::cargo_toml(config))?;
+    write_file(project_path, "src/main.rs", acl::main_rs(config))?;
+    write_file(project_path, ".gitignore", templates::gitignore())?;
+    write_file(project_path, "README.md", acl::readme(config))?;
+    write_file(project_path, "Dockerfile", acl::dockerfile(config))?;
 
     // Configuration files
-    fs::write(project_path.join("src/config.rs"), acl::config_rs(config))?;
-    fs::write(project_path.join("src/error.rs"), acl::error_rs(config))?;
+    write_file(project_path, "src/config.rs", acl::config_rs(config))?;
+    write_file(project_path, "src/error.rs", acl::error_rs(config))?;
 
     // Domain layer
-    fs::write(
-        project_path.join("src/domain/mod.rs"),
-        acl::domain_mod(config),
-    )?;
-    fs::write(
-        project_path.join("src/domain/legacy.rs"),
+    write_file(project_path, "src/domain/mod.rs", acl::domain_mod(config))?;
+    write_file(
+        project_path,
+        "src/domain/legacy.rs",
         acl::domain_legacy(config),
     )?;
-    fs::write(
-        project_path.join("src/domain/modern.rs"),
+    write_file(
+        project_path,
+        "src/domain/modern.rs",
         acl::domain_modern(config),
     )?;
-    fs::write(
-        project_path.join("src/domain/transformer.rs"),
+    write_file(
+        project_path,
+        "src/domain/transformer.rs",
         acl::domain_transformer(config),
     )?;
 
     // Application layer
-    fs::write(
-        project_path.join("src/application/mod.rs"),
+    write_file(
+        project_path,
+        "src/application/mod.rs",
         acl::application_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/application/translator.rs"),
+    write_file(
+        project_path,
+        "src/application/translator.rs",
         acl::application_translator(config),
     )?;
 
     // Infrastructure layer
-    fs::write(
-        project_path.join("src/infrastructure/mod.rs"),
+    write_file(
+        project_path,
+        "src/infrastructure/mod.rs",
         acl::infrastructure_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/infrastructure/legacy_client.rs"),
+    write_file(
+        project_path,
+        "src/infrastructure/legacy_client.rs",
         acl::infrastructure_legacy_client(config),
     )?;
-    fs::write(
-        project_path.join("src/infrastructure/health.rs"),
+    write_file(
+        project_path,
+        "src/infrastructure/health.rs",
         acl::infrastructure_health(config),
     )?;
 
     // Presentation layer
-    fs::write(
-        project_path.join("src/presentation/mod.rs"),
+    write_file(
+        project_path,
+        "src/presentation/mod.rs",
         acl::presentation_mod(config),
     )?;
-    fs::write(
-        project_path.join("src/presentation/handlers.rs"),
+    write_file(
+        project_path,
+        "src/presentation/handlers.rs",
         acl::presentation_handlers(config),
     )?;
 
+    // Integration tests
+    write_file(
+        project_path,
+        "tests/integration.rs",
+        acl::integration_tests(config),
+    )?;
+
+    Ok(())
+}
+
+/// Add the shared `errors` crate to a generated project
+///
+/// Writes the crate to `errors/`, makes the project a workspace with it as a
+/// member and adds it to the project's dependencies. Other services can join
+/// the workspace and depend on it the same way.
+///
+/// # Errors
+/// Returns an error if the project's Cargo.toml can't be read or already
+/// declares a workspace, or if writing a file fails
+pub fn generate_shared_errors(project_path: &Path) -> Result<()> {
+    let manifest = fs::read_to_string(confined_path(project_path, Path::new("Cargo.toml"))?)?;
+    if manifest.contains("[workspace]") {
+        anyhow::bail!("Cargo.toml already declares a workspace");
+    }
+    let manifest = manifest.replacen(
+        "[dependencies]\n",
+        "[dependencies]\n# Errors shared across the workspace\nerrors = { path = \"errors\" }\n\n",
+        1,
+    );
+    let manifest = format!(
+        "{}\n[workspace]\nmembers = [\".\", \"errors\"]\n",
+        manifest.trim_end()
+    );
+
+    create_dir(project_path, "errors/src")?;
+    write_file(project_path, "errors/Cargo.toml", errors::cargo_toml())?;
+    write_file(project_path, "errors/src/lib.rs", errors::lib_rs())?;
+    write_file(project_path, "Cargo.toml", manifest)?;
+
     Ok(())
 }
@@ -8,6 +8,8 @@
 //! - Documentation comments explaining each layer's purpose
 //! - Trait-based abstractions for dependency inversion
 
+use crate::config::OptionalModule;
+
 /// Generate Cargo.toml content for a new AllFrame project
 ///
 /// Creates a minimal `Cargo.toml` with:
@@ -25,9 +27,27 @@
 /// # Returns
 /// A formatted Cargo.toml file content as a String
 pub fn cargo_toml(project_name: &str) -> String {
-    // For now, we generate a minimal Cargo.toml without allframe dependency
-    // since allframe is not published to crates.io yet.
-    // When allframe is published, we'll add the features and allframe dependency.
+    cargo_toml_with_modules(project_name, &[])
+}
+
+/// Generate Cargo.toml content with optional modules enabled
+///
+/// Without modules this is [`cargo_toml`]; with any, it adds an
+/// `allframe-core` dependency with only their features enabled.
+pub fn cargo_toml_with_modules(project_name: &str, modules: &[OptionalModule]) -> String {
+    let allframe_core = if modules.is_empty() {
+        String::new()
+    } else {
+        let features: Vec<String> = modules
+            .iter()
+            .map(|module| format!("\"{}\"", module.feature()))
+            .collect();
+        format!(
+            "allframe-core = {{ version = \"0.1\", default-features = false, features = [{}] }}\n",
+            features.join(", ")
+        )
+    };
+
     format!(
         r#"[package]
 name = "{}"
@@ -40,12 +60,12 @@ tokio = {{ version = "1", features = ["full"] }}
 serde = {{ version = "1", features = ["derive"] }}
 anyhow = "1.0"
 async-trait = "0.1"
-
+{}
 [[bin]]
 name = "{}"
 path = "src/main.rs"
 "#,
-        project_name, project_name
+        project_name, allframe_core, project_name
     )
 }
 
@@ -140,6 +160,88 @@ mod tests {
 "#
 }
 
+/// Generate src/main.rs content with optional modules wired in
+///
+/// Adds to [`main_rs`] an observability guard held for the whole of `main`
+/// (`otel`), and after the greeting a `MemoryCache` round trip (`cache`)
+/// and an [`infrastructure_authenticator`] check (`auth`).
+pub fn main_rs_with_modules(modules: &[OptionalModule]) -> String {
+    let mut imports = Vec::new();
+    let mut setup = String::new();
+    let mut usage = String::new();
+    for module in modules {
+        match module {
+            OptionalModule::Auth => {
+                imports.push("use allframe_core::auth::Authenticator;");
+                usage.push_str(
+                    r#"
+    // Authentication: keys come from the comma-separated API_KEYS variable
+    let authenticator = ApiKeyAuthenticator::from_env();
+    match authenticator.authenticate("demo-key").await {
+        Ok(key) => println!("Authenticated with {}", key),
+        Err(e) => println!("demo-key rejected: {}", e),
+    }
+"#,
+                );
+            }
+            OptionalModule::Cache => {
+                imports.push("use allframe_core::cache::{Cache, MemoryCache};");
+                usage.push_str(
+                    r#"
+    // Cache: remember who was greeted last
+    let cache = MemoryCache::new();
+    cache.set("last_greeted", &"World", None).await;
+    let last_greeted: Option<String> = cache.get("last_greeted").await;
+    println!("Last greeted: {:?}", last_greeted);
+"#,
+                );
+            }
+            OptionalModule::Otel => {
+                imports.push("use allframe_core::otel::ObservabilityBuilder;");
+                setup.push_str(
+                    r#"    // Observability: flushes pending spans when dropped at the end of main
+    let _observability = ObservabilityBuilder::new(env!("CARGO_PKG_NAME"))
+        .service_version(env!("CARGO_PKG_VERSION"))
+        .log_level_from_env()
+        .build()
+        .expect("Failed to initialize observability");
+
+"#,
+                );
+            }
+        }
+    }
+
+    let mut main = main_rs().to_string();
+    if !imports.is_empty() {
+        main = main.replacen(
+            "use application::GreetingService;",
+            &format!(
+                "{}\n\nuse application::GreetingService;",
+                imports.join("\n")
+            ),
+            1,
+        );
+    }
+    if modules.contains(&OptionalModule::Auth) {
+        main = main.replacen(
+            "use infrastructure::ConsoleGreeter;",
+            "use infrastructure::{ApiKeyAuthenticator, ConsoleGreeter};",
+            1,
+        );
+    }
+    main = main.replacen(
+        "async fn main() {\n",
+        &format!("async fn main() {{\n{}", setup),
+        1,
+    );
+    main.replacen(
+        "    service.greet(\"World\").await;\n",
+        &format!("    service.greet(\"World\").await;\n{}", usage),
+        1,
+    )
+}
+
 /// Generate domain/mod.rs content
 pub fn domain_mod() -> &'static str {
     r#"//! Domain Layer
@@ -232,6 +334,87 @@ pub use console_greeter::ConsoleGreeter;
 "#
 }
 
+/// Generate infrastructure/mod.rs content with optional modules' files
+pub fn infrastructure_mod_with_modules(modules: &[OptionalModule]) -> String {
+    if !modules.contains(&OptionalModule::Auth) {
+        return infrastructure_mod().to_string();
+    }
+    infrastructure_mod()
+        .replacen(
+            "mod console_greeter;",
+            "mod authenticator;\nmod console_greeter;",
+            1,
+        )
+        .replacen(
+            "pub use console_greeter::ConsoleGreeter;",
+            "pub use authenticator::ApiKeyAuthenticator;\npub use console_greeter::ConsoleGreeter;",
+            1,
+        )
+}
+
+/// Generate infrastructure/authenticator.rs content for the `auth` module
+pub fn infrastructure_authenticator() -> &'static str {
+    r#"//! API Key Authenticator
+//!
+//! A minimal implementation of AllFrame's `Authenticator` trait. Replace it
+//! with `JwtValidator` (the `auth-jwt` feature) or a lookup against your
+//! user store.
+
+use allframe_core::auth::{AuthError, Authenticator};
+
+/// Accepts tokens that match one of a fixed set of API keys.
+pub struct ApiKeyAuthenticator {
+    keys: Vec<String>,
+}
+
+impl ApiKeyAuthenticator {
+    /// Create an authenticator accepting the given keys.
+    pub fn new(keys: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            keys: keys.into_iter().collect(),
+        }
+    }
+
+    /// Read keys from the comma-separated `API_KEYS` environment variable.
+    pub fn from_env() -> Self {
+        let keys = std::env::var("API_KEYS").unwrap_or_default();
+        Self::new(
+            keys.split(',')
+                .map(str::trim)
+                .filter(|key| !key.is_empty())
+                .map(String::from),
+        )
+    }
+}
+
+#[async_trait::async_trait]
+impl Authenticator for ApiKeyAuthenticator {
+    type Claims = String;
+
+    async fn authenticate(&self, token: &str) -> Result<Self::Claims, AuthError> {
+        if self.keys.iter().any(|key| key == token) {
+            Ok(token.to_string())
+        } else {
+            Err(AuthError::InvalidToken("unknown API key".into()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_accepts_known_keys_only() {
+        let authenticator = ApiKeyAuthenticator::new(["secret".to_string()]);
+
+        assert_eq!(authenticator.authenticate("secret").await.unwrap(), "secret");
+        assert!(authenticator.authenticate("guess").await.is_err());
+    }
+}
+"#
+}
+
 /// Generate infrastructure/console_greeter.rs content
 pub fn infrastructure_console_greeter() -> &'static str {
     r#"//! Console Greeter Implementation
@@ -268,6 +451,70 @@ pub fn presentation_mod() -> &'static str {
 "#
 }
 
+/// Generate tests/integration.rs content
+///
+/// Runs the greeting use case end-to-end through the real application and
+/// infrastructure layers.
+pub fn integration_tests() -> String {
+    format!(
+        "{}{}",
+        super::integration_header(&["application", "domain", "infrastructure", "presentation"]),
+        r#"
+use std::sync::{Arc, Mutex};
+
+use application::GreetingService;
+use domain::Greeter;
+use infrastructure::ConsoleGreeter;
+
+/// Greeter that records who it greeted
+struct RecordingGreeter(Arc<Mutex<Vec<String>>>);
+
+impl Greeter for RecordingGreeter {
+    fn greet(&self, name: &str) {
+        self.0.lock().unwrap().push(name.to_string());
+    }
+}
+
+#[tokio::test]
+async fn test_greeting_use_case() {
+    let greeted = Arc::new(Mutex::new(Vec::new()));
+    let service = GreetingService::new(RecordingGreeter(greeted.clone()));
+
+    service.greet("Alice").await;
+    service.greet("Bob").await;
+
+    assert_eq!(*greeted.lock().unwrap(), ["Alice", "Bob"]);
+}
+
+#[tokio::test]
+async fn test_console_greeter() {
+    GreetingService::new(ConsoleGreeter).greet("World").await;
+}
+"#
+    )
+}
+
+/// Generate tests/integration.rs content with tests for optional modules
+pub fn integration_tests_with_modules(modules: &[OptionalModule]) -> String {
+    let mut tests = integration_tests();
+    if modules.contains(&OptionalModule::Auth) {
+        tests.push_str(
+            r#"
+#[tokio::test]
+async fn test_api_key_authenticator() {
+    use allframe_core::auth::Authenticator;
+
+    let authenticator = infrastructure::ApiKeyAuthenticator::new(["secret".to_string()]);
+
+    assert!(authenticator.authenticate("secret").await.is_ok());
+    assert!(authenticator.authenticate("guess").await.is_err());
+}
+"#,
+        );
+    }
+    tests
+}
+
 /// Generate .gitignore content
 pub fn gitignore() -> &'static str {
     r#"# Rust
@@ -79,6 +79,13 @@ chrono = {{ version = "0.4", features = ["serde"] }}
 uuid = {{ version = "1.0", features = ["v4", "serde"] }}
 dotenvy = "0.15"
 
+[features]
+default = ["otel"]
+# Logs, traces and metrics through allframe-core's observability stack,
+# exported over OTLP when OTEL_EXPORTER_OTLP_ENDPOINT is set. Without it,
+# logs go to stdout.
+otel = ["allframe-core/otel-otlp"]
+
 [dev-dependencies]
 tokio-test = "0.4"
 mockall = "0.13"
@@ -139,18 +146,13 @@ use infrastructure::{{
     HealthServer,
 }};
 
-#[tokio::main]
+{observability}#[tokio::main]
 async fn main() -> anyhow::Result<()> {{
     // Load environment variables
     dotenvy::dotenv().ok();
 
-    // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive(tracing::Level::INFO.into()),
-        )
-        .init();
+    // Initialize logging, tracing and metrics; the guard flushes them on exit
+    let _observability = init_observability()?;
 
     // Load configuration
     let config = Config::from_env();
@@ -191,6 +193,7 @@ async fn main() -> anyhow::Result<()> {{
     Ok(())
 }}
 "#,
+        observability = super::OBSERVABILITY_INIT,
         pascal_name = pascal_name,
         display_name = bff.display_name,
         frontend_type = bff.frontend_type,
@@ -658,7 +661,8 @@ pub fn infrastructure_clients(config: &ProjectConfig) -> String {
 
 use std::collections::HashMap;
 use std::time::Duration;
-use reqwest::Client;
+use allframe_core::router::{{RequestId, REQUEST_ID_HEADER}};
+use reqwest::{{Client, RequestBuilder}};
 use uuid::Uuid;
 
 use crate::config::BackendConfig;
@@ -681,6 +685,15 @@ struct BackendClient {{
     base_url: String,
 }}
 
+impl BackendClient {{
+    /// GET request carrying the current request's correlation id
+    fn get(&self, url: &str) -> RequestBuilder {{
+        self.client
+            .get(url)
+            .header(REQUEST_ID_HEADER, RequestId::current_or_new().as_str())
+    }}
+}}
+
 impl BackendClients {{
     pub async fn new(configs: &HashMap<String, BackendConfig>) -> Result<Self, {pascal_name}Error> {{
         let mut clients = HashMap::new();
@@ -710,7 +723,7 @@ impl BackendClients {{
         let client = self.get_client("api")?;
         let url = format!("{{}}/users/{{}}", client.base_url, user_id);
 
-        let response = client.client
+        let response = client
             .get(&url)
             .send()
             .await?
@@ -724,7 +737,7 @@ impl BackendClients {{
         let client = self.get_client("api")?;
         let url = format!("{{}}/users/{{}}/resources?limit={{}}", client.base_url, user_id, limit);
 
-        let response = client.client
+        let response = client
             .get(&url)
             .send()
             .await?
@@ -738,7 +751,7 @@ impl BackendClients {{
         let client = self.get_client("api")?;
         let url = format!("{{}}/resources/{{}}", client.base_url, resource_id);
 
-        let response = client.client
+        let response = client
             .get(&url)
             .send()
             .await?
@@ -752,7 +765,7 @@ impl BackendClients {{
         let client = self.get_client("api")?;
         let url = format!("{{}}/resources/{{}}/related?limit={{}}", client.base_url, resource_id, limit);
 
-        let response = client.client
+        let response = client
             .get(&url)
             .send()
             .await?
@@ -766,7 +779,7 @@ impl BackendClients {{
         let client = self.get_client("api")?;
         let url = format!("{{}}/stats", client.base_url);
 
-        let response = client.client
+        let response = client
             .get(&url)
             .send()
             .await?
@@ -780,7 +793,7 @@ impl BackendClients {{
         let client = self.get_client("api")?;
         let url = format!("{{}}/resources/search?q={{}}", client.base_url, query);
 
-        let response = client.client
+        let response = client
             .get(&url)
             .send()
             .await?
@@ -794,7 +807,7 @@ impl BackendClients {{
         let client = self.get_client("api")?;
         let url = format!("{{}}/users/search?q={{}}", client.base_url, query);
 
-        let response = client.client
+        let response = client
             .get(&url)
             .send()
             .await?
@@ -808,7 +821,7 @@ impl BackendClients {{
         let client = self.get_client("api")?;
         let url = format!("{{}}/resources?page={{}}&per_page={{}}", client.base_url, page, per_page);
 
-        let response = client.client
+        let response = client
             .get(&url)
             .send()
             .await?
@@ -949,12 +962,17 @@ pub fn presentation_handlers(config: &ProjectConfig) -> String {
         r#"//! HTTP API handlers
 
 use std::sync::Arc;
+use allframe_core::router::{{RequestId, REQUEST_ID_HEADER}};
 use axum::{{
-    extract::{{Path, Query, State}},
+    extract::{{Path, Query, Request, State}},
+    http::HeaderValue,
+    middleware::{{self, Next}},
+    response::Response,
     routing::get,
     Json, Router,
 }};
 use serde::Deserialize;
+use tracing::Instrument;
 use uuid::Uuid;
 
 use crate::application::{pascal_name}Aggregator;
@@ -976,9 +994,30 @@ pub fn create_router(aggregator: Arc<{pascal_name}Aggregator>) -> Router {{
         .route("/api/resources", get(list_resources))
         .route("/api/resources/:id", get(get_resource_detail))
         .route("/api/search", get(search))
+        .layer(middleware::from_fn(request_id))
         .with_state(aggregator)
 }}
 
+/// Scope each request to its `x-request-id` (or a new id) and echo it
+///
+/// Backend calls made while handling the request forward the same id.
+async fn request_id(request: Request, next: Next) -> Response {{
+    let id = RequestId::from_headers(
+        request
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?))),
+    )
+    .unwrap_or_default();
+    let span = tracing::info_span!("request", request_id = %id);
+
+    let mut response = id.clone().scope(next.run(request)).instrument(span).await;
+    if let Ok(value) = HeaderValue::from_str(id.as_str()) {{
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }}
+    response
+}}
+
 #[derive(Debug, Deserialize)]
 struct PaginationQuery {{
     page: Option<i32>,
@@ -1337,6 +1376,22 @@ CMD ["/app/{name}"]
     )
 }
 
+/// Generate tests/integration.rs
+pub fn integration_tests(_config: &ProjectConfig) -> String {
+    format!(
+        "{}{}",
+        super::integration_header(&[
+            "config",
+            "error",
+            "domain",
+            "application",
+            "infrastructure",
+            "presentation"
+        ]),
+        super::INTEGRATION_HTTP_TESTS
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1347,4 +1402,19 @@ mod tests {
         assert_eq!(to_pascal_case("mobile-bff"), "MobileBff");
         assert_eq!(to_pascal_case("simple"), "Simple");
     }
+
+    #[test]
+    fn test_request_id_is_forwarded() {
+        let config = ProjectConfig::new("web-bff").with_bff(crate::config::BffConfig::default());
+
+        let handlers = presentation_handlers(&config);
+        assert!(handlers.contains(".layer(middleware::from_fn(request_id))"));
+        assert!(handlers.contains("id.clone().scope(next.run(request))"));
+
+        let clients = infrastructure_clients(&config);
+        assert!(!clients.contains("client.client"));
+        assert!(
+            clients.contains(".header(REQUEST_ID_HEADER, RequestId::current_or_new().as_str())")
+        );
+    }
 }
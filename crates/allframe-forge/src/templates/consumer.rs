@@ -24,7 +24,7 @@ pub fn cargo_toml(config: &ProjectConfig) -> String {
     let name = &config.name;
 
     let broker_deps = match consumer.broker {
-        MessageBroker::Kafka => r#"rdkafka = { version = "0.36", features = ["cmake-build"] }"#,
+        MessageBroker::Kafka => "# Kafka: allframe-core's messaging-kafka feature",
         MessageBroker::RabbitMq => r#"lapin = "2.3""#,
         MessageBroker::Redis => {
             r#"redis = { version = "0.25", features = ["tokio-comp", "streams"] }"#
@@ -43,7 +43,7 @@ description = "{display_name}"
 
 [dependencies]
 # AllFrame
-allframe-core = {{ version = "0.1", features = ["resilience", "otel"] }}
+allframe-core = {{ version = "0.1", features = ["resilience", "otel", "cqrs", "messaging", "messaging-kafka"] }}
 
 # Message Broker
 {broker_deps}
@@ -74,6 +74,13 @@ dotenvy = "0.15"
 # Health checks
 axum = "0.7"
 
+[features]
+default = ["otel"]
+# Logs, traces and metrics through allframe-core's observability stack,
+# exported over OTLP when OTEL_EXPORTER_OTLP_ENDPOINT is set. Without it,
+# logs go to stdout.
+otel = ["allframe-core/otel-otlp"]
+
 [dev-dependencies]
 tokio-test = "0.4"
 mockall = "0.13"
@@ -99,6 +106,12 @@ pub fn main_rs(config: &ProjectConfig) -> String {
 //! An event consumer service with idempotency, retry, and dead letter queue handling.
 
 use std::sync::Arc;
+
+use allframe_core::{{
+    cqrs::InMemoryIdempotencyRegistry,
+    messaging::{{DeadLetterQueue, KafkaConsumer, KafkaProducer}},
+    shutdown::GracefulShutdown,
+}};
 use tracing::info;
 
 mod config;
@@ -109,24 +122,15 @@ mod infrastructure;
 
 use config::Config;
 use application::{pascal_name}Consumer;
-use infrastructure::{{
-    KafkaMessageBroker,
-    InMemoryIdempotencyStore,
-    HealthServer,
-}};
+use infrastructure::{{kafka_config, HealthServer}};
 
-#[tokio::main]
+{observability}#[tokio::main]
 async fn main() -> anyhow::Result<()> {{
     // Load environment variables
     dotenvy::dotenv().ok();
 
-    // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive(tracing::Level::INFO.into()),
-        )
-        .init();
+    // Initialize logging, tracing and metrics; the guard flushes them on exit
+    let _observability = init_observability()?;
 
     // Load configuration
     let config = Config::from_env();
@@ -134,35 +138,43 @@ async fn main() -> anyhow::Result<()> {{
     info!("Broker: {{}}", config.broker.brokers);
     info!("Topics: {{:?}}", config.topics);
 
-    // Create idempotency store
-    let idempotency_store = Arc::new(InMemoryIdempotencyStore::new());
-
-    // Create message broker
-    let broker = KafkaMessageBroker::new(&config.broker).await?;
+    // Connect to Kafka
+    let kafka = kafka_config(&config.broker, &config.group_id);
+    let broker = KafkaConsumer::new(&kafka)?;
+    let dlq = if config.dlq.enabled {{
+        Some(DeadLetterQueue::new(KafkaProducer::new(&kafka)?).with_suffix(config.dlq.suffix.clone()))
+    }} else {{
+        None
+    }};
 
     // Create consumer
-    let consumer = {pascal_name}Consumer::new(
-        broker,
-        idempotency_store,
-        config.retry.clone(),
-        config.dlq.clone(),
-    );
+    let idempotency = Arc::new(InMemoryIdempotencyRegistry::new(config.idempotency_ttl));
+    let consumer = {pascal_name}Consumer::new(broker, dlq, idempotency, &config.retry);
 
     // Start health server in background
+    let health_port = config.server.health_port;
     let health_handle = tokio::spawn(async move {{
-        let health_server = HealthServer::new(config.server.health_port);
+        let health_server = HealthServer::new(health_port);
         health_server.run().await
     }});
 
-    // Run consumer
+    // Run consumer until SIGINT/SIGTERM
+    let shutdown = Arc::new(GracefulShutdown::new());
+    tokio::spawn({{
+        let shutdown = shutdown.clone();
+        async move {{
+            shutdown.wait().await;
+        }}
+    }});
     info!("Consumer started, waiting for messages...");
-    consumer.run(&config.topics).await?;
+    consumer.run(&config.topics, shutdown.token()).await?;
 
     health_handle.abort();
     info!("Consumer shutdown complete");
     Ok(())
 }}
 "#,
+        observability = super::OBSERVABILITY_INIT,
         display_name = consumer.display_name,
         pascal_name = pascal_name,
     )
@@ -189,6 +201,7 @@ pub struct Config {{
     pub group_id: String,
     pub retry: RetryConfig,
     pub dlq: DlqConfig,
+    pub idempotency_ttl: Duration,
     pub server: ServerConfig,
 }}
 
@@ -206,9 +219,7 @@ pub struct BrokerConfig {{
 #[derive(Debug, Clone)]
 pub struct RetryConfig {{
     pub max_attempts: u32,
-    pub initial_backoff: Duration,
-    pub max_backoff: Duration,
-    pub multiplier: f64,
+    pub backoff: Duration,
 }}
 
 /// Dead Letter Queue configuration
@@ -249,22 +260,12 @@ impl Config {{
                     .ok()
                     .and_then(|v| v.parse().ok())
                     .unwrap_or({max_attempts}),
-                initial_backoff: Duration::from_millis(
-                    std::env::var("{upper_name}_RETRY_INITIAL_BACKOFF_MS")
+                backoff: Duration::from_millis(
+                    std::env::var("{upper_name}_RETRY_BACKOFF_MS")
                         .ok()
                         .and_then(|v| v.parse().ok())
-                        .unwrap_or({initial_backoff_ms}),
+                        .unwrap_or({backoff_ms}),
                 ),
-                max_backoff: Duration::from_millis(
-                    std::env::var("{upper_name}_RETRY_MAX_BACKOFF_MS")
-                        .ok()
-                        .and_then(|v| v.parse().ok())
-                        .unwrap_or({max_backoff_ms}),
-                ),
-                multiplier: std::env::var("{upper_name}_RETRY_MULTIPLIER")
-                    .ok()
-                    .and_then(|v| v.parse().ok())
-                    .unwrap_or({multiplier}),
             }},
             dlq: DlqConfig {{
                 enabled: std::env::var("{upper_name}_DLQ_ENABLED")
@@ -274,6 +275,12 @@ impl Config {{
                 suffix: std::env::var("{upper_name}_DLQ_SUFFIX")
                     .unwrap_or_else(|_| ".dlq".to_string()),
             }},
+            idempotency_ttl: Duration::from_secs(
+                std::env::var("{upper_name}_IDEMPOTENCY_TTL_SECS")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or({idempotency_ttl_secs}),
+            ),
             server: ServerConfig {{
                 health_port: std::env::var("{upper_name}_HEALTH_PORT")
                     .ok()
@@ -291,9 +298,8 @@ impl Config {{
         upper_name = upper_name,
         service_name = service_name,
         max_attempts = consumer.retry.max_attempts,
-        initial_backoff_ms = consumer.retry.initial_backoff_ms,
-        max_backoff_ms = consumer.retry.max_backoff_ms,
-        multiplier = consumer.retry.multiplier,
+        backoff_ms = consumer.retry.initial_backoff_ms,
+        idempotency_ttl_secs = consumer.idempotency.ttl_secs,
         health_port = consumer.server.health_port,
         metrics_port = consumer.server.metrics_port,
     )
@@ -334,9 +340,9 @@ pub enum {pascal_name}Error {{
 /// Result type alias
 pub type Result<T> = std::result::Result<T, {pascal_name}Error>;
 
-impl From<{pascal_name}Error> for tonic::Status {{
-    fn from(e: {pascal_name}Error) -> Self {{
-        tonic::Status::internal(e.to_string())
+impl From<allframe_core::messaging::MessagingError> for {pascal_name}Error {{
+    fn from(e: allframe_core::messaging::MessagingError) -> Self {{
+        Self::BrokerError(e.to_string())
     }}
 }}
 "#,
@@ -573,168 +579,98 @@ pub fn application_consumer(config: &ProjectConfig) -> String {
 
 use std::sync::Arc;
 use std::time::Duration;
-use async_trait::async_trait;
-use tracing::{{info, warn, error, instrument}};
 
-use crate::config::{{RetryConfig, DlqConfig}};
+use allframe_core::cqrs::IdempotencyRegistry;
+use allframe_core::messaging::{{
+    AtLeastOnceConsumer, DeadLetterQueue, DeliveryOutcome, Message, MessageConsumer,
+}};
+use allframe_core::shutdown::ShutdownToken;
+use tracing::{{info, instrument, warn}};
+
+use crate::config::RetryConfig;
 use crate::domain::{pascal_name}HandlerRegistry;
 use crate::error::{{Result, {pascal_name}Error}};
-use crate::infrastructure::{{MessageBroker, IdempotencyStore}};
-
-/// Message from the broker
-#[derive(Debug, Clone)]
-pub struct Message {{
-    /// Message key
-    pub key: Option<String>,
-    /// Message payload
-    pub payload: Vec<u8>,
-    /// Message headers
-    pub headers: Vec<(String, String)>,
-    /// Topic
-    pub topic: String,
-    /// Partition
-    pub partition: i32,
-    /// Offset
-    pub offset: i64,
-}}
-
-impl Message {{
-    /// Get the event ID from headers
-    pub fn event_id(&self) -> Option<String> {{
-        self.headers
-            .iter()
-            .find(|(k, _)| k == "event_id")
-            .map(|(_, v)| v.clone())
-    }}
-
-    /// Get the event type from headers
-    pub fn event_type(&self) -> Option<String> {{
-        self.headers
-            .iter()
-            .find(|(k, _)| k == "event_type")
-            .map(|(_, v)| v.clone())
-    }}
-}}
 
 /// Main consumer service
-pub struct {pascal_name}Consumer<B: MessageBroker, I: IdempotencyStore> {{
-    broker: B,
-    idempotency_store: Arc<I>,
+///
+/// Offsets are committed only after a message is handled. Failing handlers
+/// are retried, then the message goes to the DLQ; without a DLQ the error
+/// stops the consumer and the message is redelivered on restart.
+pub struct {pascal_name}Consumer<C: MessageConsumer> {{
+    delivery: AtLeastOnceConsumer<C>,
     handler_registry: {pascal_name}HandlerRegistry,
-    retry_config: RetryConfig,
-    dlq_config: DlqConfig,
+    idempotency: Arc<dyn IdempotencyRegistry>,
 }}
 
-impl<B: MessageBroker, I: IdempotencyStore> {pascal_name}Consumer<B, I> {{
+impl<C: MessageConsumer> {pascal_name}Consumer<C> {{
     pub fn new(
-        broker: B,
-        idempotency_store: Arc<I>,
-        retry_config: RetryConfig,
-        dlq_config: DlqConfig,
+        consumer: C,
+        dlq: Option<DeadLetterQueue>,
+        idempotency: Arc<dyn IdempotencyRegistry>,
+        retry_config: &RetryConfig,
     ) -> Self {{
+        let mut delivery = AtLeastOnceConsumer::new(consumer)
+            .max_attempts(retry_config.max_attempts)
+            .retry_backoff(retry_config.backoff);
+        if let Some(dlq) = dlq {{
+            delivery = delivery.with_dlq(dlq);
+        }}
+
         Self {{
-            broker,
-            idempotency_store,
+            delivery,
             handler_registry: {pascal_name}HandlerRegistry::new(),
-            retry_config,
-            dlq_config,
+            idempotency,
         }}
     }}
 
-    /// Run the consumer loop
-    pub async fn run(&self, topics: &[String]) -> Result<()> {{
+    /// Consume from `topics` until `shutdown` fires
+    pub async fn run(&self, topics: &[String], mut shutdown: ShutdownToken) -> Result<()> {{
         info!("Subscribing to topics: {{:?}}", topics);
-        self.broker.subscribe(topics).await?;
-
-        loop {{
-            match self.broker.poll(Duration::from_secs(1)).await {{
-                Ok(Some(message)) => {{
-                    if let Err(e) = self.process_message(message).await {{
-                        error!("Error processing message: {{}}", e);
+        let broker = self.delivery.consumer();
+        broker.subscribe(topics).await?;
+
+        while !shutdown.is_shutdown() {{
+            tokio::select! {{
+                _ = shutdown.cancelled() => break,
+                polled = broker.poll(Duration::from_secs(1)) => {{
+                    if let Some(message) = polled? {{
+                        self.process(message).await?;
                     }}
                 }}
-                Ok(None) => {{
-                    // No message available, continue polling
-                }}
-                Err(e) => {{
-                    error!("Error polling for messages: {{}}", e);
-                    tokio::time::sleep(Duration::from_secs(1)).await;
-                }}
             }}
         }}
+        Ok(())
     }}
 
+    /// Handle one message, returning `None` if it was a duplicate
     #[instrument(skip(self, message), fields(topic = %message.topic, partition = %message.partition, offset = %message.offset))]
-    async fn process_message(&self, message: Message) -> Result<()> {{
-        let event_id = message.event_id().unwrap_or_else(|| {{
-            format!("{{}}:{{}}:{{}}", message.topic, message.partition, message.offset)
-        }});
-
-        // Check idempotency
-        if self.idempotency_store.exists(&event_id).await? {{
+    pub async fn process(&self, message: Message) -> Result<Option<DeliveryOutcome>> {{
+        let event_id = message.dedup_key();
+        let duplicate = self
+            .idempotency
+            .seen(&event_id)
+            .await
+            .map_err({pascal_name}Error::IdempotencyError)?;
+        if duplicate {{
             info!(event_id = %event_id, "Event already processed, skipping");
-            self.broker.commit(&message).await?;
-            return Ok(());
+            self.delivery.consumer().commit(&message).await?;
+            return Ok(None);
         }}
 
-        let event_type = message.event_type().unwrap_or_else(|| "Unknown".to_string());
-
-        // Process with retry
-        let result = self.process_with_retry(&event_type, &message.payload).await;
-
-        match result {{
-            Ok(()) => {{
-                // Mark as processed
-                self.idempotency_store.mark_processed(&event_id).await?;
-                self.broker.commit(&message).await?;
-                info!(event_id = %event_id, "Event processed successfully");
-            }}
-            Err(e) => {{
-                error!(event_id = %event_id, error = %e, "Failed to process event");
-
-                if self.dlq_config.enabled {{
-                    // Send to DLQ
-                    let dlq_topic = format!("{{}}{{}}", message.topic, self.dlq_config.suffix);
-                    self.broker.send_to_dlq(&dlq_topic, &message).await?;
-                    self.broker.commit(&message).await?;
-                    warn!(event_id = %event_id, dlq_topic = %dlq_topic, "Event sent to DLQ");
-                }} else {{
-                    return Err(e);
-                }}
-            }}
-        }}
-
-        Ok(())
-    }}
-
-    async fn process_with_retry(&self, event_type: &str, payload: &[u8]) -> Result<()> {{
-        let mut attempts = 0;
-        let mut backoff = self.retry_config.initial_backoff;
-
-        loop {{
-            attempts += 1;
-
-            match self.handler_registry.handle_event(event_type, payload).await {{
-                Ok(()) => return Ok(()),
-                Err(e) if attempts >= self.retry_config.max_attempts => {{
-                    return Err(e);
-                }}
-                Err(e) => {{
-                    warn!(
-                        attempt = attempts,
-                        max_attempts = self.retry_config.max_attempts,
-                        error = %e,
-                        "Handler failed, retrying"
-                    );
-
-                    tokio::time::sleep(backoff).await;
-                    backoff = std::cmp::min(
-                        Duration::from_secs_f64(backoff.as_secs_f64() * self.retry_config.multiplier),
-                        self.retry_config.max_backoff,
-                    );
-                }}
-            }}
+        let handlers = &self.handler_registry;
+        let outcome = self
+            .delivery
+            .process(message, |message| async move {{
+                let event_type = message.event_type().unwrap_or("Unknown");
+                handlers.handle_event(event_type, &message.payload).await
+            }})
+            .await?;
+
+        match outcome {{
+            DeliveryOutcome::Handled => info!(event_id = %event_id, "Event processed successfully"),
+            DeliveryOutcome::DeadLettered => warn!(event_id = %event_id, "Event sent to DLQ"),
         }}
+        Ok(Some(outcome))
     }}
 }}
 "#,
@@ -747,212 +683,39 @@ pub fn infrastructure_mod(_config: &ProjectConfig) -> String {
     r#"//! Infrastructure layer - Message brokers and stores
 
 mod broker;
-mod idempotency;
 mod health;
 
 pub use broker::*;
-pub use idempotency::*;
 pub use health::*;
 "#
     .to_string()
 }
 
 /// Generate infrastructure/broker.rs
-pub fn infrastructure_broker(config: &ProjectConfig) -> String {
-    let consumer = config.consumer.as_ref().unwrap();
-    let pascal_name = to_pascal_case(&consumer.service_name);
+pub fn infrastructure_broker(_config: &ProjectConfig) -> String {
+    r#"//! Kafka connection settings
 
-    format!(
-        r#"//! Message broker implementations
-
-use std::time::Duration;
-use async_trait::async_trait;
-use tracing::{{info, debug}};
+use allframe_core::messaging::KafkaConfig;
 
 use crate::config::BrokerConfig;
-use crate::application::Message;
-use crate::error::{{Result, {pascal_name}Error}};
-
-/// Message broker trait
-#[async_trait]
-pub trait MessageBroker: Send + Sync {{
-    /// Subscribe to topics
-    async fn subscribe(&self, topics: &[String]) -> Result<()>;
-
-    /// Poll for messages
-    async fn poll(&self, timeout: Duration) -> Result<Option<Message>>;
-
-    /// Commit message offset
-    async fn commit(&self, message: &Message) -> Result<()>;
-
-    /// Send message to DLQ
-    async fn send_to_dlq(&self, dlq_topic: &str, message: &Message) -> Result<()>;
-}}
-
-/// Kafka message broker implementation
-pub struct KafkaMessageBroker {{
-    // In a real implementation, this would hold the rdkafka consumer
-    _config: BrokerConfig,
-}}
-
-impl KafkaMessageBroker {{
-    pub async fn new(config: &BrokerConfig) -> Result<Self> {{
-        info!("Connecting to Kafka brokers: {{}}", config.brokers);
-
-        // In a real implementation:
-        // let consumer: StreamConsumer = ClientConfig::new()
-        //     .set("bootstrap.servers", &config.brokers)
-        //     .set("group.id", &group_id)
-        //     .set("enable.partition.eof", "false")
-        //     .set("session.timeout.ms", "6000")
-        //     .set("enable.auto.commit", "false")
-        //     .create()?;
-
-        Ok(Self {{
-            _config: config.clone(),
-        }})
-    }}
-}}
-
-#[async_trait]
-impl MessageBroker for KafkaMessageBroker {{
-    async fn subscribe(&self, topics: &[String]) -> Result<()> {{
-        info!("Subscribing to topics: {{:?}}", topics);
-        // In a real implementation:
-        // self.consumer.subscribe(&topics.iter().map(|s| s.as_str()).collect::<Vec<_>>())?;
-        Ok(())
-    }}
-
-    async fn poll(&self, timeout: Duration) -> Result<Option<Message>> {{
-        debug!("Polling for messages with timeout: {{:?}}", timeout);
-
-        // In a real implementation:
-        // match self.consumer.poll(timeout) {{
-        //     Some(Ok(msg)) => {{
-        //         let headers = msg.headers()
-        //             .map(|h| {{
-        //                 (0..h.count())
-        //                     .filter_map(|i| {{
-        //                         h.get(i).map(|header| {{
-        //                             (header.key.to_string(), String::from_utf8_lossy(header.value.unwrap_or_default()).to_string())
-        //                         }})
-        //                     }})
-        //                     .collect()
-        //             }})
-        //             .unwrap_or_default();
-        //
-        //         Ok(Some(Message {{
-        //             key: msg.key().map(|k| String::from_utf8_lossy(k).to_string()),
-        //             payload: msg.payload().unwrap_or_default().to_vec(),
-        //             headers,
-        //             topic: msg.topic().to_string(),
-        //             partition: msg.partition(),
-        //             offset: msg.offset(),
-        //         }}))
-        //     }}
-        //     Some(Err(e)) => Err({pascal_name}Error::BrokerError(e.to_string())),
-        //     None => Ok(None),
-        // }}
-
-        // Placeholder: simulate no messages
-        tokio::time::sleep(timeout).await;
-        Ok(None)
-    }}
 
-    async fn commit(&self, message: &Message) -> Result<()> {{
-        debug!(
-            topic = %message.topic,
-            partition = %message.partition,
-            offset = %message.offset,
-            "Committing offset"
-        );
-        // In a real implementation:
-        // self.consumer.commit_message(&message, CommitMode::Async)?;
-        Ok(())
-    }}
-
-    async fn send_to_dlq(&self, dlq_topic: &str, message: &Message) -> Result<()> {{
-        info!(
-            dlq_topic = %dlq_topic,
-            original_topic = %message.topic,
-            "Sending message to DLQ"
-        );
-        // In a real implementation, use a producer to send to DLQ
-        Ok(())
-    }}
-}}
-"#,
-        pascal_name = pascal_name,
-    )
+/// Kafka client settings for the consumer and its DLQ producer
+pub fn kafka_config(config: &BrokerConfig, group_id: &str) -> KafkaConfig {
+    let mut kafka = KafkaConfig::new(config.brokers.clone(), group_id)
+        .property("security.protocol", config.security_protocol.clone());
+    if let Some(mechanism) = &config.sasl_mechanism {
+        kafka = kafka.property("sasl.mechanism", mechanism.clone());
+    }
+    if let Some(username) = &config.sasl_username {
+        kafka = kafka.property("sasl.username", username.clone());
+    }
+    if let Some(password) = &config.sasl_password {
+        kafka = kafka.property("sasl.password", password.clone());
+    }
+    kafka
 }
-
-/// Generate infrastructure/idempotency.rs
-pub fn infrastructure_idempotency(config: &ProjectConfig) -> String {
-    let consumer = config.consumer.as_ref().unwrap();
-    let pascal_name = to_pascal_case(&consumer.service_name);
-
-    format!(
-        r#"//! Idempotency store implementations
-
-use std::collections::HashSet;
-use std::sync::RwLock;
-use async_trait::async_trait;
-
-use crate::error::{{Result, {pascal_name}Error}};
-
-/// Idempotency store trait
-#[async_trait]
-pub trait IdempotencyStore: Send + Sync {{
-    /// Check if an event has already been processed
-    async fn exists(&self, event_id: &str) -> Result<bool>;
-
-    /// Mark an event as processed
-    async fn mark_processed(&self, event_id: &str) -> Result<()>;
-}}
-
-/// In-memory idempotency store (for development/testing)
-pub struct InMemoryIdempotencyStore {{
-    processed: RwLock<HashSet<String>>,
-}}
-
-impl InMemoryIdempotencyStore {{
-    pub fn new() -> Self {{
-        Self {{
-            processed: RwLock::new(HashSet::new()),
-        }}
-    }}
-}}
-
-impl Default for InMemoryIdempotencyStore {{
-    fn default() -> Self {{
-        Self::new()
-    }}
-}}
-
-#[async_trait]
-impl IdempotencyStore for InMemoryIdempotencyStore {{
-    async fn exists(&self, event_id: &str) -> Result<bool> {{
-        let guard = self.processed.read()
-            .map_err(|e| {pascal_name}Error::IdempotencyError(e.to_string()))?;
-        Ok(guard.contains(event_id))
-    }}
-
-    async fn mark_processed(&self, event_id: &str) -> Result<()> {{
-        let mut guard = self.processed.write()
-            .map_err(|e| {pascal_name}Error::IdempotencyError(e.to_string()))?;
-        guard.insert(event_id.to_string());
-        Ok(())
-    }}
-}}
-
-// TODO: Add Redis implementation
-// pub struct RedisIdempotencyStore {{ ... }}
-
-// TODO: Add PostgreSQL implementation
-// pub struct PostgresIdempotencyStore {{ ... }}
-"#,
-        pascal_name = pascal_name,
-    )
+"#
+    .to_string()
 }
 
 /// Generate infrastructure/health.rs
@@ -1026,9 +789,10 @@ An event consumer service built with AllFrame.
 ## Features
 
 - **{broker} Consumer**: Event-driven message processing
-- **Idempotency**: Duplicate event detection
-- **Dead Letter Queue**: Failed message handling
-- **Retry with Backoff**: Configurable retry strategy
+- **At-least-once delivery**: Offsets are committed only after a message is handled
+- **Idempotency**: Duplicate events are detected by event ID and skipped
+- **Dead Letter Queue**: Messages that still fail after retrying go to the DLQ
+- **Retry with Backoff**: Configurable attempts and delay
 - **Health Checks**: Kubernetes-ready endpoints
 
 ## Configuration
@@ -1043,13 +807,15 @@ Set the following environment variables:
 
 # Retry configuration
 {upper_name}_RETRY_MAX_ATTEMPTS=3
-{upper_name}_RETRY_INITIAL_BACKOFF_MS=100
-{upper_name}_RETRY_MAX_BACKOFF_MS=10000
+{upper_name}_RETRY_BACKOFF_MS=100
 
 # DLQ configuration
 {upper_name}_DLQ_ENABLED=true
 {upper_name}_DLQ_SUFFIX=.dlq
 
+# Idempotency configuration
+{upper_name}_IDEMPOTENCY_TTL_SECS=86400
+
 # Server configuration
 {upper_name}_HEALTH_PORT=8081
 {upper_name}_METRICS_PORT=9090
@@ -1114,6 +880,74 @@ CMD ["/app/{name}"]
     )
 }
 
+/// Generate tests/integration.rs
+pub fn integration_tests(config: &ProjectConfig) -> String {
+    let consumer = config.consumer.as_ref().unwrap();
+    let pascal_name = to_pascal_case(&consumer.service_name);
+
+    let duplicate_test = format!(
+        r#"
+#[tokio::test]
+async fn test_duplicate_message_is_skipped() {{
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use allframe_core::cqrs::InMemoryIdempotencyRegistry;
+    use allframe_core::messaging::{{
+        DeliveryOutcome, InMemoryBroker, MessageConsumer, MessageProducer, OutgoingMessage,
+        EVENT_ID_HEADER, EVENT_TYPE_HEADER,
+    }};
+
+    let event = domain::EventEnvelope::new(
+        "UserCreated",
+        domain::UserCreated {{
+            user_id: uuid::Uuid::new_v4(),
+            email: "ada@example.com".to_string(),
+            name: "Ada".to_string(),
+        }},
+    );
+    let message = OutgoingMessage::json(&event)
+        .unwrap()
+        .header(EVENT_ID_HEADER, event.id.to_string())
+        .header(EVENT_TYPE_HEADER, "UserCreated");
+
+    let broker = InMemoryBroker::new();
+    broker.send("events", message.clone()).await.unwrap();
+    broker.send("events", message).await.unwrap();
+    broker.subscribe(&["events".to_string()]).await.unwrap();
+
+    let retry = config::RetryConfig {{
+        max_attempts: 3,
+        backoff: Duration::ZERO,
+    }};
+    let consumer = application::{pascal_name}Consumer::new(
+        broker.clone(),
+        None,
+        Arc::new(InMemoryIdempotencyRegistry::new(Duration::from_secs(60))),
+        &retry,
+    );
+
+    let first = broker.poll(Duration::ZERO).await.unwrap().unwrap();
+    assert_eq!(
+        consumer.process(first).await.unwrap(),
+        Some(DeliveryOutcome::Handled)
+    );
+    let duplicate = broker.poll(Duration::ZERO).await.unwrap().unwrap();
+    assert_eq!(consumer.process(duplicate).await.unwrap(), None);
+    assert_eq!(broker.committed_offset("events").await, Some(2));
+}}
+"#,
+        pascal_name = pascal_name,
+    );
+
+    format!(
+        "{}{}{}",
+        super::integration_header(&["config", "error", "domain", "application", "infrastructure"]),
+        super::INTEGRATION_HTTP_TESTS,
+        duplicate_test
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,150 @@
+//! Shared errors crate templates
+//!
+//! Templates for the `errors` crate generated by `allframe ignite
+//! --shared-errors`. The crate holds one error enum mapped to gRPC and HTTP
+//! status codes with `#[derive(GrpcError)]` and `#[derive(HttpError)]`, so
+//! every service in the workspace reports the same status for the same
+//! failure.
+
+/// Generate Cargo.toml for the errors crate
+pub fn cargo_toml() -> String {
+    r#"[package]
+name = "errors"
+version = "0.1.0"
+edition = "2021"
+rust-version = "1.89"
+description = "Errors shared by the workspace's services, with gRPC and HTTP status mappings"
+publish = false
+
+[dependencies]
+allframe-core = { version = "0.1", default-features = false, features = ["router-grpc"] }
+thiserror = "2.0"
+"#
+    .to_string()
+}
+
+/// Generate src/lib.rs for the errors crate
+pub fn lib_rs() -> String {
+    r#"//! Errors shared by the workspace's services
+//!
+//! Each variant's gRPC and HTTP status come from its `#[grpc]` and `#[http]`
+//! attributes. Services wrap [`ServiceError`] in their own error types or
+//! return it directly, so a given failure maps to the same status
+//! everywhere.
+
+use allframe_core::{GrpcError, HttpError};
+
+/// Errors with a fixed gRPC and HTTP status
+#[derive(Debug, thiserror::Error, GrpcError, HttpError)]
+#[grpc_error(crate = "allframe_core")]
+#[http_error(crate = "allframe_core")]
+pub enum ServiceError {
+    /// The requested resource doesn't exist
+    #[error("Not found: {0}")]
+    #[grpc(NOT_FOUND)]
+    #[http(NOT_FOUND)]
+    NotFound(String),
+
+    /// The request is malformed or fails validation
+    #[error("Invalid argument: {0}")]
+    #[grpc(INVALID_ARGUMENT)]
+    #[http(BAD_REQUEST)]
+    InvalidArgument(String),
+
+    /// The resource being created already exists
+    #[error("Already exists: {0}")]
+    #[grpc(ALREADY_EXISTS)]
+    #[http(CONFLICT)]
+    AlreadyExists(String),
+
+    /// The caller isn't authenticated
+    #[error("Unauthenticated: {0}")]
+    #[grpc(UNAUTHENTICATED)]
+    #[http(UNAUTHORIZED)]
+    Unauthenticated(String),
+
+    /// The caller isn't allowed to perform the operation
+    #[error("Permission denied: {0}")]
+    #[grpc(PERMISSION_DENIED)]
+    #[http(FORBIDDEN)]
+    PermissionDenied(String),
+
+    /// The caller exceeded a rate limit or quota
+    #[error("Rate limited")]
+    #[grpc(RESOURCE_EXHAUSTED)]
+    #[http(TOO_MANY_REQUESTS)]
+    RateLimited,
+
+    /// A dependency didn't respond in time
+    #[error("Timed out: {0}")]
+    #[grpc(DEADLINE_EXCEEDED)]
+    #[http(GATEWAY_TIMEOUT)]
+    Timeout(String),
+
+    /// A dependency is down; the request can be retried
+    #[error("Unavailable: {0}")]
+    #[grpc(UNAVAILABLE)]
+    #[http(SERVICE_UNAVAILABLE)]
+    Unavailable(String),
+
+    /// Anything else
+    #[error("Internal error: {0}")]
+    #[grpc(INTERNAL)]
+    #[http(INTERNAL_SERVER_ERROR)]
+    Internal(String),
+}
+
+#[cfg(test)]
+mod tests {
+    use allframe_core::{
+        router::HttpError as _,
+        tonic::{Code, Status},
+    };
+
+    use super::*;
+
+    fn grpc_code(error: ServiceError) -> Code {
+        Status::from(error).code()
+    }
+
+    #[test]
+    fn test_grpc_status_mapping() {
+        assert_eq!(
+            grpc_code(ServiceError::NotFound("order 42".into())),
+            Code::NotFound
+        );
+        assert_eq!(
+            grpc_code(ServiceError::InvalidArgument("quantity".into())),
+            Code::InvalidArgument
+        );
+        assert_eq!(
+            grpc_code(ServiceError::RateLimited),
+            Code::ResourceExhausted
+        );
+        assert_eq!(
+            grpc_code(ServiceError::Unavailable("inventory".into())),
+            Code::Unavailable
+        );
+
+        let status = Status::from(ServiceError::NotFound("order 42".into()));
+        assert_eq!(status.message(), "Not found: order 42");
+    }
+
+    #[test]
+    fn test_http_status_mapping() {
+        assert_eq!(ServiceError::NotFound("order 42".into()).status_code(), 404);
+        assert_eq!(
+            ServiceError::AlreadyExists("order 42".into()).status_code(),
+            409
+        );
+        assert_eq!(
+            ServiceError::Unauthenticated("token".into()).status_code(),
+            401
+        );
+        assert_eq!(ServiceError::RateLimited.status_code(), 429);
+        assert_eq!(ServiceError::Internal("oops".into()).status_code(), 500);
+    }
+}
+"#
+    .to_string()
+}
@@ -3,7 +3,7 @@
 //! Templates for generating exchange gateway services with gRPC,
 //! resilience patterns, caching, and observability.
 
-use crate::config::{AuthMethod, CacheBackend, ProjectConfig};
+use crate::config::{AuthMethod, CacheBackend, GatewayConfig, ProjectConfig};
 
 /// Generate Cargo.toml for gateway project
 pub fn cargo_toml(config: &ProjectConfig) -> String {
@@ -24,7 +24,7 @@ description = "{display_name}"
 
 [dependencies]
 # AllFrame
-allframe-core = {{ version = "0.1", features = ["resilience", "security", "otel"] }}
+allframe-core = {{ version = "0.1", features = ["resilience", "security", "otel", "money"] }}
 
 # gRPC
 tonic = "0.12"
@@ -66,6 +66,13 @@ tracing-subscriber = {{ version = "0.3", features = ["env-filter"] }}
 opentelemetry = {{ version = "0.27", features = ["metrics"] }}
 opentelemetry-otlp = "0.27"
 
+[features]
+default = ["otel"]
+# Logs, traces and metrics through allframe-core's observability stack,
+# exported over OTLP when OTEL_EXPORTER_OTLP_ENDPOINT is set. Without it,
+# logs go to stdout.
+otel = ["allframe-core/otel-otlp"]
+
 [dev-dependencies]
 mockall = "0.13"
 tokio-test = "0.4"
@@ -260,6 +267,7 @@ pub mod generated {{
     tonic::include_proto!("{service_name}");
 }}
 
+use allframe_core::config::show_config_requested;
 use config::Config;
 use application::{pascal_name}Service;
 use infrastructure::{{
@@ -270,23 +278,28 @@ use infrastructure::{{
 use presentation::{pascal_name}GrpcService;
 use generated::{service_name}_service_server::{pascal_name}ServiceServer;
 
-#[tokio::main]
+{observability}#[tokio::main]
 async fn main() -> anyhow::Result<()> {{
     // Load environment variables
     dotenvy::dotenv().ok();
 
-    // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive(tracing::Level::INFO.into()),
-        )
-        .init();
+    // Initialize logging, tracing and metrics; the guard flushes them on exit
+    let _observability = init_observability()?;
 
     // Load configuration
-    let config = Config::from_env();
+    let config = Config::load()?;
+    if show_config_requested() {{
+        print!("{{}}", config.report());
+        return Ok(());
+    }}
     info!("Starting {display_name} on port {{}}", config.server.grpc_port);
 
+    // OpenAPI generator advertising the configured environments
+    let _openapi = config.openapi_generator("{display_name}", env!("CARGO_PKG_VERSION"));
+    for (url, description) in &config.docs.servers {{
+        info!("API docs server: {{}} ({{}})", url, description);
+    }}
+
     // Initialize metrics
     let _metrics = Arc::new(GatewayMetrics::new());
 
@@ -300,7 +313,7 @@ async fn main() -> anyhow::Result<()> {{
     // Create HTTP client
     let client = Arc::new({pascal_name}Client::new(
         &config.{service_name}.base_url,
-        config.{service_name}.timeout,
+        config.{service_name}.timeout(),
     ));
 
     // Create service
@@ -330,6 +343,7 @@ async fn shutdown_signal() {{
     info!("Shutdown signal received");
 }}
 "#,
+        observability = super::OBSERVABILITY_INIT,
         display_name = gateway.display_name,
         service_name = service_name,
         pascal_name = pascal_name,
@@ -361,106 +375,135 @@ pub fn config_rs(config: &ProjectConfig) -> String {
     format!(
         r#"//! Configuration module
 //!
-//! Loads configuration from environment variables.
+//! Values are layered, each overriding the one before:
+//!
+//! 1. the defaults below
+//! 2. `config.toml` in the working directory, if present
+//! 3. environment variables prefixed with `{upper_name}_`, using `__`
+//!    between nested keys, e.g. `{upper_name}_SERVER__GRPC_PORT=50052`
+//!
+//! Run with `--show-config` to print each value and where it came from.
 
 use std::time::Duration;
 
-#[derive(Debug, Clone)]
+use allframe_core::{{
+    config::{{ConfigError, ConfigLoader, LoadedConfig}},
+    router::OpenApiGenerator,
+}};
+use serde::{{Deserialize, Serialize}};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {{
     pub server: ServerConfig,
     pub {service_name}: {pascal_name}Config,
     pub rate_limit: RateLimitConfig,
     pub cache: CacheConfig,
+    pub docs: DocsConfig,
 }}
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServerConfig {{
     pub grpc_port: u16,
     pub health_port: u16,
     pub metrics_port: u16,
 }}
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct {pascal_name}Config {{
     pub base_url: String,
-    pub timeout: Duration,
+    pub timeout_secs: u64,
 }}
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RateLimitConfig {{
     pub public_rps: u32,
     pub private_rps: u32,
     pub burst: u32,
 }}
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CacheConfig {{
     pub enabled: bool,
-    pub public_ttl: Duration,
-    pub private_ttl: Duration,
+    pub public_ttl_secs: u64,
+    pub private_ttl_secs: u64,
 }}
 
-impl Config {{
-    pub fn from_env() -> Self {{
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocsConfig {{
+    /// OpenAPI servers as (url, description) pairs
+    pub servers: Vec<(String, String)>,
+}}
+
+impl Default for Config {{
+    fn default() -> Self {{
         Self {{
             server: ServerConfig {{
-                grpc_port: std::env::var("{upper_name}_GATEWAY_PORT")
-                    .ok()
-                    .and_then(|s| s.parse().ok())
-                    .unwrap_or({grpc_port}),
-                health_port: std::env::var("{upper_name}_HEALTH_PORT")
-                    .ok()
-                    .and_then(|s| s.parse().ok())
-                    .unwrap_or({health_port}),
-                metrics_port: std::env::var("{upper_name}_METRICS_PORT")
-                    .ok()
-                    .and_then(|s| s.parse().ok())
-                    .unwrap_or({metrics_port}),
+                grpc_port: {grpc_port},
+                health_port: {health_port},
+                metrics_port: {metrics_port},
             }},
             {service_name}: {pascal_name}Config {{
-                base_url: std::env::var("{upper_name}_API_URL")
-                    .unwrap_or_else(|_| "{api_base_url}".to_string()),
-                timeout: Duration::from_secs(
-                    std::env::var("{upper_name}_API_TIMEOUT_SECONDS")
-                        .ok()
-                        .and_then(|s| s.parse().ok())
-                        .unwrap_or(30),
-                ),
+                base_url: "{api_base_url}".to_string(),
+                timeout_secs: 30,
             }},
             rate_limit: RateLimitConfig {{
-                public_rps: std::env::var("{upper_name}_RATE_LIMIT_PUBLIC_RPS")
-                    .ok()
-                    .and_then(|s| s.parse().ok())
-                    .unwrap_or({public_rps}),
-                private_rps: std::env::var("{upper_name}_RATE_LIMIT_PRIVATE_RPS")
-                    .ok()
-                    .and_then(|s| s.parse().ok())
-                    .unwrap_or({private_rps}),
-                burst: std::env::var("{upper_name}_RATE_LIMIT_BURST")
-                    .ok()
-                    .and_then(|s| s.parse().ok())
-                    .unwrap_or({burst}),
+                public_rps: {public_rps},
+                private_rps: {private_rps},
+                burst: {burst},
             }},
             cache: CacheConfig {{
-                enabled: std::env::var("CACHE_ENABLED")
-                    .map(|s| s.to_lowercase() == "true")
-                    .unwrap_or(true),
-                public_ttl: Duration::from_secs(
-                    std::env::var("CACHE_PUBLIC_TTL_SECONDS")
-                        .ok()
-                        .and_then(|s| s.parse().ok())
-                        .unwrap_or({public_ttl}),
-                ),
-                private_ttl: Duration::from_secs(
-                    std::env::var("CACHE_PRIVATE_TTL_SECONDS")
-                        .ok()
-                        .and_then(|s| s.parse().ok())
-                        .unwrap_or({private_ttl}),
-                ),
+                enabled: true,
+                public_ttl_secs: {public_ttl},
+                private_ttl_secs: {private_ttl},
+            }},
+            docs: DocsConfig {{
+                servers: vec![
+{docs_servers}                ],
             }},
         }}
     }}
 }}
+
+impl Config {{
+    /// Load defaults, `config.toml` and `{upper_name}_*` environment variables
+    pub fn load() -> Result<LoadedConfig<Self>, ConfigError> {{
+        ConfigLoader::new()
+            .with_defaults(&Self::default())
+            .with_file("config.toml")
+            .with_env_prefix("{upper_name}")
+            .load()
+    }}
+
+    /// OpenAPI generator with the configured servers, so the docs UI
+    /// "Try It" panel targets the right host for each environment
+    pub fn openapi_generator(&self, title: &str, version: &str) -> OpenApiGenerator {{
+        self.docs
+            .servers
+            .iter()
+            .fold(OpenApiGenerator::new(title, version), |generator, (url, description)| {{
+                generator.add_server(url, description)
+            }})
+    }}
+}}
+
+impl {pascal_name}Config {{
+    /// Get the request timeout as Duration
+    pub fn timeout(&self) -> Duration {{
+        Duration::from_secs(self.timeout_secs)
+    }}
+}}
+
+impl CacheConfig {{
+    /// Get public TTL as Duration
+    pub fn public_ttl(&self) -> Duration {{
+        Duration::from_secs(self.public_ttl_secs)
+    }}
+
+    /// Get private TTL as Duration
+    pub fn private_ttl(&self) -> Duration {{
+        Duration::from_secs(self.private_ttl_secs)
+    }}
+}}
 "#,
         service_name = service_name,
         pascal_name = to_pascal_case(service_name),
@@ -474,9 +517,36 @@ impl Config {{
         burst = gateway.rate_limit.burst,
         public_ttl = gateway.cache.public_ttl_secs,
         private_ttl = gateway.cache.private_ttl_secs,
+        docs_servers = docs_servers(gateway),
     )
 }
 
+/// Default OpenAPI servers, falling back to the local HTTP port
+fn docs_servers(gateway: &GatewayConfig) -> String {
+    let servers = if gateway.docs_servers.is_empty() {
+        vec![(
+            format!("http://localhost:{}", gateway.server.http_port),
+            "Local development".to_string(),
+        )]
+    } else {
+        gateway
+            .docs_servers
+            .iter()
+            .map(|s| (s.url.clone(), s.description.clone()))
+            .collect()
+    };
+
+    servers
+        .iter()
+        .map(|(url, description)| {
+            format!(
+                "                    ({:?}.to_string(), {:?}.to_string()),\n",
+                url, description
+            )
+        })
+        .collect()
+}
+
 /// Generate error.rs
 pub fn error_rs(config: &ProjectConfig) -> String {
     let gateway = config.gateway.as_ref().unwrap();
@@ -1625,6 +1695,7 @@ pub fn presentation_grpc(config: &ProjectConfig) -> String {
         r#"//! gRPC service implementation
 
 use std::sync::Arc;
+use allframe_core::money::AmountFormat;
 use tonic::{{Request, Response, Status}};
 use tracing::instrument;
 
@@ -1635,6 +1706,9 @@ use crate::generated::{{
     *,
 }};
 
+/// Prices and volumes cross the gRPC boundary as strings with this scale
+const AMOUNT: AmountFormat = AmountFormat::new(8);
+
 /// gRPC service implementation
 pub struct {pascal_name}GrpcService {{
     service: Arc<dyn {pascal_name}ServiceTrait>,
@@ -1704,10 +1778,10 @@ impl GrpcServiceTrait for {pascal_name}GrpcService {{
                     t.pair.clone(),
                     crate::generated::TickerInfo {{
                         pair: t.pair,
-                        last_price: t.last_price.to_string(),
-                        bid: t.bid.to_string(),
-                        ask: t.ask.to_string(),
-                        volume_24h: t.volume_24h.to_string(),
+                        last_price: AMOUNT.format(t.last_price),
+                        bid: AMOUNT.format(t.bid),
+                        ask: AMOUNT.format(t.ask),
+                        volume_24h: AMOUNT.format(t.volume_24h),
                     }},
                 )
             }})
@@ -1726,7 +1800,7 @@ impl GrpcServiceTrait for {pascal_name}GrpcService {{
 
         let balances_map = balances
             .into_iter()
-            .map(|b| (b.asset, b.free.to_string()))
+            .map(|b| (b.asset, AMOUNT.format(b.free)))
             .collect();
 
         Ok(Response::new(GetAccountBalanceResponse {{ balances: balances_map }}))
@@ -1755,9 +1829,9 @@ impl GrpcServiceTrait for {pascal_name}GrpcService {{
                 id: t.id,
                 pair: t.pair,
                 side: t.side.to_string(),
-                price: t.price.to_string(),
-                volume: t.volume.to_string(),
-                fee: t.fee.to_string(),
+                price: AMOUNT.format(t.price),
+                volume: AMOUNT.format(t.volume),
+                fee: AMOUNT.format(t.fee),
                 timestamp: t.timestamp,
             }})
             .collect();
@@ -1777,10 +1851,10 @@ impl GrpcServiceTrait for {pascal_name}GrpcService {{
             .map_err(|_| Status::invalid_argument("Invalid order side"))?;
         let order_type: OrderType = req.order_type.parse()
             .map_err(|_| Status::invalid_argument("Invalid order type"))?;
-        let volume = req.volume.parse()
-            .map_err(|_| Status::invalid_argument("Invalid volume"))?;
-        let price = req.price.map(|p| p.parse()).transpose()
-            .map_err(|_| Status::invalid_argument("Invalid price"))?;
+        let volume = AMOUNT.parse(&req.volume)
+            .map_err(|e| Status::invalid_argument(format!("Invalid volume: {{}}", e)))?;
+        let price = req.price.as_deref().map(|p| AMOUNT.parse(p)).transpose()
+            .map_err(|e| Status::invalid_argument(format!("Invalid price: {{}}", e)))?;
 
         let order = self.service
             .add_order(&creds, &req.pair, side, order_type, volume, price)
@@ -1839,24 +1913,31 @@ pub fn env_example(config: &ProjectConfig) -> String {
     let upper_name = gateway.service_name.to_uppercase();
 
     format!(
-        r#"# Server Configuration
-{upper_name}_GATEWAY_PORT={grpc_port}
-{upper_name}_HEALTH_PORT={health_port}
-{upper_name}_METRICS_PORT={metrics_port}
+        r#"# Overrides for config.toml and the built-in defaults.
+# Nested keys are separated by "__"; run with --show-config to see where
+# each value comes from.
+
+# Server Configuration
+{upper_name}_SERVER__GRPC_PORT={grpc_port}
+{upper_name}_SERVER__HEALTH_PORT={health_port}
+{upper_name}_SERVER__METRICS_PORT={metrics_port}
 
 # API Configuration
-{upper_name}_API_URL={api_base_url}
-{upper_name}_API_TIMEOUT_SECONDS=30
+{upper_name}_{upper_name}__BASE_URL={api_base_url}
+{upper_name}_{upper_name}__TIMEOUT_SECS=30
 
 # Rate Limiting
-{upper_name}_RATE_LIMIT_PUBLIC_RPS={public_rps}
-{upper_name}_RATE_LIMIT_PRIVATE_RPS={private_rps}
-{upper_name}_RATE_LIMIT_BURST={burst}
+{upper_name}_RATE_LIMIT__PUBLIC_RPS={public_rps}
+{upper_name}_RATE_LIMIT__PRIVATE_RPS={private_rps}
+{upper_name}_RATE_LIMIT__BURST={burst}
 
 # Cache Configuration
-CACHE_ENABLED=true
-CACHE_PUBLIC_TTL_SECONDS={public_ttl}
-CACHE_PRIVATE_TTL_SECONDS={private_ttl}
+{upper_name}_CACHE__ENABLED=true
+{upper_name}_CACHE__PUBLIC_TTL_SECS={public_ttl}
+{upper_name}_CACHE__PRIVATE_TTL_SECS={private_ttl}
+
+# OpenAPI servers as a JSON list of [url, description] pairs
+# {upper_name}_DOCS__SERVERS=[["http://localhost:8080","Local"],["https://staging.example.com","Staging"]]
 
 # Observability
 RUST_LOG=info
@@ -1975,6 +2056,57 @@ fn to_pascal_case(s: &str) -> String {
         .collect()
 }
 
+/// Generate tests/integration.rs
+pub fn integration_tests(config: &ProjectConfig) -> String {
+    let gateway = config.gateway.as_ref().unwrap();
+    let service_name = &gateway.service_name;
+    let pascal_name = to_pascal_case(service_name);
+
+    format!(
+        r#"{header}
+pub mod generated {{
+    tonic::include_proto!("{service_name}");
+}}
+
+use std::sync::Arc;
+
+use generated::{{{service_name}_service_server::{pascal_name}Service as _, HealthCheckRequest}};
+use tonic::Request;
+
+/// The gRPC service, wired as in `main.rs`
+fn grpc_service() -> presentation::{pascal_name}GrpcService {{
+    let config = config::Config::default();
+    let client = Arc::new(infrastructure::{pascal_name}Client::new(
+        &config.{service_name}.base_url,
+        config.{service_name}.timeout(),
+    ));
+    let service = Arc::new(application::{pascal_name}Service::new(client));
+    presentation::{pascal_name}GrpcService::new(service)
+}}
+
+#[tokio::test]
+async fn test_health_check() {{
+    let response = grpc_service()
+        .health_check(Request::new(HealthCheckRequest {{}}))
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(response.healthy, "{{}}", response.status);
+}}
+"#,
+        header = super::integration_header(&[
+            "config",
+            "error",
+            "domain",
+            "application",
+            "infrastructure",
+            "presentation"
+        ]),
+        service_name = service_name,
+        pascal_name = pascal_name,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2006,4 +2138,40 @@ mod tests {
             output.lines().find(|l| l.contains("rust-version")).unwrap_or("(not found)")
         );
     }
+
+    #[test]
+    fn test_gateway_config_rs_docs_servers() {
+        use crate::config::{DocsServerConfig, GatewayConfig, ProjectConfig};
+
+        let mut config = ProjectConfig {
+            gateway: Some(GatewayConfig::default()),
+            ..ProjectConfig::default()
+        };
+        let output = config_rs(&config);
+        assert!(output.contains(
+            r#"("http://localhost:8080".to_string(), "Local development".to_string()),"#
+        ));
+        assert!(output.contains(".with_env_prefix(\"EXCHANGE\")"));
+        assert!(output.contains("generator.add_server(url, description)"));
+
+        config.gateway = Some(GatewayConfig {
+            docs_servers: vec![
+                DocsServerConfig {
+                    url: "https://staging.example.com".to_string(),
+                    description: "Staging".to_string(),
+                },
+                DocsServerConfig {
+                    url: "https://api.example.com".to_string(),
+                    description: "Production".to_string(),
+                },
+            ],
+            ..GatewayConfig::default()
+        });
+        let output = config_rs(&config);
+        assert!(!output.contains("http://localhost:8080"));
+        assert!(output
+            .contains(r#"("https://staging.example.com".to_string(), "Staging".to_string()),"#));
+        assert!(output
+            .contains(r#"("https://api.example.com".to_string(), "Production".to_string()),"#));
+    }
 }
@@ -24,7 +24,7 @@ pub fn cargo_toml(config: &ProjectConfig) -> String {
     let name = &config.name;
 
     let broker_deps = match producer.broker {
-        MessageBroker::Kafka => r#"rdkafka = { version = "0.36", features = ["cmake-build"] }"#,
+        MessageBroker::Kafka => "# Kafka: allframe-core's messaging-kafka feature",
         MessageBroker::RabbitMq => r#"lapin = "2.3""#,
         MessageBroker::Redis => {
             r#"redis = { version = "0.25", features = ["tokio-comp", "streams"] }"#
@@ -43,7 +43,7 @@ description = "{display_name}"
 
 [dependencies]
 # AllFrame
-allframe-core = {{ version = "0.1", features = ["resilience", "otel"] }}
+allframe-core = {{ version = "0.1", features = ["resilience", "otel", "messaging", "messaging-kafka"] }}
 
 # Message Broker
 {broker_deps}
@@ -63,7 +63,7 @@ serde = {{ version = "1.0", features = ["derive"] }}
 serde_json = "1.0"
 
 # Database (for outbox)
-sqlx = {{ version = "0.8", features = ["runtime-tokio", "postgres", "uuid", "chrono", "json"] }}
+sqlx = {{ version = "0.8", features = ["runtime-tokio", "postgres", "uuid", "chrono", "json", "migrate"] }}
 
 # Error handling
 thiserror = "2.0"
@@ -79,6 +79,13 @@ chrono = {{ version = "0.4", features = ["serde"] }}
 uuid = {{ version = "1.0", features = ["v4", "serde"] }}
 dotenvy = "0.15"
 
+[features]
+default = ["otel"]
+# Logs, traces and metrics through allframe-core's observability stack,
+# exported over OTLP when OTEL_EXPORTER_OTLP_ENDPOINT is set. Without it,
+# logs go to stdout.
+otel = ["allframe-core/otel-otlp"]
+
 [dev-dependencies]
 tokio-test = "0.4"
 mockall = "0.13"
@@ -103,8 +110,13 @@ pub fn main_rs(config: &ProjectConfig) -> String {
 //!
 //! An event producer service with outbox pattern for reliable event publishing.
 
-use std::sync::Arc;
-use tracing::info;
+use std::{{sync::Arc, time::Duration}};
+
+use allframe_core::{{
+    messaging::{{KafkaConfig, KafkaProducer, OutboxRelay}},
+    shutdown::GracefulShutdown,
+}};
+use tracing::{{error, info}};
 
 mod config;
 mod error;
@@ -116,25 +128,18 @@ mod presentation;
 use config::Config;
 use application::{pascal_name}Service;
 use infrastructure::{{
-    KafkaEventPublisher,
     PostgresOutbox,
     PostgresRepository,
-    OutboxProcessor,
     HealthServer,
 }};
 
-#[tokio::main]
+{observability}#[tokio::main]
 async fn main() -> anyhow::Result<()> {{
     // Load environment variables
     dotenvy::dotenv().ok();
 
-    // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive(tracing::Level::INFO.into()),
-        )
-        .init();
+    // Initialize logging, tracing and metrics; the guard flushes them on exit
+    let _observability = init_observability()?;
 
     // Load configuration
     let config = Config::from_env();
@@ -142,17 +147,15 @@ async fn main() -> anyhow::Result<()> {{
     info!("Database: {{}}", config.database.url);
     info!("Broker: {{}}", config.broker.brokers);
 
-    // Create database pool
+    // Create database pool and apply migrations
     let db_pool = sqlx::postgres::PgPoolOptions::new()
         .max_connections(config.database.max_connections)
         .connect(&config.database.url)
         .await?;
+    sqlx::migrate!("./migrations").run(&db_pool).await?;
 
     // Create outbox
-    let outbox = Arc::new(PostgresOutbox::new(db_pool.clone()));
-
-    // Create event publisher
-    let publisher = Arc::new(KafkaEventPublisher::new(&config.broker).await?);
+    let outbox = PostgresOutbox::new(db_pool.clone(), config.broker.topic.clone());
 
     // Create repository
     let repository = Arc::new(PostgresRepository::new(db_pool.clone()));
@@ -160,17 +163,26 @@ async fn main() -> anyhow::Result<()> {{
     // Create service
     let service = Arc::new({pascal_name}Service::new(
         repository.clone(),
-        outbox.clone(),
+        Arc::new(outbox.clone()),
     ));
 
-    // Start outbox processor in background
-    let outbox_processor = OutboxProcessor::new(
-        outbox.clone(),
-        publisher.clone(),
-        config.outbox.clone(),
-    );
-    let outbox_handle = tokio::spawn(async move {{
-        outbox_processor.run().await
+    // Relay outbox entries to Kafka until shutdown
+    let shutdown = Arc::new(GracefulShutdown::new());
+    let producer = KafkaProducer::new(&KafkaConfig::new(
+        config.broker.brokers.clone(),
+        "{service_name}",
+    ))?;
+    let relay = OutboxRelay::new(outbox, producer)
+        .batch_size(config.outbox.batch_size)
+        .poll_interval(Duration::from_millis(config.outbox.poll_interval_ms))
+        .max_attempts(config.outbox.max_retries + 1);
+    let relay_handle = tokio::spawn({{
+        let token = shutdown.token();
+        async move {{
+            if let Err(e) = relay.run(token).await {{
+                error!("Outbox relay stopped: {{}}", e);
+            }}
+        }}
     }});
 
     // Start health server in background
@@ -186,16 +198,25 @@ async fn main() -> anyhow::Result<()> {{
     let listener = tokio::net::TcpListener::bind(
         format!("0.0.0.0:{{}}", config.server.port)
     ).await?;
-    axum::serve(listener, app).await?;
+    axum::serve(listener, app)
+        .with_graceful_shutdown({{
+            let shutdown = shutdown.clone();
+            async move {{
+                shutdown.wait().await;
+            }}
+        }})
+        .await?;
 
-    outbox_handle.abort();
+    relay_handle.await?;
     health_handle.abort();
     info!("Service shutdown complete");
     Ok(())
 }}
 "#,
+        observability = super::OBSERVABILITY_INIT,
         pascal_name = pascal_name,
         display_name = producer.display_name,
+        service_name = producer.service_name,
     )
 }
 
@@ -351,6 +372,12 @@ impl {pascal_name}Error {{
     }}
 }}
 
+impl From<allframe_core::messaging::MessagingError> for {pascal_name}Error {{
+    fn from(error: allframe_core::messaging::MessagingError) -> Self {{
+        Self::Broker(error.to_string())
+    }}
+}}
+
 impl axum::response::IntoResponse for {pascal_name}Error {{
     fn into_response(self) -> axum::response::Response {{
         let status = self.status_code();
@@ -566,7 +593,7 @@ use crate::domain::{{
     Update{pascal_name}Request,
 }};
 use crate::error::{pascal_name}Error;
-use super::Outbox;
+use super::{{event_message, Outbox}};
 
 /// Application service that coordinates domain operations and event publishing
 pub struct {pascal_name}Service {{
@@ -600,7 +627,7 @@ impl {pascal_name}Service {{
                 data: entity.data.clone(),
             }},
         );
-        self.outbox.store(event).await?;
+        self.outbox.store(event_message(&event)?).await?;
 
         info!("Created entity: {{}}", entity.id);
         Ok(entity)
@@ -644,7 +671,7 @@ impl {pascal_name}Service {{
                 data: request.data,
             }},
         );
-        self.outbox.store(event).await?;
+        self.outbox.store(event_message(&event)?).await?;
 
         info!("Updated entity: {{}}", entity.id);
         Ok(entity)
@@ -665,7 +692,7 @@ impl {pascal_name}Service {{
             "{pascal_name}",
             {pascal_name}Deleted {{ id }},
         );
-        self.outbox.store(event).await?;
+        self.outbox.store(event_message(&event)?).await?;
 
         info!("Deleted entity: {{}}", id);
         Ok(())
@@ -689,36 +716,28 @@ pub fn application_outbox(config: &ProjectConfig) -> String {
     format!(
         r#"//! Outbox pattern trait
 
+use allframe_core::messaging::{{OutgoingMessage, EVENT_ID_HEADER, EVENT_TYPE_HEADER}};
 use async_trait::async_trait;
 use serde::Serialize;
-use uuid::Uuid;
 
+use crate::domain::EventEnvelope;
 use crate::error::{pascal_name}Error;
 
-/// Outbox entry
-#[derive(Debug, Clone)]
-pub struct OutboxEntry {{
-    pub id: Uuid,
-    pub event_type: String,
-    pub payload: String,
-    pub created_at: chrono::DateTime<chrono::Utc>,
-    pub processed_at: Option<chrono::DateTime<chrono::Utc>>,
-    pub retries: i32,
-}}
-
-/// Outbox trait for storing events
+/// Outbox that stores events for the outbox relay to publish
 #[async_trait]
 pub trait Outbox: Send + Sync {{
-    async fn store<T: Serialize + Send + Sync>(&self, event: T) -> Result<(), {pascal_name}Error>;
-    async fn get_pending(&self, batch_size: usize) -> Result<Vec<OutboxEntry>, {pascal_name}Error>;
-    async fn mark_processed(&self, id: Uuid) -> Result<(), {pascal_name}Error>;
-    async fn mark_failed(&self, id: Uuid) -> Result<(), {pascal_name}Error>;
+    async fn store(&self, message: OutgoingMessage) -> Result<(), {pascal_name}Error>;
 }}
 
-/// Event publisher trait
-#[async_trait]
-pub trait EventPublisher: Send + Sync {{
-    async fn publish(&self, topic: &str, key: &str, payload: &str) -> Result<(), {pascal_name}Error>;
+/// Build the message for an event: its JSON encoding, keyed by aggregate so
+/// an aggregate's events stay in order, with event ID and type headers
+pub fn event_message<T: Serialize>(
+    event: &EventEnvelope<T>,
+) -> Result<OutgoingMessage, {pascal_name}Error> {{
+    Ok(OutgoingMessage::json(event)?
+        .key(event.aggregate_id.to_string())
+        .header(EVENT_ID_HEADER, event.event_id.to_string())
+        .header(EVENT_TYPE_HEADER, event.event_type.clone()))
 }}
 "#,
         pascal_name = pascal_name,
@@ -731,14 +750,10 @@ pub fn infrastructure_mod(_config: &ProjectConfig) -> String {
 
 pub mod repository;
 pub mod outbox;
-pub mod publisher;
-pub mod outbox_processor;
 pub mod health;
 
 pub use repository::*;
 pub use outbox::*;
-pub use publisher::*;
-pub use outbox_processor::*;
 pub use health::*;
 "#
     .to_string()
@@ -884,102 +899,105 @@ pub fn infrastructure_outbox(config: &ProjectConfig) -> String {
     format!(
         r##"//! PostgreSQL outbox implementation
 
+use allframe_core::messaging::{{MessagingError, OutboxEntry, OutboxStore, OutgoingMessage}};
 use async_trait::async_trait;
-use serde::Serialize;
-use sqlx::PgPool;
+use sqlx::{{types::Json, PgPool}};
 use uuid::Uuid;
 
-use crate::application::{{Outbox, OutboxEntry}};
+use crate::application::Outbox;
 use crate::error::{pascal_name}Error;
 
-/// PostgreSQL outbox implementation
+/// PostgreSQL outbox, written by the service and read by the outbox relay
+#[derive(Clone)]
 pub struct PostgresOutbox {{
     pool: PgPool,
+    topic: String,
 }}
 
 impl PostgresOutbox {{
-    pub fn new(pool: PgPool) -> Self {{
-        Self {{ pool }}
+    /// Outbox whose events are published to `topic`
+    pub fn new(pool: PgPool, topic: String) -> Self {{
+        Self {{ pool, topic }}
     }}
 }}
 
+fn store_error(error: sqlx::Error) -> MessagingError {{
+    MessagingError::Connection(format!("Outbox query failed: {{}}", error))
+}}
+
 #[async_trait]
 impl Outbox for PostgresOutbox {{
-    async fn store<T: Serialize + Send + Sync>(&self, event: T) -> Result<(), {pascal_name}Error> {{
-        let payload = serde_json::to_string(&event)?;
-        let event_type = std::any::type_name::<T>()
-            .rsplit("::")
-            .next()
-            .unwrap_or("Unknown");
-
+    async fn store(&self, message: OutgoingMessage) -> Result<(), {pascal_name}Error> {{
         sqlx::query(
             r#"
-            INSERT INTO outbox (id, event_type, payload, created_at, retries)
-            VALUES ($1, $2, $3, $4, 0)
+            INSERT INTO outbox (id, topic, message_key, payload, headers)
+            VALUES ($1, $2, $3, $4, $5)
             "#,
         )
         .bind(Uuid::new_v4())
-        .bind(event_type)
-        .bind(payload)
-        .bind(chrono::Utc::now())
+        .bind(&self.topic)
+        .bind(message.key)
+        .bind(message.payload)
+        .bind(Json(message.headers))
         .execute(&self.pool)
         .await?;
 
         Ok(())
     }}
+}}
 
-    async fn get_pending(&self, batch_size: usize) -> Result<Vec<OutboxEntry>, {pascal_name}Error> {{
-        let rows = sqlx::query_as::<_, (Uuid, String, String, chrono::DateTime<chrono::Utc>, Option<chrono::DateTime<chrono::Utc>>, i32)>(
+#[async_trait]
+impl OutboxStore for PostgresOutbox {{
+    async fn pending(
+        &self,
+        limit: usize,
+        max_attempts: u32,
+    ) -> Result<Vec<OutboxEntry>, MessagingError> {{
+        let rows = sqlx::query_as::<_, (Uuid, String, Option<String>, Vec<u8>, Json<Vec<(String, String)>>, i32)>(
             r#"
-            SELECT id, event_type, payload, created_at, processed_at, retries
+            SELECT id, topic, message_key, payload, headers, attempts
             FROM outbox
-            WHERE processed_at IS NULL
+            WHERE sent_at IS NULL AND attempts < $2
             ORDER BY created_at ASC
             LIMIT $1
-            FOR UPDATE SKIP LOCKED
             "#,
         )
-        .bind(batch_size as i64)
+        .bind(limit as i64)
+        .bind(max_attempts as i32)
         .fetch_all(&self.pool)
-        .await?;
-
-        Ok(rows.into_iter().map(|(id, event_type, payload, created_at, processed_at, retries)| OutboxEntry {{
-            id,
-            event_type,
-            payload,
-            created_at,
-            processed_at,
-            retries,
-        }}).collect())
+        .await
+        .map_err(store_error)?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(id, topic, key, payload, Json(headers), attempts)| OutboxEntry {{
+                id: id.to_string(),
+                topic,
+                message: OutgoingMessage {{ key, payload, headers }},
+                attempts: attempts as u32,
+            }})
+            .collect())
     }}
 
-    async fn mark_processed(&self, id: Uuid) -> Result<(), {pascal_name}Error> {{
-        sqlx::query(
-            r#"
-            UPDATE outbox
-            SET processed_at = $2
-            WHERE id = $1
-            "#,
-        )
-        .bind(id)
-        .bind(chrono::Utc::now())
-        .execute(&self.pool)
-        .await?;
+    async fn mark_sent(&self, id: &str) -> Result<(), MessagingError> {{
+        sqlx::query("UPDATE outbox SET sent_at = NOW() WHERE id = $1::uuid")
+            .bind(id)
+            .execute(&self.pool)
+            .await
+            .map_err(store_error)?;
 
         Ok(())
     }}
 
-    async fn mark_failed(&self, id: Uuid) -> Result<(), {pascal_name}Error> {{
+    async fn mark_failed(&self, id: &str, error: &str) -> Result<(), MessagingError> {{
         sqlx::query(
-            r#"
-            UPDATE outbox
-            SET retries = retries + 1
-            WHERE id = $1
-            "#,
+            "UPDATE outbox SET attempts = attempts + 1, last_error = $2 WHERE id = $1::uuid",
         )
         .bind(id)
+        .bind(error)
         .execute(&self.pool)
-        .await?;
+        .await
+        .map_err(store_error)?;
 
         Ok(())
     }}
@@ -989,147 +1007,36 @@ impl Outbox for PostgresOutbox {{
     )
 }
 
-/// Generate infrastructure/publisher.rs
-pub fn infrastructure_publisher(config: &ProjectConfig) -> String {
+/// Generate migrations/0001_create_tables.sql
+pub fn migration(config: &ProjectConfig) -> String {
     let producer = config.producer.as_ref().unwrap();
-    let pascal_name = to_pascal_case(&producer.service_name);
 
     format!(
-        r#"//! Kafka event publisher implementation
-
-use async_trait::async_trait;
-use rdkafka::config::ClientConfig;
-use rdkafka::producer::{{FutureProducer, FutureRecord}};
-use std::time::Duration;
-use tracing::error;
-
-use crate::application::EventPublisher;
-use crate::config::BrokerConfig;
-use crate::error::{pascal_name}Error;
-
-/// Kafka event publisher
-pub struct KafkaEventPublisher {{
-    producer: FutureProducer,
-}}
+        r#"-- Entities and the outbox their events are written to
 
-impl KafkaEventPublisher {{
-    pub async fn new(config: &BrokerConfig) -> Result<Self, {pascal_name}Error> {{
-        let producer: FutureProducer = ClientConfig::new()
-            .set("bootstrap.servers", &config.brokers)
-            .set("message.timeout.ms", "5000")
-            .create()
-            .map_err(|e| {pascal_name}Error::Broker(e.to_string()))?;
-
-        Ok(Self {{ producer }})
-    }}
-}}
-
-#[async_trait]
-impl EventPublisher for KafkaEventPublisher {{
-    async fn publish(&self, topic: &str, key: &str, payload: &str) -> Result<(), {pascal_name}Error> {{
-        let record = FutureRecord::to(topic)
-            .key(key)
-            .payload(payload);
-
-        self.producer
-            .send(record, Duration::from_secs(5))
-            .await
-            .map_err(|(e, _)| {{
-                error!("Failed to publish event: {{}}", e);
-                {pascal_name}Error::Broker(e.to_string())
-            }})?;
-
-        Ok(())
-    }}
-}}
-"#,
-        pascal_name = pascal_name,
-    )
-}
-
-/// Generate infrastructure/outbox_processor.rs
-pub fn infrastructure_outbox_processor(config: &ProjectConfig) -> String {
-    let producer = config.producer.as_ref().unwrap();
-    let pascal_name = to_pascal_case(&producer.service_name);
-
-    format!(
-        r#"//! Outbox processor for reliable event publishing
-
-use std::sync::Arc;
-use std::time::Duration;
-use tracing::{{error, info, warn}};
-
-use crate::application::{{EventPublisher, Outbox}};
-use crate::config::OutboxConfig;
-
-/// Outbox processor that polls and publishes pending events
-pub struct OutboxProcessor {{
-    outbox: Arc<dyn Outbox>,
-    publisher: Arc<dyn EventPublisher>,
-    config: OutboxConfig,
-}}
-
-impl OutboxProcessor {{
-    pub fn new(
-        outbox: Arc<dyn Outbox>,
-        publisher: Arc<dyn EventPublisher>,
-        config: OutboxConfig,
-    ) -> Self {{
-        Self {{
-            outbox,
-            publisher,
-            config,
-        }}
-    }}
-
-    pub async fn run(&self) {{
-        info!("Outbox processor started");
-
-        loop {{
-            match self.process_batch().await {{
-                Ok(count) => {{
-                    if count > 0 {{
-                        info!("Processed {{}} outbox entries", count);
-                    }}
-                }}
-                Err(e) => {{
-                    error!("Outbox processing error: {{}}", e);
-                }}
-            }}
-
-            tokio::time::sleep(Duration::from_millis(self.config.poll_interval_ms)).await;
-        }}
-    }}
-
-    async fn process_batch(&self) -> Result<usize, crate::error::{pascal_name}Error> {{
-        let entries = self.outbox.get_pending(self.config.batch_size).await?;
-        let count = entries.len();
-
-        for entry in entries {{
-            if entry.retries >= self.config.max_retries as i32 {{
-                warn!("Skipping entry {{}} after {{}} retries", entry.id, entry.retries);
-                continue;
-            }}
+CREATE TABLE IF NOT EXISTS {table_name} (
+    id UUID PRIMARY KEY,
+    name VARCHAR(255) NOT NULL,
+    data JSONB NOT NULL DEFAULT '{{}}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
 
-            match self.publisher
-                .publish(&entry.event_type, &entry.id.to_string(), &entry.payload)
-                .await
-            {{
-                Ok(_) => {{
-                    self.outbox.mark_processed(entry.id).await?;
-                }}
-                Err(e) => {{
-                    error!("Failed to publish entry {{}}: {{}}", entry.id, e);
-                    self.outbox.mark_failed(entry.id).await?;
-                }}
-            }}
-        }}
+CREATE TABLE IF NOT EXISTS outbox (
+    id UUID PRIMARY KEY,
+    topic VARCHAR(255) NOT NULL,
+    message_key TEXT,
+    payload BYTEA NOT NULL,
+    headers JSONB NOT NULL DEFAULT '[]',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    sent_at TIMESTAMPTZ,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT
+);
 
-        Ok(count)
-    }}
-}}
+CREATE INDEX IF NOT EXISTS idx_outbox_pending ON outbox (created_at) WHERE sent_at IS NULL;
 "#,
-        pascal_name = pascal_name,
+        table_name = producer.service_name.replace('-', "_"),
     )
 }
 
@@ -1290,7 +1197,7 @@ An event producer service built with AllFrame, featuring the transactional outbo
 
 ## Features
 
-- **Transactional Outbox**: Reliable event publishing with exactly-once semantics
+- **Transactional Outbox**: Events are stored in an outbox table and relayed to Kafka with at-least-once delivery
 - **REST API**: Full CRUD operations for entities
 - **PostgreSQL**: Persistent storage for entities and outbox
 - **Kafka**: Event streaming to downstream consumers
@@ -1329,28 +1236,8 @@ OUTBOX_MAX_RETRIES=3
 
 ## Database Setup
 
-Run the following SQL to create the required tables:
-
-```sql
-CREATE TABLE {table_name} (
-    id UUID PRIMARY KEY,
-    name VARCHAR(255) NOT NULL,
-    data JSONB NOT NULL DEFAULT '{{}}',
-    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
-    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
-);
-
-CREATE TABLE outbox (
-    id UUID PRIMARY KEY,
-    event_type VARCHAR(255) NOT NULL,
-    payload TEXT NOT NULL,
-    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
-    processed_at TIMESTAMPTZ,
-    retries INTEGER NOT NULL DEFAULT 0
-);
-
-CREATE INDEX idx_outbox_pending ON outbox (created_at) WHERE processed_at IS NULL;
-```
+The service applies the migrations in `migrations/` on startup, creating
+the entity table and the `outbox` table its events are written to.
 
 ## Running
 
@@ -1397,9 +1284,9 @@ cargo build --release
                           │
                           ▼
 ┌─────────────────────────────────────────────────────────────────┐
-│                    Outbox Processor                              │
+│                      Outbox Relay                                │
 │  ┌─────────────┐    ┌────────────────┐    ┌─────────────────┐  │
-│  │ Poll Outbox │───▶│ Event Publisher│───▶│      Kafka      │  │
+│  │ Poll Outbox │───▶│ Kafka Producer │───▶│      Kafka      │  │
 │  └─────────────┘    └────────────────┘    └─────────────────┘  │
 └─────────────────────────────────────────────────────────────────┘
 ```
@@ -1411,7 +1298,6 @@ MIT
         display_name = producer.display_name,
         name = name,
         db_name = name.replace('-', "_"),
-        table_name = producer.service_name.replace('-', "_"),
         route = producer.service_name.replace('_', "-"),
     )
 }
@@ -1440,8 +1326,9 @@ RUN mkdir src && echo "fn main() {{}}" > src/main.rs
 RUN cargo build --release
 RUN rm -rf src
 
-# Copy source
+# Copy source and migrations
 COPY src ./src
+COPY migrations ./migrations
 
 # Build
 RUN touch src/main.rs && cargo build --release
@@ -1468,6 +1355,22 @@ CMD ["/app/{name}"]
     )
 }
 
+/// Generate tests/integration.rs
+pub fn integration_tests(_config: &ProjectConfig) -> String {
+    format!(
+        "{}{}",
+        super::integration_header(&[
+            "config",
+            "error",
+            "domain",
+            "application",
+            "infrastructure",
+            "presentation"
+        ]),
+        super::INTEGRATION_HTTP_TESTS
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -32,7 +32,7 @@ description = "{display_name}"
 
 [dependencies]
 # AllFrame
-allframe-core = {{ version = "0.1", features = ["resilience", "otel"] }}
+allframe-core = {{ version = "0.1", features = ["resilience", "otel", "cqrs", "cqrs-sqlite"] }}
 
 # Web Framework
 axum = "0.7"
@@ -58,11 +58,15 @@ tracing-subscriber = {{ version = "0.3", features = ["env-filter"] }}
 opentelemetry = {{ version = "0.27", features = ["metrics"] }}
 
 # Utilities
-chrono = {{ version = "0.4", features = ["serde"] }}
 uuid = {{ version = "1.0", features = ["v4", "serde"] }}
-dashmap = "6.0"
 dotenvy = "0.15"
-backoff = {{ version = "0.4", features = ["tokio"] }}
+
+[features]
+default = ["otel"]
+# Logs, traces and metrics through allframe-core's observability stack,
+# exported over OTLP when OTEL_EXPORTER_OTLP_ENDPOINT is set. Without it,
+# logs go to stdout.
+otel = ["allframe-core/otel-otlp"]
 
 [dev-dependencies]
 tokio-test = "0.4"
@@ -88,6 +92,7 @@ pub fn main_rs(config: &ProjectConfig) -> String {
 //! A saga orchestrator service for coordinating distributed transactions.
 
 use std::sync::Arc;
+use allframe_core::cqrs::SqliteSagaStore;
 use tracing::info;
 
 mod config;
@@ -99,28 +104,36 @@ mod presentation;
 
 use config::Config;
 use application::{pascal_name}Orchestrator;
-use infrastructure::HealthServer;
+use infrastructure::{{HealthServer, InMemoryOrderServices}};
 
-#[tokio::main]
+{observability}#[tokio::main]
 async fn main() -> anyhow::Result<()> {{
     // Load environment variables
     dotenvy::dotenv().ok();
 
-    // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive(tracing::Level::INFO.into()),
-        )
-        .init();
+    // Initialize logging, tracing and metrics; the guard flushes them on exit
+    let _observability = init_observability()?;
 
     // Load configuration
     let config = Config::from_env();
     info!("Starting {display_name}");
     info!("Configured sagas: {{:?}}", config.sagas.iter().map(|s| &s.name).collect::<Vec<_>>());
 
-    // Create orchestrator
-    let orchestrator = Arc::new({pascal_name}Orchestrator::new(config.clone()));
+    // Saga progress is persisted so interrupted sagas survive a restart
+    let store = SqliteSagaStore::new(&config.store_path)
+        .await
+        .map_err(anyhow::Error::msg)?;
+
+    // Stand-in for the inventory and order services; replace with clients
+    // for the real ones
+    let services = Arc::new(InMemoryOrderServices::with_stock([("widget", 100)]));
+
+    // Create orchestrator and finish sagas a previous run left behind
+    let orchestrator = Arc::new({pascal_name}Orchestrator::new(&config, store, services));
+    let resumed = orchestrator.resume_incomplete().await?;
+    if resumed > 0 {{
+        info!("Resumed {{}} interrupted saga(s)", resumed);
+    }}
 
     // Start health server in background
     let health_port = config.server.health_port;
@@ -143,6 +156,7 @@ async fn main() -> anyhow::Result<()> {{
     Ok(())
 }}
 "#,
+        observability = super::OBSERVABILITY_INIT,
         pascal_name = pascal_name,
         display_name = saga.display_name,
     )
@@ -177,6 +191,9 @@ pub fn config_rs(config: &ProjectConfig) -> String {
         r##"//! Service configuration
 
 use std::env;
+use std::time::Duration;
+
+use allframe_core::cqrs::StepPolicy;
 
 /// Main configuration
 #[derive(Debug, Clone)]
@@ -184,6 +201,8 @@ pub struct Config {{
     pub server: ServerConfig,
     pub sagas: Vec<SagaConfig>,
     pub retry: RetryConfig,
+    /// SQLite database holding saga progress
+    pub store_path: String,
 }}
 
 /// Server configuration
@@ -194,6 +213,9 @@ pub struct ServerConfig {{
 }}
 
 /// Saga configuration
+///
+/// Steps run in order; each name must match a step in
+/// `application::steps::add_step`.
 #[derive(Debug, Clone)]
 pub struct SagaConfig {{
     pub name: String,
@@ -201,7 +223,7 @@ pub struct SagaConfig {{
     pub steps: Vec<String>,
 }}
 
-/// Retry configuration
+/// Retry configuration for saga steps
 #[derive(Debug, Clone)]
 pub struct RetryConfig {{
     pub max_attempts: u32,
@@ -209,6 +231,16 @@ pub struct RetryConfig {{
     pub max_backoff_ms: u64,
 }}
 
+impl RetryConfig {{
+    /// The retry policy applied to every saga step
+    pub fn step_policy(&self) -> StepPolicy {{
+        StepPolicy::new()
+            .with_max_retries(self.max_attempts.saturating_sub(1))
+            .with_initial_interval(Duration::from_millis(self.initial_backoff_ms))
+            .with_max_interval(Duration::from_millis(self.max_backoff_ms))
+    }}
+}}
+
 impl Config {{
     pub fn from_env() -> Self {{
         Self {{
@@ -239,6 +271,7 @@ impl Config {{
                     .parse()
                     .expect("MAX_BACKOFF_MS must be a number"),
             }},
+            store_path: env::var("SAGA_STORE_PATH").unwrap_or_else(|_| "sagas.db".to_string()),
         }}
     }}
 }}
@@ -268,23 +301,17 @@ pub enum {pascal_name}Error {{
     #[error("Saga not found: {{0}}")]
     SagaNotFound(String),
 
-    #[error("Saga execution failed at step {{step}}: {{reason}}")]
-    ExecutionFailed {{ step: String, reason: String }},
-
-    #[error("Compensation failed at step {{step}}: {{reason}}")]
-    CompensationFailed {{ step: String, reason: String }},
+    #[error("Unknown saga step: {{0}}")]
+    UnknownStep(String),
 
-    #[error("Saga already completed: {{0}}")]
-    AlreadyCompleted(String),
+    #[error("Invalid saga payload: {{0}}")]
+    InvalidPayload(String),
 
-    #[error("Invalid state transition: {{0}}")]
-    InvalidStateTransition(String),
+    #[error("Saga failed: {{0}}")]
+    Saga(String),
 
-    #[error("Step timeout: {{0}}")]
-    StepTimeout(String),
-
-    #[error("Internal error: {{0}}")]
-    Internal(String),
+    #[error("Saga store error: {{0}}")]
+    Store(String),
 }}
 "#,
         pascal_name = pascal_name,
@@ -295,184 +322,72 @@ pub enum {pascal_name}Error {{
 pub fn domain_mod(_config: &ProjectConfig) -> String {
     r#"//! Domain layer
 
-pub mod saga;
-pub mod step;
+pub mod order;
 
-pub use saga::*;
-pub use step::*;
+pub use order::*;
 "#
     .to_string()
 }
 
-/// Generate domain/saga.rs
-pub fn domain_saga(config: &ProjectConfig) -> String {
-    let saga = config.saga_orchestrator.as_ref().unwrap();
-    let pascal_name = to_pascal_case(&saga.service_name);
-
-    format!(
-        r#"//! Saga execution types
-
-use chrono::{{DateTime, Utc}};
-use serde::{{Deserialize, Serialize}};
-use uuid::Uuid;
-
-use crate::error::{pascal_name}Error;
-
-/// Saga execution status
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum SagaStatus {{
-    /// Saga is being executed
-    Running,
-    /// Saga completed successfully
-    Completed,
-    /// Saga failed and is compensating
-    Compensating,
-    /// Saga was rolled back successfully
-    Compensated,
-    /// Saga failed permanently
-    Failed,
-}}
-
-/// Saga execution instance
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct SagaExecution {{
-    pub id: Uuid,
-    pub saga_name: String,
-    pub status: SagaStatus,
-    pub current_step: usize,
-    pub steps: Vec<StepExecution>,
-    pub payload: serde_json::Value,
-    pub started_at: DateTime<Utc>,
-    pub completed_at: Option<DateTime<Utc>>,
-    pub error: Option<String>,
-}}
+/// Generate domain/order.rs
+pub fn domain_order(_config: &ProjectConfig) -> String {
+    r#"//! Order domain for the example saga
 
-/// Individual step execution
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct StepExecution {{
-    pub name: String,
-    pub status: StepStatus,
-    pub started_at: Option<DateTime<Utc>>,
-    pub completed_at: Option<DateTime<Utc>>,
-    pub result: Option<serde_json::Value>,
-    pub error: Option<String>,
-}}
-
-/// Step execution status
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum StepStatus {{
-    Pending,
-    Running,
-    Completed,
-    Failed,
-    Compensating,
-    Compensated,
-}}
-
-impl SagaExecution {{
-    pub fn new(saga_name: String, steps: Vec<String>, payload: serde_json::Value) -> Self {{
-        Self {{
-            id: Uuid::new_v4(),
-            saga_name,
-            status: SagaStatus::Running,
-            current_step: 0,
-            steps: steps
-                .into_iter()
-                .map(|name| StepExecution {{
-                    name,
-                    status: StepStatus::Pending,
-                    started_at: None,
-                    completed_at: None,
-                    result: None,
-                    error: None,
-                }})
-                .collect(),
-            payload,
-            started_at: Utc::now(),
-            completed_at: None,
-            error: None,
-        }}
-    }}
-
-    pub fn start_step(&mut self) -> Result<&mut StepExecution, {pascal_name}Error> {{
-        if self.current_step >= self.steps.len() {{
-            return Err({pascal_name}Error::InvalidStateTransition(
-                "No more steps to execute".to_string(),
-            ));
-        }}
-
-        let step = &mut self.steps[self.current_step];
-        step.status = StepStatus::Running;
-        step.started_at = Some(Utc::now());
-        Ok(step)
-    }}
-
-    pub fn complete_step(&mut self, result: serde_json::Value) {{
-        if let Some(step) = self.steps.get_mut(self.current_step) {{
-            step.status = StepStatus::Completed;
-            step.completed_at = Some(Utc::now());
-            step.result = Some(result);
-            self.current_step += 1;
-
-            // Check if saga is complete
-            if self.current_step >= self.steps.len() {{
-                self.status = SagaStatus::Completed;
-                self.completed_at = Some(Utc::now());
-            }}
-        }}
-    }}
-
-    pub fn fail_step(&mut self, error: String) {{
-        if let Some(step) = self.steps.get_mut(self.current_step) {{
-            step.status = StepStatus::Failed;
-            step.completed_at = Some(Utc::now());
-            step.error = Some(error.clone());
-        }}
-        self.status = SagaStatus::Compensating;
-        self.error = Some(error);
-    }}
-
-    pub fn is_complete(&self) -> bool {{
-        matches!(
-            self.status,
-            SagaStatus::Completed | SagaStatus::Compensated | SagaStatus::Failed
-        )
-    }}
-}}
-"#,
-        pascal_name = pascal_name,
-    )
+use allframe_core::cqrs::{Event, EventTypeName};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// An order to reserve stock for and confirm
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderRequest {
+    pub order_id: String,
+    pub sku: String,
+    pub quantity: u32,
 }
 
-/// Generate domain/step.rs
-pub fn domain_step(config: &ProjectConfig) -> String {
-    let saga = config.saga_orchestrator.as_ref().unwrap();
-    let pascal_name = to_pascal_case(&saga.service_name);
-
-    format!(
-        r#"//! Saga step definitions
-
-use async_trait::async_trait;
+/// Events produced by the order saga's steps
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OrderEvent {
+    StockReserved {
+        order_id: String,
+        sku: String,
+        quantity: u32,
+    },
+    StockReleased {
+        order_id: String,
+    },
+    OrderConfirmed {
+        order_id: String,
+    },
+    OrderCancelled {
+        order_id: String,
+    },
+}
 
-use crate::error::{pascal_name}Error;
+impl EventTypeName for OrderEvent {}
+impl Event for OrderEvent {}
 
-/// Trait for saga steps
+/// The services the order saga coordinates
+///
+/// A resumed saga may repeat a call that succeeded before a crash, so every
+/// operation must be idempotent per order.
 #[async_trait]
-pub trait SagaStep: Send + Sync {{
-    /// Step name
-    fn name(&self) -> &str;
+pub trait OrderServices: Send + Sync {
+    /// Reserve stock for an order
+    async fn reserve_stock(&self, order: &OrderRequest) -> Result<(), String>;
 
-    /// Execute the step
-    async fn execute(&self, payload: &serde_json::Value) -> Result<serde_json::Value, {pascal_name}Error>;
+    /// Release an order's reserved stock
+    async fn release_stock(&self, order: &OrderRequest) -> Result<(), String>;
 
-    /// Compensate (undo) the step
-    async fn compensate(&self, payload: &serde_json::Value) -> Result<(), {pascal_name}Error>;
-}}
-"#,
-        pascal_name = pascal_name,
-    )
+    /// Confirm an order whose stock is reserved
+    async fn confirm_order(&self, order: &OrderRequest) -> Result<(), String>;
+
+    /// Cancel a confirmed order
+    async fn cancel_order(&self, order: &OrderRequest) -> Result<(), String>;
+}
+"#
+    .to_string()
 }
 
 /// Generate application/mod.rs
@@ -494,230 +409,155 @@ pub fn application_orchestrator(config: &ProjectConfig) -> String {
 
     format!(
         r#"//! Saga orchestrator
+//!
+//! Runs the sagas defined in [`Config`] on AllFrame's `SagaOrchestrator`,
+//! which persists each saga's progress to a `SagaStore`. A saga's record
+//! carries `{{"saga": <name>, "payload": <payload>}}` as its context, so
+//! sagas interrupted by a restart can be rebuilt and resumed.
 
-use std::collections::HashMap;
 use std::sync::Arc;
-use dashmap::DashMap;
-use tracing::{{info, error, debug}};
+
+use allframe_core::cqrs::{{
+    SagaDefinition, SagaOrchestrator, SagaRecord, SagaStore, StepPolicy,
+}};
+use tracing::{{error, info}};
 use uuid::Uuid;
 
-use crate::config::Config;
-use crate::domain::{{SagaExecution, SagaStatus, SagaStep, StepStatus}};
-use crate::error::{pascal_name}Error;
 use crate::application::steps;
+use crate::config::{{Config, SagaConfig}};
+use crate::domain::{{OrderEvent, OrderRequest, OrderServices}};
+use crate::error::{pascal_name}Error;
 
 /// Saga orchestrator managing distributed transactions
+#[derive(Clone)]
 pub struct {pascal_name}Orchestrator {{
-    config: Config,
-    /// Running saga executions
-    executions: DashMap<Uuid, SagaExecution>,
-    /// Step implementations by saga name and step name
-    steps: HashMap<String, HashMap<String, Arc<dyn SagaStep>>>,
+    orchestrator: SagaOrchestrator<OrderEvent>,
+    store: Arc<dyn SagaStore>,
+    services: Arc<dyn OrderServices>,
+    sagas: Vec<SagaConfig>,
+    policy: StepPolicy,
 }}
 
 impl {pascal_name}Orchestrator {{
-    pub fn new(config: Config) -> Self {{
-        let mut steps_map = HashMap::new();
-
-        // Register step implementations for each saga
-        for saga_config in &config.sagas {{
-            let saga_steps = steps::create_steps(&saga_config.name, &saga_config.steps);
-            steps_map.insert(saga_config.name.clone(), saga_steps);
-        }}
-
+    pub fn new<S: SagaStore + 'static>(
+        config: &Config,
+        store: S,
+        services: Arc<dyn OrderServices>,
+    ) -> Self {{
+        let store: Arc<dyn SagaStore> = Arc::new(store);
         Self {{
-            config,
-            executions: DashMap::new(),
-            steps: steps_map,
+            orchestrator: SagaOrchestrator::new().with_store(Arc::clone(&store)),
+            store,
+            services,
+            sagas: config.sagas.clone(),
+            policy: config.retry.step_policy(),
         }}
     }}
 
-    /// Start a new saga execution
-    pub async fn start_saga(
+    /// Start a saga in the background, returning its ID
+    pub fn start_saga(
         &self,
         saga_name: &str,
         payload: serde_json::Value,
-    ) -> Result<Uuid, {pascal_name}Error> {{
-        let saga_config = self
-            .config
-            .sagas
-            .iter()
-            .find(|s| s.name == saga_name)
-            .ok_or_else(|| {pascal_name}Error::SagaNotFound(saga_name.to_string()))?;
-
-        let execution = SagaExecution::new(
-            saga_name.to_string(),
-            saga_config.steps.clone(),
-            payload,
-        );
-        let saga_id = execution.id;
-
-        self.executions.insert(saga_id, execution);
+    ) -> Result<String, {pascal_name}Error> {{
+        let saga_id = Uuid::new_v4().to_string();
+        // Fail fast on an unknown saga or invalid payload
+        self.definition(&saga_id, saga_name, &payload)?;
         info!(saga_id = %saga_id, saga = saga_name, "Started saga execution");
 
-        // Start execution in background
-        let orchestrator = self.clone_inner(saga_id);
+        let orchestrator = self.clone();
+        let (id, name) = (saga_id.clone(), saga_name.to_string());
         tokio::spawn(async move {{
-            if let Err(e) = orchestrator.run_saga(saga_id).await {{
-                error!(saga_id = %saga_id, error = %e, "Saga execution failed");
-            }}
+            log_outcome(&id, &orchestrator.run_saga(&id, &name, payload).await);
         }});
 
         Ok(saga_id)
     }}
 
-    fn clone_inner(&self, _saga_id: Uuid) -> SagaRunner {{
-        SagaRunner {{
-            executions: self.executions.clone(),
-            steps: self.steps.clone(),
-        }}
+    /// Run a saga until it completes or has been compensated
+    pub async fn run_saga(
+        &self,
+        saga_id: &str,
+        saga_name: &str,
+        payload: serde_json::Value,
+    ) -> Result<Vec<OrderEvent>, {pascal_name}Error> {{
+        let definition = self.definition(saga_id, saga_name, &payload)?;
+        self.orchestrator
+            .execute(definition)
+            .await
+            .map_err(|e| {pascal_name}Error::Saga(e.to_string()))
     }}
 
-    /// Get saga execution status
-    pub fn get_execution(&self, saga_id: Uuid) -> Option<SagaExecution> {{
-        self.executions.get(&saga_id).map(|e| e.clone())
+    /// Resume the sagas a previous run left executing or compensating,
+    /// returning how many there were
+    pub async fn resume_incomplete(&self) -> Result<usize, {pascal_name}Error> {{
+        let records = self
+            .orchestrator
+            .incomplete_sagas()
+            .await
+            .map_err(|e| {pascal_name}Error::Store(e.to_string()))?;
+
+        for record in &records {{
+            let saga_name = record.context["saga"].as_str().unwrap_or_default();
+            info!(saga_id = %record.id, saga = saga_name, "Resuming saga");
+            match self.definition(&record.id, saga_name, &record.context["payload"]) {{
+                Ok(definition) => log_outcome(&record.id, &self.orchestrator.resume(definition).await),
+                Err(e) => error!(saga_id = %record.id, error = %e, "Cannot resume saga"),
+            }}
+        }}
+        Ok(records.len())
     }}
 
-    /// List all executions
-    pub fn list_executions(&self) -> Vec<SagaExecution> {{
-        self.executions.iter().map(|e| e.value().clone()).collect()
+    /// Get a saga's persisted progress
+    pub async fn get_execution(&self, saga_id: &str) -> Result<Option<SagaRecord>, {pascal_name}Error> {{
+        self.store
+            .load(saga_id)
+            .await
+            .map_err({pascal_name}Error::Store)
     }}
 
-    /// Get configured sagas
-    pub fn get_sagas(&self) -> Vec<&crate::config::SagaConfig> {{
-        self.config.sagas.iter().collect()
+    /// List the sagas that are still executing or compensating
+    pub async fn list_in_progress(&self) -> Result<Vec<SagaRecord>, {pascal_name}Error> {{
+        self.store
+            .incomplete()
+            .await
+            .map_err({pascal_name}Error::Store)
     }}
-}}
-
-/// Runner for saga execution
-struct SagaRunner {{
-    executions: DashMap<Uuid, SagaExecution>,
-    steps: HashMap<String, HashMap<String, Arc<dyn SagaStep>>>,
-}}
-
-impl SagaRunner {{
-    async fn run_saga(&self, saga_id: Uuid) -> Result<(), {pascal_name}Error> {{
-        loop {{
-            let execution = self
-                .executions
-                .get(&saga_id)
-                .ok_or_else(|| {pascal_name}Error::SagaNotFound(saga_id.to_string()))?
-                .clone();
-
-            if execution.is_complete() {{
-                break;
-            }}
 
-            match execution.status {{
-                SagaStatus::Running => {{
-                    self.execute_next_step(saga_id, &execution).await?;
-                }}
-                SagaStatus::Compensating => {{
-                    self.compensate_steps(saga_id, &execution).await?;
-                }}
-                _ => break,
-            }}
-        }}
-
-        Ok(())
+    /// Get configured sagas
+    pub fn get_sagas(&self) -> &[SagaConfig] {{
+        &self.sagas
     }}
 
-    async fn execute_next_step(
+    /// Build the steps of a configured saga for one order
+    fn definition(
         &self,
-        saga_id: Uuid,
-        execution: &SagaExecution,
-    ) -> Result<(), {pascal_name}Error> {{
-        let step_name = &execution.steps[execution.current_step].name;
-        let saga_steps = self
-            .steps
-            .get(&execution.saga_name)
-            .ok_or_else(|| {pascal_name}Error::SagaNotFound(execution.saga_name.clone()))?;
-
-        let step = saga_steps
-            .get(step_name)
-            .ok_or_else(|| {pascal_name}Error::SagaNotFound(step_name.clone()))?;
-
-        debug!(saga_id = %saga_id, step = step_name, "Executing step");
-
-        // Mark step as running
-        if let Some(mut exec) = self.executions.get_mut(&saga_id) {{
-            let _ = exec.start_step();
-        }}
-
-        // Execute step
-        match step.execute(&execution.payload).await {{
-            Ok(result) => {{
-                if let Some(mut exec) = self.executions.get_mut(&saga_id) {{
-                    exec.complete_step(result);
-                }}
-                info!(saga_id = %saga_id, step = step_name, "Step completed");
-            }}
-            Err(e) => {{
-                if let Some(mut exec) = self.executions.get_mut(&saga_id) {{
-                    exec.fail_step(e.to_string());
-                }}
-                error!(saga_id = %saga_id, step = step_name, error = %e, "Step failed");
-            }}
-        }}
-
-        Ok(())
+        saga_id: &str,
+        saga_name: &str,
+        payload: &serde_json::Value,
+    ) -> Result<SagaDefinition<OrderEvent>, {pascal_name}Error> {{
+        let saga = self
+            .sagas
+            .iter()
+            .find(|s| s.name == saga_name)
+            .ok_or_else(|| {pascal_name}Error::SagaNotFound(saga_name.to_string()))?;
+        let order: OrderRequest = serde_json::from_value(payload.clone())
+            .map_err(|e| {pascal_name}Error::InvalidPayload(e.to_string()))?;
+
+        let context = serde_json::json!({{ "saga": saga_name, "payload": payload }});
+        saga.steps.iter().try_fold(
+            SagaDefinition::new(saga_id).with_context(context),
+            |definition, step| {{
+                steps::add_step(definition, step, &self.services, &order, self.policy.clone())
+            }},
+        )
     }}
+}}
 
-    async fn compensate_steps(
-        &self,
-        saga_id: Uuid,
-        execution: &SagaExecution,
-    ) -> Result<(), {pascal_name}Error> {{
-        let saga_steps = self
-            .steps
-            .get(&execution.saga_name)
-            .ok_or_else(|| {pascal_name}Error::SagaNotFound(execution.saga_name.clone()))?;
-
-        // Compensate completed steps in reverse order
-        for i in (0..execution.current_step).rev() {{
-            let step_exec = &execution.steps[i];
-            if step_exec.status == StepStatus::Completed {{
-                let step_name = &step_exec.name;
-                if let Some(step) = saga_steps.get(step_name) {{
-                    debug!(saga_id = %saga_id, step = step_name, "Compensating step");
-
-                    // Mark step as compensating
-                    if let Some(mut exec) = self.executions.get_mut(&saga_id) {{
-                        exec.steps[i].status = StepStatus::Compensating;
-                    }}
-
-                    match step.compensate(&execution.payload).await {{
-                        Ok(()) => {{
-                            if let Some(mut exec) = self.executions.get_mut(&saga_id) {{
-                                exec.steps[i].status = StepStatus::Compensated;
-                            }}
-                            info!(saga_id = %saga_id, step = step_name, "Step compensated");
-                        }}
-                        Err(e) => {{
-                            error!(
-                                saga_id = %saga_id,
-                                step = step_name,
-                                error = %e,
-                                "Compensation failed"
-                            );
-                            // Mark saga as failed if compensation fails
-                            if let Some(mut exec) = self.executions.get_mut(&saga_id) {{
-                                exec.status = SagaStatus::Failed;
-                            }}
-                            return Err(e);
-                        }}
-                    }}
-                }}
-            }}
-        }}
-
-        // Mark saga as compensated
-        if let Some(mut exec) = self.executions.get_mut(&saga_id) {{
-            exec.status = SagaStatus::Compensated;
-            exec.completed_at = Some(chrono::Utc::now());
-        }}
-
-        Ok(())
+fn log_outcome<E: std::fmt::Display>(saga_id: &str, result: &Result<Vec<OrderEvent>, E>) {{
+    match result {{
+        Ok(events) => info!(saga_id = %saga_id, events = events.len(), "Saga completed"),
+        Err(e) => error!(saga_id = %saga_id, error = %e, "Saga failed"),
     }}
 }}
 "#,
@@ -730,122 +570,95 @@ pub fn application_steps(config: &ProjectConfig) -> String {
     let saga = config.saga_orchestrator.as_ref().unwrap();
     let pascal_name = to_pascal_case(&saga.service_name);
 
-    // Generate step implementations for the default saga
-    let step_impls: Vec<String> = saga
-        .sagas
-        .first()
-        .map(|s| {
-            s.steps
-                .iter()
-                .map(|step| {
-                    let step_pascal = to_pascal_case(step);
-                    format!(
-                        r#"/// {step} step implementation
-pub struct {step_pascal}Step;
-
-#[async_trait]
-impl SagaStep for {step_pascal}Step {{
-    fn name(&self) -> &str {{
-        "{step}"
-    }}
-
-    async fn execute(&self, payload: &serde_json::Value) -> Result<serde_json::Value, {pascal_name}Error> {{
-        info!(step = "{step}", "Executing step");
-        // TODO: Implement actual step logic
-        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
-        Ok(serde_json::json!({{ "step": "{step}", "status": "completed" }}))
-    }}
-
-    async fn compensate(&self, payload: &serde_json::Value) -> Result<(), {pascal_name}Error> {{
-        info!(step = "{step}", "Compensating step");
-        // TODO: Implement compensation logic
-        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
-        Ok(())
-    }}
-}}"#,
-                        step = step,
-                        step_pascal = step_pascal,
-                        pascal_name = pascal_name,
-                    )
-                })
-                .collect()
-        })
-        .unwrap_or_default();
-
-    let step_registrations: Vec<String> = saga
-        .sagas
-        .first()
-        .map(|s| {
-            s.steps
-                .iter()
-                .map(|step| {
-                    let step_pascal = to_pascal_case(step);
-                    format!(
-                        r#"            steps.insert("{step}".to_string(), Arc::new({step_pascal}Step) as Arc<dyn SagaStep>);"#,
-                        step = step,
-                        step_pascal = step_pascal,
-                    )
-                })
-                .collect()
-        })
-        .unwrap_or_default();
-
     format!(
         r#"//! Saga step implementations
+//!
+//! Each step calls the order services and undoes its effect when a later
+//! step fails. A step's error message is what the saga reports as the
+//! failure.
 
-use std::collections::HashMap;
 use std::sync::Arc;
+
+use allframe_core::cqrs::{{OrchestratorSagaStep, SagaDefinition, StepPolicy}};
 use async_trait::async_trait;
-use tracing::info;
 
-use crate::domain::SagaStep;
+use crate::domain::{{OrderEvent, OrderRequest, OrderServices}};
 use crate::error::{pascal_name}Error;
 
-/// Create step implementations for a saga
-pub fn create_steps(saga_name: &str, step_names: &[String]) -> HashMap<String, Arc<dyn SagaStep>> {{
-    let mut steps: HashMap<String, Arc<dyn SagaStep>> = HashMap::new();
+/// Add the step called `name` to a saga
+pub fn add_step(
+    definition: SagaDefinition<OrderEvent>,
+    name: &str,
+    services: &Arc<dyn OrderServices>,
+    order: &OrderRequest,
+    policy: StepPolicy,
+) -> Result<SagaDefinition<OrderEvent>, {pascal_name}Error> {{
+    let services = Arc::clone(services);
+    let order = order.clone();
+    Ok(match name {{
+        "reserve_stock" => definition.add_step_with(ReserveStock {{ services, order }}, policy),
+        "confirm_order" => definition.add_step_with(ConfirmOrder {{ services, order }}, policy),
+        other => return Err({pascal_name}Error::UnknownStep(other.to_string())),
+    }})
+}}
 
-    match saga_name {{
-        "{default_saga}" => {{
-{step_registrations}
-        }}
-        _ => {{
-            // Unknown saga - create placeholder steps
-            for step_name in step_names {{
-                steps.insert(step_name.clone(), Arc::new(PlaceholderStep(step_name.clone())) as Arc<dyn SagaStep>);
-            }}
-        }}
+/// Reserves the order's stock; compensated by releasing it
+pub struct ReserveStock {{
+    services: Arc<dyn OrderServices>,
+    order: OrderRequest,
+}}
+
+#[async_trait]
+impl OrchestratorSagaStep<OrderEvent> for ReserveStock {{
+    async fn execute(&self) -> Result<Vec<OrderEvent>, String> {{
+        self.services.reserve_stock(&self.order).await?;
+        Ok(vec![OrderEvent::StockReserved {{
+            order_id: self.order.order_id.clone(),
+            sku: self.order.sku.clone(),
+            quantity: self.order.quantity,
+        }}])
     }}
 
-    steps
-}}
+    async fn compensate(&self) -> Result<Vec<OrderEvent>, String> {{
+        self.services.release_stock(&self.order).await?;
+        Ok(vec![OrderEvent::StockReleased {{
+            order_id: self.order.order_id.clone(),
+        }}])
+    }}
 
-{step_impls}
+    fn name(&self) -> &str {{
+        "reserve_stock"
+    }}
+}}
 
-/// Placeholder step for undefined sagas
-struct PlaceholderStep(String);
+/// Confirms the order; compensated by cancelling it
+pub struct ConfirmOrder {{
+    services: Arc<dyn OrderServices>,
+    order: OrderRequest,
+}}
 
 #[async_trait]
-impl SagaStep for PlaceholderStep {{
-    fn name(&self) -> &str {{
-        &self.0
+impl OrchestratorSagaStep<OrderEvent> for ConfirmOrder {{
+    async fn execute(&self) -> Result<Vec<OrderEvent>, String> {{
+        self.services.confirm_order(&self.order).await?;
+        Ok(vec![OrderEvent::OrderConfirmed {{
+            order_id: self.order.order_id.clone(),
+        }}])
     }}
 
-    async fn execute(&self, _payload: &serde_json::Value) -> Result<serde_json::Value, {pascal_name}Error> {{
-        info!(step = %self.0, "Executing placeholder step");
-        Ok(serde_json::json!({{ "step": self.0, "status": "completed" }}))
+    async fn compensate(&self) -> Result<Vec<OrderEvent>, String> {{
+        self.services.cancel_order(&self.order).await?;
+        Ok(vec![OrderEvent::OrderCancelled {{
+            order_id: self.order.order_id.clone(),
+        }}])
     }}
 
-    async fn compensate(&self, _payload: &serde_json::Value) -> Result<(), {pascal_name}Error> {{
-        info!(step = %self.0, "Compensating placeholder step");
-        Ok(())
+    fn name(&self) -> &str {{
+        "confirm_order"
     }}
 }}
 "#,
         pascal_name = pascal_name,
-        default_saga = saga.sagas.first().map(|s| s.name.as_str()).unwrap_or(""),
-        step_registrations = step_registrations.join("\n"),
-        step_impls = step_impls.join("\n\n"),
     )
 }
 
@@ -854,8 +667,10 @@ pub fn infrastructure_mod(_config: &ProjectConfig) -> String {
     r#"//! Infrastructure layer
 
 pub mod health;
+pub mod order_services;
 
 pub use health::*;
+pub use order_services::*;
 "#
     .to_string()
 }
@@ -901,6 +716,104 @@ async fn ready() -> &'static str {
     .to_string()
 }
 
+/// Generate infrastructure/order_services.rs
+pub fn infrastructure_order_services(_config: &ProjectConfig) -> String {
+    r#"//! In-memory order services
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+
+use crate::domain::{OrderRequest, OrderServices};
+
+/// In-memory stand-in for the inventory and order services
+///
+/// Cloning shares the underlying state.
+#[derive(Clone, Default)]
+pub struct InMemoryOrderServices {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Default)]
+struct State {
+    /// Available stock by SKU
+    stock: HashMap<String, u32>,
+    /// Reserved (SKU, quantity) by order ID
+    reserved: HashMap<String, (String, u32)>,
+    confirmed: HashSet<String>,
+}
+
+impl InMemoryOrderServices {
+    /// Create services with the given available stock by SKU
+    pub fn with_stock<S: Into<String>>(stock: impl IntoIterator<Item = (S, u32)>) -> Self {
+        let services = Self::default();
+        services.state.lock().unwrap().stock = stock
+            .into_iter()
+            .map(|(sku, quantity)| (sku.into(), quantity))
+            .collect();
+        services
+    }
+}
+
+// Inspection helpers, used by the integration tests
+#[allow(dead_code)]
+impl InMemoryOrderServices {
+    /// Available stock of a SKU
+    pub fn available(&self, sku: &str) -> u32 {
+        self.state.lock().unwrap().stock.get(sku).copied().unwrap_or(0)
+    }
+
+    /// Whether an order has been confirmed
+    pub fn is_confirmed(&self, order_id: &str) -> bool {
+        self.state.lock().unwrap().confirmed.contains(order_id)
+    }
+}
+
+#[async_trait]
+impl OrderServices for InMemoryOrderServices {
+    async fn reserve_stock(&self, order: &OrderRequest) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap();
+        if state.reserved.contains_key(&order.order_id) {
+            return Ok(());
+        }
+        let available = state.stock.entry(order.sku.clone()).or_default();
+        if *available < order.quantity {
+            return Err(format!("Insufficient stock of {}", order.sku));
+        }
+        *available -= order.quantity;
+        state
+            .reserved
+            .insert(order.order_id.clone(), (order.sku.clone(), order.quantity));
+        Ok(())
+    }
+
+    async fn release_stock(&self, order: &OrderRequest) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap();
+        if let Some((sku, quantity)) = state.reserved.remove(&order.order_id) {
+            *state.stock.entry(sku).or_default() += quantity;
+        }
+        Ok(())
+    }
+
+    async fn confirm_order(&self, order: &OrderRequest) -> Result<(), String> {
+        let mut state = self.state.lock().unwrap();
+        if !state.reserved.contains_key(&order.order_id) {
+            return Err(format!("No stock reserved for order {}", order.order_id));
+        }
+        state.confirmed.insert(order.order_id.clone());
+        Ok(())
+    }
+
+    async fn cancel_order(&self, order: &OrderRequest) -> Result<(), String> {
+        self.state.lock().unwrap().confirmed.remove(&order.order_id);
+        Ok(())
+    }
+}
+"#
+    .to_string()
+}
+
 /// Generate presentation/mod.rs
 pub fn presentation_mod(_config: &ProjectConfig) -> String {
     r#"//! Presentation layer
@@ -929,10 +842,8 @@ use axum::{{
     Json, Router,
 }};
 use serde::{{Deserialize, Serialize}};
-use uuid::Uuid;
 
 use crate::application::{pascal_name}Orchestrator;
-use crate::domain::SagaExecution;
 
 type AppState = Arc<{pascal_name}Orchestrator>;
 
@@ -953,7 +864,7 @@ struct StartSagaRequest {{
 
 #[derive(Debug, Serialize)]
 struct StartSagaResponse {{
-    saga_id: Uuid,
+    saga_id: String,
 }}
 
 #[derive(Debug, Serialize)]
@@ -963,10 +874,14 @@ struct SagaInfo {{
     steps: Vec<String>,
 }}
 
+fn error_response(status: StatusCode, error: impl ToString) -> axum::response::Response {{
+    (status, Json(serde_json::json!({{ "error": error.to_string() }}))).into_response()
+}}
+
 async fn list_sagas(State(orchestrator): State<AppState>) -> Json<Vec<SagaInfo>> {{
     let sagas = orchestrator
         .get_sagas()
-        .into_iter()
+        .iter()
         .map(|s| SagaInfo {{
             name: s.name.clone(),
             description: s.description.clone(),
@@ -981,35 +896,31 @@ async fn start_saga(
     Path(saga_name): Path<String>,
     Json(request): Json<StartSagaRequest>,
 ) -> impl IntoResponse {{
-    match orchestrator.start_saga(&saga_name, request.payload).await {{
+    match orchestrator.start_saga(&saga_name, request.payload) {{
         Ok(saga_id) => (
             StatusCode::ACCEPTED,
             Json(StartSagaResponse {{ saga_id }}),
         )
             .into_response(),
-        Err(e) => (
-            StatusCode::BAD_REQUEST,
-            Json(serde_json::json!({{ "error": e.to_string() }})),
-        )
-            .into_response(),
+        Err(e) => error_response(StatusCode::BAD_REQUEST, e),
     }}
 }}
 
-async fn list_executions(State(orchestrator): State<AppState>) -> Json<Vec<SagaExecution>> {{
-    Json(orchestrator.list_executions())
+async fn list_executions(State(orchestrator): State<AppState>) -> impl IntoResponse {{
+    match orchestrator.list_in_progress().await {{
+        Ok(records) => Json(records).into_response(),
+        Err(e) => error_response(StatusCode::INTERNAL_SERVER_ERROR, e),
+    }}
 }}
 
 async fn get_execution(
     State(orchestrator): State<AppState>,
-    Path(id): Path<Uuid>,
+    Path(id): Path<String>,
 ) -> impl IntoResponse {{
-    match orchestrator.get_execution(id) {{
-        Some(execution) => (StatusCode::OK, Json(execution)).into_response(),
-        None => (
-            StatusCode::NOT_FOUND,
-            Json(serde_json::json!({{ "error": "Saga execution not found" }})),
-        )
-            .into_response(),
+    match orchestrator.get_execution(&id).await {{
+        Ok(Some(record)) => Json(record).into_response(),
+        Ok(None) => error_response(StatusCode::NOT_FOUND, "Saga execution not found"),
+        Err(e) => error_response(StatusCode::INTERNAL_SERVER_ERROR, e),
     }}
 }}
 "#,
@@ -1043,8 +954,9 @@ A saga orchestrator service built with AllFrame for coordinating distributed tra
 ## Features
 
 - **Saga Pattern**: Orchestration-based saga coordination
-- **Automatic Compensation**: Automatic rollback on failures
-- **Idempotent Steps**: Each step can be safely retried
+- **Automatic Compensation**: Completed steps are undone when a later step fails
+- **Persistent Progress**: Saga progress is stored in SQLite, and interrupted sagas are resumed on startup
+- **Step Retries**: Failed steps are retried with exponential backoff
 - **OpenTelemetry**: Distributed tracing and metrics
 - **Health Checks**: Kubernetes-ready liveness and readiness probes
 
@@ -1061,10 +973,13 @@ Set the following environment variables:
 PORT=8080
 HEALTH_PORT=8081
 
-# Retry
-MAX_RETRIES=3
-INITIAL_INTERVAL_MS=100
-MAX_INTERVAL_MS=10000
+# Saga progress database
+SAGA_STORE_PATH=sagas.db
+
+# Step retries
+MAX_ATTEMPTS=3
+INITIAL_BACKOFF_MS=100
+MAX_BACKOFF_MS=10000
 ```
 
 ## Configured Sagas
@@ -1073,6 +988,13 @@ MAX_INTERVAL_MS=10000
 |------|-------------|-------|
 {saga_table}
 
+Sagas are defined in `src/config.rs`. Available steps:
+
+| Step | Action | Compensation |
+|------|--------|--------------|
+| reserve_stock | Reserve the order's stock | Release the stock |
+| confirm_order | Confirm the order | Cancel the order |
+
 ## Running
 
 ```bash
@@ -1090,24 +1012,32 @@ cargo build --release
 |--------|----------|-------------|
 | GET | /sagas | List available sagas |
 | POST | /sagas/:name/execute | Start a saga execution |
-| GET | /executions | List all executions |
-| GET | /executions/:id | Get execution status |
+| GET | /executions | List executions in progress |
+| GET | /executions/:id | Get an execution's progress |
 
 ## Starting a Saga
 
 ```bash
-curl -X POST http://localhost:8080/sagas/order_saga/execute \
+curl -X POST http://localhost:8080/sagas/{example_saga}/execute \
   -H "Content-Type: application/json" \
-  -d '{{"payload": {{"order_id": "123", "amount": 100.00}}}}'
+  -d '{{"payload": {{"order_id": "123", "sku": "widget", "quantity": 2}}}}'
 ```
 
 ## Saga Execution States
 
-- **running**: Saga is executing steps
-- **completed**: All steps completed successfully
-- **compensating**: A step failed, rolling back
-- **compensated**: Rollback completed successfully
-- **failed**: Rollback also failed (requires manual intervention)
+- **Executing**: Saga is executing steps
+- **Completed**: All steps completed successfully
+- **Compensating**: A step failed, rolling back
+- **Compensated**: Rollback completed successfully
+- **Failed**: Rollback also failed (requires manual intervention)
+
+## Restarts
+
+Progress is saved before the saga starts, after each step and when
+compensation starts. On startup, sagas left executing continue after
+their last completed step, and sagas left compensating finish their
+rollback. A step can therefore run or be compensated twice after a crash,
+so the order services must treat repeated calls for an order as no-ops.
 
 ## Architecture
 
@@ -1115,22 +1045,24 @@ curl -X POST http://localhost:8080/sagas/order_saga/execute \
 ┌───────────────────────────────────────────────────────────────┐
 │                    Saga Orchestrator                           │
 │  ┌─────────────┐    ┌─────────────┐    ┌─────────────────────┐│
-│  │  API Layer  │───▶│ Orchestrator│───▶│   Step Executors    ││
+│  │  API Layer  │───▶│ Orchestrator│───▶│     Saga Steps      ││
 │  └─────────────┘    └─────────────┘    └─────────────────────┘│
 │        │                  │                      │            │
 │        ▼                  ▼                      ▼            │
 │  ┌─────────────┐    ┌─────────────┐    ┌─────────────────────┐│
-│  │   Routes    │    │  Execution  │    │    Compensation     ││
-│  │   Handler   │    │    Store    │    │      Handler        ││
+│  │   Routes    │    │ Saga Store  │    │   Order Services    ││
+│  │   Handler   │    │  (SQLite)   │    │                     ││
 │  └─────────────┘    └─────────────┘    └─────────────────────┘│
 └───────────────────────────────────────────────────────────────┘
 ```
 
 ## Adding New Sagas
 
-1. Add saga configuration to `Config`
-2. Create step implementations in `src/application/steps.rs`
-3. Register steps in `create_steps()` function
+1. Implement the steps in `src/application/steps.rs`
+2. Register each step's name in `add_step()`
+3. Add the saga and its step names to `Config` in `src/config.rs`
+
+Replace `InMemoryOrderServices` with clients for your real services.
 
 ## License
 
@@ -1139,6 +1071,11 @@ MIT
         display_name = saga.display_name,
         name = name,
         saga_table = saga_table.join("\n"),
+        example_saga = saga
+            .sagas
+            .first()
+            .map(|s| s.name.as_str())
+            .unwrap_or("order_saga"),
     )
 }
 
@@ -1183,8 +1120,13 @@ WORKDIR /app
 
 COPY --from=builder /app/target/release/{name} /app/
 
+# Saga progress, kept across restarts
+RUN mkdir -p /app/data
+VOLUME /app/data
+
 ENV PORT=8080
 ENV HEALTH_PORT=8081
+ENV SAGA_STORE_PATH=/app/data/sagas.db
 EXPOSE 8080 8081
 
 CMD ["/app/{name}"]
@@ -1193,6 +1135,163 @@ CMD ["/app/{name}"]
     )
 }
 
+/// Generate tests/integration.rs
+pub fn integration_tests(config: &ProjectConfig) -> String {
+    let saga = config.saga_orchestrator.as_ref().unwrap();
+    let pascal_name = to_pascal_case(&saga.service_name);
+    let saga_name = saga
+        .sagas
+        .first()
+        .map(|s| s.name.as_str())
+        .unwrap_or("order_saga");
+
+    let saga_tests = format!(
+        r#"
+fn orchestrator(
+    store: allframe_core::cqrs::InMemorySagaStore,
+    services: std::sync::Arc<dyn domain::OrderServices>,
+) -> application::{pascal_name}Orchestrator {{
+    application::{pascal_name}Orchestrator::new(&config::Config::from_env(), store, services)
+}}
+
+fn order(order_id: &str) -> serde_json::Value {{
+    serde_json::json!({{ "order_id": order_id, "sku": "widget", "quantity": 2 }})
+}}
+
+#[tokio::test]
+async fn test_api_router() {{
+    let orchestrator = orchestrator(
+        Default::default(),
+        std::sync::Arc::new(infrastructure::InMemoryOrderServices::default()),
+    );
+    let app = presentation::create_router(std::sync::Arc::new(orchestrator));
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {{ axum::serve(listener, app).await }});
+
+    let response = get(port, "/sagas").await;
+    assert!(response.starts_with("HTTP/1.1 200"), "{{}}", response);
+}}
+
+#[tokio::test]
+async fn test_saga_completes() {{
+    use allframe_core::cqrs::{{InMemorySagaStore, SagaStatus, SagaStore}};
+
+    let services = infrastructure::InMemoryOrderServices::with_stock([("widget", 10)]);
+    let store = InMemorySagaStore::new();
+    let orchestrator = orchestrator(store.clone(), std::sync::Arc::new(services.clone()));
+
+    let events = orchestrator
+        .run_saga("order-1", "{saga_name}", order("order-1"))
+        .await
+        .unwrap();
+
+    assert_eq!(events.len(), 2);
+    assert_eq!(services.available("widget"), 8);
+    assert!(services.is_confirmed("order-1"));
+    let record = store.load("order-1").await.unwrap().unwrap();
+    assert_eq!(record.status, SagaStatus::Completed);
+}}
+
+/// Order services that decline every confirmation
+struct DecliningServices(infrastructure::InMemoryOrderServices);
+
+#[async_trait::async_trait]
+impl domain::OrderServices for DecliningServices {{
+    async fn reserve_stock(&self, order: &domain::OrderRequest) -> Result<(), String> {{
+        self.0.reserve_stock(order).await
+    }}
+
+    async fn release_stock(&self, order: &domain::OrderRequest) -> Result<(), String> {{
+        self.0.release_stock(order).await
+    }}
+
+    async fn confirm_order(&self, _order: &domain::OrderRequest) -> Result<(), String> {{
+        Err("payment declined".to_string())
+    }}
+
+    async fn cancel_order(&self, order: &domain::OrderRequest) -> Result<(), String> {{
+        self.0.cancel_order(order).await
+    }}
+}}
+
+#[tokio::test]
+async fn test_failing_step_triggers_compensation() {{
+    use allframe_core::cqrs::{{InMemorySagaStore, SagaStatus, SagaStore}};
+
+    let services = infrastructure::InMemoryOrderServices::with_stock([("widget", 10)]);
+    let store = InMemorySagaStore::new();
+    let orchestrator = orchestrator(
+        store.clone(),
+        std::sync::Arc::new(DecliningServices(services.clone())),
+    );
+
+    let error = orchestrator
+        .run_saga("order-2", "{saga_name}", order("order-2"))
+        .await
+        .unwrap_err();
+
+    assert!(error.to_string().contains("payment declined"), "{{}}", error);
+    // The reservation was released
+    assert_eq!(services.available("widget"), 10);
+    assert!(!services.is_confirmed("order-2"));
+    let record = store.load("order-2").await.unwrap().unwrap();
+    assert_eq!(record.status, SagaStatus::Compensated);
+    assert_eq!(record.error.as_deref(), Some("payment declined"));
+}}
+
+#[tokio::test]
+async fn test_interrupted_saga_is_resumed() {{
+    use allframe_core::cqrs::{{InMemorySagaStore, SagaRecord, SagaStatus, SagaStore}};
+    use domain::OrderServices;
+
+    // A previous run reserved the stock, then stopped before confirming
+    let services = infrastructure::InMemoryOrderServices::with_stock([("widget", 10)]);
+    let request: domain::OrderRequest = serde_json::from_value(order("order-3")).unwrap();
+    services.reserve_stock(&request).await.unwrap();
+    let store = InMemorySagaStore::new();
+    store
+        .save(&SagaRecord {{
+            id: "order-3".to_string(),
+            status: SagaStatus::Executing,
+            steps_executed: 1,
+            total_steps: 2,
+            context: serde_json::json!({{ "saga": "{saga_name}", "payload": order("order-3") }}),
+            error: None,
+            updated_at: std::time::SystemTime::now(),
+        }})
+        .await
+        .unwrap();
+
+    let orchestrator = orchestrator(store.clone(), std::sync::Arc::new(services.clone()));
+    assert_eq!(orchestrator.resume_incomplete().await.unwrap(), 1);
+
+    // Only the confirmation ran
+    assert_eq!(services.available("widget"), 8);
+    assert!(services.is_confirmed("order-3"));
+    let record = store.load("order-3").await.unwrap().unwrap();
+    assert_eq!(record.status, SagaStatus::Completed);
+}}
+"#,
+        pascal_name = pascal_name,
+        saga_name = saga_name,
+    );
+
+    format!(
+        "{}{}{}",
+        super::integration_header(&[
+            "config",
+            "error",
+            "domain",
+            "application",
+            "infrastructure",
+            "presentation"
+        ]),
+        super::INTEGRATION_HTTP_TESTS,
+        saga_tests
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
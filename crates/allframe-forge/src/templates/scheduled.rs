@@ -63,6 +63,13 @@ chrono = {{ version = "0.4", features = ["serde"] }}
 uuid = {{ version = "1.0", features = ["v4", "serde"] }}
 dotenvy = "0.15"
 
+[features]
+default = ["otel"]
+# Logs, traces and metrics through allframe-core's observability stack,
+# exported over OTLP when OTEL_EXPORTER_OTLP_ENDPOINT is set. Without it,
+# logs go to stdout.
+otel = ["allframe-core/otel-otlp"]
+
 [dev-dependencies]
 tokio-test = "0.4"
 mockall = "0.13"
@@ -100,18 +107,13 @@ use config::Config;
 use application::{pascal_name}Scheduler;
 use infrastructure::HealthServer;
 
-#[tokio::main]
+{observability}#[tokio::main]
 async fn main() -> anyhow::Result<()> {{
     // Load environment variables
     dotenvy::dotenv().ok();
 
-    // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive(tracing::Level::INFO.into()),
-        )
-        .init();
+    // Initialize logging, tracing and metrics; the guard flushes them on exit
+    let _observability = init_observability()?;
 
     // Load configuration
     let config = Config::from_env();
@@ -148,6 +150,7 @@ async fn main() -> anyhow::Result<()> {{
     Ok(())
 }}
 "#,
+        observability = super::OBSERVABILITY_INIT,
         pascal_name = pascal_name,
         display_name = scheduled.display_name,
     )
@@ -756,6 +759,15 @@ CMD ["/app/{name}"]
     )
 }
 
+/// Generate tests/integration.rs
+pub fn integration_tests(_config: &ProjectConfig) -> String {
+    format!(
+        "{}{}",
+        super::integration_header(&["config", "error", "domain", "application", "infrastructure"]),
+        super::INTEGRATION_HTTP_TESTS
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
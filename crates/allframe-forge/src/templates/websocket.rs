@@ -65,6 +65,13 @@ uuid = {{ version = "1.0", features = ["v4", "serde"] }}
 dashmap = "6.0"
 dotenvy = "0.15"
 
+[features]
+default = ["otel"]
+# Logs, traces and metrics through allframe-core's observability stack,
+# exported over OTLP when OTEL_EXPORTER_OTLP_ENDPOINT is set. Without it,
+# logs go to stdout.
+otel = ["allframe-core/otel-otlp"]
+
 [dev-dependencies]
 tokio-test = "0.4"
 
@@ -101,18 +108,13 @@ use config::Config;
 use application::{pascal_name}Hub;
 use infrastructure::HealthServer;
 
-#[tokio::main]
+{observability}#[tokio::main]
 async fn main() -> anyhow::Result<()> {{
     // Load environment variables
     dotenvy::dotenv().ok();
 
-    // Initialize tracing
-    tracing_subscriber::fmt()
-        .with_env_filter(
-            tracing_subscriber::EnvFilter::from_default_env()
-                .add_directive(tracing::Level::INFO.into()),
-        )
-        .init();
+    // Initialize logging, tracing and metrics; the guard flushes them on exit
+    let _observability = init_observability()?;
 
     // Load configuration
     let config = Config::from_env();
@@ -152,6 +154,7 @@ async fn main() -> anyhow::Result<()> {{
     Ok(())
 }}
 "#,
+        observability = super::OBSERVABILITY_INIT,
         pascal_name = pascal_name,
         display_name = ws.display_name,
     )
@@ -1048,6 +1051,44 @@ CMD ["/app/{name}"]
     )
 }
 
+/// Generate tests/integration.rs
+pub fn integration_tests(config: &ProjectConfig) -> String {
+    let ws = config.websocket_gateway.as_ref().unwrap();
+    let pascal_name = to_pascal_case(&ws.service_name);
+
+    let api_tests = format!(
+        r##"
+#[tokio::test]
+async fn test_api_router() {{
+    let hub = std::sync::Arc::new(application::{pascal_name}Hub::new(config::Config::from_env()));
+    let app = presentation::create_router(hub);
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {{ axum::serve(listener, app).await }});
+
+    let response = get(port, "/stats").await;
+    assert!(response.starts_with("HTTP/1.1 200"), "{{}}", response);
+    assert!(response.contains(r#""connections":0"#), "{{}}", response);
+}}
+"##,
+        pascal_name = pascal_name,
+    );
+
+    format!(
+        "{}{}{}",
+        super::integration_header(&[
+            "config",
+            "error",
+            "domain",
+            "application",
+            "infrastructure",
+            "presentation"
+        ]),
+        super::INTEGRATION_HTTP_TESTS,
+        api_tests
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
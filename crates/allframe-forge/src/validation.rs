@@ -1,8 +1,24 @@
-//! Project name validation
+//! Project name and path validation
 //!
-//! Validates that project names follow Rust package naming conventions.
+//! Validates that project names follow Rust package naming conventions,
+//! and that everything `ignite` writes stays inside the project directory.
 
-use anyhow::Result;
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+/// Names of the framework's own crates
+///
+/// A project named after one can't depend on it: Cargo sees the generated
+/// dependency as the package depending on itself.
+const FRAMEWORK_CRATES: &[&str] = &[
+    "allframe",
+    "allframe-core",
+    "allframe-forge",
+    "allframe-macros",
+    "allframe-mcp",
+    "allframe-tauri",
+];
 
 /// Validate project name (must be valid Rust identifier)
 ///
@@ -10,6 +26,8 @@ use anyhow::Result;
 /// - No spaces allowed
 /// - Cannot start with a number
 /// - Only alphanumeric characters, underscores, and hyphens allowed
+/// - Cannot be the name of an AllFrame crate (`allframe`, `allframe-core`,
+///   ...), with `_` and `-` treated alike
 ///
 /// # Examples
 /// ```
@@ -21,6 +39,7 @@ use anyhow::Result;
 /// assert!(validate_project_name("my project").is_err());
 /// assert!(validate_project_name("123project").is_err());
 /// assert!(validate_project_name("my@project").is_err());
+/// assert!(validate_project_name("allframe_core").is_err());
 /// ```
 pub fn validate_project_name(name: &str) -> Result<()> {
     // Check for spaces
@@ -44,9 +63,97 @@ pub fn validate_project_name(name: &str) -> Result<()> {
         );
     }
 
+    let normalized = name.to_ascii_lowercase().replace('_', "-");
+    if FRAMEWORK_CRATES.contains(&normalized.as_str()) {
+        anyhow::bail!(
+            "Invalid project name: '{}' is the name of an AllFrame crate, so the project couldn't \
+             depend on it; try a name like '{}-app'",
+            name,
+            name
+        );
+    }
+
+    Ok(())
+}
+
+/// Validate the path a project is created at
+///
+/// Rejects paths with `..` components, which could place the project
+/// outside the directory the path is given relative to.
+///
+/// # Examples
+/// ```
+/// # use std::path::Path;
+/// # use allframe_forge::validation::validate_project_path;
+/// assert!(validate_project_path(Path::new("my-project")).is_ok());
+/// assert!(validate_project_path(Path::new("/srv/my-project")).is_ok());
+///
+/// assert!(validate_project_path(Path::new("../my-project")).is_err());
+/// assert!(validate_project_path(Path::new("apps/../../my-project")).is_err());
+/// ```
+pub fn validate_project_path(path: &Path) -> Result<()> {
+    if path.components().any(|c| c == Component::ParentDir) {
+        anyhow::bail!(
+            "Invalid project path: '{}' must not contain '..' components",
+            path.display()
+        );
+    }
     Ok(())
 }
 
+/// Resolve the path of a generated file or directory inside a project
+///
+/// `relative` must be relative and free of `..` components. Its deepest
+/// existing ancestor is then resolved, symlinks included, and must still be
+/// under `root`, so a symlink inside the project can't redirect a write
+/// elsewhere either.
+///
+/// # Errors
+/// Returns an error if the path would escape `root`, or if `root` can't be
+/// resolved
+pub fn confined_path(root: &Path, relative: &Path) -> Result<PathBuf> {
+    if relative
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        anyhow::bail!(
+            "Refusing to write '{}': generated paths must stay inside the project",
+            relative.display()
+        );
+    }
+
+    let canonical_root = root
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve project root {}", root.display()))?;
+    let path = root.join(relative);
+
+    // Dangling symlinks count as existing, so they fail to resolve below
+    // rather than being written through
+    let mut ancestor = path.as_path();
+    while std::fs::symlink_metadata(ancestor).is_err() {
+        ancestor = match ancestor.parent() {
+            Some(parent) => parent,
+            None => break,
+        };
+    }
+    let resolved = ancestor.canonicalize().with_context(|| {
+        format!(
+            "Refusing to write '{}': failed to resolve {}",
+            relative.display(),
+            ancestor.display()
+        )
+    })?;
+    if !resolved.starts_with(&canonical_root) {
+        anyhow::bail!(
+            "Refusing to write '{}': it resolves outside the project to {}",
+            relative.display(),
+            resolved.display()
+        );
+    }
+
+    Ok(path)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -79,4 +186,81 @@ mod tests {
         assert!(validate_project_name("my$project").is_err());
         assert!(validate_project_name("my project!").is_err());
     }
+
+    #[test]
+    fn test_invalid_framework_crate_names() {
+        for name in [
+            "allframe",
+            "allframe-core",
+            "allframe_core",
+            "AllFrame-Macros",
+            "allframe-forge",
+            "allframe-mcp",
+            "allframe-tauri",
+        ] {
+            let error = validate_project_name(name).unwrap_err().to_string();
+            assert!(error.contains("AllFrame crate"), "{name}");
+            assert!(error.contains(&format!("'{}-app'", name)), "{name}");
+        }
+
+        assert!(validate_project_name("allframe-app").is_ok());
+        assert!(validate_project_name("my-allframe").is_ok());
+    }
+
+    #[test]
+    fn test_project_path_traversal() {
+        assert!(validate_project_path(Path::new("my-project")).is_ok());
+        assert!(validate_project_path(Path::new("./apps/my-project")).is_ok());
+
+        for path in [
+            "..",
+            "../my-project",
+            "apps/../../my-project",
+            "/tmp/../etc/x",
+        ] {
+            let error = validate_project_path(Path::new(path)).unwrap_err();
+            assert!(error.to_string().contains("'..'"), "{path}");
+        }
+    }
+
+    #[test]
+    fn test_confined_path() {
+        let root = tempfile::TempDir::new().unwrap();
+        let root = root.path();
+
+        assert_eq!(
+            confined_path(root, Path::new("src/main.rs")).unwrap(),
+            root.join("src/main.rs")
+        );
+
+        for relative in [
+            "../outside.rs",
+            "src/../../outside.rs",
+            "/etc/passwd",
+            "src/./../../../outside.rs",
+        ] {
+            let error = confined_path(root, Path::new(relative)).unwrap_err();
+            assert!(
+                error.to_string().contains("Refusing to write"),
+                "{relative}"
+            );
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_confined_path_rejects_symlink_escapes() {
+        let outside = tempfile::TempDir::new().unwrap();
+        let root = tempfile::TempDir::new().unwrap();
+        std::os::unix::fs::symlink(outside.path(), root.path().join("src")).unwrap();
+        std::os::unix::fs::symlink(
+            outside.path().join("missing"),
+            root.path().join("dangling.rs"),
+        )
+        .unwrap();
+
+        let error = confined_path(root.path(), Path::new("src/main.rs")).unwrap_err();
+        assert!(error.to_string().contains("outside the project"));
+        assert!(confined_path(root.path(), Path::new("dangling.rs")).is_err());
+    }
 }
@@ -0,0 +1,296 @@
+//! Interactive prompts for `allframe ignite --interactive`
+//!
+//! The wizard asks only for what the command-line flags left out: the
+//! archetype, the service name and archetype-specific options such as the
+//! gateway's authentication method or the consumer's broker. Defaults come
+//! from the archetype's config struct, so accepting every default gives the
+//! same project as running `ignite` without the wizard.
+
+use std::io::{BufRead, Write};
+
+use anyhow::Result;
+use clap::ValueEnum;
+
+use crate::{
+    config::{AntiCorruptionLayerConfig, AuthMethod, BffConfig, GatewayConfig, MessageBroker},
+    CliArchetype, IgniteOptions,
+};
+
+/// Authentication methods offered for gateways
+const AUTH_METHODS: &[(AuthMethod, &str, &str)] = &[
+    (AuthMethod::None, "none", "No authentication"),
+    (AuthMethod::ApiKey, "api-key", "API key in a header"),
+    (
+        AuthMethod::HmacSha256,
+        "hmac-sha256",
+        "HMAC-SHA256 signature",
+    ),
+    (
+        AuthMethod::HmacSha512Base64,
+        "hmac-sha512-base64",
+        "HMAC-SHA512 signature, Base64-encoded",
+    ),
+    (AuthMethod::OAuth2, "oauth2", "OAuth2"),
+    (AuthMethod::Jwt, "jwt", "JWT bearer token"),
+];
+
+/// Brokers offered for consumers and producers
+const BROKERS: &[(MessageBroker, &str, &str)] = &[
+    (MessageBroker::Kafka, "kafka", "Apache Kafka"),
+    (MessageBroker::RabbitMq, "rabbitmq", "RabbitMQ"),
+    (MessageBroker::Redis, "redis", "Redis Streams"),
+    (MessageBroker::Sqs, "sqs", "AWS SQS"),
+    (MessageBroker::PubSub, "pubsub", "Google Cloud Pub/Sub"),
+];
+
+/// Prompts reading answers from `input` and writing questions to `output`
+pub(crate) struct Wizard<R, W> {
+    input: R,
+    output: W,
+}
+
+impl<R: BufRead, W: Write> Wizard<R, W> {
+    pub(crate) fn new(input: R, output: W) -> Self {
+        Self { input, output }
+    }
+
+    /// Ask for every option of `project_name` not already set
+    pub(crate) fn complete(
+        &mut self,
+        project_name: &str,
+        options: &mut IgniteOptions,
+    ) -> Result<()> {
+        let archetype = match options.archetype {
+            Some(archetype) => archetype,
+            None => {
+                let choices: Vec<(CliArchetype, String, String)> = CliArchetype::value_variants()
+                    .iter()
+                    .filter_map(|variant| {
+                        let value = variant.to_possible_value()?;
+                        let help = value.get_help().map(|h| h.to_string()).unwrap_or_default();
+                        Some((*variant, value.get_name().to_string(), help))
+                    })
+                    .collect();
+                let archetype = self.select("Project archetype", &choices, 0)?;
+                options.archetype = Some(archetype);
+                archetype
+            }
+        };
+        if matches!(archetype, CliArchetype::Basic) {
+            return Ok(());
+        }
+
+        if options.service_name.is_none() {
+            let default = project_name.replace('-', "_");
+            options.service_name = Some(self.text("Service name", &default)?);
+        }
+
+        match archetype {
+            CliArchetype::Gateway => {
+                let defaults = GatewayConfig::default();
+                if options.api_base_url.is_none() {
+                    options.api_base_url =
+                        Some(self.text("External API base URL", &defaults.api_base_url)?);
+                }
+                if options.auth_method.is_none() {
+                    options.auth_method = Some(self.select_from(
+                        "Authentication method",
+                        AUTH_METHODS,
+                        defaults.auth_method,
+                    )?);
+                }
+            }
+            CliArchetype::Consumer => {
+                self.ask_broker(options)?;
+                if options.group_id.is_none() {
+                    let default = format!(
+                        "{}-group",
+                        options.service_name.as_deref().unwrap_or_default()
+                    );
+                    options.group_id = Some(self.text("Consumer group ID", &default)?);
+                }
+            }
+            CliArchetype::Producer => self.ask_broker(options)?,
+            CliArchetype::Bff if options.api_base_url.is_none() => {
+                let defaults = BffConfig::default();
+                let default = defaults
+                    .backends
+                    .first()
+                    .map(|backend| backend.base_url.as_str())
+                    .unwrap_or_default();
+                options.api_base_url = Some(self.text("Backend API base URL", default)?);
+            }
+            CliArchetype::LegacyAdapter if options.api_base_url.is_none() => {
+                let defaults = AntiCorruptionLayerConfig::default();
+                options.api_base_url = Some(self.text(
+                    "Legacy system connection string",
+                    &defaults.legacy_system.connection_string,
+                )?);
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    fn ask_broker(&mut self, options: &mut IgniteOptions) -> Result<()> {
+        if options.broker.is_none() {
+            options.broker =
+                Some(self.select_from("Message broker", BROKERS, MessageBroker::default())?);
+        }
+        Ok(())
+    }
+
+    fn select_from<T: Copy + PartialEq>(
+        &mut self,
+        question: &str,
+        choices: &[(T, &str, &str)],
+        default: T,
+    ) -> Result<T> {
+        let choices: Vec<(T, String, String)> = choices
+            .iter()
+            .map(|(value, name, help)| (*value, name.to_string(), help.to_string()))
+            .collect();
+        let default = choices
+            .iter()
+            .position(|(value, _, _)| *value == default)
+            .unwrap_or(0);
+        self.select(question, &choices, default)
+    }
+
+    /// Ask to pick one of `choices` by number or name
+    fn select<T: Copy>(
+        &mut self,
+        question: &str,
+        choices: &[(T, String, String)],
+        default: usize,
+    ) -> Result<T> {
+        writeln!(self.output, "{}:", question)?;
+        for (i, (_, name, help)) in choices.iter().enumerate() {
+            if help.is_empty() {
+                writeln!(self.output, "  {}) {}", i + 1, name)?;
+            } else {
+                writeln!(self.output, "  {}) {} - {}", i + 1, name, help)?;
+            }
+        }
+
+        loop {
+            let answer = self.ask(&format!("Choose [{}]", default + 1))?;
+            if answer.is_empty() {
+                return Ok(choices[default].0);
+            }
+            let by_number = answer
+                .parse::<usize>()
+                .ok()
+                .and_then(|n| n.checked_sub(1))
+                .and_then(|i| choices.get(i));
+            let by_name = || {
+                choices
+                    .iter()
+                    .find(|(_, name, _)| name.eq_ignore_ascii_case(&answer))
+            };
+            match by_number.or_else(by_name) {
+                Some((value, _, _)) => return Ok(*value),
+                None => writeln!(
+                    self.output,
+                    "Enter a number from 1 to {} or one of the names above",
+                    choices.len()
+                )?,
+            }
+        }
+    }
+
+    /// Ask for free text, returning `default` for an empty answer
+    fn text(&mut self, question: &str, default: &str) -> Result<String> {
+        let answer = self.ask(&format!("{} [{}]", question, default))?;
+        Ok(if answer.is_empty() {
+            default.to_string()
+        } else {
+            answer
+        })
+    }
+
+    fn ask(&mut self, prompt: &str) -> Result<String> {
+        write!(self.output, "{}: ", prompt)?;
+        self.output.flush()?;
+
+        let mut line = String::new();
+        if self.input.read_line(&mut line)? == 0 {
+            anyhow::bail!("Input ended before all questions were answered");
+        }
+        Ok(line.trim().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(project_name: &str, options: &mut IgniteOptions, input: &str) -> Result<String> {
+        let mut output = Vec::new();
+        Wizard::new(input.as_bytes(), &mut output).complete(project_name, options)?;
+        Ok(String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn test_asks_archetype_specific_questions() {
+        let mut options = IgniteOptions::default();
+        let output = run(
+            "my-gateway",
+            &mut options,
+            "gateway\n\nhttps://api.kraken.com\n3\n",
+        )
+        .unwrap();
+
+        assert!(matches!(options.archetype, Some(CliArchetype::Gateway)));
+        assert_eq!(options.service_name.as_deref(), Some("my_gateway"));
+        assert_eq!(
+            options.api_base_url.as_deref(),
+            Some("https://api.kraken.com")
+        );
+        assert_eq!(options.auth_method, Some(AuthMethod::HmacSha256));
+        assert!(output.contains("2) gateway - API Gateway service"));
+        assert!(output.contains("Service name [my_gateway]: "));
+
+        let mut options = IgniteOptions::default();
+        run("orders", &mut options, "3\n\nrabbitmq\n\n").unwrap();
+        assert!(matches!(options.archetype, Some(CliArchetype::Consumer)));
+        assert_eq!(options.broker, Some(MessageBroker::RabbitMq));
+        assert_eq!(options.group_id.as_deref(), Some("orders-group"));
+    }
+
+    #[test]
+    fn test_skips_questions_answered_by_flags() {
+        let mut options = IgniteOptions {
+            archetype: Some(CliArchetype::Consumer),
+            service_name: Some("orders".to_string()),
+            group_id: Some("orders-v2".to_string()),
+            broker: Some(MessageBroker::Kafka),
+            ..IgniteOptions::default()
+        };
+        assert_eq!(run("orders", &mut options, "").unwrap(), "");
+
+        let mut options = IgniteOptions {
+            archetype: Some(CliArchetype::Basic),
+            ..IgniteOptions::default()
+        };
+        assert_eq!(run("hello", &mut options, "").unwrap(), "");
+        assert!(options.service_name.is_none());
+    }
+
+    #[test]
+    fn test_invalid_answers_and_end_of_input() {
+        let mut options = IgniteOptions {
+            archetype: Some(CliArchetype::Producer),
+            service_name: Some("events".to_string()),
+            ..IgniteOptions::default()
+        };
+        let output = run("events", &mut options, "9\nnats\n\n").unwrap();
+        assert_eq!(options.broker, Some(MessageBroker::Kafka));
+        assert_eq!(output.matches("Enter a number from 1 to 5").count(), 2);
+
+        let mut options = IgniteOptions::default();
+        let error = run("events", &mut options, "4\n").unwrap_err();
+        assert!(error.to_string().contains("Input ended"));
+    }
+}
@@ -3,11 +3,13 @@
 //! Tests that each archetype scaffolds a valid project structure.
 
 use allframe_forge::config::{
-    AntiCorruptionLayerConfig, ConsumerConfig, GatewayConfig, ProducerConfig, ProjectConfig,
-    SagaOrchestratorConfig, ScheduledConfig, WebSocketGatewayConfig,
+    AntiCorruptionLayerConfig, ConsumerConfig, GatewayConfig, OptionalModule, ProducerConfig,
+    ProjectConfig, SagaOrchestratorConfig, ScheduledConfig, WebSocketGatewayConfig,
 };
 use allframe_forge::scaffolding;
 use allframe_forge::validation::validate_project_name;
+use std::path::Path;
+use std::process::Command;
 use tempfile::TempDir;
 
 fn make_config(name: &str) -> ProjectConfig {
@@ -16,6 +18,41 @@ fn make_config(name: &str) -> ProjectConfig {
     config
 }
 
+/// Run `cargo test` in a generated project, building it against this
+/// workspace's `allframe-core` instead of the published one
+fn assert_generated_tests_pass(project_path: &Path) {
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).join("../..");
+    let allframe_core = workspace
+        .join("crates/allframe-core")
+        .canonicalize()
+        .unwrap();
+
+    let manifest = project_path.join("Cargo.toml");
+    let mut cargo_toml = std::fs::read_to_string(&manifest).unwrap();
+    cargo_toml.push_str(&format!(
+        "\n[patch.crates-io]\nallframe-core = {{ path = {:?} }}\n",
+        allframe_core
+    ));
+    std::fs::write(&manifest, cargo_toml).unwrap();
+
+    // Share one target directory so dependencies build once
+    let output = Command::new(env!("CARGO"))
+        .arg("test")
+        .current_dir(project_path)
+        .env(
+            "CARGO_TARGET_DIR",
+            workspace.join("target/generated-projects"),
+        )
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "cargo test failed in {}:\n{}",
+        project_path.display(),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
 // --- Basic archetype ---
 
 #[test]
@@ -28,6 +65,7 @@ fn test_scaffold_basic_structure() {
     scaffolding::generate_files(&project_path, "my-basic-app").unwrap();
 
     assert!(project_path.join("Cargo.toml").exists());
+    assert!(project_path.join("tests/integration.rs").exists());
     assert!(project_path.join("src/main.rs").exists());
     assert!(project_path.join("src/domain").is_dir());
     assert!(project_path.join("src/application").is_dir());
@@ -38,6 +76,83 @@ fn test_scaffold_basic_structure() {
     assert!(cargo_toml.contains(r#"edition = "2021""#));
     assert!(cargo_toml.contains(r#"rust-version = "1.89""#));
     assert!(cargo_toml.contains("my-basic-app"));
+
+    let integration = std::fs::read_to_string(project_path.join("tests/integration.rs")).unwrap();
+    assert!(integration.contains("#[path = \"../src/domain/mod.rs\"]\nmod domain;"));
+    assert!(integration.contains("async fn test_greeting_use_case()"));
+    assert!(!cargo_toml.contains("allframe-core"));
+    assert!(!project_path
+        .join("src/infrastructure/authenticator.rs")
+        .exists());
+}
+
+#[test]
+fn test_scaffold_basic_with_modules() {
+    let tmp = TempDir::new().unwrap();
+    let project_path = tmp.path().join("my-svc");
+    std::fs::create_dir_all(&project_path).unwrap();
+
+    scaffolding::create_directory_structure(&project_path).unwrap();
+    scaffolding::generate_files_with_modules(
+        &project_path,
+        "my-svc",
+        &[
+            OptionalModule::Auth,
+            OptionalModule::Cache,
+            OptionalModule::Otel,
+        ],
+    )
+    .unwrap();
+
+    let cargo_toml = std::fs::read_to_string(project_path.join("Cargo.toml")).unwrap();
+    assert!(cargo_toml.contains(
+        r#"allframe-core = { version = "0.1", default-features = false, features = ["auth", "cache-memory", "otel"] }"#
+    ));
+
+    let main_rs = std::fs::read_to_string(project_path.join("src/main.rs")).unwrap();
+    assert!(main_rs.contains("use infrastructure::{ApiKeyAuthenticator, ConsoleGreeter};"));
+    assert!(main_rs.contains("async fn main() {\n    // Observability"));
+    assert!(main_rs.contains("let _observability = ObservabilityBuilder::new"));
+    assert!(main_rs.contains("let cache = MemoryCache::new();"));
+    assert!(main_rs.contains("authenticator.authenticate(\"demo-key\")"));
+
+    let infrastructure =
+        std::fs::read_to_string(project_path.join("src/infrastructure/mod.rs")).unwrap();
+    assert!(infrastructure.contains("mod authenticator;"));
+    assert!(infrastructure.contains("pub use authenticator::ApiKeyAuthenticator;"));
+    assert!(project_path
+        .join("src/infrastructure/authenticator.rs")
+        .exists());
+
+    let integration = std::fs::read_to_string(project_path.join("tests/integration.rs")).unwrap();
+    assert!(integration.contains("async fn test_api_key_authenticator()"));
+}
+
+#[test]
+#[ignore = "builds generated projects; run with --ignored"]
+fn test_generated_basic_tests_pass() {
+    let tmp = TempDir::new().unwrap();
+
+    let plain = tmp.path().join("plain-app");
+    std::fs::create_dir_all(&plain).unwrap();
+    scaffolding::create_directory_structure(&plain).unwrap();
+    scaffolding::generate_files(&plain, "plain-app").unwrap();
+    assert_generated_tests_pass(&plain);
+
+    let with_modules = tmp.path().join("modules-app");
+    std::fs::create_dir_all(&with_modules).unwrap();
+    scaffolding::create_directory_structure(&with_modules).unwrap();
+    scaffolding::generate_files_with_modules(
+        &with_modules,
+        "modules-app",
+        &[
+            OptionalModule::Auth,
+            OptionalModule::Cache,
+            OptionalModule::Otel,
+        ],
+    )
+    .unwrap();
+    assert_generated_tests_pass(&with_modules);
 }
 
 // --- Gateway archetype ---
@@ -55,6 +170,7 @@ fn test_scaffold_gateway_structure() {
     scaffolding::generate_gateway_files(&project_path, &config).unwrap();
 
     assert!(project_path.join("Cargo.toml").exists());
+    assert!(project_path.join("tests/integration.rs").exists());
     assert!(project_path.join("src/main.rs").exists());
     assert!(project_path.join("src/domain").is_dir());
     assert!(project_path.join("proto").is_dir());
@@ -68,6 +184,10 @@ fn test_scaffold_gateway_structure() {
         cargo_toml.contains(r#"rust-version = "1.89""#),
         "Gateway must use MSRV 1.89"
     );
+
+    let integration = std::fs::read_to_string(project_path.join("tests/integration.rs")).unwrap();
+    assert!(integration.contains("#[path = \"../src/config.rs\"]\nmod config;"));
+    assert!(integration.contains("async fn test_health_check()"));
 }
 
 // --- Consumer archetype ---
@@ -85,11 +205,15 @@ fn test_scaffold_consumer_structure() {
     scaffolding::generate_consumer_files(&project_path, &config).unwrap();
 
     assert!(project_path.join("Cargo.toml").exists());
+    assert!(project_path.join("tests/integration.rs").exists());
     assert!(project_path.join("src/main.rs").exists());
 
     let cargo_toml = std::fs::read_to_string(project_path.join("Cargo.toml")).unwrap();
     assert!(cargo_toml.contains(r#"edition = "2021""#));
     assert!(cargo_toml.contains(r#"rust-version = "1.89""#));
+
+    let tests = std::fs::read_to_string(project_path.join("tests/integration.rs")).unwrap();
+    assert!(tests.contains("fn test_duplicate_message_is_skipped()"));
 }
 
 // --- Producer archetype ---
@@ -107,7 +231,14 @@ fn test_scaffold_producer_structure() {
     scaffolding::generate_producer_files(&project_path, &config).unwrap();
 
     assert!(project_path.join("Cargo.toml").exists());
+    assert!(project_path.join("tests/integration.rs").exists());
     assert!(project_path.join("src/main.rs").exists());
+
+    let migration =
+        std::fs::read_to_string(project_path.join("migrations/0001_create_tables.sql")).unwrap();
+    assert!(migration.contains("CREATE TABLE IF NOT EXISTS outbox"));
+    let main = std::fs::read_to_string(project_path.join("src/main.rs")).unwrap();
+    assert!(main.contains("OutboxRelay::new(outbox, producer)"));
 }
 
 // --- Error handling: missing config ---
@@ -157,6 +288,9 @@ fn test_invalid_project_names() {
     assert!(validate_project_name("123start").is_err());
     assert!(validate_project_name("has space").is_err());
     assert!(validate_project_name("special!chars").is_err());
+    assert!(validate_project_name("allframe").is_err());
+    assert!(validate_project_name("allframe-core").is_err());
+    assert!(validate_project_name("allframe_macros").is_err());
 }
 
 // --- Additional archetype structure tests ---
@@ -174,7 +308,16 @@ fn test_scaffold_scheduled_structure() {
     scaffolding::generate_scheduled_files(&project_path, &config).unwrap();
 
     assert!(project_path.join("Cargo.toml").exists());
+    assert!(project_path.join("tests/integration.rs").exists());
     assert!(project_path.join("src/main.rs").exists());
+
+    let main = std::fs::read_to_string(project_path.join("src/main.rs")).unwrap();
+    assert!(main.contains("let _observability = init_observability()?;"));
+    assert!(main.contains("ObservabilityBuilder::new(env!(\"CARGO_PKG_NAME\"))"));
+    assert!(main.contains("#[cfg(not(feature = \"otel\"))]"));
+    let cargo_toml = std::fs::read_to_string(project_path.join("Cargo.toml")).unwrap();
+    assert!(cargo_toml.contains("default = [\"otel\"]"));
+    assert!(cargo_toml.contains("otel = [\"allframe-core/otel-otlp\"]"));
 }
 
 #[test]
@@ -190,6 +333,7 @@ fn test_scaffold_websocket_structure() {
     scaffolding::generate_websocket_files(&project_path, &config).unwrap();
 
     assert!(project_path.join("Cargo.toml").exists());
+    assert!(project_path.join("tests/integration.rs").exists());
 }
 
 #[test]
@@ -205,6 +349,12 @@ fn test_scaffold_saga_structure() {
     scaffolding::generate_saga_files(&project_path, &config).unwrap();
 
     assert!(project_path.join("Cargo.toml").exists());
+    assert!(project_path.join("tests/integration.rs").exists());
+
+    let main = std::fs::read_to_string(project_path.join("src/main.rs")).unwrap();
+    assert!(main.contains("orchestrator.resume_incomplete().await?"));
+    let tests = std::fs::read_to_string(project_path.join("tests/integration.rs")).unwrap();
+    assert!(tests.contains("fn test_failing_step_triggers_compensation()"));
 }
 
 #[test]
@@ -220,4 +370,29 @@ fn test_scaffold_acl_structure() {
     scaffolding::generate_acl_files(&project_path, &config).unwrap();
 
     assert!(project_path.join("Cargo.toml").exists());
+    assert!(project_path.join("tests/integration.rs").exists());
+}
+
+#[test]
+fn test_scaffold_shared_errors() {
+    let tmp = TempDir::new().unwrap();
+    let project_path = tmp.path().join("my-consumer");
+    std::fs::create_dir_all(&project_path).unwrap();
+
+    scaffolding::create_consumer_structure(&project_path).unwrap();
+    let mut config = make_config("my-consumer");
+    config.consumer = Some(ConsumerConfig::default());
+    scaffolding::generate_consumer_files(&project_path, &config).unwrap();
+    scaffolding::generate_shared_errors(&project_path).unwrap();
+
+    let cargo_toml = std::fs::read_to_string(project_path.join("Cargo.toml")).unwrap();
+    assert!(cargo_toml.contains("errors = { path = \"errors\" }"));
+    assert!(cargo_toml.ends_with("[workspace]\nmembers = [\".\", \"errors\"]\n"));
+
+    let errors = std::fs::read_to_string(project_path.join("errors/src/lib.rs")).unwrap();
+    assert!(errors.contains("#[derive(Debug, thiserror::Error, GrpcError, HttpError)]"));
+    assert!(errors.contains("fn test_http_status_mapping()"));
+
+    // A second run would declare the workspace twice
+    assert!(scaffolding::generate_shared_errors(&project_path).is_err());
 }
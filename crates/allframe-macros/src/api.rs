@@ -1,13 +1,13 @@
 //! API Handler Macro Implementation
 //!
 //! This module implements the `#[api_handler]` procedural macro for automatic
-//! OpenAPI 3.1 schema generation.
+//! OpenAPI 3.1 schema generation and router registration.
 
 use std::collections::HashMap;
 
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use syn::{parse2, FnArg, ItemFn, Pat, Result, ReturnType};
+use syn::{parse2, Error, FnArg, Ident, ItemFn, Pat, Result, ReturnType, Type};
 
 /// Implementation of the #[api_handler] macro
 ///
@@ -15,6 +15,9 @@ use syn::{parse2, FnArg, ItemFn, Pat, Result, ReturnType};
 /// - Keeps the original function intact
 /// - Generates a `{function_name}_openapi_schema()` function that returns JSON
 ///   schema
+/// - Generates a `{function_name}_register_into(&mut Router)` function that
+///   registers the handler under `METHOD:path` together with its
+///   `RouteMetadata`
 ///
 /// Supports:
 /// - Automatic type introspection for request/response
@@ -57,6 +60,8 @@ pub fn api_handler_impl(attr: TokenStream, item: TokenStream) -> Result<TokenStr
         &custom_responses,
     );
 
+    let register_fn = register_fn(&func, &path, &method, &description)?;
+
     // Generate the output
     let expanded = quote! {
         // Keep the original function
@@ -66,11 +71,130 @@ pub fn api_handler_impl(attr: TokenStream, item: TokenStream) -> Result<TokenStr
         fn #schema_fn_name() -> String {
             #schema.to_string()
         }
+
+        #register_fn
     };
 
     Ok(expanded)
 }
 
+/// Generate `{function_name}_register_into(&mut Router)`
+///
+/// The handler takes at most one argument, deserialized from the request
+/// payload. `String` responses are returned as-is, `Result<T, E>` uses
+/// `register_result*` (so `E: Display`), and anything else is serialized as
+/// JSON. Request and response schemas are attached when the types implement
+/// `ToJsonSchema`.
+fn register_fn(func: &ItemFn, path: &str, method: &str, description: &str) -> Result<TokenStream> {
+    let func_name = &func.sig.ident;
+    let vis = &func.vis;
+    let register_fn_name = Ident::new(&format!("{}_register_into", func_name), func_name.span());
+    let router = Ident::new("router", Span::mixed_site());
+    let request = Ident::new("request", Span::mixed_site());
+
+    let method = method.to_uppercase();
+    let handler_name = format!("{}:{}", method, path);
+
+    let request_type = match func.sig.inputs.len() {
+        0 => None,
+        1 => match &func.sig.inputs[0] {
+            FnArg::Typed(arg) if !matches!(*arg.ty, Type::Reference(_)) => Some(&*arg.ty),
+            arg => {
+                return Err(Error::new_spanned(
+                    arg,
+                    "#[api_handler] functions take the request by value",
+                ))
+            }
+        },
+        _ => {
+            return Err(Error::new_spanned(
+                &func.sig.inputs,
+                "#[api_handler] functions take at most one argument (the request)",
+            ))
+        }
+    };
+
+    let call = match request_type {
+        Some(_) => quote! { #func_name(#request) },
+        None => quote! { #func_name() },
+    };
+    let call = if func.sig.asyncness.is_some() {
+        quote! { #call.await }
+    } else {
+        call
+    };
+    let handler = match request_type {
+        Some(ty) => quote! { |#request: #ty| async move { #call } },
+        None => quote! { || async move { #call } },
+    };
+
+    let output = match &func.sig.output {
+        ReturnType::Type(_, ty) => Some(&**ty),
+        ReturnType::Default => None,
+    };
+    let output_name = output.and_then(|ty| match ty {
+        Type::Path(path) => path.path.segments.last(),
+        _ => None,
+    });
+    let (register, response_type) = match output_name {
+        Some(segment) if segment.ident == "String" => ("register", output),
+        Some(segment) if segment.ident == "Result" => {
+            let ok = match &segment.arguments {
+                syn::PathArguments::AngleBracketed(args) => {
+                    args.args.iter().find_map(|arg| match arg {
+                        syn::GenericArgument::Type(ty) => Some(ty),
+                        _ => None,
+                    })
+                }
+                _ => None,
+            };
+            ("register_result", ok)
+        }
+        _ => ("register_typed", output),
+    };
+    let register = match request_type {
+        Some(_) => Ident::new(&format!("{}_with_args", register), Span::call_site()),
+        None => Ident::new(register, Span::call_site()),
+    };
+
+    let request_schema = request_type.map(|ty| {
+        quote! {
+            if let ::core::option::Option::Some(schema) =
+                (&::allframe_core::router::schema::SchemaProbe::<#ty>::new()).probe()
+            {
+                metadata = metadata.with_request_schema(schema);
+            }
+        }
+    });
+    let response_schema = response_type.map(|ty| {
+        quote! {
+            if let ::core::option::Option::Some(schema) =
+                (&::allframe_core::router::schema::SchemaProbe::<#ty>::new()).probe()
+            {
+                metadata = metadata.with_response_schema(schema);
+            }
+        }
+    });
+
+    Ok(quote! {
+        /// Register this handler and its route metadata with a router
+        #[allow(dead_code)]
+        #vis fn #register_fn_name(#router: &mut ::allframe_core::router::Router) {
+            #[allow(unused_imports)]
+            use ::allframe_core::router::schema::{ProbeNoSchema as _, ProbeSchema as _};
+
+            #router.#register(#handler_name, #handler);
+
+            #[allow(unused_mut)]
+            let mut metadata = ::allframe_core::router::RouteMetadata::new(#path, #method, "rest")
+                .with_description(#description);
+            #request_schema
+            #response_schema
+            #router.add_route(metadata);
+        }
+    })
+}
+
 /// Represents a function parameter with its name and type
 struct ParamInfo {
     name: String,
@@ -434,4 +558,34 @@ mod tests {
             Some("Create user".to_string())
         );
     }
+
+    #[test]
+    fn test_generates_router_registration() {
+        let attr = quote! { path = "/users", method = "post", description = "Create user" };
+        let item = quote! {
+            pub async fn create_user(req: CreateUser) -> Result<User, ApiError> {
+                todo!()
+            }
+        };
+
+        let output = api_handler_impl(attr, item).unwrap().to_string();
+        assert!(output.contains("pub fn create_user_register_into"));
+        assert!(output.contains("register_result_with_args (\"POST:/users\""));
+        assert!(output.contains("RouteMetadata :: new (\"/users\" , \"POST\" , \"rest\")"));
+        assert!(output.contains("SchemaProbe :: < CreateUser >"));
+        assert!(output.contains("SchemaProbe :: < User >"));
+    }
+
+    #[test]
+    fn test_rejects_multiple_arguments() {
+        let attr = quote! { path = "/users/{id}", method = "PUT" };
+        let item = quote! {
+            async fn update_user(id: u64, req: UpdateUser) -> String {
+                todo!()
+            }
+        };
+
+        let err = api_handler_impl(attr, item).unwrap_err();
+        assert!(err.to_string().contains("at most one argument"));
+    }
 }
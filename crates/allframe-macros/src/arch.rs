@@ -11,14 +11,23 @@
 //!    ↓
 //! Layer 1: Domain (business logic, no dependencies)
 //!
-//! Enforcement uses type-name heuristics on struct fields: types whose names
-//! contain "Repository", "UseCase", or "Handler" are assumed to belong to the
-//! corresponding layer. This catches the vast majority of real-world violations
-//! while remaining zero-cost at runtime.
+//! Handlers additionally may not depend on repositories directly; they must go
+//! through a use case.
+//!
+//! Annotated structs and enums implement `allframe_core::arch::Layered`, and
+//! the types of struct fields (or handler function arguments) are checked
+//! with const assertions against it, so the compiler resolves each type
+//! wherever it is declared. Types without a layer are ignored. Names
+//! containing "Repository", "UseCase", or "Handler" are also assumed to
+//! belong to the corresponding layer, which covers trait objects and generic
+//! types the assertions can't name. Either way the check is zero-cost at
+//! runtime.
 
 use proc_macro2::TokenStream;
-use quote::quote;
-use syn::{parse2, Error, Fields, Item, ItemStruct, Result, Type};
+use quote::{quote, quote_spanned, ToTokens};
+use syn::{
+    parse2, spanned::Spanned, Error, Fields, FnArg, Generics, Item, ItemStruct, Result, Type,
+};
 
 /// Layer hierarchy - lower numbers are inner layers
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
@@ -41,30 +50,104 @@ impl Layer {
     }
 
     /// Check if this layer can depend on another layer
-    #[cfg(test)]
     pub fn can_depend_on(&self, other: &Layer) -> bool {
+        // Handlers must go through a use case to reach a repository
+        if *self == Layer::Handler && *other == Layer::Repository {
+            return false;
+        }
         // A layer can only depend on layers with lower numbers (inner layers)
         (*self as u8) > (*other as u8)
     }
 }
 
-/// Forbidden layer names for each layer (types at these layers cannot appear as fields)
-fn forbidden_type_patterns(layer: Layer) -> &'static [(&'static str, Layer)] {
-    match layer {
-        Layer::Domain => &[
-            ("Repository", Layer::Repository),
-            ("UseCase", Layer::UseCase),
-            ("Handler", Layer::Handler),
-        ],
-        Layer::Repository => &[
-            ("UseCase", Layer::UseCase),
-            ("Handler", Layer::Handler),
-        ],
-        Layer::UseCase => &[("Handler", Layer::Handler)],
-        Layer::Handler => &[], // Handlers can depend on anything
+/// Name patterns identifying the layer of a type
+const LAYER_TYPE_PATTERNS: &[(&str, Layer)] = &[
+    ("Repository", Layer::Repository),
+    ("UseCase", Layer::UseCase),
+    ("Handler", Layer::Handler),
+];
+
+const ALL_LAYERS: [Layer; 4] = [
+    Layer::Domain,
+    Layer::Repository,
+    Layer::UseCase,
+    Layer::Handler,
+];
+
+/// Layer a type appears to belong to, judging by its name
+fn layer_of(type_name: &str) -> Option<Layer> {
+    LAYER_TYPE_PATTERNS
+        .iter()
+        .find(|(pattern, _)| type_name.contains(pattern))
+        .map(|(_, layer)| *layer)
+}
+
+/// The concrete types inside `ty` that const assertions can name, looking
+/// through the same wrappers as [`extract_inner_type_name`]
+fn extract_checkable_types(ty: &Type) -> Vec<&Type> {
+    match ty {
+        Type::Path(type_path) if type_path.qself.is_none() => {
+            let Some(segment) = type_path.path.segments.last() else {
+                return Vec::new();
+            };
+            let is_wrapper = matches!(
+                segment.ident.to_string().as_str(),
+                "Arc" | "Box" | "Rc" | "Option" | "Vec" | "Mutex" | "RwLock"
+            );
+            if !is_wrapper {
+                return vec![ty];
+            }
+            let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+                return Vec::new();
+            };
+            args.args
+                .iter()
+                .filter_map(|arg| match arg {
+                    syn::GenericArgument::Type(inner) => Some(inner),
+                    _ => None,
+                })
+                .flat_map(extract_checkable_types)
+                .collect()
+        }
+        Type::Reference(reference) => extract_checkable_types(&reference.elem),
+        // Trait objects and the like have no `Layered` impl to look up
+        _ => Vec::new(),
     }
 }
 
+/// Violation message for a dependency of `current_layer` on `layer`
+#[allow(clippy::too_many_arguments)]
+fn violation_message(
+    owner: &syn::Ident,
+    current_layer: Layer,
+    kind: &str,
+    name: &str,
+    type_name: &str,
+    certainty: &str,
+    layer: Layer,
+) -> String {
+    let hint = if current_layer == Layer::Handler && layer == Layer::Repository {
+        " Handlers must depend on Use Cases."
+    } else {
+        ""
+    };
+    format!(
+        "Clean Architecture violation: {} layer type `{}` has {} `{}` \
+         with type `{}` which {} a {} layer type. \
+         The {} layer cannot depend on the {} layer.{}",
+        current_layer.name(),
+        owner,
+        kind,
+        name,
+        type_name,
+        certainty,
+        layer.name(),
+        current_layer.name(),
+        layer.name(),
+        hint,
+    )
+}
+
 /// Extract the innermost type name from a type, stripping wrappers like Arc<T>,
 /// Box<T>, Option<T>, Vec<T>, dyn Trait, etc.
 fn extract_inner_type_name(ty: &Type) -> Vec<String> {
@@ -115,48 +198,101 @@ fn extract_inner_type_name(ty: &Type) -> Vec<String> {
 }
 
 /// Check struct fields for layer violations
-fn validate_struct_fields(s: &ItemStruct, current_layer: Layer) -> Result<()> {
-    let forbidden = forbidden_type_patterns(current_layer);
-    if forbidden.is_empty() {
-        return Ok(());
-    }
-
+///
+/// Returns the const assertions checking the fields' declared layers.
+fn validate_struct_fields(s: &ItemStruct, current_layer: Layer) -> Result<TokenStream> {
     let fields = match &s.fields {
         Fields::Named(named) => &named.named,
-        _ => return Ok(()),
+        _ => return Ok(TokenStream::new()),
     };
 
+    let mut checks = TokenStream::new();
     for field in fields {
-        let type_names = extract_inner_type_name(&field.ty);
-        for type_name in &type_names {
-            for (pattern, violated_layer) in forbidden {
-                if type_name.contains(pattern) {
-                    let field_name = field
-                        .ident
-                        .as_ref()
-                        .map(|i| i.to_string())
-                        .unwrap_or_else(|| "unnamed".to_string());
-                    return Err(Error::new_spanned(
-                        &field.ty,
-                        format!(
-                            "Clean Architecture violation: {} layer type `{}` has field `{}` \
-                             with type `{}` which appears to be a {} layer type. \
-                             The {} layer cannot depend on the {} layer.",
-                            current_layer.name(),
-                            s.ident,
-                            field_name,
-                            type_name,
-                            violated_layer.name(),
-                            current_layer.name(),
-                            violated_layer.name(),
-                        ),
-                    ));
-                }
-            }
+        let field_name = field
+            .ident
+            .as_ref()
+            .map(|i| i.to_string())
+            .unwrap_or_else(|| "unnamed".to_string());
+        checks.extend(validate_dependency(
+            &s.ident,
+            &s.generics,
+            current_layer,
+            "field",
+            &field_name,
+            &field.ty,
+        )?);
+    }
+
+    Ok(checks)
+}
+
+/// Check a single field or argument type for layer violations
+///
+/// Types whose names give away a forbidden layer are rejected here; the
+/// returned const assertions reject types declared in a forbidden layer.
+/// Owners with generic parameters get no assertions, since their field
+/// types can't be named outside the item.
+fn validate_dependency(
+    owner: &syn::Ident,
+    generics: &Generics,
+    current_layer: Layer,
+    kind: &str,
+    name: &str,
+    ty: &Type,
+) -> Result<TokenStream> {
+    for type_name in extract_inner_type_name(ty) {
+        let Some(layer) = layer_of(&type_name) else {
+            continue;
+        };
+        if layer == current_layer || current_layer.can_depend_on(&layer) {
+            continue;
         }
+        return Err(Error::new_spanned(
+            ty,
+            violation_message(
+                owner,
+                current_layer,
+                kind,
+                name,
+                &type_name,
+                "appears to be",
+                layer,
+            ),
+        ));
     }
 
-    Ok(())
+    if !generics.params.is_empty() {
+        return Ok(TokenStream::new());
+    }
+
+    let forbidden: Vec<Layer> = ALL_LAYERS
+        .into_iter()
+        .filter(|layer| *layer != current_layer && !current_layer.can_depend_on(layer))
+        .collect();
+    let mut checks = TokenStream::new();
+    for checked in extract_checkable_types(ty) {
+        let type_name = checked.to_token_stream().to_string().replace(' ', "");
+        let asserts = forbidden.iter().map(|layer| {
+            let number = *layer as u8;
+            let message =
+                violation_message(owner, current_layer, kind, name, &type_name, "is", *layer);
+            quote_spanned! {checked.span()=>
+                ::core::assert!(
+                    allframe_core::arch::LayerProbe::<#checked>::LAYER != #number,
+                    #message
+                );
+            }
+        });
+        checks.extend(quote_spanned! {checked.span()=>
+            const _: () = {
+                #[allow(unused_imports)]
+                use allframe_core::arch::Unlayered as _;
+                #(#asserts)*
+            };
+        });
+    }
+
+    Ok(checks)
 }
 
 /// Marks a type as part of the Domain layer (Layer 1)
@@ -167,16 +303,16 @@ fn validate_struct_fields(s: &ItemStruct, current_layer: Layer) -> Result<()> {
 pub fn domain_impl(_attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
     let item = parse2::<Item>(item)?;
 
-    match &item {
+    let checks = match &item {
         Item::Struct(s) => validate_struct_fields(s, Layer::Domain)?,
-        Item::Enum(_) => {}
+        Item::Enum(_) => TokenStream::new(),
         _ => {
             return Err(Error::new_spanned(
                 &item,
                 "#[domain] can only be applied to structs or enums",
             ));
         }
-    }
+    };
 
     let layer_metadata = generate_layer_metadata(&item, Layer::Domain);
 
@@ -184,6 +320,8 @@ pub fn domain_impl(_attr: TokenStream, item: TokenStream) -> Result<TokenStream>
         #item
 
         #layer_metadata
+
+        #checks
     })
 }
 
@@ -193,16 +331,16 @@ pub fn domain_impl(_attr: TokenStream, item: TokenStream) -> Result<TokenStream>
 pub fn repository_impl(_attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
     let item = parse2::<Item>(item)?;
 
-    match &item {
+    let checks = match &item {
         Item::Struct(s) => validate_struct_fields(s, Layer::Repository)?,
-        Item::Trait(_) => {}
+        Item::Trait(_) => TokenStream::new(),
         _ => {
             return Err(Error::new_spanned(
                 &item,
                 "#[repository] can only be applied to traits or structs",
             ));
         }
-    }
+    };
 
     let layer_metadata = generate_layer_metadata(&item, Layer::Repository);
 
@@ -210,6 +348,8 @@ pub fn repository_impl(_attr: TokenStream, item: TokenStream) -> Result<TokenStr
         #item
 
         #layer_metadata
+
+        #checks
     })
 }
 
@@ -219,7 +359,7 @@ pub fn repository_impl(_attr: TokenStream, item: TokenStream) -> Result<TokenStr
 pub fn use_case_impl(_attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
     let item = parse2::<Item>(item)?;
 
-    match &item {
+    let checks = match &item {
         Item::Struct(s) => validate_struct_fields(s, Layer::UseCase)?,
         _ => {
             return Err(Error::new_spanned(
@@ -227,7 +367,7 @@ pub fn use_case_impl(_attr: TokenStream, item: TokenStream) -> Result<TokenStrea
                 "#[use_case] can only be applied to structs",
             ));
         }
-    }
+    };
 
     let layer_metadata = generate_layer_metadata(&item, Layer::UseCase);
 
@@ -235,24 +375,45 @@ pub fn use_case_impl(_attr: TokenStream, item: TokenStream) -> Result<TokenStrea
         #item
 
         #layer_metadata
+
+        #checks
     })
 }
 
 /// Marks a type as part of the Handler layer (Layer 4)
 ///
-/// Handlers can depend on Use Cases, Repositories, and Domain entities.
+/// Handlers can depend on Use Cases and Domain entities, but not directly on
+/// Repositories.
 pub fn handler_impl(_attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
     let item = parse2::<Item>(item)?;
 
-    match &item {
-        Item::Struct(_) | Item::Fn(_) => {}
+    let checks = match &item {
+        Item::Struct(s) => validate_struct_fields(s, Layer::Handler)?,
+        Item::Fn(f) => {
+            let mut checks = TokenStream::new();
+            for input in &f.sig.inputs {
+                if let FnArg::Typed(arg) = input {
+                    let pat = &arg.pat;
+                    let name = quote!(#pat).to_string();
+                    checks.extend(validate_dependency(
+                        &f.sig.ident,
+                        &f.sig.generics,
+                        Layer::Handler,
+                        "argument",
+                        &name,
+                        &arg.ty,
+                    )?);
+                }
+            }
+            checks
+        }
         _ => {
             return Err(Error::new_spanned(
                 &item,
                 "#[handler] can only be applied to structs or functions",
             ));
         }
-    }
+    };
 
     let layer_metadata = generate_layer_metadata(&item, Layer::Handler);
 
@@ -260,6 +421,8 @@ pub fn handler_impl(_attr: TokenStream, item: TokenStream) -> Result<TokenStream
         #item
 
         #layer_metadata
+
+        #checks
     })
 }
 
@@ -276,6 +439,21 @@ fn generate_layer_metadata(item: &Item, layer: Layer) -> TokenStream {
     let layer_name = layer.name();
     let layer_number = layer as u8;
 
+    // Traits may not be dyn-compatible, and functions aren't types, so only
+    // structs and enums carry their layer into the type system
+    let layered = match item {
+        Item::Struct(syn::ItemStruct { generics, .. })
+        | Item::Enum(syn::ItemEnum { generics, .. }) => {
+            let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+            quote! {
+                impl #impl_generics allframe_core::arch::Layered for #type_name #ty_generics #where_clause {
+                    const LAYER: u8 = #layer_number;
+                }
+            }
+        }
+        _ => quote! {},
+    };
+
     quote! {
         #[allow(non_upper_case_globals)]
         const _: () = {
@@ -283,6 +461,8 @@ fn generate_layer_metadata(item: &Item, layer: Layer) -> TokenStream {
             const __ALLFRAME_LAYER_NUMBER: u8 = #layer_number;
             const __ALLFRAME_TYPE_NAME: &str = stringify!(#type_name);
         };
+
+        #layered
     }
 }
 
@@ -301,7 +481,7 @@ mod tests {
     #[test]
     fn test_can_depend_on() {
         assert!(Layer::Handler.can_depend_on(&Layer::UseCase));
-        assert!(Layer::Handler.can_depend_on(&Layer::Repository));
+        assert!(!Layer::Handler.can_depend_on(&Layer::Repository));
         assert!(Layer::Handler.can_depend_on(&Layer::Domain));
 
         assert!(Layer::UseCase.can_depend_on(&Layer::Repository));
@@ -425,17 +605,118 @@ mod tests {
     }
 
     #[test]
-    fn test_handler_allows_any_field() {
+    fn test_handler_allows_use_case_and_domain_fields() {
         let input = quote! {
             struct GoodHandler {
                 use_case: Arc<GetUserUseCase>,
-                repo: Arc<dyn UserRepository>,
+                user: User,
             }
         };
         let result = handler_impl(TokenStream::new(), input);
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_handler_rejects_repository_field() {
+        let input = quote! {
+            struct BadHandler {
+                use_case: Arc<GetUserUseCase>,
+                repo: Arc<dyn UserRepository>,
+            }
+        };
+        let err = handler_impl(TokenStream::new(), input)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("Clean Architecture violation"),
+            "Error: {}",
+            err
+        );
+        assert!(
+            err.contains("Handlers must depend on Use Cases"),
+            "Error: {}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_handler_fn_rejects_repository_argument() {
+        let input = quote! {
+            async fn get_user(repo: Arc<dyn UserRepository>) -> Option<User> {
+                repo.find("1").await
+            }
+        };
+        let err = handler_impl(TokenStream::new(), input)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("argument `repo`"), "Error: {}", err);
+    }
+
+    #[test]
+    fn test_layered_impl_for_annotated_types() {
+        let output = domain_impl(TokenStream::new(), quote! { enum Status { Active } })
+            .unwrap()
+            .to_string();
+        assert!(
+            output.contains("impl allframe_core :: arch :: Layered for Status"),
+            "Output: {}",
+            output
+        );
+        assert!(
+            output.contains("const LAYER : u8 = 1u8"),
+            "Output: {}",
+            output
+        );
+
+        // Traits may not be dyn-compatible, so they get no impl
+        let output = repository_impl(TokenStream::new(), quote! { trait AccountStore {} })
+            .unwrap()
+            .to_string();
+        assert!(!output.contains("Layered for"), "Output: {}", output);
+    }
+
+    #[test]
+    fn test_fields_checked_against_declared_layers() {
+        // Names without a layer hint are left to the compiler
+        let input = quote! {
+            struct ShowAccount {
+                store: Arc<accounts::AccountStore>,
+                user: &'static User,
+            }
+        };
+        let output = handler_impl(TokenStream::new(), input).unwrap().to_string();
+        assert!(
+            output.contains("LayerProbe :: < accounts :: AccountStore > :: LAYER != 2u8"),
+            "Output: {}",
+            output
+        );
+        assert!(output.contains("LayerProbe :: < User > :: LAYER != 2u8"));
+        assert!(output.contains("with type `accounts::AccountStore` which is a repository"));
+
+        // Use cases may depend on repositories, not handlers
+        let input = quote! {
+            struct PlaceOrder {
+                endpoint: Endpoint,
+            }
+        };
+        let output = use_case_impl(TokenStream::new(), input)
+            .unwrap()
+            .to_string();
+        assert!(output.contains("LayerProbe :: < Endpoint > :: LAYER != 4u8"));
+        assert!(!output.contains("LAYER != 2u8"));
+    }
+
+    #[test]
+    fn test_generic_owners_skip_declared_layer_checks() {
+        let input = quote! {
+            struct ShowAccount<S> {
+                store: Arc<S>,
+            }
+        };
+        let output = handler_impl(TokenStream::new(), input).unwrap().to_string();
+        assert!(!output.contains("LayerProbe"), "Output: {}", output);
+    }
+
     #[test]
     fn test_extract_inner_type_through_arc() {
         let ty: Type = syn::parse_quote!(Arc<dyn UserRepository>);
@@ -0,0 +1,112 @@
+//! FromEnv derive macro for layered configuration structs
+//!
+//! Implements `allframe_core::di::FromEnv` by loading the struct with
+//! `allframe_core::config::ConfigLoader`, so a configuration struct can be
+//! provided to a DI container with `#[provide(from_env)]`.
+//!
+//! ```rust,ignore
+//! #[derive(Default, Serialize, Deserialize, FromEnv)]
+//! #[config(prefix = "APP", file = "config.toml", defaults)]
+//! struct AppConfig {
+//!     port: u16,
+//! }
+//! ```
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse2, DeriveInput, Error, LitStr, Result};
+
+/// Options from `#[config(...)]`
+#[derive(Default)]
+struct ConfigAttrs {
+    prefix: Option<LitStr>,
+    files: Vec<LitStr>,
+    defaults: bool,
+}
+
+fn parse_config_attrs(input: &DeriveInput) -> Result<ConfigAttrs> {
+    let mut attrs = ConfigAttrs::default();
+    for attr in &input.attrs {
+        if !attr.path().is_ident("config") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("prefix") {
+                attrs.prefix = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("file") {
+                attrs.files.push(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("defaults") {
+                attrs.defaults = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown config attribute"))
+            }
+        })?;
+    }
+    Ok(attrs)
+}
+
+pub fn from_env_impl(input: TokenStream) -> Result<TokenStream> {
+    let input: DeriveInput = parse2(input)?;
+    let attrs = parse_config_attrs(&input)?;
+    let Some(prefix) = attrs.prefix else {
+        return Err(Error::new_spanned(
+            &input.ident,
+            "FromEnv requires #[config(prefix = \"...\")] naming the environment variable prefix",
+        ));
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let defaults = attrs.defaults.then(|| {
+        quote! { .with_defaults(&<Self as ::std::default::Default>::default()) }
+    });
+    let files = attrs.files.iter().map(|file| quote! { .with_file(#file) });
+
+    Ok(quote! {
+        impl #impl_generics ::allframe_core::di::FromEnv for #name #ty_generics #where_clause {
+            fn from_env() -> ::std::result::Result<Self, ::allframe_core::di::DependencyError> {
+                ::allframe_core::config::ConfigLoader::new()
+                    #defaults
+                    #(#files)*
+                    .with_env_prefix(#prefix)
+                    .load::<Self>()
+                    .map(::allframe_core::config::LoadedConfig::into_inner)
+                    .map_err(::std::convert::Into::into)
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generates_loader_chain() {
+        let input = quote! {
+            #[config(prefix = "APP", file = "base.toml", file = "local.toml", defaults)]
+            struct AppConfig {
+                port: u16,
+            }
+        };
+        let output = from_env_impl(input).unwrap().to_string();
+        assert!(output.contains("impl :: allframe_core :: di :: FromEnv for AppConfig"));
+        assert!(output.contains(". with_file (\"base.toml\") . with_file (\"local.toml\")"));
+        assert!(output.contains(". with_env_prefix (\"APP\")"));
+        assert!(output.contains("with_defaults"));
+    }
+
+    #[test]
+    fn test_requires_prefix() {
+        let input = quote! {
+            struct AppConfig {
+                port: u16,
+            }
+        };
+        let error = from_env_impl(input).unwrap_err();
+        assert!(error.to_string().contains("requires #[config(prefix"));
+    }
+}
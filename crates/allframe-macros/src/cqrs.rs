@@ -4,36 +4,108 @@
 //! - #[command] - Mark a struct as a command (generates `Command` trait impl)
 //! - #[query] - Mark a struct as a query (generates `Query` trait impl)
 //! - #[event] - Mark an enum/struct as an event (generates `EventTypeName` + `Event` trait impls)
+//!
+//! With a `derive` argument (`#[command(derive)]`, `#[event(derive)]`, ...)
+//! they also derive whichever of `Clone`, `Serialize` and `Deserialize` the
+//! type doesn't derive itself. Derives are only visible to the macro when
+//! it is placed above the `#[derive]`.
 //! - #[command_handler] - Mark a function as a command handler
 //! - #[query_handler] - Mark a function as a query handler
+//!
+//! Handlers taking only their message are submitted to an `inventory`
+//! collection, which `CommandBus::auto_register` and `QueryBus::auto_register`
+//! walk at runtime.
 
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{parse2, Error, Item, ItemEnum, ItemStruct, Result};
+use syn::{
+    parse::Parser, parse2, punctuated::Punctuated, Attribute, Error, FnArg, GenericArgument, Item,
+    ItemFn, ItemStruct, LitStr, Path, PathArguments, PathSegment, Result, ReturnType, Token, Type,
+};
 
 /// Extract the ident from a struct item
 fn struct_ident(s: &ItemStruct) -> &syn::Ident {
     &s.ident
 }
 
-/// Extract the ident from an enum item
-fn enum_ident(e: &ItemEnum) -> &syn::Ident {
-    &e.ident
+/// Parse the `derive` flag of `#[command]` and `#[query]`
+fn derive_flag(attr: TokenStream, macro_name: &str) -> Result<bool> {
+    let mut derive = false;
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("derive") {
+            derive = true;
+            Ok(())
+        } else {
+            Err(meta.error(format!("#[{}] only accepts `derive`", macro_name)))
+        }
+    });
+    parser.parse2(attr)?;
+    Ok(derive)
+}
+
+/// Add `Clone`, `Serialize` and `Deserialize` derives the type doesn't
+/// already have
+///
+/// Serde derives go through `allframe_core::serde`, so the user crate doesn't
+/// need its own serde dependency.
+fn add_message_derives(attrs: &mut Vec<Attribute>) -> Result<()> {
+    let mut derived = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("derive")) {
+        let paths = attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?;
+        derived.extend(
+            paths
+                .iter()
+                .filter_map(|path| path.segments.last())
+                .map(|segment| segment.ident.to_string()),
+        );
+    }
+    let has = |name: &str| derived.iter().any(|derive| derive == name);
+
+    let mut derives = Vec::new();
+    if !has("Clone") {
+        derives.push(quote! { ::core::clone::Clone });
+    }
+    if !has("Serialize") {
+        derives.push(quote! { allframe_core::serde::Serialize });
+    }
+    if !has("Deserialize") {
+        derives.push(quote! { allframe_core::serde::Deserialize });
+    }
+    if derives.is_empty() {
+        return Ok(());
+    }
+
+    let mut added = Attribute::parse_outer.parse2(quote! {
+        #[derive(#(#derives),*)]
+    })?;
+    if !(has("Serialize") && has("Deserialize")) {
+        added.extend(Attribute::parse_outer.parse2(quote! {
+            #[serde(crate = "allframe_core::serde")]
+        })?);
+    }
+    attrs.splice(0..0, added);
+    Ok(())
 }
 
 /// Mark a struct as a Command
 ///
-/// Generates `impl allframe_core::cqrs::Command for StructName {}`.
+/// Generates `impl allframe_core::cqrs::Command for StructName {}`; with
+/// `#[command(derive)]` it also derives `Clone`, `Serialize` and
+/// `Deserialize` unless the struct already does.
 /// The struct must be `Send + Sync + 'static` (enforced by the trait bounds).
-pub fn command_impl(_attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
-    let item = parse2::<Item>(item)?;
+pub fn command_impl(attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
+    let derive = derive_flag(attr, "command")?;
+    let mut item = parse2::<Item>(item)?;
 
-    match &item {
+    match &mut item {
         Item::Struct(s) => {
+            if derive {
+                add_message_derives(&mut s.attrs)?;
+            }
             let name = struct_ident(s);
             let (impl_generics, ty_generics, where_clause) = s.generics.split_for_impl();
             let output = quote! {
-                #item
+                #s
 
                 impl #impl_generics allframe_core::cqrs::Command for #name #ty_generics #where_clause {}
             };
@@ -48,17 +120,23 @@ pub fn command_impl(_attr: TokenStream, item: TokenStream) -> Result<TokenStream
 
 /// Mark a struct as a Query
 ///
-/// Generates `impl allframe_core::cqrs::Query for StructName {}`.
+/// Generates `impl allframe_core::cqrs::Query for StructName {}`; with
+/// `#[query(derive)]` it also derives `Clone`, `Serialize` and
+/// `Deserialize` unless the struct already does.
 /// The struct must be `Send + Sync + 'static` (enforced by the trait bounds).
-pub fn query_impl(_attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
-    let item = parse2::<Item>(item)?;
+pub fn query_impl(attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
+    let derive = derive_flag(attr, "query")?;
+    let mut item = parse2::<Item>(item)?;
 
-    match &item {
+    match &mut item {
         Item::Struct(s) => {
+            if derive {
+                add_message_derives(&mut s.attrs)?;
+            }
             let name = struct_ident(s);
             let (impl_generics, ty_generics, where_clause) = s.generics.split_for_impl();
             let output = quote! {
-                #item
+                #s
 
                 impl #impl_generics allframe_core::cqrs::Query for #name #ty_generics #where_clause {}
             };
@@ -73,73 +151,253 @@ pub fn query_impl(_attr: TokenStream, item: TokenStream) -> Result<TokenStream>
 
 /// Mark an enum or struct as an Event
 ///
-/// Generates `impl EventTypeName for TypeName {}` and `impl Event for TypeName {}`.
-/// The type must already derive `Clone, Serialize, Deserialize` to satisfy Event's
-/// supertraits.
-pub fn event_impl(_attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
-    let item = parse2::<Item>(item)?;
-
-    match &item {
-        Item::Enum(e) => {
-            let name = enum_ident(e);
-            let (impl_generics, ty_generics, where_clause) = e.generics.split_for_impl();
-            let output = quote! {
-                #item
+/// Generates `impl EventTypeName for TypeName` and `impl Event for TypeName {}`;
+/// with `#[event(derive)]` it also derives `Clone`, `Serialize` and
+/// `Deserialize` unless the type already does. The event type name is the
+/// type's name, or the value of `#[event(name = "...")]`; set it explicitly
+/// to keep stored events readable after the type is renamed.
+pub fn event_impl(attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
+    let mut item = parse2::<Item>(item)?;
 
-                impl #impl_generics allframe_core::cqrs::EventTypeName for #name #ty_generics #where_clause {}
-                impl #impl_generics allframe_core::cqrs::Event for #name #ty_generics #where_clause {}
-            };
-            Ok(output)
+    let mut type_name: Option<LitStr> = None;
+    let mut derive = false;
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("name") {
+            type_name = Some(meta.value()?.parse()?);
+            Ok(())
+        } else if meta.path.is_ident("derive") {
+            derive = true;
+            Ok(())
+        } else {
+            Err(meta.error("expected `name` or `derive`"))
         }
-        Item::Struct(s) => {
-            let name = struct_ident(s);
-            let (impl_generics, ty_generics, where_clause) = s.generics.split_for_impl();
-            let output = quote! {
-                #item
+    });
+    parser.parse2(attr)?;
 
-                impl #impl_generics allframe_core::cqrs::EventTypeName for #name #ty_generics #where_clause {}
-                impl #impl_generics allframe_core::cqrs::Event for #name #ty_generics #where_clause {}
-            };
-            Ok(output)
+    let (attrs, name, generics) = match &mut item {
+        Item::Enum(e) => (&mut e.attrs, &e.ident, &e.generics),
+        Item::Struct(s) => (&mut s.attrs, &s.ident, &s.generics),
+        _ => {
+            return Err(Error::new_spanned(
+                &item,
+                "#[event] can only be applied to enums or structs",
+            ))
         }
-        _ => Err(Error::new_spanned(
-            &item,
-            "#[event] can only be applied to enums or structs",
-        )),
+    };
+    if derive {
+        add_message_derives(attrs)?;
     }
+
+    let type_name = type_name.unwrap_or_else(|| LitStr::new(&name.to_string(), name.span()));
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let impls = quote! {
+        impl #impl_generics allframe_core::cqrs::EventTypeName for #name #ty_generics #where_clause {
+            fn event_type_name() -> &'static str {
+                #type_name
+            }
+        }
+        impl #impl_generics allframe_core::cqrs::Event for #name #ty_generics #where_clause {}
+    };
+
+    Ok(quote! {
+        #item
+
+        #impls
+    })
 }
 
 /// Mark a function as a Command Handler
 ///
-/// Validates that the item is a function and passes it through.
-/// Command handlers implement the `CommandHandler` trait on structs;
-/// this macro serves as documentation and compile-time validation.
+/// Checks at compile time that the handler's first argument is a `#[command]`
+/// type. A handler taking only the command and returning
+/// `Result<Vec<E>, Err>` (or `CommandResult<E>`) with `Err: Into<CommandError>`
+/// is also registered for `CommandBus::auto_register`.
 pub fn command_handler_impl(_attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
     let item = parse2::<Item>(item)?;
 
-    match &item {
-        Item::Fn(_) => Ok(quote! { #item }),
-        _ => Err(Error::new_spanned(
+    let Item::Fn(func) = &item else {
+        return Err(Error::new_spanned(
             &item,
             "#[command_handler] can only be applied to functions",
-        )),
-    }
+        ));
+    };
+
+    let check = message_check(func, quote! { allframe_core::cqrs::Command });
+    let registration = handler_target(func, "CommandResult").and_then(|(command, output)| {
+        let event = vec_item(output)?;
+        let call = handler_call(func);
+        let name = func.sig.ident.to_string();
+        Some(quote! {
+            const _: () = {
+                struct Registered;
+
+                #[allframe_core::async_trait::async_trait]
+                impl allframe_core::cqrs::CommandHandler<#command, #event> for Registered {
+                    async fn handle(&self, message: #command) -> allframe_core::cqrs::CommandResult<#event> {
+                        #call.map_err(::core::convert::Into::into)
+                    }
+                }
+
+                fn entry() -> ::std::boxed::Box<dyn ::std::any::Any + ::core::marker::Send> {
+                    allframe_core::cqrs::CommandHandlerRegistration::entry::<#command, #event, _>(Registered)
+                }
+
+                allframe_core::inventory::submit! {
+                    allframe_core::cqrs::CommandHandlerRegistration::new(#name, entry)
+                }
+            };
+        })
+    });
+
+    Ok(quote! {
+        #item
+        #check
+        #registration
+    })
 }
 
 /// Mark a function as a Query Handler
 ///
-/// Validates that the item is a function and passes it through.
-/// Query handlers implement the `QueryHandler` trait on structs;
-/// this macro serves as documentation and compile-time validation.
+/// Checks at compile time that the handler's first argument is a `#[query]`
+/// type. A handler taking only the query and returning `Result<R, Err>` (or
+/// `QueryResult<R>`) with `Err: Into<QueryError>` is also registered for
+/// `QueryBus::auto_register`.
 pub fn query_handler_impl(_attr: TokenStream, item: TokenStream) -> Result<TokenStream> {
     let item = parse2::<Item>(item)?;
 
-    match &item {
-        Item::Fn(_) => Ok(quote! { #item }),
-        _ => Err(Error::new_spanned(
+    let Item::Fn(func) = &item else {
+        return Err(Error::new_spanned(
             &item,
             "#[query_handler] can only be applied to functions",
-        )),
+        ));
+    };
+
+    let check = message_check(func, quote! { allframe_core::cqrs::Query });
+    let registration = handler_target(func, "QueryResult").map(|(query, output)| {
+        let call = handler_call(func);
+        let name = func.sig.ident.to_string();
+        quote! {
+            const _: () = {
+                struct Registered;
+
+                #[allframe_core::async_trait::async_trait]
+                impl allframe_core::cqrs::QueryHandler<#query, #output> for Registered {
+                    async fn handle(&self, message: #query) -> allframe_core::cqrs::QueryResult<#output> {
+                        #call.map_err(::core::convert::Into::into)
+                    }
+                }
+
+                fn entry() -> ::std::boxed::Box<dyn ::std::any::Any + ::core::marker::Send> {
+                    allframe_core::cqrs::QueryHandlerRegistration::entry::<#query, #output, _>(Registered)
+                }
+
+                allframe_core::inventory::submit! {
+                    allframe_core::cqrs::QueryHandlerRegistration::new(#name, entry)
+                }
+            };
+        }
+    });
+
+    Ok(quote! {
+        #item
+        #check
+        #registration
+    })
+}
+
+/// The message type of a handler: its first argument, behind any reference
+///
+/// Generic handlers are skipped since their types can't be named outside
+/// the function.
+fn message_type(func: &ItemFn) -> Option<&Type> {
+    if !func.sig.generics.params.is_empty() {
+        return None;
+    }
+    match func.sig.inputs.first()? {
+        FnArg::Typed(arg) => match &*arg.ty {
+            Type::Reference(reference) => Some(&reference.elem),
+            ty => Some(ty),
+        },
+        FnArg::Receiver(_) => None,
+    }
+}
+
+/// Compile-time assertion that the handler's message implements `bound`
+fn message_check(func: &ItemFn, bound: TokenStream) -> Option<TokenStream> {
+    let message = message_type(func)?;
+    Some(quote! {
+        const _: fn() = || {
+            fn assert_message<T: #bound>() {}
+            assert_message::<#message>();
+        };
+    })
+}
+
+/// Message and success types of a handler that can be auto-registered
+///
+/// The handler must take the message by value as its only argument and
+/// return `Result<T, _>` or `<alias>Result<T>`.
+fn handler_target<'a>(func: &'a ItemFn, alias: &str) -> Option<(&'a Type, &'a Type)> {
+    if func.sig.inputs.len() != 1 {
+        return None;
+    }
+    let message = match func.sig.inputs.first()? {
+        FnArg::Typed(arg) if !matches!(*arg.ty, Type::Reference(_)) => message_type(func)?,
+        _ => return None,
+    };
+
+    let ReturnType::Type(_, output) = &func.sig.output else {
+        return None;
+    };
+    let segment = type_segment(output)?;
+    let args = generic_types(segment);
+    let ok = match (segment.ident.to_string().as_str(), args.as_slice()) {
+        ("Result", [ok, _]) => *ok,
+        (name, [ok]) if name == alias => *ok,
+        _ => return None,
+    };
+    Some((message, ok))
+}
+
+/// The element type of a `Vec<T>`
+fn vec_item(ty: &Type) -> Option<&Type> {
+    let segment = type_segment(ty)?;
+    match generic_types(segment).as_slice() {
+        [item] if segment.ident == "Vec" => Some(item),
+        _ => None,
+    }
+}
+
+/// Last path segment of a type
+fn type_segment(ty: &Type) -> Option<&PathSegment> {
+    match ty {
+        Type::Path(path) => path.path.segments.last(),
+        _ => None,
+    }
+}
+
+/// Generic type arguments of a path segment
+fn generic_types(segment: &PathSegment) -> Vec<&Type> {
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) => args
+            .args
+            .iter()
+            .filter_map(|arg| match arg {
+                GenericArgument::Type(ty) => Some(ty),
+                _ => None,
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Call the handler function with `message`, awaiting it if async
+fn handler_call(func: &ItemFn) -> TokenStream {
+    let name = &func.sig.ident;
+    if func.sig.asyncness.is_some() {
+        quote! { #name(message).await }
+    } else {
+        quote! { #name(message) }
     }
 }
 
@@ -224,6 +482,74 @@ mod tests {
         assert!(output.contains("Event"));
     }
 
+    #[test]
+    fn test_message_derives_skip_existing() {
+        let input = quote! {
+            #[derive(Clone, Debug, serde::Serialize)]
+            struct UserCreated {
+                id: String,
+            }
+        };
+        let output = event_impl(quote! { derive }, input).unwrap().to_string();
+        assert!(output.contains("# [derive (allframe_core :: serde :: Deserialize)]"));
+        assert!(output.contains("serde (crate = \"allframe_core::serde\")"));
+        assert!(!output.contains(":: core :: clone :: Clone"));
+
+        let input = quote! {
+            #[derive(Clone, Serialize, Deserialize)]
+            struct UserCreated {
+                id: String,
+            }
+        };
+        let output = event_impl(quote! { derive }, input).unwrap().to_string();
+        assert!(!output.contains("allframe_core :: serde"));
+    }
+
+    #[test]
+    fn test_message_derives_are_opt_in() {
+        // Derives above the macro are invisible to it, so it must not add
+        // its own unless asked
+        let input = quote! {
+            struct CreateUser {
+                email: String,
+            }
+        };
+        let output = command_impl(TokenStream::new(), input.clone())
+            .unwrap()
+            .to_string();
+        assert!(!output.contains("derive"));
+
+        let output = command_impl(quote! { derive }, input.clone())
+            .unwrap()
+            .to_string();
+        assert!(output.contains(":: core :: clone :: Clone"));
+        assert!(output.contains("allframe_core :: serde :: Serialize"));
+
+        let err = query_impl(quote! { name = "x" }, input).unwrap_err();
+        assert!(err.to_string().contains("only accepts `derive`"));
+    }
+
+    #[test]
+    fn test_event_name_override() {
+        let input = quote! {
+            struct UserCreated {
+                id: String,
+            }
+        };
+        let output = event_impl(quote! { name = "user.created" }, input.clone())
+            .unwrap()
+            .to_string();
+        assert!(output.contains("\"user.created\""));
+
+        let output = event_impl(TokenStream::new(), input.clone())
+            .unwrap()
+            .to_string();
+        assert!(output.contains("\"UserCreated\""));
+
+        let err = event_impl(quote! { version = 2 }, input).unwrap_err();
+        assert!(err.to_string().contains("expected `name`"));
+    }
+
     #[test]
     fn test_event_rejects_function() {
         let input = quote! {
@@ -275,4 +601,48 @@ mod tests {
         let result = query_handler_impl(TokenStream::new(), input);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_command_handler_checks_command_type() {
+        let input = quote! {
+            async fn handle_create(cmd: &CreateUser, store: &Store) -> Result<(), Error> {
+                Ok(())
+            }
+        };
+        let output = command_handler_impl(TokenStream::new(), input)
+            .unwrap()
+            .to_string();
+        assert!(output.contains("assert_message :: < CreateUser >"));
+        assert!(!output.contains("inventory"));
+    }
+
+    #[test]
+    fn test_command_handler_registers_single_argument_handler() {
+        let input = quote! {
+            async fn handle_create(cmd: CreateUser) -> Result<Vec<UserEvent>, String> {
+                Ok(vec![])
+            }
+        };
+        let output = command_handler_impl(TokenStream::new(), input)
+            .unwrap()
+            .to_string();
+        assert!(output.contains("CommandHandler < CreateUser , UserEvent >"));
+        assert!(output.contains("handle_create (message) . await"));
+        assert!(output.contains("CommandHandlerRegistration :: new (\"handle_create\" , entry)"));
+    }
+
+    #[test]
+    fn test_query_handler_registers_single_argument_handler() {
+        let input = quote! {
+            fn handle_get(q: GetUser) -> QueryResult<Option<User>> {
+                Ok(None)
+            }
+        };
+        let output = query_handler_impl(TokenStream::new(), input)
+            .unwrap()
+            .to_string();
+        assert!(output.contains("assert_message :: < GetUser >"));
+        assert!(output.contains("QueryHandler < GetUser , Option < User > >"));
+        assert!(output.contains("handle_get (message) . map_err"));
+    }
 }
@@ -10,6 +10,8 @@
 //! - `#[provide(singleton)]` - Shared instance (default)
 //! - `#[provide(transient)]` - New instance on each access
 //! - `#[provide(async)]` - Async initialization
+//! - `#[provide(cfg = "feature = \"x\"")]` - Only include the field when the
+//!   cfg predicate holds
 //! - `#[depends(field1, field2)]` - Explicit dependencies
 //!
 //! Multiple options can be combined: `#[provide(singleton, async)]`
@@ -17,7 +19,7 @@
 use std::collections::{HashMap, HashSet};
 
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{quote, ToTokens};
 use syn::{parse2, Data, DeriveInput, Error, Fields, Result, Type};
 
 /// Configuration for a field's dependency injection behavior
@@ -31,6 +33,32 @@ struct ProvideConfig {
     singleton: bool,
     /// Whether initialization is async
     is_async: bool,
+    /// cfg predicate the field is gated on
+    cfg: Option<TokenStream>,
+}
+
+impl ProvideConfig {
+    /// `#[cfg(...)]` attribute for code referencing the field
+    fn cfg_attr(&self) -> Option<TokenStream> {
+        self.cfg.as_ref().map(|cfg| quote! { #[cfg(#cfg)] })
+    }
+
+    /// Whether only a cfg predicate was given
+    fn is_cfg_only(&self) -> bool {
+        self.cfg.is_some()
+            && self.custom_expr.is_none()
+            && !self.from_env
+            && self.singleton
+            && !self.is_async
+    }
+
+    /// Whether a field with this config is compiled whenever `other` is
+    fn available_with(&self, other: &ProvideConfig) -> bool {
+        match &self.cfg {
+            None => true,
+            Some(cfg) => other.cfg.as_ref().map(|c| c.to_string()) == Some(cfg.to_string()),
+        }
+    }
 }
 
 /// Represents information about a field in the DI container
@@ -50,6 +78,7 @@ fn parse_provide_attr(attr: &syn::Attribute) -> Result<ProvideConfig> {
         ..Default::default()
     };
 
+    let mut has_cfg = false;
     let result = attr.parse_nested_meta(|meta| {
         if meta.path.is_ident("from_env") {
             config.from_env = true;
@@ -63,6 +92,11 @@ fn parse_provide_attr(attr: &syn::Attribute) -> Result<ProvideConfig> {
         } else if meta.path.is_ident("async") {
             config.is_async = true;
             Ok(())
+        } else if meta.path.is_ident("cfg") {
+            has_cfg = true;
+            let value: syn::LitStr = meta.value()?.parse()?;
+            config.cfg = Some(value.parse::<syn::Meta>()?.into_token_stream());
+            Ok(())
         } else {
             // Unknown option - will try to parse as expression below
             Err(meta.error("unknown provide option"))
@@ -71,6 +105,7 @@ fn parse_provide_attr(attr: &syn::Attribute) -> Result<ProvideConfig> {
 
     match result {
         Ok(()) => Ok(config),
+        Err(err) if has_cfg => Err(err),
         Err(_) => {
             // If nested meta parsing fails, try to parse as expression
             // This handles #[provide(MyType::new())]
@@ -144,7 +179,15 @@ pub fn di_container_impl(_attr: TokenStream, item: TokenStream) -> Result<TokenS
 
         for attr in &field.attrs {
             if attr.path().is_ident("provide") {
-                config = parse_provide_attr(attr)?;
+                let parsed = parse_provide_attr(attr)?;
+                if parsed.is_cfg_only() {
+                    // A cfg-only #[provide] can be combined with another one
+                    config.cfg = parsed.cfg;
+                } else {
+                    let cfg = config.cfg.take();
+                    config = parsed;
+                    config.cfg = config.cfg.take().or(cfg);
+                }
             } else if attr.path().is_ident("depends") {
                 explicit_deps = parse_depends_attr(attr)?;
             }
@@ -181,6 +224,7 @@ pub fn di_container_impl(_attr: TokenStream, item: TokenStream) -> Result<TokenS
         let ty = &field_info.ty;
         let field_name_str = name.to_string();
         let config = &field_info.config;
+        let cfg_attr = config.cfg_attr();
 
         let init_expr = if let Some(expr) = &config.custom_expr {
             // Use the provided expression
@@ -232,16 +276,25 @@ pub fn di_container_impl(_attr: TokenStream, item: TokenStream) -> Result<TokenS
         // Wrap in Arc if this is a dependency of others
         if is_dependency_of_others.contains(&field_name_str) && config.singleton {
             let_bindings.push(quote! {
+                #cfg_attr
                 let #name = ::std::sync::Arc::new(#init_expr);
             });
         } else {
             let_bindings.push(quote! {
+                #cfg_attr
                 let #name = #init_expr;
             });
         }
     }
 
-    let struct_fields: Vec<_> = init_order.iter().map(|f| &f.name).collect();
+    let struct_fields: Vec<_> = init_order
+        .iter()
+        .map(|f| {
+            let name = &f.name;
+            let cfg_attr = f.config.cfg_attr();
+            quote! { #cfg_attr #name }
+        })
+        .collect();
 
     // Generate accessor methods
     let mut accessors = Vec::new();
@@ -249,10 +302,12 @@ pub fn di_container_impl(_attr: TokenStream, item: TokenStream) -> Result<TokenS
         let name = &field_info.name;
         let ty = &field_info.ty;
         let field_name_str = name.to_string();
+        let cfg_attr = field_info.config.cfg_attr();
 
         if is_dependency_of_others.contains(&field_name_str) && field_info.config.singleton {
             // Return cloned Arc for singleton dependencies
             accessors.push(quote! {
+                #cfg_attr
                 #vis fn #name(&self) -> ::std::sync::Arc<#ty> {
                     ::std::sync::Arc::clone(&self.#name)
                 }
@@ -262,12 +317,14 @@ pub fn di_container_impl(_attr: TokenStream, item: TokenStream) -> Result<TokenS
             let ty_inner = ty;
             if field_info.config.is_async {
                 accessors.push(quote! {
+                    #cfg_attr
                     #vis async fn #name(&self) -> Result<#ty_inner, ::allframe_core::di::DependencyError> {
                         <#ty_inner as ::allframe_core::di::AsyncInit>::init().await
                     }
                 });
             } else {
                 accessors.push(quote! {
+                    #cfg_attr
                     #vis fn #name(&self) -> #ty_inner {
                         #ty_inner::new()
                     }
@@ -275,6 +332,7 @@ pub fn di_container_impl(_attr: TokenStream, item: TokenStream) -> Result<TokenS
             }
         } else {
             accessors.push(quote! {
+                #cfg_attr
                 #vis fn #name(&self) -> &#ty {
                     &self.#name
                 }
@@ -329,6 +387,9 @@ pub fn di_container_impl(_attr: TokenStream, item: TokenStream) -> Result<TokenS
                 // Wrap singleton dependencies in Arc
                 if let Some(ident) = &field.ident {
                     let field_info = field_infos.iter().find(|f| &f.name == ident);
+                    if let Some(cfg_attr) = field_info.and_then(|info| info.config.cfg_attr()) {
+                        field.attrs.push(syn::parse_quote! { #cfg_attr });
+                    }
                     if let Some(info) = field_info {
                         if is_dependency_of_others.contains(&ident.to_string())
                             && info.config.singleton
@@ -372,6 +433,19 @@ fn compute_initialization_order(fields: &[FieldInfo]) -> Result<(Vec<FieldInfo>,
         if !field.explicit_deps.is_empty() {
             for dep in &field.explicit_deps {
                 let dep_name = dep.to_string();
+                if let Some(dep_field) = fields.iter().find(|f| f.name == *dep) {
+                    if !dep_field.config.available_with(&field.config) {
+                        let cfg = dep_field.config.cfg.as_ref().unwrap();
+                        return Err(Error::new_spanned(
+                            dep,
+                            format!(
+                                "`{}` depends on `{}`, which only exists with cfg({}); \
+                                 gate `{}` with the same #[provide(cfg = ...)]",
+                                field.name, dep, cfg, field.name
+                            ),
+                        ));
+                    }
+                }
                 forward_graph
                     .get_mut(&field_name)
                     .unwrap()
@@ -382,7 +456,7 @@ fn compute_initialization_order(fields: &[FieldInfo]) -> Result<(Vec<FieldInfo>,
             }
         } else if field.config.custom_expr.is_none() {
             // Only use heuristics if no explicit deps and no custom expression
-            let deps = find_dependencies(&field.ty, &field_name, fields);
+            let deps = find_dependencies(&field.ty, &field.config, fields);
             for dep in deps {
                 let dep_name = dep.name.to_string();
                 forward_graph
@@ -460,6 +534,9 @@ fn compute_initialization_order(fields: &[FieldInfo]) -> Result<(Vec<FieldInfo>,
 /// - A type containing "controller" depends on a prior type containing "service"
 /// - Types sharing a common 4-character prefix are considered related
 ///
+/// Fields gated with `#[provide(cfg = ...)]` are only considered by fields
+/// with the same cfg.
+///
 /// **Limitations**: This heuristic can produce false positives (e.g., two unrelated
 /// types that happen to share a prefix) or miss legitimate dependencies between
 /// types with non-standard names.
@@ -469,7 +546,7 @@ fn compute_initialization_order(fields: &[FieldInfo]) -> Result<(Vec<FieldInfo>,
 /// to explicitly declare dependency relationships.
 fn find_dependencies<'a>(
     ty: &Type,
-    _field_name: &str,
+    config: &ProvideConfig,
     all_fields: &'a [FieldInfo],
 ) -> Vec<&'a FieldInfo> {
     let mut deps = Vec::new();
@@ -494,6 +571,11 @@ fn find_dependencies<'a>(
             .unwrap_or(&current_type_str);
 
         for prev_field in &all_fields[..pos] {
+            // Never infer a dependency on a field that may be compiled out
+            if !prev_field.config.available_with(config) {
+                continue;
+            }
+
             let prev_type_str = {
                 let t = &prev_field.ty;
                 quote!(#t).to_string()
@@ -1,7 +1,9 @@
-//! Error derive macros for automatic gRPC status conversion
+//! Error derive macros for automatic gRPC and HTTP status conversion
 //!
 //! Provides the #[derive(GrpcError)] macro that automatically generates
-//! `From<Error> for tonic::Status` implementations.
+//! `From<Error> for tonic::Status` implementations, and the
+//! #[derive(HttpError)] macro that maps variants to HTTP status codes for the
+//! REST adapter.
 //!
 //! ## Usage
 //!
@@ -20,6 +22,26 @@
 //! }
 //! ```
 //!
+//! ## Catch-all Variant
+//!
+//! Mark one variant with `#[grpc(default)]` to map it with
+//! `router::status::from_error`, which picks a code from the wrapped error
+//! (I/O error kind, timeouts, "not found" messages) and falls back to
+//! `INTERNAL`. The enum must implement `std::error::Error`.
+//!
+//! ```rust,ignore
+//! #[derive(Debug, thiserror::Error, GrpcError)]
+//! pub enum MyError {
+//!     #[error("Not found: {0}")]
+//!     #[grpc(NOT_FOUND)]
+//!     NotFound(String),
+//!
+//!     #[error(transparent)]
+//!     #[grpc(default)]
+//!     Other(#[from] std::io::Error),
+//! }
+//! ```
+//!
 //! ## Custom Crate Path
 //!
 //! If you're using `allframe-core` directly instead of `allframe`, specify the
@@ -30,6 +52,27 @@
 //! #[grpc_error(crate = "allframe_core")]
 //! pub enum MyError { ... }
 //! ```
+//!
+//! ## HTTP Status Codes
+//!
+//! `#[http(...)]` takes either a numeric code or a status name; variants
+//! without an attribute map to `500`. Use `#[http_error(crate = "...")]` for a
+//! custom crate path.
+//!
+//! ```rust,ignore
+//! use allframe::HttpError;
+//!
+//! #[derive(Debug, thiserror::Error, HttpError)]
+//! pub enum MyError {
+//!     #[error("Not found: {0}")]
+//!     #[http(404)]
+//!     NotFound(String),
+//!
+//!     #[error("Invalid input")]
+//!     #[http(BAD_REQUEST)]
+//!     InvalidInput,
+//! }
+//! ```
 
 use proc_macro2::TokenStream;
 use quote::quote;
@@ -56,6 +99,34 @@ const VALID_CODES: &[&str] = &[
     "UNAUTHENTICATED",
 ];
 
+/// Named HTTP status codes accepted by `#[http(NAME)]`
+const HTTP_STATUS_NAMES: &[(&str, u16)] = &[
+    ("OK", 200),
+    ("CREATED", 201),
+    ("ACCEPTED", 202),
+    ("NO_CONTENT", 204),
+    ("BAD_REQUEST", 400),
+    ("UNAUTHORIZED", 401),
+    ("PAYMENT_REQUIRED", 402),
+    ("FORBIDDEN", 403),
+    ("NOT_FOUND", 404),
+    ("METHOD_NOT_ALLOWED", 405),
+    ("NOT_ACCEPTABLE", 406),
+    ("REQUEST_TIMEOUT", 408),
+    ("CONFLICT", 409),
+    ("GONE", 410),
+    ("PRECONDITION_FAILED", 412),
+    ("PAYLOAD_TOO_LARGE", 413),
+    ("UNSUPPORTED_MEDIA_TYPE", 415),
+    ("UNPROCESSABLE_ENTITY", 422),
+    ("TOO_MANY_REQUESTS", 429),
+    ("INTERNAL_SERVER_ERROR", 500),
+    ("NOT_IMPLEMENTED", 501),
+    ("BAD_GATEWAY", 502),
+    ("SERVICE_UNAVAILABLE", 503),
+    ("GATEWAY_TIMEOUT", 504),
+];
+
 /// Implement the GrpcError derive macro
 pub fn grpc_error_impl(input: TokenStream) -> Result<TokenStream> {
     let input = parse2::<DeriveInput>(input)?;
@@ -77,18 +148,42 @@ pub fn grpc_error_impl(input: TokenStream) -> Result<TokenStream> {
     };
 
     let mut match_arms = Vec::new();
+    let mut has_default = false;
 
     for variant in &data.variants {
         let variant_name = &variant.ident;
         let grpc_code =
             extract_grpc_code(&variant.attrs)?.unwrap_or_else(|| "INTERNAL".to_string());
 
+        // Generate pattern based on variant fields
+        let pattern = match &variant.fields {
+            Fields::Unit => quote! { #name::#variant_name },
+            Fields::Unnamed(_) => quote! { #name::#variant_name(..) },
+            Fields::Named(_) => quote! { #name::#variant_name { .. } },
+        };
+
+        if grpc_code == "DEFAULT" {
+            if has_default {
+                return Err(Error::new_spanned(
+                    variant,
+                    "Only one variant can be marked #[grpc(default)]",
+                ));
+            }
+            has_default = true;
+            match_arms.push(quote! {
+                #pattern => {
+                    ::#crate_ident::router::status::from_error(&err)
+                }
+            });
+            continue;
+        }
+
         // Validate the code
         if !VALID_CODES.contains(&grpc_code.as_str()) {
             return Err(Error::new_spanned(
                 variant,
                 format!(
-                    "Invalid gRPC code '{}'. Valid codes are: {}",
+                    "Invalid gRPC code '{}'. Valid codes are: {} (or `default`)",
                     grpc_code,
                     VALID_CODES.join(", ")
                 ),
@@ -97,13 +192,6 @@ pub fn grpc_error_impl(input: TokenStream) -> Result<TokenStream> {
 
         let code_ident = Ident::new(&grpc_code.to_lowercase(), variant_name.span());
 
-        // Generate pattern based on variant fields
-        let pattern = match &variant.fields {
-            Fields::Unit => quote! { #name::#variant_name },
-            Fields::Unnamed(_) => quote! { #name::#variant_name(..) },
-            Fields::Named(_) => quote! { #name::#variant_name { .. } },
-        };
-
         match_arms.push(quote! {
             #pattern => {
                 ::#crate_ident::tonic::Status::#code_ident(err.to_string())
@@ -124,10 +212,113 @@ pub fn grpc_error_impl(input: TokenStream) -> Result<TokenStream> {
     Ok(expanded)
 }
 
+/// Implement the HttpError derive macro
+pub fn http_error_impl(input: TokenStream) -> Result<TokenStream> {
+    let input = parse2::<DeriveInput>(input)?;
+    let name = &input.ident;
+
+    let crate_path = extract_crate_path_from(&input.attrs, "http_error")?
+        .unwrap_or_else(|| "allframe".to_string());
+    let crate_ident = Ident::new(&crate_path, proc_macro2::Span::call_site());
+
+    let data = match &input.data {
+        Data::Enum(data) => data,
+        _ => {
+            return Err(Error::new_spanned(
+                &input,
+                "HttpError can only be derived for enums",
+            ))
+        }
+    };
+
+    let mut match_arms = Vec::new();
+
+    for variant in &data.variants {
+        let variant_name = &variant.ident;
+        let status = extract_http_status(&variant.attrs)?.unwrap_or(500);
+
+        let pattern = match &variant.fields {
+            Fields::Unit => quote! { #name::#variant_name },
+            Fields::Unnamed(_) => quote! { #name::#variant_name(..) },
+            Fields::Named(_) => quote! { #name::#variant_name { .. } },
+        };
+
+        match_arms.push(quote! { #pattern => #status });
+    }
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let expanded = quote! {
+        impl #impl_generics ::#crate_ident::router::HttpError for #name #ty_generics #where_clause {
+            fn status_code(&self) -> u16 {
+                match self {
+                    #(#match_arms),*
+                }
+            }
+        }
+    };
+
+    Ok(expanded)
+}
+
+/// Extract the HTTP status from #[http(404)] or #[http(NOT_FOUND)]
+fn extract_http_status(attrs: &[Attribute]) -> Result<Option<u16>> {
+    for attr in attrs {
+        if !attr.path().is_ident("http") {
+            continue;
+        }
+
+        let expr: syn::Expr = attr.parse_args()?;
+        let status = match &expr {
+            syn::Expr::Lit(syn::ExprLit {
+                lit: Lit::Int(int), ..
+            }) => int.base10_parse::<u16>()?,
+            syn::Expr::Path(path) if path.path.get_ident().is_some() => {
+                let ident = path.path.get_ident().unwrap().to_string().to_uppercase();
+                match HTTP_STATUS_NAMES.iter().find(|(name, _)| *name == ident) {
+                    Some((_, code)) => *code,
+                    None => {
+                        let names: Vec<&str> = HTTP_STATUS_NAMES.iter().map(|(n, _)| *n).collect();
+                        return Err(Error::new_spanned(
+                            attr,
+                            format!(
+                                "Unknown HTTP status '{}'. Use a numeric code or one of: {}",
+                                ident,
+                                names.join(", ")
+                            ),
+                        ));
+                    }
+                }
+            }
+            _ => {
+                return Err(Error::new_spanned(
+                    attr,
+                    "Expected #[http(CODE)] with a numeric code or status name",
+                ))
+            }
+        };
+
+        if !(100..=599).contains(&status) {
+            return Err(Error::new_spanned(
+                attr,
+                format!("Invalid HTTP status code {}, expected 100-599", status),
+            ));
+        }
+
+        return Ok(Some(status));
+    }
+    Ok(None)
+}
+
 /// Extract the crate path from #[grpc_error(crate = "...")] attribute
 fn extract_crate_path(attrs: &[Attribute]) -> Result<Option<String>> {
+    extract_crate_path_from(attrs, "grpc_error")
+}
+
+/// Extract the crate path from a `#[<attr_name>(crate = "...")]` attribute
+fn extract_crate_path_from(attrs: &[Attribute], attr_name: &str) -> Result<Option<String>> {
     for attr in attrs {
-        if attr.path().is_ident("grpc_error") {
+        if attr.path().is_ident(attr_name) {
             // Parse #[grpc_error(crate = "path")]
             let meta = attr.parse_args::<Meta>()?;
             if let Meta::NameValue(nv) = meta {
@@ -163,6 +354,100 @@ fn extract_grpc_code(attrs: &[Attribute]) -> Result<Option<String>> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_default_variant_uses_from_error() {
+        let input = quote! {
+            enum AppError {
+                #[grpc(NOT_FOUND)]
+                NotFound(String),
+                #[grpc(default)]
+                Other(std::io::Error),
+            }
+        };
+
+        let output = grpc_error_impl(input).unwrap().to_string();
+        assert!(output.contains("tonic :: Status :: not_found"));
+        assert!(output.contains("router :: status :: from_error (& err)"));
+    }
+
+    #[test]
+    fn test_rejects_multiple_default_variants() {
+        let input = quote! {
+            enum AppError {
+                #[grpc(default)]
+                A(std::io::Error),
+                #[grpc(default)]
+                B(std::io::Error),
+            }
+        };
+
+        let err = grpc_error_impl(input).unwrap_err();
+        assert!(err.to_string().contains("Only one variant"));
+    }
+
+    #[test]
+    fn test_http_error_numeric_and_named_codes() {
+        let input = quote! {
+            enum UserError {
+                #[http(404)]
+                NotFound(String),
+                #[http(BAD_REQUEST)]
+                Invalid { field: String },
+                Internal,
+            }
+        };
+
+        let output = http_error_impl(input).unwrap().to_string();
+        assert!(output.contains("allframe :: router :: HttpError for UserError"));
+        assert!(output.contains("UserError :: NotFound (..) => 404u16"));
+        assert!(output.contains("UserError :: Invalid { .. } => 400u16"));
+        assert!(output.contains("UserError :: Internal => 500u16"));
+    }
+
+    #[test]
+    fn test_http_error_custom_crate_path() {
+        let input = quote! {
+            #[http_error(crate = "allframe_core")]
+            enum UserError {
+                #[http(CONFLICT)]
+                Duplicate,
+            }
+        };
+
+        let output = http_error_impl(input).unwrap().to_string();
+        assert!(output.contains(":: allframe_core :: router :: HttpError"));
+        assert!(output.contains("409u16"));
+    }
+
+    #[test]
+    fn test_http_error_rejects_invalid_codes() {
+        let unknown = quote! {
+            enum UserError {
+                #[http(TEAPOT)]
+                Teapot,
+            }
+        };
+        let err = http_error_impl(unknown).unwrap_err();
+        assert!(err.to_string().contains("Unknown HTTP status 'TEAPOT'"));
+
+        let out_of_range = quote! {
+            enum UserError {
+                #[http(999)]
+                Weird,
+            }
+        };
+        let err = http_error_impl(out_of_range).unwrap_err();
+        assert!(err.to_string().contains("expected 100-599"));
+    }
+
+    #[test]
+    fn test_http_error_rejects_structs() {
+        let input = quote! {
+            struct NotAnEnum;
+        };
+        assert!(http_error_impl(input).is_err());
+    }
+
     #[test]
     fn test_valid_codes() {
         assert!(VALID_CODES.contains(&"INTERNAL"));
@@ -1,11 +1,14 @@
 //! HealthCheck derive macro for automatic health check implementation
 //!
 //! Generates implementations of the HealthCheck trait by collecting
-//! all fields that implement the Dependency trait.
+//! all fields that implement the Dependency trait. Fields can be grouped
+//! with `#[health(group = "...")]`, and a struct-level
+//! `#[health(group = "...", quorum = N)]` sets how many members of a group
+//! must be healthy.
 
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{parse2, Data, DeriveInput, Error, Fields, Result};
+use syn::{parse2, Data, DeriveInput, Error, Fields, LitInt, LitStr, Result};
 
 /// Configuration for health check fields
 #[derive(Default)]
@@ -14,6 +17,8 @@ struct HealthFieldConfig {
     critical: Option<bool>,
     /// Timeout in seconds
     timeout_secs: Option<u64>,
+    /// Group of redundant dependencies this field belongs to
+    group: Option<LitStr>,
     /// Skip this field from health checks
     skip: bool,
 }
@@ -47,6 +52,9 @@ fn parse_health_attrs(attrs: &[syn::Attribute]) -> Result<HealthFieldConfig> {
                         .map_err(|e| syn::Error::new_spanned(&lit, e))?;
                     config.timeout_secs = Some(secs);
                     Ok(())
+                } else if meta.path.is_ident("group") {
+                    config.group = Some(meta.value()?.parse()?);
+                    Ok(())
                 } else {
                     Err(meta.error("unknown health attribute"))
                 }
@@ -57,6 +65,56 @@ fn parse_health_attrs(attrs: &[syn::Attribute]) -> Result<HealthFieldConfig> {
     Ok(config)
 }
 
+/// Parse struct-level `#[health(group = "...", quorum = N)]` attributes
+fn parse_group_quorums(attrs: &[syn::Attribute]) -> Result<Vec<(LitStr, usize)>> {
+    let mut quorums = Vec::new();
+
+    for attr in attrs {
+        if !attr.path().is_ident("health") {
+            continue;
+        }
+
+        let mut group: Option<LitStr> = None;
+        let mut quorum: Option<usize> = None;
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("group") {
+                group = Some(meta.value()?.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("quorum") {
+                let lit: LitInt = meta.value()?.parse()?;
+                let value: usize = lit.base10_parse()?;
+                if value == 0 {
+                    return Err(Error::new_spanned(lit, "quorum must be at least 1"));
+                }
+                quorum = Some(value);
+                Ok(())
+            } else {
+                Err(meta.error("expected `group` or `quorum`"))
+            }
+        })?;
+
+        match (group, quorum) {
+            (Some(group), Some(quorum)) => {
+                if quorums
+                    .iter()
+                    .any(|(g, _): &(LitStr, usize)| g.value() == group.value())
+                {
+                    return Err(Error::new_spanned(group, "duplicate quorum for group"));
+                }
+                quorums.push((group, quorum));
+            }
+            _ => {
+                return Err(Error::new_spanned(
+                    attr,
+                    "struct-level #[health] requires both `group` and `quorum`",
+                ))
+            }
+        }
+    }
+
+    Ok(quorums)
+}
+
 /// Parse a timeout string like "5s" or "10s" into seconds
 fn parse_timeout(s: &str) -> std::result::Result<u64, String> {
     let s = s.trim();
@@ -100,10 +158,11 @@ pub fn health_check_impl(input: TokenStream) -> Result<TokenStream> {
         }
     };
 
+    let quorums = parse_group_quorums(&input.attrs)?;
+
     // Collect fields that should be included in health checks
-    let mut dependency_refs = Vec::new();
-    let mut wrapper_structs = Vec::new();
-    let mut wrapper_field_inits = Vec::new();
+    let mut dependencies = Vec::new();
+    let mut groups = Vec::new();
 
     for field in fields {
         let field_name = field.ident.as_ref().unwrap();
@@ -113,146 +172,62 @@ pub fn health_check_impl(input: TokenStream) -> Result<TokenStream> {
             continue;
         }
 
-        // Generate a wrapper if timeout or critical is customized
-        if config.timeout_secs.is_some() || config.critical.is_some() {
-            let wrapper_name =
-                syn::Ident::new(&format!("__{}HealthWrapper", field_name), field_name.span());
-
-            let timeout_impl = if let Some(secs) = config.timeout_secs {
-                quote! {
-                    fn timeout(&self) -> ::std::time::Duration {
-                        ::std::time::Duration::from_secs(#secs)
-                    }
-                }
-            } else {
-                quote! {}
-            };
-
-            let critical_impl = if let Some(critical) = config.critical {
-                quote! {
-                    fn is_critical(&self) -> bool {
-                        #critical
-                    }
-                }
-            } else {
-                quote! {}
-            };
-
-            wrapper_structs.push(quote! {
-                struct #wrapper_name<'a, T: ::allframe_core::health::Dependency>(&'a T);
-
-                impl<'a, T: ::allframe_core::health::Dependency> ::allframe_core::health::Dependency for #wrapper_name<'a, T> {
-                    fn name(&self) -> &str {
-                        self.0.name()
-                    }
+        let critical = config
+            .critical
+            .map(|critical| quote! { .with_critical(#critical) });
+        let timeout = config.timeout_secs.map(|secs| {
+            quote! { .with_timeout(::std::time::Duration::from_secs(#secs)) }
+        });
+        let group = config
+            .group
+            .as_ref()
+            .map(|group| quote! { .with_group(#group) });
+        if let Some(group) = config.group {
+            groups.push(group.value());
+        }
 
-                    fn check(&self) -> ::std::pin::Pin<::std::boxed::Box<dyn ::std::future::Future<Output = ::allframe_core::health::DependencyStatus> + Send + '_>> {
-                        self.0.check()
-                    }
+        dependencies.push(quote! {
+            ::std::sync::Arc::new(
+                ::allframe_core::health::ConfiguredDependency::new(
+                    ::core::clone::Clone::clone(&self.#field_name)
+                )
+                #critical
+                #timeout
+                #group
+            )
+        });
+    }
 
-                    #timeout_impl
-                    #critical_impl
-                }
-            });
-
-            wrapper_field_inits.push(quote! {
-                ::std::sync::Arc::new(#wrapper_name(&self.#field_name))
-            });
-        } else {
-            // No wrapper needed, use field directly
-            dependency_refs.push(quote! {
-                ::std::sync::Arc::new(FieldRef(&self.#field_name))
-            });
+    for (group, _) in &quorums {
+        if !groups.contains(&group.value()) {
+            return Err(Error::new_spanned(
+                group,
+                format!("no field is in health group \"{}\"", group.value()),
+            ));
         }
     }
 
-    // Generate the implementation
-    let all_deps: Vec<_> = dependency_refs
-        .into_iter()
-        .chain(wrapper_field_inits)
-        .collect();
-
-    let expanded = quote! {
-        // Helper struct to wrap field references
-        struct FieldRef<'a, T: ::allframe_core::health::Dependency>(&'a T);
-
-        impl<'a, T: ::allframe_core::health::Dependency> ::allframe_core::health::Dependency for FieldRef<'a, T> {
-            fn name(&self) -> &str {
-                self.0.name()
-            }
-
-            fn check(&self) -> ::std::pin::Pin<::std::boxed::Box<dyn ::std::future::Future<Output = ::allframe_core::health::DependencyStatus> + Send + '_>> {
-                self.0.check()
-            }
-
-            fn is_critical(&self) -> bool {
-                self.0.is_critical()
-            }
-
-            fn timeout(&self) -> ::std::time::Duration {
-                self.0.timeout()
+    let group_quorum = (!quorums.is_empty()).then(|| {
+        let arms = quorums.iter().map(|(group, quorum)| {
+            quote! { #group => ::core::option::Option::Some(#quorum), }
+        });
+        quote! {
+            fn group_quorum(&self, group: &str) -> ::core::option::Option<usize> {
+                match group {
+                    #(#arms)*
+                    _ => ::core::option::Option::None,
+                }
             }
         }
+    });
 
-        #(#wrapper_structs)*
-
+    let expanded = quote! {
         impl #impl_generics ::allframe_core::health::HealthCheck for #name #ty_generics #where_clause {
             fn dependencies(&self) -> ::std::vec::Vec<::std::sync::Arc<dyn ::allframe_core::health::Dependency>> {
-                vec![#(#all_deps),*]
+                ::std::vec![#(#dependencies),*]
             }
 
-            fn check_all(&self) -> ::std::pin::Pin<::std::boxed::Box<dyn ::std::future::Future<Output = ::allframe_core::health::HealthReport> + Send + '_>> {
-                ::std::boxed::Box::pin(async move {
-                    let start = ::std::time::Instant::now();
-                    let deps = <Self as ::allframe_core::health::HealthCheck>::dependencies(self);
-                    let mut results = ::std::vec::Vec::new();
-                    let mut overall = ::allframe_core::health::OverallStatus::Healthy;
-
-                    for dep in &deps {
-                        let dep_start = ::std::time::Instant::now();
-                        let name = dep.name().to_string();
-                        let is_critical = dep.is_critical();
-                        let timeout = dep.timeout();
-
-                        let status = match ::tokio::time::timeout(timeout, dep.check()).await {
-                            Ok(status) => status,
-                            Err(_) => ::allframe_core::health::DependencyStatus::Unhealthy(
-                                "timeout".to_string()
-                            ),
-                        };
-
-                        let duration = dep_start.elapsed();
-
-                        // Update overall status
-                        match &status {
-                            ::allframe_core::health::DependencyStatus::Unhealthy(_) if is_critical => {
-                                overall = ::allframe_core::health::OverallStatus::Unhealthy;
-                            }
-                            ::allframe_core::health::DependencyStatus::Degraded(_) if overall != ::allframe_core::health::OverallStatus::Unhealthy => {
-                                overall = ::allframe_core::health::OverallStatus::Degraded;
-                            }
-                            ::allframe_core::health::DependencyStatus::Unhealthy(_) if overall == ::allframe_core::health::OverallStatus::Healthy => {
-                                overall = ::allframe_core::health::OverallStatus::Degraded;
-                            }
-                            _ => {}
-                        }
-
-                        results.push(::allframe_core::health::DependencyReport {
-                            name,
-                            status,
-                            duration,
-                            critical: is_critical,
-                        });
-                    }
-
-                    ::allframe_core::health::HealthReport {
-                        status: overall,
-                        dependencies: results,
-                        total_duration: start.elapsed(),
-                        timestamp: ::std::time::SystemTime::now(),
-                    }
-                })
-            }
+            #group_quorum
         }
     };
 
@@ -270,4 +245,53 @@ mod tests {
         assert_eq!(parse_timeout(" 5s ").unwrap(), 5);
         assert!(parse_timeout("invalid").is_err());
     }
+
+    #[test]
+    fn test_groups_and_quorum() {
+        let input = quote! {
+            #[health(group = "cache", quorum = 1)]
+            struct AppHealth {
+                #[health(group = "cache")]
+                redis_a: Arc<RedisDependency>,
+                #[health(group = "cache", critical = false)]
+                redis_b: Arc<RedisDependency>,
+                #[health(skip)]
+                config: Config,
+            }
+        };
+
+        let output = health_check_impl(input).unwrap().to_string();
+        assert!(output.contains(". with_group (\"cache\")"));
+        assert!(output.contains(". with_critical (false)"));
+        assert!(output.contains("\"cache\" => :: core :: option :: Option :: Some (1usize)"));
+        assert!(!output.contains("config"));
+    }
+
+    #[test]
+    fn test_quorum_requires_known_group() {
+        let input = quote! {
+            #[health(group = "cahce", quorum = 1)]
+            struct AppHealth {
+                #[health(group = "cache")]
+                redis: Arc<RedisDependency>,
+            }
+        };
+
+        let err = health_check_impl(input).unwrap_err().to_string();
+        assert!(err.contains("no field is in health group \"cahce\""));
+    }
+
+    #[test]
+    fn test_quorum_must_be_positive() {
+        let input = quote! {
+            #[health(group = "cache", quorum = 0)]
+            struct AppHealth {
+                #[health(group = "cache")]
+                redis: Arc<RedisDependency>,
+            }
+        };
+
+        let err = health_check_impl(input).unwrap_err().to_string();
+        assert!(err.contains("quorum must be at least 1"));
+    }
 }
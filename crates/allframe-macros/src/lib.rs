@@ -8,6 +8,7 @@
 mod allframe_handler;
 mod api;
 mod arch;
+mod config;
 mod cqrs;
 mod di;
 mod error;
@@ -15,6 +16,7 @@ mod health;
 mod otel;
 mod resilience;
 mod saga;
+mod schema;
 mod security;
 mod tauri_compat;
 
@@ -35,8 +37,14 @@ use proc_macro::TokenStream;
 /// - `#[provide(singleton)]` - Shared instance (default)
 /// - `#[provide(transient)]` - New instance on each access
 /// - `#[provide(async)]` - Async initialization using `AsyncInit` trait
+/// - `#[provide(cfg = "feature = \"cache-redis\"")]` - Only include the
+///   field (and its accessor) when the cfg predicate holds
 /// - `#[depends(field1, field2)]` - Explicit dependencies
 ///
+/// A field can only depend on a cfg-gated field if it is gated on the same
+/// predicate. `cfg` can be combined with other options, or given in a
+/// separate `#[provide]`.
+///
 /// # Example (Sync)
 /// ```ignore
 /// #[di_container]
@@ -65,6 +73,18 @@ use proc_macro::TokenStream;
 ///
 /// let container = AppContainer::build().await?;
 /// ```
+///
+/// # Example (Feature-gated)
+/// ```ignore
+/// #[di_container]
+/// struct AppContainer {
+///     settings: Settings,
+///
+///     #[provide(cfg = "feature = \"cache-redis\"")]
+///     #[provide(RedisCache::connect())]
+///     cache: RedisCache,
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn di_container(attr: TokenStream, item: TokenStream) -> TokenStream {
     let attr = proc_macro2::TokenStream::from(attr);
@@ -77,7 +97,13 @@ pub fn di_container(attr: TokenStream, item: TokenStream) -> TokenStream {
 
 /// API handler with auto OpenAPI generation
 ///
-/// Generates OpenAPI 3.1 schema for the annotated function.
+/// Generates OpenAPI 3.1 schema for the annotated function, and a
+/// `{name}_register_into(&mut Router)` function that registers the handler
+/// as `METHOD:path` along with its `RouteMetadata` (path, method,
+/// description, and request/response schemas for `ToJsonSchema` types).
+///
+/// The handler takes at most one argument, deserialized from the request
+/// payload. A `Result<T, E>` return needs `E: Display`.
 ///
 /// # Example
 /// ```ignore
@@ -86,8 +112,13 @@ pub fn di_container(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///     // handler implementation
 /// }
 ///
-/// // Generated function:
+/// // Generated functions:
 /// // fn create_user_openapi_schema() -> String { /* JSON schema */ }
+/// // fn create_user_register_into(router: &mut Router) { /* handler + route */ }
+///
+/// let mut router = Router::new();
+/// create_user_register_into(&mut router);
+/// assert_eq!(router.routes()[0].path, "/users");
 /// ```
 #[proc_macro_attribute]
 pub fn api_handler(attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -191,6 +222,8 @@ pub fn handler(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// Marks a struct as a Command (CQRS write operation)
 ///
 /// Commands represent write operations that change state and produce events.
+/// `#[command(derive)]` also derives `Clone`, `Serialize` and `Deserialize`
+/// unless the struct already derives them.
 ///
 /// # Example
 /// ```ignore
@@ -213,6 +246,8 @@ pub fn command(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// Marks a struct as a Query (CQRS read operation)
 ///
 /// Queries represent read operations that don't change state.
+/// `#[query(derive)]` also derives `Clone`, `Serialize` and `Deserialize`
+/// unless the struct already derives them.
 ///
 /// # Example
 /// ```ignore
@@ -234,6 +269,9 @@ pub fn query(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// Marks an enum or struct as an Event
 ///
 /// Events represent immutable facts that have occurred in the system.
+/// Implements `EventTypeName` with the type's name. `#[event(derive)]` also
+/// derives `Clone`, `Serialize` and `Deserialize` unless the type already
+/// derives them.
 ///
 /// # Example
 /// ```ignore
@@ -242,6 +280,13 @@ pub fn query(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///     Created { user_id: String, email: String },
 ///     Updated { user_id: String, email: String },
 /// }
+///
+/// // Stored as "user.renamed" even if the type is renamed later
+/// #[event(name = "user.renamed", derive)]
+/// struct UserRenamed {
+///     user_id: String,
+///     name: String,
+/// }
 /// ```
 #[proc_macro_attribute]
 pub fn event(attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -255,7 +300,12 @@ pub fn event(attr: TokenStream, item: TokenStream) -> TokenStream {
 
 /// Marks a function as a Command Handler
 ///
-/// Command handlers process commands and produce events.
+/// Command handlers process commands and produce events. The first argument
+/// must be a `#[command]` type, checked at compile time.
+///
+/// A handler whose only argument is the command and which returns
+/// `Result<Vec<E>, Err>` (with `Err: Into<CommandError>`) is registered for
+/// `CommandBus::auto_register`, so no manual `register` call is needed.
 ///
 /// # Example
 /// ```ignore
@@ -263,6 +313,9 @@ pub fn event(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// async fn handle_create_user(cmd: CreateUserCommand) -> Result<Vec<Event>, String> {
 ///     Ok(vec![Event::UserCreated { ... }])
 /// }
+///
+/// let bus = CommandBus::<Event>::new();
+/// bus.auto_register().await;
 /// ```
 #[proc_macro_attribute]
 pub fn command_handler(attr: TokenStream, item: TokenStream) -> TokenStream {
@@ -276,7 +329,12 @@ pub fn command_handler(attr: TokenStream, item: TokenStream) -> TokenStream {
 
 /// Marks a function as a Query Handler
 ///
-/// Query handlers process queries and return data from projections.
+/// Query handlers process queries and return data from projections. The
+/// first argument must be a `#[query]` type, checked at compile time.
+///
+/// A handler whose only argument is the query and which returns
+/// `Result<R, Err>` (with `Err: Into<QueryError>`) is registered for
+/// `QueryBus::auto_register`.
 ///
 /// # Example
 /// ```ignore
@@ -345,6 +403,10 @@ pub fn traced(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///     #[error("Internal error: {0}")]
 ///     #[grpc(INTERNAL)]
 ///     Internal(String),
+///
+///     #[error(transparent)]
+///     #[grpc(default)]
+///     Other(#[from] std::io::Error),
 /// }
 ///
 /// // Auto-generates: impl From<AppError> for tonic::Status
@@ -356,7 +418,12 @@ pub fn traced(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// - `PERMISSION_DENIED`, `RESOURCE_EXHAUSTED`, `FAILED_PRECONDITION`
 /// - `ABORTED`, `OUT_OF_RANGE`, `UNIMPLEMENTED`, `INTERNAL`
 /// - `UNAVAILABLE`, `DATA_LOSS`, `UNAUTHENTICATED`
-#[proc_macro_derive(GrpcError, attributes(grpc))]
+///
+/// `#[grpc(default)]` marks a single catch-all variant that is mapped with
+/// `router::status::from_error` (timeouts → `DEADLINE_EXCEEDED`, not-found →
+/// `NOT_FOUND`, otherwise `INTERNAL`); the enum must implement
+/// `std::error::Error`. Variants without an attribute map to `INTERNAL`.
+#[proc_macro_derive(GrpcError, attributes(grpc, grpc_error))]
 pub fn grpc_error(input: TokenStream) -> TokenStream {
     let input = proc_macro2::TokenStream::from(input);
 
@@ -365,10 +432,87 @@ pub fn grpc_error(input: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Derive macro mapping error variants to HTTP status codes
+///
+/// Implements `allframe::router::HttpError`, which provides
+/// `status_code()` and a `RestResponse`-building `into_response()`.
+/// Use `#[http(CODE)]` on variants with either a numeric code or a status
+/// name; variants without an attribute map to `500`.
+///
+/// # Example
+/// ```ignore
+/// use allframe::HttpError;
+/// use thiserror::Error;
+///
+/// #[derive(Error, Debug, HttpError)]
+/// pub enum UserError {
+///     #[error("User not found: {0}")]
+///     #[http(404)]
+///     NotFound(String),
+///
+///     #[error("Invalid email: {0}")]
+///     #[http(BAD_REQUEST)]
+///     InvalidEmail(String),
+///
+///     #[error("Database error")]
+///     Database,
+/// }
+///
+/// // UserError::NotFound("42".into()).status_code() == 404
+/// ```
+#[proc_macro_derive(HttpError, attributes(http, http_error))]
+pub fn http_error(input: TokenStream) -> TokenStream {
+    let input = proc_macro2::TokenStream::from(input);
+
+    error::http_error_impl(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Derive macro loading a configuration struct from layered sources
+///
+/// Implements `allframe_core::di::FromEnv` with
+/// `allframe_core::config::ConfigLoader`, so the struct can be used with
+/// `#[provide(from_env)]`. Values are merged from the `Default` impl (with
+/// `defaults`), then the config files in order, then environment variables
+/// with the prefix. The struct must implement `Deserialize`, and
+/// `Serialize + Default` with `defaults`.
+///
+/// # Example
+/// ```ignore
+/// use allframe_core::di::FromEnv;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Default, Serialize, Deserialize, FromEnv)]
+/// #[config(prefix = "APP", file = "config.toml", defaults)]
+/// struct AppConfig {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// // APP_PORT=9090 overrides `port` from config.toml
+/// let config = AppConfig::from_env()?;
+/// ```
+///
+/// # Attributes
+/// - `#[config(prefix = "APP")]` - Environment variable prefix (required)
+/// - `#[config(file = "config.toml")]` - Config file, skipped if missing;
+///   may be repeated
+/// - `#[config(defaults)]` - Start from `Default::default()`
+#[proc_macro_derive(FromEnv, attributes(config))]
+pub fn from_env(input: TokenStream) -> TokenStream {
+    let input = proc_macro2::TokenStream::from(input);
+
+    config::from_env_impl(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
 /// Derive macro for automatic HealthCheck implementation
 ///
 /// Generates the `HealthCheck` trait implementation by collecting all fields
-/// that implement the `Dependency` trait.
+/// that implement the `Dependency` trait. Fields are cloned into the
+/// dependency list, so wrap dependencies that aren't `Clone` in an `Arc`.
 ///
 /// # Example
 /// ```ignore
@@ -382,12 +526,16 @@ pub fn grpc_error(input: TokenStream) -> TokenStream {
 /// impl Dependency for DatabaseDependency { /* ... */ }
 ///
 /// #[derive(HealthCheck)]
+/// #[health(group = "cache", quorum = 1)]
 /// struct AppHealth {
-///     #[health(timeout = "5s", critical = true)]
-///     redis: RedisDependency,
+///     #[health(group = "cache")]
+///     redis_primary: Arc<RedisDependency>,
+///
+///     #[health(group = "cache")]
+///     redis_replica: Arc<RedisDependency>,
 ///
 ///     #[health(timeout = "10s", critical = false)]
-///     database: DatabaseDependency,
+///     database: Arc<DatabaseDependency>,
 ///
 ///     #[health(skip)]
 ///     config: Config, // Not a dependency
@@ -401,6 +549,10 @@ pub fn grpc_error(input: TokenStream) -> TokenStream {
 /// - `#[health(skip)]` - Skip this field from health checks
 /// - `#[health(critical = true)]` - Mark dependency as critical (default: true)
 /// - `#[health(timeout = "5s")]` - Set check timeout (default: 5s)
+/// - `#[health(group = "cache")]` - Put the dependency in a group of redundant
+///   dependencies, reported as one unit
+/// - `#[health(group = "cache", quorum = 1)]` (on the struct) - Healthy members
+///   the group needs to be healthy (default: all of them)
 #[proc_macro_derive(HealthCheck, attributes(health))]
 pub fn health_check(input: TokenStream) -> TokenStream {
     let input = proc_macro2::TokenStream::from(input);
@@ -442,7 +594,13 @@ pub fn health_check(input: TokenStream) -> TokenStream {
 ///
 /// # Attributes
 /// - `#[sensitive]` - Mark field as sensitive, will be displayed as `***`
+/// - `#[sensitive(reveal = 4)]` - Show only the first and last 4 characters
+///   (`sk_l***c123`); short values are displayed as `***`
+/// - `#[sensitive(hash)]` - Display a short stable hash (`#9f0c22e1`), so
+///   values can be correlated across log lines without being revealed
 /// - `#[obfuscate(with = "function_name")]` - Use custom function to obfuscate
+///
+/// The `reveal` and `hash` modes format the field with `Display`.
 #[proc_macro_derive(Obfuscate, attributes(sensitive, obfuscate))]
 pub fn obfuscate(input: TokenStream) -> TokenStream {
     let input = proc_macro2::TokenStream::from(input);
@@ -452,19 +610,74 @@ pub fn obfuscate(input: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Derive macro for automatic ToJsonSchema implementation
+///
+/// Generates an `allframe_core::router::ToJsonSchema` implementation whose
+/// schema matches the type's serde JSON representation, for use in OpenAPI
+/// request/response documentation and MCP tool input schemas.
+///
+/// # Example
+/// ```ignore
+/// use allframe_core::router::ToJsonSchema;
+///
+/// #[derive(Serialize, Deserialize, ToJsonSchema)]
+/// #[schema(description = "A user account")]
+/// struct CreateUser {
+///     #[schema(description = "Display name", example = "Alice")]
+///     name: String,
+///     #[schema(example = 30)]
+///     age: Option<u32>, // optional: not listed in "required"
+///     roles: Vec<Role>, // array of the nested schema
+/// }
+///
+/// #[derive(Serialize, Deserialize, ToJsonSchema)]
+/// #[serde(rename_all = "lowercase")]
+/// enum Role {
+///     Admin, // unit-only enums become string enums: ["admin", "member"]
+///     Member,
+/// }
+/// ```
+///
+/// # Mapping
+/// - Structs with named fields become `object` schemas; `Option<T>` fields
+///   (and `#[serde(default)]` fields) are not required
+/// - Newtype structs use the inner type's schema
+/// - Unit-only enums become string enums; other enums become `oneOf` in
+///   serde's externally tagged form
+/// - `#[serde(rename)]`, `#[serde(rename_all)]` and `#[serde(skip)]` are
+///   honored
+///
+/// # Attributes
+/// - `#[schema(description = "...")]` - Description on the type or a field
+/// - `#[schema(example = <value>)]` - Example value for a field (any
+///   `serde_json::json!` expression)
+#[proc_macro_derive(ToJsonSchema, attributes(schema))]
+pub fn to_json_schema(input: TokenStream) -> TokenStream {
+    let input = proc_macro2::TokenStream::from(input);
+
+    schema::to_json_schema_impl(input)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
 /// Attribute macro for automatic retry with exponential backoff
 ///
-/// Wraps an async function with retry logic using `RetryExecutor`.
+/// Wraps an async function with retry logic using `RetryConfig` backoff.
 ///
 /// # Example
 /// ```ignore
 /// use allframe_macros::retry;
 ///
-/// #[retry(max_retries = 3, initial_interval_ms = 100)]
-/// async fn fetch_data() -> Result<String, std::io::Error> {
-///     // This will be retried up to 3 times on failure
+/// fn is_transient(error: &reqwest::Error) -> bool {
+///     error.is_timeout() || error.status().map_or(true, |s| s.is_server_error())
+/// }
+///
+/// #[retry(max_retries = 3, initial_interval_ms = 100, retry_if = is_transient)]
+/// async fn fetch_data() -> Result<String, reqwest::Error> {
+///     // Timeouts and 5xx responses are retried up to 3 times
 ///     reqwest::get("https://api.example.com/data")
 ///         .await?
+///         .error_for_status()?
 ///         .text()
 ///         .await
 /// }
@@ -475,6 +688,9 @@ pub fn obfuscate(input: TokenStream) -> TokenStream {
 /// - `initial_interval_ms` - Initial backoff in milliseconds (default: 500)
 /// - `max_interval_ms` - Maximum backoff in milliseconds (default: 30000)
 /// - `multiplier` - Backoff multiplier (default: 2.0)
+/// - `retry_if` - Path to a `fn(&E) -> bool` deciding whether an error is
+///   retried (default: retry every error)
+/// - `jitter` - Randomize backoff intervals (default: true)
 #[proc_macro_attribute]
 pub fn retry(attr: TokenStream, item: TokenStream) -> TokenStream {
     let attr = proc_macro2::TokenStream::from(attr);
@@ -494,10 +710,17 @@ pub fn retry(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// use allframe_macros::circuit_breaker;
 ///
 /// #[circuit_breaker(name = "external_api", failure_threshold = 5)]
-/// async fn call_external_api() -> Result<String, std::io::Error> {
+/// async fn call_external_api() -> Result<String, ApiError> {
 ///     // After 5 failures, the circuit opens and calls fail fast
+///     // with `ApiError::from(CircuitOpenError)`
 ///     external_service::call().await
 /// }
+///
+/// #[circuit_breaker(fallback = Ok(cache.get(id)), fallback_on_error)]
+/// async fn get_user(cache: &Cache, id: u64) -> Result<User, ApiError> {
+///     // Serves cached data while the circuit is open or the call fails
+///     user_service::get(id).await
+/// }
 /// ```
 ///
 /// # Parameters
@@ -505,6 +728,9 @@ pub fn retry(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// - `failure_threshold` - Failures before opening (default: 5)
 /// - `success_threshold` - Successes to close in half-open (default: 3)
 /// - `timeout_ms` - Time before half-open in milliseconds (default: 30000)
+/// - `fallback` - Expression returned while the circuit is open; it may use
+///   the function's arguments (default: `Err(CircuitOpenError.into())`)
+/// - `fallback_on_error` - Also return the fallback when the call fails
 #[proc_macro_attribute]
 pub fn circuit_breaker(attr: TokenStream, item: TokenStream) -> TokenStream {
     let attr = proc_macro2::TokenStream::from(attr);
@@ -524,15 +750,24 @@ pub fn circuit_breaker(attr: TokenStream, item: TokenStream) -> TokenStream {
 /// use allframe_macros::rate_limited;
 ///
 /// #[rate_limited(rps = 100, burst = 10)]
-/// fn handle_request() -> Result<Response, std::io::Error> {
-///     // Limited to 100 requests per second with burst of 10
+/// fn handle_request() -> Result<Response, ApiError> {
+///     // Limited to 100 requests per second with burst of 10; rejected calls
+///     // return `ApiError::from(RateLimitError { retry_after })`
 ///     process_request()
 /// }
+///
+/// #[rate_limited(rps = 10, burst = 20, key = request.client_id)]
+/// fn handle_client(request: &Request) -> Result<Response, ApiError> {
+///     // Each client gets its own bucket
+///     process(request)
+/// }
 /// ```
 ///
 /// # Parameters
 /// - `rps` - Requests per second (default: 100)
 /// - `burst` - Burst capacity (default: 10)
+/// - `key` - Expression over the function's arguments selecting a per-key
+///   bucket; must implement `Display` (default: one bucket for all calls)
 #[proc_macro_attribute]
 pub fn rate_limited(attr: TokenStream, item: TokenStream) -> TokenStream {
     let attr = proc_macro2::TokenStream::from(attr);
@@ -543,6 +778,68 @@ pub fn rate_limited(attr: TokenStream, item: TokenStream) -> TokenStream {
         .into()
 }
 
+/// Attribute macro for per-attempt timeouts
+///
+/// Cancels the function body if it runs longer than the given duration.
+///
+/// # Example
+/// ```ignore
+/// use allframe_macros::timeout;
+///
+/// #[timeout(ms = 500)]
+/// async fn fetch_quote() -> Result<Quote, ApiError> {
+///     // Returns `ApiError::from(TimeoutError { duration })` after 500ms
+///     quote_service::latest().await
+/// }
+/// ```
+///
+/// # Parameters
+/// - `ms` - Timeout in milliseconds (required)
+///
+/// # Composition
+/// `#[timeout]`, `#[retry]` and `#[circuit_breaker]` can be stacked in any
+/// order and always nest the same way: the timeout bounds each attempt, retry
+/// wraps the timed attempts, and the circuit breaker wraps the whole retry
+/// loop, recording one success or failure per call.
+#[proc_macro_attribute]
+pub fn timeout(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attr = proc_macro2::TokenStream::from(attr);
+    let item = proc_macro2::TokenStream::from(item);
+
+    resilience::timeout_impl(attr, item)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
+/// Attribute macro combining retry, circuit breaker and timeout
+///
+/// Each layer takes the same parameters as its standalone macro and is
+/// optional. Layers nest as timeout (innermost, per attempt), then retry,
+/// then circuit breaker (outermost).
+///
+/// # Example
+/// ```ignore
+/// use allframe_macros::resilient;
+///
+/// #[resilient(
+///     retry(max_retries = 3, retry_if = is_transient),
+///     circuit_breaker(failure_threshold = 5, fallback = Ok(cache.get(id))),
+///     timeout(ms = 500),
+/// )]
+/// async fn get_user(cache: &Cache, id: u64) -> Result<User, ApiError> {
+///     user_service::get(id).await
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn resilient(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attr = proc_macro2::TokenStream::from(attr);
+    let item = proc_macro2::TokenStream::from(item);
+
+    resilience::resilient_impl(attr, item)
+        .unwrap_or_else(|err| err.to_compile_error())
+        .into()
+}
+
 /// Marks a struct as a saga step with automatic Debug and SagaStep trait
 /// implementation
 ///
@@ -1,10 +1,18 @@
 //! Resilience-related macros for AllFrame.
 //!
-//! Provides attribute macros for retry, circuit breaker, and rate limiting.
-
-use proc_macro2::TokenStream;
+//! Provides attribute macros for retry, circuit breaker, timeout, and rate
+//! limiting.
+//!
+//! `#[retry]`, `#[circuit_breaker]` and `#[timeout]` compose in a fixed order
+//! regardless of how the attributes are stacked: the timeout applies to each
+//! attempt, retry wraps the timed attempts, and the circuit breaker wraps the
+//! whole retry loop (recording one outcome per call). The first of these
+//! attributes to expand consumes the others, so they always generate a
+//! single wrapper. `#[resilient(...)]` configures all three in one attribute.
+
+use proc_macro2::{Ident, Span, TokenStream};
 use quote::quote;
-use syn::{parse2, ItemFn, LitInt, LitStr};
+use syn::{parse2, Attribute, Expr, ItemFn, LitBool, LitInt, LitStr, Path, ReturnType};
 
 /// Configuration parsed from `#[retry(...)]` attributes.
 #[derive(Default)]
@@ -13,6 +21,8 @@ struct RetryConfig {
     initial_interval_ms: Option<u64>,
     max_interval_ms: Option<u64>,
     multiplier: Option<f64>,
+    retry_if: Option<Path>,
+    jitter: Option<bool>,
 }
 
 /// Implementation of the `#[retry]` attribute macro.
@@ -48,78 +58,18 @@ struct RetryConfig {
 /// };
 /// let result = orchestrator.execute_with_policy(policy, || fetch_data()).await;
 /// ```
+///
+/// The function must be async and return a `Result`. Its body is re-run with
+/// exponential backoff while it fails and `retry_if` (a predicate on `&E`,
+/// retrying every error by default) returns `true`; the last result is
+/// returned as is.
 pub fn retry_impl(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
-    let config = parse_retry_attr(attr)?;
-    let func: ItemFn = parse2(item)?;
-
-    let visibility = &func.vis;
-    let sig = &func.sig;
-    let block = &func.block;
-    let attrs = &func.attrs;
-
-    // Build retry policy configuration
-    let max_retries = config.max_retries.unwrap_or(3);
-    let initial_interval_ms = config.initial_interval_ms.unwrap_or(500);
-    let max_interval_ms = config.max_interval_ms.unwrap_or(30000);
-    let multiplier = config.multiplier.unwrap_or(2.0);
-
-    // Generate deprecation warning
-    let deprecation_warning = quote! {
-        #[deprecated(
-            since = "0.1.13",
-            note = "The #[retry] macro uses legacy architecture. Consider migrating to the new Clean Architecture resilience system. See: https://docs.allframe.rs/guides/MIGRATION_GUIDE.html"
-        )]
+    let stack = ResilienceStack {
+        retry: Some(parse_retry_attr(attr)?),
+        legacy: Some("retry"),
+        ..Default::default()
     };
-
-    Ok(quote! {
-        #deprecation_warning
-        #(#attrs)*
-        #visibility #sig {
-            // Import the new architectural components
-            #[cfg(feature = "resilience")]
-            {
-                use allframe_core::application::resilience::{ResilienceOrchestrator, DefaultResilienceOrchestrator};
-                use allframe_core::domain::resilience::{ResiliencePolicy, BackoffStrategy};
-
-                // Create orchestrator (lazy initialization for performance)
-                static ORCHESTRATOR: std::sync::OnceLock<std::sync::Arc<dyn ResilienceOrchestrator + Send + Sync>> = std::sync::OnceLock::new();
-                let orchestrator = ORCHESTRATOR.get_or_init(|| {
-                    std::sync::Arc::new(DefaultResilienceOrchestrator::new())
-                });
-
-                // Build the retry policy using new architecture
-                let policy = ResiliencePolicy::Retry {
-                    max_attempts: #max_retries,
-                    backoff: BackoffStrategy::Exponential {
-                        initial_delay: std::time::Duration::from_millis(#initial_interval_ms),
-                        multiplier: #multiplier,
-                        max_delay: Some(std::time::Duration::from_millis(#max_interval_ms)),
-                        jitter: true,
-                    },
-                };
-
-                // Execute with new architecture
-                // The orchestration returns the same Result type as the original function
-                // This maintains backward compatibility
-                match orchestrator.execute_with_policy(policy, || async {
-                    #block
-                }).await {
-                    Ok(result) => result,
-                    Err(_) => {
-                        // For backward compatibility, panic on orchestration errors
-                        // The old macro would typically let the operation's own errors bubble up
-                        panic!("Resilience orchestration failed");
-                    }
-                }
-            }
-
-            // Fallback for when resilience features are not enabled
-            #[cfg(not(feature = "resilience"))]
-            {
-                compile_error!("The #[retry] macro requires the 'resilience' feature. Enable it in Cargo.toml or migrate to the new Clean Architecture approach.");
-            }
-        }
-    })
+    expand_stack(stack, parse2(item)?)
 }
 
 fn parse_retry_attr(attr: TokenStream) -> syn::Result<RetryConfig> {
@@ -142,14 +92,18 @@ fn parse_retry_attr(attr: TokenStream) -> syn::Result<RetryConfig> {
         } else if meta.path.is_ident("multiplier") {
             let value: syn::LitFloat = meta.value()?.parse()?;
             config.multiplier = Some(value.base10_parse()?);
+        } else if meta.path.is_ident("retry_if") {
+            config.retry_if = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("jitter") {
+            let value: LitBool = meta.value()?.parse()?;
+            config.jitter = Some(value.value);
+        } else {
+            return Err(meta.error("unsupported retry parameter"));
         }
         Ok(())
     });
 
-    parse2::<syn::parse::Nothing>(attr.clone())
-        .ok()
-        .map(|_| ())
-        .or_else(|| syn::parse::Parser::parse2(parser, attr).ok());
+    syn::parse::Parser::parse2(parser, attr)?;
 
     Ok(config)
 }
@@ -161,6 +115,8 @@ struct CircuitBreakerConfig {
     failure_threshold: Option<u32>,
     success_threshold: Option<u32>,
     timeout_ms: Option<u64>,
+    fallback: Option<Expr>,
+    fallback_on_error: bool,
 }
 
 /// Implementation of the `#[circuit_breaker]` attribute macro.
@@ -171,70 +127,19 @@ struct CircuitBreakerConfig {
 /// `ResiliencePolicy::CircuitBreaker { failure_threshold: 5, .. }` passed to
 /// `ResilienceOrchestrator::execute_with_policy()`. See `#[retry]` docs for
 /// a full migration example.
+///
+/// The function must return a `Result`. Each annotated function gets its own
+/// `CircuitBreaker`; while it is open, the function returns the `fallback`
+/// expression (which is evaluated in the function body and may use its
+/// arguments) or, without a fallback, `Err(CircuitOpenError.into())`. With
+/// `fallback_on_error`, the fallback is also returned when the body fails.
 pub fn circuit_breaker_impl(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
-    let config = parse_circuit_breaker_attr(attr)?;
-    let func: ItemFn = parse2(item)?;
-
-    let visibility = &func.vis;
-    let sig = &func.sig;
-    let block = &func.block;
-    let attrs = &func.attrs;
-
-    let failure_threshold = config.failure_threshold.unwrap_or(5);
-    let success_threshold = config.success_threshold.unwrap_or(3);
-    let timeout_ms = config.timeout_ms.unwrap_or(30000);
-
-    // Generate deprecation warning for circuit breaker macro
-    let deprecation_warning = quote! {
-        #[deprecated(
-            since = "0.1.13",
-            note = "The #[circuit_breaker] macro uses legacy architecture. Consider migrating to the new Clean Architecture resilience system. See: https://docs.allframe.rs/guides/MIGRATION_GUIDE.html"
-        )]
+    let stack = ResilienceStack {
+        circuit_breaker: Some(parse_circuit_breaker_attr(attr)?),
+        legacy: Some("circuit_breaker"),
+        ..Default::default()
     };
-
-    Ok(quote! {
-        #deprecation_warning
-        #(#attrs)*
-        #visibility #sig {
-            // Import the new architectural components
-            #[cfg(feature = "resilience")]
-            {
-                use allframe_core::application::resilience::{ResilienceOrchestrator, DefaultResilienceOrchestrator};
-                use allframe_core::domain::resilience::ResiliencePolicy;
-                use std::time::Duration;
-
-                // Create orchestrator (lazy initialization for performance)
-                static ORCHESTRATOR: std::sync::OnceLock<std::sync::Arc<dyn ResilienceOrchestrator + Send + Sync>> = std::sync::OnceLock::new();
-                let orchestrator = ORCHESTRATOR.get_or_init(|| {
-                    std::sync::Arc::new(DefaultResilienceOrchestrator::new())
-                });
-
-                // Build the circuit breaker policy using new architecture
-                let policy = ResiliencePolicy::CircuitBreaker {
-                    failure_threshold: #failure_threshold,
-                    recovery_timeout: Duration::from_millis(#timeout_ms),
-                    success_threshold: #success_threshold,
-                };
-
-                // Execute with new architecture
-                match orchestrator.execute_with_policy(policy, || async {
-                    #block
-                }).await {
-                    Ok(result) => result,
-                    Err(_) => {
-                        // For backward compatibility, panic on circuit breaker errors
-                        panic!("Circuit breaker error in legacy macro");
-                    }
-                }
-            }
-
-            // Fallback for when resilience features are not enabled
-            #[cfg(not(feature = "resilience"))]
-            {
-                compile_error!("The #[circuit_breaker] macro requires the 'resilience' feature. Enable it in Cargo.toml or migrate to the new Clean Architecture approach.");
-            }
-        }
-    })
+    expand_stack(stack, parse2(item)?)
 }
 
 fn parse_circuit_breaker_attr(attr: TokenStream) -> syn::Result<CircuitBreakerConfig> {
@@ -257,23 +162,414 @@ fn parse_circuit_breaker_attr(attr: TokenStream) -> syn::Result<CircuitBreakerCo
         } else if meta.path.is_ident("timeout_ms") {
             let value: LitInt = meta.value()?.parse()?;
             config.timeout_ms = Some(value.base10_parse()?);
+        } else if meta.path.is_ident("fallback") {
+            config.fallback = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("fallback_on_error") {
+            config.fallback_on_error = true;
+        } else {
+            return Err(meta.error("unsupported circuit_breaker parameter"));
         }
         Ok(())
     });
 
-    parse2::<syn::parse::Nothing>(attr.clone())
-        .ok()
-        .map(|_| ())
-        .or_else(|| syn::parse::Parser::parse2(parser, attr).ok());
+    syn::parse::Parser::parse2(parser, attr)?;
+
+    if config.fallback_on_error && config.fallback.is_none() {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "`fallback_on_error` requires a `fallback` expression",
+        ));
+    }
 
     Ok(config)
 }
 
+/// Configuration parsed from `#[timeout(...)]` attributes.
+struct TimeoutConfig {
+    ms: u64,
+}
+
+/// Implementation of the `#[timeout]` attribute macro.
+///
+/// The function must be async and return a `Result` whose error type
+/// implements `From<TimeoutError>`. Each attempt of the body that runs longer
+/// than `ms` milliseconds is cancelled and returns `Err(TimeoutError.into())`.
+pub fn timeout_impl(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let stack = ResilienceStack {
+        timeout: Some(parse_timeout_attr(attr)?),
+        ..Default::default()
+    };
+    expand_stack(stack, parse2(item)?)
+}
+
+fn parse_timeout_attr(attr: TokenStream) -> syn::Result<TimeoutConfig> {
+    let mut ms = None;
+
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("ms") {
+            let value: LitInt = meta.value()?.parse()?;
+            ms = Some(value.base10_parse()?);
+            Ok(())
+        } else {
+            Err(meta.error("unsupported timeout parameter"))
+        }
+    });
+
+    syn::parse::Parser::parse2(parser, attr)?;
+
+    match ms {
+        Some(ms) => Ok(TimeoutConfig { ms }),
+        None => Err(syn::Error::new(
+            Span::call_site(),
+            "#[timeout] requires a duration, e.g. `#[timeout(ms = 500)]`",
+        )),
+    }
+}
+
+/// Implementation of the `#[resilient]` attribute macro.
+///
+/// Configures retry, circuit breaker and timeout in one attribute, each with
+/// the parameters of its standalone macro:
+///
+/// ```ignore
+/// #[resilient(
+///     retry(max_retries = 3, retry_if = is_transient),
+///     circuit_breaker(failure_threshold = 5, fallback = Ok(cached(id))),
+///     timeout(ms = 500),
+/// )]
+/// async fn get_user(id: u64) -> Result<User, ApiError> { /* ... */ }
+/// ```
+pub fn resilient_impl(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
+    let mut stack = ResilienceStack::default();
+
+    let parser = syn::meta::parser(|meta| {
+        let args = if meta.input.peek(syn::token::Paren) {
+            let content;
+            syn::parenthesized!(content in meta.input);
+            content.parse::<TokenStream>()?
+        } else {
+            TokenStream::new()
+        };
+
+        if meta.path.is_ident("retry") {
+            stack.set_retry(parse_retry_attr(args)?, meta.path.clone())
+        } else if meta.path.is_ident("circuit_breaker") {
+            stack.set_circuit_breaker(parse_circuit_breaker_attr(args)?, meta.path.clone())
+        } else if meta.path.is_ident("timeout") {
+            stack.set_timeout(parse_timeout_attr(args)?, meta.path.clone())
+        } else {
+            Err(meta.error("expected `retry`, `circuit_breaker` or `timeout`"))
+        }
+    });
+
+    syn::parse::Parser::parse2(parser, attr)?;
+
+    expand_stack(stack, parse2(item)?)
+}
+
+/// The resilience layers applied to one function
+#[derive(Default)]
+struct ResilienceStack {
+    retry: Option<RetryConfig>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    timeout: Option<TimeoutConfig>,
+    /// Deprecated attribute that produced the stack, if any
+    legacy: Option<&'static str>,
+}
+
+impl ResilienceStack {
+    fn set_retry(&mut self, config: RetryConfig, span: impl quote::ToTokens) -> syn::Result<()> {
+        if self.retry.replace(config).is_some() {
+            return Err(syn::Error::new_spanned(
+                span,
+                "duplicate retry configuration",
+            ));
+        }
+        Ok(())
+    }
+
+    fn set_circuit_breaker(
+        &mut self,
+        config: CircuitBreakerConfig,
+        span: impl quote::ToTokens,
+    ) -> syn::Result<()> {
+        if self.circuit_breaker.replace(config).is_some() {
+            return Err(syn::Error::new_spanned(
+                span,
+                "duplicate circuit_breaker configuration",
+            ));
+        }
+        Ok(())
+    }
+
+    fn set_timeout(
+        &mut self,
+        config: TimeoutConfig,
+        span: impl quote::ToTokens,
+    ) -> syn::Result<()> {
+        if self.timeout.replace(config).is_some() {
+            return Err(syn::Error::new_spanned(
+                span,
+                "duplicate timeout configuration",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Move stacked `#[retry]`, `#[circuit_breaker]` and `#[timeout]`
+    /// attributes from the function into the stack
+    fn absorb(&mut self, attrs: &mut Vec<Attribute>) -> syn::Result<()> {
+        let mut kept = Vec::with_capacity(attrs.len());
+
+        for attr in attrs.drain(..) {
+            let Some(name) = resilience_attr_name(&attr) else {
+                kept.push(attr);
+                continue;
+            };
+            let args = match &attr.meta {
+                syn::Meta::Path(_) => TokenStream::new(),
+                syn::Meta::List(list) => list.tokens.clone(),
+                syn::Meta::NameValue(_) => {
+                    return Err(syn::Error::new_spanned(
+                        &attr,
+                        "expected parameters in parentheses",
+                    ))
+                }
+            };
+
+            match name.as_str() {
+                "retry" => {
+                    self.set_retry(parse_retry_attr(args)?, &attr)?;
+                    self.legacy.get_or_insert("retry");
+                }
+                "circuit_breaker" => {
+                    self.set_circuit_breaker(parse_circuit_breaker_attr(args)?, &attr)?;
+                    self.legacy.get_or_insert("circuit_breaker");
+                }
+                _ => self.set_timeout(parse_timeout_attr(args)?, &attr)?,
+            }
+        }
+
+        *attrs = kept;
+        Ok(())
+    }
+}
+
+/// Name of a resilience attribute that takes part in composition
+///
+/// Matches `#[retry]`, `#[circuit_breaker]` and `#[timeout]`, either bare or
+/// through an AllFrame crate path, so unrelated attributes such as
+/// `#[other_crate::timeout]` are left alone.
+fn resilience_attr_name(attr: &Attribute) -> Option<String> {
+    let segments = &attr.path().segments;
+    let last = segments.last()?.ident.to_string();
+    if !matches!(last.as_str(), "retry" | "circuit_breaker" | "timeout") {
+        return None;
+    }
+
+    let allframe_path = segments.iter().rev().skip(1).all(|segment| {
+        matches!(
+            segment.ident.to_string().as_str(),
+            "allframe" | "allframe_core" | "allframe_macros"
+        )
+    });
+    allframe_path.then_some(last)
+}
+
+/// Generate the function wrapped in every layer of the stack
+fn expand_stack(mut stack: ResilienceStack, mut func: ItemFn) -> syn::Result<TokenStream> {
+    stack.absorb(&mut func.attrs)?;
+
+    let visibility = &func.vis;
+    let sig = &func.sig;
+    let block = &func.block;
+    let attrs = &func.attrs;
+
+    let output = match &sig.output {
+        ReturnType::Type(_, ty) => ty,
+        ReturnType::Default => {
+            return Err(syn::Error::new_spanned(
+                sig,
+                "resilience macros require a function returning Result",
+            ))
+        }
+    };
+    if sig.asyncness.is_none() && (stack.retry.is_some() || stack.timeout.is_some()) {
+        let name = if stack.retry.is_some() {
+            "retry"
+        } else {
+            "timeout"
+        };
+        return Err(syn::Error::new_spanned(
+            sig.fn_token,
+            format!("#[{}] requires an async function", name),
+        ));
+    }
+
+    // Locals use mixed-site hygiene so they can't shadow the function's
+    // arguments inside the body or a fallback expression
+    let breaker = Ident::new("circuit_breaker", Span::mixed_site());
+    let retry_config = Ident::new("retry_config", Span::mixed_site());
+    let attempt = Ident::new("attempt", Span::mixed_site());
+    let error = Ident::new("error", Span::mixed_site());
+    let result = Ident::new("result", Span::mixed_site());
+
+    // A single attempt, run in its own async block / closure so that `return`
+    // and `?` in the body don't skip the outer layers
+    let mut call = if sig.asyncness.is_some() {
+        let attempt_future = quote! {
+            async {
+                let #result: #output = #block;
+                #result
+            }
+        };
+        match &stack.timeout {
+            Some(TimeoutConfig { ms }) => quote! {
+                match ::allframe_core::resilience::with_timeout(
+                    ::std::time::Duration::from_millis(#ms),
+                    #attempt_future,
+                )
+                .await
+                {
+                    ::core::result::Result::Ok(#result) => #result,
+                    ::core::result::Result::Err(#error) => {
+                        ::core::result::Result::Err(::core::convert::Into::into(#error))
+                    }
+                }
+            },
+            None => quote! { #attempt_future.await },
+        }
+    } else {
+        quote! { (|| -> #output #block)() }
+    };
+
+    if let Some(config) = &stack.retry {
+        let max_retries = config.max_retries.unwrap_or(3);
+        let initial_interval_ms = config.initial_interval_ms.unwrap_or(500);
+        let max_interval_ms = config.max_interval_ms.unwrap_or(30000);
+        let multiplier = config.multiplier.unwrap_or(2.0);
+
+        let jitter = (!config.jitter.unwrap_or(true)).then(|| {
+            quote! { .with_randomization_factor(0.0) }
+        });
+        let retryable = match &config.retry_if {
+            Some(predicate) => quote! {
+                ::core::result::Result::Err(#error) => {
+                    #attempt < #retry_config.max_retries && #predicate(#error)
+                }
+            },
+            None => quote! {
+                ::core::result::Result::Err(_) => #attempt < #retry_config.max_retries,
+            },
+        };
+
+        call = quote! {{
+            let #retry_config = ::allframe_core::resilience::RetryConfig::new(#max_retries)
+                .with_initial_interval(::std::time::Duration::from_millis(#initial_interval_ms))
+                .with_max_interval(::std::time::Duration::from_millis(#max_interval_ms))
+                .with_multiplier(#multiplier)
+                #jitter;
+
+            let mut #attempt: u32 = 0;
+            loop {
+                let #result: #output = #call;
+
+                let retry = match &#result {
+                    #retryable
+                    ::core::result::Result::Ok(_) => false,
+                };
+                if !retry {
+                    break #result;
+                }
+                // Drop the error before sleeping so the future stays `Send`
+                ::core::mem::drop(#result);
+
+                ::allframe_core::tokio::time::sleep(#retry_config.calculate_interval(#attempt)).await;
+                #attempt += 1;
+            }
+        }};
+    }
+
+    let body = match &stack.circuit_breaker {
+        Some(config) => {
+            let name = config.name.clone().unwrap_or_else(|| sig.ident.to_string());
+            let failure_threshold = config.failure_threshold.unwrap_or(5);
+            let success_threshold = config.success_threshold.unwrap_or(3);
+            let timeout_ms = config.timeout_ms.unwrap_or(30000);
+
+            let on_open = match &config.fallback {
+                Some(fallback) => quote! {
+                    if #breaker.check().is_err() {
+                        return #fallback;
+                    }
+                },
+                None => quote! {
+                    if let ::core::result::Result::Err(#error) = #breaker.check() {
+                        return ::core::result::Result::Err(::core::convert::Into::into(#error));
+                    }
+                },
+            };
+            let on_error = match &config.fallback {
+                Some(fallback) if config.fallback_on_error => quote! {
+                    if #result.is_err() {
+                        return #fallback;
+                    }
+                },
+                _ => quote! {},
+            };
+
+            quote! {
+                static CIRCUIT_BREAKER: ::std::sync::OnceLock<::allframe_core::resilience::CircuitBreaker> =
+                    ::std::sync::OnceLock::new();
+                let #breaker = CIRCUIT_BREAKER.get_or_init(|| {
+                    ::allframe_core::resilience::CircuitBreaker::new(
+                        #name,
+                        ::allframe_core::resilience::CircuitBreakerConfig::new(#failure_threshold)
+                            .with_success_threshold(#success_threshold)
+                            .with_timeout(::std::time::Duration::from_millis(#timeout_ms)),
+                    )
+                });
+
+                #on_open
+
+                let #result: #output = #call;
+                match &#result {
+                    ::core::result::Result::Ok(_) => #breaker.record_success(),
+                    ::core::result::Result::Err(_) => #breaker.record_failure(),
+                }
+                #on_error
+                #result
+            }
+        }
+        None => call,
+    };
+
+    // Generate deprecation warning for the legacy macros
+    let deprecation_warning = stack.legacy.map(|name| {
+        let note = format!(
+            "The #[{}] macro uses legacy architecture. Consider migrating to the new Clean Architecture resilience system. See: https://docs.allframe.rs/guides/MIGRATION_GUIDE.html",
+            name
+        );
+        quote! {
+            #[deprecated(since = "0.1.13", note = #note)]
+        }
+    });
+
+    Ok(quote! {
+        #deprecation_warning
+        #(#attrs)*
+        #visibility #sig {
+            #body
+        }
+    })
+}
+
 /// Configuration parsed from `#[rate_limited(...)]` attributes.
 #[derive(Default)]
 struct RateLimitConfig {
     rps: Option<u32>,
     burst: Option<u32>,
+    key: Option<Expr>,
 }
 
 /// Implementation of the `#[rate_limited]` attribute macro.
@@ -284,6 +580,13 @@ struct RateLimitConfig {
 /// `ResiliencePolicy::RateLimited { max_requests: 100, window: Duration::from_secs(1) }`
 /// passed to `ResilienceOrchestrator::execute_with_policy()`. See `#[retry]` docs
 /// for a full migration example.
+///
+/// The function must return a `Result` whose error type implements
+/// `From<RateLimitError>`; rejected calls return `Err(RateLimitError.into())`
+/// without running the body. With `key = expr`, the expression (which may use
+/// the function's arguments and must implement `Display`) selects a bucket of
+/// a per-function `KeyedRateLimiter`; otherwise all calls share one
+/// `RateLimiter`.
 pub fn rate_limited_impl(attr: TokenStream, item: TokenStream) -> syn::Result<TokenStream> {
     let config = parse_rate_limit_attr(attr)?;
     let func: ItemFn = parse2(item)?;
@@ -296,6 +599,29 @@ pub fn rate_limited_impl(attr: TokenStream, item: TokenStream) -> syn::Result<To
     let rps = config.rps.unwrap_or(100);
     let burst = config.burst.unwrap_or(10);
 
+    let limiter = Ident::new("rate_limiter", Span::mixed_site());
+    let error = Ident::new("error", Span::mixed_site());
+
+    let check = match &config.key {
+        Some(key) => quote! {
+            static RATE_LIMITER: ::std::sync::OnceLock<
+                ::allframe_core::resilience::KeyedRateLimiter<::std::string::String>,
+            > = ::std::sync::OnceLock::new();
+            let #limiter = RATE_LIMITER.get_or_init(|| {
+                ::allframe_core::resilience::KeyedRateLimiter::new(#rps, #burst)
+            });
+            #limiter.check(&::std::string::ToString::to_string(&(#key)))
+        },
+        None => quote! {
+            static RATE_LIMITER: ::std::sync::OnceLock<::allframe_core::resilience::RateLimiter> =
+                ::std::sync::OnceLock::new();
+            let #limiter = RATE_LIMITER.get_or_init(|| {
+                ::allframe_core::resilience::RateLimiter::new(#rps, #burst)
+            });
+            #limiter.check()
+        },
+    };
+
     // Generate deprecation warning for rate limiting macro
     let deprecation_warning = quote! {
         #[deprecated(
@@ -308,41 +634,10 @@ pub fn rate_limited_impl(attr: TokenStream, item: TokenStream) -> syn::Result<To
         #deprecation_warning
         #(#attrs)*
         #visibility #sig {
-            // Import the new architectural components
-            #[cfg(feature = "resilience")]
-            {
-                use allframe_core::application::resilience::{ResilienceOrchestrator, DefaultResilienceOrchestrator};
-                use allframe_core::domain::resilience::ResiliencePolicy;
-
-                // Create orchestrator (lazy initialization for performance)
-                static ORCHESTRATOR: std::sync::OnceLock<std::sync::Arc<dyn ResilienceOrchestrator + Send + Sync>> = std::sync::OnceLock::new();
-                let orchestrator = ORCHESTRATOR.get_or_init(|| {
-                    std::sync::Arc::new(DefaultResilienceOrchestrator::new())
-                });
-
-                // Build the rate limiting policy using new architecture
-                let policy = ResiliencePolicy::RateLimit {
-                    requests_per_second: #rps,
-                    burst_capacity: #burst,
-                };
-
-                // Execute with new architecture
-                match orchestrator.execute_with_policy(policy, || async {
-                    #block
-                }).await {
-                    Ok(result) => result,
-                    Err(_) => {
-                        // For backward compatibility, panic on rate limiting errors
-                        panic!("Rate limiting error in legacy macro");
-                    }
-                }
-            }
-
-            // Fallback for when resilience features are not enabled
-            #[cfg(not(feature = "resilience"))]
-            {
-                compile_error!("The #[rate_limited] macro requires the 'resilience' feature. Enable it in Cargo.toml or migrate to the new Clean Architecture approach.");
+            if let ::core::result::Result::Err(#error) = { #check } {
+                return ::core::result::Result::Err(::core::convert::Into::into(#error));
             }
+            #block
         }
     })
 }
@@ -361,14 +656,15 @@ fn parse_rate_limit_attr(attr: TokenStream) -> syn::Result<RateLimitConfig> {
         } else if meta.path.is_ident("burst") {
             let value: LitInt = meta.value()?.parse()?;
             config.burst = Some(value.base10_parse()?);
+        } else if meta.path.is_ident("key") {
+            config.key = Some(meta.value()?.parse()?);
+        } else {
+            return Err(meta.error("unsupported rate_limited parameter"));
         }
         Ok(())
     });
 
-    parse2::<syn::parse::Nothing>(attr.clone())
-        .ok()
-        .map(|_| ())
-        .or_else(|| syn::parse::Parser::parse2(parser, attr).ok());
+    syn::parse::Parser::parse2(parser, attr)?;
 
     Ok(config)
 }
@@ -389,8 +685,40 @@ mod tests {
         let result = retry_impl(attr, item);
         assert!(result.is_ok());
         let output = result.unwrap().to_string();
-        assert!(output.contains("ResilienceOrchestrator"));
-        assert!(output.contains("ResiliencePolicy"));
+        assert!(output.contains("RetryConfig"));
+        assert!(output.contains("calculate_interval"));
+    }
+
+    #[test]
+    fn test_retry_predicate_and_jitter() {
+        let attr = quote! { max_retries = 2, retry_if = ApiError::is_transient, jitter = false };
+        let item = quote! {
+            async fn fetch_data() -> Result<String, ApiError> {
+                Ok("data".to_string())
+            }
+        };
+
+        let output = retry_impl(attr, item).unwrap().to_string();
+        assert!(output.contains("ApiError :: is_transient (error)"));
+        assert!(output.contains("with_randomization_factor (0.0)"));
+    }
+
+    #[test]
+    fn test_retry_rejects_invalid_config() {
+        let item = quote! {
+            async fn fetch_data() -> Result<(), std::io::Error> { Ok(()) }
+        };
+        let err = retry_impl(quote! { retry_if = "transient" }, item.clone()).unwrap_err();
+        assert!(err.to_string().contains("expected"));
+
+        let err = retry_impl(quote! { backoff = 5 }, item).unwrap_err();
+        assert!(err.to_string().contains("unsupported retry parameter"));
+
+        let sync_fn = quote! {
+            fn fetch_data() -> Result<(), std::io::Error> { Ok(()) }
+        };
+        let err = retry_impl(TokenStream::new(), sync_fn).unwrap_err();
+        assert!(err.to_string().contains("requires an async function"));
     }
 
     #[test]
@@ -408,6 +736,86 @@ mod tests {
         assert!(output.contains("CircuitBreaker"));
     }
 
+    #[test]
+    fn test_circuit_breaker_fallback() {
+        let attr = quote! { failure_threshold = 2, fallback = Ok(cached(id)), fallback_on_error };
+        let item = quote! {
+            async fn get_user(id: u64) -> Result<String, std::io::Error> {
+                fetch(id).await
+            }
+        };
+
+        let output = circuit_breaker_impl(attr, item).unwrap().to_string();
+        assert!(output.contains("return Ok (cached (id))"));
+        assert!(output.contains("is_err ()"));
+    }
+
+    #[test]
+    fn test_circuit_breaker_rejects_invalid_config() {
+        let item = quote! {
+            fn call_api() -> Result<(), std::io::Error> { Ok(()) }
+        };
+        let err = circuit_breaker_impl(quote! { fallback_on_error }, item.clone()).unwrap_err();
+        assert!(err.to_string().contains("requires a `fallback`"));
+
+        let err = circuit_breaker_impl(quote! { threshold = 5 }, item).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("unsupported circuit_breaker parameter"));
+
+        let err = circuit_breaker_impl(TokenStream::new(), quote! { fn ping() {} }).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("require a function returning Result"));
+    }
+
+    #[test]
+    fn test_stacked_attributes_are_absorbed() {
+        let item = quote! {
+            #[retry(max_retries = 2)]
+            #[other::timeout(5)]
+            #[allframe::circuit_breaker(failure_threshold = 1)]
+            async fn call_api() -> Result<(), ApiError> { Ok(()) }
+        };
+
+        let output = timeout_impl(quote! { ms = 100 }, item).unwrap().to_string();
+        assert!(output.contains("with_timeout"));
+        assert!(output.contains("calculate_interval"));
+        assert!(output.contains("CircuitBreaker"));
+        assert!(output.contains("# [other :: timeout (5)]"));
+        assert!(!output.contains("# [retry"));
+        assert!(output.contains("deprecated"));
+    }
+
+    #[test]
+    fn test_resilient_rejects_invalid_config() {
+        let item = quote! {
+            #[timeout(ms = 5)]
+            async fn call_api() -> Result<(), ApiError> { Ok(()) }
+        };
+        let err = resilient_impl(quote! { timeout(ms = 10) }, item).unwrap_err();
+        assert!(err.to_string().contains("duplicate timeout configuration"));
+
+        let item = quote! {
+            async fn call_api() -> Result<(), ApiError> { Ok(()) }
+        };
+        let err = resilient_impl(quote! { bulkhead(max = 5) }, item.clone()).unwrap_err();
+        assert!(err.to_string().contains("expected `retry`"));
+
+        let err = resilient_impl(quote! { timeout }, item).unwrap_err();
+        assert!(err.to_string().contains("requires a duration"));
+
+        let output = resilient_impl(
+            quote! { retry, circuit_breaker },
+            quote! {
+                async fn call_api() -> Result<(), ApiError> { Ok(()) }
+            },
+        )
+        .unwrap()
+        .to_string();
+        assert!(!output.contains("deprecated"));
+    }
+
     #[test]
     fn test_rate_limited_impl_basic() {
         let attr = TokenStream::new();
@@ -420,7 +828,26 @@ mod tests {
         let result = rate_limited_impl(attr, item);
         assert!(result.is_ok());
         let output = result.unwrap().to_string();
-        assert!(output.contains("ResilienceOrchestrator"));
-        assert!(output.contains("RateLimit"));
+        assert!(output.contains("RateLimiter"));
+        assert!(!output.contains("KeyedRateLimiter"));
+    }
+
+    #[test]
+    fn test_rate_limited_keyed() {
+        let attr = quote! { rps = 5, burst = 5, key = request.tenant_id };
+        let item = quote! {
+            async fn handle(request: &Request) -> Result<(), ApiError> {
+                Ok(())
+            }
+        };
+
+        let output = rate_limited_impl(attr, item).unwrap().to_string();
+        assert!(output.contains("KeyedRateLimiter"));
+        assert!(output.contains("request . tenant_id"));
+
+        let err = rate_limited_impl(quote! { per_key = true }, quote! { fn f() {} }).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("unsupported rate_limited parameter"));
     }
 }
@@ -0,0 +1,406 @@
+//! JSON Schema derive macro for request/response types.
+//!
+//! Provides `#[derive(ToJsonSchema)]`, generating an
+//! `allframe_core::router::ToJsonSchema` implementation that mirrors the
+//! type's serde JSON representation.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{
+    parse2, parse_quote, Attribute, Data, DeriveInput, Expr, Field, Fields, GenericParam, LitStr,
+    Type,
+};
+
+/// Implementation of the `#[derive(ToJsonSchema)]` macro.
+pub fn to_json_schema_impl(input: TokenStream) -> syn::Result<TokenStream> {
+    let mut input: DeriveInput = parse2(input)?;
+    let name = &input.ident;
+    let name_str = name.to_string();
+
+    let container = SchemaAttrs::parse(&input.attrs)?;
+    let serde = SerdeAttrs::parse(&input.attrs)?;
+
+    let body = match &input.data {
+        Data::Struct(data) => fields_schema(&data.fields, serde.rename_all.as_deref())?,
+        Data::Enum(data) => {
+            let rename_all = serde.rename_all.as_deref();
+            let unit_only = data
+                .variants
+                .iter()
+                .all(|v| matches!(v.fields, Fields::Unit));
+
+            if unit_only {
+                let names = data
+                    .variants
+                    .iter()
+                    .map(|v| variant_name(&v.ident.to_string(), &v.attrs, rename_all))
+                    .collect::<syn::Result<Vec<_>>>()?;
+                quote! {
+                    ::allframe_core::serde_json::json!({
+                        "type": "string",
+                        "enum": [#(#names),*]
+                    })
+                }
+            } else {
+                // Externally tagged, matching serde's default enum representation
+                let mut variants = Vec::new();
+                for variant in &data.variants {
+                    let tag = variant_name(&variant.ident.to_string(), &variant.attrs, rename_all)?;
+                    let schema = match &variant.fields {
+                        Fields::Unit => {
+                            variants.push(quote! {
+                                ::allframe_core::serde_json::json!({
+                                    "type": "string",
+                                    "enum": [#tag]
+                                })
+                            });
+                            continue;
+                        }
+                        fields => fields_schema(fields, None)?,
+                    };
+                    variants.push(quote! {
+                        ::allframe_core::serde_json::json!({
+                            "type": "object",
+                            "properties": { #tag: (#schema) },
+                            "required": [#tag],
+                            "additionalProperties": false
+                        })
+                    });
+                }
+                quote! {
+                    ::allframe_core::serde_json::json!({
+                        "oneOf": [#(#variants),*]
+                    })
+                }
+            }
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                &input,
+                "ToJsonSchema can only be derived for structs and enums",
+            ))
+        }
+    };
+
+    let body = match container.description {
+        Some(desc) => quote! {{
+            let mut schema = #body;
+            if let ::allframe_core::serde_json::Value::Object(ref mut map) = schema {
+                map.insert(
+                    "description".to_string(),
+                    ::allframe_core::serde_json::Value::String(#desc.to_string()),
+                );
+            }
+            schema
+        }},
+        None => body,
+    };
+
+    for param in &mut input.generics.params {
+        if let GenericParam::Type(ty) = param {
+            ty.bounds
+                .push(parse_quote!(::allframe_core::router::ToJsonSchema));
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::allframe_core::router::ToJsonSchema for #name #ty_generics #where_clause {
+            fn schema() -> ::allframe_core::serde_json::Value {
+                #body
+            }
+
+            fn schema_name() -> ::core::option::Option<::std::string::String> {
+                ::core::option::Option::Some(#name_str.to_string())
+            }
+        }
+    })
+}
+
+/// Generate the schema expression for a set of struct or variant fields
+fn fields_schema(fields: &Fields, rename_all: Option<&str>) -> syn::Result<TokenStream> {
+    match fields {
+        Fields::Named(named) => {
+            let mut properties = Vec::new();
+            let mut required = Vec::new();
+
+            for field in &named.named {
+                let serde = SerdeAttrs::parse(&field.attrs)?;
+                if serde.skip {
+                    continue;
+                }
+
+                let ident = field.ident.as_ref().unwrap().to_string();
+                let ident = ident.trim_start_matches("r#");
+                let key = serde
+                    .rename
+                    .unwrap_or_else(|| apply_rename_all(ident, rename_all));
+                let schema = field_schema(field)?;
+
+                if !is_option(&field.ty) && !serde.default {
+                    required.push(key.clone());
+                }
+                properties.push(quote! {
+                    properties.insert(#key.to_string(), #schema);
+                });
+            }
+
+            let required = (!required.is_empty()).then(|| {
+                quote! { , "required": [#(#required),*] }
+            });
+
+            let mutability = (!properties.is_empty()).then(|| quote! { mut });
+
+            Ok(quote! {{
+                let #mutability properties = ::allframe_core::serde_json::Map::new();
+                #(#properties)*
+                ::allframe_core::serde_json::json!({
+                    "type": "object",
+                    "properties": properties
+                    #required
+                })
+            }})
+        }
+        Fields::Unnamed(unnamed) if unnamed.unnamed.len() == 1 => field_schema(&unnamed.unnamed[0]),
+        Fields::Unnamed(unnamed) => {
+            let items = unnamed
+                .unnamed
+                .iter()
+                .map(field_schema)
+                .collect::<syn::Result<Vec<_>>>()?;
+            let len = items.len();
+            Ok(quote! {
+                ::allframe_core::serde_json::json!({
+                    "type": "array",
+                    "prefixItems": [#((#items)),*],
+                    "minItems": #len,
+                    "maxItems": #len
+                })
+            })
+        }
+        Fields::Unit => Ok(quote! {
+            ::allframe_core::serde_json::json!({ "type": "null" })
+        }),
+    }
+}
+
+/// Generate the schema expression for a single field, applying `#[schema]`
+fn field_schema(field: &Field) -> syn::Result<TokenStream> {
+    let ty = &field.ty;
+    let attrs = SchemaAttrs::parse(&field.attrs)?;
+
+    let description = attrs.description.map(|desc| {
+        quote! {
+            map.insert(
+                "description".to_string(),
+                ::allframe_core::serde_json::Value::String(#desc.to_string()),
+            );
+        }
+    });
+    let example = attrs.example.map(|example| {
+        quote! {
+            map.insert("example".to_string(), ::allframe_core::serde_json::json!(#example));
+        }
+    });
+
+    if description.is_none() && example.is_none() {
+        return Ok(quote! {
+            <#ty as ::allframe_core::router::ToJsonSchema>::schema()
+        });
+    }
+
+    Ok(quote! {{
+        let mut field_schema = <#ty as ::allframe_core::router::ToJsonSchema>::schema();
+        if let ::allframe_core::serde_json::Value::Object(ref mut map) = field_schema {
+            #description
+            #example
+        }
+        field_schema
+    }})
+}
+
+/// Whether the type is syntactically an `Option<T>`
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident == "Option")
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// Serialized name of an enum variant
+fn variant_name(ident: &str, attrs: &[Attribute], rename_all: Option<&str>) -> syn::Result<String> {
+    let serde = SerdeAttrs::parse(attrs)?;
+    Ok(serde
+        .rename
+        .unwrap_or_else(|| apply_rename_all(ident, rename_all)))
+}
+
+/// Apply a serde `rename_all` rule to a field or variant name
+fn apply_rename_all(name: &str, rule: Option<&str>) -> String {
+    let Some(rule) = rule else {
+        return name.to_string();
+    };
+
+    // Split into lowercase words from either snake_case or PascalCase
+    let mut words: Vec<String> = Vec::new();
+    for part in name.split('_').filter(|p| !p.is_empty()) {
+        let mut current = String::new();
+        for ch in part.chars() {
+            if ch.is_uppercase() && !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            current.extend(ch.to_lowercase());
+        }
+        if !current.is_empty() {
+            words.push(current);
+        }
+    }
+
+    let capitalize = |word: &String| {
+        let mut chars = word.chars();
+        chars
+            .next()
+            .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+            .unwrap_or_default()
+    };
+
+    match rule {
+        "lowercase" => words.concat(),
+        "UPPERCASE" => words.concat().to_uppercase(),
+        "PascalCase" => words.iter().map(capitalize).collect(),
+        "camelCase" => words
+            .iter()
+            .enumerate()
+            .map(|(i, w)| if i == 0 { w.clone() } else { capitalize(w) })
+            .collect(),
+        "snake_case" => words.join("_"),
+        "SCREAMING_SNAKE_CASE" => words.join("_").to_uppercase(),
+        "kebab-case" => words.join("-"),
+        "SCREAMING-KEBAB-CASE" => words.join("-").to_uppercase(),
+        _ => name.to_string(),
+    }
+}
+
+/// Parsed `#[schema(...)]` attributes
+#[derive(Default)]
+struct SchemaAttrs {
+    description: Option<String>,
+    example: Option<Expr>,
+}
+
+impl SchemaAttrs {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut parsed = Self::default();
+
+        for attr in attrs {
+            if !attr.path().is_ident("schema") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("description") {
+                    let value: LitStr = meta.value()?.parse()?;
+                    parsed.description = Some(value.value());
+                    Ok(())
+                } else if meta.path.is_ident("example") {
+                    parsed.example = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `description` or `example`"))
+                }
+            })?;
+        }
+
+        Ok(parsed)
+    }
+}
+
+/// The subset of `#[serde(...)]` attributes that affect the JSON shape
+#[derive(Default)]
+struct SerdeAttrs {
+    rename: Option<String>,
+    rename_all: Option<String>,
+    skip: bool,
+    default: bool,
+}
+
+impl SerdeAttrs {
+    fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut parsed = Self::default();
+
+        for attr in attrs {
+            if !attr.path().is_ident("serde") {
+                continue;
+            }
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    let value: LitStr = meta.value()?.parse()?;
+                    parsed.rename = Some(value.value());
+                } else if meta.path.is_ident("rename_all") {
+                    let value: LitStr = meta.value()?.parse()?;
+                    parsed.rename_all = Some(value.value());
+                } else if meta.path.is_ident("skip") || meta.path.is_ident("skip_serializing") {
+                    parsed.skip = true;
+                } else if meta.path.is_ident("default") {
+                    parsed.default = true;
+                    if meta.input.peek(syn::Token![=]) {
+                        let _: Expr = meta.value()?.parse()?;
+                    }
+                } else if meta.input.peek(syn::Token![=]) {
+                    // Other serde attributes don't change the schema
+                    let _: Expr = meta.value()?.parse()?;
+                } else if meta.input.peek(syn::token::Paren) {
+                    let _content;
+                    syn::parenthesized!(_content in meta.input);
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(parsed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_rename_all() {
+        assert_eq!(apply_rename_all("user_id", Some("camelCase")), "userId");
+        assert_eq!(apply_rename_all("user_id", Some("PascalCase")), "UserId");
+        assert_eq!(
+            apply_rename_all("InProgress", Some("snake_case")),
+            "in_progress"
+        );
+        assert_eq!(
+            apply_rename_all("InProgress", Some("SCREAMING_SNAKE_CASE")),
+            "IN_PROGRESS"
+        );
+        assert_eq!(
+            apply_rename_all("InProgress", Some("kebab-case")),
+            "in-progress"
+        );
+        assert_eq!(apply_rename_all("Active", Some("lowercase")), "active");
+        assert_eq!(apply_rename_all("user_id", None), "user_id");
+    }
+
+    #[test]
+    fn test_rejects_unknown_schema_attribute() {
+        let input = quote! {
+            struct Foo {
+                #[schema(title = "nope")]
+                bar: String,
+            }
+        };
+
+        let err = to_json_schema_impl(input).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("expected `description` or `example`"));
+    }
+}
@@ -16,11 +16,16 @@ pub fn obfuscate_impl(input: TokenStream) -> syn::Result<TokenStream> {
     let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
     let format_fields = generate_format_fields(&input)?;
+    // Enums produce a `match` with a format! per variant
+    let body = match &input.data {
+        Data::Enum(_) => format_fields,
+        _ => quote! { format!(#format_fields) },
+    };
 
     Ok(quote! {
         impl #impl_generics allframe_core::security::Obfuscate for #name #ty_generics #where_clause {
             fn obfuscate(&self) -> String {
-                format!(#format_fields)
+                #body
             }
         }
     })
@@ -46,10 +51,7 @@ fn generate_format_fields(input: &DeriveInput) -> syn::Result<TokenStream> {
                     let field_name_str = field_name.to_string();
 
                     // Check for #[sensitive] attribute
-                    let is_sensitive = field
-                        .attrs
-                        .iter()
-                        .any(|attr| attr.path().is_ident("sensitive"));
+                    let sensitivity = sensitivity(&field.attrs)?;
 
                     // Check for #[obfuscate(with = "...")] attribute
                     let custom_obfuscator = get_obfuscate_with(&field.attrs)?;
@@ -59,8 +61,10 @@ fn generate_format_fields(input: &DeriveInput) -> syn::Result<TokenStream> {
                     }
                     first = false;
 
-                    if is_sensitive {
-                        format_parts.push(format!("{}: ***", field_name_str));
+                    if let Some(sensitivity) = sensitivity {
+                        let (part, arg) = sensitivity.format(quote! { self.#field_name });
+                        format_parts.push(format!("{}: {}", field_name_str, part));
+                        format_args.extend(arg);
                     } else if let Some(obfuscator) = custom_obfuscator {
                         format_parts.push(format!("{}: {{}}", field_name_str));
                         let obf_ident: Ident = syn::parse_str(&obfuscator)?;
@@ -84,18 +88,17 @@ fn generate_format_fields(input: &DeriveInput) -> syn::Result<TokenStream> {
                 let mut format_args = Vec::new();
 
                 for (i, field) in fields.unnamed.iter().enumerate() {
-                    let is_sensitive = field
-                        .attrs
-                        .iter()
-                        .any(|attr| attr.path().is_ident("sensitive"));
+                    let sensitivity = sensitivity(&field.attrs)?;
 
                     if i > 0 {
                         format_parts.push(", ".to_string());
                     }
 
                     let index = syn::Index::from(i);
-                    if is_sensitive {
-                        format_parts.push("***".to_string());
+                    if let Some(sensitivity) = sensitivity {
+                        let (part, arg) = sensitivity.format(quote! { self.#index });
+                        format_parts.push(part);
+                        format_args.extend(arg);
                     } else {
                         format_parts.push("{}".to_string());
                         format_args.push(quote! { self.#index });
@@ -123,12 +126,7 @@ fn generate_format_fields(input: &DeriveInput) -> syn::Result<TokenStream> {
 
                 match &variant.fields {
                     Fields::Named(fields) => {
-                        let field_names: Vec<_> = fields
-                            .named
-                            .iter()
-                            .map(|f| f.ident.as_ref().unwrap())
-                            .collect();
-
+                        let mut field_patterns = Vec::new();
                         let mut format_parts = vec![format!("{}::{} {{{{ ", name_str, variant_str)];
                         let mut format_args = Vec::<proc_macro2::TokenStream>::new();
                         let mut first = true;
@@ -136,21 +134,27 @@ fn generate_format_fields(input: &DeriveInput) -> syn::Result<TokenStream> {
                         for field in &fields.named {
                             let field_name = field.ident.as_ref().unwrap();
                             let field_name_str = field_name.to_string();
-                            let is_sensitive = field
-                                .attrs
-                                .iter()
-                                .any(|attr| attr.path().is_ident("sensitive"));
+                            let sensitivity = sensitivity(&field.attrs)?;
 
                             if !first {
                                 format_parts.push(", ".to_string());
                             }
                             first = false;
 
-                            if is_sensitive {
-                                format_parts.push(format!("{}: ***", field_name_str));
+                            if let Some(sensitivity) = sensitivity {
+                                let (part, arg) = sensitivity.format(quote! { #field_name });
+                                format_parts.push(format!("{}: {}", field_name_str, part));
+                                // Redacted fields aren't bound, avoiding unused variables
+                                if arg.is_some() {
+                                    field_patterns.push(quote! { #field_name });
+                                } else {
+                                    field_patterns.push(quote! { #field_name: _ });
+                                }
+                                format_args.extend(arg);
                             } else {
                                 format_parts.push(format!("{}: {{:?}}", field_name_str));
                                 format_args.push(quote! { #field_name });
+                                field_patterns.push(quote! { #field_name });
                             }
                         }
 
@@ -159,11 +163,11 @@ fn generate_format_fields(input: &DeriveInput) -> syn::Result<TokenStream> {
                         let format_lit =
                             LitStr::new(&format_str, proc_macro2::Span::call_site());
 
-                        quote! {
-                            #name::#variant_name { #(#field_names),* } => {
+                        Ok(quote! {
+                            #name::#variant_name { #(#field_patterns),* } => {
                                 format!(#format_lit, #(#format_args),*)
                             }
-                        }
+                        })
                     }
                     Fields::Unnamed(fields) => {
                         let bindings: Vec<_> = (0..fields.unnamed.len())
@@ -177,18 +181,17 @@ fn generate_format_fields(input: &DeriveInput) -> syn::Result<TokenStream> {
                         let mut format_args = Vec::<proc_macro2::TokenStream>::new();
 
                         for (i, field) in fields.unnamed.iter().enumerate() {
-                            let is_sensitive = field
-                                .attrs
-                                .iter()
-                                .any(|attr| attr.path().is_ident("sensitive"));
+                            let sensitivity = sensitivity(&field.attrs)?;
                             let binding = &bindings[i];
 
                             if i > 0 {
                                 format_parts.push(", ".to_string());
                             }
 
-                            if is_sensitive {
-                                format_parts.push("***".to_string());
+                            if let Some(sensitivity) = sensitivity {
+                                let (part, arg) = sensitivity.format(quote! { #binding });
+                                format_parts.push(part);
+                                format_args.extend(arg);
                             } else {
                                 format_parts.push("{:?}".to_string());
                                 format_args.push(quote! { #binding });
@@ -200,21 +203,22 @@ fn generate_format_fields(input: &DeriveInput) -> syn::Result<TokenStream> {
                         let format_lit =
                             LitStr::new(&format_str, proc_macro2::Span::call_site());
 
-                        quote! {
+                        Ok(quote! {
                             #name::#variant_name(#(#bindings),*) => {
                                 format!(#format_lit, #(#format_args),*)
                             }
-                        }
+                        })
                     }
                     Fields::Unit => {
                         let variant_full = format!("{}::{}", name_str, variant_str);
                         let lit = LitStr::new(&variant_full, proc_macro2::Span::call_site());
-                        quote! {
+                        Ok(quote! {
                             #name::#variant_name => #lit.to_string()
-                        }
+                        })
                     }
                 }
             });
+            let match_arms = match_arms.collect::<syn::Result<Vec<_>>>()?;
 
             let format_lit = LitStr::new(&name_str, proc_macro2::Span::call_site());
             let _ = format_lit; // suppress unused warning
@@ -231,6 +235,64 @@ fn generate_format_fields(input: &DeriveInput) -> syn::Result<TokenStream> {
     }
 }
 
+/// How a `#[sensitive]` field is rendered
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Sensitivity {
+    /// `#[sensitive]`: replaced by `***`
+    Redact,
+    /// `#[sensitive(reveal = N)]`: first and last N characters shown
+    Reveal(usize),
+    /// `#[sensitive(hash)]`: replaced by a short stable hash
+    Hash,
+}
+
+impl Sensitivity {
+    /// Format string part and argument (if any) rendering `value`
+    fn format(self, value: TokenStream) -> (String, Option<TokenStream>) {
+        let text = quote! { &::std::string::ToString::to_string(&#value) };
+        match self {
+            Sensitivity::Redact => ("***".to_string(), None),
+            Sensitivity::Reveal(n) => (
+                "{}".to_string(),
+                Some(quote! { allframe_core::security::obfuscate_reveal(#text, #n) }),
+            ),
+            Sensitivity::Hash => (
+                "{}".to_string(),
+                Some(quote! { allframe_core::security::obfuscate_hash(#text) }),
+            ),
+        }
+    }
+}
+
+/// Parse the `#[sensitive]` attribute of a field, if present
+fn sensitivity(attrs: &[syn::Attribute]) -> syn::Result<Option<Sensitivity>> {
+    let Some(attr) = attrs.iter().find(|attr| attr.path().is_ident("sensitive")) else {
+        return Ok(None);
+    };
+    if let syn::Meta::Path(_) = attr.meta {
+        return Ok(Some(Sensitivity::Redact));
+    }
+
+    let mut result = Sensitivity::Redact;
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("reveal") {
+            let value: syn::LitInt = meta.value()?.parse()?;
+            let n = value.base10_parse::<usize>()?;
+            if n == 0 {
+                return Err(syn::Error::new_spanned(value, "reveal must be at least 1"));
+            }
+            result = Sensitivity::Reveal(n);
+            Ok(())
+        } else if meta.path.is_ident("hash") {
+            result = Sensitivity::Hash;
+            Ok(())
+        } else {
+            Err(meta.error("expected `reveal = N` or `hash`"))
+        }
+    })?;
+    Ok(Some(result))
+}
+
 fn get_obfuscate_with(attrs: &[syn::Attribute]) -> syn::Result<Option<String>> {
     for attr in attrs {
         if attr.path().is_ident("obfuscate") {
@@ -320,4 +382,48 @@ mod tests {
         assert!(output.contains("***"), "Should obfuscate sensitive fields");
         assert!(output.contains("username"), "Should show non-sensitive fields");
     }
+
+    #[test]
+    fn test_sensitive_modes() {
+        let input: TokenStream = quote! {
+            struct Card {
+                #[sensitive(reveal = 4)]
+                number: String,
+                #[sensitive(hash)]
+                email: String,
+                #[sensitive]
+                cvv: String,
+            }
+        };
+
+        let output = obfuscate_impl(input).unwrap().to_string();
+        assert!(output.contains("obfuscate_reveal"));
+        assert!(output.contains("obfuscate_hash"));
+        assert!(output.contains("cvv: ***"));
+    }
+
+    #[test]
+    fn test_rejects_unknown_sensitive_mode() {
+        let input: TokenStream = quote! {
+            struct Card {
+                #[sensitive(mask)]
+                number: String,
+            }
+        };
+
+        let err = obfuscate_impl(input).unwrap_err();
+        assert!(err.to_string().contains("expected `reveal = N` or `hash`"));
+    }
+
+    #[test]
+    fn test_rejects_zero_reveal() {
+        let input: TokenStream = quote! {
+            enum Secret {
+                Key(#[sensitive(reveal = 0)] String),
+            }
+        };
+
+        let err = obfuscate_impl(input).unwrap_err();
+        assert!(err.to_string().contains("reveal must be at least 1"));
+    }
 }
@@ -0,0 +1,8 @@
+//! Compile-time tests for the architecture layer macros
+
+#[test]
+fn test_layer_rules_at_compile_time() {
+    let cases = trybuild::TestCases::new();
+    cases.pass("tests/ui/arch/pass_*.rs");
+    cases.compile_fail("tests/ui/arch/fail_*.rs");
+}
@@ -0,0 +1,46 @@
+//! Tests for the FromEnv derive macro
+
+use allframe_core::di::FromEnv;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, FromEnv)]
+#[config(prefix = "ALLFRAME_MACROS_TEST", defaults)]
+struct WorkerConfig {
+    queue: String,
+    concurrency: u32,
+    verbose: bool,
+}
+
+impl Default for WorkerConfig {
+    fn default() -> Self {
+        Self {
+            queue: "jobs".to_string(),
+            concurrency: 4,
+            verbose: false,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, FromEnv)]
+#[config(prefix = "ALLFRAME_MACROS_MISSING")]
+#[allow(dead_code)]
+struct RequiredConfig {
+    endpoint: String,
+}
+
+#[test]
+fn test_from_env_merges_defaults_and_environment() {
+    std::env::set_var("ALLFRAME_MACROS_TEST_CONCURRENCY", "16");
+    std::env::set_var("ALLFRAME_MACROS_TEST_VERBOSE", "true");
+
+    let config = WorkerConfig::from_env().unwrap();
+    assert_eq!(config.queue, "jobs");
+    assert_eq!(config.concurrency, 16);
+    assert!(config.verbose);
+}
+
+#[test]
+fn test_from_env_reports_missing_values() {
+    let error = RequiredConfig::from_env().unwrap_err();
+    assert!(error.to_string().contains("endpoint"), "{error}");
+}
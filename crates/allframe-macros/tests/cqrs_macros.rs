@@ -76,3 +76,100 @@ fn test_event_macro_on_struct() {
     assert_event::<ItemCreated>();
     assert_eq!(ItemCreated::event_type_name(), "ItemCreated");
 }
+
+// --- Generated serde derives and stable event names ---
+
+#[allframe_macros::command(derive)]
+struct RenameOrder {
+    id: String,
+    name: String,
+}
+
+#[allframe_macros::event(name = "order.renamed", derive)]
+#[derive(Debug, PartialEq)]
+struct OrderRenamed {
+    id: String,
+    name: String,
+}
+
+#[test]
+fn test_macros_derive_serde_and_clone() {
+    let cmd = RenameOrder {
+        id: "o-1".to_string(),
+        name: "Gift".to_string(),
+    };
+    let json = serde_json::to_string(&cmd.clone()).unwrap();
+    let back: RenameOrder = serde_json::from_str(&json).unwrap();
+    assert_eq!(back.id, "o-1");
+    assert_eq!(back.name, "Gift");
+
+    let event = OrderRenamed {
+        id: "o-1".to_string(),
+        name: "Gift".to_string(),
+    };
+    let json = serde_json::to_value(&event).unwrap();
+    assert_eq!(serde_json::from_value::<OrderRenamed>(json).unwrap(), event);
+}
+
+#[test]
+fn test_event_name_override() {
+    fn assert_event<T: Event>() {}
+    assert_event::<OrderRenamed>();
+    assert_eq!(OrderRenamed::event_type_name(), "order.renamed");
+}
+
+// --- Handler auto-registration ---
+
+#[allframe_macros::command_handler]
+async fn rename_order(cmd: RenameOrder) -> Result<Vec<OrderRenamed>, String> {
+    if cmd.name.is_empty() {
+        return Err("name is required".to_string());
+    }
+    Ok(vec![OrderRenamed {
+        id: cmd.id,
+        name: cmd.name,
+    }])
+}
+
+#[allframe_macros::query_handler]
+fn order_by_id(query: GetOrderById) -> Result<Option<String>, String> {
+    Ok(Some(format!("order:{}", query.id)))
+}
+
+#[tokio::test]
+async fn test_command_handler_auto_registers() {
+    let bus = allframe_core::cqrs::CommandBus::<OrderRenamed>::new();
+    assert_eq!(bus.auto_register().await, 1);
+
+    let events = bus
+        .dispatch(RenameOrder {
+            id: "o-1".to_string(),
+            name: "Gift".to_string(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(events[0].name, "Gift");
+
+    let error = bus
+        .dispatch(RenameOrder {
+            id: "o-1".to_string(),
+            name: String::new(),
+        })
+        .await
+        .unwrap_err();
+    assert!(error.to_string().contains("name is required"));
+}
+
+#[tokio::test]
+async fn test_query_handler_auto_registers() {
+    let bus = allframe_core::cqrs::QueryBus::new();
+    assert!(bus.auto_register().await >= 1);
+
+    let order: Option<String> = bus
+        .dispatch(GetOrderById {
+            id: "o-1".to_string(),
+        })
+        .await
+        .unwrap();
+    assert_eq!(order.as_deref(), Some("order:o-1"));
+}
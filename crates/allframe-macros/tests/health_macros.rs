@@ -0,0 +1,82 @@
+//! Tests for the HealthCheck derive macro
+
+use std::sync::Arc;
+
+use allframe_core::health::{
+    AlwaysHealthy, AlwaysUnhealthy, Dependency, HealthCheck, OverallStatus,
+};
+use allframe_macros::HealthCheck;
+
+#[derive(HealthCheck)]
+#[health(group = "cache", quorum = 1)]
+struct AppHealth {
+    #[health(group = "cache")]
+    redis_1: Arc<dyn Dependency>,
+    #[health(group = "cache")]
+    redis_2: Arc<dyn Dependency>,
+    #[health(group = "cache")]
+    redis_3: Arc<dyn Dependency>,
+    #[health(timeout = "2s")]
+    database: Arc<AlwaysHealthy>,
+    #[health(critical = false)]
+    search: Arc<dyn Dependency>,
+    #[health(skip)]
+    #[allow(dead_code)]
+    region: String,
+}
+
+fn app(redis_up: [bool; 3], search_up: bool) -> AppHealth {
+    let dep = |name: &str, up: bool| -> Arc<dyn Dependency> {
+        if up {
+            Arc::new(AlwaysHealthy::new(name))
+        } else {
+            Arc::new(AlwaysUnhealthy::new(name, "down"))
+        }
+    };
+
+    AppHealth {
+        redis_1: dep("redis-1", redis_up[0]),
+        redis_2: dep("redis-2", redis_up[1]),
+        redis_3: dep("redis-3", redis_up[2]),
+        database: Arc::new(AlwaysHealthy::new("postgres")),
+        search: dep("search", search_up),
+        region: "eu-west-1".to_string(),
+    }
+}
+
+#[tokio::test]
+async fn test_collects_dependencies() {
+    let deps = app([true; 3], true).dependencies();
+
+    assert_eq!(deps.len(), 5);
+    assert_eq!(deps[3].name(), "postgres");
+    assert_eq!(deps[3].timeout(), std::time::Duration::from_secs(2));
+    assert!(!deps[4].is_critical());
+    assert_eq!(deps[0].group(), Some("cache"));
+}
+
+#[tokio::test]
+async fn test_group_quorum() {
+    // One of three replicas is enough
+    let report = app([false, true, false], true).check_all().await;
+    assert_eq!(report.status, OverallStatus::Healthy);
+
+    let cache = report.group("cache").unwrap();
+    assert_eq!(cache.status, OverallStatus::Healthy);
+    assert_eq!((cache.healthy, cache.total, cache.quorum), (1, 3, 1));
+
+    // No replica at all takes the (critical) group down
+    let report = app([false; 3], true).check_all().await;
+    assert_eq!(
+        report.group("cache").unwrap().status,
+        OverallStatus::Unhealthy
+    );
+    assert_eq!(report.status, OverallStatus::Unhealthy);
+}
+
+#[tokio::test]
+async fn test_non_critical_failure_degrades() {
+    let report = app([true; 3], false).check_all().await;
+    assert_eq!(report.status, OverallStatus::Degraded);
+    assert_eq!(report.failed_dependencies()[0].name, "search");
+}
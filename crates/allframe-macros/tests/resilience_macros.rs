@@ -0,0 +1,207 @@
+//! Tests for the resilience attribute macros
+
+#![allow(deprecated)]
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use std::time::Duration;
+
+use allframe_core::resilience::{CircuitOpenError, RateLimitError, TimeoutError};
+use allframe_macros::{circuit_breaker, rate_limited, resilient, retry, timeout};
+
+#[derive(Debug, Clone, PartialEq)]
+enum ApiError {
+    Unavailable,
+    CircuitOpen,
+    RateLimited,
+    TimedOut,
+}
+
+impl From<CircuitOpenError> for ApiError {
+    fn from(_: CircuitOpenError) -> Self {
+        ApiError::CircuitOpen
+    }
+}
+
+impl From<RateLimitError> for ApiError {
+    fn from(_: RateLimitError) -> Self {
+        ApiError::RateLimited
+    }
+}
+
+impl From<TimeoutError> for ApiError {
+    fn from(_: TimeoutError) -> Self {
+        ApiError::TimedOut
+    }
+}
+
+#[circuit_breaker(failure_threshold = 2, timeout_ms = 60000)]
+async fn flaky(fail: bool) -> Result<u32, ApiError> {
+    if fail {
+        return Err(ApiError::Unavailable);
+    }
+    Ok(1)
+}
+
+#[circuit_breaker(failure_threshold = 1, timeout_ms = 60000, fallback = Ok(format!("cached:{id}")))]
+async fn get_profile(id: u32, fail: bool) -> Result<String, ApiError> {
+    if fail {
+        Err(ApiError::Unavailable)
+    } else {
+        Ok(format!("live:{id}"))
+    }
+}
+
+#[circuit_breaker(
+    failure_threshold = 10,
+    fallback = Ok(format!("stale:{key}")),
+    fallback_on_error
+)]
+fn lookup(key: &str, fail: bool) -> Result<String, ApiError> {
+    let value = if fail {
+        Err(ApiError::Unavailable)
+    } else {
+        Ok(key)
+    }?;
+    Ok(format!("fresh:{value}"))
+}
+
+#[tokio::test]
+async fn test_circuit_breaker_opens_after_failures() {
+    assert_eq!(flaky(false).await, Ok(1));
+    assert_eq!(flaky(true).await, Err(ApiError::Unavailable));
+    assert_eq!(flaky(true).await, Err(ApiError::Unavailable));
+    assert_eq!(flaky(false).await, Err(ApiError::CircuitOpen));
+}
+
+#[tokio::test]
+async fn test_circuit_breaker_fallback_when_open() {
+    assert_eq!(get_profile(1, false).await, Ok("live:1".to_string()));
+    assert_eq!(get_profile(1, true).await, Err(ApiError::Unavailable));
+    assert_eq!(get_profile(7, false).await, Ok("cached:7".to_string()));
+}
+
+#[test]
+fn test_circuit_breaker_fallback_on_error() {
+    assert_eq!(lookup("a", false), Ok("fresh:a".to_string()));
+    assert_eq!(lookup("b", true), Ok("stale:b".to_string()));
+}
+
+static FETCH_ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+
+fn is_transient(error: &ApiError) -> bool {
+    *error == ApiError::Unavailable
+}
+
+#[retry(max_retries = 3, initial_interval_ms = 1, retry_if = is_transient, jitter = false)]
+async fn fetch(error: Option<ApiError>, succeed_on: u32) -> Result<u32, ApiError> {
+    let attempt = FETCH_ATTEMPTS.fetch_add(1, Ordering::SeqCst) + 1;
+    match &error {
+        Some(error) if attempt < succeed_on => Err(error.clone()),
+        _ => Ok(attempt),
+    }
+}
+
+#[tokio::test]
+async fn test_retry_predicate() {
+    FETCH_ATTEMPTS.store(0, Ordering::SeqCst);
+    assert_eq!(fetch(Some(ApiError::Unavailable), 3).await, Ok(3));
+
+    // Non-transient errors are returned without retrying
+    FETCH_ATTEMPTS.store(0, Ordering::SeqCst);
+    assert_eq!(
+        fetch(Some(ApiError::CircuitOpen), 3).await,
+        Err(ApiError::CircuitOpen)
+    );
+    assert_eq!(FETCH_ATTEMPTS.load(Ordering::SeqCst), 1);
+
+    // Gives up after max_retries
+    FETCH_ATTEMPTS.store(0, Ordering::SeqCst);
+    assert_eq!(
+        fetch(Some(ApiError::Unavailable), 10).await,
+        Err(ApiError::Unavailable)
+    );
+    assert_eq!(FETCH_ATTEMPTS.load(Ordering::SeqCst), 4);
+
+    // The retry loop can run on a multi-threaded runtime
+    fn assert_send<T: Send>(_: T) {}
+    assert_send(fetch(None, 1));
+}
+
+struct Request {
+    tenant_id: u32,
+}
+
+#[rate_limited(rps = 1, burst = 1, key = request.tenant_id)]
+fn handle(request: &Request) -> Result<u32, ApiError> {
+    Ok(request.tenant_id)
+}
+
+#[rate_limited(rps = 1, burst = 2)]
+async fn global() -> Result<(), RateLimitError> {
+    Ok(())
+}
+
+#[test]
+fn test_rate_limited_per_key() {
+    let tenant = |tenant_id| Request { tenant_id };
+
+    assert_eq!(handle(&tenant(1)), Ok(1));
+    assert_eq!(handle(&tenant(1)), Err(ApiError::RateLimited));
+    assert_eq!(handle(&tenant(2)), Ok(2));
+}
+
+#[tokio::test]
+async fn test_rate_limited_returns_retry_after() {
+    assert!(global().await.is_ok());
+    assert!(global().await.is_ok());
+
+    let error = global().await.unwrap_err();
+    assert!(error.retry_after > std::time::Duration::ZERO);
+}
+
+static STACKED_ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+static RESILIENT_ATTEMPTS: AtomicU32 = AtomicU32::new(0);
+
+async fn hang(attempts: &AtomicU32) -> Result<(), ApiError> {
+    attempts.fetch_add(1, Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_secs(5)).await;
+    Ok(())
+}
+
+// Attribute order is deliberately scrambled; the nesting is fixed
+#[timeout(ms = 10)]
+#[circuit_breaker(failure_threshold = 1, timeout_ms = 60000)]
+#[retry(max_retries = 2, initial_interval_ms = 1)]
+async fn stacked() -> Result<(), ApiError> {
+    hang(&STACKED_ATTEMPTS).await
+}
+
+#[resilient(
+    timeout(ms = 10),
+    circuit_breaker(failure_threshold = 1, timeout_ms = 60000),
+    retry(max_retries = 2, initial_interval_ms = 1)
+)]
+async fn combined() -> Result<(), ApiError> {
+    hang(&RESILIENT_ATTEMPTS).await
+}
+
+#[tokio::test]
+async fn test_stacked_macros_nest_in_fixed_order() {
+    // Each attempt times out and is retried: 1 + 2 retries
+    assert_eq!(stacked().await, Err(ApiError::TimedOut));
+    assert_eq!(STACKED_ATTEMPTS.load(Ordering::SeqCst), 3);
+
+    // The breaker saw one failed call and now rejects without attempting
+    assert_eq!(stacked().await, Err(ApiError::CircuitOpen));
+    assert_eq!(STACKED_ATTEMPTS.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn test_resilient_macro() {
+    assert_eq!(combined().await, Err(ApiError::TimedOut));
+    assert_eq!(RESILIENT_ATTEMPTS.load(Ordering::SeqCst), 3);
+
+    assert_eq!(combined().await, Err(ApiError::CircuitOpen));
+    assert_eq!(RESILIENT_ATTEMPTS.load(Ordering::SeqCst), 3);
+}
@@ -0,0 +1,142 @@
+//! Tests for the ToJsonSchema derive macro
+
+use std::collections::HashMap;
+
+use allframe_core::router::ToJsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+#[derive(Serialize, Deserialize, ToJsonSchema)]
+#[schema(description = "A postal address")]
+struct Address {
+    street: String,
+    #[schema(description = "Postal code", example = "10115")]
+    zip: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, ToJsonSchema)]
+#[serde(rename_all = "camelCase")]
+struct CreateUser {
+    #[schema(description = "Display name", example = "Alice")]
+    display_name: String,
+    #[schema(example = 30)]
+    age: Option<u32>,
+    address: Address,
+    roles: Vec<Role>,
+    #[serde(default)]
+    tags: HashMap<String, String>,
+    #[serde(rename = "e-mail")]
+    email: String,
+    #[serde(skip)]
+    #[allow(dead_code)]
+    internal: bool,
+}
+
+#[derive(Serialize, Deserialize, ToJsonSchema)]
+#[serde(rename_all = "lowercase")]
+enum Role {
+    Admin,
+    Member,
+}
+
+#[derive(Serialize, Deserialize, ToJsonSchema)]
+enum Shape {
+    Empty,
+    Circle(f64),
+    Rect { width: f64, height: f64 },
+}
+
+#[derive(Serialize, Deserialize, ToJsonSchema)]
+struct UserId(u64);
+
+#[derive(Serialize, Deserialize, ToJsonSchema)]
+struct Page<T> {
+    items: Vec<T>,
+    total: u64,
+}
+
+#[test]
+fn test_struct_schema() {
+    let schema = CreateUser::schema();
+
+    assert_eq!(schema["type"], "object");
+    assert_eq!(schema["properties"]["displayName"]["type"], "string");
+    assert_eq!(
+        schema["properties"]["displayName"]["description"],
+        "Display name"
+    );
+    assert_eq!(schema["properties"]["displayName"]["example"], "Alice");
+    assert_eq!(schema["properties"]["age"]["type"], "integer");
+    assert_eq!(schema["properties"]["age"]["nullable"], true);
+    assert_eq!(schema["properties"]["age"]["example"], 30);
+    assert_eq!(schema["properties"]["e-mail"]["type"], "string");
+    assert!(schema["properties"]["internal"].is_null());
+    assert_eq!(
+        schema["required"],
+        json!(["displayName", "address", "roles", "e-mail"])
+    );
+    assert_eq!(CreateUser::schema_name(), Some("CreateUser".to_string()));
+}
+
+#[test]
+fn test_nested_struct_and_collections() {
+    let schema = CreateUser::schema();
+
+    let address = &schema["properties"]["address"];
+    assert_eq!(address["type"], "object");
+    assert_eq!(address["description"], "A postal address");
+    assert_eq!(address["properties"]["zip"]["example"], "10115");
+    assert_eq!(address["required"], json!(["street"]));
+
+    let roles = &schema["properties"]["roles"];
+    assert_eq!(roles["type"], "array");
+    assert_eq!(roles["items"]["enum"], json!(["admin", "member"]));
+
+    assert_eq!(
+        schema["properties"]["tags"]["additionalProperties"]["type"],
+        "string"
+    );
+}
+
+#[test]
+fn test_unit_enum_is_string_enum() {
+    assert_eq!(
+        Role::schema(),
+        json!({ "type": "string", "enum": ["admin", "member"] })
+    );
+}
+
+#[test]
+fn test_data_enum_is_one_of() {
+    let schema = Shape::schema();
+    let variants = schema["oneOf"].as_array().unwrap();
+
+    assert_eq!(variants.len(), 3);
+    assert_eq!(variants[0], json!({ "type": "string", "enum": ["Empty"] }));
+    assert_eq!(variants[1]["required"], json!(["Circle"]));
+    assert_eq!(variants[1]["properties"]["Circle"]["type"], "number");
+    assert_eq!(
+        variants[2]["properties"]["Rect"]["required"],
+        json!(["width", "height"])
+    );
+}
+
+#[test]
+fn test_newtype_and_generic_structs() {
+    assert_eq!(UserId::schema()["type"], "integer");
+
+    let schema = Page::<Role>::schema();
+    assert_eq!(schema["properties"]["items"]["items"]["type"], "string");
+    assert_eq!(schema["required"], json!(["items", "total"]));
+}
+
+#[test]
+fn test_optional_only_struct_has_no_required() {
+    #[derive(ToJsonSchema)]
+    #[allow(dead_code)]
+    struct Filters {
+        query: Option<String>,
+    }
+
+    assert!(Filters::schema().get("required").is_none());
+}
@@ -0,0 +1,75 @@
+//! Tests for the Obfuscate derive macro
+
+use allframe_core::security::{obfuscate_hash, Obfuscate};
+use allframe_macros::Obfuscate;
+
+#[derive(Obfuscate)]
+struct Payment {
+    amount: u64,
+    #[sensitive(reveal = 4)]
+    card_number: String,
+    #[sensitive(hash)]
+    email: String,
+    #[sensitive]
+    cvv: String,
+}
+
+#[derive(Obfuscate)]
+enum Credential {
+    ApiKey(#[sensitive(reveal = 2)] String),
+    Login {
+        #[sensitive(hash)]
+        username: String,
+        #[sensitive]
+        password: String,
+    },
+}
+
+#[test]
+fn test_sensitive_modes() {
+    let payment = Payment {
+        amount: 4200,
+        card_number: "4111111111111111".to_string(),
+        email: "alice@example.com".to_string(),
+        cvv: "123".to_string(),
+    };
+
+    assert_eq!(payment.cvv.len(), 3);
+    assert_eq!(
+        payment.obfuscate(),
+        format!(
+            "Payment {{ amount: 4200, card_number: 4111***1111, email: {}, cvv: *** }}",
+            obfuscate_hash("alice@example.com")
+        )
+    );
+}
+
+#[test]
+fn test_sensitive_modes_in_enum() {
+    assert_eq!(
+        Credential::ApiKey("sk_live_abc".to_string()).obfuscate(),
+        "Credential::ApiKey(sk***bc)"
+    );
+
+    let login = Credential::Login {
+        username: "alice".to_string(),
+        password: "hunter2".to_string(),
+    };
+    if let Credential::Login { password, .. } = &login {
+        assert_eq!(password, "hunter2");
+    }
+    assert_eq!(
+        login.obfuscate(),
+        format!(
+            "Credential::Login {{ username: {}, password: *** }}",
+            obfuscate_hash("alice")
+        )
+    );
+}
+
+#[test]
+fn test_hash_is_stable_and_hides_value() {
+    let hash = obfuscate_hash("alice@example.com");
+    assert_eq!(hash, obfuscate_hash("alice@example.com"));
+    assert!(!hash.contains("alice"));
+}
@@ -0,0 +1,24 @@
+use std::sync::Arc;
+
+use allframe_macros::{handler, repository};
+
+// Checked wherever the repository is declared
+#[handler]
+struct ShowAccount {
+    store: Arc<storage::AccountStore>,
+}
+
+mod storage {
+    #[allframe_macros::repository]
+    pub struct AccountStore;
+}
+
+#[repository]
+struct Ledger;
+
+#[handler]
+fn show_ledger(ledger: &Ledger) {
+    let _ = ledger;
+}
+
+fn main() {}
@@ -0,0 +1,31 @@
+use allframe_macros::{handler, use_case};
+
+mod storage {
+    #[allframe_macros::repository]
+    pub struct Accounts;
+}
+
+mod views {
+    /// Unrelated to the repository of the same name, and in no layer
+    pub struct Accounts;
+}
+
+#[use_case]
+struct ListAccounts {
+    accounts: storage::Accounts,
+}
+
+#[handler]
+struct ShowAccounts {
+    list: ListAccounts,
+    accounts: views::Accounts,
+}
+
+fn main() {
+    let _ = ShowAccounts {
+        list: ListAccounts {
+            accounts: storage::Accounts,
+        },
+        accounts: views::Accounts,
+    };
+}
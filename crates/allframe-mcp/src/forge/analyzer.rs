@@ -1,7 +1,7 @@
 //! Project analyzer for understanding AllFrame project structure
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fs,
     path::{Path, PathBuf},
 };
@@ -80,6 +80,25 @@ pub struct Repository {
     pub entity: Option<String>,
 }
 
+/// Definition of a repository trait, parsed for code generation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepositoryTrait {
+    /// Trait name (e.g., "UserRepository")
+    pub name: String,
+    /// File path relative to project root
+    pub file_path: String,
+    /// Module path of the defining file (e.g., "crate::domain::user_repository")
+    pub module_path: String,
+    /// Whether the trait is annotated with `#[async_trait]`
+    pub is_async_trait: bool,
+    /// Signatures of methods without a default body, without the trailing `;`
+    pub methods: Vec<String>,
+    /// `use` statements of the defining file, rewritten to absolute paths
+    pub uses: Vec<String>,
+    /// Public types defined alongside the trait (e.g., its error type)
+    pub types: Vec<String>,
+}
+
 /// Complete project structure analysis
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectStructure {
@@ -103,6 +122,76 @@ pub struct ProjectStructure {
     pub dependencies: Vec<String>,
 }
 
+/// Kind of component checked for tests
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComponentKind {
+    /// Domain entity
+    Entity,
+    /// Application service
+    Service,
+    /// Presentation handler
+    Handler,
+}
+
+impl ComponentKind {
+    /// The layer components of this kind live in
+    pub fn layer(&self) -> Layer {
+        match self {
+            ComponentKind::Entity => Layer::Domain,
+            ComponentKind::Service => Layer::Application,
+            ComponentKind::Handler => Layer::Presentation,
+        }
+    }
+}
+
+/// Whether one entity, service or handler has tests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComponentCoverage {
+    /// Component name
+    pub name: String,
+    /// Entity, service or handler
+    pub kind: ComponentKind,
+    /// Layer the component lives in
+    pub layer: Layer,
+    /// File path relative to project root
+    pub file_path: String,
+    /// Test files (relative to project root) that exercise the component
+    pub tests: Vec<String>,
+}
+
+impl ComponentCoverage {
+    /// Whether any test exercises the component
+    pub fn is_tested(&self) -> bool {
+        !self.tests.is_empty()
+    }
+}
+
+/// Tested and untested component counts for one layer
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayerCoverage {
+    /// Components with tests
+    pub tested: usize,
+    /// Components without tests
+    pub untested: usize,
+}
+
+/// Which entities, services and handlers lack tests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TestCoverageReport {
+    /// Every entity, service and handler, with the tests found for it
+    pub components: Vec<ComponentCoverage>,
+    /// Counts per layer directory name
+    pub by_layer: BTreeMap<String, LayerCoverage>,
+}
+
+impl TestCoverageReport {
+    /// Components without tests
+    pub fn gaps(&self) -> impl Iterator<Item = &ComponentCoverage> {
+        self.components.iter().filter(|c| !c.is_tested())
+    }
+}
+
 /// Project analyzer that parses existing AllFrame projects
 pub struct ProjectAnalyzer {
     project_path: PathBuf,
@@ -161,6 +250,179 @@ impl ProjectAnalyzer {
         })
     }
 
+    /// Report which entities, services and handlers lack tests.
+    ///
+    /// A component counts as tested when it is mentioned by name in the
+    /// `#[cfg(test)]` module of its own file, in a sibling test file
+    /// (`tests.rs`, `<file>_test.rs`, `<file>_tests.rs` or
+    /// `<file>/tests.rs`), or in an integration test under `tests/`.
+    pub fn analyze_test_coverage(&self) -> Result<TestCoverageReport, String> {
+        let structure = self.analyze()?;
+        let integration_tests = self.read_integration_tests()?;
+
+        let components = structure
+            .entities
+            .iter()
+            .map(|e| (e.name.as_str(), ComponentKind::Entity, e.file_path.as_str()))
+            .chain(structure.services.iter().map(|s| {
+                (
+                    s.name.as_str(),
+                    ComponentKind::Service,
+                    s.file_path.as_str(),
+                )
+            }))
+            .chain(structure.handlers.iter().map(|h| {
+                (
+                    h.name.as_str(),
+                    ComponentKind::Handler,
+                    h.file_path.as_str(),
+                )
+            }));
+
+        let mut report = TestCoverageReport {
+            components: Vec::new(),
+            by_layer: BTreeMap::new(),
+        };
+        for (name, kind, file_path) in components {
+            let mut tests: Vec<String> = self
+                .unit_test_sources(file_path)
+                .into_iter()
+                .chain(integration_tests.iter().cloned())
+                .filter(|(_, source)| mentions(source, name))
+                .map(|(path, _)| path)
+                .collect();
+            tests.dedup();
+
+            let layer = kind.layer();
+            let counts = report
+                .by_layer
+                .entry(layer.dir_name().to_string())
+                .or_default();
+            if tests.is_empty() {
+                counts.untested += 1;
+            } else {
+                counts.tested += 1;
+            }
+            report.components.push(ComponentCoverage {
+                name: name.to_string(),
+                kind,
+                layer,
+                file_path: file_path.to_string(),
+                tests,
+            });
+        }
+
+        Ok(report)
+    }
+
+    /// Locate and parse the repository trait `name` in the domain layer
+    pub fn find_repository_trait(&self, name: &str) -> Result<RepositoryTrait, String> {
+        let structure = self.analyze()?;
+        let repository = structure
+            .repositories
+            .iter()
+            .find(|r| r.name == name)
+            .ok_or_else(|| format!("Repository trait '{}' not found in src/domain", name))?;
+
+        let content = fs::read_to_string(self.project_path.join(&repository.file_path))
+            .map_err(|e| format!("Failed to read {}: {}", repository.file_path, e))?;
+        let start = content
+            .find(&format!("pub trait {}", name))
+            .ok_or_else(|| format!("Repository trait '{}' not found", name))?;
+        let body_start = content[start..]
+            .find('{')
+            .map(|i| start + i + 1)
+            .ok_or_else(|| format!("Repository trait '{}' has no body", name))?;
+
+        let module_path = module_path(&repository.file_path);
+        let parent_path = module_path
+            .rsplit_once("::")
+            .map_or("crate", |(parent, _)| parent);
+
+        let uses = top_level_uses(&content)
+            .into_iter()
+            .map(|u| {
+                u.replace("super::", &format!("{}::", parent_path))
+                    .replace("self::", &format!("{}::", module_path))
+            })
+            .collect();
+
+        let types = content
+            .lines()
+            .filter_map(|line| {
+                let rest = ["pub struct ", "pub enum ", "pub type "]
+                    .iter()
+                    .find_map(|prefix| line.strip_prefix(prefix))?;
+                let type_name = rest.split([' ', '{', '<', '(', ';', '=']).next()?;
+                (!type_name.is_empty()).then(|| type_name.to_string())
+            })
+            .collect();
+
+        Ok(RepositoryTrait {
+            name: name.to_string(),
+            file_path: repository.file_path.clone(),
+            is_async_trait: content[..start]
+                .trim_end()
+                .lines()
+                .rev()
+                .map(str::trim)
+                .take_while(|line| line.starts_with("#[") || line.starts_with("///"))
+                .any(|line| line.contains("async_trait")),
+            module_path,
+            methods: required_methods(&content[body_start..]),
+            uses,
+            types,
+        })
+    }
+
+    /// Test code for the source file `file_path`: its own `#[cfg(test)]`
+    /// module and any sibling test files, as (relative path, source) pairs
+    fn unit_test_sources(&self, file_path: &str) -> Vec<(String, String)> {
+        let mut sources = Vec::new();
+        let path = Path::new(file_path);
+
+        if let Ok(content) = fs::read_to_string(self.project_path.join(path)) {
+            if let Some(start) = content.find("#[cfg(test)]") {
+                sources.push((file_path.to_string(), content[start..].to_string()));
+            }
+        }
+
+        let (Some(dir), Some(stem)) = (path.parent(), path.file_stem().and_then(|s| s.to_str()))
+        else {
+            return sources;
+        };
+        for sibling in [
+            dir.join("tests.rs"),
+            dir.join(format!("{}_test.rs", stem)),
+            dir.join(format!("{}_tests.rs", stem)),
+            dir.join(stem).join("tests.rs"),
+        ] {
+            if sibling == path {
+                continue;
+            }
+            if let Ok(content) = fs::read_to_string(self.project_path.join(&sibling)) {
+                sources.push((sibling.display().to_string(), content));
+            }
+        }
+
+        sources
+    }
+
+    /// Integration tests under `tests/`, as (relative path, source) pairs
+    fn read_integration_tests(&self) -> Result<Vec<(String, String)>, String> {
+        let mut files = Vec::new();
+        self.collect_rs_files(&self.project_path.join("tests"), &mut files)?;
+        files.sort();
+
+        Ok(files
+            .into_iter()
+            .filter_map(|file| {
+                let content = fs::read_to_string(self.project_path.join(&file)).ok()?;
+                Some((file, content))
+            })
+            .collect())
+    }
+
     /// Parse project name from Cargo.toml
     fn parse_project_name(&self) -> Result<String, String> {
         let cargo_toml = self.project_path.join("Cargo.toml");
@@ -587,6 +849,95 @@ impl ProjectAnalyzer {
     }
 }
 
+/// Module path of a source file relative to the project root
+fn module_path(file_path: &str) -> String {
+    let path = file_path.trim_start_matches("src/").trim_end_matches(".rs");
+    let path = path.strip_suffix("/mod").unwrap_or(path);
+    format!("crate::{}", path.replace('/', "::"))
+}
+
+/// Top-level `use` statements in `source`, whitespace-normalized
+fn top_level_uses(source: &str) -> Vec<String> {
+    let mut uses = Vec::new();
+    let mut current: Option<String> = None;
+    for line in source.lines() {
+        if current.is_none() && (line.starts_with("use ") || line.starts_with("pub use ")) {
+            current = Some(String::new());
+        }
+        if let Some(statement) = current.as_mut() {
+            statement.push_str(line.trim());
+            statement.push(' ');
+            if line.trim_end().ends_with(';') {
+                let statement = current.take().unwrap_or_default();
+                uses.push(
+                    statement
+                        .split_whitespace()
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                        .replace("{ ", "{")
+                        .replace(" }", "}")
+                        .replace(",}", "}"),
+                );
+            }
+        }
+    }
+    uses
+}
+
+/// Signatures of the methods in a trait body that have no default body
+///
+/// `body` starts just after the trait's opening brace.
+fn required_methods(body: &str) -> Vec<String> {
+    let code: String = body
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.starts_with("//") && !line.starts_with("#["))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut methods = Vec::new();
+    let mut item = String::new();
+    let mut depth = 0usize;
+    for c in code.chars() {
+        match c {
+            '{' => {
+                depth += 1;
+                item.clear();
+            }
+            '}' if depth == 0 => break,
+            '}' => {
+                depth -= 1;
+                item.clear();
+            }
+            ';' if depth == 0 => {
+                let signature = item.split_whitespace().collect::<Vec<_>>().join(" ");
+                if signature.starts_with("fn ") || signature.starts_with("async fn ") {
+                    methods.push(
+                        signature
+                            .replace("( ", "(")
+                            .replace(" )", ")")
+                            .replace(",)", ")"),
+                    );
+                }
+                item.clear();
+            }
+            _ if depth == 0 => item.push(c),
+            _ => {}
+        }
+    }
+    methods
+}
+
+/// Whether `source` mentions the identifier `name` as a whole word
+fn mentions(source: &str, name: &str) -> bool {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    source.match_indices(name).any(|(start, _)| {
+        let before = source[..start].chars().next_back();
+        let after = source[start + name.len()..].chars().next();
+        !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -598,4 +949,167 @@ mod tests {
         assert_eq!(Layer::Infrastructure.dir_name(), "infrastructure");
         assert_eq!(Layer::Presentation.dir_name(), "presentation");
     }
+
+    #[test]
+    fn test_mentions_whole_words_only() {
+        assert!(mentions("let order = Order::new();", "Order"));
+        assert!(mentions("get_order(req)", "get_order"));
+        assert!(!mentions("OrderLine::new()", "Order"));
+        assert!(!mentions("get_orders()", "get_order"));
+    }
+
+    #[test]
+    fn test_module_path() {
+        assert_eq!(
+            module_path("src/domain/order_repository.rs"),
+            "crate::domain::order_repository"
+        );
+        assert_eq!(module_path("src/domain/mod.rs"), "crate::domain");
+    }
+
+    #[test]
+    fn test_find_repository_trait() {
+        let dir =
+            std::env::temp_dir().join(format!("allframe-forge-{}-repo-trait", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("src/domain")).unwrap();
+        fs::write(dir.join("Cargo.toml"), "[package]\nname = \"shop\"\n").unwrap();
+        fs::write(
+            dir.join("src/domain/order_repository.rs"),
+            r#"use async_trait::async_trait;
+use uuid::Uuid;
+
+use super::{
+    order::Order,
+    order::OrderStatus,
+};
+
+/// Repository error
+#[derive(Debug)]
+pub enum RepositoryError {
+    NotFound(String),
+}
+
+/// Order repository
+#[async_trait]
+pub trait OrderRepository: Send + Sync {
+    /// Find by ID
+    async fn find_by_id(&self, id: Uuid) -> Result<Option<Order>, RepositoryError>;
+
+    async fn find_by_status(
+        &self,
+        status: OrderStatus,
+    ) -> Result<Vec<Order>, RepositoryError>;
+
+    /// Has a default body, so implementations needn't provide it
+    async fn exists(&self, id: Uuid) -> bool {
+        matches!(self.find_by_id(id).await, Ok(Some(_)))
+    }
+}
+"#,
+        )
+        .unwrap();
+
+        let analyzer = ProjectAnalyzer::new(&dir).unwrap();
+        let repository = analyzer.find_repository_trait("OrderRepository").unwrap();
+
+        assert_eq!(repository.module_path, "crate::domain::order_repository");
+        assert!(repository.is_async_trait);
+        assert_eq!(
+            repository.methods,
+            [
+                "async fn find_by_id(&self, id: Uuid) -> Result<Option<Order>, RepositoryError>",
+                "async fn find_by_status(&self, status: OrderStatus) -> Result<Vec<Order>, RepositoryError>",
+            ]
+        );
+        assert!(repository
+            .uses
+            .contains(&"use crate::domain::{order::Order, order::OrderStatus};".to_string()));
+        assert_eq!(repository.types, ["RepositoryError"]);
+
+        assert!(analyzer.find_repository_trait("UserRepository").is_err());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_analyze_test_coverage() {
+        let dir =
+            std::env::temp_dir().join(format!("allframe-forge-{}-coverage", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        for layer in ["domain", "application", "presentation"] {
+            fs::create_dir_all(dir.join("src").join(layer)).unwrap();
+        }
+        fs::create_dir_all(dir.join("tests")).unwrap();
+        fs::write(dir.join("Cargo.toml"), "[package]\nname = \"shop\"\n").unwrap();
+
+        // Tested in its own file's test module
+        fs::write(
+            dir.join("src/domain/order.rs"),
+            "pub struct Order {\n    pub id: String,\n}\n\n\
+             #[cfg(test)]\nmod tests {\n    fn order() -> super::Order { todo!() }\n}\n",
+        )
+        .unwrap();
+        // Mentioned only outside the test module: untested
+        fs::write(
+            dir.join("src/domain/customer.rs"),
+            "pub struct Customer {\n    pub id: String,\n}\n\n\
+             #[cfg(test)]\nmod tests {}\n",
+        )
+        .unwrap();
+        // Tested by a sibling test file
+        fs::write(
+            dir.join("src/application/order_service.rs"),
+            "pub struct OrderService {\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("src/application/order_service_tests.rs"),
+            "#[test]\nfn places() { let _ = OrderService {}; }\n",
+        )
+        .unwrap();
+        // One handler covered by an integration test, one not
+        fs::write(
+            dir.join("src/presentation/orders.rs"),
+            "pub async fn get_order() {}\npub async fn delete_order() {}\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("tests/orders.rs"),
+            "#[tokio::test]\nasync fn fetch() { get_order().await; }\n",
+        )
+        .unwrap();
+
+        let report = ProjectAnalyzer::new(&dir)
+            .unwrap()
+            .analyze_test_coverage()
+            .unwrap();
+
+        let tests_of = |name: &str| {
+            report
+                .components
+                .iter()
+                .find(|c| c.name == name)
+                .unwrap()
+                .tests
+                .clone()
+        };
+        assert_eq!(tests_of("Order"), ["src/domain/order.rs"]);
+        assert_eq!(
+            tests_of("OrderService"),
+            ["src/application/order_service_tests.rs"]
+        );
+        assert_eq!(tests_of("get_order"), ["tests/orders.rs"]);
+
+        let mut gaps: Vec<&str> = report.gaps().map(|c| c.name.as_str()).collect();
+        gaps.sort_unstable();
+        assert_eq!(gaps, ["Customer", "delete_order"]);
+
+        let domain = &report.by_layer["domain"];
+        assert_eq!((domain.tested, domain.untested), (1, 1));
+        let presentation = &report.by_layer["presentation"];
+        assert_eq!((presentation.tested, presentation.untested), (1, 1));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
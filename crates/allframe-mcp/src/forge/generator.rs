@@ -4,7 +4,10 @@ use std::{fs, path::PathBuf};
 
 use serde::{Deserialize, Serialize};
 
-use super::analyzer::Layer;
+use super::{
+    analyzer::{Layer, RepositoryTrait},
+    mod_file,
+};
 
 /// Field definition for entity generation
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -75,6 +78,37 @@ pub struct HandlerRequest {
     pub service: Option<String>,
 }
 
+/// Endpoint generation request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EndpointRequest {
+    /// HTTP method
+    pub method: String,
+    /// Route path, with `{param}` or `:param` segments
+    pub path: String,
+    /// What the endpoint does
+    pub description: String,
+    /// Use case name (PascalCase), derived from the method and path if
+    /// omitted
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Request DTO fields
+    #[serde(default)]
+    pub request_fields: Vec<FieldDef>,
+    /// Response DTO fields
+    #[serde(default)]
+    pub response_fields: Vec<FieldDef>,
+}
+
+/// Repository implementation generation request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RepositoryImplRequest {
+    /// Repository trait name (e.g., "UserRepository")
+    pub trait_name: String,
+    /// Implementation struct name, `<trait_name>Impl` if omitted
+    #[serde(default)]
+    pub struct_name: Option<String>,
+}
+
 /// Generation result
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GenerationResult {
@@ -168,34 +202,8 @@ impl CodeGenerator {
     fn generate_entity_content(&self, request: &EntityRequest) -> String {
         let entity_name = &request.name;
 
-        let mut fields = String::new();
-        let mut has_uuid = false;
-        let mut has_chrono = false;
-
-        for field in &request.fields {
-            let field_type = if field.optional {
-                format!("Option<{}>", field.field_type)
-            } else {
-                field.field_type.clone()
-            };
-
-            if field.field_type.contains("Uuid") {
-                has_uuid = true;
-            }
-            if field.field_type.contains("DateTime") || field.field_type.contains("NaiveDate") {
-                has_chrono = true;
-            }
-
-            fields.push_str(&format!("    pub {}: {},\n", field.name, field_type));
-        }
-
-        let mut imports = String::new();
-        if has_uuid {
-            imports.push_str("use uuid::Uuid;\n");
-        }
-        if has_chrono {
-            imports.push_str("use chrono::{DateTime, Utc};\n");
-        }
+        let fields = field_lines(&request.fields);
+        let mut imports = field_imports(&request.fields);
         imports.push_str("use serde::{Deserialize, Serialize};\n");
 
         format!(
@@ -282,6 +290,179 @@ pub trait {entity_name}Repository: Send + Sync {{
         )
     }
 
+    /// Generate an infrastructure implementation of a repository trait
+    ///
+    /// Writes `src/infrastructure/<struct>.rs` with every required trait
+    /// method stubbed, a `shared()` constructor for injecting it into
+    /// services as `Arc<dyn Trait>`, and a `mockall` mock for tests, then
+    /// declares the module in `src/infrastructure/mod.rs`.
+    pub fn generate_repository_impl(
+        &self,
+        repository: &RepositoryTrait,
+        struct_name: Option<&str>,
+    ) -> GenerationResult {
+        let struct_name = struct_name
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("{}Impl", repository.name));
+        if !is_pascal_case(&struct_name) {
+            return GenerationResult::error(format!(
+                "Struct name must be PascalCase: {}",
+                struct_name
+            ));
+        }
+
+        let layer = Layer::Infrastructure;
+        if !self
+            .project_path
+            .join("src")
+            .join(layer.dir_name())
+            .is_dir()
+        {
+            return GenerationResult::error(format!(
+                "Missing layer directory: src/{}",
+                layer.dir_name()
+            ));
+        }
+
+        let snake_name = to_snake_case(&struct_name);
+        let path = format!("src/{}/{}.rs", layer.dir_name(), snake_name);
+        if self.project_path.join(&path).exists() {
+            return GenerationResult::error(format!("{} already exists", path));
+        }
+
+        let content = self.generate_repository_impl_content(repository, &struct_name);
+        let violations = layer_violations(layer, &content);
+        if !violations.is_empty() {
+            return GenerationResult::error(format!(
+                "{} would break architecture rules: {}",
+                path,
+                violations.join("; ")
+            ));
+        }
+
+        if let Err(e) = fs::write(self.project_path.join(&path), content) {
+            return GenerationResult::error(format!("Failed to write {}: {}", path, e));
+        }
+        if let Err(e) = self.add_modules(layer, &[&snake_name], true) {
+            return GenerationResult::error(format!("Failed to update mod.rs: {}", e));
+        }
+
+        GenerationResult::success(vec![path], vec!["src/infrastructure/mod.rs".to_string()])
+    }
+
+    /// Generate repository implementation file content
+    fn generate_repository_impl_content(
+        &self,
+        repository: &RepositoryTrait,
+        struct_name: &str,
+    ) -> String {
+        let trait_name = &repository.name;
+
+        let mut imports = Vec::new();
+        if !repository.uses.iter().any(|u| u.contains("Arc")) {
+            imports.push("use std::sync::Arc;".to_string());
+        }
+        if repository.is_async_trait && !repository.uses.iter().any(|u| u.contains("async_trait")) {
+            imports.push("use async_trait::async_trait;".to_string());
+        }
+        imports.extend(repository.uses.iter().cloned());
+        let mut items = vec![trait_name.clone()];
+        items.extend(repository.types.iter().cloned());
+        imports.push(format!(
+            "use {}::{{{}}};",
+            repository.module_path,
+            items.join(", ")
+        ));
+        // std, then external crates, then the crate itself
+        let mut groups: [Vec<String>; 3] = Default::default();
+        for import in imports {
+            let path = import.trim_start_matches("pub ").trim_start_matches("use ");
+            let group = if path.starts_with("std::") {
+                0
+            } else if path.starts_with("crate::") {
+                2
+            } else {
+                1
+            };
+            groups[group].push(import);
+        }
+        let imports = groups
+            .iter()
+            .filter(|group| !group.is_empty())
+            .map(|group| group.join("\n"))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+
+        let async_attr = if repository.is_async_trait {
+            "#[async_trait]\n"
+        } else {
+            ""
+        };
+        let stubs: String = repository
+            .methods
+            .iter()
+            .map(|signature| {
+                let name = signature
+                    .split("fn ")
+                    .nth(1)
+                    .and_then(|rest| rest.split(['(', '<']).next())
+                    .unwrap_or_default();
+                format!(
+                    "    {} {{\n        todo!(\"{}::{}\")\n    }}\n",
+                    signature, struct_name, name
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let mock_methods: String = repository
+            .methods
+            .iter()
+            .map(|signature| format!("        {};\n", signature))
+            .collect();
+
+        format!(
+            r#"//! {struct_name}: {trait_name} implementation
+
+{imports}
+
+/// {trait_name} implementation
+#[derive(Debug, Clone, Default)]
+pub struct {struct_name} {{}}
+
+impl {struct_name} {{
+    /// Create a new {struct_name}
+    pub fn new() -> Self {{
+        Self::default()
+    }}
+
+    /// Share the repository for injection into services
+    pub fn shared(self) -> Arc<dyn {trait_name}> {{
+        Arc::new(self)
+    }}
+}}
+
+{async_attr}#[allow(unused_variables)]
+impl {trait_name} for {struct_name} {{
+{stubs}}}
+
+#[cfg(test)]
+mockall::mock! {{
+    pub {trait_name} {{}}
+
+    {async_attr_indented}impl {trait_name} for {trait_name} {{
+{mock_methods}    }}
+}}
+"#,
+            struct_name = struct_name,
+            trait_name = trait_name,
+            imports = imports,
+            async_attr = async_attr,
+            async_attr_indented = async_attr.replace('\n', "\n    "),
+            stubs = stubs,
+            mock_methods = mock_methods,
+        )
+    }
+
     /// Generate a new service
     pub fn generate_service(&self, request: &ServiceRequest) -> GenerationResult {
         let mut files_created = Vec::new();
@@ -469,6 +650,273 @@ use uuid::Uuid;
         )
     }
 
+    /// Generate a REST endpoint end-to-end
+    ///
+    /// Writes the request/response DTOs and use case to the application
+    /// layer, and a handler with its route registration and a test stub to
+    /// the presentation layer, then registers the handler in
+    /// `src/presentation/routes.rs`. Nothing is written unless the request
+    /// and the generated code pass [`validate_endpoint`](Self::validate_endpoint).
+    pub fn generate_endpoint(&self, request: &EndpointRequest) -> GenerationResult {
+        let plan = match self.validate_endpoint(request) {
+            Ok(plan) => plan,
+            Err(e) => return GenerationResult::error(e),
+        };
+
+        let mut files_created = Vec::new();
+        let mut files_modified = Vec::new();
+
+        for (path, content) in &plan.files {
+            if let Err(e) = fs::write(self.project_path.join(path), content) {
+                return GenerationResult::error(format!("Failed to write {}: {}", path, e));
+            }
+            files_created.push(path.clone());
+        }
+
+        let snake_name = to_snake_case(&plan.name);
+        if let Err(e) = self.update_mod_file(Layer::Application, &snake_name, false) {
+            return GenerationResult::error(format!("Failed to update mod.rs: {}", e));
+        }
+        files_modified.push("src/application/mod.rs".to_string());
+
+        let handler_module = format!("{}_handler", snake_name);
+        if let Err(e) = self.add_modules(Layer::Presentation, &[&handler_module, "routes"], false) {
+            return GenerationResult::error(format!("Failed to update mod.rs: {}", e));
+        }
+        files_modified.push("src/presentation/mod.rs".to_string());
+
+        let routes_path = self.project_path.join(ROUTES_FILE);
+        let routes_existed = routes_path.exists();
+        if let Err(e) = fs::write(&routes_path, &plan.routes) {
+            return GenerationResult::error(format!("Failed to write {}: {}", ROUTES_FILE, e));
+        }
+        if routes_existed {
+            files_modified.push(ROUTES_FILE.to_string());
+        } else {
+            files_created.push(ROUTES_FILE.to_string());
+        }
+
+        GenerationResult::success(files_created, files_modified)
+    }
+
+    /// Check an endpoint request and plan the files it generates
+    ///
+    /// Fails if the method or path is invalid, a layer directory is
+    /// missing, a generated file already exists, or generated code would
+    /// import from a layer its own layer mustn't depend on.
+    pub fn validate_endpoint(&self, request: &EndpointRequest) -> Result<EndpointPlan, String> {
+        let method = request.method.to_uppercase();
+        if !HTTP_METHODS.contains(&method.as_str()) {
+            return Err(format!("Unsupported HTTP method: {}", request.method));
+        }
+        if !request.path.starts_with('/') {
+            return Err(format!("Route path must start with '/': {}", request.path));
+        }
+        let name = match &request.name {
+            Some(name) => name.clone(),
+            None => endpoint_name(&method, &request.path)
+                .ok_or_else(|| format!("Can't derive a name from {}; pass one", request.path))?,
+        };
+        if !is_pascal_case(&name) {
+            return Err(format!("Endpoint name must be PascalCase: {}", name));
+        }
+
+        for layer in [Layer::Application, Layer::Presentation] {
+            let dir = self.project_path.join("src").join(layer.dir_name());
+            if !dir.is_dir() {
+                return Err(format!("Missing layer directory: src/{}", layer.dir_name()));
+            }
+            // The mod.rs is edited after the new files are written, so make
+            // sure it can be
+            if let Ok(source) = fs::read_to_string(dir.join("mod.rs")) {
+                mod_file::add_module(&source, "routes", false)
+                    .map_err(|e| format!("src/{}/mod.rs: {}", layer.dir_name(), e))?;
+            }
+        }
+
+        let snake_name = to_snake_case(&name);
+        let files = vec![
+            (
+                format!("src/application/{}.rs", snake_name),
+                self.generate_use_case_content(request, &method, &name),
+            ),
+            (
+                format!("src/presentation/{}_handler.rs", snake_name),
+                self.generate_endpoint_handler_content(request, &method, &name),
+            ),
+        ];
+        for (path, content) in &files {
+            if self.project_path.join(path).exists() {
+                return Err(format!("{} already exists", path));
+            }
+            let layer = if path.starts_with("src/application/") {
+                Layer::Application
+            } else {
+                Layer::Presentation
+            };
+            let violations = layer_violations(layer, content);
+            if !violations.is_empty() {
+                return Err(format!(
+                    "{} would break architecture rules: {}",
+                    path,
+                    violations.join("; ")
+                ));
+            }
+        }
+
+        let existing = fs::read_to_string(self.project_path.join(ROUTES_FILE)).ok();
+        let routes = add_route_registration(existing.as_deref(), &name)?;
+
+        Ok(EndpointPlan {
+            name,
+            files,
+            routes,
+        })
+    }
+
+    /// Generate the DTOs and use case for an endpoint
+    fn generate_use_case_content(
+        &self,
+        request: &EndpointRequest,
+        method: &str,
+        name: &str,
+    ) -> String {
+        let mut all_fields = request.request_fields.clone();
+        all_fields.extend(request.response_fields.iter().cloned());
+        let imports = field_imports(&all_fields);
+        let description = request
+            .description
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        format!(
+            r#"//! {name} use case
+//!
+//! {method} {path}: {description}
+
+use allframe_core::router::ToJsonSchema;
+{imports}use serde::{{Deserialize, Serialize}};
+use thiserror::Error;
+
+/// Request for {method} {path}
+#[derive(Debug, Clone, Serialize, Deserialize, ToJsonSchema)]
+pub struct {name}Request {{
+{request_fields}}}
+
+/// Response for {method} {path}
+#[derive(Debug, Clone, Serialize, Deserialize, ToJsonSchema)]
+pub struct {name}Response {{
+{response_fields}}}
+
+/// {name} error
+#[derive(Debug, Error)]
+pub enum {name}Error {{
+    #[error("Validation error: {{0}}")]
+    Validation(String),
+    #[error("Not found: {{0}}")]
+    NotFound(String),
+    #[error("Internal error: {{0}}")]
+    Internal(String),
+}}
+
+/// {description}
+#[derive(Debug, Default)]
+pub struct {name};
+
+impl {name} {{
+    /// Create a new {name}
+    pub fn new() -> Self {{
+        Self
+    }}
+
+    /// Execute the use case
+    pub async fn execute(&self, request: {name}Request) -> Result<{name}Response, {name}Error> {{
+        todo!("Implement {name} for {{:?}}", request)
+    }}
+}}
+"#,
+            name = name,
+            method = method,
+            path = request.path,
+            description = description,
+            imports = imports,
+            request_fields = field_lines(&request.request_fields),
+            response_fields = field_lines(&request.response_fields),
+        )
+    }
+
+    /// Generate the handler, route registration and test stub for an
+    /// endpoint
+    fn generate_endpoint_handler_content(
+        &self,
+        request: &EndpointRequest,
+        method: &str,
+        name: &str,
+    ) -> String {
+        format!(
+            r#"//! {method} {path} handler
+
+use std::sync::Arc;
+
+use allframe_core::router::{{Method, RouteBuilder, Router, ToJsonSchema}};
+
+use crate::application::{{{name}, {name}Error, {name}Request, {name}Response}};
+
+/// Router handler name for {method} {path}
+pub const HANDLER: &str = "{method}:{path}";
+
+/// Register {method} {path} with its OpenAPI metadata
+pub fn register(router: &mut Router, use_case: Arc<{name}>) {{
+    router.register_result_with_args::<{name}Request, {name}Response, {name}Error, _, _>(
+        HANDLER,
+        move |request| {{
+            let use_case = use_case.clone();
+            async move {{ use_case.execute(request).await }}
+        }},
+    );
+    router.add_route(
+        RouteBuilder::new("{path}", Method::{method})
+            .description({description:?})
+            .request_schema({name}Request::schema())
+            .response_schema({name}Response::schema())
+            .build(),
+    );
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    #[test]
+    fn test_route_is_registered() {{
+        let mut router = Router::new();
+        register(&mut router, Arc::new({name}::new()));
+        assert!(router
+            .routes()
+            .iter()
+            .any(|route| route.path == "{path}" && route.method == "{method}"));
+    }}
+
+    #[tokio::test]
+    #[ignore = "implement {name}::execute first"]
+    async fn test_{snake_name}() {{
+        let mut router = Router::new();
+        register(&mut router, Arc::new({name}::new()));
+        // TODO: Send a valid request and check the response
+        let response = router.execute_with_args(HANDLER, "{{}}").await;
+        assert!(response.is_ok());
+    }}
+}}
+"#,
+            name = name,
+            snake_name = to_snake_case(name),
+            method = method,
+            path = request.path,
+            description = request.description,
+        )
+    }
+
     /// Update domain mod.rs with new entity
     fn update_mod_file(
         &self,
@@ -476,6 +924,26 @@ use uuid::Uuid;
         module_name: &str,
         with_repository: bool,
     ) -> Result<(), String> {
+        self.add_modules(layer, &[module_name], true)?;
+        if with_repository {
+            self.add_modules(layer, &[&format!("{}_repository", module_name)], true)?;
+        }
+        Ok(())
+    }
+
+    /// Update application mod.rs with new service
+    fn update_service_mod_file(&self, service_name: &str) -> Result<(), String> {
+        self.add_modules(
+            Layer::Application,
+            &[&format!("{}_service", service_name)],
+            true,
+        )
+    }
+
+    /// Declare modules in a layer's mod.rs, optionally re-exporting them
+    ///
+    /// Modules that are already declared or re-exported are left alone.
+    fn add_modules(&self, layer: Layer, modules: &[&str], re_export: bool) -> Result<(), String> {
         let mod_path = self
             .project_path
             .join("src")
@@ -483,56 +951,175 @@ use uuid::Uuid;
             .join("mod.rs");
 
         let existing = fs::read_to_string(&mod_path).unwrap_or_default();
-
         let mut content = existing.clone();
-
-        // Add module declaration if not present
-        let mod_decl = format!("pub mod {};", module_name);
-        if !content.contains(&mod_decl) {
-            content = format!("{}\n{}", mod_decl, content);
+        for module in modules {
+            content = mod_file::add_module(&content, module, re_export)?;
         }
-
-        // Add re-export if not present
-        let re_export = format!("pub use {}::*;", module_name);
-        if !content.contains(&re_export) && !content.contains(&format!("pub use {}::", module_name))
-        {
-            content.push_str(&format!("\n{}", re_export));
+        if content == existing {
+            return Ok(());
         }
+        fs::write(&mod_path, content).map_err(|e| format!("Failed to write mod.rs: {}", e))
+    }
+}
 
-        // Add repository if requested
-        if with_repository {
-            let repo_mod = format!("pub mod {}_repository;", module_name);
-            if !content.contains(&repo_mod) {
-                content = format!("{}\n{}", repo_mod, content);
-            }
-            let repo_export = format!("pub use {}_repository::*;", module_name);
-            if !content.contains(&repo_export) {
-                content.push_str(&format!("\n{}", repo_export));
-            }
-        }
+/// Files an endpoint generates, checked by
+/// [`CodeGenerator::validate_endpoint`]
+#[derive(Debug, Clone)]
+pub struct EndpointPlan {
+    /// Use case name (PascalCase)
+    pub name: String,
+    /// New files as (path relative to the project root, content)
+    pub files: Vec<(String, String)>,
+    /// Content of `src/presentation/routes.rs` with the endpoint registered
+    pub routes: String,
+}
 
-        fs::write(&mod_path, content).map_err(|e| format!("Failed to write mod.rs: {}", e))
+/// Route registration module maintained by [`CodeGenerator::generate_endpoint`]
+const ROUTES_FILE: &str = "src/presentation/routes.rs";
+
+/// Line in `register_routes` that new registrations are inserted above
+const ROUTES_MARKER: &str = "// forge:endpoints";
+
+const HTTP_METHODS: &[&str] = &["GET", "POST", "PUT", "DELETE", "PATCH"];
+
+/// Add an endpoint's registration to the routes module, creating it if
+/// needed
+fn add_route_registration(existing: Option<&str>, name: &str) -> Result<String, String> {
+    let snake_name = to_snake_case(name);
+    let content = existing.map(str::to_string).unwrap_or_else(|| {
+        format!(
+            r#"//! Route registration
+
+use allframe_core::router::Router;
+
+/// Register every endpoint's handler and route
+pub fn register_routes(router: &mut Router) {{
+    {marker}
+}}
+"#,
+            marker = ROUTES_MARKER
+        )
+    });
+
+    let registration = format!(
+        "super::{snake_name}_handler::register(router, std::sync::Arc::new(crate::application::{name}::new()));",
+        snake_name = snake_name,
+        name = name,
+    );
+    if content.contains(&registration) {
+        return Ok(content);
     }
 
-    /// Update application mod.rs with new service
-    fn update_service_mod_file(&self, service_name: &str) -> Result<(), String> {
-        let mod_path = self.project_path.join("src/application/mod.rs");
+    let marker_line = content
+        .lines()
+        .find(|line| line.trim() == ROUTES_MARKER)
+        .ok_or_else(|| format!("{} has no '{}' line", ROUTES_FILE, ROUTES_MARKER))?;
+    let indent = &marker_line[..marker_line.len() - marker_line.trim_start().len()];
+    Ok(content.replacen(
+        marker_line,
+        &format!("{indent}{registration}\n{marker_line}"),
+        1,
+    ))
+}
 
-        let existing = fs::read_to_string(&mod_path).unwrap_or_default();
-        let mut content = existing.clone();
+/// Derive a use case name such as `CreateOrders` from a method and path
+fn endpoint_name(method: &str, path: &str) -> Option<String> {
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let is_param = |s: &&str| s.starts_with(':') || s.starts_with('{');
+    let ends_with_param = segments.last().is_some_and(is_param);
+
+    let verb = match method {
+        "GET" if ends_with_param => "Get",
+        "GET" => "List",
+        "POST" => "Create",
+        "PUT" => "Update",
+        "PATCH" => "Patch",
+        "DELETE" => "Delete",
+        _ => return None,
+    };
+    let resource: String = segments
+        .iter()
+        .filter(|s| !is_param(s))
+        .map(|s| to_pascal_case(s))
+        .collect();
+    if resource.is_empty() {
+        return None;
+    }
+    Some(format!("{}{}", verb, resource))
+}
 
-        let mod_decl = format!("pub mod {}_service;", service_name);
-        if !content.contains(&mod_decl) {
-            content = format!("{}\n{}", mod_decl, content);
-        }
+fn is_pascal_case(name: &str) -> bool {
+    name.chars().next().is_some_and(|c| c.is_ascii_uppercase())
+        && name.chars().all(|c| c.is_ascii_alphanumeric())
+}
 
-        let re_export = format!("pub use {}_service::*;", service_name);
-        if !content.contains(&re_export) {
-            content.push_str(&format!("\n{}", re_export));
-        }
+/// Imports architecture rules forbid in a layer, one message per import
+fn layer_violations(layer: Layer, content: &str) -> Vec<String> {
+    let forbidden: &[Layer] = match layer {
+        Layer::Domain => &[
+            Layer::Application,
+            Layer::Infrastructure,
+            Layer::Presentation,
+        ],
+        Layer::Application => &[Layer::Infrastructure, Layer::Presentation],
+        Layer::Infrastructure => &[Layer::Presentation],
+        Layer::Presentation => &[],
+    };
+    forbidden
+        .iter()
+        .filter(|other| content.contains(&format!("crate::{}", other.dir_name())))
+        .map(|other| {
+            format!(
+                "{} must not depend on {}",
+                layer.dir_name(),
+                other.dir_name()
+            )
+        })
+        .collect()
+}
 
-        fs::write(&mod_path, content).map_err(|e| format!("Failed to write mod.rs: {}", e))
+/// Struct field lines for field definitions
+fn field_lines(fields: &[FieldDef]) -> String {
+    fields
+        .iter()
+        .map(|field| {
+            let field_type = if field.optional {
+                format!("Option<{}>", field.field_type)
+            } else {
+                field.field_type.clone()
+            };
+            format!("    pub {}: {},\n", field.name, field_type)
+        })
+        .collect()
+}
+
+/// `use` lines for the external types field definitions need
+fn field_imports(fields: &[FieldDef]) -> String {
+    let mut imports = String::new();
+    if fields.iter().any(|f| f.field_type.contains("Uuid")) {
+        imports.push_str("use uuid::Uuid;\n");
+    }
+    if fields
+        .iter()
+        .any(|f| f.field_type.contains("DateTime") || f.field_type.contains("NaiveDate"))
+    {
+        imports.push_str("use chrono::{DateTime, Utc};\n");
     }
+    imports
+}
+
+/// Convert a snake_case or kebab-case path segment to PascalCase
+fn to_pascal_case(s: &str) -> String {
+    s.split(['_', '-'])
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect()
 }
 
 /// Convert PascalCase to snake_case
@@ -585,4 +1172,207 @@ mod tests {
         assert!(json.contains("User"));
         assert!(json.contains("email"));
     }
+
+    fn temp_project(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("allframe-forge-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        for layer in ["application", "presentation"] {
+            fs::create_dir_all(dir.join("src").join(layer)).unwrap();
+        }
+        fs::write(dir.join("src/presentation/mod.rs"), "pub mod handlers;\n").unwrap();
+        dir
+    }
+
+    fn endpoint(method: &str, path: &str) -> EndpointRequest {
+        EndpointRequest {
+            method: method.to_string(),
+            path: path.to_string(),
+            description: "Cancel an order".to_string(),
+            name: None,
+            request_fields: vec![FieldDef {
+                name: "reason".to_string(),
+                field_type: "String".to_string(),
+                optional: true,
+            }],
+            response_fields: vec![FieldDef {
+                name: "cancelled_at".to_string(),
+                field_type: "DateTime<Utc>".to_string(),
+                optional: false,
+            }],
+        }
+    }
+
+    #[test]
+    fn test_endpoint_name() {
+        assert_eq!(endpoint_name("POST", "/orders").unwrap(), "CreateOrders");
+        assert_eq!(endpoint_name("GET", "/orders/{id}").unwrap(), "GetOrders");
+        assert_eq!(
+            endpoint_name("GET", "/order-items").unwrap(),
+            "ListOrderItems"
+        );
+        assert_eq!(
+            endpoint_name("POST", "/orders/:id/cancel").unwrap(),
+            "CreateOrdersCancel"
+        );
+        assert!(endpoint_name("GET", "/{id}").is_none());
+    }
+
+    #[test]
+    fn test_generate_endpoint() {
+        let dir = temp_project("endpoint");
+        let generator = CodeGenerator::new(&dir);
+
+        let mut request = endpoint("post", "/orders/{id}/cancel");
+        request.name = Some("CancelOrder".to_string());
+        let result = generator.generate_endpoint(&request);
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(
+            result.files_created,
+            [
+                "src/application/cancel_order.rs",
+                "src/presentation/cancel_order_handler.rs",
+                "src/presentation/routes.rs"
+            ]
+        );
+
+        let use_case = fs::read_to_string(dir.join("src/application/cancel_order.rs")).unwrap();
+        assert!(use_case.contains("#[derive(Debug, Clone, Serialize, Deserialize, ToJsonSchema)]"));
+        assert!(use_case.contains("pub reason: Option<String>,"));
+        assert!(use_case.contains("use chrono::{DateTime, Utc};"));
+        assert!(use_case.contains("pub async fn execute(&self, request: CancelOrderRequest)"));
+
+        let handler =
+            fs::read_to_string(dir.join("src/presentation/cancel_order_handler.rs")).unwrap();
+        assert!(handler.contains(r#"pub const HANDLER: &str = "POST:/orders/{id}/cancel";"#));
+        assert!(handler.contains(r#"RouteBuilder::new("/orders/{id}/cancel", Method::POST)"#));
+        assert!(handler.contains("#[cfg(test)]"));
+
+        let presentation_mod = fs::read_to_string(dir.join("src/presentation/mod.rs")).unwrap();
+        assert!(presentation_mod.contains("pub mod cancel_order_handler;"));
+        assert!(presentation_mod.contains("pub mod routes;"));
+        let application_mod = fs::read_to_string(dir.join("src/application/mod.rs")).unwrap();
+        assert!(application_mod.contains("pub use cancel_order::*;"));
+
+        // A second endpoint is registered next to the first
+        let result = generator.generate_endpoint(&endpoint("GET", "/orders"));
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(
+            result.files_modified.last().unwrap(),
+            "src/presentation/routes.rs"
+        );
+        let routes = fs::read_to_string(dir.join("src/presentation/routes.rs")).unwrap();
+        let cancel = routes.find("cancel_order_handler::register").unwrap();
+        let list = routes.find("list_orders_handler::register").unwrap();
+        assert!(cancel < list && list < routes.find(ROUTES_MARKER).unwrap());
+
+        // Existing files are never overwritten
+        let result = generator.generate_endpoint(&endpoint("GET", "/orders"));
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("already exists"));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_validate_endpoint() {
+        let dir = temp_project("validate");
+        let generator = CodeGenerator::new(&dir);
+
+        let error = generator
+            .validate_endpoint(&endpoint("TRACE", "/orders"))
+            .unwrap_err();
+        assert!(error.contains("Unsupported HTTP method"));
+        let error = generator
+            .validate_endpoint(&endpoint("GET", "orders"))
+            .unwrap_err();
+        assert!(error.contains("must start with '/'"));
+
+        let mut request = endpoint("GET", "/orders");
+        request.name = Some("list_orders".to_string());
+        assert!(generator
+            .validate_endpoint(&request)
+            .unwrap_err()
+            .contains("PascalCase"));
+
+        fs::write(dir.join(ROUTES_FILE), "pub fn register_routes() {}\n").unwrap();
+        let error = generator
+            .validate_endpoint(&endpoint("GET", "/orders"))
+            .unwrap_err();
+        assert!(error.contains("forge:endpoints"));
+        // Validation failures write nothing
+        assert!(!dir.join("src/application/list_orders.rs").exists());
+
+        assert_eq!(
+            layer_violations(Layer::Application, "use crate::infrastructure::Db;"),
+            ["application must not depend on infrastructure"]
+        );
+        assert!(layer_violations(Layer::Presentation, "use crate::application::X;").is_empty());
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_generate_repository_impl() {
+        let dir = temp_project("repository-impl");
+        let repository = RepositoryTrait {
+            name: "OrderRepository".to_string(),
+            file_path: "src/domain/order_repository.rs".to_string(),
+            module_path: "crate::domain::order_repository".to_string(),
+            is_async_trait: true,
+            methods: vec![
+                "async fn find_by_id(&self, id: Uuid) -> Result<Option<Order>, RepositoryError>"
+                    .to_string(),
+                "async fn save(&self, order: &Order) -> Result<(), RepositoryError>".to_string(),
+            ],
+            uses: vec![
+                "use uuid::Uuid;".to_string(),
+                "use crate::domain::order::Order;".to_string(),
+            ],
+            types: vec!["RepositoryError".to_string()],
+        };
+        let generator = CodeGenerator::new(&dir);
+
+        // No infrastructure layer yet
+        let result = generator.generate_repository_impl(&repository, None);
+        assert!(result.error.unwrap().contains("src/infrastructure"));
+
+        fs::create_dir_all(dir.join("src/infrastructure")).unwrap();
+        let result = generator.generate_repository_impl(&repository, None);
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(
+            result.files_created,
+            ["src/infrastructure/order_repository_impl.rs"]
+        );
+
+        let content =
+            fs::read_to_string(dir.join("src/infrastructure/order_repository_impl.rs")).unwrap();
+        assert!(content
+            .contains("use crate::domain::order_repository::{OrderRepository, RepositoryError};"));
+        assert!(content.starts_with(
+            "//! OrderRepositoryImpl: OrderRepository implementation\n\n\
+             use std::sync::Arc;\n\n\
+             use async_trait::async_trait;\n\
+             use uuid::Uuid;\n\n\
+             use crate::domain::order::Order;\n"
+        ));
+        assert!(content.contains("#[async_trait]\n#[allow(unused_variables)]\nimpl OrderRepository for OrderRepositoryImpl {"));
+        assert!(content.contains("todo!(\"OrderRepositoryImpl::find_by_id\")"));
+        assert!(content.contains("pub fn shared(self) -> Arc<dyn OrderRepository>"));
+        assert!(content.contains("mockall::mock! {"));
+        assert!(content.contains(
+            "        async fn save(&self, order: &Order) -> Result<(), RepositoryError>;\n"
+        ));
+        assert!(fs::read_to_string(dir.join("src/infrastructure/mod.rs"))
+            .unwrap()
+            .contains("pub mod order_repository_impl;"));
+
+        let result = generator.generate_repository_impl(&repository, None);
+        assert!(result.error.unwrap().contains("already exists"));
+        let result =
+            generator.generate_repository_impl(&repository, Some("InMemoryOrderRepository"));
+        assert!(result.success);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
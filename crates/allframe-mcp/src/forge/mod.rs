@@ -24,8 +24,12 @@
 
 mod analyzer;
 mod generator;
+mod mod_file;
 mod server;
 
-pub use analyzer::{Entity, Handler, Layer, ProjectAnalyzer, ProjectStructure, Service};
+pub use analyzer::{
+    ComponentCoverage, ComponentKind, Entity, Handler, Layer, LayerCoverage, ProjectAnalyzer,
+    ProjectStructure, RepositoryTrait, Service, TestCoverageReport,
+};
 pub use generator::CodeGenerator;
 pub use server::ForgeMcpServer;
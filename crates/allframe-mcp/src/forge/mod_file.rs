@@ -0,0 +1,272 @@
+//! Idempotent edits to `mod.rs` files
+//!
+//! The generator adds modules to existing `mod.rs` files that users may
+//! have edited by hand. Edits are planned on the parsed file, so existing
+//! declarations and re-exports are recognised however they're written, and
+//! applied by inserting whole lines, so the rest of the file keeps its
+//! formatting and comments.
+
+use syn::{spanned::Spanned, Item, UseTree};
+
+/// Add `pub mod <module>;` and, if `re_export` is set, `pub use
+/// <module>::*;` to a `mod.rs` source
+///
+/// Either is only added if absent: any `mod <module>` declaration counts,
+/// as does any `use` of a path starting with `<module>` or
+/// `self::<module>`. Adding the same module twice returns the source
+/// unchanged.
+///
+/// The declaration goes with the other `mod` declarations, at its sorted
+/// position if they're sorted and after them otherwise; the re-export goes
+/// after the last `pub use`, or after the declarations if there is none.
+pub fn add_module(source: &str, module: &str, re_export: bool) -> Result<String, String> {
+    let mut source = source.to_string();
+
+    let file = parse(&source)?;
+    if !has_module(&file.items, module) {
+        let line = declaration_line(&file, module);
+        source = insert_line(&source, line, &format!("pub mod {};", module));
+    }
+
+    if re_export {
+        let file = parse(&source)?;
+        if !has_re_export(&file.items, module) {
+            let line = re_export_line(&file);
+            source = insert_line(&source, line, &format!("pub use {}::*;", module));
+        }
+    }
+
+    Ok(source)
+}
+
+fn parse(source: &str) -> Result<syn::File, String> {
+    syn::parse_file(source).map_err(|e| format!("Failed to parse mod.rs: {}", e))
+}
+
+fn has_module(items: &[Item], module: &str) -> bool {
+    items
+        .iter()
+        .any(|item| matches!(item, Item::Mod(m) if m.ident == module))
+}
+
+fn has_re_export(items: &[Item], module: &str) -> bool {
+    items.iter().any(|item| match item {
+        Item::Use(u) => use_starts_with(&u.tree, module),
+        _ => false,
+    })
+}
+
+fn use_starts_with(tree: &UseTree, module: &str) -> bool {
+    match tree {
+        UseTree::Path(path) if path.ident == "self" => use_starts_with(&path.tree, module),
+        UseTree::Path(path) => path.ident == module,
+        UseTree::Group(group) => group.items.iter().any(|t| use_starts_with(t, module)),
+        _ => false,
+    }
+}
+
+/// Where a new line goes: before line `Before(n)` or after line `After(n)`
+/// (1-based), or at the start of the file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Line {
+    Start,
+    Before(usize),
+    After(usize),
+}
+
+fn declaration_line(file: &syn::File, module: &str) -> Line {
+    let mods: Vec<&syn::ItemMod> = file
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Mod(m) if m.content.is_none() => Some(m),
+            _ => None,
+        })
+        .collect();
+
+    let Some(last) = mods.last() else {
+        return after_header(file);
+    };
+    let sorted = mods.windows(2).all(|pair| pair[0].ident <= pair[1].ident);
+    if sorted {
+        if let Some(next) = mods.iter().find(|m| m.ident.to_string().as_str() > module) {
+            return Line::Before(next.span().start().line);
+        }
+    }
+    Line::After(last.span().end().line)
+}
+
+fn re_export_line(file: &syn::File) -> Line {
+    let last_pub_use =
+        file.items.iter().rev().find(
+            |item| matches!(item, Item::Use(u) if matches!(u.vis, syn::Visibility::Public(_))),
+        );
+    if let Some(item) = last_pub_use {
+        return Line::After(item.span().end().line);
+    }
+    match file
+        .items
+        .iter()
+        .rev()
+        .find(|item| matches!(item, Item::Mod(_)))
+    {
+        Some(item) => Line::After(item.span().end().line),
+        None => after_header(file),
+    }
+}
+
+/// After the file's inner attributes and `//!` docs
+fn after_header(file: &syn::File) -> Line {
+    match file.attrs.last() {
+        Some(attr) => Line::After(attr.span().end().line),
+        None => Line::Start,
+    }
+}
+
+/// Insert a line, separating it from a header or from a different kind of
+/// item with a blank line
+///
+/// Keeps the source's line endings (`\r\n` if it uses any) and whether it
+/// ends with a newline; an empty source gets one.
+fn insert_line(source: &str, at: Line, text: &str) -> String {
+    let mut lines: Vec<&str> = source.lines().collect();
+    let mut index = match at {
+        Line::Start => 0,
+        Line::Before(line) => line - 1,
+        Line::After(line) => line,
+    }
+    .min(lines.len());
+    if let Line::Before(_) = at {
+        // Keep comments above the following item attached to it
+        while index > 0 && is_comment(lines[index - 1]) {
+            index -= 1;
+        }
+    }
+
+    let is_use = |line: &str| line.trim_start().starts_with("pub use ");
+    let same_kind = |line: &str| {
+        let trimmed = line.trim_start();
+        if is_comment(line) {
+            true
+        } else if is_use(text) {
+            is_use(line)
+        } else {
+            trimmed.starts_with("pub mod ") || trimmed.starts_with("mod ")
+        }
+    };
+
+    let mut block = Vec::new();
+    if index > 0 && !lines[index - 1].trim().is_empty() && !same_kind(lines[index - 1]) {
+        block.push("");
+    }
+    block.push(text);
+    if index < lines.len() && !lines[index].trim().is_empty() && !same_kind(lines[index]) {
+        block.push("");
+    }
+    lines.splice(index..index, block);
+
+    let newline = if source.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut result = lines.join(newline);
+    if source.is_empty() || source.ends_with('\n') {
+        result.push_str(newline);
+    }
+    result
+}
+
+/// A `//` comment, but not a `//!` inner doc comment
+fn is_comment(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("//") && !trimmed.starts_with("//!")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_adding_twice_is_a_no_op() {
+        let once = add_module("", "order", true).unwrap();
+        assert_eq!(once, "pub mod order;\n\npub use order::*;\n");
+        assert_eq!(add_module(&once, "order", true).unwrap(), once);
+
+        // Existing declarations and re-exports count however they're written
+        let source = "mod order;\npub use self::order::{Order, OrderId};\n";
+        assert_eq!(add_module(source, "order", true).unwrap(), source);
+        let source = "pub(crate) mod order {}\npub use {order::Order, user::User};\n";
+        assert_eq!(add_module(source, "order", true).unwrap(), source);
+    }
+
+    #[test]
+    fn test_keeps_ordering_and_formatting() {
+        let source = "\
+//! Domain layer
+
+pub mod customer;
+// Orders and their lines
+pub mod order;
+pub mod user;
+
+pub use customer::*;
+pub use order::*;
+
+/// Kept as written
+pub fn   helper() {}
+";
+        let updated = add_module(source, "invoice", true).unwrap();
+        assert_eq!(
+            updated,
+            "\
+//! Domain layer
+
+pub mod customer;
+pub mod invoice;
+// Orders and their lines
+pub mod order;
+pub mod user;
+
+pub use customer::*;
+pub use order::*;
+pub use invoice::*;
+
+/// Kept as written
+pub fn   helper() {}
+"
+        );
+        assert_eq!(add_module(&updated, "invoice", true).unwrap(), updated);
+
+        // Unsorted declarations get the new one at the end
+        let source = "pub mod b;\npub mod a;\n";
+        assert_eq!(
+            add_module(source, "c", false).unwrap(),
+            "pub mod b;\npub mod a;\npub mod c;\n"
+        );
+    }
+
+    #[test]
+    fn test_header_and_errors() {
+        assert_eq!(
+            add_module("//! Handlers\n", "routes", false).unwrap(),
+            "//! Handlers\n\npub mod routes;\n"
+        );
+        assert_eq!(
+            add_module("pub mod routes;\n", "routes", true).unwrap(),
+            "pub mod routes;\n\npub use routes::*;\n"
+        );
+        // Line endings and a missing final newline are kept
+        assert_eq!(
+            add_module("pub mod a;\r\npub mod c;\r\n", "b", true).unwrap(),
+            "pub mod a;\r\npub mod b;\r\npub mod c;\r\n\r\npub use b::*;\r\n"
+        );
+        assert_eq!(
+            add_module("pub mod a;", "b", false).unwrap(),
+            "pub mod a;\npub mod b;"
+        );
+        assert!(add_module("pub mod {", "routes", false)
+            .unwrap_err()
+            .contains("Failed to parse mod.rs"));
+    }
+}
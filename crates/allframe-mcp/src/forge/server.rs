@@ -9,22 +9,12 @@ use serde_json::{json, Value};
 
 use super::{
     analyzer::{ProjectAnalyzer, ProjectStructure},
-    generator::{CodeGenerator, EntityRequest, HandlerRequest, ServiceRequest},
+    generator::{
+        CodeGenerator, EndpointRequest, EntityRequest, HandlerRequest, RepositoryImplRequest,
+        ServiceRequest,
+    },
 };
-use crate::McpTool;
-
-/// MCP Resource for project context
-#[derive(Debug, Clone)]
-pub struct McpResource {
-    /// Resource URI
-    pub uri: String,
-    /// Resource name
-    pub name: String,
-    /// MIME type
-    pub mime_type: String,
-    /// Description
-    pub description: String,
-}
+use crate::{McpPrompt, McpResource, McpTool};
 
 /// Forge MCP Server for AI-assisted code generation
 pub struct ForgeMcpServer {
@@ -171,6 +161,79 @@ impl ForgeMcpServer {
                     "required": ["name", "method", "path"]
                 }"#,
             ),
+            McpTool::new(
+                "generate_endpoint",
+                "Generate a REST endpoint end-to-end: request/response DTOs, a use case, a \
+                 handler with route registration, and a test stub, after validating \
+                 architecture rules",
+                r#"{
+                    "type": "object",
+                    "properties": {
+                        "method": {
+                            "type": "string",
+                            "description": "HTTP method",
+                            "enum": ["GET", "POST", "PUT", "DELETE", "PATCH"]
+                        },
+                        "path": {
+                            "type": "string",
+                            "description": "Route path (e.g., '/orders/{id}/cancel')"
+                        },
+                        "description": {
+                            "type": "string",
+                            "description": "What the endpoint does"
+                        },
+                        "name": {
+                            "type": "string",
+                            "description": "Use case name in PascalCase (derived from method and path if omitted)"
+                        },
+                        "request_fields": {
+                            "type": "array",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "name": {"type": "string"},
+                                    "field_type": {"type": "string"},
+                                    "optional": {"type": "boolean"}
+                                },
+                                "required": ["name", "field_type"]
+                            },
+                            "description": "Request DTO fields"
+                        },
+                        "response_fields": {
+                            "type": "array",
+                            "items": {
+                                "type": "object",
+                                "properties": {
+                                    "name": {"type": "string"},
+                                    "field_type": {"type": "string"},
+                                    "optional": {"type": "boolean"}
+                                },
+                                "required": ["name", "field_type"]
+                            },
+                            "description": "Response DTO fields"
+                        }
+                    },
+                    "required": ["method", "path", "description"]
+                }"#,
+            ),
+            McpTool::new(
+                "add_repository_impl",
+                "Generate an infrastructure implementation of a domain repository trait, with stubbed methods, DI constructor and mockall mock",
+                r#"{
+                    "type": "object",
+                    "properties": {
+                        "trait_name": {
+                            "type": "string",
+                            "description": "Repository trait name (e.g., 'UserRepository')"
+                        },
+                        "struct_name": {
+                            "type": "string",
+                            "description": "Implementation struct name (default: '<trait_name>Impl')"
+                        }
+                    },
+                    "required": ["trait_name"]
+                }"#,
+            ),
             McpTool::new(
                 "list_entities",
                 "List all domain entities in the project",
@@ -186,6 +249,20 @@ impl ForgeMcpServer {
                 "List all handlers in the presentation layer",
                 r#"{"type": "object", "properties": {}}"#,
             ),
+            McpTool::new(
+                "find_untested",
+                "Find entities, services and handlers without tests, with per-layer counts",
+                r#"{
+                    "type": "object",
+                    "properties": {
+                        "layer": {
+                            "type": "string",
+                            "enum": ["domain", "application", "presentation"],
+                            "description": "Only report components in this layer"
+                        }
+                    }
+                }"#,
+            ),
             McpTool::new(
                 "read_file",
                 "Read a file from the project",
@@ -301,6 +378,24 @@ impl ForgeMcpServer {
                 mime_type: "application/json".to_string(),
                 description: "List of HTTP handlers".to_string(),
             },
+            McpResource {
+                uri: "allframe://project/repositories".to_string(),
+                name: "Domain Repositories".to_string(),
+                mime_type: "application/json".to_string(),
+                description: "Repository traits and the entities they store".to_string(),
+            },
+            McpResource {
+                uri: "allframe://project/files".to_string(),
+                name: "Source Files".to_string(),
+                mime_type: "application/json".to_string(),
+                description: "Source files grouped by Clean Architecture layer".to_string(),
+            },
+            McpResource {
+                uri: "allframe://project/dependencies".to_string(),
+                name: "Dependencies".to_string(),
+                mime_type: "application/json".to_string(),
+                description: "Crate dependencies from Cargo.toml".to_string(),
+            },
             McpResource {
                 uri: "sagas://registry".to_string(),
                 name: "Saga Registry".to_string(),
@@ -310,6 +405,63 @@ impl ForgeMcpServer {
         ]
     }
 
+    /// List available prompts: the code-generation workflows as reusable
+    /// instructions that point the client at the right resources and tools
+    pub fn list_prompts(&self) -> Vec<McpPrompt> {
+        vec![
+            McpPrompt::new(
+                "generate_aggregate",
+                "Add a `{{name}}` aggregate to this AllFrame project.\n\n\
+                 Fields: {{fields}}\n\n\
+                 First read allframe://project/entities and \
+                 allframe://project/repositories to follow the existing naming and \
+                 field conventions. Then call `add_entity` with a repository, \
+                 `add_service` for its use cases and `generate_endpoint` for each \
+                 operation the frontend needs. {{notes}}",
+            )
+            .with_description("Generate a domain aggregate with repository, service and endpoints")
+            .with_argument("name", "Aggregate name in PascalCase (e.g. Order)", true)
+            .with_argument(
+                "fields",
+                "Fields and types (e.g. id: String, total: u64)",
+                true,
+            )
+            .with_argument("notes", "Extra requirements", false),
+            McpPrompt::new(
+                "review_architecture",
+                "Review this project's Clean Architecture.\n\n\
+                 Read allframe://project/structure and allframe://project/files, and \
+                 use `read_file` where needed. Check that the domain layer depends on \
+                 nothing else, that application services depend only on domain \
+                 traits, and that infrastructure implements domain repositories. \
+                 List each violation with its file and a suggested fix. {{focus}}",
+            )
+            .with_description("Review layer dependencies and report Clean Architecture violations")
+            .with_argument("focus", "Area to focus on", false),
+            McpPrompt::new(
+                "design_saga",
+                "Design a `{{name}}` saga for this workflow: {{workflow}}\n\n\
+                 Read sagas://registry to avoid overlapping existing sagas. Give each \
+                 step a compensation, then call `create_saga` with the steps and \
+                 `analyze_saga` to check the result.",
+            )
+            .with_description("Design a saga with compensating steps for a workflow")
+            .with_argument("name", "Saga name in PascalCase", true)
+            .with_argument("workflow", "What the saga coordinates", true),
+        ]
+    }
+
+    /// Render a prompt with the given arguments
+    pub fn get_prompt(&self, name: &str, args: &Value) -> Result<(McpPrompt, String), String> {
+        let prompt = self
+            .list_prompts()
+            .into_iter()
+            .find(|p| p.name == name)
+            .ok_or_else(|| format!("Unknown prompt: {}", name))?;
+        let text = prompt.render(args)?;
+        Ok((prompt, text))
+    }
+
     /// Read a resource
     pub fn read_resource(&mut self, uri: &str) -> Result<Value, String> {
         let structure = self.get_structure()?;
@@ -327,6 +479,15 @@ impl ForgeMcpServer {
             "allframe://project/handlers" => {
                 Ok(serde_json::to_value(&structure.handlers).map_err(|e| e.to_string())?)
             }
+            "allframe://project/repositories" => {
+                Ok(serde_json::to_value(&structure.repositories).map_err(|e| e.to_string())?)
+            }
+            "allframe://project/files" => {
+                Ok(serde_json::to_value(&structure.files_by_layer).map_err(|e| e.to_string())?)
+            }
+            "allframe://project/dependencies" => {
+                Ok(serde_json::to_value(&structure.dependencies).map_err(|e| e.to_string())?)
+            }
             "sagas://registry" => self.read_saga_registry(),
             uri if uri.starts_with("saga://") => self.read_saga_resource(uri),
             _ => Err(format!("Unknown resource: {}", uri)),
@@ -366,6 +527,25 @@ impl ForgeMcpServer {
                 Ok(serde_json::to_value(result).map_err(|e| e.to_string())?)
             }
 
+            "generate_endpoint" => {
+                let request: EndpointRequest = serde_json::from_value(args)
+                    .map_err(|e| format!("Invalid endpoint request: {}", e))?;
+                let result = self.generator.generate_endpoint(&request);
+                self.invalidate_cache();
+                Ok(serde_json::to_value(result).map_err(|e| e.to_string())?)
+            }
+
+            "add_repository_impl" => {
+                let request: RepositoryImplRequest = serde_json::from_value(args)
+                    .map_err(|e| format!("Invalid repository request: {}", e))?;
+                let repository = self.analyzer.find_repository_trait(&request.trait_name)?;
+                let result = self
+                    .generator
+                    .generate_repository_impl(&repository, request.struct_name.as_deref());
+                self.invalidate_cache();
+                Ok(serde_json::to_value(result).map_err(|e| e.to_string())?)
+            }
+
             "list_entities" => {
                 let structure = self.get_structure()?;
                 Ok(serde_json::to_value(&structure.entities).map_err(|e| e.to_string())?)
@@ -381,6 +561,21 @@ impl ForgeMcpServer {
                 Ok(serde_json::to_value(&structure.handlers).map_err(|e| e.to_string())?)
             }
 
+            "find_untested" => {
+                let layer = args.get("layer").and_then(|l| l.as_str());
+                let report = self.analyzer.analyze_test_coverage()?;
+                let untested: Vec<_> = report
+                    .gaps()
+                    .filter(|c| layer.map_or(true, |l| c.layer.dir_name() == l))
+                    .collect();
+
+                Ok(json!({
+                    "by_layer": report.by_layer,
+                    "untested": untested,
+                    "untested_count": untested.len(),
+                }))
+            }
+
             "read_file" => {
                 let path = args
                     .get("path")
@@ -469,6 +664,7 @@ impl ForgeMcpServer {
         );
         eprintln!("Tools: {}", self.list_tools().len());
         eprintln!("Resources: {}", self.list_resources().len());
+        eprintln!("Prompts: {}", self.list_prompts().len());
         eprintln!("Listening on stdio...");
 
         for line in stdin.lock().lines() {
@@ -524,6 +720,9 @@ impl ForgeMcpServer {
                     "protocolVersion": "2024-11-05",
                     "capabilities": {
                         "tools": {},
+                        "prompts": {
+                            "listChanged": false
+                        },
                         "resources": {
                             "subscribe": false,
                             "listChanged": false
@@ -576,17 +775,7 @@ impl ForgeMcpServer {
             }
 
             "resources/list" => {
-                let resources = self.list_resources();
-                json!({
-                    "resources": resources.iter().map(|r| {
-                        json!({
-                            "uri": r.uri,
-                            "name": r.name,
-                            "mimeType": r.mime_type,
-                            "description": r.description
-                        })
-                    }).collect::<Vec<_>>()
-                })
+                json!({ "resources": self.list_resources() })
             }
 
             "resources/read" => {
@@ -611,6 +800,33 @@ impl ForgeMcpServer {
                 }
             }
 
+            "prompts/list" => json!({ "prompts": self.list_prompts() }),
+
+            "prompts/get" => {
+                let params = &request["params"];
+                let name = params["name"].as_str().unwrap_or("");
+                let arguments = params.get("arguments").cloned().unwrap_or(json!({}));
+
+                match self.get_prompt(name, &arguments) {
+                    Ok((prompt, text)) => {
+                        json!({
+                            "description": prompt.description,
+                            "messages": [{
+                                "role": "user",
+                                "content": {"type": "text", "text": text}
+                            }]
+                        })
+                    }
+                    Err(e) => {
+                        return json!({
+                            "jsonrpc": "2.0",
+                            "error": {"code": -32602, "message": e},
+                            "id": id
+                        });
+                    }
+                }
+            }
+
             "ping" => json!({}),
 
             "notifications/initialized" => {
@@ -873,10 +1089,153 @@ impl ForgeMcpServer {
 
 #[cfg(test)]
 mod tests {
+    use std::fs;
+
+    use super::*;
+
     #[test]
     fn test_list_tools() {
         // Can't test without a real project, but we can check tool definitions
-        let tools_count = 11; // analyze, add_entity, add_service, add_handler, list_*, read_file, saga tools
+        let tools_count = 12; // analyze, add_*, generate_endpoint, list_*, read_file, saga tools
         assert!(tools_count > 0);
     }
+
+    #[test]
+    fn test_project_structure_resources() {
+        let dir =
+            std::env::temp_dir().join(format!("allframe-forge-{}-resources", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("src/domain")).unwrap();
+        fs::write(
+            dir.join("Cargo.toml"),
+            "[package]\nname = \"shop\"\n\n[dependencies]\nserde = \"1\"\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("src/domain/order.rs"),
+            "pub struct Order {\n    pub id: String,\n}\n",
+        )
+        .unwrap();
+
+        let mut server = ForgeMcpServer::new(dir.clone()).unwrap();
+
+        let list =
+            server.handle_request(json!({"jsonrpc": "2.0", "id": 1, "method": "resources/list"}));
+        let uris: Vec<&str> = list["result"]["resources"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["uri"].as_str().unwrap())
+            .collect();
+        assert!(uris.contains(&"allframe://project/structure"));
+        assert!(uris.contains(&"allframe://project/dependencies"));
+        assert_eq!(
+            list["result"]["resources"][0]["mimeType"],
+            "application/json"
+        );
+
+        let read = server.handle_request(json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "resources/read",
+            "params": {"uri": "allframe://project/structure"}
+        }));
+        let text = read["result"]["contents"][0]["text"].as_str().unwrap();
+        let structure: Value = serde_json::from_str(text).unwrap();
+        assert_eq!(structure["name"], "shop");
+
+        for uri in [
+            "allframe://project/repositories",
+            "allframe://project/files",
+            "allframe://project/dependencies",
+        ] {
+            assert!(server.read_resource(uri).is_ok(), "{uri}");
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_find_untested() {
+        let dir =
+            std::env::temp_dir().join(format!("allframe-forge-{}-untested", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("src/domain")).unwrap();
+        fs::create_dir_all(dir.join("src/application")).unwrap();
+        fs::write(dir.join("Cargo.toml"), "[package]\nname = \"shop\"\n").unwrap();
+        fs::write(
+            dir.join("src/domain/order.rs"),
+            "pub struct Order {\n    pub id: String,\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("src/application/order_service.rs"),
+            "pub struct OrderService {}\n\n#[cfg(test)]\nmod tests {\n    use super::OrderService;\n}\n",
+        )
+        .unwrap();
+
+        let mut server = ForgeMcpServer::new(dir.clone()).unwrap();
+
+        let all = server.call_tool("find_untested", json!({})).unwrap();
+        assert_eq!(all["untested_count"], 1);
+        assert_eq!(all["untested"][0]["name"], "Order");
+        assert_eq!(all["untested"][0]["kind"], "entity");
+        assert_eq!(all["by_layer"]["application"]["tested"], 1);
+
+        let application = server
+            .call_tool("find_untested", json!({"layer": "application"}))
+            .unwrap();
+        assert_eq!(application["untested_count"], 0);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_prompts() {
+        let dir =
+            std::env::temp_dir().join(format!("allframe-forge-{}-prompts", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("Cargo.toml"), "[package]\nname = \"shop\"\n").unwrap();
+        let mut server = ForgeMcpServer::new(dir.clone()).unwrap();
+
+        let list =
+            server.handle_request(json!({"jsonrpc": "2.0", "id": 1, "method": "prompts/list"}));
+        let names: Vec<&str> = list["result"]["prompts"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            ["generate_aggregate", "review_architecture", "design_saga"]
+        );
+
+        let get = server.handle_request(json!({
+            "jsonrpc": "2.0",
+            "id": 2,
+            "method": "prompts/get",
+            "params": {
+                "name": "generate_aggregate",
+                "arguments": {"name": "Order", "fields": "id: String, total: u64"}
+            }
+        }));
+        let text = get["result"]["messages"][0]["content"]["text"]
+            .as_str()
+            .unwrap();
+        assert!(text.starts_with("Add a `Order` aggregate"));
+        assert!(text.contains("Fields: id: String, total: u64"));
+        assert!(!text.contains("{{"));
+
+        let missing = server.handle_request(json!({
+            "jsonrpc": "2.0",
+            "id": 3,
+            "method": "prompts/get",
+            "params": {"name": "design_saga", "arguments": {"name": "Checkout"}}
+        }));
+        assert_eq!(missing["error"]["code"], -32602);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
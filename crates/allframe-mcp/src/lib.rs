@@ -19,6 +19,39 @@
 //! # }
 //! ```
 //!
+//! # Resources
+//!
+//! Readable context (files, config) is exposed with `register_resource` and
+//! served over `resources/list` and `resources/read`:
+//!
+//! ```rust,no_run
+//! use allframe_mcp::{McpServer, ResourceContent};
+//!
+//! let mcp_server = McpServer::new();
+//! mcp_server.register_resource("file:///project/Cargo.toml", || async {
+//!     std::fs::read_to_string("Cargo.toml")
+//!         .map(ResourceContent::text)
+//!         .map_err(|e| e.to_string())
+//! });
+//! ```
+//!
+//! # Prompts
+//!
+//! Reusable prompt templates are exposed with `register_prompt` and served
+//! over `prompts/list` and `prompts/get`:
+//!
+//! ```rust
+//! use allframe_mcp::{McpPrompt, McpServer};
+//!
+//! let mcp_server = McpServer::new();
+//! mcp_server.register_prompt(McpPrompt::new("review", "Review {{file}}"));
+//!
+//! let (_, text) = mcp_server
+//!     .get_prompt("review", &serde_json::json!({"file": "src/main.rs"}))
+//!     .unwrap();
+//! assert_eq!(text, "Review src/main.rs");
+//! ```
+//!
 //! # Forge MCP Server (Code Generation)
 //!
 //! ```rust,no_run
@@ -33,12 +66,19 @@
 //! ```
 
 pub mod forge;
+pub mod prompts;
+pub mod resources;
 pub mod schema;
 pub mod server;
 pub mod stdio;
 pub mod tools;
 
-pub use schema::{coerce_type, extract_enum_values, openapi_to_json_schema, validate_input};
+pub use prompts::{McpPrompt, PromptArgument};
+pub use resources::{McpResource, ResourceContent};
+pub use schema::{
+    coerce_to_schema, coerce_type, extract_enum_values, openapi_to_json_schema, validate_input,
+    ValidationError,
+};
 pub use server::McpServer;
 pub use stdio::{init_tracing, StdioConfig, StdioTransport};
 pub use tools::McpTool;
@@ -0,0 +1,170 @@
+//! MCP Prompt templates
+//!
+//! Prompts are reusable, parameterized instructions a client can offer its
+//! user (listed with `prompts/list`, rendered with `prompts/get`). Templates
+//! use `{{argument}}` placeholders; every placeholder is a required argument
+//! unless declared otherwise with [`McpPrompt::with_argument`].
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// An argument a prompt template accepts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PromptArgument {
+    /// Argument name (matches the `{{name}}` placeholder)
+    pub name: String,
+    /// Human-readable description
+    pub description: String,
+    /// Whether `prompts/get` fails without it
+    pub required: bool,
+}
+
+/// An MCP Prompt: a named template rendered with arguments
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct McpPrompt {
+    /// Prompt name
+    pub name: String,
+    /// Human-readable description
+    pub description: String,
+    /// Arguments, in order of first appearance in the template
+    pub arguments: Vec<PromptArgument>,
+    /// Template text with `{{argument}}` placeholders
+    #[serde(skip)]
+    pub template: String,
+}
+
+impl McpPrompt {
+    /// Create a prompt whose placeholders are all required arguments
+    pub fn new(name: impl Into<String>, template: impl Into<String>) -> Self {
+        let name = name.into();
+        let template = template.into();
+        let mut arguments: Vec<PromptArgument> = Vec::new();
+        for placeholder in placeholders(&template) {
+            if !arguments.iter().any(|a| a.name == placeholder) {
+                arguments.push(PromptArgument {
+                    name: placeholder.to_string(),
+                    description: String::new(),
+                    required: true,
+                });
+            }
+        }
+
+        Self {
+            description: format!("Prompt: {}", name),
+            name,
+            arguments,
+            template,
+        }
+    }
+
+    /// Set the description
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = description.into();
+        self
+    }
+
+    /// Describe an argument, or declare one the template doesn't use
+    pub fn with_argument(
+        mut self,
+        name: impl Into<String>,
+        description: impl Into<String>,
+        required: bool,
+    ) -> Self {
+        let argument = PromptArgument {
+            name: name.into(),
+            description: description.into(),
+            required,
+        };
+        match self.arguments.iter_mut().find(|a| a.name == argument.name) {
+            Some(existing) => *existing = argument,
+            None => self.arguments.push(argument),
+        }
+        self
+    }
+
+    /// Render the template with `args` (a JSON object).
+    ///
+    /// Missing optional arguments render as empty; non-string values
+    /// render as JSON.
+    pub fn render(&self, args: &Value) -> Result<String, String> {
+        let missing: Vec<&str> = self
+            .arguments
+            .iter()
+            .filter(|a| a.required && args.get(&a.name).map_or(true, Value::is_null))
+            .map(|a| a.name.as_str())
+            .collect();
+        if !missing.is_empty() {
+            return Err(format!(
+                "Missing required arguments for prompt '{}': {}",
+                self.name,
+                missing.join(", ")
+            ));
+        }
+
+        let mut rendered = String::with_capacity(self.template.len());
+        let mut rest = self.template.as_str();
+        while let Some(start) = rest.find("{{") {
+            let Some(len) = rest[start + 2..].find("}}") else {
+                break;
+            };
+            rendered.push_str(&rest[..start]);
+            let name = rest[start + 2..start + 2 + len].trim();
+            match args.get(name) {
+                Some(Value::String(s)) => rendered.push_str(s),
+                Some(Value::Null) | None => {}
+                Some(other) => rendered.push_str(&other.to_string()),
+            }
+            rest = &rest[start + 4 + len..];
+        }
+        rendered.push_str(rest);
+        Ok(rendered)
+    }
+}
+
+/// Placeholder names in `template`, in order, with repeats
+fn placeholders(template: &str) -> impl Iterator<Item = &str> {
+    template.split("{{").skip(1).filter_map(|part| {
+        let (name, _) = part.split_once("}}")?;
+        let name = name.trim();
+        (!name.is_empty()).then_some(name)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_placeholders_become_required_arguments() {
+        let prompt = McpPrompt::new("review", "Review {{ file }} for {{focus}}, then {{file}}");
+        let names: Vec<&str> = prompt.arguments.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(names, ["file", "focus"]);
+        assert!(prompt.arguments.iter().all(|a| a.required));
+    }
+
+    #[test]
+    fn test_render() {
+        let prompt = McpPrompt::new("entity", "Add {{name}} with {{fields}}{{note}}")
+            .with_argument("note", "Extra instructions", false);
+
+        let rendered = prompt
+            .render(&json!({"name": "Order", "fields": ["id", "total"]}))
+            .unwrap();
+        assert_eq!(rendered, r#"Add Order with ["id","total"]"#);
+
+        let err = prompt.render(&json!({"name": "Order"})).unwrap_err();
+        assert!(err.contains("fields"));
+    }
+
+    #[test]
+    fn test_serializes_without_template() {
+        let prompt = McpPrompt::new("greet", "Hello {{who}}").with_description("Say hello");
+        let json = serde_json::to_value(&prompt).unwrap();
+        assert_eq!(json["description"], "Say hello");
+        assert_eq!(json["arguments"][0]["name"], "who");
+        assert_eq!(json["arguments"][0]["required"], true);
+        assert!(json.get("template").is_none());
+    }
+}
@@ -0,0 +1,139 @@
+//! MCP Resource definitions
+//!
+//! Resources are readable context (files, config, project metadata) that an
+//! LLM client can list with `resources/list` and fetch with `resources/read`,
+//! as opposed to tools, which it calls.
+
+use serde::{Deserialize, Serialize};
+
+/// An MCP Resource the client can read by URI
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct McpResource {
+    /// Resource URI (e.g. `file:///project/Cargo.toml`)
+    pub uri: String,
+    /// Human-readable name
+    pub name: String,
+    /// MIME type of the content
+    pub mime_type: String,
+    /// Human-readable description
+    pub description: String,
+}
+
+impl McpResource {
+    /// Create a new MCP resource
+    pub fn new(
+        uri: impl Into<String>,
+        name: impl Into<String>,
+        mime_type: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Self {
+        Self {
+            uri: uri.into(),
+            name: name.into(),
+            mime_type: mime_type.into(),
+            description: description.into(),
+        }
+    }
+
+    /// Create a plain-text resource named after its URI
+    pub fn from_uri(uri: impl Into<String>) -> Self {
+        let uri = uri.into();
+        let description = format!("Resource: {}", uri);
+
+        Self {
+            name: uri.clone(),
+            uri,
+            mime_type: "text/plain".to_string(),
+            description,
+        }
+    }
+}
+
+impl From<&str> for McpResource {
+    fn from(uri: &str) -> Self {
+        Self::from_uri(uri)
+    }
+}
+
+impl From<String> for McpResource {
+    fn from(uri: String) -> Self {
+        Self::from_uri(uri)
+    }
+}
+
+/// The content of a resource, as returned by `resources/read`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceContent {
+    /// MIME type of `text`, overriding the resource's declared type
+    pub mime_type: Option<String>,
+    /// The content itself
+    pub text: String,
+}
+
+impl ResourceContent {
+    /// Text content of the resource's declared MIME type
+    pub fn text(text: impl Into<String>) -> Self {
+        Self {
+            mime_type: None,
+            text: text.into(),
+        }
+    }
+
+    /// Pretty-printed JSON content
+    pub fn json(value: &serde_json::Value) -> Self {
+        Self {
+            mime_type: Some("application/json".to_string()),
+            text: serde_json::to_string_pretty(value).unwrap_or_default(),
+        }
+    }
+}
+
+impl From<String> for ResourceContent {
+    fn from(text: String) -> Self {
+        Self::text(text)
+    }
+}
+
+impl From<&str> for ResourceContent {
+    fn from(text: &str) -> Self {
+        Self::text(text)
+    }
+}
+
+impl From<serde_json::Value> for ResourceContent {
+    fn from(value: serde_json::Value) -> Self {
+        Self::json(&value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resource_from_uri() {
+        let resource = McpResource::from("file:///project/Cargo.toml");
+        assert_eq!(resource.uri, "file:///project/Cargo.toml");
+        assert_eq!(resource.name, "file:///project/Cargo.toml");
+        assert_eq!(resource.mime_type, "text/plain");
+        assert!(!resource.description.is_empty());
+    }
+
+    #[test]
+    fn test_resource_serializes_camel_case() {
+        let resource = McpResource::new("config://app", "App config", "application/json", "");
+        let json = serde_json::to_value(&resource).unwrap();
+        assert_eq!(json["mimeType"], "application/json");
+        assert!(json.get("mime_type").is_none());
+    }
+
+    #[test]
+    fn test_content_conversions() {
+        assert_eq!(ResourceContent::from("hi"), ResourceContent::text("hi"));
+
+        let content = ResourceContent::from(serde_json::json!({"a": 1}));
+        assert_eq!(content.mime_type.as_deref(), Some("application/json"));
+        assert!(content.text.contains("\"a\": 1"));
+    }
+}
@@ -1,6 +1,10 @@
 //! JSON Schema generation from OpenAPI specifications
 
-use serde_json::{json, Value};
+use std::{fmt, sync::OnceLock};
+
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
 
 /// Convert OpenAPI schema to JSON Schema (draft 2020-12)
 pub fn openapi_to_json_schema(openapi_schema: &Value) -> Result<String, String> {
@@ -36,77 +40,474 @@ pub fn openapi_to_json_schema(openapi_schema: &Value) -> Result<String, String>
         .map_err(|e| format!("Failed to serialize JSON Schema: {}", e))
 }
 
+/// A single input validation failure
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValidationError {
+    /// JSON Pointer to the offending value (e.g. `/user/email`, `""` for
+    /// the root)
+    pub path: String,
+    /// What was wrong with the value
+    pub message: String,
+}
+
+impl ValidationError {
+    fn new(path: &str, message: impl Into<String>) -> Self {
+        Self {
+            path: path.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
 /// Validate input against JSON Schema
-pub fn validate_input(input: &Value, schema: &str) -> Result<(), Vec<String>> {
-    // Parse schema
-    let schema_val: Value =
-        serde_json::from_str(schema).map_err(|e| vec![format!("Invalid schema: {}", e)])?;
+///
+/// Checks `type` (including `nullable`), `enum`, `required`, nested
+/// `properties`/`items`, `additionalProperties: false`, numeric
+/// `minimum`/`maximum`/`exclusiveMinimum`/`exclusiveMaximum`, string
+/// `minLength`/`maxLength`/`pattern`, array `minItems`/`maxItems`, and the
+/// `email`, `uuid`, `date` and `date-time` formats. Every violation is
+/// reported with the JSON Pointer path of the offending value.
+pub fn validate_input(input: &Value, schema: &str) -> Result<(), Vec<ValidationError>> {
+    let schema_val: Value = serde_json::from_str(schema)
+        .map_err(|e| vec![ValidationError::new("", format!("Invalid schema: {}", e))])?;
 
-    // Basic validation - check required fields
     let mut errors = Vec::new();
+    validate_value(input, &schema_val, "", &mut errors);
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
 
-    if let Some(required) = schema_val.get("required").and_then(|r| r.as_array()) {
-        let input_obj = input
-            .as_object()
-            .ok_or(vec!["Input must be an object".to_string()])?;
+fn validate_value(value: &Value, schema: &Value, path: &str, errors: &mut Vec<ValidationError>) {
+    let nullable = schema.get("nullable") == Some(&Value::Bool(true));
+    if value.is_null() && nullable {
+        return;
+    }
 
-        for req_field in required {
-            if let Some(field_name) = req_field.as_str() {
-                if !input_obj.contains_key(field_name) {
-                    errors.push(format!("Missing required field: {}", field_name));
-                }
+    if let Some(expected) = schema.get("type") {
+        let expected: Vec<&str> = match expected {
+            Value::String(t) => vec![t.as_str()],
+            Value::Array(types) => types.iter().filter_map(|t| t.as_str()).collect(),
+            _ => vec![],
+        };
+        if !expected.is_empty() && !expected.iter().any(|t| type_matches(value, t)) {
+            errors.push(ValidationError::new(
+                path,
+                format!(
+                    "wrong type: expected {}, got {}",
+                    expected.join(" or "),
+                    type_name(value)
+                ),
+            ));
+            // Constraints for the expected type don't apply to this value
+            return;
+        }
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(|e| e.as_array()) {
+        if !allowed.contains(value) {
+            let allowed: Vec<String> = allowed.iter().map(|v| v.to_string()).collect();
+            errors.push(ValidationError::new(
+                path,
+                format!("must be one of: {}", allowed.join(", ")),
+            ));
+        }
+    }
+
+    match value {
+        Value::Number(n) => validate_number(n.as_f64().unwrap_or_default(), schema, path, errors),
+        Value::String(s) => validate_string(s, schema, path, errors),
+        Value::Array(items) => validate_array(items, schema, path, errors),
+        Value::Object(map) => validate_object(map, schema, path, errors),
+        Value::Null | Value::Bool(_) => {}
+    }
+}
+
+fn validate_number(n: f64, schema: &Value, path: &str, errors: &mut Vec<ValidationError>) {
+    let bound = |key: &str| schema.get(key).and_then(|v| v.as_f64());
+
+    if let Some(min) = bound("minimum") {
+        if n < min {
+            errors.push(ValidationError::new(path, format!("must be >= {}", min)));
+        }
+    }
+    if let Some(max) = bound("maximum") {
+        if n > max {
+            errors.push(ValidationError::new(path, format!("must be <= {}", max)));
+        }
+    }
+    if let Some(min) = bound("exclusiveMinimum") {
+        if n <= min {
+            errors.push(ValidationError::new(path, format!("must be > {}", min)));
+        }
+    }
+    if let Some(max) = bound("exclusiveMaximum") {
+        if n >= max {
+            errors.push(ValidationError::new(path, format!("must be < {}", max)));
+        }
+    }
+}
+
+fn validate_string(s: &str, schema: &Value, path: &str, errors: &mut Vec<ValidationError>) {
+    let len = s.chars().count() as u64;
+
+    if let Some(min) = schema.get("minLength").and_then(|v| v.as_u64()) {
+        if len < min {
+            errors.push(ValidationError::new(
+                path,
+                format!("must be at least {} characters", min),
+            ));
+        }
+    }
+    if let Some(max) = schema.get("maxLength").and_then(|v| v.as_u64()) {
+        if len > max {
+            errors.push(ValidationError::new(
+                path,
+                format!("must be at most {} characters", max),
+            ));
+        }
+    }
+    if let Some(pattern) = schema.get("pattern").and_then(|v| v.as_str()) {
+        match Regex::new(pattern) {
+            Ok(re) if !re.is_match(s) => {
+                errors.push(ValidationError::new(
+                    path,
+                    format!("must match pattern {}", pattern),
+                ));
             }
+            Ok(_) => {}
+            Err(e) => errors.push(ValidationError::new(
+                path,
+                format!("invalid pattern {} in schema: {}", pattern, e),
+            )),
+        }
+    }
+    if let Some(format) = schema.get("format").and_then(|v| v.as_str()) {
+        if !format_matches(s, format) {
+            errors.push(ValidationError::new(
+                path,
+                format!("must be a valid {}", format),
+            ));
         }
     }
+}
 
-    // Check property types if specified
-    if let Some(properties) = schema_val.get("properties").and_then(|p| p.as_object()) {
-        if let Some(input_obj) = input.as_object() {
-            for (prop_name, prop_schema) in properties {
-                if let Some(input_value) = input_obj.get(prop_name) {
-                    if let Some(expected_type) = prop_schema.get("type").and_then(|t| t.as_str()) {
-                        let actual_type = match input_value {
-                            Value::Null => "null",
-                            Value::Bool(_) => "boolean",
-                            Value::Number(_) => "number",
-                            Value::String(_) => "string",
-                            Value::Array(_) => "array",
-                            Value::Object(_) => "object",
-                        };
-
-                        if expected_type != actual_type {
-                            errors.push(format!(
-                                "Field '{}' has wrong type: expected {}, got {}",
-                                prop_name, expected_type, actual_type
-                            ));
-                        }
-                    }
+fn validate_array(items: &[Value], schema: &Value, path: &str, errors: &mut Vec<ValidationError>) {
+    let len = items.len() as u64;
+
+    if let Some(min) = schema.get("minItems").and_then(|v| v.as_u64()) {
+        if len < min {
+            errors.push(ValidationError::new(
+                path,
+                format!("must have at least {} items", min),
+            ));
+        }
+    }
+    if let Some(max) = schema.get("maxItems").and_then(|v| v.as_u64()) {
+        if len > max {
+            errors.push(ValidationError::new(
+                path,
+                format!("must have at most {} items", max),
+            ));
+        }
+    }
+    if let Some(item_schema) = schema.get("items").filter(|s| s.is_object()) {
+        for (i, item) in items.iter().enumerate() {
+            validate_value(item, item_schema, &format!("{}/{}", path, i), errors);
+        }
+    }
+}
+
+fn validate_object(
+    map: &Map<String, Value>,
+    schema: &Value,
+    path: &str,
+    errors: &mut Vec<ValidationError>,
+) {
+    let child = |key: &str| format!("{}/{}", path, key.replace('~', "~0").replace('/', "~1"));
+
+    if let Some(required) = schema.get("required").and_then(|r| r.as_array()) {
+        for field in required.iter().filter_map(|f| f.as_str()) {
+            if !map.contains_key(field) {
+                errors.push(ValidationError::new(
+                    &child(field),
+                    "missing required field",
+                ));
+            }
+        }
+    }
+
+    let properties = schema.get("properties").and_then(|p| p.as_object());
+    if let Some(properties) = properties {
+        for (name, prop_schema) in properties {
+            if let Some(value) = map.get(name) {
+                validate_value(value, prop_schema, &child(name), errors);
+            }
+        }
+    }
+
+    match schema.get("additionalProperties") {
+        Some(Value::Bool(false)) => {
+            for name in map.keys() {
+                if !properties.is_some_and(|p| p.contains_key(name)) {
+                    errors.push(ValidationError::new(&child(name), "unknown field"));
                 }
             }
         }
+        Some(extra_schema @ Value::Object(_)) => {
+            for (name, value) in map {
+                if !properties.is_some_and(|p| p.contains_key(name)) {
+                    validate_value(value, extra_schema, &child(name), errors);
+                }
+            }
+        }
+        _ => {}
     }
+}
 
-    if errors.is_empty() {
-        Ok(())
-    } else {
-        Err(errors)
+fn type_matches(value: &Value, expected: &str) -> bool {
+    match (expected, value) {
+        ("integer", Value::Number(n)) => {
+            !n.is_f64() || n.as_f64().is_some_and(|f| f.fract() == 0.0)
+        }
+        ("number", Value::Number(_)) => true,
+        (expected, value) => expected == type_name(value),
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
     }
 }
 
-/// Convert string to number if schema expects number
+/// Check the `email`, `uuid`, `date` and `date-time` formats; other formats
+/// are annotations only and always pass
+fn format_matches(s: &str, format: &str) -> bool {
+    static EMAIL: OnceLock<Regex> = OnceLock::new();
+    static UUID: OnceLock<Regex> = OnceLock::new();
+    static DATE_TIME: OnceLock<Regex> = OnceLock::new();
+
+    match format {
+        "email" => EMAIL
+            .get_or_init(|| Regex::new(r"^[^@\s]+@[^@\s]+\.[^@\s.]+$").unwrap())
+            .is_match(s),
+        "uuid" => UUID
+            .get_or_init(|| {
+                Regex::new(r"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$")
+                    .unwrap()
+            })
+            .is_match(s),
+        "date" => valid_date(s),
+        "date-time" => {
+            let re = DATE_TIME.get_or_init(|| {
+                Regex::new(
+                    r"^(\d{4}-\d{2}-\d{2})[Tt ](\d{2}):(\d{2}):(\d{2})(\.\d+)?([Zz]|[+-](\d{2}):(\d{2}))$",
+                )
+                .unwrap()
+            });
+            let Some(caps) = re.captures(s) else {
+                return false;
+            };
+            let num = |i: usize| caps.get(i).map_or(0, |m| m.as_str().parse::<u32>().unwrap_or(99));
+            valid_date(&caps[1])
+                && num(2) < 24
+                && num(3) < 60
+                && num(4) <= 60
+                && num(7) < 24
+                && num(8) < 60
+        }
+        _ => true,
+    }
+}
+
+/// Check a `YYYY-MM-DD` calendar date
+fn valid_date(s: &str) -> bool {
+    let parts: Vec<&str> = s.split('-').collect();
+    let [year, month, day] = parts.as_slice() else {
+        return false;
+    };
+    if year.len() != 4 || month.len() != 2 || day.len() != 2 {
+        return false;
+    }
+    let (Ok(year), Ok(month), Ok(day)) = (
+        year.parse::<u32>(),
+        month.parse::<u32>(),
+        day.parse::<u32>(),
+    ) else {
+        return false;
+    };
+
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return false,
+    };
+    (1..=days_in_month).contains(&day)
+}
+
+/// Coerce a value to the expected JSON type
+///
+/// Values that already have the expected type are returned unchanged.
+/// Strings are parsed into numbers, integers, booleans, `null`, or (when
+/// they contain JSON) objects and arrays; numbers and booleans are converted
+/// to strings. Anything else is an error rather than being passed through.
 pub fn coerce_type(value: &Value, expected_type: &str) -> Result<Value, String> {
+    if type_matches(value, expected_type) {
+        return Ok(value.clone());
+    }
+
     match (value, expected_type) {
-        (Value::String(s), "number") | (Value::String(s), "integer") => s
+        (Value::String(s), "integer") => {
+            s.trim()
+                .parse::<i64>()
+                .map(|n| json!(n))
+                .or_else(|_| match s.trim().parse::<f64>() {
+                    Ok(f) if f.fract() == 0.0 && f.abs() < i64::MAX as f64 => Ok(json!(f as i64)),
+                    _ => Err(format!("Cannot convert '{}' to integer", s)),
+                })
+        }
+        (Value::String(s), "number") => s
+            .trim()
             .parse::<i64>()
             .map(|n| json!(n))
-            .or_else(|_| s.parse::<f64>().map(|n| json!(n)))
+            .or_else(|_| s.trim().parse::<f64>().map(|n| json!(n)))
             .map_err(|e| format!("Cannot convert '{}' to number: {}", s, e)),
-        (Value::String(s), "boolean") => match s.to_lowercase().as_str() {
+        (Value::String(s), "boolean") => match s.trim().to_lowercase().as_str() {
             "true" | "1" | "yes" => Ok(json!(true)),
             "false" | "0" | "no" => Ok(json!(false)),
             _ => Err(format!("Cannot convert '{}' to boolean", s)),
         },
-        (v, _) => Ok(v.clone()),
+        (Value::String(s), "null") if s.trim() == "null" => Ok(Value::Null),
+        (Value::String(s), "object" | "array") => match serde_json::from_str::<Value>(s) {
+            Ok(parsed) if type_matches(&parsed, expected_type) => Ok(parsed),
+            _ => Err(format!("Cannot convert '{}' to {}", s, expected_type)),
+        },
+        (Value::Number(_) | Value::Bool(_), "string") => Ok(Value::String(value.to_string())),
+        (v, expected) => Err(format!(
+            "Cannot convert {} {} to {}",
+            type_name(v),
+            v,
+            expected
+        )),
+    }
+}
+
+/// Recursively coerce a value into the shape declared by a JSON Schema
+///
+/// Walks `properties`, `additionalProperties` and `items`, applying
+/// [`coerce_type`] wherever the value's type differs from the schema's
+/// `type` (e.g. `"42"` → `42` for an integer field nested in an array of
+/// objects). Already-correct values and fields without a declared type are
+/// left untouched; the first value that cannot be coerced fails the whole
+/// call with its JSON Pointer path.
+///
+/// # Example
+///
+/// ```rust
+/// use allframe_mcp::coerce_to_schema;
+/// use serde_json::json;
+///
+/// let schema = json!({
+///     "type": "object",
+///     "properties": {
+///         "limit": {"type": "integer"},
+///         "tags": {"type": "array", "items": {"type": "boolean"}}
+///     }
+/// });
+///
+/// let args = json!({"limit": "10", "tags": ["true", false]});
+/// let coerced = coerce_to_schema(&args, &schema).unwrap();
+/// assert_eq!(coerced, json!({"limit": 10, "tags": [true, false]}));
+/// ```
+pub fn coerce_to_schema(value: &Value, schema: &Value) -> Result<Value, String> {
+    coerce_at(value, schema, "")
+}
+
+fn coerce_at(value: &Value, schema: &Value, path: &str) -> Result<Value, String> {
+    if value.is_null() && schema.get("nullable") == Some(&Value::Bool(true)) {
+        return Ok(Value::Null);
+    }
+
+    let types: Vec<&str> = match schema.get("type") {
+        Some(Value::String(t)) => vec![t.as_str()],
+        Some(Value::Array(types)) => types.iter().filter_map(|t| t.as_str()).collect(),
+        _ => vec![],
+    };
+
+    let value = if types.is_empty() || types.iter().any(|t| type_matches(value, t)) {
+        value.clone()
+    } else {
+        let mut last_error = String::new();
+        let mut coerced = None;
+        for expected in &types {
+            match coerce_type(value, expected) {
+                Ok(v) => {
+                    coerced = Some(v);
+                    break;
+                }
+                Err(e) => last_error = e,
+            }
+        }
+        match coerced {
+            Some(v) => v,
+            None if path.is_empty() => return Err(last_error),
+            None => return Err(format!("{} at {}", last_error, path)),
+        }
+    };
+
+    match value {
+        Value::Object(map) => {
+            let properties = schema.get("properties").and_then(|p| p.as_object());
+            let extra = schema.get("additionalProperties").filter(|s| s.is_object());
+            let mut coerced = Map::new();
+            for (name, field) in map {
+                let field_schema = properties.and_then(|p| p.get(&name)).or(extra);
+                let field = match field_schema {
+                    Some(field_schema) => coerce_at(
+                        &field,
+                        field_schema,
+                        &format!("{}/{}", path, name.replace('~', "~0").replace('/', "~1")),
+                    )?,
+                    None => field,
+                };
+                coerced.insert(name, field);
+            }
+            Ok(Value::Object(coerced))
+        }
+        Value::Array(items) => match schema.get("items").filter(|s| s.is_object()) {
+            Some(item_schema) => items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| coerce_at(item, item_schema, &format!("{}/{}", path, i)))
+                .collect::<Result<Vec<_>, _>>()
+                .map(Value::Array),
+            None => Ok(Value::Array(items)),
+        },
+        other => Ok(other),
     }
 }
 
@@ -178,7 +579,8 @@ mod tests {
         assert!(result.is_err());
         let errors = result.unwrap_err();
         assert_eq!(errors.len(), 1);
-        assert!(errors[0].contains("Missing required field: name"));
+        assert_eq!(errors[0].path, "/name");
+        assert_eq!(errors[0].message, "missing required field");
     }
 
     #[test]
@@ -195,7 +597,8 @@ mod tests {
 
         assert!(result.is_err());
         let errors = result.unwrap_err();
-        assert!(errors[0].contains("wrong type"));
+        assert_eq!(errors[0].path, "/age");
+        assert!(errors[0].message.contains("wrong type"));
     }
 
     #[test]
@@ -230,6 +633,135 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_coerce_string_to_integer() {
+        assert_eq!(coerce_type(&json!("42"), "integer").unwrap(), json!(42));
+        assert_eq!(coerce_type(&json!("3.0"), "integer").unwrap(), json!(3));
+        assert!(coerce_type(&json!("3.5"), "integer").is_err());
+    }
+
+    #[test]
+    fn test_coerce_keeps_correct_values() {
+        assert_eq!(coerce_type(&json!(42), "integer").unwrap(), json!(42));
+        assert_eq!(coerce_type(&json!(1.5), "number").unwrap(), json!(1.5));
+        assert_eq!(coerce_type(&json!("hi"), "string").unwrap(), json!("hi"));
+    }
+
+    #[test]
+    fn test_coerce_mismatch_fails() {
+        let err = coerce_type(&json!(true), "array").unwrap_err();
+        assert_eq!(err, "Cannot convert boolean true to array");
+        assert!(coerce_type(&json!({"a": 1}), "number").is_err());
+    }
+
+    #[test]
+    fn test_coerce_scalars_to_string() {
+        assert_eq!(coerce_type(&json!(42), "string").unwrap(), json!("42"));
+        assert_eq!(
+            coerce_type(&json!(false), "string").unwrap(),
+            json!("false")
+        );
+    }
+
+    #[test]
+    fn test_coerce_to_schema_nested() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "limit": {"type": "integer"},
+                "filter": {
+                    "type": "object",
+                    "properties": {
+                        "active": {"type": "boolean"},
+                        "min_score": {"type": "number"}
+                    }
+                },
+                "ids": {"type": "array", "items": {"type": "integer"}},
+                "name": {"type": "string"},
+                "extra": {}
+            }
+        });
+        let input = json!({
+            "limit": "10",
+            "filter": {"active": "true", "min_score": "0.5"},
+            "ids": ["1", 2, "3"],
+            "name": "already a string",
+            "extra": "untouched",
+            "unknown": "42"
+        });
+
+        let coerced = coerce_to_schema(&input, &schema).unwrap();
+
+        assert_eq!(
+            coerced,
+            json!({
+                "limit": 10,
+                "filter": {"active": true, "min_score": 0.5},
+                "ids": [1, 2, 3],
+                "name": "already a string",
+                "extra": "untouched",
+                "unknown": "42"
+            })
+        );
+    }
+
+    #[test]
+    fn test_coerce_to_schema_parses_stringified_json() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "point": {
+                    "type": "object",
+                    "properties": {"x": {"type": "integer"}}
+                },
+                "tags": {"type": "array", "items": {"type": "string"}}
+            }
+        });
+        let input = json!({"point": r#"{"x": "7"}"#, "tags": r#"["a", 1]"#});
+
+        let coerced = coerce_to_schema(&input, &schema).unwrap();
+
+        assert_eq!(coerced, json!({"point": {"x": 7}, "tags": ["a", "1"]}));
+    }
+
+    #[test]
+    fn test_coerce_to_schema_reports_path() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "items": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {"qty": {"type": "integer"}}
+                    }
+                }
+            }
+        });
+        let input = json!({"items": [{"qty": "1"}, {"qty": "lots"}]});
+
+        let err = coerce_to_schema(&input, &schema).unwrap_err();
+
+        assert_eq!(err, "Cannot convert 'lots' to integer at /items/1/qty");
+    }
+
+    #[test]
+    fn test_coerce_to_schema_nullable_and_type_lists() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "note": {"type": "string", "nullable": true},
+                "count": {"type": ["integer", "null"]}
+            }
+        });
+
+        let coerced = coerce_to_schema(&json!({"note": null, "count": "null"}), &schema).unwrap();
+        assert_eq!(coerced, json!({"note": null, "count": null}));
+
+        let coerced = coerce_to_schema(&json!({"count": "5"}), &schema).unwrap();
+        assert_eq!(coerced, json!({"count": 5}));
+    }
+
     #[test]
     fn test_extract_enum_values() {
         let schema = json!({
@@ -282,4 +814,228 @@ mod tests {
         let input = json!({"tags": ["rust", "web"]});
         assert!(validate_input(&input, schema).is_ok());
     }
+
+    #[test]
+    fn test_validate_numeric_ranges() {
+        let schema = r#"{
+            "type": "object",
+            "properties": {
+                "age": {"type": "integer", "minimum": 0, "maximum": 150},
+                "ratio": {"type": "number", "exclusiveMinimum": 0, "exclusiveMaximum": 1}
+            }
+        }"#;
+
+        assert!(validate_input(&json!({"age": 30, "ratio": 0.5}), schema).is_ok());
+
+        let errors = validate_input(&json!({"age": 200, "ratio": 1}), schema).unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                ValidationError::new("/age", "must be <= 150"),
+                ValidationError::new("/ratio", "must be < 1"),
+            ]
+        );
+
+        let errors = validate_input(&json!({"age": -1, "ratio": 0}), schema).unwrap_err();
+        assert_eq!(errors[0].message, "must be >= 0");
+        assert_eq!(errors[1].message, "must be > 0");
+    }
+
+    #[test]
+    fn test_validate_string_lengths_and_pattern() {
+        let schema = r#"{
+            "type": "object",
+            "properties": {
+                "code": {"type": "string", "minLength": 2, "maxLength": 4, "pattern": "^[A-Z]+$"}
+            }
+        }"#;
+
+        assert!(validate_input(&json!({"code": "ABC"}), schema).is_ok());
+
+        let errors = validate_input(&json!({"code": "a"}), schema).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].message, "must be at least 2 characters");
+        assert_eq!(errors[1].message, "must match pattern ^[A-Z]+$");
+
+        let errors = validate_input(&json!({"code": "ABCDE"}), schema).unwrap_err();
+        assert_eq!(errors[0].message, "must be at most 4 characters");
+    }
+
+    #[test]
+    fn test_validate_enum() {
+        let schema = r#"{"type": "string", "enum": ["red", "green"]}"#;
+
+        assert!(validate_input(&json!("red"), schema).is_ok());
+
+        let errors = validate_input(&json!("blue"), schema).unwrap_err();
+        assert_eq!(errors[0].path, "");
+        assert_eq!(errors[0].message, r#"must be one of: "red", "green""#);
+    }
+
+    #[test]
+    fn test_validate_formats() {
+        let schema = r#"{
+            "type": "object",
+            "properties": {
+                "email": {"type": "string", "format": "email"},
+                "id": {"type": "string", "format": "uuid"},
+                "at": {"type": "string", "format": "date-time"},
+                "on": {"type": "string", "format": "date"},
+                "site": {"type": "string", "format": "uri"}
+            }
+        }"#;
+
+        let valid = json!({
+            "email": "alice@example.com",
+            "id": "550e8400-e29b-41d4-a716-446655440000",
+            "at": "2024-02-29T13:45:00.123+02:00",
+            "on": "2024-12-31",
+            "site": "anything goes"
+        });
+        assert!(validate_input(&valid, schema).is_ok());
+
+        let invalid = json!({
+            "email": "alice@",
+            "id": "550e8400-e29b-41d4-a716",
+            "at": "2023-02-29T25:00:00Z",
+            "on": "2024-13-01"
+        });
+        let errors = validate_input(&invalid, schema).unwrap_err();
+        let paths: Vec<&str> = errors.iter().map(|e| e.path.as_str()).collect();
+        assert_eq!(paths.len(), 4);
+        for path in ["/email", "/id", "/at", "/on"] {
+            assert!(paths.contains(&path), "missing error for {}", path);
+        }
+        assert!(errors
+            .iter()
+            .any(|e| e.message == "must be a valid date-time"));
+    }
+
+    #[test]
+    fn test_validate_nested_paths() {
+        let schema = r#"{
+            "type": "object",
+            "properties": {
+                "users": {
+                    "type": "array",
+                    "minItems": 1,
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "email": {"type": "string", "format": "email"}
+                        },
+                        "required": ["email"],
+                        "additionalProperties": false
+                    }
+                }
+            }
+        }"#;
+
+        let input = json!({
+            "users": [{"email": "ok@example.com"}, {"email": "nope", "admin": true}, {}]
+        });
+        let errors = validate_input(&input, schema).unwrap_err();
+
+        assert_eq!(
+            errors,
+            vec![
+                ValidationError::new("/users/1/email", "must be a valid email"),
+                ValidationError::new("/users/1/admin", "unknown field"),
+                ValidationError::new("/users/2/email", "missing required field"),
+            ]
+        );
+
+        let errors = validate_input(&json!({"users": []}), schema).unwrap_err();
+        assert_eq!(
+            errors[0],
+            ValidationError::new("/users", "must have at least 1 items")
+        );
+    }
+
+    #[test]
+    fn test_validation_error_display() {
+        assert_eq!(
+            ValidationError::new("/user/email", "must be a valid email").to_string(),
+            "/user/email: must be a valid email"
+        );
+        assert_eq!(
+            ValidationError::new("", "wrong type: expected object, got string").to_string(),
+            "wrong type: expected object, got string"
+        );
+    }
+
+    #[test]
+    fn test_validate_invalid_schema() {
+        let errors = validate_input(&json!({}), "not json").unwrap_err();
+        assert!(errors[0].message.starts_with("Invalid schema"));
+    }
+
+    mod derived {
+        use std::collections::HashMap;
+
+        use allframe_core::router::ToJsonSchema;
+        use serde::{Deserialize, Serialize};
+
+        use super::*;
+
+        #[derive(Serialize, Deserialize, ToJsonSchema)]
+        struct SearchArgs {
+            #[schema(description = "Search query", example = "rust")]
+            query: String,
+            limit: u32,
+            exact: Option<bool>,
+            filters: HashMap<String, String>,
+        }
+
+        fn derived_json_schema() -> String {
+            openapi_to_json_schema(&SearchArgs::schema()).unwrap()
+        }
+
+        #[test]
+        fn test_derived_schema_converts_to_json_schema() {
+            let parsed: Value = serde_json::from_str(&derived_json_schema()).unwrap();
+
+            assert_eq!(parsed["type"], "object");
+            assert_eq!(parsed["properties"]["query"]["description"], "Search query");
+            assert_eq!(parsed["required"], json!(["query", "limit", "filters"]));
+        }
+
+        #[test]
+        fn test_derived_schema_round_trip_validates() {
+            let args = SearchArgs {
+                query: "rust".to_string(),
+                limit: 10,
+                exact: None,
+                filters: HashMap::from([("lang".to_string(), "en".to_string())]),
+            };
+            let input = serde_json::to_value(&args).unwrap();
+
+            assert!(validate_input(&input, &derived_json_schema()).is_ok());
+        }
+
+        #[test]
+        fn test_derived_schema_rejects_bad_input() {
+            let input = json!({"query": 42, "filters": {}});
+            let errors = validate_input(&input, &derived_json_schema()).unwrap_err();
+
+            assert!(errors
+                .iter()
+                .any(|e| e.path == "/limit" && e.message == "missing required field"));
+            assert!(errors
+                .iter()
+                .any(|e| e.path == "/query" && e.message.contains("wrong type")));
+        }
+
+        #[test]
+        fn test_integer_fields_reject_fractions() {
+            let input = json!({"query": "rust", "limit": 2.5, "filters": {}});
+            let errors = validate_input(&input, &derived_json_schema()).unwrap_err();
+
+            assert_eq!(errors[0].path, "/limit");
+            assert_eq!(
+                errors[0].message,
+                "wrong type: expected integer, got number"
+            );
+        }
+    }
 }
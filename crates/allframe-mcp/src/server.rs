@@ -1,12 +1,16 @@
 //! MCP Server implementation
 
-use std::collections::HashMap;
+use std::collections::{hash_map::Entry, BTreeMap, HashMap};
 use std::future::Future;
 use std::pin::Pin;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
 
-use allframe_core::router::Router;
+use allframe_core::router::{ExecuteError, Router};
+use tokio::sync::oneshot;
 
+use super::prompts::McpPrompt;
+use super::resources::{McpResource, ResourceContent};
 use super::tools::McpTool;
 
 /// Type alias for local tool handlers.
@@ -16,11 +20,21 @@ type ToolHandler = Arc<
         + Sync,
 >;
 
+/// Type alias for resource readers.
+type ResourceHandler = Arc<
+    dyn Fn() -> Pin<Box<dyn Future<Output = Result<ResourceContent, String>> + Send>> + Send + Sync,
+>;
+
 /// MCP Server that exposes Router handlers as LLM-callable tools
 pub struct McpServer {
     router: Option<Arc<Router>>,
     tools: Vec<McpTool>,
     local_tools: RwLock<HashMap<String, ToolHandler>>,
+    resources: RwLock<BTreeMap<String, (McpResource, ResourceHandler)>>,
+    prompts: RwLock<BTreeMap<String, McpPrompt>>,
+    default_timeout: Option<Duration>,
+    tool_timeouts: HashMap<String, Duration>,
+    in_flight: Mutex<HashMap<String, oneshot::Sender<()>>>,
     listening: bool,
 }
 
@@ -31,6 +45,11 @@ impl McpServer {
             router: None,
             tools: Vec::new(),
             local_tools: RwLock::new(HashMap::new()),
+            resources: RwLock::new(BTreeMap::new()),
+            prompts: RwLock::new(BTreeMap::new()),
+            default_timeout: None,
+            tool_timeouts: HashMap::new(),
+            in_flight: Mutex::new(HashMap::new()),
             listening: false,
         }
     }
@@ -42,10 +61,36 @@ impl McpServer {
             router: Some(Arc::new(router)),
             tools,
             local_tools: RwLock::new(HashMap::new()),
+            resources: RwLock::new(BTreeMap::new()),
+            prompts: RwLock::new(BTreeMap::new()),
+            default_timeout: None,
+            tool_timeouts: HashMap::new(),
+            in_flight: Mutex::new(HashMap::new()),
             listening: false,
         }
     }
 
+    /// Time out every tool call after `timeout` (no timeout by default).
+    pub fn with_default_timeout(mut self, timeout: Duration) -> Self {
+        self.default_timeout = Some(timeout);
+        self
+    }
+
+    /// Time out calls to the tool `name` after `timeout`, overriding the
+    /// default.
+    pub fn with_tool_timeout(mut self, name: impl Into<String>, timeout: Duration) -> Self {
+        self.tool_timeouts.insert(name.into(), timeout);
+        self
+    }
+
+    /// The timeout applied to calls to the tool `name`, if any.
+    pub fn tool_timeout(&self, name: &str) -> Option<Duration> {
+        self.tool_timeouts
+            .get(name)
+            .copied()
+            .or(self.default_timeout)
+    }
+
     /// Discover tools from Router handlers
     fn discover_tools(router: &Router) -> Vec<McpTool> {
         router
@@ -79,7 +124,86 @@ impl McpServer {
                 .cloned()
                 .ok_or_else(|| format!("Local tool not found: {}", name))?
         };
-        handler(args).await
+        self.with_timeout(name, handler(args)).await
+    }
+
+    /// Register a readable resource.
+    ///
+    /// `resource` is a URI or a full [`McpResource`]; `reader` produces its
+    /// content each time the client reads it.
+    pub fn register_resource<F, Fut>(&self, resource: impl Into<McpResource>, reader: F)
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<ResourceContent, String>> + Send + 'static,
+    {
+        let resource = resource.into();
+        let reader: ResourceHandler = Arc::new(move || Box::pin(reader()));
+        let mut resources = self.resources.write().unwrap();
+        resources.insert(resource.uri.clone(), (resource, reader));
+    }
+
+    /// Get the count of registered resources.
+    pub fn resource_count(&self) -> usize {
+        self.resources.read().unwrap().len()
+    }
+
+    /// List all registered resources, ordered by URI.
+    pub fn list_resources(&self) -> Vec<McpResource> {
+        let resources = self.resources.read().unwrap();
+        resources
+            .values()
+            .map(|(resource, _)| resource.clone())
+            .collect()
+    }
+
+    /// Read a resource by URI.
+    ///
+    /// Returns the resource (for its declared MIME type) and its content.
+    pub async fn read_resource(&self, uri: &str) -> Result<(McpResource, ResourceContent), String> {
+        let (resource, reader) = {
+            let resources = self.resources.read().unwrap();
+            resources
+                .get(uri)
+                .cloned()
+                .ok_or_else(|| format!("Resource not found: {}", uri))?
+        };
+        let content = reader().await?;
+        Ok((resource, content))
+    }
+
+    /// Register a prompt template, replacing any with the same name.
+    pub fn register_prompt(&self, prompt: McpPrompt) {
+        let mut prompts = self.prompts.write().unwrap();
+        prompts.insert(prompt.name.clone(), prompt);
+    }
+
+    /// Get the count of registered prompts.
+    pub fn prompt_count(&self) -> usize {
+        self.prompts.read().unwrap().len()
+    }
+
+    /// List all registered prompts, ordered by name.
+    pub fn list_prompts(&self) -> Vec<McpPrompt> {
+        self.prompts.read().unwrap().values().cloned().collect()
+    }
+
+    /// Render a prompt by name with the given arguments.
+    ///
+    /// Returns the prompt (for its description) and the rendered text.
+    pub fn get_prompt(
+        &self,
+        name: &str,
+        args: &serde_json::Value,
+    ) -> Result<(McpPrompt, String), String> {
+        let prompt = {
+            let prompts = self.prompts.read().unwrap();
+            prompts
+                .get(name)
+                .cloned()
+                .ok_or_else(|| format!("Prompt not found: {}", name))?
+        };
+        let text = prompt.render(args)?;
+        Ok((prompt, text))
     }
 
     /// Returns whether the server is listening on a network port.
@@ -93,6 +217,12 @@ impl McpServer {
         self.tools.len() + local_count
     }
 
+    /// Whether a tool named `name` is registered (router or local).
+    pub fn has_tool(&self, name: &str) -> bool {
+        self.tools.iter().any(|t| t.name == name)
+            || self.local_tools.read().unwrap().contains_key(name)
+    }
+
     /// List all available tools (router-discovered + locally registered).
     pub fn list_tools(&self) -> Vec<McpTool> {
         let mut all_tools = self.tools.clone();
@@ -104,10 +234,81 @@ impl McpServer {
     }
 
     /// Call a tool by name with given arguments (router-based).
+    ///
+    /// The call fails with an error if it exceeds the tool's timeout; the
+    /// handler future is dropped, cancelling it.
     pub async fn call_tool(
         &self,
         name: &str,
         args: serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        self.with_timeout(name, self.dispatch_tool(name, args))
+            .await
+    }
+
+    /// Call a tool that can be aborted with [`cancel`](Self::cancel).
+    ///
+    /// `call_id` identifies the call (the JSON-RPC request id over stdio).
+    /// A call whose id is already in flight fails without running the tool.
+    pub async fn call_tool_cancellable(
+        &self,
+        call_id: impl Into<String>,
+        name: &str,
+        args: serde_json::Value,
+    ) -> Result<serde_json::Value, String> {
+        let call_id = call_id.into();
+        let (cancel_tx, mut cancel_rx) = oneshot::channel();
+        match self.in_flight.lock().unwrap().entry(call_id.clone()) {
+            Entry::Occupied(_) => {
+                return Err(format!("Tool call already in flight: {}", call_id));
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(cancel_tx);
+            }
+        }
+
+        // Only an explicit cancel aborts the call, not a dropped sender
+        let result = tokio::select! {
+            result = self.call_tool(name, args) => result,
+            Ok(()) = &mut cancel_rx => Err(format!("Tool call cancelled: {}", name)),
+        };
+
+        // Once cancelled, the id may already belong to a newer call
+        drop(cancel_rx);
+        let mut in_flight = self.in_flight.lock().unwrap();
+        if in_flight.get(&call_id).is_some_and(|tx| tx.is_closed()) {
+            in_flight.remove(&call_id);
+        }
+        result
+    }
+
+    /// Abort the in-flight call `call_id`, dropping its handler future.
+    ///
+    /// Returns `false` if no such call is running.
+    pub fn cancel(&self, call_id: &str) -> bool {
+        let cancel_tx = self.in_flight.lock().unwrap().remove(call_id);
+        match cancel_tx {
+            Some(cancel_tx) => cancel_tx.send(()).is_ok(),
+            None => false,
+        }
+    }
+
+    async fn with_timeout<F>(&self, name: &str, call: F) -> Result<serde_json::Value, String>
+    where
+        F: Future<Output = Result<serde_json::Value, String>>,
+    {
+        match self.tool_timeout(name) {
+            Some(timeout) => tokio::time::timeout(timeout, call)
+                .await
+                .unwrap_or_else(|_| Err(format!("Tool '{}' timed out after {:?}", name, timeout))),
+            None => call.await,
+        }
+    }
+
+    async fn dispatch_tool(
+        &self,
+        name: &str,
+        args: serde_json::Value,
     ) -> Result<serde_json::Value, String> {
         // Try local tools first
         let local_handler = {
@@ -131,6 +332,7 @@ impl McpServer {
         let request = format!("{}", args);
         match router.call_handler(name, &request).await {
             Ok(response) => Ok(serde_json::Value::String(response)),
+            Err(ExecuteError::NotFound(name)) => Err(format!("Tool not found: {}", name)),
             Err(e) => Err(format!("Tool execution failed: {}", e)),
         }
     }
@@ -214,4 +416,208 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap()["msg"], "hi");
     }
+
+    #[tokio::test]
+    async fn test_server_resources() {
+        let server = McpServer::new();
+        assert_eq!(server.resource_count(), 0);
+
+        server.register_resource("config://app", || async {
+            Ok(ResourceContent::text("debug = true"))
+        });
+        server.register_resource(
+            McpResource::new("config://db", "Database", "application/json", "DB settings"),
+            || async { Ok(serde_json::json!({"pool": 4}).into()) },
+        );
+        server.register_resource("config://broken", || async {
+            Err("permission denied".to_string())
+        });
+
+        let uris: Vec<String> = server.list_resources().into_iter().map(|r| r.uri).collect();
+        assert_eq!(uris, ["config://app", "config://broken", "config://db"]);
+        // Resources are not tools
+        assert_eq!(server.tool_count(), 0);
+
+        let (resource, content) = server.read_resource("config://app").await.unwrap();
+        assert_eq!(resource.mime_type, "text/plain");
+        assert_eq!(content.text, "debug = true");
+
+        let (resource, content) = server.read_resource("config://db").await.unwrap();
+        assert_eq!(resource.name, "Database");
+        assert!(content.text.contains("\"pool\": 4"));
+
+        let err = server.read_resource("config://broken").await.unwrap_err();
+        assert_eq!(err, "permission denied");
+        let err = server.read_resource("config://missing").await.unwrap_err();
+        assert!(err.contains("not found"));
+    }
+
+    #[tokio::test]
+    async fn test_slow_tool_times_out() {
+        let server = McpServer::new()
+            .with_default_timeout(Duration::from_secs(60))
+            .with_tool_timeout("slow", Duration::from_millis(20));
+        server.register_tool("slow", |_| async {
+            tokio::time::sleep(Duration::from_secs(60)).await;
+            Ok(serde_json::json!("done"))
+        });
+        server.register_tool("fast", |_| async { Ok(serde_json::json!("done")) });
+
+        assert_eq!(server.tool_timeout("slow"), Some(Duration::from_millis(20)));
+        assert_eq!(server.tool_timeout("fast"), Some(Duration::from_secs(60)));
+
+        let err = server
+            .call_tool("slow", serde_json::json!({}))
+            .await
+            .unwrap_err();
+        assert!(err.contains("timed out"), "{err}");
+        let err = server
+            .call_tool_local("slow", serde_json::json!({}))
+            .await
+            .unwrap_err();
+        assert!(err.contains("timed out"), "{err}");
+        assert!(server
+            .call_tool("fast", serde_json::json!({}))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_router_tool_times_out() {
+        let mut router = Router::new();
+        router.register("hang", || async {
+            std::future::pending::<()>().await;
+            String::new()
+        });
+        let server = McpServer::with_router(router).with_default_timeout(Duration::from_millis(20));
+
+        let err = server
+            .call_tool("hang", serde_json::json!({}))
+            .await
+            .unwrap_err();
+        assert!(err.contains("timed out"), "{err}");
+    }
+
+    #[tokio::test]
+    async fn test_slow_tool_is_cancelled() {
+        let server = Arc::new(McpServer::new());
+        let dropped = Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+        /// Flags when the handler future is dropped mid-call
+        struct DropFlag(Arc<std::sync::atomic::AtomicBool>);
+        impl Drop for DropFlag {
+            fn drop(&mut self) {
+                self.0.store(true, std::sync::atomic::Ordering::SeqCst);
+            }
+        }
+
+        let flag = dropped.clone();
+        server.register_tool("slow", move |_| {
+            let guard = DropFlag(flag.clone());
+            async move {
+                let _guard = guard;
+                tokio::time::sleep(Duration::from_secs(60)).await;
+                Ok(serde_json::json!("done"))
+            }
+        });
+
+        let call = tokio::spawn({
+            let server = server.clone();
+            async move {
+                server
+                    .call_tool_cancellable("7", "slow", serde_json::json!({}))
+                    .await
+            }
+        });
+        // Wait for the call to be registered before cancelling it
+        while !server.in_flight.lock().unwrap().contains_key("7") {
+            tokio::task::yield_now().await;
+        }
+        assert!(server.cancel("7"));
+
+        let err = call.await.unwrap().unwrap_err();
+        assert!(err.contains("cancelled"), "{err}");
+        assert!(dropped.load(std::sync::atomic::Ordering::SeqCst));
+        // Finished calls can't be cancelled
+        assert!(!server.cancel("7"));
+    }
+
+    #[tokio::test]
+    async fn test_cancellable_calls_with_same_id() {
+        let server = Arc::new(McpServer::new());
+        let (release_tx, release_rx) = tokio::sync::watch::channel(false);
+        server.register_tool("wait", move |_| {
+            let mut release = release_rx.clone();
+            async move {
+                release.wait_for(|released| *released).await.unwrap();
+                Ok(serde_json::json!("done"))
+            }
+        });
+
+        let first = tokio::spawn({
+            let server = server.clone();
+            async move {
+                server
+                    .call_tool_cancellable("1", "wait", serde_json::json!({}))
+                    .await
+            }
+        });
+        while !server.in_flight.lock().unwrap().contains_key("1") {
+            tokio::task::yield_now().await;
+        }
+
+        // A second call with the same id is refused, leaving the first alone
+        let err = server
+            .call_tool_cancellable("1", "wait", serde_json::json!({}))
+            .await
+            .unwrap_err();
+        assert!(err.contains("already in flight"), "{err}");
+        assert!(server.in_flight.lock().unwrap().contains_key("1"));
+
+        release_tx.send(true).unwrap();
+        assert_eq!(first.await.unwrap().unwrap(), serde_json::json!("done"));
+        assert!(server.in_flight.lock().unwrap().is_empty());
+
+        // The id can be reused once the call has finished
+        assert_eq!(
+            server
+                .call_tool_cancellable("1", "wait", serde_json::json!({}))
+                .await
+                .unwrap(),
+            serde_json::json!("done")
+        );
+    }
+
+    #[test]
+    fn test_server_prompts() {
+        let server = McpServer::new();
+        assert_eq!(server.prompt_count(), 0);
+
+        server.register_prompt(
+            McpPrompt::new("summarize", "Summarize {{topic}} in {{words}} words")
+                .with_description("Summarize a topic"),
+        );
+        server.register_prompt(McpPrompt::new("greet", "Hello!"));
+
+        let names: Vec<String> = server.list_prompts().into_iter().map(|p| p.name).collect();
+        assert_eq!(names, ["greet", "summarize"]);
+
+        let (prompt, text) = server
+            .get_prompt(
+                "summarize",
+                &serde_json::json!({"topic": "CQRS", "words": 50}),
+            )
+            .unwrap();
+        assert_eq!(prompt.description, "Summarize a topic");
+        assert_eq!(text, "Summarize CQRS in 50 words");
+
+        assert!(server
+            .get_prompt("summarize", &serde_json::json!({}))
+            .unwrap_err()
+            .contains("Missing required arguments"));
+        assert!(server
+            .get_prompt("missing", &serde_json::json!({}))
+            .unwrap_err()
+            .contains("not found"));
+    }
 }
@@ -27,15 +27,40 @@
 //! ```
 
 use std::{
-    io::{stdin, stdout, BufRead, Write},
-    sync::atomic::{AtomicU64, Ordering},
+    io::{stdin, stdout, BufRead, Read, Write},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
     time::Instant,
 };
 
 use serde_json::{json, Value};
+use tokio::task::JoinSet;
 
 use crate::McpServer;
 
+// JSON-RPC 2.0 error codes
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const INTERNAL_ERROR: i64 = -32603;
+/// MCP: the requested resource doesn't exist
+const RESOURCE_NOT_FOUND: i64 = -32002;
+
+/// Largest `Content-Length` frame accepted; larger frames are discarded
+const MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
+
+/// How a message was framed on stdin; its response is framed the same way
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Framing {
+    /// One JSON message per line (the MCP stdio transport)
+    Newline,
+    /// LSP-style `Content-Length` header, blank line, then the body
+    ContentLength,
+}
+
 /// Configuration for the STDIO transport
 #[derive(Debug, Clone)]
 pub struct StdioConfig {
@@ -103,11 +128,13 @@ impl StdioTransport {
     }
 
     /// Serve MCP protocol over stdio
+    ///
+    /// Tool calls run concurrently with reading further requests, so a
+    /// `notifications/cancelled` for an in-flight call can abort it.
     pub async fn serve(self) {
         self.log_startup();
 
-        let stdin = stdin();
-        let mut stdout = stdout();
+        let transport = Arc::new(self);
 
         // Set up shutdown signal handling
         let shutdown = async {
@@ -119,84 +146,119 @@ impl StdioTransport {
 
                 tokio::select! {
                     _ = async { if let Some(ref mut s) = sigterm { s.recv().await } else { std::future::pending().await } } => {
-                        self.log_info("Received SIGTERM");
+                        transport.log_info("Received SIGTERM");
                     }
                     _ = async { if let Some(ref mut s) = sigint { s.recv().await } else { std::future::pending().await } } => {
-                        self.log_info("Received SIGINT");
+                        transport.log_info("Received SIGINT");
                     }
                 }
             }
             #[cfg(not(unix))]
             {
                 tokio::signal::ctrl_c().await.ok();
-                self.log_info("Received shutdown signal");
+                transport.log_info("Received shutdown signal");
             }
         };
 
         // Run the main loop with shutdown handling
         tokio::select! {
-            _ = self.run_loop(&stdin, &mut stdout) => {}
+            _ = transport.clone().run_loop() => {}
             _ = shutdown => {
-                self.log_info("Shutting down gracefully");
+                transport.log_info("Shutting down gracefully");
             }
         }
 
-        self.log_shutdown();
+        transport.log_shutdown();
     }
 
-    async fn run_loop(&self, stdin: &std::io::Stdin, stdout: &mut std::io::Stdout) {
-        for line in stdin.lock().lines() {
-            let line = match line {
-                Ok(l) => l,
+    async fn run_loop(self: Arc<Self>) {
+        // Read stdin on its own thread so that waiting for the next line
+        // doesn't hold up in-flight tool calls
+        let (frame_tx, mut frames) = tokio::sync::mpsc::unbounded_channel();
+        std::thread::spawn(move || {
+            let mut stdin = stdin().lock();
+            loop {
+                match read_frame(&mut stdin) {
+                    Ok(Some(frame)) => {
+                        if frame_tx.send(Ok(frame)).is_err() {
+                            break;
+                        }
+                    }
+                    Ok(None) => break,
+                    Err(e) => {
+                        // A read error (including EOF mid-frame) ends the input
+                        let _ = frame_tx.send(Err(e));
+                        break;
+                    }
+                }
+            }
+        });
+
+        let mut tool_calls = JoinSet::new();
+        while let Some(frame) = frames.recv().await {
+            while tool_calls.try_join_next().is_some() {}
+
+            let (message, framing) = match frame {
+                Ok(frame) => frame,
                 Err(e) => {
-                    self.log_error(&format!("Error reading line: {}", e));
+                    self.log_error(&format!("Error reading stdin: {}", e));
                     continue;
                 }
             };
 
-            // Skip empty lines
-            if line.trim().is_empty() {
-                continue;
-            }
-
             self.request_count.fetch_add(1, Ordering::SeqCst);
             let request_id = self.request_count.load(Ordering::SeqCst);
 
-            self.log_request(request_id, &line);
+            self.log_request(request_id, &message);
 
-            // Parse request
-            let request: Value = match serde_json::from_str(&line) {
-                Ok(r) => r,
-                Err(e) => {
-                    self.log_error(&format!("Parse error: {}", e));
-                    let error = json!({
-                        "jsonrpc": "2.0",
-                        "error": {
-                            "code": -32700,
-                            "message": "Parse error"
-                        },
-                        "id": null
-                    });
-                    self.write_response(stdout, &error, request_id);
+            let request = match self.parse_message(&message) {
+                Ok(request) => request,
+                Err(error) => {
+                    self.write_response(&error, request_id, framing);
                     continue;
                 }
             };
 
+            // Tool calls may be slow: run them concurrently so later
+            // requests (including their cancellation) are still read
+            if request["method"] == "tools/call" {
+                let transport = self.clone();
+                tool_calls.spawn(async move {
+                    if let Some(resp) = transport.handle_request(request).await {
+                        transport.write_response(&resp, request_id, framing);
+                    }
+                });
+                continue;
+            }
+
             // Handle request
             let response = self.handle_request(request).await;
 
             // Check if this was a notification (no response needed)
             if let Some(resp) = response {
-                self.write_response(stdout, &resp, request_id);
+                self.write_response(&resp, request_id, framing);
             }
         }
+
+        // Stdin closed: let in-flight tool calls respond before exiting
+        while tool_calls.join_next().await.is_some() {}
     }
 
-    fn write_response(&self, stdout: &mut std::io::Stdout, response: &Value, request_id: u64) {
+    fn write_response(&self, response: &Value, request_id: u64, framing: Framing) {
         match serde_json::to_string(&response) {
             Ok(json_str) => {
                 self.log_response(request_id, &json_str);
-                if let Err(e) = writeln!(stdout, "{}", json_str) {
+                let mut stdout = stdout().lock();
+                let written = match framing {
+                    Framing::Newline => writeln!(stdout, "{}", json_str),
+                    Framing::ContentLength => write!(
+                        stdout,
+                        "Content-Length: {}\r\n\r\n{}",
+                        json_str.len(),
+                        json_str
+                    ),
+                };
+                if let Err(e) = written {
                     self.log_error(&format!("Error writing response: {}", e));
                 }
                 if let Err(e) = stdout.flush() {
@@ -209,7 +271,43 @@ impl StdioTransport {
         }
     }
 
+    /// Parse a message into a request, or the error response to send
+    fn parse_message(&self, message: &str) -> Result<Value, Value> {
+        match serde_json::from_str::<Value>(message) {
+            Ok(Value::Array(_)) => {
+                self.log_warn("Rejected JSON-RPC batch request");
+                Err(error_response(
+                    None,
+                    INVALID_REQUEST,
+                    "Invalid Request: batch requests are not supported",
+                ))
+            }
+            Ok(request) => Ok(request),
+            Err(e) => {
+                self.log_error(&format!("Parse error: {}", e));
+                Err(error_response(None, PARSE_ERROR, "Parse error"))
+            }
+        }
+    }
+
+    /// Handle one request; `None` means no response is sent.
+    ///
+    /// Notifications (objects without an `id`) never get a response, not
+    /// even an error; invalid ones are only logged.
     async fn handle_request(&self, request: Value) -> Option<Value> {
+        let id = request.get("id").cloned();
+
+        if let Err(message) = validate_request(&request) {
+            self.log_warn(&message);
+            let notification = request.is_object() && id.is_none();
+            return (!notification).then(|| error_response(id, INVALID_REQUEST, message));
+        }
+
+        let response = self.dispatch(request).await;
+        response.filter(|_| id.is_some())
+    }
+
+    async fn dispatch(&self, request: Value) -> Option<Value> {
         let method = request["method"].as_str().unwrap_or("");
         let id = request.get("id").cloned();
 
@@ -221,7 +319,15 @@ impl StdioTransport {
                 return None;
             }
             "notifications/cancelled" => {
-                self.log_info("Request cancelled by client");
+                let cancelled = &request["params"]["requestId"];
+                if self.mcp.cancel(&cancelled.to_string()) {
+                    self.log_info(&format!("Request {} cancelled by client", cancelled));
+                } else {
+                    self.log_info(&format!(
+                        "Cancelled request {} was not in flight",
+                        cancelled
+                    ));
+                }
                 return None;
             }
             _ => {}
@@ -231,11 +337,19 @@ impl StdioTransport {
             // Initialize
             "initialize" => {
                 self.log_info("Initializing MCP connection");
+                let mut capabilities = json!({ "tools": {} });
+                if self.mcp.prompt_count() > 0 {
+                    capabilities["prompts"] = json!({ "listChanged": false });
+                }
+                if self.mcp.resource_count() > 0 {
+                    capabilities["resources"] = json!({
+                        "subscribe": false,
+                        "listChanged": false
+                    });
+                }
                 json!({
                     "protocolVersion": self.config.protocol_version,
-                    "capabilities": {
-                        "tools": {}
-                    },
+                    "capabilities": capabilities,
                     "serverInfo": {
                         "name": self.config.server_name,
                         "version": self.config.server_version
@@ -278,8 +392,12 @@ impl StdioTransport {
             // Call a tool
             "tools/call" => {
                 let params = &request["params"];
-                let name = params["name"].as_str().unwrap_or("");
-                let arguments = params.get("arguments").cloned().unwrap_or(json!({}));
+                let (name, arguments) = match string_param(params, "name")
+                    .and_then(|name| Ok((name, object_param(params, "arguments")?)))
+                {
+                    Ok(params) => params,
+                    Err(e) => return Some(error_response(id, INVALID_PARAMS, e)),
+                };
 
                 self.log_info(&format!("Calling tool: {}", name));
 
@@ -298,7 +416,30 @@ impl StdioTransport {
                     }));
                 }
 
-                match self.mcp.call_tool(name, arguments).await {
+                if !self.mcp.has_tool(name) {
+                    return Some(error_response(
+                        id,
+                        INVALID_PARAMS,
+                        format!("Unknown tool: {}", name),
+                    ));
+                }
+
+                // Calls are keyed by JSON-RPC id so the client can cancel them
+                let result = match &id {
+                    Some(id) => {
+                        self.mcp
+                            .call_tool_cancellable(id.to_string(), name, arguments)
+                            .await
+                    }
+                    None => self.mcp.call_tool(name, arguments).await,
+                };
+
+                match result {
+                    // A cancelled request gets no response
+                    Err(e) if e.starts_with("Tool call cancelled") => {
+                        self.log_info(&e);
+                        return None;
+                    }
                     Ok(result) => {
                         json!({
                             "content": [{
@@ -320,6 +461,82 @@ impl StdioTransport {
                 }
             }
 
+            // List available resources
+            "resources/list" => {
+                json!({ "resources": self.mcp.list_resources() })
+            }
+
+            // Read a resource
+            "resources/read" => {
+                let uri = match string_param(&request["params"], "uri") {
+                    Ok(uri) => uri,
+                    Err(e) => return Some(error_response(id, INVALID_PARAMS, e)),
+                };
+
+                self.log_info(&format!("Reading resource: {}", uri));
+
+                match self.mcp.read_resource(uri).await {
+                    Ok((resource, content)) => {
+                        json!({
+                            "contents": [{
+                                "uri": uri,
+                                "mimeType": content.mime_type.unwrap_or(resource.mime_type),
+                                "text": content.text
+                            }]
+                        })
+                    }
+                    Err(e) => {
+                        self.log_error(&format!("Resource error: {}", e));
+                        // Read failures are reported as internal errors
+                        let code = if e.starts_with("Resource not found") {
+                            RESOURCE_NOT_FOUND
+                        } else {
+                            INTERNAL_ERROR
+                        };
+                        let mut error = error_response(id, code, e);
+                        error["error"]["data"] = json!({ "uri": uri });
+                        return Some(error);
+                    }
+                }
+            }
+
+            // List available prompts
+            "prompts/list" => {
+                json!({ "prompts": self.mcp.list_prompts() })
+            }
+
+            // Render a prompt
+            "prompts/get" => {
+                let params = &request["params"];
+                let (name, arguments) = match string_param(params, "name")
+                    .and_then(|name| Ok((name, object_param(params, "arguments")?)))
+                {
+                    Ok(params) => params,
+                    Err(e) => return Some(error_response(id, INVALID_PARAMS, e)),
+                };
+
+                self.log_info(&format!("Getting prompt: {}", name));
+
+                match self.mcp.get_prompt(name, &arguments) {
+                    Ok((prompt, text)) => {
+                        json!({
+                            "description": prompt.description,
+                            "messages": [{
+                                "role": "user",
+                                "content": {
+                                    "type": "text",
+                                    "text": text
+                                }
+                            }]
+                        })
+                    }
+                    Err(e) => {
+                        self.log_error(&format!("Prompt error: {}", e));
+                        return Some(error_response(id, INVALID_PARAMS, e));
+                    }
+                }
+            }
+
             // Ping
             "ping" => {
                 json!({})
@@ -328,14 +545,11 @@ impl StdioTransport {
             // Unknown method
             _ => {
                 self.log_warn(&format!("Unknown method: {}", method));
-                return Some(json!({
-                    "jsonrpc": "2.0",
-                    "error": {
-                        "code": -32601,
-                        "message": format!("Method not found: {}", method)
-                    },
-                    "id": id
-                }));
+                return Some(error_response(
+                    id,
+                    METHOD_NOT_FOUND,
+                    format!("Method not found: {}", method),
+                ));
             }
         };
 
@@ -358,6 +572,8 @@ impl StdioTransport {
                 "uptime_seconds": self.start_time.elapsed().as_secs(),
                 "request_count": self.request_count.load(Ordering::SeqCst),
                 "tool_count": self.mcp.tool_count(),
+                "resource_count": self.mcp.resource_count(),
+                "prompt_count": self.mcp.prompt_count(),
                 "pid": std::process::id()
             },
             "build": {
@@ -401,11 +617,7 @@ impl StdioTransport {
 
     fn log_request(&self, id: u64, content: &str) {
         // Truncate long requests for logging
-        let truncated = if content.len() > 500 {
-            format!("{}...(truncated)", &content[..500])
-        } else {
-            content.to_string()
-        };
+        let truncated = truncate(content, 500);
 
         #[cfg(feature = "tracing")]
         tracing::debug!(request_id = id, request = %truncated, "Received MCP request");
@@ -417,11 +629,7 @@ impl StdioTransport {
     }
 
     fn log_response(&self, id: u64, content: &str) {
-        let truncated = if content.len() > 500 {
-            format!("{}...(truncated)", &content[..500])
-        } else {
-            content.to_string()
-        };
+        let truncated = truncate(content, 500);
 
         #[cfg(feature = "tracing")]
         tracing::debug!(request_id = id, response = %truncated, "Sending MCP response");
@@ -457,6 +665,118 @@ impl StdioTransport {
     }
 }
 
+/// Read one message from `reader`: a line of JSON, or a `Content-Length`
+/// framed body. Blank lines are skipped; `Ok(None)` means end of input.
+///
+/// Bytes that aren't valid UTF-8 are replaced rather than rejected, so
+/// garbled input surfaces as a parse error instead of ending the loop.
+fn read_frame(reader: &mut impl BufRead) -> std::io::Result<Option<(String, Framing)>> {
+    let mut line = Vec::new();
+    loop {
+        line.clear();
+        if reader.read_until(b'\n', &mut line)? == 0 {
+            return Ok(None);
+        }
+        let text = String::from_utf8_lossy(&line);
+        let text = text.trim();
+        if text.is_empty() {
+            continue;
+        }
+
+        let length = text
+            .split_once(':')
+            .filter(|(name, _)| name.trim().eq_ignore_ascii_case("content-length"))
+            .map(|(_, value)| value.trim().parse::<usize>());
+        let length = match length {
+            Some(Ok(length)) => length,
+            // Not a header (or an unparseable one): a newline-delimited message
+            _ => return Ok(Some((text.to_string(), Framing::Newline))),
+        };
+
+        // Skip any further headers up to the blank line
+        loop {
+            line.clear();
+            if reader.read_until(b'\n', &mut line)? == 0 {
+                return Ok(None);
+            }
+            if String::from_utf8_lossy(&line).trim().is_empty() {
+                break;
+            }
+        }
+
+        if length > MAX_FRAME_LEN {
+            std::io::copy(
+                &mut Read::take(&mut *reader, length as u64),
+                &mut std::io::sink(),
+            )?;
+            return Ok(Some((String::new(), Framing::ContentLength)));
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body)?;
+        return Ok(Some((
+            String::from_utf8_lossy(&body).into_owned(),
+            Framing::ContentLength,
+        )));
+    }
+}
+
+/// Check the JSON-RPC 2.0 request envelope
+fn validate_request(request: &Value) -> Result<(), String> {
+    if !request.is_object() {
+        return Err("Invalid Request: expected a JSON object".to_string());
+    }
+    if request.get("jsonrpc").and_then(Value::as_str) != Some("2.0") {
+        return Err("Invalid Request: jsonrpc must be \"2.0\"".to_string());
+    }
+    if !request.get("method").is_some_and(Value::is_string) {
+        return Err("Invalid Request: method must be a string".to_string());
+    }
+    match request.get("id") {
+        None | Some(Value::Null | Value::Number(_) | Value::String(_)) => Ok(()),
+        Some(_) => Err("Invalid Request: id must be a string or number".to_string()),
+    }
+}
+
+/// A required string parameter
+fn string_param<'a>(params: &'a Value, key: &str) -> Result<&'a str, String> {
+    params
+        .get(key)
+        .and_then(Value::as_str)
+        .ok_or_else(|| format!("Invalid params: missing string `{}`", key))
+}
+
+/// An optional object parameter, `{}` when absent
+fn object_param(params: &Value, key: &str) -> Result<Value, String> {
+    match params.get(key) {
+        None | Some(Value::Null) => Ok(json!({})),
+        Some(value @ Value::Object(_)) => Ok(value.clone()),
+        Some(_) => Err(format!("Invalid params: `{}` must be an object", key)),
+    }
+}
+
+fn error_response(id: Option<Value>, code: i64, message: impl Into<String>) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "error": {
+            "code": code,
+            "message": message.into()
+        },
+        "id": id
+    })
+}
+
+/// Truncate `content` to at most `max` bytes for logging, on a char boundary
+fn truncate(content: &str, max: usize) -> String {
+    if content.len() <= max {
+        return content.to_string();
+    }
+    let mut end = max;
+    while !content.is_char_boundary(end) {
+        end -= 1;
+    }
+    format!("{}...(truncated)", &content[..end])
+}
+
 /// Initialize tracing with file output if ALLFRAME_MCP_LOG_FILE is set
 #[cfg(feature = "tracing")]
 pub fn init_tracing() {
@@ -491,6 +811,7 @@ pub fn init_tracing() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::McpPrompt;
 
     #[test]
     fn test_config_default() {
@@ -510,4 +831,313 @@ mod tests {
         assert_eq!(config.server_name, "my-server");
         assert_eq!(config.log_file, Some("/tmp/mcp.log".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_resources_list_and_read() {
+        let mcp = McpServer::new();
+        mcp.register_resource("file:///project/README.md", || async {
+            Ok("# Project".into())
+        });
+        let transport = StdioTransport::new(mcp, StdioConfig::default());
+
+        let init = transport
+            .handle_request(json!({"jsonrpc": "2.0", "id": 1, "method": "initialize"}))
+            .await
+            .unwrap();
+        assert!(init["result"]["capabilities"]["resources"].is_object());
+
+        let list = transport
+            .handle_request(json!({"jsonrpc": "2.0", "id": 2, "method": "resources/list"}))
+            .await
+            .unwrap();
+        let resources = list["result"]["resources"].as_array().unwrap();
+        assert_eq!(resources.len(), 1);
+        assert_eq!(resources[0]["uri"], "file:///project/README.md");
+        assert_eq!(resources[0]["mimeType"], "text/plain");
+
+        let read = transport
+            .handle_request(json!({
+                "jsonrpc": "2.0",
+                "id": 3,
+                "method": "resources/read",
+                "params": {"uri": "file:///project/README.md"}
+            }))
+            .await
+            .unwrap();
+        let contents = &read["result"]["contents"][0];
+        assert_eq!(contents["uri"], "file:///project/README.md");
+        assert_eq!(contents["mimeType"], "text/plain");
+        assert_eq!(contents["text"], "# Project");
+
+        let missing = transport
+            .handle_request(json!({
+                "jsonrpc": "2.0",
+                "id": 4,
+                "method": "resources/read",
+                "params": {"uri": "file:///missing"}
+            }))
+            .await
+            .unwrap();
+        assert_eq!(missing["error"]["code"], -32002);
+        assert_eq!(missing["id"], 4);
+    }
+
+    #[tokio::test]
+    async fn test_resources_capability_only_when_registered() {
+        let transport = StdioTransport::new(McpServer::new(), StdioConfig::default());
+        let init = transport
+            .handle_request(json!({"jsonrpc": "2.0", "id": 1, "method": "initialize"}))
+            .await
+            .unwrap();
+        assert!(init["result"]["capabilities"].get("resources").is_none());
+        assert!(init["result"]["capabilities"].get("prompts").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_slow_tool_call_is_cancelled() {
+        let mcp = McpServer::new();
+        mcp.register_tool("slow", |_| async {
+            tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+            Ok(json!("done"))
+        });
+        let transport = Arc::new(StdioTransport::new(mcp, StdioConfig::default()));
+
+        let call = tokio::spawn({
+            let transport = transport.clone();
+            async move {
+                transport
+                    .handle_request(json!({
+                        "jsonrpc": "2.0",
+                        "id": 5,
+                        "method": "tools/call",
+                        "params": {"name": "slow", "arguments": {}}
+                    }))
+                    .await
+            }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+
+        let ack = transport
+            .handle_request(json!({
+                "jsonrpc": "2.0",
+                "method": "notifications/cancelled",
+                "params": {"requestId": 5, "reason": "user aborted"}
+            }))
+            .await;
+        assert!(ack.is_none());
+
+        let response = tokio::time::timeout(std::time::Duration::from_secs(5), call)
+            .await
+            .expect("cancelled call should finish")
+            .unwrap();
+        assert!(response.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_tool_timeout_is_an_error_result() {
+        let mcp = McpServer::new().with_default_timeout(std::time::Duration::from_millis(20));
+        mcp.register_tool("slow", |_| async {
+            tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+            Ok(json!("done"))
+        });
+        let transport = StdioTransport::new(mcp, StdioConfig::default());
+
+        let response = transport
+            .handle_request(json!({
+                "jsonrpc": "2.0",
+                "id": 6,
+                "method": "tools/call",
+                "params": {"name": "slow", "arguments": {}}
+            }))
+            .await
+            .unwrap();
+        assert_eq!(response["result"]["isError"], true);
+        let text = response["result"]["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("timed out"), "{text}");
+    }
+
+    #[test]
+    fn test_read_frame_newline_and_content_length() {
+        let body = r#"{"jsonrpc":"2.0","id":2,"method":"ping"}"#;
+        let input = format!(
+            "\n{{\"jsonrpc\":\"2.0\",\"id\":1,\"method\":\"ping\"}}\r\n\
+             Content-Length: {}\r\nContent-Type: application/json\r\n\r\n{}\
+             {{\"jsonrpc\":\"2.0\",\"id\":3,\"method\":\"ping\"}}\n",
+            body.len(),
+            body
+        );
+        let mut reader = std::io::Cursor::new(input.into_bytes());
+
+        let (first, framing) = read_frame(&mut reader).unwrap().unwrap();
+        assert_eq!(framing, Framing::Newline);
+        assert_eq!(serde_json::from_str::<Value>(&first).unwrap()["id"], 1);
+
+        let (second, framing) = read_frame(&mut reader).unwrap().unwrap();
+        assert_eq!(framing, Framing::ContentLength);
+        assert_eq!(second, body);
+
+        // The frame ends exactly at its length: the next line is intact
+        let (third, framing) = read_frame(&mut reader).unwrap().unwrap();
+        assert_eq!(framing, Framing::Newline);
+        assert_eq!(serde_json::from_str::<Value>(&third).unwrap()["id"], 3);
+
+        assert!(read_frame(&mut reader).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_read_frame_garbled_input() {
+        // Invalid UTF-8 becomes a (garbled) message rather than a read error
+        let mut reader = std::io::Cursor::new(b"{\"id\": \xff\xfe}\n".to_vec());
+        let (message, _) = read_frame(&mut reader).unwrap().unwrap();
+        assert!(message.contains('\u{fffd}'));
+
+        // A truncated frame is an error, not a hang or panic
+        let mut reader = std::io::Cursor::new(b"Content-Length: 100\r\n\r\n{\"id\"".to_vec());
+        assert!(read_frame(&mut reader).is_err());
+
+        // An unparseable length is passed through as a message
+        let mut reader = std::io::Cursor::new(b"Content-Length: lots\n".to_vec());
+        let (message, framing) = read_frame(&mut reader).unwrap().unwrap();
+        assert_eq!(
+            (message.as_str(), framing),
+            ("Content-Length: lots", Framing::Newline)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_malformed_messages_get_errors_and_loop_survives() {
+        let transport = StdioTransport::new(McpServer::new(), StdioConfig::default());
+
+        let code = |response: &Value| response["error"]["code"].as_i64().unwrap();
+
+        // Parse errors and batches are rejected before dispatch
+        let error = transport
+            .parse_message("{\"jsonrpc\": \"2.0\", ")
+            .unwrap_err();
+        assert_eq!(code(&error), PARSE_ERROR);
+        assert!(error["id"].is_null());
+        let error = transport.parse_message("[]").unwrap_err();
+        assert_eq!(code(&error), INVALID_REQUEST);
+
+        for (request, expected) in [
+            (json!(42), INVALID_REQUEST),
+            (json!({"id": 1, "method": "ping"}), INVALID_REQUEST),
+            (json!({"jsonrpc": "2.0", "id": 1}), INVALID_REQUEST),
+            (
+                json!({"jsonrpc": "2.0", "id": 1, "method": 7}),
+                INVALID_REQUEST,
+            ),
+            (
+                json!({"jsonrpc": "2.0", "id": [1], "method": "ping"}),
+                INVALID_REQUEST,
+            ),
+            (
+                json!({"jsonrpc": "2.0", "id": 1, "method": "tools/frobnicate"}),
+                METHOD_NOT_FOUND,
+            ),
+            (
+                json!({"jsonrpc": "2.0", "id": 1, "method": "tools/call"}),
+                INVALID_PARAMS,
+            ),
+            (
+                json!({"jsonrpc": "2.0", "id": 1, "method": "tools/call",
+                       "params": {"name": "missing"}}),
+                INVALID_PARAMS,
+            ),
+            (
+                json!({"jsonrpc": "2.0", "id": 1, "method": "tools/call",
+                       "params": {"name": "missing", "arguments": "x"}}),
+                INVALID_PARAMS,
+            ),
+            (
+                json!({"jsonrpc": "2.0", "id": 1, "method": "resources/read", "params": {}}),
+                INVALID_PARAMS,
+            ),
+            (
+                json!({"jsonrpc": "2.0", "id": 1, "method": "prompts/get", "params": {}}),
+                INVALID_PARAMS,
+            ),
+        ] {
+            let response = transport.handle_request(request.clone()).await.unwrap();
+            assert_eq!(code(&response), expected, "{request}");
+            assert_eq!(response["jsonrpc"], "2.0");
+        }
+
+        // Notifications never get a response, even for unknown methods or
+        // invalid ones
+        let response = transport
+            .handle_request(json!({"jsonrpc": "2.0", "method": "notifications/unknown"}))
+            .await;
+        assert!(response.is_none());
+        let response = transport
+            .handle_request(json!({"jsonrpc": "1.0", "method": 7}))
+            .await;
+        assert!(response.is_none());
+
+        // The transport still serves requests afterwards
+        let pong = transport
+            .handle_request(json!({"jsonrpc": "2.0", "id": "last", "method": "ping"}))
+            .await
+            .unwrap();
+        assert_eq!(pong["id"], "last");
+        assert!(pong["result"].is_object());
+    }
+
+    #[test]
+    fn test_truncate_on_char_boundary() {
+        assert_eq!(truncate("short", 500), "short");
+        let long = "é".repeat(300);
+        let truncated = truncate(&long, 501);
+        assert!(truncated.starts_with(&"é".repeat(250)));
+        assert!(truncated.ends_with("...(truncated)"));
+    }
+
+    #[tokio::test]
+    async fn test_prompts_list_and_get() {
+        let mcp = McpServer::new();
+        mcp.register_prompt(
+            McpPrompt::new("review", "Review {{file}}").with_description("Review a file"),
+        );
+        let transport = StdioTransport::new(mcp, StdioConfig::default());
+
+        let init = transport
+            .handle_request(json!({"jsonrpc": "2.0", "id": 1, "method": "initialize"}))
+            .await
+            .unwrap();
+        assert!(init["result"]["capabilities"]["prompts"].is_object());
+
+        let list = transport
+            .handle_request(json!({"jsonrpc": "2.0", "id": 2, "method": "prompts/list"}))
+            .await
+            .unwrap();
+        let prompt = &list["result"]["prompts"][0];
+        assert_eq!(prompt["name"], "review");
+        assert_eq!(prompt["description"], "Review a file");
+        assert_eq!(prompt["arguments"][0]["name"], "file");
+
+        let get = transport
+            .handle_request(json!({
+                "jsonrpc": "2.0",
+                "id": 3,
+                "method": "prompts/get",
+                "params": {"name": "review", "arguments": {"file": "src/main.rs"}}
+            }))
+            .await
+            .unwrap();
+        assert_eq!(get["result"]["description"], "Review a file");
+        let message = &get["result"]["messages"][0];
+        assert_eq!(message["role"], "user");
+        assert_eq!(message["content"]["text"], "Review src/main.rs");
+
+        let missing_arg = transport
+            .handle_request(json!({
+                "jsonrpc": "2.0",
+                "id": 4,
+                "method": "prompts/get",
+                "params": {"name": "review"}
+            }))
+            .await
+            .unwrap();
+        assert_eq!(missing_arg["error"]["code"], -32602);
+    }
 }
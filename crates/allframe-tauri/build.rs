@@ -1,6 +1,9 @@
 const COMMANDS: &[&str] = &[
     "allframe_list",
     "allframe_call",
+    "allframe_batch",
+    "allframe_invalidate",
+    "allframe_query",
     "allframe_stream",
     "allframe_stream_cancel",
 ];
@@ -37,7 +37,10 @@ use serde::Serialize;
 use tauri::plugin::TauriPlugin;
 use tauri::{Emitter, Manager, Runtime};
 
+use crate::cache::HandlerCacheConfig;
 use crate::plugin::{boot_progress_event, build_plugin, ActiveStreams};
+#[cfg(feature = "cqrs")]
+use crate::queries::QueryRegistry;
 use crate::server::TauriServer;
 
 // ─── Types ──────────────────────────────────────────────────────────────────
@@ -155,6 +158,9 @@ pub struct BootBuilder<R: Runtime> {
     router: Router,
     boot_fn: Option<BoxedBootFn<R>>,
     step_count: u32,
+    cache: Option<HandlerCacheConfig>,
+    #[cfg(feature = "cqrs")]
+    queries: Option<QueryRegistry>,
 }
 
 impl<R: Runtime> BootBuilder<R> {
@@ -167,6 +173,17 @@ impl<R: Runtime> BootBuilder<R> {
     pub fn step_count(&self) -> u32 {
         self.step_count
     }
+
+    /// Returns `true` if handler result caching has been configured.
+    pub fn has_cache(&self) -> bool {
+        self.cache.is_some()
+    }
+
+    /// Returns `true` if CQRS queries have been exposed.
+    #[cfg(feature = "cqrs")]
+    pub fn has_queries(&self) -> bool {
+        self.queries.is_some()
+    }
 }
 
 impl<R: Runtime> BootBuilder<R> {
@@ -176,9 +193,29 @@ impl<R: Runtime> BootBuilder<R> {
             router,
             boot_fn: None,
             step_count: 0,
+            cache: None,
+            #[cfg(feature = "cqrs")]
+            queries: None,
         }
     }
 
+    /// Cache handler results as configured.
+    ///
+    /// Off by default; see [`HandlerCacheConfig`].
+    pub fn cache(mut self, config: HandlerCacheConfig) -> Self {
+        self.cache = Some(config);
+        self
+    }
+
+    /// Expose CQRS queries to the frontend via `allframe_query`.
+    ///
+    /// See [`QueryRegistry`].
+    #[cfg(feature = "cqrs")]
+    pub fn queries(mut self, registry: QueryRegistry) -> Self {
+        self.queries = Some(registry);
+        self
+    }
+
     /// Set the async boot closure.
     ///
     /// `steps` is the total number of progress steps (for the denominator
@@ -208,6 +245,9 @@ impl<R: Runtime> BootBuilder<R> {
             router,
             boot_fn,
             step_count,
+            cache,
+            #[cfg(feature = "cqrs")]
+            queries,
         } = self;
 
         build_plugin(move |app_handle| {
@@ -238,7 +278,17 @@ impl<R: Runtime> BootBuilder<R> {
                 // rt drops here — boot runtime is ephemeral
             }
 
-            app_handle.manage(TauriServer::new(router));
+            let server = TauriServer::new(router);
+            let server = match cache {
+                Some(config) => server.with_cache(config),
+                None => server,
+            };
+            #[cfg(feature = "cqrs")]
+            let server = match queries {
+                Some(registry) => server.with_queries(registry),
+                None => server,
+            };
+            app_handle.manage(server);
             app_handle.manage(Arc::new(ActiveStreams::new()));
             Ok(())
         })
@@ -324,6 +374,27 @@ mod tests {
         assert_eq!(builder.step_count(), 0);
     }
 
+    #[test]
+    fn test_boot_builder_cache_is_opt_in() {
+        let builder: BootBuilder<tauri::Wry> = BootBuilder::new(Router::new());
+        assert!(!builder.has_cache());
+
+        let builder = builder.cache(HandlerCacheConfig::new());
+        assert!(builder.has_cache());
+    }
+
+    #[cfg(feature = "cqrs")]
+    #[test]
+    fn test_boot_builder_queries_are_opt_in() {
+        use allframe_core::cqrs::QueryBus;
+
+        let builder: BootBuilder<tauri::Wry> = BootBuilder::new(Router::new());
+        assert!(!builder.has_queries());
+
+        let builder = builder.queries(QueryRegistry::new(QueryBus::new()));
+        assert!(builder.has_queries());
+    }
+
     #[test]
     fn test_boot_builder_on_boot_configures() {
         let router = Router::new();
@@ -0,0 +1,310 @@
+//! Opt-in caching of handler results
+//!
+//! Read handlers that are called repeatedly with the same arguments
+//! (settings, lists) can have their results cached for a TTL, so screens
+//! don't recompute them on every call. Caching is off unless a handler is
+//! listed in a [`HandlerCacheConfig`].
+//!
+//! Results are keyed by handler name and arguments, and tagged with the
+//! handler name in a core [`MemoryCache`], so every cached result of a
+//! handler can be dropped at once: explicitly with the
+//! `allframe_invalidate` command, or automatically when a write handler
+//! declared with [`HandlerCacheConfig::invalidates`] succeeds.
+//!
+//! ```rust
+//! use std::time::Duration;
+//!
+//! use allframe_core::router::Router;
+//! use allframe_tauri::{HandlerCacheConfig, TauriServer};
+//!
+//! let mut router = Router::new();
+//! router.register("get_settings", || async { "{}".to_string() });
+//! router.register("save_settings", || async { "ok".to_string() });
+//!
+//! let server = TauriServer::new(router).with_cache(
+//!     HandlerCacheConfig::new()
+//!         .cache("get_settings", Duration::from_secs(60))
+//!         .invalidates("save_settings", ["get_settings"]),
+//! );
+//! ```
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use allframe_core::cache::{Cache, MemoryCache};
+use tokio::sync::RwLock;
+
+/// Which handlers to cache, and which writes invalidate them
+#[derive(Debug, Clone, Default)]
+pub struct HandlerCacheConfig {
+    ttls: HashMap<String, Duration>,
+    invalidations: HashMap<String, Vec<String>>,
+}
+
+impl HandlerCacheConfig {
+    /// Create a config that caches nothing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cache successful results of `handler` for `ttl`
+    pub fn cache(mut self, handler: impl Into<String>, ttl: Duration) -> Self {
+        self.ttls.insert(handler.into(), ttl);
+        self
+    }
+
+    /// Invalidate the cached results of `readers` whenever `writer`
+    /// succeeds
+    pub fn invalidates<I, S>(mut self, writer: impl Into<String>, readers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.invalidations
+            .entry(writer.into())
+            .or_default()
+            .extend(readers.into_iter().map(Into::into));
+        self
+    }
+
+    /// The TTL for `handler`, if its results are cached
+    pub fn ttl(&self, handler: &str) -> Option<Duration> {
+        self.ttls.get(handler).copied()
+    }
+
+    /// The handlers whose results `writer` invalidates
+    pub fn invalidated_by(&self, writer: &str) -> &[String] {
+        self.invalidations
+            .get(writer)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+/// Handler result cache used by [`TauriServer`](crate::TauriServer)
+pub(crate) struct HandlerCache {
+    config: HandlerCacheConfig,
+    store: MemoryCache,
+    /// Bumped by every invalidation, so a read that overlapped one doesn't
+    /// cache its possibly stale result
+    generation: RwLock<u64>,
+}
+
+impl HandlerCache {
+    pub(crate) fn new(config: HandlerCacheConfig) -> Self {
+        Self {
+            config,
+            store: MemoryCache::new(),
+            generation: RwLock::new(0),
+        }
+    }
+
+    /// The current generation, to pass to [`record`](Self::record) for a
+    /// call that starts now
+    pub(crate) async fn generation(&self) -> u64 {
+        *self.generation.read().await
+    }
+
+    /// The cached result of calling `handler` with `args`
+    pub(crate) async fn get(&self, handler: &str, args: &str) -> Option<String> {
+        self.config.ttl(handler)?;
+        self.store.get(&key(handler, args)).await
+    }
+
+    /// Record a successful call, caching its result or invalidating the
+    /// handlers it writes to
+    ///
+    /// `generation` is the [`generation`](Self::generation) from before the
+    /// call ran. The result isn't cached if anything was invalidated since.
+    pub(crate) async fn record(&self, handler: &str, args: &str, result: &str, generation: u64) {
+        if let Some(ttl) = self.config.ttl(handler) {
+            let current = self.generation.read().await;
+            if *current == generation {
+                self.store
+                    .set_tagged(&key(handler, args), &result, Some(ttl), &[handler])
+                    .await;
+            }
+        }
+        for reader in self.config.invalidated_by(handler) {
+            self.invalidate(reader).await;
+        }
+    }
+
+    /// Drop every cached result of `handler`, returning how many there were
+    pub(crate) async fn invalidate(&self, handler: &str) -> usize {
+        let mut generation = self.generation.write().await;
+        *generation += 1;
+        self.store.invalidate_tag(handler).await
+    }
+}
+
+fn key(handler: &str, args: &str) -> String {
+    format!("{handler}:{args}")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use allframe_core::router::Router;
+
+    use super::*;
+    use crate::{BatchCall, BatchItemResponse, TauriServer};
+
+    /// A router whose `count` handler returns how often it has run
+    fn counting_router() -> (Router, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut router = Router::new();
+        let counter = calls.clone();
+        router.register("count", move || {
+            let counter = counter.clone();
+            async move { (counter.fetch_add(1, Ordering::SeqCst) + 1).to_string() }
+        });
+        router.register("save", || async { "saved".to_string() });
+        router.register_result("fail", || async {
+            Err::<String, String>("boom".to_string())
+        });
+        (router, calls)
+    }
+
+    #[tokio::test]
+    async fn test_cache_is_off_by_default() {
+        let (router, calls) = counting_router();
+        let server = TauriServer::new(router);
+
+        server.call_handler("count", "{}").await.unwrap();
+        let second = server.call_handler("count", "{}").await.unwrap();
+        assert_eq!(second.result, "2");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert_eq!(server.invalidate("count").await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_cached_results_and_invalidation() {
+        let (router, calls) = counting_router();
+        let server = TauriServer::new(router)
+            .with_cache(HandlerCacheConfig::new().cache("count", Duration::from_secs(60)));
+
+        assert_eq!(
+            server.call_handler("count", "{}").await.unwrap().result,
+            "1"
+        );
+        assert_eq!(
+            server.call_handler("count", "{}").await.unwrap().result,
+            "1"
+        );
+        // Different arguments are cached separately
+        assert_eq!(
+            server
+                .call_handler("count", "{\"page\":2}")
+                .await
+                .unwrap()
+                .result,
+            "2"
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        assert_eq!(server.invalidate("count").await, 2);
+        assert_eq!(
+            server.call_handler("count", "{}").await.unwrap().result,
+            "3"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_writes_invalidate_readers() {
+        let (router, calls) = counting_router();
+        let server = TauriServer::new(router).with_cache(
+            HandlerCacheConfig::new()
+                .cache("count", Duration::from_secs(60))
+                .invalidates("save", ["count"])
+                .invalidates("fail", ["count"]),
+        );
+
+        server.call_handler("count", "{}").await.unwrap();
+        // A failed write leaves the cache alone
+        assert!(server.call_handler("fail", "{}").await.is_err());
+        assert_eq!(
+            server.call_handler("count", "{}").await.unwrap().result,
+            "1"
+        );
+
+        server.call_handler("save", "{}").await.unwrap();
+        assert_eq!(
+            server.call_handler("count", "{}").await.unwrap().result,
+            "2"
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_read_overlapping_an_invalidation_is_not_cached() {
+        let release = Arc::new(tokio::sync::Notify::new());
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut router = Router::new();
+        let (gate, counter) = (release.clone(), calls.clone());
+        router.register("slow", move || {
+            let (gate, counter) = (gate.clone(), counter.clone());
+            async move {
+                let call = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                if call == 1 {
+                    gate.notified().await;
+                }
+                call.to_string()
+            }
+        });
+        let server = Arc::new(
+            TauriServer::new(router)
+                .with_cache(HandlerCacheConfig::new().cache("slow", Duration::from_secs(60))),
+        );
+
+        let reader = tokio::spawn({
+            let server = server.clone();
+            async move { server.call_handler("slow", "{}").await }
+        });
+        while calls.load(Ordering::SeqCst) == 0 {
+            tokio::task::yield_now().await;
+        }
+        // Invalidated while the first read is still running
+        server.invalidate("slow").await;
+        release.notify_one();
+        assert_eq!(reader.await.unwrap().unwrap().result, "1");
+
+        assert_eq!(server.call_handler("slow", "{}").await.unwrap().result, "2");
+    }
+
+    #[tokio::test]
+    async fn test_batch_calls_use_the_cache() {
+        let (router, calls) = counting_router();
+        let server = TauriServer::new(router)
+            .with_cache(HandlerCacheConfig::new().cache("count", Duration::from_secs(60)));
+        server.call_handler("count", "{}").await.unwrap();
+
+        let call = BatchCall {
+            handler: "count".to_string(),
+            args: serde_json::json!({}),
+        };
+        let responses = server.call_batch(vec![call.clone(), call]).await;
+        let cached = BatchItemResponse::Ok {
+            result: "1".to_string(),
+        };
+        assert_eq!(responses, [cached.clone(), cached]);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_entries_expire() {
+        let (router, calls) = counting_router();
+        let server = TauriServer::new(router)
+            .with_cache(HandlerCacheConfig::new().cache("count", Duration::from_millis(20)));
+
+        server.call_handler("count", "{}").await.unwrap();
+        tokio::time::sleep(Duration::from_millis(40)).await;
+        assert_eq!(
+            server.call_handler("count", "{}").await.unwrap().result,
+            "2"
+        );
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -15,3 +15,21 @@
 //! Calls a handler by name. Parameters:
 //! - `handler: String` — the handler name
 //! - `args: Value` — JSON arguments passed to the handler
+//!
+//! ### `plugin:allframe-tauri|allframe_batch`
+//! Calls several handlers concurrently. Parameters:
+//! - `calls: Vec<BatchCall>` — `{handler, args}` objects
+//!
+//! Returns one `BatchItemResponse` per call, in order.
+//!
+//! ### `plugin:allframe-tauri|allframe_invalidate`
+//! Drops the cached results of a handler. Parameters:
+//! - `handler: String` — the handler name
+//!
+//! Returns the number of cached results dropped.
+//!
+//! ### `plugin:allframe-tauri|allframe_query`
+//! Runs a CQRS query exposed with a `QueryRegistry` (`cqrs` feature).
+//! Parameters:
+//! - `query: String` — the query name
+//! - `params: Option<Value>` — the query's fields as JSON (default `{}`)
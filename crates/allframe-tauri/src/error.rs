@@ -19,4 +19,8 @@ pub enum TauriServerError {
     /// Attempted to call a non-streaming handler as streaming
     #[error("Not a streaming handler: {0}")]
     NotStreamingHandler(String),
+
+    /// The requested query is not exposed to the frontend
+    #[error("Query not found: {0}")]
+    QueryNotFound(String),
 }
@@ -34,6 +34,18 @@
 //!     handler: "get_user",
 //!     args: { id: 42 }
 //! });
+//!
+//! // Call several handlers in one IPC round-trip
+//! const results = await invoke("plugin:allframe-tauri|allframe_batch", {
+//!     calls: [{ handler: "get_user", args: { id: 42 } }, { handler: "get_stats" }]
+//! });
+//! // [{ status: "ok", result: "..." }, { status: "error", error: "..." }]
+//!
+//! // Run a CQRS query exposed with a QueryRegistry (`cqrs` feature)
+//! const balance = await invoke("plugin:allframe-tauri|allframe_query", {
+//!     query: "GetBalance",
+//!     params: { account: "savings" }
+//! });
 //! ```
 //!
 //! # In-Process Dispatch (Local LLM / Ollama)
@@ -56,18 +68,26 @@
 //! ```
 
 pub mod boot;
+pub mod cache;
 pub mod commands;
 pub mod error;
 pub mod plugin;
+#[cfg(feature = "cqrs")]
+pub mod queries;
 pub mod server;
 pub mod types;
 
 pub use allframe_core::router::StreamReceiver;
 pub use boot::{BootBuilder, BootContext, BootError, BootProgress};
+pub use cache::HandlerCacheConfig;
 pub use error::TauriServerError;
 pub use plugin::{builder, init, init_with_state, PLUGIN_NAME};
+#[cfg(feature = "cqrs")]
+pub use queries::QueryRegistry;
 pub use server::TauriServer;
-pub use types::{CallResponse, HandlerInfo, HandlerKind, StreamStartResponse};
+pub use types::{
+    BatchCall, BatchItemResponse, CallResponse, HandlerInfo, HandlerKind, StreamStartResponse,
+};
 
 #[cfg(test)]
 mod tests {
@@ -553,4 +573,107 @@ mod tests {
         let response = handle.await.unwrap().unwrap();
         assert_eq!(response.result, "done");
     }
+
+    #[tokio::test]
+    async fn test_call_batch_mixed_results() {
+        #[derive(serde::Deserialize)]
+        struct Input {
+            x: i32,
+        }
+
+        let mut router = Router::new();
+        router.register("ping", || async { "pong".to_string() });
+        router.register_with_args("double", |args: Input| async move {
+            format!(r#"{{"doubled":{}}}"#, args.x * 2)
+        });
+        router.register_result("fail", || async {
+            Err::<String, String>("boom".to_string())
+        });
+
+        let server = TauriServer::new(router);
+        let calls: Vec<BatchCall> = serde_json::from_str(
+            r#"[
+                {"handler": "double", "args": {"x": 21}},
+                {"handler": "missing"},
+                {"handler": "ping", "args": {}},
+                {"handler": "fail", "args": {}},
+                {"handler": "double", "args": {"x": "nope"}}
+            ]"#,
+        )
+        .unwrap();
+
+        let responses = server.call_batch(calls).await;
+        assert_eq!(responses.len(), 5);
+        assert_eq!(
+            responses[0],
+            BatchItemResponse::Ok {
+                result: r#"{"doubled":42}"#.to_string()
+            }
+        );
+        assert_eq!(
+            responses[1],
+            BatchItemResponse::Error {
+                error: "Handler not found: missing".to_string()
+            }
+        );
+        assert_eq!(
+            responses[2],
+            BatchItemResponse::Ok {
+                result: "pong".to_string()
+            }
+        );
+        assert!(
+            matches!(&responses[3], BatchItemResponse::Error { error } if error.contains("boom"))
+        );
+        assert!(matches!(responses[4], BatchItemResponse::Error { .. }));
+
+        let json = serde_json::to_value(&responses[..2]).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([
+                {"status": "ok", "result": "{\"doubled\":42}"},
+                {"status": "error", "error": "Handler not found: missing"}
+            ])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_call_batch_runs_concurrently() {
+        use std::sync::Arc;
+
+        // Each handler waits for the other, so this only completes if both
+        // run at the same time
+        let barrier = Arc::new(tokio::sync::Barrier::new(2));
+        let mut router = Router::new();
+        for name in ["left", "right"] {
+            let barrier = barrier.clone();
+            router.register(name, move || {
+                let barrier = barrier.clone();
+                async move {
+                    barrier.wait().await;
+                    name.to_string()
+                }
+            });
+        }
+
+        let server = TauriServer::new(router);
+        let calls = ["left", "right"]
+            .map(|handler| BatchCall {
+                handler: handler.to_string(),
+                args: serde_json::Value::Null,
+            })
+            .to_vec();
+        let responses = server.call_batch(calls).await;
+        assert_eq!(
+            responses,
+            [
+                BatchItemResponse::Ok {
+                    result: "left".to_string()
+                },
+                BatchItemResponse::Ok {
+                    result: "right".to_string()
+                }
+            ]
+        );
+    }
 }
@@ -10,7 +10,7 @@ use tokio::sync::Mutex;
 
 use crate::error::TauriServerError;
 use crate::server::TauriServer;
-use crate::types::{CallResponse, HandlerInfo, StreamStartResponse};
+use crate::types::{BatchCall, BatchItemResponse, CallResponse, HandlerInfo, StreamStartResponse};
 
 /// The plugin identifier used for Tauri 2 ACL resolution and IPC routing.
 ///
@@ -75,6 +75,45 @@ pub(crate) async fn allframe_call(
     server.call_handler(&handler, &args_str).await
 }
 
+/// Call several handlers concurrently in one IPC round-trip.
+///
+/// Returns one `{status: "ok", result}` or `{status: "error", error}` per
+/// call, in request order.
+#[tauri::command]
+pub(crate) async fn allframe_batch(
+    calls: Vec<BatchCall>,
+    server: tauri::State<'_, TauriServer>,
+) -> Result<Vec<BatchItemResponse>, TauriServerError> {
+    Ok(server.call_batch(calls).await)
+}
+
+/// Drop the cached results of a handler (see [`HandlerCacheConfig`]).
+///
+/// Returns the number of cached results dropped.
+///
+/// [`HandlerCacheConfig`]: crate::HandlerCacheConfig
+#[tauri::command]
+pub(crate) async fn allframe_invalidate(
+    handler: String,
+    server: tauri::State<'_, TauriServer>,
+) -> Result<usize, TauriServerError> {
+    Ok(server.invalidate(&handler).await)
+}
+
+/// Run an exposed CQRS query by name (see `QueryRegistry`).
+///
+/// `params` defaults to `{}` for queries without fields. Without the `cqrs`
+/// feature every query is reported as not found.
+#[tauri::command]
+pub(crate) async fn allframe_query(
+    query: String,
+    params: Option<serde_json::Value>,
+    server: tauri::State<'_, TauriServer>,
+) -> Result<CallResponse, TauriServerError> {
+    let params = params.unwrap_or_else(|| serde_json::json!({}));
+    server.query(&query, params).await
+}
+
 /// Start a streaming handler. Returns a stream_id immediately.
 /// Stream items are emitted as Tauri events:
 /// - `allframe-tauri:stream:{handler}:{stream_id}` — each item
@@ -214,6 +253,9 @@ where
         .invoke_handler(tauri::generate_handler![
             allframe_list,
             allframe_call,
+            allframe_batch,
+            allframe_invalidate,
+            allframe_query,
             allframe_stream,
             allframe_stream_cancel,
         ])
@@ -0,0 +1,260 @@
+//! CQRS queries callable from the frontend
+//!
+//! A [`QueryRegistry`] names the `#[query]` types the frontend may run and
+//! dispatches them through the core [`QueryBus`], so projections can be read
+//! with `allframe_query` without writing a pass-through handler per query.
+//! Parameters are deserialized into the query type and the handler's result
+//! is returned as JSON. Query middleware on the bus (caching, authorization)
+//! applies as for any other dispatch.
+//!
+//! ```rust
+//! use allframe_core::cqrs::{QueryBus, QueryHandler, QueryResult};
+//! use allframe_core::async_trait::async_trait;
+//! use allframe_core::router::Router;
+//! use allframe_tauri::{QueryRegistry, TauriServer};
+//!
+//! #[allframe_macros::query(derive)]
+//! struct GetBalance {
+//!     account: String,
+//! }
+//!
+//! struct BalanceHandler;
+//!
+//! #[async_trait]
+//! impl QueryHandler<GetBalance, u64> for BalanceHandler {
+//!     async fn handle(&self, query: GetBalance) -> QueryResult<u64> {
+//!         Ok(query.account.len() as u64)
+//!     }
+//! }
+//!
+//! # #[tokio::main]
+//! # async fn main() {
+//! let bus = QueryBus::new();
+//! bus.register(BalanceHandler).await;
+//!
+//! let server = TauriServer::new(Router::new())
+//!     .with_queries(QueryRegistry::new(bus).query::<GetBalance, u64>());
+//!
+//! let response = server
+//!     .query("GetBalance", serde_json::json!({ "account": "savings" }))
+//!     .await
+//!     .unwrap();
+//! assert_eq!(response.result, "7");
+//! # }
+//! ```
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+
+use allframe_core::cqrs::{Query, QueryBus};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::error::TauriServerError;
+
+/// Type-erased dispatch of one query type from JSON params
+type QueryFn = Box<
+    dyn Fn(
+            QueryBus,
+            Value,
+        ) -> Pin<Box<dyn Future<Output = Result<String, TauriServerError>> + Send>>
+        + Send
+        + Sync,
+>;
+
+/// Queries the frontend can run by name
+pub struct QueryRegistry {
+    bus: QueryBus,
+    queries: HashMap<String, QueryFn>,
+}
+
+impl QueryRegistry {
+    /// Create a registry dispatching to `bus`
+    pub fn new(bus: QueryBus) -> Self {
+        Self {
+            bus,
+            queries: HashMap::new(),
+        }
+    }
+
+    /// Expose `Q`, returning `R`, under its type name (e.g. `GetBalance`)
+    pub fn query<Q, R>(self) -> Self
+    where
+        Q: Query + DeserializeOwned,
+        R: Serialize + Send + Sync + 'static,
+    {
+        let type_name = std::any::type_name::<Q>();
+        let name = type_name.rsplit("::").next().unwrap_or(type_name);
+        self.query_named::<Q, R>(name)
+    }
+
+    /// Expose `Q`, returning `R`, under `name`
+    pub fn query_named<Q, R>(mut self, name: impl Into<String>) -> Self
+    where
+        Q: Query + DeserializeOwned,
+        R: Serialize + Send + Sync + 'static,
+    {
+        let dispatch: QueryFn = Box::new(|bus, params| {
+            Box::pin(async move {
+                let query: Q = serde_json::from_value(params).map_err(|e| {
+                    TauriServerError::ExecutionFailed(format!("Invalid query params: {e}"))
+                })?;
+                let result: R = bus
+                    .dispatch(query)
+                    .await
+                    .map_err(|e| TauriServerError::ExecutionFailed(e.to_string()))?;
+                serde_json::to_string(&result)
+                    .map_err(|e| TauriServerError::ExecutionFailed(e.to_string()))
+            })
+        });
+        self.queries.insert(name.into(), dispatch);
+        self
+    }
+
+    /// Names of the exposed queries, sorted
+    pub fn names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.queries.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Run the query exposed as `name`, returning its result as JSON
+    pub async fn dispatch(&self, name: &str, params: Value) -> Result<String, TauriServerError> {
+        let dispatch = self
+            .queries
+            .get(name)
+            .ok_or_else(|| TauriServerError::QueryNotFound(name.to_string()))?;
+        dispatch(self.bus.clone(), params).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use allframe_core::async_trait::async_trait;
+    use allframe_core::cqrs::{
+        testing::replay_projection, Event, EventStore, EventTypeName, Projection, QueryHandler,
+        QueryResult,
+    };
+    use allframe_core::router::Router;
+    use serde_json::json;
+
+    use super::*;
+    use crate::TauriServer;
+
+    #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+    enum AccountEvent {
+        Deposited { account: String, amount: u64 },
+        Withdrawn { account: String, amount: u64 },
+    }
+
+    impl EventTypeName for AccountEvent {}
+    impl Event for AccountEvent {}
+
+    #[derive(Default)]
+    struct Balances(HashMap<String, u64>);
+
+    impl Projection for Balances {
+        type Event = AccountEvent;
+
+        fn apply(&mut self, event: &AccountEvent) {
+            match event {
+                AccountEvent::Deposited { account, amount } => {
+                    *self.0.entry(account.clone()).or_default() += amount;
+                }
+                AccountEvent::Withdrawn { account, amount } => {
+                    *self.0.entry(account.clone()).or_default() -= amount;
+                }
+            }
+        }
+    }
+
+    #[allframe_macros::query(derive)]
+    struct GetBalance {
+        account: String,
+    }
+
+    /// Reads the balance from the projection rebuilt from the store
+    struct BalanceHandler {
+        store: Arc<EventStore<AccountEvent>>,
+    }
+
+    #[async_trait]
+    impl QueryHandler<GetBalance, Option<u64>> for BalanceHandler {
+        async fn handle(&self, query: GetBalance) -> QueryResult<Option<u64>> {
+            let events = self.store.get_all_events().await?;
+            let balances = replay_projection(Balances::default(), events, |_| {});
+            Ok(balances.0.get(&query.account).copied())
+        }
+    }
+
+    async fn server(store: Arc<EventStore<AccountEvent>>) -> TauriServer {
+        let bus = QueryBus::new();
+        bus.register(BalanceHandler { store }).await;
+        TauriServer::new(Router::new())
+            .with_queries(QueryRegistry::new(bus).query::<GetBalance, Option<u64>>())
+    }
+
+    #[tokio::test]
+    async fn test_query_projection_after_events_are_appended() {
+        let store = Arc::new(EventStore::new());
+        let server = server(store.clone()).await;
+        assert_eq!(server.list_queries(), ["GetBalance"]);
+
+        let before = server
+            .query("GetBalance", json!({ "account": "alice" }))
+            .await
+            .unwrap();
+        assert_eq!(before.result, "null");
+
+        store
+            .append(
+                "alice",
+                vec![
+                    AccountEvent::Deposited {
+                        account: "alice".into(),
+                        amount: 100,
+                    },
+                    AccountEvent::Withdrawn {
+                        account: "alice".into(),
+                        amount: 30,
+                    },
+                ],
+            )
+            .await
+            .unwrap();
+
+        let after = server
+            .query("GetBalance", json!({ "account": "alice" }))
+            .await
+            .unwrap();
+        assert_eq!(after.result, "70");
+    }
+
+    #[tokio::test]
+    async fn test_query_errors() {
+        let server = server(Arc::new(EventStore::new())).await;
+
+        let err = server.query("GetOrders", json!({})).await.unwrap_err();
+        assert!(matches!(err, TauriServerError::QueryNotFound(name) if name == "GetOrders"));
+
+        let err = server
+            .query("GetBalance", json!({ "acct": "alice" }))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, TauriServerError::ExecutionFailed(msg) if msg.starts_with("Invalid query params"))
+        );
+
+        // Without a registry no queries are exposed
+        let bare = TauriServer::new(Router::new());
+        assert!(bare.list_queries().is_empty());
+        assert!(matches!(
+            bare.query("GetBalance", json!({})).await,
+            Err(TauriServerError::QueryNotFound(_))
+        ));
+    }
+}
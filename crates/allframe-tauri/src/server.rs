@@ -5,11 +5,14 @@
 
 use std::sync::Arc;
 
-use allframe_core::router::{Router, StreamReceiver};
+use allframe_core::router::{ExecuteError, Router, StreamReceiver};
 use tokio::task::JoinHandle;
 
+use crate::cache::{HandlerCache, HandlerCacheConfig};
 use crate::error::TauriServerError;
-use crate::types::{CallResponse, HandlerInfo, HandlerKind};
+#[cfg(feature = "cqrs")]
+use crate::queries::QueryRegistry;
+use crate::types::{BatchCall, BatchItemResponse, CallResponse, HandlerInfo, HandlerKind};
 
 /// Wraps an AllFrame `Router` for Tauri IPC dispatch.
 ///
@@ -19,6 +22,9 @@ use crate::types::{CallResponse, HandlerInfo, HandlerKind};
 pub struct TauriServer {
     router: Arc<Router>,
     handlers: Vec<HandlerInfo>,
+    cache: Option<Arc<HandlerCache>>,
+    #[cfg(feature = "cqrs")]
+    queries: Option<QueryRegistry>,
 }
 
 impl TauriServer {
@@ -44,9 +50,69 @@ impl TauriServer {
         Self {
             router: Arc::new(router),
             handlers,
+            cache: None,
+            #[cfg(feature = "cqrs")]
+            queries: None,
         }
     }
 
+    /// Cache handler results as configured (off by default).
+    ///
+    /// See [`HandlerCacheConfig`] for which handlers are cached and which
+    /// writes invalidate them.
+    pub fn with_cache(mut self, config: HandlerCacheConfig) -> Self {
+        self.cache = Some(Arc::new(HandlerCache::new(config)));
+        self
+    }
+
+    /// Drop every cached result of `handler`.
+    ///
+    /// Returns the number of results dropped (0 if caching is off).
+    pub async fn invalidate(&self, handler: &str) -> usize {
+        match &self.cache {
+            Some(cache) => cache.invalidate(handler).await,
+            None => 0,
+        }
+    }
+
+    /// Expose CQRS queries to the frontend (none by default).
+    ///
+    /// See [`QueryRegistry`].
+    #[cfg(feature = "cqrs")]
+    pub fn with_queries(mut self, registry: QueryRegistry) -> Self {
+        self.queries = Some(registry);
+        self
+    }
+
+    /// Names of the exposed queries, sorted
+    pub fn list_queries(&self) -> Vec<&str> {
+        #[cfg(feature = "cqrs")]
+        if let Some(registry) = &self.queries {
+            return registry.names();
+        }
+        Vec::new()
+    }
+
+    /// Run an exposed query by name with JSON params.
+    ///
+    /// The result is the query handler's return value as JSON. Without the
+    /// `cqrs` feature no queries can be exposed, so this always fails with
+    /// [`TauriServerError::QueryNotFound`].
+    pub async fn query(
+        &self,
+        name: &str,
+        params: serde_json::Value,
+    ) -> Result<CallResponse, TauriServerError> {
+        #[cfg(feature = "cqrs")]
+        if let Some(registry) = &self.queries {
+            let result = registry.dispatch(name, params).await?;
+            return Ok(CallResponse { result });
+        }
+        #[cfg(not(feature = "cqrs"))]
+        let _ = params;
+        Err(TauriServerError::QueryNotFound(name.to_string()))
+    }
+
     /// List all registered handlers
     pub fn list_handlers(&self) -> &[HandlerInfo] {
         &self.handlers
@@ -70,10 +136,41 @@ impl TauriServer {
             return Err(TauriServerError::HandlerNotFound(name.to_string()));
         }
 
-        match self.router.call_handler(name, args).await {
-            Ok(result) => Ok(CallResponse { result }),
-            Err(e) => Err(TauriServerError::ExecutionFailed(e)),
+        call_router(&self.router, self.cache.as_deref(), name, args).await
+    }
+
+    /// Call several handlers concurrently.
+    ///
+    /// Returns one response per call, in the same order. A failing call
+    /// doesn't affect the others.
+    pub async fn call_batch(&self, calls: Vec<BatchCall>) -> Vec<BatchItemResponse> {
+        let tasks: Vec<_> = calls
+            .into_iter()
+            .map(|call| {
+                let known = self.handlers.iter().any(|h| h.name == call.handler);
+                let router = self.router.clone();
+                let cache = self.cache.clone();
+                tokio::spawn(async move {
+                    if !known {
+                        return Err(TauriServerError::HandlerNotFound(call.handler));
+                    }
+                    let args = call.args.to_string();
+                    call_router(&router, cache.as_deref(), &call.handler, &args).await
+                })
+            })
+            .collect();
+
+        let mut responses = Vec::with_capacity(tasks.len());
+        for task in tasks {
+            let response = match task.await {
+                Ok(result) => result.into(),
+                Err(e) => BatchItemResponse::Error {
+                    error: format!("Handler task panicked: {e}"),
+                },
+            };
+            responses.push(response);
         }
+        responses
     }
 
     /// Call a streaming handler by name.
@@ -124,6 +221,32 @@ impl TauriServer {
     }
 }
 
+async fn call_router(
+    router: &Router,
+    cache: Option<&HandlerCache>,
+    name: &str,
+    args: &str,
+) -> Result<CallResponse, TauriServerError> {
+    let mut generation = 0;
+    if let Some(cache) = cache {
+        generation = cache.generation().await;
+        if let Some(result) = cache.get(name, args).await {
+            return Ok(CallResponse { result });
+        }
+    }
+
+    match router.call_handler(name, args).await {
+        Ok(result) => {
+            if let Some(cache) = cache {
+                cache.record(name, args, &result, generation).await;
+            }
+            Ok(CallResponse { result })
+        }
+        Err(ExecuteError::NotFound(name)) => Err(TauriServerError::HandlerNotFound(name)),
+        Err(e) => Err(TauriServerError::ExecutionFailed(e.to_string())),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -36,3 +36,42 @@ pub struct StreamStartResponse {
     /// Unique stream ID for this invocation
     pub stream_id: String,
 }
+
+/// One call in an `allframe_batch` request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchCall {
+    /// Handler name
+    pub handler: String,
+    /// JSON arguments passed to the handler (defaults to `null`)
+    #[serde(default)]
+    pub args: serde_json::Value,
+}
+
+/// Outcome of one call in a batch, in the same position as its request
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum BatchItemResponse {
+    /// The handler succeeded
+    Ok {
+        /// The handler's return value
+        result: String,
+    },
+    /// The handler was not found or failed
+    Error {
+        /// Error message
+        error: String,
+    },
+}
+
+impl From<Result<CallResponse, crate::error::TauriServerError>> for BatchItemResponse {
+    fn from(result: Result<CallResponse, crate::error::TauriServerError>) -> Self {
+        match result {
+            Ok(response) => BatchItemResponse::Ok {
+                result: response.result,
+            },
+            Err(e) => BatchItemResponse::Error {
+                error: e.to_string(),
+            },
+        }
+    }
+}
@@ -111,6 +111,12 @@ async fn main() {
     println!("Status: {}", error_response.status());
     println!("Body: {}\n", error_response.body());
 
+    // Build a response with a custom status and headers
+    let created = RestResponse::json(&serde_json::json!({ "id": 42 }))
+        .with_status(201)
+        .header("location", "/users/42");
+    println!("{}\n", RestAdapter::new().render_response(&created));
+
     println!("--- Example 6: List All Handlers ---");
     let users_response = router.execute("list_users").await.unwrap();
     println!("GET /users");
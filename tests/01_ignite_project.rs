@@ -117,6 +117,43 @@ fn ignite_fails_with_invalid_project_name() {
         .stderr(predicate::str::contains("Invalid project name"));
 }
 
+#[test]
+fn ignite_rejects_path_traversal() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+    let work_dir = temp_dir.path().join("work");
+    fs::create_dir(&work_dir).expect("Failed to create directory");
+
+    // Try to create a project outside the working directory
+    let mut cmd = Command::cargo_bin("allframe").expect("Failed to find allframe binary");
+
+    cmd.arg("ignite")
+        .arg("../escaped")
+        .current_dir(&work_dir)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("must not contain '..'"));
+
+    assert!(!temp_dir.path().join("escaped").exists());
+}
+
+#[test]
+fn ignite_interactive_without_terminal_uses_flags() {
+    let temp_dir = TempDir::new().expect("Failed to create temp directory");
+
+    // stdin isn't a terminal here, so the wizard must not wait for answers
+    let mut cmd = Command::cargo_bin("allframe").expect("Failed to find allframe binary");
+
+    cmd.arg("ignite")
+        .arg("scripted")
+        .arg("--interactive")
+        .current_dir(temp_dir.path())
+        .assert()
+        .success()
+        .stderr(predicate::str::contains("skipping interactive prompts"));
+
+    assert!(temp_dir.path().join("scripted/src/main.rs").exists());
+}
+
 #[test]
 fn ignite_fails_if_directory_already_exists() {
     let temp_dir = TempDir::new().expect("Failed to create temp directory");
@@ -89,3 +89,64 @@ fn test_di_auto_wire_simple() {
     let container = AppContainer::new();
     assert_eq!(container.service().greet(), "Hello");
 }
+
+/// Test fields gated on a feature with #[provide(cfg = ...)]
+#[test]
+fn test_di_cfg_gated_fields() {
+    struct Settings;
+
+    impl Settings {
+        fn new() -> Self {
+            Self
+        }
+
+        fn name(&self) -> &'static str {
+            "desktop"
+        }
+    }
+
+    #[cfg(feature = "cache-redis")]
+    struct RedisCache;
+
+    #[cfg(feature = "cache-redis")]
+    impl RedisCache {
+        fn new() -> Self {
+            Self
+        }
+
+        fn backend(&self) -> &'static str {
+            "redis"
+        }
+    }
+
+    #[cfg(not(feature = "cache-redis"))]
+    struct LocalCache;
+
+    #[cfg(not(feature = "cache-redis"))]
+    impl LocalCache {
+        fn new() -> Self {
+            Self
+        }
+
+        fn backend(&self) -> &'static str {
+            "local"
+        }
+    }
+
+    #[di_container]
+    struct AppContainer {
+        settings: Settings,
+        #[provide(cfg = "feature = \"cache-redis\"")]
+        redis: RedisCache,
+        #[provide(cfg = "not(feature = \"cache-redis\")")]
+        local: LocalCache,
+    }
+
+    let container = AppContainer::new();
+    assert_eq!(container.settings().name(), "desktop");
+
+    #[cfg(feature = "cache-redis")]
+    assert_eq!(container.redis().backend(), "redis");
+    #[cfg(not(feature = "cache-redis"))]
+    assert_eq!(container.local().backend(), "local");
+}
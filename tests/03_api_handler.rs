@@ -151,6 +151,12 @@ fn test_api_handler_with_error_responses() {
         error: String,
     }
 
+    impl std::fmt::Display for ErrorResponse {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(&self.error)
+        }
+    }
+
     #[api_handler(
         path = "/validate",
         method = "POST",
@@ -248,3 +254,46 @@ fn test_openapi_schema_aggregation() {
     assert!(schema_1.contains("/users"));
     assert!(schema_2.contains("/posts"));
 }
+
+/// Test that the annotation drives router registration
+#[tokio::test]
+async fn test_api_handler_registers_route() {
+    use allframe_core::router::{Router, ToJsonSchema};
+
+    #[derive(Debug, Serialize, Deserialize)]
+    struct Greeting {
+        name: String,
+    }
+
+    impl ToJsonSchema for Greeting {
+        fn schema() -> serde_json::Value {
+            serde_json::json!({
+                "type": "object",
+                "properties": { "name": { "type": "string" } }
+            })
+        }
+    }
+
+    #[api_handler(path = "/greet", method = "POST", description = "Greet someone")]
+    async fn greet(req: Greeting) -> String {
+        format!("Hello, {}!", req.name)
+    }
+
+    let mut router = Router::new();
+    greet_register_into(&mut router);
+
+    let routes = router.routes();
+    assert_eq!(routes.len(), 1);
+    assert_eq!(routes[0].path, "/greet");
+    assert_eq!(routes[0].method, "POST");
+    assert_eq!(routes[0].protocol, "rest");
+    assert_eq!(routes[0].description.as_deref(), Some("Greet someone"));
+    assert_eq!(routes[0].request_schema, Some(Greeting::schema()));
+    assert_eq!(routes[0].response_schema, Some(String::schema()));
+
+    let response = router
+        .execute_with_args("POST:/greet", r#"{"name":"Ada"}"#)
+        .await
+        .unwrap();
+    assert_eq!(response, "Hello, Ada!");
+}
@@ -168,6 +168,39 @@ async fn test_e2e_multi_protocol() {
     assert!(!grpc_response.is_empty());
 }
 
+/// Test that configured request limits are enforced by the adapters
+#[tokio::test]
+async fn test_config_request_limits() {
+    use allframe_core::router::Router;
+
+    let config_toml = r#"
+        [server]
+        protocols = ["rest", "graphql"]
+
+        [server.rest]
+        max_body_bytes = 32
+        max_json_depth = 2
+
+        [server.graphql]
+        max_query_depth = 2
+    "#;
+
+    let config = RouterConfig::from_toml(config_toml).unwrap();
+    let router = Router::with_config(config);
+
+    let large_body = format!("POST /users \"{}\"", "x".repeat(64));
+    let response = router.route_request("rest", &large_body).await.unwrap();
+    assert!(response.starts_with("HTTP 413"));
+
+    let deep_body = "POST /users {\"a\":{\"b\":[1]}}";
+    let response = router.route_request("rest", deep_body).await.unwrap();
+    assert!(response.starts_with("HTTP 400"));
+
+    let deep_query = "{ user { friends { name } } }";
+    let response = router.call_graphql(deep_query).await.unwrap();
+    assert!(response.contains("Query depth 3 exceeds maximum of 2"));
+}
+
 /// Test that changing config doesn't require code changes
 #[test]
 fn test_config_change_no_code_change() {
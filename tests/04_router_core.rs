@@ -92,3 +92,29 @@ async fn test_register_protocol_adapter() {
     assert!(router.has_adapter("rest"));
     assert!(!router.has_adapter("graphql"));
 }
+
+/// Test that middleware layers wrap handler calls and expose the request id
+#[cfg(feature = "otel")]
+#[tokio::test]
+async fn test_logging_middleware_correlation_id() {
+    use allframe_core::router::{
+        request_id, LoggingMiddleware, MiddlewareRequest, Router, REQUEST_ID_HEADER,
+    };
+
+    let mut router = Router::new();
+    router.layer(LoggingMiddleware::new());
+    router.register("whoami", || async move { request_id().unwrap_or_default() });
+
+    // Propagates an incoming correlation id
+    let request =
+        MiddlewareRequest::new("handler", "whoami", "{}").with_header(REQUEST_ID_HEADER, "req-42");
+    assert_eq!(router.dispatch(request).await.unwrap(), "req-42");
+
+    // Generates one when absent
+    let generated = router.execute("whoami").await.unwrap();
+    assert!(!generated.is_empty());
+    assert_ne!(generated, "req-42");
+
+    // No id outside of a request
+    assert_eq!(request_id(), None);
+}
@@ -151,3 +151,61 @@ async fn test_grpc_service_registration() {
 
     // MVP: Service-level registration will come in later phases
 }
+
+/// Test GrpcError derive with explicit codes and a #[grpc(default)] catch-all
+#[cfg(feature = "router-grpc")]
+mod grpc_error_derive {
+    use allframe::tonic::{Code, Status};
+    use allframe::GrpcError;
+
+    #[derive(Debug, thiserror::Error, GrpcError)]
+    enum AppError {
+        #[error("User not found: {0}")]
+        #[grpc(NOT_FOUND)]
+        UserNotFound(String),
+
+        #[error("Rate limited")]
+        #[grpc(RESOURCE_EXHAUSTED)]
+        RateLimited,
+
+        #[error(transparent)]
+        #[grpc(default)]
+        Other(#[from] std::io::Error),
+    }
+
+    #[test]
+    fn test_explicit_codes_take_precedence() {
+        let status: Status = AppError::UserNotFound("42".to_string()).into();
+        assert_eq!(status.code(), Code::NotFound);
+        assert_eq!(status.message(), "User not found: 42");
+
+        let status: Status = AppError::RateLimited.into();
+        assert_eq!(status.code(), Code::ResourceExhausted);
+    }
+
+    #[test]
+    fn test_default_variant_maps_wrapped_error() {
+        let timeout = std::io::Error::new(std::io::ErrorKind::TimedOut, "backend timed out");
+        let status: Status = AppError::from(timeout).into();
+        assert_eq!(status.code(), Code::DeadlineExceeded);
+        assert_eq!(status.message(), "backend timed out");
+
+        let other = std::io::Error::other("disk on fire");
+        let status: Status = AppError::from(other).into();
+        assert_eq!(status.code(), Code::Internal);
+    }
+
+    #[derive(Debug, thiserror::Error, GrpcError)]
+    #[grpc_error(crate = "allframe_core")]
+    enum CoreError {
+        #[error("Already exists")]
+        #[grpc(ALREADY_EXISTS)]
+        Duplicate,
+    }
+
+    #[test]
+    fn test_custom_crate_path() {
+        let status: Status = CoreError::Duplicate.into();
+        assert_eq!(status.code(), Code::AlreadyExists);
+    }
+}
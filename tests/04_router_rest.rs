@@ -9,7 +9,7 @@
 //! Advanced features (parameter extraction, body parsing) will come in later
 //! phases.
 
-use allframe_core::router::{ProtocolAdapter, RestAdapter, RestResponse, Router};
+use allframe_core::router::{ExecuteError, ProtocolAdapter, RestAdapter, RestResponse, Router};
 
 /// Test basic REST adapter creation and registration
 #[tokio::test]
@@ -102,8 +102,11 @@ async fn test_rest_error_handling() {
 
     // Test nonexistent handler
     let error = router.execute("nonexistent").await;
-    assert!(error.is_err());
-    assert!(error.unwrap_err().contains("not found"));
+    assert_eq!(
+        error,
+        Err(ExecuteError::NotFound("nonexistent".to_string()))
+    );
+    assert!(error.unwrap_err().to_string().contains("not found"));
 }
 
 /// Test REST response structure
@@ -137,3 +140,92 @@ async fn test_rest_route_matching() {
     assert_eq!(response.status(), 200);
     assert_eq!(response.body(), "OK");
 }
+
+/// Test that the REST adapter renders built responses faithfully
+#[test]
+fn test_rest_response_builder_rendering() {
+    let adapter = RestAdapter::new();
+
+    let response = RestResponse::ok("cached")
+        .header("x-foo", "bar")
+        .header("cache-control", "max-age=60")
+        .with_status(201);
+    assert_eq!(
+        adapter.render_response(&response),
+        "HTTP/1.1 201 Created\r\nx-foo: bar\r\ncache-control: max-age=60\r\ncontent-length: 6\r\n\r\ncached"
+    );
+
+    let json = RestResponse::json(&vec!["a", "b"]);
+    assert_eq!(
+        adapter.render_response(&json),
+        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: 9\r\n\r\n[\"a\",\"b\"]"
+    );
+
+    assert_eq!(
+        adapter.render_response(&RestResponse::no_content()),
+        "HTTP/1.1 204 No Content\r\n\r\n"
+    );
+}
+
+/// Test HttpError derive mapping error variants to HTTP status codes
+mod http_error_derive {
+    use allframe::HttpError;
+    use allframe_core::router::{HttpError as _, RestAdapter};
+
+    #[derive(Debug, thiserror::Error, HttpError)]
+    enum UserError {
+        #[error("User not found: {0}")]
+        #[http(404)]
+        NotFound(String),
+
+        #[error("Invalid email: {email}")]
+        #[http(BAD_REQUEST)]
+        InvalidEmail { email: String },
+
+        #[error("Database unavailable")]
+        Database,
+    }
+
+    #[test]
+    fn test_status_codes() {
+        assert_eq!(UserError::NotFound("42".into()).status_code(), 404);
+        assert_eq!(
+            UserError::InvalidEmail {
+                email: "nope".into()
+            }
+            .status_code(),
+            400
+        );
+        assert_eq!(UserError::Database.status_code(), 500);
+    }
+
+    #[test]
+    fn test_into_response() {
+        let response = UserError::NotFound("42".into()).into_response();
+        assert_eq!(response.status(), 404);
+        assert_eq!(response.body(), r#"{"error":"User not found: 42"}"#);
+    }
+
+    #[tokio::test]
+    async fn test_fallible_handler_through_rest_adapter() {
+        async fn get_user(id: u32) -> Result<String, UserError> {
+            match id {
+                42 => Ok("Alice".to_string()),
+                _ => Err(UserError::NotFound(id.to_string())),
+            }
+        }
+
+        let adapter = RestAdapter::new();
+
+        let found = adapter.respond(get_user(42).await);
+        assert_eq!(found.status(), 200);
+        assert_eq!(found.body(), r#""Alice""#);
+
+        let missing = adapter.respond(get_user(7).await);
+        assert_eq!(missing.status(), 404);
+        assert_eq!(
+            adapter.format_response(missing.status(), missing.body()),
+            r#"HTTP 404 {"error":"User not found: 7"}"#
+        );
+    }
+}
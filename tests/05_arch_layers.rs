@@ -122,20 +122,14 @@ fn test_handler_depends_on_use_case() {
 }
 
 /// Test that handlers CANNOT depend on repositories directly
-/// This test documents that the violation is allowed for now
-/// Full compile-time validation will be added in a future iteration
+///
+/// `#[handler]` rejects repository fields and arguments at compile time
+/// (covered by the macro's unit tests); at runtime the layer metadata reports
+/// the same rule.
 #[test]
 fn test_handler_cannot_depend_on_repository() {
-    // For MVP, we're focusing on the happy path (valid architecture)
-    // Compile-time violation detection requires more advanced proc macro analysis
-    // that tracks all types across the crate and validates field types.
-    //
-    // This is planned for a future iteration once the basic layer system is proven.
-    //
-    // For now, we document the intended behavior:
-    // - Handlers SHOULD NOT depend on repositories directly
-    // - This will be enforced in a future release
-    // - Developers should follow the pattern even though not enforced yet
-
-    assert!(true); // GREEN - documenting intended future behavior
+    let handler = allframe_core::arch::LayerMetadata::new("handler", 4, "GetUserHandler");
+
+    assert!(!handler.can_depend_on("repository"));
+    assert!(handler.can_depend_on("use_case"));
 }
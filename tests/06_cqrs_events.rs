@@ -217,3 +217,234 @@ async fn test_event_stream_subscribe() {
     let received = rx.recv().await;
     assert!(received.is_some());
 }
+
+/// Test subscribe_from - late subscribers replay history, then live-tail
+#[tokio::test]
+async fn test_event_stream_subscribe_from() {
+    use allframe_core::cqrs::Start;
+    use tokio::sync::mpsc;
+
+    #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Counted(u32);
+
+    impl EventTypeName for Counted {}
+    impl Event for Counted {}
+
+    let store = EventStore::new();
+    store
+        .append("a", vec![Counted(1), Counted(2)])
+        .await
+        .unwrap();
+    store.append("b", vec![Counted(3)]).await.unwrap();
+
+    // A capacity-1 channel exercises backpressure during replay
+    let (tx, mut rx) = mpsc::channel(1);
+    store.subscribe_from(tx, Start::Beginning).await.unwrap();
+
+    let (from_tx, mut from_rx) = mpsc::channel(10);
+    store
+        .subscribe_from(from_tx, Start::Sequence(3))
+        .await
+        .unwrap();
+
+    let appender = {
+        let store = store.clone();
+        tokio::spawn(async move {
+            for n in 4..=6 {
+                store.append("a", vec![Counted(n)]).await.unwrap();
+            }
+        })
+    };
+
+    let mut received = Vec::new();
+    while received.len() < 6 {
+        received.push(rx.recv().await.unwrap().0);
+    }
+    assert_eq!(received, vec![1, 2, 3, 4, 5, 6]);
+    appender.await.unwrap();
+
+    let mut received = Vec::new();
+    while received.len() < 4 {
+        received.push(from_rx.recv().await.unwrap().0);
+    }
+    assert_eq!(received, vec![3, 4, 5, 6]);
+}
+
+/// Test append_batch - bulk import of 10k events keeps global and per-aggregate order
+#[tokio::test]
+async fn test_event_store_append_batch_bulk_import() {
+    use tokio::sync::mpsc;
+
+    #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Imported {
+        aggregate: u32,
+        n: u32,
+    }
+
+    impl EventTypeName for Imported {}
+    impl Event for Imported {}
+
+    let store = EventStore::new();
+    let (tx, mut rx) = mpsc::channel(64);
+    store.subscribe(tx).await;
+    let consumer = tokio::spawn(async move {
+        let mut received = Vec::new();
+        while received.len() < 10_000 {
+            received.push(rx.recv().await.unwrap());
+        }
+        received
+    });
+
+    // 100 aggregates x 100 events
+    let batch: Vec<(String, Vec<Imported>)> = (0..100)
+        .map(|aggregate| {
+            let events = (0..100).map(|n| Imported { aggregate, n }).collect();
+            (format!("agg-{}", aggregate), events)
+        })
+        .collect();
+    let expected: Vec<Imported> = batch
+        .iter()
+        .flat_map(|(_, events)| events.clone())
+        .collect();
+
+    let started = std::time::Instant::now();
+    store.append_batch(batch).await.unwrap();
+    let elapsed = started.elapsed();
+
+    assert_eq!(store.get_all_events().await.unwrap(), expected);
+    let events = store.get_events("agg-42").await.unwrap();
+    assert_eq!(events.len(), 100);
+    assert!(events
+        .iter()
+        .enumerate()
+        .all(|(i, e)| e.aggregate == 42 && e.n == i as u32));
+
+    // Subscribers see every event exactly once, in batch order
+    assert_eq!(consumer.await.unwrap(), expected);
+    assert!(
+        elapsed < std::time::Duration::from_secs(5),
+        "took {:?}",
+        elapsed
+    );
+}
+
+/// Test that subscribers only hear about events the backend actually stored
+#[tokio::test]
+async fn test_failed_append_notifies_no_subscribers() {
+    use allframe_core::cqrs::{EventMetadata, EventStoreBackend};
+    use tokio::sync::mpsc;
+
+    #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+    struct Rejected(u32);
+
+    impl EventTypeName for Rejected {}
+    impl Event for Rejected {}
+
+    /// Backend whose writes always fail
+    struct Unavailable;
+
+    #[async_trait::async_trait]
+    impl EventStoreBackend<Rejected> for Unavailable {
+        async fn append(&self, _: &str, _: Vec<Rejected>) -> Result<(), String> {
+            Err("connection refused".to_string())
+        }
+
+        async fn get_events(&self, _: &str) -> Result<Vec<Rejected>, String> {
+            Ok(Vec::new())
+        }
+
+        async fn get_all_events(&self) -> Result<Vec<Rejected>, String> {
+            Ok(Vec::new())
+        }
+
+        async fn get_events_after(&self, _: &str, _: u64) -> Result<Vec<Rejected>, String> {
+            Ok(Vec::new())
+        }
+    }
+
+    let store = EventStore::with_backend(Unavailable);
+    let (tx, mut rx) = mpsc::channel(10);
+    store.subscribe(tx).await;
+
+    assert!(store.append("a", vec![Rejected(1)]).await.is_err());
+    assert!(store
+        .append_with_metadata("a", vec![Rejected(2)], EventMetadata::new())
+        .await
+        .is_err());
+    assert!(store
+        .append_batch(vec![("b".to_string(), vec![Rejected(3)])])
+        .await
+        .is_err());
+
+    assert!(rx.try_recv().is_err());
+}
+
+/// Test InMemoryBackend limits - oldest aggregates are evicted and handed to
+/// the hook
+#[tokio::test]
+async fn test_in_memory_backend_eviction() {
+    use std::sync::{Arc, Mutex};
+
+    use allframe_core::cqrs::{EventStoreBackend, InMemoryBackend};
+
+    let evicted = Arc::new(Mutex::new(Vec::new()));
+    let hook_evicted = Arc::clone(&evicted);
+    let backend = InMemoryBackend::new()
+        .with_max_events(4)
+        .with_max_aggregates(2)
+        .with_on_evict(move |aggregate_id, events: Vec<UserEvent>| {
+            hook_evicted
+                .lock()
+                .unwrap()
+                .push((aggregate_id.to_string(), events.len()));
+        });
+    let created = |id: &str| UserEvent::Created {
+        user_id: id.to_string(),
+        email: format!("{}@example.com", id),
+    };
+    let updated = |id: &str| UserEvent::EmailUpdated {
+        user_id: id.to_string(),
+        new_email: format!("{}@example.org", id),
+    };
+
+    backend
+        .append("user-1", vec![created("user-1"), updated("user-1")])
+        .await
+        .unwrap();
+    backend
+        .append("user-2", vec![created("user-2")])
+        .await
+        .unwrap();
+    backend
+        .save_snapshot("user-1", b"snapshot".to_vec(), 2)
+        .await
+        .unwrap();
+
+    // A third aggregate evicts the oldest one, with its snapshot
+    backend
+        .append("user-3", vec![created("user-3")])
+        .await
+        .unwrap();
+    assert_eq!(*evicted.lock().unwrap(), vec![("user-1".to_string(), 2)]);
+    assert!(backend.get_events("user-1").await.unwrap().is_empty());
+    assert!(backend.get_latest_snapshot("user-1").await.is_err());
+
+    // Going over max_events evicts others, never the aggregate appended to
+    backend
+        .append(
+            "user-3",
+            vec![updated("user-3"), updated("user-3"), updated("user-3")],
+        )
+        .await
+        .unwrap();
+    assert_eq!(evicted.lock().unwrap()[1], ("user-2".to_string(), 1));
+    assert_eq!(backend.get_events("user-3").await.unwrap().len(), 4);
+
+    // Global sequence numbers survive eviction (user-3's events are 4..=7)
+    assert_eq!(backend.get_events_since(5).await.unwrap().len(), 2);
+
+    let stats = backend.stats().await;
+    assert_eq!(stats.total_events, 4);
+    assert_eq!(stats.total_aggregates, 1);
+    assert_eq!(stats.backend_specific["evicted_aggregates"], "2");
+}
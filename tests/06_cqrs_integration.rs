@@ -305,6 +305,353 @@ async fn test_snapshot_optimization() {
     assert_eq!(rebuilt_aggregate.count, 1001);
 }
 
+/// Test reading a long stream in pages and loading an aggregate from it
+#[tokio::test]
+async fn test_paged_event_reads() {
+    #[derive(Default)]
+    struct CounterAggregate {
+        count: i32,
+    }
+
+    impl Aggregate for CounterAggregate {
+        type Event = UserEvent;
+
+        fn apply_event(&mut self, event: &Self::Event) {
+            if let UserEvent::Incremented { amount } = event {
+                self.count += amount;
+            }
+        }
+    }
+
+    let event_store = EventStore::new();
+    let events = (1..=2500)
+        .map(|amount| UserEvent::Incremented { amount })
+        .collect();
+    event_store.append("counter-123", events).await.unwrap();
+
+    let page = event_store
+        .get_events_page("counter-123", 0, 1000)
+        .await
+        .unwrap();
+    assert_eq!(page.events.len(), 1000);
+    assert_eq!(page.version, 1000);
+    assert!(page.has_more);
+
+    let last = event_store
+        .get_events_page("counter-123", 2000, 1000)
+        .await
+        .unwrap();
+    assert_eq!(last.events[0], UserEvent::Incremented { amount: 2001 });
+    assert_eq!((last.events.len(), last.version), (500, 2500));
+    assert!(!last.has_more);
+
+    let empty = event_store.get_events_page("missing", 0, 10).await.unwrap();
+    assert!(empty.events.is_empty() && !empty.has_more);
+
+    let (aggregate, version) = event_store
+        .load_aggregate::<CounterAggregate>("counter-123")
+        .await
+        .unwrap();
+    assert_eq!(aggregate.count, (1..=2500).sum::<i32>());
+    assert_eq!(version, 2500);
+}
+
+/// Test that a snapshot policy makes aggregate loads start from snapshots
+#[tokio::test]
+async fn test_snapshot_policy() {
+    use allframe_core::cqrs::{EventStoreBackend, SnapshotPolicy};
+
+    #[derive(Default, serde::Serialize, serde::Deserialize)]
+    struct CounterAggregate {
+        count: i32,
+        /// Events applied since the aggregate was created or restored
+        #[serde(skip)]
+        applied: u64,
+    }
+
+    impl Aggregate for CounterAggregate {
+        type Event = UserEvent;
+
+        fn apply_event(&mut self, event: &Self::Event) {
+            self.applied += 1;
+            if let UserEvent::Incremented { amount } = event {
+                self.count += amount;
+            }
+        }
+    }
+
+    let event_store = EventStore::new()
+        .with_snapshot_policy::<CounterAggregate>(SnapshotPolicy::EveryEvents(100));
+    let increments = |n| vec![UserEvent::Incremented { amount: 1 }; n];
+
+    // Below the threshold nothing is saved
+    event_store
+        .append("counter-1", increments(50))
+        .await
+        .unwrap();
+    event_store
+        .load_aggregate::<CounterAggregate>("counter-1")
+        .await
+        .unwrap();
+    assert!(event_store
+        .backend()
+        .get_latest_snapshot("counter-1")
+        .await
+        .is_err());
+
+    // Crossing it snapshots at the new version, as the aggregate was loaded
+    event_store
+        .append("counter-1", increments(100))
+        .await
+        .unwrap();
+    let (_, snapshot_version) = event_store
+        .backend()
+        .get_latest_snapshot("counter-1")
+        .await
+        .unwrap();
+    assert_eq!(snapshot_version, 150);
+    let (aggregate, version) = event_store
+        .load_aggregate::<CounterAggregate>("counter-1")
+        .await
+        .unwrap();
+    assert_eq!((aggregate.count, aggregate.applied, version), (150, 0, 150));
+
+    // Later loads replay only the events after the snapshot
+    event_store
+        .append("counter-1", increments(10))
+        .await
+        .unwrap();
+    let (aggregate, version) = event_store
+        .load_aggregate::<CounterAggregate>("counter-1")
+        .await
+        .unwrap();
+    assert_eq!(
+        (aggregate.count, aggregate.applied, version),
+        (160, 10, 160)
+    );
+
+    // Appends snapshot again once the threshold is crossed again
+    event_store
+        .append("counter-1", increments(100))
+        .await
+        .unwrap();
+    let (_, snapshot_version) = event_store
+        .backend()
+        .get_latest_snapshot("counter-1")
+        .await
+        .unwrap();
+    assert_eq!(snapshot_version, 260);
+
+    // Aggregate types without a policy ignore snapshots
+    let (aggregate, _) = EventStore::with_backend(event_store.backend().clone())
+        .load_aggregate::<CounterAggregate>("counter-1")
+        .await
+        .unwrap();
+    assert_eq!(aggregate.applied, 260);
+}
+
+/// Test that a failed snapshot write doesn't fail the aggregate load
+#[tokio::test]
+async fn test_snapshot_failure_keeps_load() {
+    use allframe_core::cqrs::{EventStoreBackend, InMemoryBackend, SnapshotPolicy};
+
+    /// Backend whose snapshot writes always fail
+    #[derive(Default)]
+    struct NoSnapshots(InMemoryBackend<UserEvent>);
+
+    #[async_trait::async_trait]
+    impl EventStoreBackend<UserEvent> for NoSnapshots {
+        async fn append(&self, aggregate_id: &str, events: Vec<UserEvent>) -> Result<(), String> {
+            self.0.append(aggregate_id, events).await
+        }
+
+        async fn get_events(&self, aggregate_id: &str) -> Result<Vec<UserEvent>, String> {
+            self.0.get_events(aggregate_id).await
+        }
+
+        async fn get_all_events(&self) -> Result<Vec<UserEvent>, String> {
+            self.0.get_all_events().await
+        }
+
+        async fn get_events_after(
+            &self,
+            aggregate_id: &str,
+            version: u64,
+        ) -> Result<Vec<UserEvent>, String> {
+            self.0.get_events_after(aggregate_id, version).await
+        }
+
+        async fn save_snapshot(&self, _: &str, _: Vec<u8>, _: u64) -> Result<(), String> {
+            Err("disk full".to_string())
+        }
+    }
+
+    #[derive(Default, serde::Serialize, serde::Deserialize)]
+    struct CounterAggregate {
+        count: i32,
+    }
+
+    impl Aggregate for CounterAggregate {
+        type Event = UserEvent;
+
+        fn apply_event(&mut self, event: &Self::Event) {
+            if let UserEvent::Incremented { amount } = event {
+                self.count += amount;
+            }
+        }
+    }
+
+    let event_store = EventStore::with_backend(NoSnapshots::default())
+        .with_snapshot_policy::<CounterAggregate>(SnapshotPolicy::EveryEvents(1));
+    event_store
+        .append("counter-1", vec![UserEvent::Incremented { amount: 3 }])
+        .await
+        .unwrap();
+
+    let (aggregate, version) = event_store
+        .load_aggregate::<CounterAggregate>("counter-1")
+        .await
+        .unwrap();
+    assert_eq!((aggregate.count, version), (3, 1));
+
+    // Appends to it still succeed too
+    event_store
+        .append("counter-1", vec![UserEvent::Incremented { amount: 1 }])
+        .await
+        .unwrap();
+}
+
+/// Test that metadata appended with events is returned in envelopes
+#[tokio::test]
+async fn test_event_metadata_envelopes() {
+    use allframe_core::cqrs::EventMetadata;
+
+    let event_store = EventStore::new();
+    event_store
+        .append(
+            "user-1",
+            vec![UserEvent::Created {
+                user_id: "1".to_string(),
+                email: "a@example.com".to_string(),
+            }],
+        )
+        .await
+        .unwrap();
+    let metadata = EventMetadata::new()
+        .with_causation_id("cmd-2")
+        .with_correlation_id("signup-1")
+        .with("user", "admin");
+    event_store
+        .append_with_metadata(
+            "user-1",
+            vec![UserEvent::EmailUpdated {
+                new_email: "b@example.com".to_string(),
+            }],
+            metadata,
+        )
+        .await
+        .unwrap();
+
+    let envelopes = event_store
+        .get_events_with_metadata("user-1")
+        .await
+        .unwrap();
+    assert_eq!(envelopes.len(), 2);
+    assert_eq!(envelopes[0].version, 1);
+    assert_eq!(envelopes[0].causation_id, None);
+    assert_eq!(envelopes[1].aggregate_id, "user-1");
+    assert_eq!(envelopes[1].version, 2);
+    assert_eq!(envelopes[1].causation_id.as_deref(), Some("cmd-2"));
+    assert_eq!(envelopes[1].correlation_id.as_deref(), Some("signup-1"));
+    assert_eq!(envelopes[1].metadata["user"], "admin");
+    assert_ne!(envelopes[0].event_id, envelopes[1].event_id);
+    assert!(envelopes[0].timestamp <= envelopes[1].timestamp);
+
+    // Plain reads still return the bare events
+    let events = event_store.get_events("user-1").await.unwrap();
+    assert_eq!(events[1], envelopes[1].event);
+}
+
+/// Test an aggregate's commands given-when-then, and its state event by event
+#[tokio::test]
+async fn test_aggregate_testing_harness() {
+    use allframe_core::cqrs::{
+        testing::{replay, AggregateTest},
+        Command, CommandError, CommandHandler, CommandResult,
+    };
+
+    #[derive(Default)]
+    struct Counter {
+        count: i32,
+        deleted: bool,
+    }
+
+    impl Aggregate for Counter {
+        type Event = UserEvent;
+
+        fn apply_event(&mut self, event: &Self::Event) {
+            match event {
+                UserEvent::Incremented { amount } => self.count += amount,
+                UserEvent::Deleted => self.deleted = true,
+                _ => {}
+            }
+        }
+    }
+
+    struct Increment {
+        amount: i32,
+    }
+
+    impl Command for Increment {}
+
+    #[async_trait::async_trait]
+    impl CommandHandler<Increment, UserEvent> for Counter {
+        async fn handle(&self, command: Increment) -> CommandResult<UserEvent> {
+            if self.deleted {
+                return Err(CommandError::BusinessLogic("counter deleted".to_string()));
+            }
+            if self.count + command.amount > 10 {
+                return Err(CommandError::BusinessLogic("limit is 10".to_string()));
+            }
+            Ok(vec![UserEvent::Incremented {
+                amount: command.amount,
+            }])
+        }
+    }
+
+    // State after each event
+    let mut counts = Vec::new();
+    let counter = replay::<Counter>(
+        [
+            UserEvent::Incremented { amount: 2 },
+            UserEvent::Incremented { amount: 3 },
+            UserEvent::Deleted,
+        ],
+        |counter| counts.push((counter.count, counter.deleted)),
+    );
+    assert_eq!(counts, [(2, false), (5, false), (5, true)]);
+    assert!(counter.deleted);
+
+    // Given past events, when a command is handled, then expect new events
+    let counter = AggregateTest::<Counter>::given([UserEvent::Incremented { amount: 4 }])
+        .when(Increment { amount: 5 })
+        .await
+        .then_expect([UserEvent::Incremented { amount: 5 }]);
+    assert_eq!(counter.count, 9);
+
+    // ...or an error
+    let error = AggregateTest::<Counter>::given([UserEvent::Incremented { amount: 8 }])
+        .when(Increment { amount: 5 })
+        .await
+        .then_expect_error();
+    assert_eq!(error.to_string(), "Business logic error: limit is 10");
+
+    AggregateTest::<Counter>::given([UserEvent::Deleted])
+        .when(Increment { amount: 1 })
+        .await
+        .then_expect_error();
+}
+
 /// Test saga coordination for multi-aggregate transactions
 #[tokio::test]
 async fn test_saga_coordination() {
@@ -22,7 +22,7 @@ async fn test_context_propagation_through_di() {
     let result = operation().await.unwrap();
     assert!(!result.is_empty());
 
-    // Note: For MVP, context propagation uses placeholders
+    // Note: Without an OpenTelemetry subscriber, this is the all-zero trace id
 }
 
 /// Test trace ID consistency throughout request
@@ -43,7 +43,7 @@ async fn test_trace_id_consistency() {
     let trace1 = operation1().await.unwrap();
     let trace2 = operation2().await.unwrap();
 
-    // For MVP, returns placeholder trace IDs
+    // Without an OpenTelemetry subscriber, both get the all-zero trace id
     assert_eq!(trace1, trace2);
 }
 
@@ -75,6 +75,40 @@ async fn test_metric_labels() {
     assert_eq!(get_200, 0); // MVP returns 0
 }
 
+/// Test asserting on a labelled counter
+#[tokio::test]
+async fn test_requests_total_by_method() {
+    let metrics = MetricsRecorder::new();
+
+    async fn handle(metrics: &MetricsRecorder, method: &str) {
+        metrics.increment_counter_with_labels("requests_total", &[("method", method)], 1);
+        metrics.record_histogram_with_labels("request_duration_ms", &[("method", method)], 12.5);
+    }
+
+    handle(&metrics, "GET").await;
+    handle(&metrics, "GET").await;
+    handle(&metrics, "POST").await;
+
+    assert_eq!(
+        metrics.counter_value("requests_total", &[("method", "GET")]),
+        2
+    );
+    assert_eq!(
+        metrics.counter_value("requests_total", &[("method", "POST")]),
+        1
+    );
+    assert_eq!(
+        metrics.counter_value("requests_total", &[("method", "PUT")]),
+        0
+    );
+    assert_eq!(
+        metrics
+            .histogram("request_duration_ms", &[("method", "GET")])
+            .count(),
+        2
+    );
+}
+
 /// Test metric aggregation
 #[tokio::test]
 async fn test_metric_aggregation() {
@@ -85,7 +85,7 @@ async fn test_async_span_propagation() {
 
         let span_id_after = current_span_id();
 
-        // For MVP, span IDs are placeholders
+        // The span is the same across await points
         assert_eq!(span_id_before, span_id_after);
         Ok(())
     }
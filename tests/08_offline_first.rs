@@ -226,6 +226,222 @@ async fn test_sqlite_event_store_events_after_version() {
     assert_eq!(events.len(), 50);
 }
 
+/// Test paging through a SQLite stream without loading it whole
+#[tokio::test]
+#[cfg(feature = "cqrs-sqlite")]
+async fn test_sqlite_event_store_pages_and_streams() {
+    use allframe_core::cqrs::{EventStoreBackend, SqliteEventStoreBackend};
+
+    let dir = tempfile::tempdir().unwrap();
+    let db_path = dir.path().join("events.db");
+    let backend = SqliteEventStoreBackend::<DocumentEvent>::new(db_path.to_str().unwrap())
+        .await
+        .unwrap();
+    let tags = (0..25)
+        .map(|i| DocumentEvent::TagAdded {
+            tag: format!("tag-{}", i),
+        })
+        .collect();
+    backend.append("doc-1", tags).await.unwrap();
+    backend
+        .append("doc-2", vec![DocumentEvent::Deleted])
+        .await
+        .unwrap();
+
+    let page = backend.get_events_page("doc-1", 20, 10).await.unwrap();
+    assert_eq!(page.events.len(), 5);
+    assert_eq!(page.version, 25);
+    assert!(!page.has_more);
+    assert!(backend.get_events_page("doc-1", 10, 10).await.unwrap().has_more);
+
+    let mut stream = backend.stream_events("doc-1", 10);
+    let mut streamed = Vec::new();
+    while let Some(event) = stream.next().await {
+        streamed.push(event.unwrap());
+    }
+    assert_eq!(streamed, backend.get_events("doc-1").await.unwrap());
+    assert_eq!(stream.version(), 25);
+
+    let store = EventStore::with_backend(backend);
+    let (doc, version) = store
+        .load_aggregate::<DocumentAggregate>("doc-1")
+        .await
+        .unwrap();
+    assert_eq!((doc.tags.len(), version), (25, 25));
+}
+
+/// Test that SQLite stores event metadata, including in databases created
+/// before the metadata columns existed
+#[tokio::test]
+#[cfg(feature = "cqrs-sqlite")]
+async fn test_sqlite_event_store_metadata() {
+    use allframe_core::cqrs::{EventMetadata, SqliteEventStoreBackend};
+
+    let dir = tempfile::tempdir().unwrap();
+    let db_path = dir.path().join("events.db");
+    {
+        let conn = rusqlite::Connection::open(&db_path).unwrap();
+        conn.execute_batch(
+            r#"CREATE TABLE events (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                aggregate_id TEXT NOT NULL,
+                event_data BLOB NOT NULL,
+                created_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );
+            INSERT INTO events (aggregate_id, event_data)
+                VALUES ('doc-1', CAST('{"Created":{"doc_id":"doc-1","title":"Old"}}' AS BLOB));"#,
+        )
+        .unwrap();
+    }
+
+    let backend = SqliteEventStoreBackend::<DocumentEvent>::new(db_path.to_str().unwrap())
+        .await
+        .unwrap();
+    let store = EventStore::with_backend(backend);
+    store
+        .append_with_metadata(
+            "doc-1",
+            vec![DocumentEvent::Deleted],
+            EventMetadata::new()
+                .with_causation_id("cmd-9")
+                .with_correlation_id("cleanup"),
+        )
+        .await
+        .unwrap();
+
+    let envelopes = store.get_events_with_metadata("doc-1").await.unwrap();
+    assert_eq!(envelopes.len(), 2);
+    assert_eq!(envelopes[0].causation_id, None);
+    assert!(envelopes[0].timestamp > std::time::UNIX_EPOCH);
+    assert_eq!(envelopes[1].event, DocumentEvent::Deleted);
+    assert_eq!(envelopes[1].version, 2);
+    assert_eq!(envelopes[1].causation_id.as_deref(), Some("cmd-9"));
+    assert_eq!(envelopes[1].correlation_id.as_deref(), Some("cleanup"));
+    assert_eq!(store.get_events("doc-1").await.unwrap().len(), 2);
+}
+
+/// Test that the SQLite idempotency registry remembers ids across restarts
+#[tokio::test]
+#[cfg(feature = "cqrs-sqlite")]
+async fn test_sqlite_idempotency_registry() {
+    use std::time::Duration;
+
+    use allframe_core::cqrs::{IdempotencyRegistry, SqliteIdempotencyRegistry};
+
+    let dir = tempfile::tempdir().unwrap();
+    let db_path = dir.path().join("idempotency.db");
+    let path = db_path.to_str().unwrap();
+
+    let registry = SqliteIdempotencyRegistry::new(path, Duration::from_secs(60))
+        .await
+        .unwrap();
+    assert!(!registry.seen("msg-1").await.unwrap());
+    assert!(registry.seen("msg-1").await.unwrap());
+    drop(registry);
+
+    // Survives reopening
+    let registry = SqliteIdempotencyRegistry::new(path, Duration::from_secs(60))
+        .await
+        .unwrap();
+    assert!(registry.seen("msg-1").await.unwrap());
+    assert_eq!(registry.evict_expired().await.unwrap(), 0);
+
+    // Expired ids count as unseen and are evicted
+    let registry = SqliteIdempotencyRegistry::new(path, Duration::from_millis(20))
+        .await
+        .unwrap();
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    assert!(!registry.seen("msg-1").await.unwrap());
+    assert!(!registry.seen("msg-2").await.unwrap());
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    assert_eq!(registry.evict_expired().await.unwrap(), 2);
+}
+
+/// Test that the SQLite saga store keeps incomplete sagas across reopening
+#[tokio::test]
+#[cfg(feature = "cqrs-sqlite")]
+async fn test_sqlite_saga_store() {
+    use allframe_core::cqrs::{SagaRecord, SagaStatus, SagaStore, SqliteSagaStore};
+
+    let dir = tempfile::tempdir().unwrap();
+    let db_path = dir.path().join("sagas.db");
+    let path = db_path.to_str().unwrap();
+
+    let record = |id: &str, status: SagaStatus| SagaRecord {
+        id: id.to_string(),
+        status,
+        steps_executed: 1,
+        total_steps: 2,
+        context: serde_json::json!({ "order_id": id }),
+        error: None,
+        updated_at: std::time::SystemTime::now(),
+    };
+
+    let store = SqliteSagaStore::new(path).await.unwrap();
+    for (id, status) in [
+        ("a", SagaStatus::Executing),
+        ("b", SagaStatus::Executing),
+        ("c", SagaStatus::Compensated),
+        ("a", SagaStatus::Compensating),
+    ] {
+        store.save(&record(id, status)).await.unwrap();
+    }
+    drop(store);
+
+    // Survives reopening, least recently saved first
+    let store = SqliteSagaStore::new(path).await.unwrap();
+    let incomplete = store.incomplete().await.unwrap();
+    let ids: Vec<&str> = incomplete.iter().map(|r| r.id.as_str()).collect();
+    assert_eq!(ids, ["b", "a"]);
+    assert_eq!(incomplete[1].status, SagaStatus::Compensating);
+    assert_eq!(incomplete[1].context["order_id"], "a");
+    assert_eq!(
+        store.load("c").await.unwrap().unwrap().status,
+        SagaStatus::Compensated
+    );
+    assert!(store.load("missing").await.unwrap().is_none());
+}
+
+/// Test that SQLite backend imports a 10k event batch in one transaction
+#[tokio::test]
+#[cfg(feature = "cqrs-sqlite")]
+async fn test_sqlite_event_store_append_batch() {
+    use allframe_core::cqrs::SqliteEventStoreBackend;
+
+    let dir = tempfile::tempdir().unwrap();
+    let db_path = dir.path().join("events.db");
+    let backend =
+        SqliteEventStoreBackend::<DocumentEvent>::new(db_path.to_str().unwrap())
+            .await
+            .unwrap();
+    let store = EventStore::with_backend(backend);
+
+    // 10 documents x 1000 tags
+    let batch: Vec<(String, Vec<DocumentEvent>)> = (0..10)
+        .map(|doc| {
+            let events = (0..1000)
+                .map(|i| DocumentEvent::TagAdded {
+                    tag: format!("doc-{}-tag-{}", doc, i),
+                })
+                .collect();
+            (format!("doc-{}", doc), events)
+        })
+        .collect();
+    store.append_batch(batch).await.unwrap();
+
+    let all = store.get_all_events().await.unwrap();
+    assert_eq!(all.len(), 10_000);
+    assert!(matches!(&all[1000], DocumentEvent::TagAdded { tag } if tag == "doc-1-tag-0"));
+
+    let events = store.get_events("doc-7").await.unwrap();
+    assert_eq!(events.len(), 1000);
+    assert!(matches!(&events[999], DocumentEvent::TagAdded { tag } if tag == "doc-7-tag-999"));
+
+    let stats = store.backend().stats().await;
+    assert_eq!(stats.total_events, 10_000);
+    assert_eq!(stats.total_aggregates, 10);
+}
+
 /// Test that SQLite backend supports snapshot persistence
 #[tokio::test]
 #[cfg(feature = "cqrs-sqlite")]
@@ -257,6 +473,86 @@ async fn test_sqlite_event_store_snapshots() {
     assert_eq!(data, snapshot_data);
 }
 
+/// Run the same calls against a backend and collect every result, for
+/// comparing backends
+#[cfg(feature = "cqrs-sqlite")]
+async fn backend_parity_trace<B>(backend: &B) -> Vec<String>
+where
+    B: allframe_core::cqrs::EventStoreBackend<DocumentEvent>,
+{
+    let created = DocumentEvent::Created {
+        doc_id: "doc-1".to_string(),
+        title: "Draft".to_string(),
+    };
+    let tag = |tag: &str| DocumentEvent::TagAdded {
+        tag: tag.to_string(),
+    };
+    backend
+        .append("doc-1", vec![created, tag("a")])
+        .await
+        .unwrap();
+    backend.append("doc-2", vec![tag("b")]).await.unwrap();
+    backend.append("doc-3", vec![]).await.unwrap();
+    backend.append("doc-1", vec![tag("c")]).await.unwrap();
+
+    let mut trace = vec![
+        format!("{:?}", backend.get_events_after("doc-1", 1).await),
+        format!("{:?}", backend.get_events_after("doc-1", 10).await),
+        format!("{:?}", backend.get_events_after("missing", 0).await),
+        format!("{:?}", backend.get_events_page("doc-1", 1, 1).await),
+        format!("{:?}", backend.get_events("doc-3").await),
+        format!("{:?}", backend.get_events_since(2).await),
+        format!("{}", backend.get_latest_snapshot("doc-1").await.is_err()),
+    ];
+
+    backend
+        .save_snapshot("doc-1", b"v2".to_vec(), 2)
+        .await
+        .unwrap();
+    backend
+        .save_snapshot("doc-1", b"v3".to_vec(), 3)
+        .await
+        .unwrap();
+    backend
+        .save_snapshot("doc-2", b"v1".to_vec(), 1)
+        .await
+        .unwrap();
+    trace.push(format!("{:?}", backend.get_latest_snapshot("doc-1").await));
+    trace.push(format!("{:?}", backend.get_latest_snapshot("doc-2").await));
+
+    let stats = backend.stats().await;
+    trace.push(format!(
+        "{} events, {} aggregates, {} snapshots, backend_type: {}",
+        stats.total_events,
+        stats.total_aggregates,
+        stats.total_snapshots,
+        stats.backend_specific.contains_key("backend_type")
+    ));
+    trace
+}
+
+/// Test that the in-memory and SQLite backends are interchangeable,
+/// snapshots included
+#[tokio::test]
+#[cfg(feature = "cqrs-sqlite")]
+async fn test_in_memory_backend_matches_sqlite() {
+    use allframe_core::cqrs::{InMemoryBackend, SqliteEventStoreBackend};
+
+    let dir = tempfile::tempdir().unwrap();
+    let db_path = dir.path().join("events.db");
+    let sqlite = SqliteEventStoreBackend::<DocumentEvent>::new(db_path.to_str().unwrap())
+        .await
+        .unwrap();
+    let memory = InMemoryBackend::<DocumentEvent>::new();
+
+    let expected = backend_parity_trace(&sqlite).await;
+    assert_eq!(backend_parity_trace(&memory).await, expected);
+    assert_eq!(
+        expected.last().unwrap(),
+        "4 events, 2 aggregates, 2 snapshots, backend_type: true"
+    );
+}
+
 /// Test that SQLite backend enables WAL mode for concurrent access
 #[tokio::test]
 #[cfg(feature = "cqrs-sqlite")]